@@ -0,0 +1,195 @@
+//! The volume encryption keys are never stored by the control-plane, only the reference to them.
+//! The keys are resolved through their provider whenever a volume target is published, and are
+//! handed over to the io-engine which encrypts the volume data at rest.
+
+use crate::EncryptionKeyArgs;
+use agents::errors::SvcError;
+use base64::Engine;
+use std::{path::PathBuf, time::Duration};
+use stor_port::types::v0::transport::{EncryptionKeyProvider, EncryptionKeyRef};
+
+/// Timeout for the requests to the remote key providers.
+const KEY_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// The service account credentials of the pod.
+const SERVICE_ACCOUNT_DIR: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+
+/// A provider of volume encryption keys.
+#[async_trait::async_trait]
+trait KeyProvider: Send + Sync + std::fmt::Debug {
+    /// Get the key material of the referenced key.
+    async fn key(&self, key_ref: &EncryptionKeyRef) -> Result<String, String>;
+}
+
+/// Resolves the volume encryption keys through their providers.
+/// Providers which are not configured reject all keys.
+#[derive(Debug)]
+pub(crate) struct EncryptionKeys {
+    file: Option<FileKeyProvider>,
+    secret: Option<SecretKeyProvider>,
+    kms: Option<KmsKeyProvider>,
+}
+
+impl EncryptionKeys {
+    /// Create a new `Self` from the cli arguments.
+    /// The Secret provider is only available when running within a Kubernetes cluster.
+    pub(crate) fn new(args: &EncryptionKeyArgs) -> Result<Self, SvcError> {
+        Ok(Self {
+            file: args
+                .encryption_key_dir
+                .clone()
+                .map(|dir| FileKeyProvider { dir }),
+            secret: SecretKeyProvider::in_cluster()?,
+            kms: match &args.encryption_kms_endpoint {
+                Some(endpoint) => Some(KmsKeyProvider {
+                    client: http_client(reqwest::Client::builder())?,
+                    endpoint: endpoint.clone(),
+                }),
+                None => None,
+            },
+        })
+    }
+
+    /// Get the key material of the referenced key.
+    pub(crate) async fn resolve(&self, key_ref: &EncryptionKeyRef) -> Result<String, SvcError> {
+        let provider: Option<&dyn KeyProvider> = match key_ref.provider {
+            EncryptionKeyProvider::File => self.file.as_ref().map(|p| p as _),
+            EncryptionKeyProvider::Secret => self.secret.as_ref().map(|p| p as _),
+            EncryptionKeyProvider::Kms => self.kms.as_ref().map(|p| p as _),
+        };
+        let result = match provider {
+            Some(provider) => provider.key(key_ref).await,
+            None => Err(format!(
+                "the {} provider is not configured",
+                key_ref.provider
+            )),
+        };
+        match result.map(|key| key.trim().to_string()) {
+            Ok(key) if key.is_empty() => Err("the key is empty".to_string()),
+            result => result,
+        }
+        .map_err(|reason| SvcError::EncryptionKeyResolve {
+            key: key_ref.to_string(),
+            reason,
+        })
+    }
+}
+
+fn http_client(builder: reqwest::ClientBuilder) -> Result<reqwest::Client, SvcError> {
+    builder
+        .timeout(KEY_REQUEST_TIMEOUT)
+        .build()
+        .map_err(|error| SvcError::Internal {
+            details: format!("Failed to build the encryption key http client: {error}"),
+        })
+}
+
+/// Keys stored as files in a local directory, one key per file.
+#[derive(Debug)]
+struct FileKeyProvider {
+    dir: PathBuf,
+}
+
+#[async_trait::async_trait]
+impl KeyProvider for FileKeyProvider {
+    async fn key(&self, key_ref: &EncryptionKeyRef) -> Result<String, String> {
+        key_ref.validate()?;
+        let path = self.dir.join(&key_ref.name);
+        tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|error| format!("failed to read '{}': {error}", path.display()))
+    }
+}
+
+/// Keys stored in Kubernetes Secrets, read through the api-server with the pod's service account.
+#[derive(Debug)]
+struct SecretKeyProvider {
+    client: reqwest::Client,
+    api_server: String,
+}
+
+impl SecretKeyProvider {
+    fn in_cluster() -> Result<Option<Self>, SvcError> {
+        let (Ok(host), Ok(port)) = (
+            std::env::var("KUBERNETES_SERVICE_HOST"),
+            std::env::var("KUBERNETES_SERVICE_PORT"),
+        ) else {
+            return Ok(None);
+        };
+        let ca = std::fs::read(format!("{SERVICE_ACCOUNT_DIR}/ca.crt"))
+            .ok()
+            .and_then(|ca| reqwest::Certificate::from_pem(&ca).ok());
+        let Some(ca) = ca else {
+            tracing::warn!("Service account CA not found, disabling the Secret key provider");
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            client: http_client(reqwest::Client::builder().add_root_certificate(ca))?,
+            api_server: format!("https://{host}:{port}"),
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyProvider for SecretKeyProvider {
+    async fn key(&self, key_ref: &EncryptionKeyRef) -> Result<String, String> {
+        key_ref.validate()?;
+        let namespace = key_ref.namespace.as_deref().unwrap_or_default();
+        // the token is periodically rotated, so always read the current one
+        let token = tokio::fs::read_to_string(format!("{SERVICE_ACCOUNT_DIR}/token"))
+            .await
+            .map_err(|error| format!("failed to read the service account token: {error}"))?;
+        let url = format!(
+            "{}/api/v1/namespaces/{namespace}/secrets/{}",
+            self.api_server, key_ref.name
+        );
+        let secret: serde_json::Value =
+            json_get(self.client.get(url).bearer_auth(token.trim())).await?;
+
+        let Some(data) = secret["data"][key_ref.secret_key()].as_str() else {
+            return Err(format!(
+                "the Secret has no '{}' entry",
+                key_ref.secret_key()
+            ));
+        };
+        let key = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|error| format!("invalid Secret data: {error}"))?;
+        String::from_utf8(key).map_err(|error| format!("invalid Secret data: {error}"))
+    }
+}
+
+/// Keys served by a key management server.
+/// This is a minimal stand-in for a KMIP server: the key `name` is fetched from
+/// `$endpoint/v1/keys/$name` which returns `{ "key": "$key" }`.
+#[derive(Debug)]
+struct KmsKeyProvider {
+    client: reqwest::Client,
+    endpoint: url::Url,
+}
+
+#[async_trait::async_trait]
+impl KeyProvider for KmsKeyProvider {
+    async fn key(&self, key_ref: &EncryptionKeyRef) -> Result<String, String> {
+        key_ref.validate()?;
+        let url = self
+            .endpoint
+            .join(&format!("v1/keys/{}", key_ref.name))
+            .map_err(|error| format!("invalid key name: {error}"))?;
+        let key: serde_json::Value = json_get(self.client.get(url)).await?;
+
+        match key["key"].as_str() {
+            Some(key) => Ok(key.to_string()),
+            None => Err("the key server replied without a key".to_string()),
+        }
+    }
+}
+
+async fn json_get(request: reqwest::RequestBuilder) -> Result<serde_json::Value, String> {
+    let response = request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|error| error.to_string())?;
+    let body = response.bytes().await.map_err(|error| error.to_string())?;
+    serde_json::from_slice(&body).map_err(|error| format!("invalid response: {error}"))
+}
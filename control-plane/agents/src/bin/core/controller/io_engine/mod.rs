@@ -20,8 +20,8 @@ use stor_port::{
         GetBlockDevices, GetRebuildRecord, ImportPool, IoEngCreateSnapshotClone, ListRebuildRecord,
         ListReplicaSnapshots, ListSnapshotClones, Nexus, NexusChildAction, NexusChildActionContext,
         NexusChildActionKind, NexusId, PoolState, RebuildHistory, Register, RemoveNexusChild,
-        Replica, ReplicaId, ReplicaSnapshot, ResizeNexus, ResizeReplica, SetReplicaEntityId,
-        ShareNexus, ShareReplica, ShutdownNexus, UnshareNexus, UnshareReplica,
+        Replica, ReplicaId, ReplicaSnapshot, ResizeNexus, ResizeReplica, SetNexusQos,
+        SetReplicaEntityId, ShareNexus, ShareReplica, ShutdownNexus, UnshareNexus, UnshareReplica,
     },
 };
 
@@ -115,6 +115,8 @@ pub(crate) trait NexusApi<Sht> {
     async fn resize_nexus(&self, request: &ResizeNexus) -> Result<Nexus, SvcError>;
     /// Shutdown a nexus via gRPC.
     async fn shutdown_nexus(&self, request: &ShutdownNexus) -> Result<Sht, SvcError>;
+    /// Set the IO limits of a nexus via gRPC.
+    async fn set_nexus_qos(&self, request: &SetNexusQos) -> Result<(), SvcError>;
 }
 
 #[async_trait]
@@ -10,8 +10,8 @@ use stor_port::{
     transport_api::ResourceKind,
     types::v0::transport::{
         AddNexusChild, Child, CreateNexus, DestroyNexus, FaultNexusChild, Nexus, NexusChildAction,
-        NexusChildActionContext, NexusId, RemoveNexusChild, ResizeNexus, ShareNexus, ShutdownNexus,
-        UnshareNexus,
+        NexusChildActionContext, NexusId, RemoveNexusChild, ResizeNexus, SetNexusQos, ShareNexus,
+        ShutdownNexus, UnshareNexus,
     },
 };
 
@@ -104,6 +104,14 @@ impl crate::controller::io_engine::NexusApi<()> for super::RpcClient {
             })?;
         Ok(())
     }
+
+    async fn set_nexus_qos(&self, _request: &SetNexusQos) -> Result<(), SvcError> {
+        Err(SvcError::GrpcRequestError {
+            resource: ResourceKind::Nexus,
+            request: "set_nexus_qos".to_string(),
+            source: tonic::Status::unimplemented(""),
+        })
+    }
 }
 
 #[async_trait::async_trait]
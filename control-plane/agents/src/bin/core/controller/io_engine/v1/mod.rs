@@ -24,6 +24,8 @@ type SnapshotClient = rpc::v1::snapshot::snapshot_rpc_client::SnapshotRpcClient<
 /// The V1 SnapshotRebuildClient.
 type SnapshotRebuildClient =
     rpc::v1::snapshot_rebuild::snapshot_rebuild_rpc_client::SnapshotRebuildRpcClient<Channel>;
+/// The V1 JsonClient.
+type JsonClient = rpc::v1::json::JsonRpcClient<Channel>;
 
 /// A collection of all clients for the Io-Engine V1 services.
 #[derive(Clone, Debug)]
@@ -34,6 +36,7 @@ pub(crate) struct RpcClient {
     pool: PoolClient,
     snapshot: SnapshotClient,
     snapshot_rebuild: SnapshotRebuildClient,
+    json: JsonClient,
     context: GrpcContext,
 }
 
@@ -55,7 +58,8 @@ impl RpcClient {
             nexus: NexusClient::new(channel.clone()),
             pool: PoolClient::new(channel.clone()),
             snapshot: SnapshotClient::new(channel.clone()),
-            snapshot_rebuild: SnapshotRebuildClient::new(channel),
+            snapshot_rebuild: SnapshotRebuildClient::new(channel.clone()),
+            json: JsonClient::new(channel),
             context: context.clone(),
         })
     }
@@ -83,6 +87,10 @@ impl RpcClient {
     fn snap_rebuild(&self) -> SnapshotRebuildClient {
         self.snapshot_rebuild.clone()
     }
+    /// Get the v1 json client.
+    fn json(&self) -> JsonClient {
+        self.json.clone()
+    }
 
     async fn fetcher_client(&self) -> Result<Self, SvcError> {
         let mut context = self.context.clone();
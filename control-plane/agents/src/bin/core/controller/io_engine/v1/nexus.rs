@@ -13,7 +13,8 @@ use stor_port::{
     types::v0::transport::{
         AddNexusChild, CreateNexus, DestroyNexus, FaultNexusChild, GetRebuildRecord,
         ListRebuildRecord, Nexus, NexusChildAction, NexusChildActionContext, NexusId, NodeId,
-        RebuildHistory, RemoveNexusChild, ResizeNexus, ShareNexus, ShutdownNexus, UnshareNexus,
+        RebuildHistory, RemoveNexusChild, ResizeNexus, SetNexusQos, ShareNexus, ShutdownNexus,
+        UnshareNexus,
     },
 };
 
@@ -170,6 +171,23 @@ impl crate::controller::io_engine::NexusApi<()> for super::RpcClient {
             })?;
         Ok(())
     }
+
+    /// The io-engine has no dedicated gRPC for the nexus IO limits, and so they're set on the
+    /// nexus bdev via the json-rpc passthrough.
+    #[tracing::instrument(name = "rpc::v1::nexus::set_qos", level = "debug", skip(self), err)]
+    async fn set_nexus_qos(&self, request: &SetNexusQos) -> Result<(), SvcError> {
+        let json_request = request.to_rpc();
+        let _ = self
+            .json()
+            .json_rpc_call(json_request.clone())
+            .await
+            .map_err(|error| SvcError::JsonRpc {
+                method: json_request.method,
+                params: json_request.params,
+                error: error.to_string(),
+            })?;
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
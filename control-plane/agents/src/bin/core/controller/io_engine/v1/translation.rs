@@ -491,6 +491,24 @@ impl AgentToIoEngine for transport::ResizeNexus {
     }
 }
 
+impl AgentToIoEngine for transport::SetNexusQos {
+    type IoEngineMessage = v1::json::JsonRpcRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        // Limits which are not set must be cleared, which is done by setting them to 0.
+        let params = serde_json::json!({
+            "name": self.name,
+            "rw_ios_per_sec": self.qos.rw_iops.unwrap_or_default(),
+            "rw_mbytes_per_sec": self.qos.rw_mbps.unwrap_or_default(),
+            "r_mbytes_per_sec": self.qos.read_mbps.unwrap_or_default(),
+            "w_mbytes_per_sec": self.qos.write_mbps.unwrap_or_default(),
+        });
+        v1::json::JsonRpcRequest {
+            method: "bdev_set_qos_limit".to_string(),
+            params: params.to_string(),
+        }
+    }
+}
+
 impl AgentToIoEngine for transport::ShareNexus {
     type IoEngineMessage = v1::nexus::PublishNexusRequest;
     fn to_rpc(&self) -> Self::IoEngineMessage {
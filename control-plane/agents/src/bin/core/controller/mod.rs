@@ -1,5 +1,7 @@
 //! Common modules used by the different core services
 
+/// Resolution of the volume encryption keys.
+pub(crate) mod encryption;
 pub(crate) mod io_engine;
 /// Various policies' definitions(e.g. rebuild policy)
 pub(crate) mod policies;
@@ -473,7 +473,11 @@ pub(super) async fn missing_nexus_recreate(
             return PollResult::Ok(PollerState::Idle);
         }
 
-        match node.create_nexus(&CreateNexus::from(&nexus)).await {
+        let qos = context.specs().nexus_owner_qos(&nexus);
+        match node
+            .create_nexus(&CreateNexus::from(&nexus).with_qos(qos))
+            .await
+        {
             Ok(_) => {
                 nexus.info_span(|| tracing::info!("Nexus successfully recreated"));
                 PollResult::Ok(PollerState::Idle)
@@ -31,6 +31,8 @@ impl ReconcilerWorker {
         let poll_targets: Vec<Box<dyn TaskPoller>> = vec![
             Box::new(PersistentStoreReconciler::new()),
             Box::new(pool::PoolReconciler::new()),
+            Box::new(pool::PoolRebalanceReconciler::new()),
+            Box::new(pool::PoolEvacuationReconciler::new()),
             Box::new(nexus::NexusReconciler::new()),
             Box::new(volume::VolumeReconciler::new()),
             Box::new(replica::ReplicaReconciler::new()),
@@ -0,0 +1,152 @@
+use crate::{
+    controller::{
+        resources::{operations_helper::OperationSequenceGuard, OperationGuardArc, TraceSpan},
+        task_poller::{PollContext, PollPeriods, PollResult, PollTimer, PollerState, TaskPoller},
+    },
+    volume::MoveReplicaRequest,
+};
+use stor_port::types::v0::{
+    store::pool::{PoolEvacuationProgress, PoolSpec},
+    transport::{MoveVolumeReplica, ReplicaId, VolumeId},
+};
+
+/// Pool Evacuation reconciler, which moves the volume replicas off the pools being evacuated:
+/// 1. a replica move is started for each volume with a replica on the pool, unless the volume is
+///    already moving a replica or the maximum number of rebuilds has been reached
+/// 2. the volume replica move reconciler retires the moved replicas once the new replicas have
+///    been rebuilt, leaving them to be garbage collected
+/// 3. once no volume replicas remain on the pool, the pool is marked as evacuated
+/// The new replicas are rebuilt by the volume target, and so the replicas of unpublished volumes
+/// are only moved once their volumes are published. Until then they're recorded as blocked in the
+/// evacuation progress, and the evacuation cannot complete.
+#[derive(Debug)]
+pub(crate) struct PoolEvacuationReconciler {
+    counter: PollTimer,
+}
+impl PoolEvacuationReconciler {
+    /// Return new `Self` with the provided period.
+    pub(crate) fn from(period: PollPeriods) -> Self {
+        Self {
+            counter: PollTimer::from(period),
+        }
+    }
+    /// Return new `Self` with the default period.
+    pub(crate) fn new() -> Self {
+        Self::from(1)
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for PoolEvacuationReconciler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let mut results = vec![];
+        for pool in context.specs().pools_rsc() {
+            if !pool.lock().evacuating() {
+                continue;
+            }
+            let mut pool = match pool.operation_guard() {
+                Ok(guard) => guard,
+                Err(_) => {
+                    results.push(PollResult::Ok(PollerState::Busy));
+                    continue;
+                }
+            };
+            results.push(evacuate_pool(&mut pool, context).await);
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
+
+/// Record the evacuation progress of the pool and start moving its remaining volume replicas.
+async fn evacuate_pool(
+    pool: &mut OperationGuardArc<PoolSpec>,
+    context: &PollContext,
+) -> PollResult {
+    let registry = context.registry();
+    let pool_id = pool.as_ref().id.clone();
+    let replicas = context
+        .specs()
+        .replicas_cloned()
+        .into_iter()
+        .filter(|replica| replica.pool_name() == &pool_id)
+        .filter_map(|replica| {
+            let volume = replica.owners.volume().cloned()?;
+            Some((volume, replica.uuid))
+        })
+        .collect::<Vec<(VolumeId, ReplicaId)>>();
+
+    let blocked = replicas
+        .iter()
+        .filter(|(volume_id, _)| {
+            context
+                .specs()
+                .volume_rsc(volume_id)
+                .map_or(false, |volume| volume.lock().target().is_none())
+        })
+        .map(|(volume_id, _)| volume_id)
+        .collect::<Vec<_>>();
+    let progress = PoolEvacuationProgress {
+        remaining_replicas: replicas.len() as u64,
+        blocked_replicas: blocked.len() as u64,
+    };
+    let recorded = pool.as_ref().evacuation.as_ref().map(|e| e.progress());
+    if recorded != Some(progress) || progress.remaining_replicas == 0 {
+        pool.set_evacuation_progress(registry, progress).await?;
+        if progress.blocked_replicas > 0 {
+            pool.warn_span(|| {
+                tracing::warn!(
+                    volumes = ?blocked,
+                    "Cannot move the replicas of unpublished volumes off the evacuating pool until the volumes are published"
+                )
+            });
+        }
+    }
+    if progress.remaining_replicas == 0 {
+        pool.info_span(|| tracing::info!("Successfully evacuated pool"));
+        return PollResult::Ok(PollerState::Idle);
+    }
+
+    for (volume_id, replica_id) in replicas {
+        if registry.rebuild_allowed().await.is_err() {
+            break;
+        }
+        let Some(volume) = context.specs().volume_rsc(&volume_id) else {
+            continue;
+        };
+        let mut volume = match volume.operation_guard() {
+            Ok(guard) => guard,
+            Err(_) => continue,
+        };
+        if volume.as_ref().replica_move.is_some() || volume.as_ref().target().is_none() {
+            continue;
+        }
+
+        let request = MoveVolumeReplica::new(volume_id, replica_id.clone(), None, None);
+        match volume
+            .start_replica_move(registry, &MoveReplicaRequest::from(&request))
+            .await
+        {
+            Ok(new_replica) => volume.info_span(|| {
+                tracing::info!(
+                    replica.uuid = replica_id.as_str(),
+                    replica.new_uuid = new_replica.uuid.as_str(),
+                    pool.id = pool_id.as_str(),
+                    "Moving replica off the evacuating pool"
+                )
+            }),
+            Err(error) => volume.warn_span(|| {
+                tracing::warn!(
+                    replica.uuid = replica_id.as_str(),
+                    pool.id = pool_id.as_str(),
+                    %error,
+                    "Failed to move replica off the evacuating pool"
+                )
+            }),
+        }
+    }
+    PollResult::Ok(PollerState::Busy)
+}
@@ -1,4 +1,9 @@
 mod capacity;
+mod evacuate;
+mod rebalance;
+
+pub(crate) use evacuate::PoolEvacuationReconciler;
+pub(crate) use rebalance::PoolRebalanceReconciler;
 
 use crate::controller::{
     reconciler::{GarbageCollect, ReCreate},
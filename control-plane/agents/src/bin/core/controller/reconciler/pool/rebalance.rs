@@ -0,0 +1,111 @@
+use crate::{
+    controller::{
+        resources::{operations_helper::OperationSequenceGuard, TraceSpan},
+        scheduling::rebalance::replica_moves,
+        task_poller::{PollContext, PollPeriods, PollResult, PollTimer, PollerState, TaskPoller},
+    },
+    volume::MoveReplicaRequest,
+};
+use stor_port::types::v0::transport::PoolId;
+
+use std::collections::HashSet;
+
+/// Pool Rebalance reconciler, which is only enabled with the `pool_rebalance` argument.
+/// It evens out the utilisation of the pools by moving volume replicas from over-utilised pools
+/// to under-utilised pools:
+/// 1. a new replica is created on the under-utilised pool and added to the volume target
+/// 2. once the new replica has been rebuilt the volume replica move reconciler retires the moved
+///    replica, leaving it to be garbage collected
+/// New moves are not started while the maximum number of rebuilds has been reached, nor from or
+/// to pools which are already part of a move in progress.
+#[derive(Debug)]
+pub(crate) struct PoolRebalanceReconciler {
+    counter: PollTimer,
+}
+impl PoolRebalanceReconciler {
+    /// Return new `Self` with the provided period.
+    pub(crate) fn from(period: PollPeriods) -> Self {
+        Self {
+            counter: PollTimer::from(period),
+        }
+    }
+    /// Return new `Self` with the default period.
+    pub(crate) fn new() -> Self {
+        Self::from(1)
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for PoolRebalanceReconciler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let args = context.registry().pool_rebalance_args();
+        if !args.pool_rebalance {
+            return PollResult::Ok(PollerState::Idle);
+        }
+
+        start_move(args.pool_rebalance_threshold, context).await
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
+
+/// Start the first proposed replica move which does not involve pools with moves in progress.
+async fn start_move(threshold: u64, context: &PollContext) -> PollResult {
+    let registry = context.registry();
+    if registry.rebuild_allowed().await.is_err() {
+        return PollResult::Ok(PollerState::Busy);
+    }
+
+    let busy_pools = context
+        .specs()
+        .volumes()
+        .into_iter()
+        .filter_map(|volume| volume.replica_move)
+        .flat_map(|m| [m.replica, m.new_replica])
+        .filter_map(|replica| context.specs().replica_rsc(&replica))
+        .map(|replica| replica.lock().pool.pool_name().clone())
+        .collect::<HashSet<PoolId>>();
+    let Some(proposal) = replica_moves(registry, threshold)
+        .await
+        .into_iter()
+        .find(|m| !busy_pools.contains(&m.source_pool) && !busy_pools.contains(&m.target_pool))
+    else {
+        return PollResult::Ok(PollerState::Idle);
+    };
+
+    let Some(volume) = context.specs().volume_rsc(&proposal.volume) else {
+        return PollResult::Ok(PollerState::Idle);
+    };
+    let mut volume = match volume.operation_guard() {
+        Ok(guard) => guard,
+        Err(_) => return PollResult::Ok(PollerState::Busy),
+    };
+
+    let request = MoveReplicaRequest::from(&proposal);
+    match volume.start_replica_move(registry, &request).await {
+        Ok(_) => {
+            volume.info_span(|| {
+                tracing::info!(
+                    replica.uuid = proposal.replica.as_str(),
+                    replica.old_pool = proposal.source_pool.as_str(),
+                    replica.pool = proposal.target_pool.as_str(),
+                    "Rebalancing replica onto a less utilised pool"
+                )
+            });
+            PollResult::Ok(PollerState::Busy)
+        }
+        Err(error) => {
+            volume.error_span(|| {
+                tracing::error!(
+                    replica.uuid = proposal.replica.as_str(),
+                    replica.old_pool = proposal.source_pool.as_str(),
+                    %error,
+                    "Failed to rebalance replica"
+                )
+            });
+            Err(error)
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::controller::task_poller::{
+    PollContext, PollPeriods, PollResult, PollTimer, PollerState, TaskPoller,
+};
+use agents::errors::SvcError;
+use stor_port::types::v0::{
+    store::snapshots::export::{SnapshotExportSpec, SnapshotExportStatus},
+    transport::VolumeId,
+};
+
+/// Snapshot Export reconciler.
+/// Completes the snapshot exports whose creation or deletion was interrupted, by deleting them
+/// from their backup target along with their temporary transfer volumes.
+#[derive(Debug)]
+pub(super) struct SnapshotExportReconciler {
+    counter: PollTimer,
+}
+impl SnapshotExportReconciler {
+    /// Return new `Self` with the provided period.
+    pub(super) fn from(period: PollPeriods) -> Self {
+        Self {
+            counter: PollTimer::from(period),
+        }
+    }
+    /// Return new `Self` with the default period.
+    pub(super) fn new() -> Self {
+        Self::from(1)
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for SnapshotExportReconciler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let mut results = vec![];
+        for export in context.specs().snapshot_exports() {
+            if export.status == SnapshotExportStatus::Exported {
+                continue;
+            }
+            results.push(snapshot_export_reconciler(&export, context).await);
+        }
+        for volume_id in context.specs().snapshot_export_volumes() {
+            results.push(snapshot_export_volume_reconciler(&volume_id, context).await);
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
+
+/// Delete the snapshot export which is still exporting or deleting, unless its snapshot is busy,
+/// as the export's operation may then still be in progress.
+#[tracing::instrument(skip(export, context), level = "trace", fields(snapshot_export.id = %export.id, request.reconcile = true))]
+async fn snapshot_export_reconciler(
+    export: &SnapshotExportSpec,
+    context: &PollContext,
+) -> PollResult {
+    match context
+        .specs()
+        .reconcile_snapshot_export(context.registry(), export)
+        .await
+    {
+        Ok(()) => Ok(PollerState::Idle),
+        Err(SvcError::Conflict {}) => Ok(PollerState::Busy),
+        Err(error) => {
+            tracing::error!(
+                snapshot_export.id = %export.id,
+                %error,
+                "Failed to delete the interrupted snapshot export"
+            );
+            Err(error)
+        }
+    }
+}
+
+/// Destroy the temporary transfer volume which was left behind by a snapshot export or restore,
+/// unless the volume is busy.
+#[tracing::instrument(skip(context), level = "trace", fields(volume.uuid = %volume_id, request.reconcile = true))]
+async fn snapshot_export_volume_reconciler(
+    volume_id: &VolumeId,
+    context: &PollContext,
+) -> PollResult {
+    match context
+        .specs()
+        .reconcile_snapshot_export_volume(context.registry(), volume_id)
+        .await
+    {
+        Ok(()) => Ok(PollerState::Idle),
+        Err(SvcError::Conflict {}) => Ok(PollerState::Busy),
+        Err(error) => {
+            tracing::error!(
+                volume.uuid = %volume_id,
+                %error,
+                "Failed to destroy the snapshot export transfer volume"
+            );
+            Err(error)
+        }
+    }
+}
@@ -60,6 +60,7 @@ impl GarbageCollect for OperationGuardArc<VolumeSnapshot> {
             creating_orphaned_volume_snapshot_reconciler(self, context).await,
             prune_volume_snapshot_reconciler(self, context).await,
             delete_no_transaction_volume_snapshot_reconciler(self, context).await,
+            unused_internal_volume_snapshot_reconciler(self, context).await,
         ])
     }
 
@@ -204,3 +205,38 @@ async fn delete_no_transaction_volume_snapshot_reconciler(
 
     Ok(PollerState::Idle)
 }
+
+#[tracing::instrument(skip(snapshot, context), level = "trace", fields(snapshot.id = %snapshot.uuid(), request.reconcile = true))]
+async fn unused_internal_volume_snapshot_reconciler(
+    snapshot: &mut OperationGuardArc<VolumeSnapshot>,
+    context: &PollContext,
+) -> PollResult {
+    // Only internal snapshots which are no longer used by any volume clone are collected.
+    if !snapshot.as_ref().spec().internal()
+        || !snapshot.as_ref().status().created()
+        || snapshot.as_ref().num_restores() > 0
+    {
+        return Ok(PollerState::Idle);
+    }
+
+    match snapshot
+        .destroy_unused_clone_source(context.registry())
+        .await
+    {
+        Ok(_) => {
+            tracing::info!(
+                snapshot.uuid = %snapshot.uuid(),
+                "Unused internal VolumeSnapshot deleted successfully"
+            );
+            Ok(PollerState::Idle)
+        }
+        Err(error) => {
+            tracing::error!(
+                snapshot.uuid = %snapshot.uuid(),
+                %error,
+                "Failed to delete unused internal VolumeSnapshot"
+            );
+            Err(error)
+        }
+    }
+}
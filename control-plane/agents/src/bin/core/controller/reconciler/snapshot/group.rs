@@ -0,0 +1,65 @@
+use crate::controller::task_poller::{
+    PollContext, PollPeriods, PollResult, PollTimer, PollerState, TaskPoller,
+};
+use agents::errors::SvcError;
+use stor_port::types::v0::store::snapshots::group::{SnapshotGroupSpec, SnapshotGroupStatus};
+
+/// Snapshot Group reconciler.
+/// Completes the snapshot groups whose creation or deletion was interrupted, by deleting their
+/// member snapshots along with the group itself.
+#[derive(Debug)]
+pub(super) struct SnapshotGroupReconciler {
+    counter: PollTimer,
+}
+impl SnapshotGroupReconciler {
+    /// Return new `Self` with the provided period.
+    pub(super) fn from(period: PollPeriods) -> Self {
+        Self {
+            counter: PollTimer::from(period),
+        }
+    }
+    /// Return new `Self` with the default period.
+    pub(super) fn new() -> Self {
+        Self::from(1)
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for SnapshotGroupReconciler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let mut results = vec![];
+        for group in context.specs().snapshot_groups() {
+            if group.status == SnapshotGroupStatus::Created {
+                continue;
+            }
+            results.push(snapshot_group_reconciler(&group, context).await);
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
+
+/// Delete the snapshot group which is still creating or deleting, unless one of its volumes is
+/// busy, as the group's operation may then still be in progress.
+#[tracing::instrument(skip(group, context), level = "trace", fields(snapshot_group.id = %group.id, request.reconcile = true))]
+async fn snapshot_group_reconciler(group: &SnapshotGroupSpec, context: &PollContext) -> PollResult {
+    match context
+        .specs()
+        .reconcile_snapshot_group(context.registry(), group)
+        .await
+    {
+        Ok(()) => Ok(PollerState::Idle),
+        Err(SvcError::Conflict {}) => Ok(PollerState::Busy),
+        Err(error) => {
+            tracing::error!(
+                snapshot_group.id = %group.id,
+                %error,
+                "Failed to delete the interrupted snapshot group"
+            );
+            Err(error)
+        }
+    }
+}
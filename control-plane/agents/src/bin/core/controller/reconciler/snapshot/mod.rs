@@ -1,9 +1,15 @@
 use crate::controller::{
-    reconciler::snapshot::garbage_collector::GarbageCollector,
+    reconciler::snapshot::{
+        export::SnapshotExportReconciler, garbage_collector::GarbageCollector,
+        group::SnapshotGroupReconciler, schedule::SnapshotScheduler,
+    },
     task_poller::{PollContext, PollPeriods, PollResult, PollTimer, TaskPoller},
 };
 
+mod export;
 mod garbage_collector;
+mod group;
+mod schedule;
 
 /// VolumeSnapshot Reconciler.
 #[derive(Debug)]
@@ -16,7 +22,12 @@ impl VolumeSnapshotReconciler {
     pub(crate) fn from(period: PollPeriods) -> Self {
         VolumeSnapshotReconciler {
             counter: PollTimer::from(period),
-            poll_targets: vec![Box::new(GarbageCollector::new())],
+            poll_targets: vec![
+                Box::new(GarbageCollector::new()),
+                Box::new(SnapshotScheduler::new()),
+                Box::new(SnapshotGroupReconciler::new()),
+                Box::new(SnapshotExportReconciler::new()),
+            ],
         }
     }
     /// Return new `Self` with the default period.
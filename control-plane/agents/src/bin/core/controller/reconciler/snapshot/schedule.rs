@@ -0,0 +1,293 @@
+use crate::{
+    controller::{
+        resources::{
+            operations::ResourceSnapshotting, operations_helper::OperationSequenceGuard,
+            OperationGuardArc, ResourceMutex,
+        },
+        task_poller::{PollContext, PollPeriods, PollResult, PollTimer, PollerState, TaskPoller},
+    },
+    volume::{DestroyVolumeSnapshotRequest, VolumeFilesystem},
+};
+use agents::errors::SvcError;
+use stor_port::types::v0::{
+    store::{
+        snapshots::{
+            schedule::SnapshotScheduleSpec,
+            volume::{VolumeSnapshot, VolumeSnapshotUserSpec},
+        },
+        volume::VolumeSpec,
+    },
+    transport::{SnapshotId, SnapshotScheduleId, VolumeId},
+};
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Snapshot Schedule reconciler.
+/// Periodically snapshots the volumes targeted by the snapshot schedules and prunes the
+/// scheduled snapshots which are no longer retained.
+#[derive(Debug)]
+pub(super) struct SnapshotScheduler {
+    counter: PollTimer,
+}
+impl SnapshotScheduler {
+    /// Return new `Self` with the provided period.
+    pub(super) fn from(period: PollPeriods) -> Self {
+        Self {
+            counter: PollTimer::from(period),
+        }
+    }
+    /// Return new `Self` with the default period.
+    pub(super) fn new() -> Self {
+        Self::from(1)
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for SnapshotScheduler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let schedules = context.specs().snapshot_schedules();
+        if schedules.is_empty() {
+            return PollResult::Ok(PollerState::Idle);
+        }
+
+        let mut index = ScheduledSnapshot::index(context);
+        let volumes = context.specs().volumes_rsc();
+        let mut results = vec![];
+        for volume in &volumes {
+            let mut snapshots = index.remove(volume.uuid()).unwrap_or_default();
+            for schedule in &schedules {
+                if !schedule.target.matches(&volume.lock()) {
+                    continue;
+                }
+                results.push(
+                    snapshot_schedule_reconciler(schedule, volume, &mut snapshots, context).await,
+                );
+            }
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
+
+/// A snapshot taken by a schedule.
+/// The scheduled snapshots are indexed by volume once per reconcile pass, and the index is kept
+/// up to date with the snapshots created and pruned during the pass.
+struct ScheduledSnapshot {
+    snapshot: ResourceMutex<VolumeSnapshot>,
+    schedule: SnapshotScheduleId,
+    /// Creation time of the snapshot, or `None` while it is still being created.
+    timestamp: Option<DateTime<Utc>>,
+}
+
+impl ScheduledSnapshot {
+    /// Get all scheduled snapshots which are either created or being created, by volume.
+    fn index(context: &PollContext) -> HashMap<VolumeId, Vec<Self>> {
+        let mut index = HashMap::<VolumeId, Vec<Self>>::new();
+        for snapshot in context.specs().volume_snapshots_rsc() {
+            if let Some((volume_id, snapshot)) = Self::from_rsc(snapshot) {
+                index.entry(volume_id).or_default().push(snapshot);
+            }
+        }
+        index
+    }
+    fn from_rsc(snapshot_rsc: ResourceMutex<VolumeSnapshot>) -> Option<(VolumeId, Self)> {
+        let snapshot = snapshot_rsc.lock();
+        let schedule = snapshot.spec().schedule()?.clone();
+        let timestamp = match snapshot.metadata().timestamp() {
+            Some(timestamp) if snapshot.status().created() => Some(*timestamp),
+            _ if snapshot.status().creating() => None,
+            _ => return None,
+        };
+        let volume_id = snapshot.spec().source_id().clone();
+        drop(snapshot);
+        Some((
+            volume_id,
+            Self {
+                snapshot: snapshot_rsc,
+                schedule,
+                timestamp,
+            },
+        ))
+    }
+}
+
+/// Snapshot the volume once the schedule triggers since its last scheduled snapshot, and then
+/// prune the volume's scheduled snapshots which are no longer retained.
+#[tracing::instrument(skip(schedule, volume, snapshots, context), level = "trace", fields(snapshot_schedule.id = %schedule.id, volume.uuid = %volume.uuid(), request.reconcile = true))]
+async fn snapshot_schedule_reconciler(
+    schedule: &SnapshotScheduleSpec,
+    volume: &ResourceMutex<VolumeSpec>,
+    snapshots: &mut Vec<ScheduledSnapshot>,
+    context: &PollContext,
+) -> PollResult {
+    if !volume.lock().status.created() {
+        return Ok(PollerState::Idle);
+    }
+    let Ok(mut volume) = volume.operation_guard() else {
+        return Ok(PollerState::Busy);
+    };
+
+    let now = Utc::now();
+    if snapshot_due(schedule, snapshots, now) {
+        if let Err(error) =
+            create_scheduled_snapshot(schedule, &mut volume, snapshots, context).await
+        {
+            tracing::error!(
+                snapshot_schedule.id = %schedule.id,
+                volume.uuid = %volume.uuid(),
+                %error,
+                "Failed to run the snapshot schedule"
+            );
+            return Err(error);
+        }
+    }
+
+    let retained = scheduled_snapshots(snapshots, Some(&schedule.id));
+    for (position, (snapshot, timestamp)) in retained.into_iter().enumerate() {
+        let age = (now - timestamp).to_std().unwrap_or_default();
+        if schedule.retention.expired(position, age) {
+            destroy_scheduled_snapshot(&mut volume, snapshot, snapshots, context).await?;
+        }
+    }
+    Ok(PollerState::Idle)
+}
+
+/// Check if the volume, with the given scheduled snapshots, is due a new snapshot from the given
+/// schedule. A snapshot which is still being created is never due another one.
+fn snapshot_due(
+    schedule: &SnapshotScheduleSpec,
+    snapshots: &[ScheduledSnapshot],
+    now: DateTime<Utc>,
+) -> bool {
+    let mut last_snapshot = None;
+    for snapshot in snapshots.iter().filter(|s| s.schedule == schedule.id) {
+        match snapshot.timestamp {
+            Some(timestamp) => last_snapshot = last_snapshot.max(Some(timestamp)),
+            None => return false,
+        }
+    }
+    match last_snapshot {
+        None => true,
+        Some(timestamp) => schedule.trigger.due(timestamp, now),
+    }
+}
+
+/// Create a new snapshot of the volume, tagged with the given schedule.
+/// If the volume has reached its maximum number of snapshots, then the oldest scheduled snapshot
+/// is pruned to make room for the new one.
+async fn create_scheduled_snapshot(
+    schedule: &SnapshotScheduleSpec,
+    volume: &mut OperationGuardArc<VolumeSpec>,
+    snapshots: &mut Vec<ScheduledSnapshot>,
+    context: &PollContext,
+) -> Result<(), SvcError> {
+    if let Some(max_snapshots) = volume.as_ref().max_snapshots {
+        if volume.as_ref().metadata.num_snapshots() as u32 >= max_snapshots {
+            let oldest = scheduled_snapshots(snapshots, None).pop();
+            let Some((snapshot, _)) = oldest else {
+                return Err(SvcError::SnapshotMaxLimit {
+                    max_snapshots,
+                    volume_id: volume.uuid().to_string(),
+                });
+            };
+            destroy_scheduled_snapshot(volume, snapshot, snapshots, context).await?;
+        }
+    }
+
+    let filesystem = match schedule.quiesce {
+        true => VolumeFilesystem::published(context.registry(), volume.as_ref()),
+        false => None,
+    };
+    if let Some(filesystem) = &filesystem {
+        filesystem.freeze().await?;
+    }
+
+    let spec = VolumeSnapshotUserSpec::new_scheduled(
+        volume.uuid(),
+        SnapshotId::new(),
+        schedule.id.clone(),
+    );
+    let result = volume.create_snap(context.registry(), &spec).await;
+
+    // Always unfreeze the filesystem, even if the snapshot creation failed.
+    // A filesystem left frozen blocks the application's writes, so failing to unfreeze it fails
+    // the run in place of the snapshot result.
+    if let Some(filesystem) = &filesystem {
+        if let Err(error) = filesystem.unfreeze().await {
+            if let Err(error) = &result {
+                tracing::error!(
+                    snapshot_schedule.id = %schedule.id,
+                    volume.uuid = %volume.uuid(),
+                    %error,
+                    "Failed to create scheduled volume snapshot"
+                );
+            }
+            tracing::error!(
+                snapshot_schedule.id = %schedule.id,
+                volume.uuid = %volume.uuid(),
+                %error,
+                "Failed to unfreeze the volume filesystem, it remains frozen"
+            );
+            return Err(error);
+        }
+    }
+    result?;
+
+    if let Some((_, snapshot)) = context
+        .specs()
+        .volume_snapshot_rsc(spec.uuid())
+        .and_then(ScheduledSnapshot::from_rsc)
+    {
+        snapshots.push(snapshot);
+    }
+    tracing::info!(
+        snapshot_schedule.id = %schedule.id,
+        volume.uuid = %volume.uuid(),
+        snapshot.uuid = %spec.uuid(),
+        "Created scheduled volume snapshot"
+    );
+    Ok(())
+}
+
+/// Delete the given scheduled snapshot of the volume.
+async fn destroy_scheduled_snapshot(
+    volume: &mut OperationGuardArc<VolumeSpec>,
+    snapshot: ResourceMutex<VolumeSnapshot>,
+    snapshots: &mut Vec<ScheduledSnapshot>,
+    context: &PollContext,
+) -> Result<(), SvcError> {
+    let snapshot_id = snapshot.uuid().clone();
+    let request = DestroyVolumeSnapshotRequest::new(
+        snapshot,
+        Some(volume.uuid().clone()),
+        snapshot_id.clone(),
+    );
+    volume.destroy_snap(context.registry(), &request).await?;
+    snapshots.retain(|snapshot| snapshot.snapshot.uuid() != &snapshot_id);
+
+    tracing::info!(
+        volume.uuid = %volume.uuid(),
+        snapshot.uuid = %snapshot_id,
+        "Pruned scheduled volume snapshot"
+    );
+    Ok(())
+}
+
+/// Get the created snapshots which were taken by the given schedule, or by any schedule,
+/// sorted from newest to oldest.
+fn scheduled_snapshots(
+    snapshots: &[ScheduledSnapshot],
+    schedule: Option<&SnapshotScheduleId>,
+) -> Vec<(ResourceMutex<VolumeSnapshot>, DateTime<Utc>)> {
+    let mut snapshots = snapshots
+        .iter()
+        .filter(|snapshot| schedule.map_or(true, |schedule| schedule == &snapshot.schedule))
+        .filter_map(|snapshot| Some((snapshot.snapshot.clone(), snapshot.timestamp?)))
+        .collect::<Vec<_>>();
+    snapshots.sort_by(|(_, a), (_, b)| b.cmp(a));
+    snapshots
+}
@@ -174,7 +174,11 @@ async fn nexus_replica_count_reconciler(
                 counter
             });
 
+    // The moved replica is only retired once its replacement has been rebuilt.
+    let moving = vol_spec_clone.replica_move.is_some();
+
     match nexus_replica_children.cmp(&volume_replicas) {
+        Ordering::Greater if moving => PollResult::Ok(PollerState::Idle),
         Ordering::Less | Ordering::Greater => {
             nexus_replica_count_reconciler_traced(
                 volume,
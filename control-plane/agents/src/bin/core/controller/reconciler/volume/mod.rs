@@ -1,12 +1,16 @@
 mod garbage_collector;
 mod hot_spare;
 mod nexus;
+mod rebuild_history;
+mod replica_move;
+mod snapshot_restore;
 
 use crate::controller::task_poller::{PollContext, PollPeriods, PollResult, PollTimer, TaskPoller};
 
 use crate::controller::reconciler::volume::{
     garbage_collector::GarbageCollector, hot_spare::HotSpareReconciler,
-    nexus::VolumeNexusReconciler,
+    nexus::VolumeNexusReconciler, rebuild_history::RebuildHistoryRecorder,
+    replica_move::ReplicaMoveReconciler, snapshot_restore::SnapshotRestoreReconciler,
 };
 
 /// Volume Reconciler loop which:
@@ -26,6 +30,9 @@ impl VolumeReconciler {
                 Box::new(HotSpareReconciler::new()),
                 Box::new(GarbageCollector::new()),
                 Box::new(VolumeNexusReconciler::new()),
+                Box::new(RebuildHistoryRecorder::new()),
+                Box::new(ReplicaMoveReconciler::new()),
+                Box::new(SnapshotRestoreReconciler::new()),
             ],
         }
     }
@@ -0,0 +1,83 @@
+use crate::controller::task_poller::{
+    PollContext, PollPeriods, PollResult, PollTimer, PollerState, TaskPoller,
+};
+use stor_port::types::v0::{store::rebuild_history::VolumeRebuildRecord, transport::VolumeId};
+
+use std::collections::HashMap;
+
+/// Rebuild History reconciler.
+/// Records the rebuild history fetched from the io-engines into the persistent store, keyed by
+/// volume, so it outlives the io-engine restarts and the volume target switchovers.
+/// The histories of deleted volumes are removed.
+#[derive(Debug)]
+pub(super) struct RebuildHistoryRecorder {
+    counter: PollTimer,
+}
+impl RebuildHistoryRecorder {
+    /// Return new `Self` with the provided period.
+    pub(super) fn from(period: PollPeriods) -> Self {
+        Self {
+            counter: PollTimer::from(period),
+        }
+    }
+    /// Return new `Self` with the default period.
+    pub(super) fn new() -> Self {
+        Self::from(1)
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for RebuildHistoryRecorder {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let specs = context.specs();
+
+        let mut records = HashMap::<VolumeId, Vec<VolumeRebuildRecord>>::new();
+        for node in context.registry().node_wrappers().await {
+            let node = node.read().await;
+            for history in node.rebuild_histories() {
+                let Some(volume_id) = specs.rebuild_history_volume(&history) else {
+                    continue;
+                };
+                let volume_records = records.entry(volume_id).or_default();
+                volume_records.extend(history.records.into_iter().map(|record| {
+                    VolumeRebuildRecord {
+                        nexus: history.uuid.clone(),
+                        node: node.id().clone(),
+                        record,
+                    }
+                }));
+            }
+        }
+
+        let mut results = vec![];
+        // Existing histories are always revisited, as their records may have expired.
+        for history in specs.rebuild_histories() {
+            if specs.volume_rsc(&history.volume_id).is_none() {
+                let result = specs
+                    .delete_rebuild_history(context.registry(), &history.volume_id)
+                    .await;
+                results.push(result.map(|_| PollerState::Idle));
+            } else {
+                records.entry(history.volume_id).or_default();
+            }
+        }
+        for (volume_id, records) in records {
+            let result = specs
+                .record_rebuild_history(context.registry(), &volume_id, records)
+                .await;
+            if let Err(error) = &result {
+                tracing::error!(
+                    volume.uuid = %volume_id,
+                    %error,
+                    "Failed to record the volume rebuild history"
+                );
+            }
+            results.push(result.map(|_| PollerState::Idle));
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
@@ -0,0 +1,95 @@
+use crate::controller::{
+    reconciler::{PollContext, TaskPoller},
+    resources::{
+        operations_helper::OperationSequenceGuard, OperationGuardArc, ResourceMutex, TraceSpan,
+    },
+    task_poller::{PollResult, PollerState},
+};
+use stor_port::types::v0::store::volume::VolumeSpec;
+
+/// Volume replica move reconciler.
+/// Completes the replica moves, started by the user or by the pool rebalancer, by retiring the
+/// moved replica once the new replica has been rebuilt.
+/// A move is abandoned if the volume target is gone or if the new replica is no longer part of
+/// it, eg: because it faulted whilst rebuilding, in which case the hot spare reconciler fixes up
+/// the replica count.
+#[derive(Debug)]
+pub(super) struct ReplicaMoveReconciler {}
+impl ReplicaMoveReconciler {
+    /// Return a new `Self`
+    pub(super) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for ReplicaMoveReconciler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let mut results = vec![];
+        for volume in context.specs().volumes_rsc() {
+            if volume.lock().replica_move.is_none() {
+                continue;
+            }
+            results.push(replica_move_reconcile(&volume, context).await);
+        }
+        Self::squash_results(results)
+    }
+}
+
+#[tracing::instrument(level = "debug", skip(context, volume_spec), fields(volume.uuid = %volume_spec.uuid(), request.reconcile = true))]
+async fn replica_move_reconcile(
+    volume_spec: &ResourceMutex<VolumeSpec>,
+    context: &PollContext,
+) -> PollResult {
+    let mut volume = match volume_spec.operation_guard() {
+        Ok(guard) => guard,
+        Err(_) => return PollResult::Ok(PollerState::Busy),
+    };
+    let Some(replica_move) = volume.as_ref().replica_move.clone() else {
+        return PollResult::Ok(PollerState::Idle);
+    };
+
+    let Some(nexus) = context.specs().volume_target_nexus_rsc(volume.as_ref()) else {
+        return abandon_move(&mut volume, "the volume target is gone", context).await;
+    };
+    let nexus = nexus.lock().clone();
+    let Some(new_child) = nexus.replica_uuid_uri(&replica_move.new_replica).cloned() else {
+        return abandon_move(
+            &mut volume,
+            "the new replica is no longer part of the volume target",
+            context,
+        )
+        .await;
+    };
+
+    let nexus_state = context.registry().nexus(&nexus.uuid).await?;
+    let rebuilt = nexus_state
+        .children
+        .iter()
+        .find(|c| &c.uri == new_child.uri())
+        .map_or(false, |c| c.state.online() && c.rebuild_progress.is_none());
+    if !rebuilt {
+        return PollResult::Ok(PollerState::Busy);
+    }
+
+    volume.complete_replica_move(context.registry()).await?;
+    volume.info_span(|| {
+        tracing::info!(
+            replica.uuid = replica_move.replica.as_str(),
+            replica.new_uuid = replica_move.new_replica.as_str(),
+            "Successfully moved replica"
+        )
+    });
+    PollResult::Ok(PollerState::Idle)
+}
+
+/// Abandon the replica move, leaving the hot spare reconciler to fix up the replica count.
+async fn abandon_move(
+    volume: &mut OperationGuardArc<VolumeSpec>,
+    reason: &str,
+    context: &PollContext,
+) -> PollResult {
+    volume.end_replica_move(context.registry()).await?;
+    volume.warn_span(|| tracing::warn!(reason, "Abandoned the replica move"));
+    PollResult::Ok(PollerState::Idle)
+}
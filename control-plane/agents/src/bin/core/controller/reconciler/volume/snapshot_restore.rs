@@ -0,0 +1,49 @@
+use crate::controller::{
+    reconciler::{PollContext, TaskPoller},
+    resources::{operations_helper::OperationSequenceGuard, ResourceMutex},
+    task_poller::{PollResult, PollerState},
+};
+use stor_port::types::v0::store::volume::VolumeSpec;
+
+/// Volume snapshot restore reconciler.
+/// Resumes the snapshot restores which were interrupted, eg: by a restart of the core agent,
+/// using the restore recorded in the volume spec operation.
+#[derive(Debug)]
+pub(super) struct SnapshotRestoreReconciler {}
+impl SnapshotRestoreReconciler {
+    /// Return a new `Self`
+    pub(super) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for SnapshotRestoreReconciler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let mut results = vec![];
+        for volume in context.specs().volumes_rsc() {
+            if volume.lock().snapshot_restore().is_none() {
+                continue;
+            }
+            results.push(snapshot_restore_reconcile(&volume, context).await);
+        }
+        Self::squash_results(results)
+    }
+}
+
+#[tracing::instrument(level = "debug", skip(context, volume_spec), fields(volume.uuid = %volume_spec.uuid(), request.reconcile = true))]
+async fn snapshot_restore_reconcile(
+    volume_spec: &ResourceMutex<VolumeSpec>,
+    context: &PollContext,
+) -> PollResult {
+    // a restore which is still in progress holds the volume guard
+    let mut volume = match volume_spec.operation_guard() {
+        Ok(guard) => guard,
+        Err(_) => return PollResult::Ok(PollerState::Busy),
+    };
+    if volume.resume_snapshot_restore(context.registry()).await {
+        PollResult::Ok(PollerState::Idle)
+    } else {
+        PollResult::Ok(PollerState::Busy)
+    }
+}
@@ -16,11 +16,12 @@
 use super::{resources::operations_helper::*, wrapper::NodeWrapper};
 use crate::{
     controller::{
+        encryption::EncryptionKeys,
         reconciler::ReconcilerControl,
         task_poller::{PollEvent, PollTriggerEvent},
         wrapper::InternalOps,
     },
-    ThinArgs,
+    EncryptionKeyArgs, PoolRebalanceArgs, ThinArgs,
 };
 use agents::errors::SvcError;
 use std::{
@@ -31,16 +32,19 @@ use std::{
 };
 use stor_port::{
     pstor::{
-        detect_product_v1_prefix, etcd::Etcd, Error as StoreError, StorableObject, Store, StoreKey,
-        StoreKv, StoreObj,
+        backend::StoreBackend, detect_product_v1_prefix, Error as StoreError, StorableObject,
+        Store, StoreKey, StoreKv, StoreObj, StoreTxn, StoreTxnRequest,
     },
     types::v0::{
         store::{
             nexus_persistence::delete_all_v1_nexus_info,
+            rebuild_history::RebuildHistoryRetention,
             registry::{ControlPlaneService, CoreRegistryConfig, NodeRegistration},
             volume::InitiatorAC,
         },
-        transport::{DeregisterAppNode, HostNqn, NodeId, RegisterAppNode},
+        transport::{
+            AppNodeId, DeregisterAppNode, HostNqn, NodeId, RegisterAppNode, VolumeShareProtocol,
+        },
     },
     HostAccessControl,
 };
@@ -50,14 +54,14 @@ use tracing::warn;
 /// Registry containing all io-engine instances (aka nodes).
 #[derive(Clone, Debug)]
 pub(crate) struct Registry {
-    inner: Arc<RegistryInner<Etcd>>,
+    inner: Arc<RegistryInner<StoreBackend>>,
 }
 
 /// Map that stores the actual state of the nodes.
 pub(crate) type NodesMapLocked = Arc<RwLock<HashMap<NodeId, Arc<RwLock<NodeWrapper>>>>>;
 
 impl Deref for Registry {
-    type Target = Arc<RegistryInner<Etcd>>;
+    type Target = Arc<RegistryInner<StoreBackend>>;
 
     fn deref(&self) -> &Self::Target {
         &self.inner
@@ -100,6 +104,12 @@ pub(crate) struct RegistryInner<S: Store> {
     legacy_prefix_present: bool,
     /// Thin provisioning parameters.
     thin_args: ThinArgs,
+    /// Retention of the persisted rebuild history of the volumes.
+    rebuild_history_retention: RebuildHistoryRetention,
+    /// Pool rebalance parameters.
+    pool_rebalance_args: PoolRebalanceArgs,
+    /// Resolver of the volume encryption keys.
+    encryption_keys: EncryptionKeys,
     /// Check if the HA feature is enabled.
     ha_disabled: bool,
     /// Etcd max page size.
@@ -125,13 +135,16 @@ impl Registry {
         create_volume_limit: usize,
         host_acl: Vec<HostAccessControl>,
         thin_args: ThinArgs,
+        rebuild_history_retention: RebuildHistoryRetention,
+        pool_rebalance_args: PoolRebalanceArgs,
+        encryption_key_args: &EncryptionKeyArgs,
         ha_enabled: bool,
         etcd_max_page_size: i64,
     ) -> Result<Self, SvcError> {
         let store_endpoint = Self::format_store_endpoint(&store_url);
         tracing::info!("Connecting to persistent store at {}", store_endpoint);
-        let mut store = Etcd::new_leased(
-            [&store_endpoint],
+        let mut store = StoreBackend::new_leased(
+            &store_endpoint,
             ControlPlaneService::CoreAgent.to_string(),
             store_lease_tll,
         )
@@ -177,6 +190,9 @@ impl Registry {
                 host_acl,
                 legacy_prefix_present,
                 thin_args,
+                rebuild_history_retention,
+                pool_rebalance_args,
+                encryption_keys: EncryptionKeys::new(encryption_key_args)?,
                 ha_disabled: ha_enabled,
                 etcd_max_page_size,
             }),
@@ -216,6 +232,7 @@ impl Registry {
     }
 
     /// Formats the store endpoint with a default port if one isn't supplied.
+    /// Non-etcd endpoints such as `mem://` always contain a scheme so they're left untouched.
     fn format_store_endpoint(endpoint: &str) -> String {
         match endpoint.contains(':') {
             true => endpoint.to_string(),
@@ -266,6 +283,21 @@ impl Registry {
         &self.thin_args
     }
 
+    /// Get the retention of the persisted rebuild history of the volumes.
+    pub(crate) fn rebuild_history_retention(&self) -> &RebuildHistoryRetention {
+        &self.rebuild_history_retention
+    }
+
+    /// Get the pool rebalance configuration parameters.
+    pub(crate) fn pool_rebalance_args(&self) -> &PoolRebalanceArgs {
+        &self.pool_rebalance_args
+    }
+
+    /// Get the resolver of the volume encryption keys.
+    pub(crate) fn encryption_keys(&self) -> &EncryptionKeys {
+        &self.encryption_keys
+    }
+
     /// Get the `CoreRegistryConfig`.
     pub(crate) fn config(&self) -> parking_lot::RwLockReadGuard<CoreRegistryConfig> {
         self.config.read()
@@ -323,6 +355,25 @@ impl Registry {
         }
     }
 
+    /// Serialized transaction to the persistent store.
+    /// All of the transaction operations are committed atomically, or none at all.
+    pub(crate) async fn store_txn(&self, txn: StoreTxnRequest) -> Result<(), SvcError> {
+        let store = self.store.clone();
+        match tokio::time::timeout(self.store_timeout, async move {
+            let mut store = store.lock().await;
+            Self::op_with_threshold(async move { store.txn(txn).await }).await
+        })
+        .await
+        {
+            Ok(result) => result.map_err(Into::into),
+            Err(_) => Err(StoreError::Timeout {
+                operation: "Txn".to_string(),
+                timeout: self.store_timeout,
+            }
+            .into()),
+        }
+    }
+
     /// Serialized read from the persistent store.
     pub(crate) async fn load_obj<O: StorableObject>(&self, key: &O::Key) -> Result<O, SvcError> {
         let store = self.store.clone();
@@ -380,8 +431,13 @@ impl Registry {
         result
     }
 
+    /// Get the page size used when loading entries from the persistent store.
+    pub(crate) fn etcd_max_page_size(&self) -> i64 {
+        self.etcd_max_page_size
+    }
+
     /// Get a reference to the persistent store
-    pub(crate) fn store(&self) -> &Arc<Mutex<Etcd>> {
+    pub(crate) fn store(&self) -> &Arc<Mutex<StoreBackend>> {
         &self.store
     }
 
@@ -502,20 +558,58 @@ impl Registry {
         })
     }
 
-    /// Get the allowed nqn's for the given request, if enabled.
+    /// Get the allowed initiators for the given request and share protocol, if enabled.
     pub(crate) fn host_acl_nodename(
         &self,
         req: HostAccessControl,
+        share: Option<VolumeShareProtocol>,
         nodes: &[String],
-    ) -> Vec<InitiatorAC> {
-        match self.host_acl.contains(&req) {
-            true => nodes
-                .iter()
-                .map(|nodename| {
-                    InitiatorAC::new(nodename.clone(), HostNqn::from_nodename(nodename))
-                })
-                .collect(),
-            false => vec![],
+    ) -> Result<Vec<InitiatorAC>, SvcError> {
+        match self.host_acl_enabled(req) {
+            true => self.initiators_nodename(share, nodes),
+            false => Ok(vec![]),
+        }
+    }
+
+    /// Check if the host access control is enabled for the given request.
+    pub(crate) fn host_acl_enabled(&self, req: HostAccessControl) -> bool {
+        self.host_acl.contains(&req)
+    }
+
+    /// Get the initiators for the given nodes, regardless of the host access control.
+    /// NVMe initiators are identified by their nodename based nqn, whereas iSCSI initiators are
+    /// identified by the iqn which their app node registered with.
+    pub(crate) fn initiators_nodename(
+        &self,
+        share: Option<VolumeShareProtocol>,
+        nodes: &[String],
+    ) -> Result<Vec<InitiatorAC>, SvcError> {
+        nodes
+            .iter()
+            .map(|nodename| {
+                let node_nqn = match share {
+                    Some(VolumeShareProtocol::Iscsi) => self.app_node_iqn(nodename)?,
+                    _ => HostNqn::from_nodename(nodename),
+                };
+                Ok(InitiatorAC::new(nodename.clone(), node_nqn))
+            })
+            .collect()
+    }
+
+    /// Get the iSCSI initiator name which the given app node registered with.
+    fn app_node_iqn(&self, nodename: &str) -> Result<HostNqn, SvcError> {
+        let invalid = || SvcError::InvalidIscsiInitiator {
+            node: nodename.to_string(),
+        };
+        let spec = self.specs().app_node_spec(&AppNodeId::from(nodename));
+        let iqn = spec
+            .as_ref()
+            .and_then(|spec| spec.labels.as_ref())
+            .and_then(|labels| labels.get(utils::CSI_NODE_ISCSI_IQN))
+            .ok_or_else(invalid)?;
+        match HostNqn::try_from(iqn) {
+            Ok(iqn) if iqn.is_iqn() => Ok(iqn),
+            _ => Err(invalid()),
         }
     }
 
@@ -29,11 +29,7 @@ pub(crate) async fn migrate_product_v1_to_v2<S: Store>(
             .last()
             .ok_or_else(|| StoreError::InvalidKey { key: k.to_string() })?;
 
-        let new_value = match spec_type {
-            StorableObjectType::VolumeSpec => migrate_volume_labels(v)?,
-            StorableObjectType::PoolSpec => migrate_pool_labels(v)?,
-            _ => v,
-        };
+        let new_value = migrate_product_v1_value(spec_type, v)?;
 
         store
             .put_kv(
@@ -49,6 +45,19 @@ pub(crate) async fn migrate_product_v1_to_v2<S: Store>(
     Ok(())
 }
 
+/// Migrate a value of the given type which may have been written by product v1 to latest.
+/// Values which are already up to date are returned as they are.
+pub(crate) fn migrate_product_v1_value(
+    spec_type: StorableObjectType,
+    value: Value,
+) -> Result<Value, StoreError> {
+    match spec_type {
+        StorableObjectType::VolumeSpec => migrate_volume_labels(value),
+        StorableObjectType::PoolSpec => migrate_pool_labels(value),
+        _ => Ok(value),
+    }
+}
+
 /// Migrate the labels put by product v1 csi-controller to latest.
 fn migrate_volume_labels(mut value: Value) -> Result<Value, StoreError> {
     let mut spec: VolumeSpec =
@@ -9,7 +9,7 @@ use stor_port::types::v0::store::{
 };
 
 mod app_node;
-mod migration;
+pub(crate) mod migration;
 mod nexus;
 mod node;
 mod pool;
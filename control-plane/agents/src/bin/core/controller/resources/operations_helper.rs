@@ -7,7 +7,7 @@ use crate::controller::{
 };
 use agents::errors::SvcError;
 use stor_port::{
-    pstor::{product_v1_key_prefix, API_VERSION},
+    pstor::{product_v1_key_prefix, StoreTxnRequest, API_VERSION},
     transport_api::{ErrorChain, ResourceKind},
     types::v0::{
         openapi::apis::Uuid,
@@ -19,12 +19,19 @@ use stor_port::{
             nexus::NexusSpec,
             node::NodeSpec,
             pool::PoolSpec,
+            rebuild_history::VolumeRebuildHistory,
             replica::ReplicaSpec,
-            snapshots::volume::VolumeSnapshot,
-            volume::{AffinityGroupSpec, VolumeContentSource, VolumeSpec},
+            snapshots::{
+                export::SnapshotExportSpec, group::SnapshotGroupSpec,
+                schedule::SnapshotScheduleSpec, volume::VolumeSnapshot,
+            },
+            volume::{AffinityGroupSpec, VolumeSpec},
             AsOperationSequencer, OperationMode, OperationSequence, SpecStatus, SpecTransaction,
         },
-        transport::{AppNodeId, NexusId, NodeId, PoolId, ReplicaId, SnapshotId, VolumeId},
+        transport::{
+            AppNodeId, NexusId, NodeId, PoolId, ReplicaId, SnapshotExportId, SnapshotGroupId,
+            SnapshotId, SnapshotScheduleId, VolumeId,
+        },
     },
 };
 
@@ -32,7 +39,7 @@ use async_trait::async_trait;
 use parking_lot::RwLock;
 use serde::de::DeserializeOwned;
 use snafu::{ResultExt, Snafu};
-use std::{fmt::Debug, ops::Deref, sync::Arc};
+use std::{collections::BTreeMap, fmt::Debug, ops::Deref, sync::Arc};
 
 #[derive(Debug, Snafu)]
 #[snafu(context(suffix(false)))]
@@ -160,6 +167,26 @@ pub(crate) trait GuardedOperationsHelper:
         registry: &Registry,
         on_fail: OnCreateFail,
     ) -> Result<R, SvcError>
+    where
+        Self::Inner: SpecTransaction<O>,
+    {
+        self.complete_create_txn(result, registry, on_fail, &mut LinkedTxn::new())
+            .await
+    }
+
+    /// Completes a create operation by trying to update the spec in the persistent store,
+    /// atomically with the `linked` spec updates of the resources which were created or updated
+    /// as part of it.
+    /// If the operation failed, the `linked` transaction is not committed.
+    /// If the persistent store operation fails then the spec is marked accordingly and the dirty
+    /// spec reconciler will attempt to update the store when the store is back online.
+    async fn complete_create_txn<O, R: Send + Debug>(
+        &mut self,
+        result: Result<R, SvcError>,
+        registry: &Registry,
+        on_fail: OnCreateFail,
+        linked: &mut LinkedTxn,
+    ) -> Result<R, SvcError>
     where
         Self::Inner: SpecTransaction<O>,
     {
@@ -170,7 +197,7 @@ pub(crate) trait GuardedOperationsHelper:
                 let mut spec_clone = self.lock().clone();
                 spec_clone.commit_op();
 
-                let stored = registry.store_obj(&spec_clone).await;
+                let stored = linked.commit(registry, Some(&spec_clone)).await;
                 match stored {
                     Ok(_) => {
                         self.complete_op();
@@ -448,10 +475,31 @@ pub(crate) trait GuardedOperationsHelper:
     /// If the persistent store operation fails then the spec is marked accordingly and the dirty
     /// spec reconciler will attempt to update the store when the store is back online.
     async fn complete_update<R: Send + Debug, O>(
+        &mut self,
+        registry: &Registry,
+        result: Result<R, SvcError>,
+        spec_clone: Self::Inner,
+    ) -> Result<R, SvcError>
+    where
+        Self::Inner: SpecTransaction<O>,
+        Self::Inner: StorableObject,
+    {
+        self.complete_update_txn(registry, result, spec_clone, &mut LinkedTxn::new())
+            .await
+    }
+
+    /// Completes an update operation by trying to update the spec in the persistent store,
+    /// atomically with the `linked` spec updates of the resources which were created, updated or
+    /// destroyed as part of it.
+    /// If the operation failed, the `linked` transaction is not committed.
+    /// If the persistent store operation fails then the spec is marked accordingly and the dirty
+    /// spec reconciler will attempt to update the store when the store is back online.
+    async fn complete_update_txn<R: Send + Debug, O>(
         &mut self,
         registry: &Registry,
         result: Result<R, SvcError>,
         mut spec_clone: Self::Inner,
+        linked: &mut LinkedTxn,
     ) -> Result<R, SvcError>
     where
         Self::Inner: SpecTransaction<O>,
@@ -462,12 +510,14 @@ pub(crate) trait GuardedOperationsHelper:
             Ok(val) => {
                 tracing::info!(?val, "complete_update");
 
-                if !store_obj {
+                if !store_obj && linked.is_empty() {
                     self.complete_op();
                     return Ok(val);
                 }
                 spec_clone.commit_op();
-                let stored = registry.store_obj(&spec_clone).await;
+                let stored = linked
+                    .commit(registry, store_obj.then_some(&spec_clone))
+                    .await;
                 match stored {
                     Ok(_) => {
                         self.complete_op();
@@ -501,6 +551,142 @@ pub(crate) trait GuardedOperationsHelper:
         }
     }
 
+    /// Completes a create operation which is linked to the operation of another resource: rather
+    /// than being stored on its own, the created spec is added to the `linked` transaction to be
+    /// committed atomically with the other resource's spec.
+    /// Once the transaction has been attempted, the operation must be completed with
+    /// `complete_linked_op`, until then the resource remains busy.
+    async fn complete_create_linked<O, R: Send + Debug>(
+        &mut self,
+        result: Result<R, SvcError>,
+        registry: &Registry,
+        on_fail: OnCreateFail,
+        linked: &mut LinkedTxn,
+    ) -> Result<R, SvcError>
+    where
+        Self::Inner: SpecTransaction<O>,
+    {
+        match result {
+            Ok(val) => {
+                tracing::info!(?val, "complete_create_linked");
+                self.link_op::<O>(linked)?;
+                Ok(val)
+            }
+            Err(error) => Err(self.handle_create_failed(registry, error, on_fail).await),
+        }
+    }
+
+    /// Start an update operation which is linked to the operation of another resource, and so,
+    /// rather than being logged, it is added to the `linked` transaction to be committed
+    /// atomically with the other resource's spec.
+    /// This is only suitable for configuration-only changes, since the update is not logged.
+    /// Once the transaction has been attempted, the operation must be completed with
+    /// `complete_linked_op`, until then the resource remains busy.
+    async fn start_linked_update(
+        &self,
+        registry: &Registry,
+        state: &Self::State,
+        update_operation: Self::UpdateOp,
+        linked: &mut LinkedTxn,
+    ) -> Result<(), SvcError>
+    where
+        Self::Inner: PartialEq<Self::State>,
+        Self::Inner: SpecTransaction<Self::UpdateOp>,
+        Self::Inner: StorableObject,
+    {
+        let mut spec = self.lock().clone();
+        spec.start_update_inner(registry, state, update_operation)
+            .await?;
+        *self.lock() = spec;
+
+        self.link_op::<Self::UpdateOp>(linked)
+    }
+
+    /// Completes a destroy operation which is linked to the operation of another resource: rather
+    /// than being deleted on its own, the spec deletion is added to the `linked` transaction to be
+    /// committed atomically with the other resource's spec.
+    /// Once the transaction has been attempted, the operation must be completed with
+    /// `complete_linked_op`, until then the resource remains busy.
+    async fn complete_destroy_linked<O, R: Send + Debug>(
+        &mut self,
+        result: Result<R, SvcError>,
+        registry: &Registry,
+        linked: &mut LinkedTxn,
+    ) -> Result<R, SvcError>
+    where
+        Self::Inner: SpecTransaction<O>,
+        Self::Inner: StorableObject,
+    {
+        match result {
+            Ok(val) => {
+                tracing::info!(?val, "complete_destroy_linked");
+                linked.delete_obj(&self.lock().key());
+                self.lock().set_op_result(true);
+                Ok(val)
+            }
+            Err(error) => self.complete_destroy(Err(error), registry).await,
+        }
+    }
+
+    /// Add the spec, with its pending operation committed, to the `linked` transaction.
+    /// The spec may have been modified in place since it was first linked, in which case its
+    /// linked update is replaced.
+    fn link_op<O>(&self, linked: &mut LinkedTxn) -> Result<(), SvcError>
+    where
+        Self::Inner: SpecTransaction<O>,
+    {
+        let mut spec_clone = self.lock().clone();
+        spec_clone.commit_op();
+        if let Err(error) = linked.put_obj(&spec_clone) {
+            self.lock().clear_op();
+            return Err(error);
+        }
+        // if the linked transaction fails to be committed, the dirty spec reconciler takes over
+        self.lock().set_op_result(true);
+        Ok(())
+    }
+
+    /// Completes an operation which was linked to the operation of another resource, once the
+    /// `linked` transaction has been attempted.
+    /// If the transaction was not committed because the other resource's operation failed, the
+    /// spec is updated on its own, as the linked operation itself did succeed.
+    /// If the persistent store operation failed then the spec is marked accordingly and the dirty
+    /// spec reconciler will attempt to update the store when the store is back online.
+    async fn complete_linked_op<O>(&mut self, registry: &Registry, linked: &mut LinkedTxn)
+    where
+        Self::Inner: SpecTransaction<O>,
+        Self::Inner: StorableObject,
+    {
+        let key = self.lock().key().key();
+        let Some(delete) = linked.deletes(&key) else {
+            // the operation failed and was already completed
+            return;
+        };
+        let stored = match linked.committed {
+            Some(committed) => committed,
+            None => {
+                // the linked transaction is discarded, so store it on our own
+                linked.unlink(&key);
+                let mut spec_clone = self.lock().clone();
+                spec_clone.commit_op();
+                match delete {
+                    true => registry.delete_kv(&key).await,
+                    false => registry.store_obj(&spec_clone).await,
+                }
+                .is_ok()
+            }
+        };
+        match (stored, delete) {
+            (true, true) => {
+                self.remove_spec(registry);
+                self.complete_op();
+            }
+            (true, false) => self.complete_op(),
+            (false, true) => self.update(),
+            (false, false) => {}
+        }
+    }
+
     /// Validates the outcome of an intermediate step, part of a transaction operation.
     /// In case of an error, it undoes the changes to the spec.
     /// If the persistent store is unavailable the spec is marked as dirty and the dirty
@@ -656,6 +842,69 @@ pub(crate) trait GuardedOperationsHelper:
     }
 }
 
+/// Spec updates of resources which are linked to the operation of another resource, so that they
+/// are committed to the persistent store atomically with that resource's spec, rather than each on
+/// their own.
+/// Every linked resource must be completed with `GuardedOperationsHelper::complete_linked_op` once
+/// the transaction has been attempted.
+#[derive(Debug, Default)]
+pub(crate) struct LinkedTxn {
+    /// The linked specs by key, where `None` means the spec is deleted.
+    specs: BTreeMap<String, Option<serde_json::Value>>,
+    /// Whether the transaction was committed, once attempted.
+    committed: Option<bool>,
+}
+
+impl LinkedTxn {
+    /// Create a new empty `Self`.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+    /// Check if there are no linked specs.
+    fn is_empty(&self) -> bool {
+        self.specs.is_empty()
+    }
+    /// Put the given spec, replacing any previous update of the same spec.
+    fn put_obj<O: StorableObject>(&mut self, object: &O) -> Result<(), SvcError> {
+        let value =
+            serde_json::to_value(object).map_err(|source| StoreError::SerialiseValue { source })?;
+        self.specs.insert(object.key().key(), Some(value));
+        Ok(())
+    }
+    /// Delete the spec with the given key.
+    fn delete_obj<K: ObjectKey>(&mut self, key: &K) {
+        self.specs.insert(key.key(), None);
+    }
+    /// Check whether the spec with the given key is deleted or updated, if it's linked at all.
+    fn deletes(&self, key: &str) -> Option<bool> {
+        self.specs.get(key).map(Option::is_none)
+    }
+    /// Remove the spec with the given key.
+    fn unlink(&mut self, key: &str) {
+        self.specs.remove(key);
+    }
+    /// Commit the linked specs along with the given `spec`, if any, in a single transaction.
+    async fn commit<O: StorableObject>(
+        &mut self,
+        registry: &Registry,
+        spec: Option<&O>,
+    ) -> Result<(), SvcError> {
+        let mut txn = StoreTxnRequest::new();
+        if let Some(spec) = spec {
+            txn.put_obj(spec)?;
+        }
+        for (key, value) in &self.specs {
+            match value {
+                Some(value) => txn.put_kv(key, value)?,
+                None => txn.delete_kv(key),
+            }
+        }
+        let result = registry.store_txn(txn).await;
+        self.committed = Some(result.is_ok());
+        result
+    }
+}
+
 #[async_trait::async_trait]
 pub(crate) trait SpecOperationsHelper:
     Clone + Debug + StorableObject + AsOperationSequencer + PartialEq<Self::Create>
@@ -893,8 +1142,28 @@ pub(crate) struct ResourceSpecs {
     /// Top-level volume snapshots.
     pub(crate) volume_snapshots: ResourceMutexMap<SnapshotId, VolumeSnapshot>,
     pub(crate) app_nodes: ResourceMutexMap<AppNodeId, AppNodeSpec>,
+    pub(crate) snapshot_schedules: ResourceMutexMap<SnapshotScheduleId, SnapshotScheduleSpec>,
+    /// Persisted rebuild history of the volumes.
+    pub(crate) rebuild_histories: ResourceMutexMap<VolumeId, VolumeRebuildHistory>,
+    pub(crate) snapshot_groups: ResourceMutexMap<SnapshotGroupId, SnapshotGroupSpec>,
+    pub(crate) snapshot_exports: ResourceMutexMap<SnapshotExportId, SnapshotExportSpec>,
 }
 
+/// The spec types which are persisted in the store and loaded on startup.
+const SPEC_TYPES: [StorableObjectType; 11] = [
+    StorableObjectType::VolumeSpec,
+    StorableObjectType::NodeSpec,
+    StorableObjectType::NexusSpec,
+    StorableObjectType::PoolSpec,
+    StorableObjectType::ReplicaSpec,
+    StorableObjectType::VolumeSnapshot,
+    StorableObjectType::AppNodeSpec,
+    StorableObjectType::SnapshotScheduleSpec,
+    StorableObjectType::VolumeRebuildHistory,
+    StorableObjectType::SnapshotGroupSpec,
+    StorableObjectType::SnapshotExportSpec,
+];
+
 impl ResourceSpecsLocked {
     pub(crate) fn new() -> Self {
         ResourceSpecsLocked::default()
@@ -907,23 +1176,68 @@ impl ResourceSpecsLocked {
         legacy_prefix_present: bool,
         etcd_max_page_size: i64,
     ) -> Result<(), SvcError> {
-        let spec_types = [
-            StorableObjectType::VolumeSpec,
-            StorableObjectType::NodeSpec,
-            StorableObjectType::NexusSpec,
-            StorableObjectType::PoolSpec,
-            StorableObjectType::ReplicaSpec,
-            StorableObjectType::VolumeSnapshot,
-            StorableObjectType::AppNodeSpec,
-        ];
-        for spec in &spec_types {
-            self.populate_specs(store, *spec, legacy_prefix_present, etcd_max_page_size)
+        for spec in SPEC_TYPES {
+            self.populate_specs(store, spec, legacy_prefix_present, etcd_max_page_size)
                 .await
                 .map_err(|error| SvcError::Internal {
                     details: error.full_string(),
                 })?;
         }
+        self.patch_runtime_info();
+
+        // Remove all entries of v1 key prefix.
+        store
+            .delete_values_prefix(&product_v1_key_prefix())
+            .await
+            .map_err(|error| StoreError::Generic {
+                source: Box::new(error),
+                description: "Product v1 prefix cleanup failed".to_string(),
+            })?;
+        Ok(())
+    }
+
+    /// Reload the resource specs after the persistent store has been restored from a backup.
+    /// Node and app node specs which are already known are left as they are, whereas all other
+    /// resources are expected not to be known yet.
+    pub(crate) async fn reload<S: Store>(
+        &self,
+        store: &mut S,
+        etcd_max_page_size: i64,
+    ) -> Result<(), SvcError> {
+        for spec in SPEC_TYPES {
+            match spec {
+                StorableObjectType::NodeSpec | StorableObjectType::AppNodeSpec => {
+                    self.merge_specs(store, spec, etcd_max_page_size).await
+                }
+                _ => {
+                    self.populate_specs(store, spec, false, etcd_max_page_size)
+                        .await
+                }
+            }
+            .map_err(|error| SvcError::Internal {
+                details: error.full_string(),
+            })?;
+        }
+        self.patch_runtime_info();
+        Ok(())
+    }
+
+    /// Check whether any resource other than the node and app node specs is known.
+    pub(crate) fn has_resources(&self) -> bool {
+        let specs = self.read();
+        specs.volumes.len() > 0
+            || specs.nexuses.len() > 0
+            || specs.pools.len() > 0
+            || specs.replicas.len() > 0
+            || specs.volume_snapshots.len() > 0
+            || specs.snapshot_schedules.len() > 0
+            || specs.rebuild_histories.len() > 0
+            || specs.snapshot_groups.len() > 0
+            || specs.snapshot_exports.len() > 0
+    }
 
+    /// Patch up the runtime information which is not persisted in the store.
+    fn patch_runtime_info(&self) {
         // patch up the missing replica nexus owners
         for replica in self.read().replicas.values() {
             self.read()
@@ -934,9 +1248,13 @@ impl ResourceSpecsLocked {
         }
         // add runtime information for volume snapshots
         for snapshot in self.read().volume_snapshots.values() {
-            let volume_id = snapshot.immutable_ref().spec().source_id();
-            if let Some(volume) = self.read().volumes.get(volume_id) {
-                volume.lock().insert_snapshot(snapshot.uuid());
+            let spec = snapshot.immutable_ref().spec().clone();
+            if let Some(volume) = self.read().volumes.get(spec.source_id()) {
+                let mut volume = volume.lock();
+                if spec.internal() {
+                    volume.set_internal_snapshot(snapshot.uuid());
+                }
+                volume.insert_snapshot(snapshot.uuid());
             }
         }
 
@@ -944,23 +1262,13 @@ impl ResourceSpecsLocked {
         for volume in self.read().volumes.values() {
             match volume.immutable_ref().content_source.as_ref() {
                 None => continue,
-                Some(VolumeContentSource::Snapshot(snap_uuid, _)) => {
-                    if let Some(snapshot) = self.read().volume_snapshots.get(snap_uuid) {
+                Some(source) => {
+                    if let Some(snapshot) = self.read().volume_snapshots.get(source.snapshot()) {
                         snapshot.lock().insert_restore(volume.uuid())
                     }
                 }
             }
         }
-
-        // Remove all entries of v1 key prefix.
-        store
-            .delete_values_prefix(&product_v1_key_prefix())
-            .await
-            .map_err(|error| StoreError::Generic {
-                source: Box::new(error),
-                description: "Product v1 prefix cleanup failed".to_string(),
-            })?;
-        Ok(())
     }
 
     /// Deserialise a vector of serde_json values into specific spec types.
@@ -988,6 +1296,54 @@ impl ResourceSpecsLocked {
         Ok(result)
     }
 
+    /// Get all the values of the given type from the persistent store.
+    async fn get_spec_values<S: Store>(
+        store: &mut S,
+        spec_type: StorableObjectType,
+        etcd_max_page_size: i64,
+    ) -> Result<Vec<serde_json::Value>, SpecError> {
+        let prefix = key_prefix_obj(spec_type, API_VERSION);
+        let store_entries = store
+            .get_values_paged_all(&prefix, etcd_max_page_size)
+            .await
+            .map_err(|e| SpecError::StoreGet {
+                source: Box::new(e),
+            })?;
+        Ok(store_entries.into_iter().map(|e| e.1).collect())
+    }
+
+    /// Add the specs of the given type from the persistent store which are not known yet.
+    async fn merge_specs<S: Store>(
+        &self,
+        store: &mut S,
+        spec_type: StorableObjectType,
+        etcd_max_page_size: i64,
+    ) -> Result<(), SpecError> {
+        let store_values = Self::get_spec_values(store, spec_type, etcd_max_page_size).await?;
+
+        let mut resource_specs = self.0.write();
+        match spec_type {
+            StorableObjectType::NodeSpec => {
+                let specs =
+                    Self::deserialise_specs::<NodeSpec>(store_values).context(Deserialise {
+                        obj_type: StorableObjectType::NodeSpec,
+                    })?;
+                resource_specs.nodes.populate_missing(specs);
+            }
+            StorableObjectType::AppNodeSpec => {
+                let specs =
+                    Self::deserialise_specs::<AppNodeSpec>(store_values).context(Deserialise {
+                        obj_type: StorableObjectType::AppNodeSpec,
+                    })?;
+                resource_specs.app_nodes.populate_missing(specs);
+            }
+            _ => {
+                unimplemented!("{} specs cannot be merged", spec_type);
+            }
+        };
+        Ok(())
+    }
+
     /// Populate the resource specs with data from the persistent store.
     async fn populate_specs<S: Store>(
         &self,
@@ -1003,14 +1359,7 @@ impl ResourceSpecsLocked {
                     source: Box::new(e),
                 })?;
         }
-        let prefix = key_prefix_obj(spec_type, API_VERSION);
-        let store_entries = store
-            .get_values_paged_all(&prefix, etcd_max_page_size)
-            .await
-            .map_err(|e| SpecError::StoreGet {
-                source: Box::new(e),
-            })?;
-        let store_values = store_entries.iter().map(|e| e.1.clone()).collect();
+        let store_values = Self::get_spec_values(store, spec_type, etcd_max_page_size).await?;
 
         let mut resource_specs = self.0.write();
         match spec_type {
@@ -1068,6 +1417,38 @@ impl ResourceSpecsLocked {
                     })?;
                 resource_specs.app_nodes.populate(specs);
             }
+            StorableObjectType::SnapshotScheduleSpec => {
+                let specs = Self::deserialise_specs::<SnapshotScheduleSpec>(store_values).context(
+                    Deserialise {
+                        obj_type: StorableObjectType::SnapshotScheduleSpec,
+                    },
+                )?;
+                resource_specs.snapshot_schedules.populate(specs);
+            }
+            StorableObjectType::VolumeRebuildHistory => {
+                let specs = Self::deserialise_specs::<VolumeRebuildHistory>(store_values).context(
+                    Deserialise {
+                        obj_type: StorableObjectType::VolumeRebuildHistory,
+                    },
+                )?;
+                resource_specs.rebuild_histories.populate(specs);
+            }
+            StorableObjectType::SnapshotGroupSpec => {
+                let specs = Self::deserialise_specs::<SnapshotGroupSpec>(store_values).context(
+                    Deserialise {
+                        obj_type: StorableObjectType::SnapshotGroupSpec,
+                    },
+                )?;
+                resource_specs.snapshot_groups.populate(specs);
+            }
+            StorableObjectType::SnapshotExportSpec => {
+                let specs = Self::deserialise_specs::<SnapshotExportSpec>(store_values).context(
+                    Deserialise {
+                        obj_type: StorableObjectType::SnapshotExportSpec,
+                    },
+                )?;
+                resource_specs.snapshot_exports.populate(specs);
+            }
             _ => {
                 // Not all spec types are persisted in the store.
                 unimplemented!("{} not persisted in store", spec_type);
@@ -69,6 +69,16 @@ where
         }
     }
 
+    /// Populate the resource map with the values which are not present yet.
+    /// Unlike `populate`, any references to the existing values remain valid.
+    pub(crate) fn populate_missing(&mut self, values: impl IntoVec<S>) {
+        for value in values.into_vec() {
+            if !self.map.contains_key(value.uid()) {
+                self.map.insert(value.uid().clone(), value.into());
+            }
+        }
+    }
+
     /// Get all the resources as a vector.
     pub(crate) fn to_vec(&self) -> Vec<ResourceMutex<S>> {
         self.map.values().cloned().collect()
@@ -2,7 +2,10 @@ mod snapshot;
 
 use super::{ResourceMutex, ResourceUid};
 use stor_port::types::v0::{
-    store::volume::{AffinityGroupSpec, VolumeSpec},
+    store::{
+        rebuild_history::VolumeRebuildHistory,
+        volume::{AffinityGroupSpec, VolumeSpec},
+    },
     transport::VolumeId,
 };
 
@@ -19,6 +22,13 @@ impl ResourceUid for VolumeSpec {
     }
 }
 
+impl ResourceUid for VolumeRebuildHistory {
+    type Uid = VolumeId;
+    fn uid(&self) -> &Self::Uid {
+        &self.volume_id
+    }
+}
+
 impl ResourceUid for AffinityGroupSpec {
     type Uid = String;
     fn uid(&self) -> &Self::Uid {
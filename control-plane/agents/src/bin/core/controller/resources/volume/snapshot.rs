@@ -1,12 +1,14 @@
 use super::{ResourceMutex, ResourceUid};
 use stor_port::types::v0::{
-    store::snapshots::{volume::VolumeSnapshot, ReplicaSnapshotState},
-    transport::SnapshotId,
+    store::snapshots::{
+        export::SnapshotExportSpec, group::SnapshotGroupSpec, schedule::SnapshotScheduleSpec,
+        volume::VolumeSnapshot, ReplicaSnapshotState,
+    },
+    transport::{SnapshotExportId, SnapshotGroupId, SnapshotId, SnapshotScheduleId},
 };
 
 impl ResourceMutex<VolumeSnapshot> {
     /// Get the resource uuid.
-    #[allow(dead_code)]
     pub fn uuid(&self) -> &SnapshotId {
         self.immutable_ref().uid()
     }
@@ -23,3 +25,21 @@ impl ResourceUid for ReplicaSnapshotState {
         self.snapshot.snap_uuid()
     }
 }
+impl ResourceUid for SnapshotScheduleSpec {
+    type Uid = SnapshotScheduleId;
+    fn uid(&self) -> &Self::Uid {
+        &self.id
+    }
+}
+impl ResourceUid for SnapshotGroupSpec {
+    type Uid = SnapshotGroupId;
+    fn uid(&self) -> &Self::Uid {
+        &self.id
+    }
+}
+impl ResourceUid for SnapshotExportSpec {
+    type Uid = SnapshotExportId;
+    fn uid(&self) -> &Self::Uid {
+        &self.id
+    }
+}
@@ -1,6 +1,7 @@
 pub(crate) mod affinity_group;
 pub(crate) mod nexus;
 pub(crate) mod pool;
+pub(crate) mod rebalance;
 pub(crate) mod resources;
 pub(crate) mod volume;
 mod volume_policy;
@@ -11,7 +12,7 @@ use crate::controller::scheduling::{
     volume::{ReplicaResizePoolsContext, VolumeReplicasForNexusCtx},
 };
 use std::{cmp::Ordering, collections::HashMap, future::Future};
-use weighted_scoring::{Criteria, Value, ValueGrading, WeightedScore};
+use weighted_scoring::{Criteria, Ranged, Value, ValueGrading, WeightedScore};
 
 #[async_trait::async_trait(?Send)]
 pub(crate) trait ResourcePolicy<Request: ResourceFilter>: Sized {
@@ -142,6 +143,13 @@ impl SortBuilder {
 
     /// Build the comparator based on the weights of sort criteria.
     pub(crate) fn compare(&self, a: &PoolItem, b: &PoolItem) -> std::cmp::Ordering {
+        let (score_a, score_b) = self.scores(a, b);
+        score_b.cmp(&score_a)
+    }
+
+    /// Get the weighted scores of both pools, relative to each other.
+    /// The better pool gets the higher score.
+    pub(crate) fn scores(&self, a: &PoolItem, b: &PoolItem) -> (Ranged, Ranged) {
         let mut weighted_score = WeightedScore::dual_values();
         for criteria in &self.sort_criterias {
             let value_a = (criteria.value_fn)(a);
@@ -149,8 +157,7 @@ impl SortBuilder {
             weighted_score =
                 weighted_score.weigh(criteria.criteria, criteria.grading, value_a, value_b);
         }
-        let (score_a, score_b) = weighted_score.score().unwrap();
-        score_b.cmp(&score_a)
+        weighted_score.score().unwrap()
     }
 }
 
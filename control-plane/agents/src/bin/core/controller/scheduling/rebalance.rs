@@ -0,0 +1,119 @@
+use crate::{
+    controller::{
+        registry::Registry,
+        scheduling::{
+            resources::{PoolItem, PoolItemLister},
+            volume_policy::SimplePolicy,
+            SortBuilder,
+        },
+    },
+    volume::volume_move_replica_candidates,
+};
+use stor_port::types::v0::transport::{PoolStatus, ReplicaMove, VolumeId, VolumeStatus};
+
+use std::collections::HashSet;
+
+/// Get the replica moves which would even out the utilisation of the pools.
+/// Pools are scored against each other using the `SimplePolicy` weights and any two pools whose
+/// scores differ by at least `threshold` are considered imbalanced.
+/// At most one replica is moved off each over-utilised pool and each volume only has one replica
+/// moved at a time.
+/// The replacement pools are picked by the volume scheduler, and so they honour the volume's
+/// topology, affinity group and the node cordons.
+pub(crate) async fn replica_moves(registry: &Registry, threshold: u64) -> Vec<ReplicaMove> {
+    let sorter = SimplePolicy::utilisation_sorter();
+    let mut pools = PoolItemLister::list(registry, &None)
+        .await
+        .into_iter()
+        .filter(|item| item.pool.state().status == PoolStatus::Online)
+        .collect::<Vec<_>>();
+    pools.sort_by(|a, b| sorter.compare(a, b));
+
+    let Some(least_utilised) = pools.first() else {
+        return vec![];
+    };
+
+    let mut moving = HashSet::new();
+    let mut moves = vec![];
+    // from the most utilised pool, until the remaining pools are balanced enough.
+    for source in pools.iter().rev() {
+        if score_gap(&sorter, least_utilised, source) < threshold {
+            break;
+        }
+        if let Some(replica_move) =
+            pool_replica_move(registry, &sorter, &pools, source, threshold, &moving).await
+        {
+            moving.insert(replica_move.volume.clone());
+            moves.push(replica_move);
+        }
+    }
+    moves
+}
+
+/// Find a replica on the `source` pool which can be moved to a pool whose score is better by at
+/// least `threshold`.
+/// Only replicas of healthy volumes with a target and without a replica move in progress are
+/// moved, so the new replica can be rebuilt from the remaining replicas.
+async fn pool_replica_move(
+    registry: &Registry,
+    sorter: &SortBuilder,
+    pools: &[PoolItem],
+    source: &PoolItem,
+    threshold: u64,
+    moving: &HashSet<VolumeId>,
+) -> Option<ReplicaMove> {
+    for replica in source.pool.replicas() {
+        let Some(volume_id) = registry
+            .specs()
+            .replica_rsc(&replica.uuid)
+            .and_then(|spec| spec.lock().owners.volume().cloned())
+        else {
+            continue;
+        };
+        if moving.contains(&volume_id) {
+            continue;
+        }
+        let Ok(volume) = registry.specs().volume_clone(&volume_id) else {
+            continue;
+        };
+        if !volume.status.created()
+            || volume.operation.is_some()
+            || volume.replica_move.is_some()
+            || volume.target().is_none()
+        {
+            continue;
+        }
+        match registry.volume_state(&volume_id).await {
+            Ok(state) if state.status == VolumeStatus::Online => {}
+            _ => continue,
+        }
+
+        let Ok(candidates) = volume_move_replica_candidates(registry, &volume, &replica.uuid).await
+        else {
+            continue;
+        };
+        let target = candidates.into_iter().find_map(|candidate| {
+            pools
+                .iter()
+                .find(|item| item.pool.id == candidate.pool_id)
+                .filter(|item| score_gap(sorter, item, source) >= threshold)
+        });
+        if let Some(target) = target {
+            return Some(ReplicaMove {
+                volume: volume_id,
+                replica: replica.uuid.clone(),
+                source_node: source.pool.node.clone(),
+                source_pool: source.pool.id.clone(),
+                target_node: target.pool.node.clone(),
+                target_pool: target.pool.id.clone(),
+            });
+        }
+    }
+    None
+}
+
+/// How much better the `better` pool scores against the `worse` pool.
+fn score_gap(sorter: &SortBuilder, better: &PoolItem, worse: &PoolItem) -> u64 {
+    let (better, worse) = sorter.scores(better, worse);
+    better.val().saturating_sub(worse.val())
+}
@@ -17,7 +17,7 @@ use stor_port::types::v0::{
         nexus::NexusSpec, nexus_persistence::NexusInfo, snapshots::replica::ReplicaSnapshot,
         volume::VolumeSpec,
     },
-    transport::{NodeId, PoolId, VolumeState},
+    transport::{NodeId, PoolId, VolumeCapacity, VolumeState},
 };
 
 /// Move replica to another pool.
@@ -216,6 +216,71 @@ impl ResourceFilter for AddVolumeReplica {
     }
 }
 
+/// Calculates the capacity available to new volumes, using the same pool and node filters as the
+/// replica placement of a new volume.
+pub(crate) struct VolumeCapacityCandidates {
+    candidates: AddVolumeReplica,
+}
+
+impl VolumeCapacityCandidates {
+    /// Return a new `Self` for new volumes with the given spec, whose size is disregarded.
+    pub(crate) async fn new(spec: &VolumeSpec, registry: &Registry) -> Self {
+        let request = GetSuitablePools::new(spec, None);
+        Self {
+            candidates: AddVolumeReplica::builder(request, registry).await,
+        }
+    }
+    /// Get the size of the largest replica which may be placed on the given pool, or 0 if no
+    /// replica may be placed on it at all.
+    /// As the filters only ever reject more pools as the size grows, the size is found with a
+    /// binary search bounded by the pool capacity.
+    fn max_replica_size(&self, pool: &PoolItem) -> u64 {
+        let suitable = |size: u64| {
+            let mut candidates = self.candidates.clone();
+            candidates.data.context.spec.size = size;
+            candidates.data.list = vec![pool.clone()];
+            !candidates.with_default_policy().collect().is_empty()
+        };
+        let (mut low, mut high) = (0, pool.pool.capacity);
+        while high - low > 1 {
+            let size = low + (high - low) / 2;
+            if suitable(size) {
+                low = size;
+            } else {
+                high = size;
+            }
+        }
+        low
+    }
+    /// Get the capacity available to the new volumes.
+    /// The largest volume is limited by the node which can take the Nth largest replica, where N
+    /// is the number of volume replicas, as each replica must be placed on a different node.
+    pub(crate) fn capacity(&self) -> VolumeCapacity {
+        let replicas = self.candidates.data.context.num_replicas.max(1) as usize;
+
+        let mut available_capacity = 0;
+        let mut node_max_sizes = HashMap::<NodeId, u64>::new();
+        for pool in &self.candidates.data.list {
+            let size = self.max_replica_size(pool);
+            if size == 0 {
+                continue;
+            }
+            available_capacity += size;
+            let node_size = node_max_sizes.entry(pool.pool.node.clone()).or_default();
+            *node_size = size.max(*node_size);
+        }
+
+        let mut node_max_sizes = node_max_sizes.into_values().collect::<Vec<_>>();
+        node_max_sizes.sort_unstable_by(|a, b| b.cmp(a));
+        let maximum_volume_size = node_max_sizes
+            .get(replicas - 1)
+            .copied()
+            .unwrap_or_default();
+
+        VolumeCapacity::new(available_capacity / replicas as u64, maximum_volume_size)
+    }
+}
+
 /// Decrease a volume's replicas when it exceeds the required count.
 #[derive(Clone)]
 pub(crate) struct DecreaseVolumeReplica {
@@ -29,6 +29,7 @@ impl DefaultBasePolicy {
     fn filter_pools(request: AddVolumeReplica) -> AddVolumeReplica {
         request
             .filter(pool::PoolBaseFilters::usable)
+            .filter(pool::PoolBaseFilters::cordoned)
             .filter(pool::PoolBaseFilters::capacity)
             .filter(pool::PoolBaseFilters::min_free_space)
             .filter(pool::PoolBaseFilters::topology)
@@ -58,6 +59,7 @@ impl DefaultBasePolicy {
     fn filter_clone_pools(request: CloneVolumeSnapshot) -> CloneVolumeSnapshot {
         request
             .filter(pool::PoolBaseFilters::usable)
+            .filter(pool::PoolBaseFilters::cordoned)
             .filter(pool::PoolBaseFilters::capacity)
             .filter(pool::PoolBaseFilters::min_free_space)
     }
@@ -75,6 +75,15 @@ impl PoolBaseFilters {
         item.pool.status != PoolStatus::Faulted && item.pool.status != PoolStatus::Unknown
     }
 
+    /// Should only attempt to use pools which are not cordoned, or being evacuated.
+    pub(crate) fn cordoned(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
+        !request
+            .registry()
+            .specs()
+            .pool_rsc(&item.pool.id)
+            .map_or(false, |pool| pool.lock().cordoned())
+    }
+
     /// Should only attempt to use pools having specific creation label if topology has it.
     pub(crate) fn topology(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
         let volume_pool_topology_inclusion_labels: HashMap<String, String>;
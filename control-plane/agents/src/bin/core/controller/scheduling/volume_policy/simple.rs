@@ -158,6 +158,15 @@ impl SimplePolicy {
         }
     }
 
+    /// Sort pools by how much they're utilised, using the same weights as `sort_by_weights`
+    /// without the Affinity Group criteria, from the least utilised to the most utilised.
+    pub(crate) fn utilisation_sorter() -> SortBuilder {
+        SortBuilder::new()
+            .with_criteria(SimplePolicy::non_ag_total_replica_count)
+            .with_criteria(SimplePolicy::free_space)
+            .with_criteria(SimplePolicy::over_commitment)
+    }
+
     /// Helper to figure out space availability based on pool free, pool available and
     /// volume's allocated space.
     fn min_free_space_util(&self, free: u64, allocated: &Option<u64>, required: u64) -> bool {
@@ -379,4 +388,20 @@ mod tests {
 
         // todo: add more tests before merge
     }
+    #[test]
+    fn utilisation_scores() {
+        let gig = 1024 * 1024 * 1024;
+        let sorter = SimplePolicy::utilisation_sorter();
+
+        let empty = make_pool("1", "1", gig * 10, 0, None);
+        let full = make_pool("2", "2", gig, 10, None);
+        let (score_empty, score_full) = sorter.scores(&empty, &full);
+        assert!(score_empty.val() > score_full.val());
+        assert!(score_empty.val() - score_full.val() >= 50);
+        assert_eq!(sorter.compare(&empty, &full), Ordering::Less);
+
+        let same = make_pool("3", "3", gig * 10, 0, None);
+        let (score_empty, score_same) = sorter.scores(&empty, &same);
+        assert_eq!(score_empty, score_same);
+    }
 }
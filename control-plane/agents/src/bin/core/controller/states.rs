@@ -238,6 +238,11 @@ impl ResourceStates {
         self.rebuild_history.get(id)
     }
 
+    /// Get all rebuild histories.
+    pub(crate) fn rebuild_histories_cloned(&self) -> Vec<RebuildHistory> {
+        Self::cloned_inner_states(self.rebuild_history.values())
+    }
+
     /// Get a rebuild history with the given ID.
     pub(crate) fn rebuild_history_time(&self) -> Option<prost_types::Timestamp> {
         self.rebuild_history_since.clone()
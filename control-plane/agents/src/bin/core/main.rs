@@ -22,7 +22,7 @@ use controller::registry::NumRebuilds;
 use std::{net::SocketAddr, num::ParseIntError};
 use utils::{version_info_str, DEFAULT_GRPC_SERVER_ADDR, ETCD_MAX_PAGE_LIMIT};
 
-use stor_port::HostAccessControl;
+use stor_port::{types::v0::store::rebuild_history::RebuildHistoryRetention, HostAccessControl};
 use utils::tracing_telemetry::{trace::TracerProvider, FmtLayer, FmtStyle, KeyValue};
 
 /// The Cli arguments for this binary.
@@ -56,7 +56,8 @@ pub(crate) struct CliArgs {
     pub(crate) deadline: humantime::Duration,
 
     /// The Persistent Store URLs to connect to.
-    /// (supports the http/https schema)
+    /// (supports the http/https schema for etcd, `mem://` for an in-memory store and
+    /// `file:///path/to/file` for an in-memory store persisted to a single file)
     #[clap(long, short, default_value = "http://localhost:2379")]
     pub(crate) store: String,
 
@@ -106,6 +107,15 @@ pub(crate) struct CliArgs {
     #[clap(flatten)]
     thin_args: ThinArgs,
 
+    #[clap(flatten)]
+    rebuild_history_args: RebuildHistoryArgs,
+
+    #[clap(flatten)]
+    pool_rebalance_args: PoolRebalanceArgs,
+
+    #[clap(flatten)]
+    encryption_key_args: EncryptionKeyArgs,
+
     /// Events message-bus endpoint url.
     #[clap(long, short)]
     events_url: Option<url::Url>,
@@ -159,6 +169,52 @@ pub(crate) struct ThinArgs {
     volume_commitment_initial: u64,
 }
 
+/// Retention of the persisted rebuild history of the volumes.
+#[derive(Debug, clap::Parser, Clone)]
+pub(crate) struct RebuildHistoryArgs {
+    /// The maximum number of rebuild records to keep for each volume.
+    #[clap(long, default_value = "256")]
+    rebuild_history_max_records: usize,
+    /// The maximum age of the rebuild records to keep, by their end time.
+    /// If `None` the rebuild records are only pruned when exceeding the maximum number of records.
+    #[clap(long)]
+    rebuild_history_max_age: Option<humantime::Duration>,
+}
+impl From<RebuildHistoryArgs> for RebuildHistoryRetention {
+    fn from(args: RebuildHistoryArgs) -> Self {
+        Self {
+            max_records: args.rebuild_history_max_records,
+            max_age: args.rebuild_history_max_age.map(Into::into),
+        }
+    }
+}
+
+/// Cluster wide pool rebalance parameters.
+#[derive(Debug, clap::Parser, Clone)]
+pub(crate) struct PoolRebalanceArgs {
+    /// Enable the pool rebalancer, which moves volume replicas from over-utilised pools to
+    /// under-utilised pools.
+    #[clap(long, env = "POOL_REBALANCE")]
+    pool_rebalance: bool,
+    /// The minimum difference between the weighted scores of two pools, out of 100, for them to
+    /// be considered imbalanced.
+    /// The pools are scored by the same criteria used when placing new replicas.
+    #[clap(long, env = "POOL_REBALANCE_THRESHOLD", value_parser = clap::value_parser!(u64).range(1..=100), default_value = "20")]
+    pool_rebalance_threshold: u64,
+}
+
+/// Volume encryption key provider parameters.
+#[derive(Debug, clap::Parser, Clone)]
+pub(crate) struct EncryptionKeyArgs {
+    /// The directory holding the keys of the `file` encryption key provider, one key per file.
+    #[clap(long, env = "ENCRYPTION_KEY_DIR")]
+    encryption_key_dir: Option<std::path::PathBuf>,
+    /// The url of the key management server of the `kms` encryption key provider.
+    /// Keys are fetched from `$url/v1/keys/$name`.
+    #[clap(long, env = "ENCRYPTION_KMS_ENDPOINT")]
+    encryption_kms_endpoint: Option<url::Url>,
+}
+
 fn value_parse_percent(value: &str) -> Result<u64, ParseIntError> {
     value.replace('%', "").parse()
 }
@@ -198,6 +254,9 @@ async fn server(cli_args: CliArgs) -> anyhow::Result<()> {
             cli_args.hosts_acl.clone()
         },
         cli_args.thin_args,
+        cli_args.rebuild_history_args.into(),
+        cli_args.pool_rebalance_args,
+        &cli_args.encryption_key_args,
         cli_args.disable_ha,
         cli_args.etcd_page_limit as i64,
     )
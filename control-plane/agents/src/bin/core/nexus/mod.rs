@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 mod operations;
 mod operations_helper;
+mod rebuild_history;
 mod registry;
 /// Nexus Scheduling helpers.
 pub(crate) mod scheduling;
@@ -8,7 +8,8 @@ use crate::{
                 ResourceSharing, ResourceShutdownOperations,
             },
             operations_helper::{
-                GuardedOperationsHelper, OnCreateFail, OperationSequenceGuard, SpecOperationsHelper,
+                GuardedOperationsHelper, LinkedTxn, OnCreateFail, OperationSequenceGuard,
+                SpecOperationsHelper,
             },
             OperationGuardArc, TraceSpan, UpdateInnerValue,
         },
@@ -25,8 +26,12 @@ use stor_port::types::v0::{
     },
     transport::{
         child::Child,
-        nexus::{CreateNexus, DestroyNexus, Nexus, ResizeNexus, ShareNexus, UnshareNexus},
-        AddNexusChild, FaultNexusChild, NexusOwners, NodeStatus, RemoveNexusChild, ShutdownNexus,
+        nexus::{
+            CreateNexus, DestroyNexus, Nexus, NexusShareProtocol, ResizeNexus, ShareNexus,
+            UnshareNexus,
+        },
+        AddNexusChild, FaultNexusChild, HostNqn, NexusOwners, NodeStatus, RemoveNexusChild,
+        ShutdownNexus,
     },
 };
 
@@ -40,27 +45,12 @@ impl ResourceLifecycle for OperationGuardArc<NexusSpec> {
         registry: &Registry,
         request: &Self::Create,
     ) -> Result<Self::CreateOutput, SvcError> {
-        let specs = registry.specs();
-
-        if registry.node_cordoned(&request.node)? {
-            return Err(CordonedNode {
-                node_id: request.node.to_string(),
-            });
-        }
-        if request.children.is_empty() {
-            return Err(SvcError::InvalidArguments {});
-        }
-
-        let node = registry.node_wrapper(&request.node).await?;
-
-        let mut nexus = specs
-            .get_or_create_nexus(request)
-            .operation_guard_wait()
-            .await?;
-        let _ = nexus.start_create(registry, request).await?;
+        let (mut nexus, node) = Self::start_create_nexus(registry, request).await?;
 
         let result = nexus.create_nexus(registry, node, request).await;
-        specs.on_create_set_owners(request, &nexus, &result);
+        registry
+            .specs()
+            .on_create_set_owners(request, &nexus, &result);
 
         let nexus_state = nexus
             .complete_create(result, registry, OnCreateFail::SetDeleting)
@@ -187,6 +177,7 @@ impl ResourceSharing for Option<&mut OperationGuardArc<NexusSpec>> {
         request: &Self::Share,
     ) -> Result<Self::ShareOutput, SvcError> {
         let node = registry.node_wrapper(&request.node).await?;
+        let request = &with_encryption_key(registry, request).await?;
 
         if let Some(nexus) = self {
             let status = registry.nexus(&request.uuid).await?;
@@ -419,6 +410,124 @@ impl ResourceOwnerUpdate for OperationGuardArc<NexusSpec> {
 }
 
 impl OperationGuardArc<NexusSpec> {
+    /// Creates a nexus and shares it, if requested, linked to the operation of its owner, so the
+    /// nexus spec is committed to the persistent store atomically with the owner's spec.
+    /// See `GuardedOperationsHelper::complete_create_linked`.
+    pub(crate) async fn create_linked(
+        registry: &Registry,
+        request: &CreateNexus,
+        share: Option<(NexusShareProtocol, Vec<HostNqn>)>,
+        linked: &mut LinkedTxn,
+    ) -> Result<(Self, Nexus), SvcError> {
+        let (mut nexus, node) = Self::start_create_nexus(registry, request).await?;
+
+        let result = nexus.create_nexus(registry, node.clone(), request).await;
+        registry
+            .specs()
+            .on_create_set_owners(request, &nexus, &result);
+        let result = match (result, share) {
+            (Ok(state), Some((protocol, allowed_hosts))) => {
+                nexus
+                    .share_creating(registry, &node, state, protocol, allowed_hosts)
+                    .await
+            }
+            (result, _) => result,
+        };
+
+        let nexus_state = nexus
+            .complete_create_linked(result, registry, OnCreateFail::SetDeleting, linked)
+            .await?;
+        Ok((nexus, nexus_state))
+    }
+
+    /// Destroys the nexus, linked to the operation of its owner, so the nexus spec is deleted from
+    /// the persistent store atomically with the owner's spec update.
+    /// See `GuardedOperationsHelper::complete_destroy_linked`.
+    pub(crate) async fn destroy_linked(
+        &mut self,
+        registry: &Registry,
+        request: &DestroyNexus,
+        linked: &mut LinkedTxn,
+    ) -> Result<(), SvcError> {
+        let node = match registry.node_wrapper(&request.node).await {
+            Err(error) if !request.lazy() => Err(error),
+            other => Ok(other),
+        }?;
+
+        self.start_destroy_by(registry, request.disowners()).await?;
+
+        let result = match node {
+            Ok(node) => node.destroy_nexus(request).await,
+            _ => Err(SvcError::NodeNotOnline {
+                node: request.node.to_owned(),
+            }),
+        };
+        registry.specs().on_delete_disown_replicas(self);
+        self.complete_destroy_linked(result, registry, linked).await
+    }
+
+    /// Checks that the nexus can be created on the requested node and starts its creation.
+    async fn start_create_nexus(
+        registry: &Registry,
+        request: &CreateNexus,
+    ) -> Result<(Self, std::sync::Arc<tokio::sync::RwLock<NodeWrapper>>), SvcError> {
+        if registry.node_cordoned(&request.node)? {
+            return Err(CordonedNode {
+                node_id: request.node.to_string(),
+            });
+        }
+        if request.children.is_empty() {
+            return Err(SvcError::InvalidArguments {});
+        }
+
+        let node = registry.node_wrapper(&request.node).await?;
+
+        let nexus = registry
+            .specs()
+            .get_or_create_nexus(request)
+            .operation_guard_wait()
+            .await?;
+        let _ = nexus.start_create(registry, request).await?;
+        Ok((nexus, node))
+    }
+
+    /// Shares the nexus as part of its creation, so the share is committed along with it.
+    /// If the nexus cannot be shared it is destroyed, failing its creation.
+    async fn share_creating(
+        &self,
+        registry: &Registry,
+        node: &std::sync::Arc<tokio::sync::RwLock<NodeWrapper>>,
+        mut nexus_state: Nexus,
+        protocol: NexusShareProtocol,
+        allowed_hosts: Vec<HostNqn>,
+    ) -> Result<Nexus, SvcError> {
+        let request = ShareNexus::new(&nexus_state, protocol, allowed_hosts.clone());
+        let result = match with_encryption_key(registry, &request).await {
+            Ok(request) => node.share_nexus(&request).await,
+            Err(error) => Err(error),
+        };
+        match result {
+            Ok(device_uri) => {
+                {
+                    let mut spec = self.lock();
+                    spec.share = protocol.into();
+                    spec.allowed_hosts = allowed_hosts.clone();
+                }
+                nexus_state.share = protocol.into();
+                nexus_state.allowed_hosts = allowed_hosts;
+                nexus_state.device_uri = device_uri;
+                Ok(nexus_state)
+            }
+            Err(error) => {
+                // If we fail to do this inline, the garbage collector will pick up the slack.
+                node.destroy_nexus(&DestroyNexus::from(nexus_state).with_disown_all())
+                    .await
+                    .ok();
+                Err(error)
+            }
+        }
+    }
+
     async fn create_nexus(
         &self,
         registry: &Registry,
@@ -536,7 +645,11 @@ impl OperationGuardArc<NexusSpec> {
             return Err(SvcError::NoOnlineReplicas { id: nexus.name });
         }
 
-        let nexus_state = match node.create_nexus(&CreateNexus::from(&nexus)).await {
+        let qos = registry.specs().nexus_owner_qos(&nexus);
+        let nexus_state = match node
+            .create_nexus(&CreateNexus::from(&nexus).with_qos(qos))
+            .await
+        {
             Ok(nexus_state) if nexus_state.io_online() => Ok(nexus_state),
             Ok(nexus_state) => {
                 nexus.warn_span(|| {
@@ -558,10 +671,29 @@ impl OperationGuardArc<NexusSpec> {
         self.info_span(|| tracing::info!("Nexus successfully recreated"));
         // todo: would be good if nexus create also supported publish/share..
         if nexus_state.share != nexus.share {
-            node.share_nexus(&ShareNexus::from(&nexus)).await?;
+            let request = with_encryption_key(registry, &ShareNexus::from(&nexus)).await?;
+            node.share_nexus(&request).await?;
             self.info_span(|| tracing::info!("Nexus protocol changed successfully"));
         }
 
         Ok(())
     }
 }
+
+/// Set the key which encrypts the volume owning the nexus, if the volume is encrypted.
+/// The key is resolved through its provider every time, as it's never stored.
+async fn with_encryption_key(
+    registry: &Registry,
+    request: &ShareNexus,
+) -> Result<ShareNexus, SvcError> {
+    let specs = registry.specs();
+    let mut request = request.clone();
+    let encryption = specs
+        .nexus_rsc(&request.uuid)
+        .map(|nexus| nexus.lock().clone())
+        .and_then(|nexus| specs.nexus_owner_encryption(&nexus));
+    if let (None, Some(key_ref)) = (&request.key, encryption) {
+        request.key = Some(registry.encryption_keys().resolve(&key_ref).await?);
+    }
+    Ok(request)
+}
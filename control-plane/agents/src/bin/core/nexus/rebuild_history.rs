@@ -0,0 +1,116 @@
+use crate::controller::{
+    registry::Registry,
+    resources::{operations_helper::ResourceSpecsLocked, ResourceMutex},
+};
+use agents::errors::SvcError;
+use stor_port::{
+    pstor::ObjectKey,
+    types::v0::{
+        store::rebuild_history::{
+            VolumeRebuildHistory, VolumeRebuildHistoryKey, VolumeRebuildRecord,
+        },
+        transport::{GetRebuildHistories, RebuildHistory, VolumeId},
+    },
+};
+
+impl ResourceSpecsLocked {
+    /// Get a copy of the persisted rebuild history of all volumes.
+    pub(crate) fn rebuild_histories(&self) -> Vec<VolumeRebuildHistory> {
+        let specs = self.read();
+        specs
+            .rebuild_histories
+            .values()
+            .map(|v| v.lock().clone())
+            .collect()
+    }
+
+    /// Get the persisted rebuild history of the given volume.
+    pub(crate) fn rebuild_history_rsc(
+        &self,
+        volume_id: &VolumeId,
+    ) -> Option<ResourceMutex<VolumeRebuildHistory>> {
+        let specs = self.read();
+        specs.rebuild_histories.get(volume_id).cloned()
+    }
+
+    /// Get the persisted rebuild histories, keeping only the records which match the request.
+    /// Volumes without any matching records are left out.
+    pub(crate) fn filtered_rebuild_histories(
+        &self,
+        request: &GetRebuildHistories,
+    ) -> Vec<VolumeRebuildHistory> {
+        self.rebuild_histories()
+            .into_iter()
+            .filter(|history| {
+                request
+                    .volume
+                    .as_ref()
+                    .map_or(true, |volume| volume == &history.volume_id)
+            })
+            .filter_map(|mut history| {
+                history.records.retain(|record| request.matches(record));
+                (!history.records.is_empty()).then_some(history)
+            })
+            .collect()
+    }
+
+    /// Get the existing volume which owns the nexus with the given rebuild history.
+    /// The nexus spec may already be gone, in which case we fall back to the nexus name which
+    /// is the uuid of the volume it belonged to.
+    pub(crate) fn rebuild_history_volume(&self, history: &RebuildHistory) -> Option<VolumeId> {
+        let owner = self
+            .nexus_rsc(&history.uuid)
+            .and_then(|nexus| nexus.lock().owner.clone());
+        let volume_id = match owner {
+            Some(owner) => owner,
+            None => VolumeId::try_from(history.name.as_str()).ok()?,
+        };
+        // the volume may have been deleted, in which case its history is not recorded.
+        self.volume_rsc(&volume_id).map(|_| volume_id)
+    }
+
+    /// Merge the rebuild records into the persisted rebuild history of the volume, applying
+    /// the registry's retention policy.
+    /// The store is only updated if the history has been modified.
+    pub(crate) async fn record_rebuild_history(
+        &self,
+        registry: &Registry,
+        volume_id: &VolumeId,
+        records: Vec<VolumeRebuildRecord>,
+    ) -> Result<(), SvcError> {
+        let mut history = match self.rebuild_history_rsc(volume_id) {
+            Some(history) => history.lock().clone(),
+            None if records.is_empty() => return Ok(()),
+            None => VolumeRebuildHistory::new(volume_id.clone()),
+        };
+        if !history.merge(records, registry.rebuild_history_retention()) {
+            return Ok(());
+        }
+        registry.store_obj(&history).await?;
+
+        let mut specs = self.write();
+        match specs.rebuild_histories.get(volume_id) {
+            Some(spec) => {
+                *spec.lock() = history;
+            }
+            None => {
+                specs.rebuild_histories.insert(history);
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete the persisted rebuild history of the volume.
+    pub(crate) async fn delete_rebuild_history(
+        &self,
+        registry: &Registry,
+        volume_id: &VolumeId,
+    ) -> Result<(), SvcError> {
+        registry
+            .delete_kv(&VolumeRebuildHistoryKey::from(volume_id).key())
+            .await?;
+        let mut specs = self.write();
+        specs.rebuild_histories.remove(volume_id);
+        Ok(())
+    }
+}
@@ -18,12 +18,16 @@ use grpc::{
     },
 };
 use stor_port::{
-    transport_api::{v0::Nexuses, ReplyError},
+    transport_api::{
+        v0::{Nexuses, VolumeRebuildHistories},
+        ReplyError,
+    },
     types::v0::{
         store::nexus::NexusSpec,
         transport::{
-            AddNexusChild, Child, CreateNexus, DestroyNexus, Filter, GetNexuses, GetRebuildRecord,
-            Nexus, RebuildHistory, RemoveNexusChild, ShareNexus, UnshareNexus,
+            AddNexusChild, Child, CreateNexus, DestroyNexus, Filter, GetNexuses,
+            GetRebuildHistories, GetRebuildRecord, Nexus, RebuildHistory, RemoveNexusChild,
+            ShareNexus, UnshareNexus,
         },
     },
 };
@@ -121,6 +125,15 @@ impl NexusOperations for Service {
                 .await??;
         Ok(records)
     }
+
+    async fn get_rebuild_histories(
+        &self,
+        req: &GetRebuildHistories,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeRebuildHistories, ReplyError> {
+        let histories = self.get_rebuild_histories(req).await?;
+        Ok(histories)
+    }
 }
 
 impl Service {
@@ -212,4 +225,22 @@ impl Service {
                 nexus_id: request.nexus.to_string(),
             })
     }
+
+    /// Gets the persisted rebuild history of the volumes.
+    #[tracing::instrument(level = "debug", skip(self), err)]
+    pub(super) async fn get_rebuild_histories(
+        &self,
+        request: &GetRebuildHistories,
+    ) -> Result<VolumeRebuildHistories, SvcError> {
+        if let Some(volume_id) = &request.volume {
+            self.specs()
+                .volume_rsc(volume_id)
+                .ok_or(SvcError::VolumeNotFound {
+                    vol_id: volume_id.to_string(),
+                })?;
+        }
+        Ok(VolumeRebuildHistories(
+            self.specs().filtered_rebuild_histories(request),
+        ))
+    }
 }
@@ -51,11 +51,18 @@ impl SpecOperationsHelper for NexusSpec {
         op: Self::UpdateOp,
     ) -> Result<(), SvcError> {
         match &op {
-            NexusOperation::Share(_, _) if state.share.shared() => Err(SvcError::AlreadyShared {
-                kind: ResourceKind::Nexus,
-                id: self.uuid_str(),
-                share: state.share.to_string(),
-            }),
+            // the allowed hosts of a shared nexus may be updated, example: when a read-only
+            // volume target is published to another frontend node
+            NexusOperation::Share(protocol, allowed_hosts)
+                if state.share.shared()
+                    && (protocol != &state.share || allowed_hosts == &self.allowed_hosts) =>
+            {
+                Err(SvcError::AlreadyShared {
+                    kind: ResourceKind::Nexus,
+                    id: self.uuid_str(),
+                    share: state.share.to_string(),
+                })
+            }
             NexusOperation::Share(_, _) => Ok(()),
             NexusOperation::Unshare if !state.share.shared() => Err(SvcError::NotShared {
                 kind: ResourceKind::Nexus,
@@ -30,8 +30,8 @@ use stor_port::{
             NexusChildAction, NexusChildActionContext, NexusChildActionKind, NexusId, NodeId,
             NodeState, NodeStatus, PoolId, PoolState, RebuildHistory, Register, RemoveNexusChild,
             Replica, ReplicaId, ReplicaName, ReplicaSnapshot, ResizeNexus, ResizeReplica,
-            SetReplicaEntityId, ShareNexus, ShareReplica, ShutdownNexus, SnapshotId, UnshareNexus,
-            UnshareReplica, VolumeId,
+            SetNexusQos, SetReplicaEntityId, ShareNexus, ShareReplica, ShutdownNexus, SnapshotId,
+            UnshareNexus, UnshareReplica, VolumeId,
         },
     },
 };
@@ -570,6 +570,11 @@ impl NodeWrapper {
             .map(|rs| rs.inner().clone())
     }
 
+    /// Gets the rebuild history of all nexuses from the resource map.
+    pub(crate) fn rebuild_histories(&self) -> Vec<RebuildHistory> {
+        self.resources().rebuild_histories_cloned()
+    }
+
     /// Get nexus for the given volume.
     fn volume_nexus(&self, volume_id: &VolumeId) -> Option<Nexus> {
         self.resources()
@@ -1450,6 +1455,17 @@ impl NexusApi<()> for Arc<tokio::sync::RwLock<NodeWrapper>> {
         match result {
             Ok(nexus) => {
                 self.update_nexus_state(Either::Insert(nexus.clone())).await;
+                if let Some(qos) = request.set_qos_request() {
+                    if let Err(error) = dataplane.set_nexus_qos(&qos).await {
+                        // don't leave behind a nexus which is not enforcing the IO limits
+                        let destroy = DestroyNexus::new(request.node.clone(), request.uuid.clone());
+                        if dataplane.destroy_nexus(&destroy).await.is_ok() {
+                            self.update_nexus_state(Either::Remove(request.uuid.clone()))
+                                .await;
+                        }
+                        return Err(error);
+                    }
+                }
                 Ok(nexus)
             }
             Err(error) => {
@@ -1527,6 +1543,11 @@ impl NexusApi<()> for Arc<tokio::sync::RwLock<NodeWrapper>> {
         self.update_nexus_state(Either::Insert(nexus.clone())).await;
         Ok(nexus)
     }
+    /// Set the IO limits of a nexus on the node via gRPC.
+    async fn set_nexus_qos(&self, request: &SetNexusQos) -> Result<(), SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        dataplane.set_nexus_qos(request).await
+    }
 }
 
 #[async_trait]
@@ -2,7 +2,7 @@ use crate::controller::{
     io_engine::PoolApi,
     registry::Registry,
     resources::{
-        operations::ResourceLifecycle,
+        operations::{ResourceCordon, ResourceLifecycle},
         operations_helper::{GuardedOperationsHelper, OnCreateFail, OperationSequenceGuard},
         OperationGuardArc,
     },
@@ -11,8 +11,8 @@ use agents::errors::{SvcError, SvcError::CordonedNode};
 use stor_port::{
     transport_api::ResourceKind,
     types::v0::{
-        store::pool::PoolSpec,
-        transport::{CreatePool, CtrlPoolState, DestroyPool, Pool},
+        store::pool::{PoolEvacuationProgress, PoolOperation, PoolSpec},
+        transport::{CreatePool, CtrlPoolState, DestroyPool, Pool, PoolState},
     },
 };
 
@@ -135,3 +135,67 @@ impl ResourceLifecycle for Option<OperationGuardArc<PoolSpec>> {
         }
     }
 }
+
+/// Resource Cordon Operations.
+#[async_trait::async_trait]
+impl ResourceCordon for OperationGuardArc<PoolSpec> {
+    type CordonOutput = PoolSpec;
+    type UncordonOutput = PoolSpec;
+
+    /// Cordon a pool via operation guard functions.
+    async fn cordon(
+        &mut self,
+        registry: &Registry,
+        label: String,
+    ) -> Result<Self::CordonOutput, SvcError> {
+        self.update(registry, PoolOperation::Cordon(label)).await
+    }
+
+    /// Uncordon a pool via operation guard functions.
+    /// Removing the evacuation label cancels the evacuation.
+    async fn uncordon(
+        &mut self,
+        registry: &Registry,
+        label: String,
+    ) -> Result<Self::UncordonOutput, SvcError> {
+        self.update(registry, PoolOperation::Uncordon(label)).await
+    }
+}
+
+/// Pool evacuation Operations.
+impl OperationGuardArc<PoolSpec> {
+    /// Start evacuating the pool, cordoning it with the given label.
+    /// The pool evacuation reconciler moves the volume replicas off the pool.
+    pub(crate) async fn evacuate(
+        &mut self,
+        registry: &Registry,
+        label: String,
+    ) -> Result<PoolSpec, SvcError> {
+        self.update(registry, PoolOperation::Evacuate(label)).await
+    }
+
+    /// Record the number of volume replicas which remain to be moved off the pool, and how many
+    /// of them cannot be moved yet.
+    pub(crate) async fn set_evacuation_progress(
+        &mut self,
+        registry: &Registry,
+        progress: PoolEvacuationProgress,
+    ) -> Result<PoolSpec, SvcError> {
+        self.update(registry, PoolOperation::SetEvacuationProgress(progress))
+            .await
+    }
+
+    /// Update the pool spec with the given operation.
+    /// These operations only affect the control-plane, so the pool state is not required.
+    async fn update(
+        &mut self,
+        registry: &Registry,
+        operation: PoolOperation,
+    ) -> Result<PoolSpec, SvcError> {
+        let state = PoolState::from(self.as_ref());
+        let spec_clone = self.start_update(registry, &state, operation).await?;
+
+        self.complete_update(registry, Ok(()), spec_clone).await?;
+        Ok(self.as_ref().clone())
+    }
+}
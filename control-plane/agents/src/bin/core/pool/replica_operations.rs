@@ -6,11 +6,15 @@ use crate::controller::{
             ResourceLifecycle, ResourceLifecycleExt, ResourceOffspring, ResourceOwnerUpdate,
             ResourceResize, ResourceSharing,
         },
-        operations_helper::{GuardedOperationsHelper, OnCreateFail, OperationSequenceGuard},
+        operations_helper::{
+            GuardedOperationsHelper, LinkedTxn, OnCreateFail, OperationSequenceGuard,
+        },
         OperationGuardArc, UpdateInnerValue,
     },
+    wrapper::NodeWrapper,
 };
 use agents::errors::{SvcError, SvcError::CordonedNode};
+use std::sync::Arc;
 use stor_port::types::v0::{
     store::{
         nexus::NexusSpec,
@@ -32,21 +36,7 @@ impl ResourceLifecycle for OperationGuardArc<ReplicaSpec> {
         registry: &Registry,
         request: &Self::Create,
     ) -> Result<Self::CreateOutput, SvcError> {
-        let specs = registry.specs();
-
-        if registry.node_cordoned(&request.node)? {
-            return Err(CordonedNode {
-                node_id: request.node.to_string(),
-            });
-        }
-
-        let node = registry.node_wrapper(&request.node).await?;
-
-        let mut replica = specs
-            .get_or_create_replica(request)
-            .operation_guard_wait()
-            .await?;
-        let _ = replica.start_create(registry, request).await?;
+        let (mut replica, node) = Self::start_create_replica(registry, request).await?;
 
         let result = node.create_replica(request).await;
         let on_fail = OnCreateFail::eeinval_delete(&result);
@@ -88,11 +78,7 @@ impl ResourceLifecycle for Option<&mut OperationGuardArc<ReplicaSpec>> {
                 .start_destroy_by(registry, &request.disowners)
                 .await?;
 
-            let result = match node.destroy_replica(request).await {
-                Ok(()) => Ok(()),
-                Err(error) if error.tonic_code() == tonic::Code::NotFound => Ok(()),
-                Err(error) => Err(error),
-            };
+            let result = destroy_replica(&node, request).await;
             replica.complete_destroy(result, registry).await
         } else {
             node.destroy_replica(request).await
@@ -228,6 +214,28 @@ impl ResourceOwnerUpdate for OperationGuardArc<ReplicaSpec> {
 }
 
 impl OperationGuardArc<ReplicaSpec> {
+    /// Starts removing the given owners from this replica, linked to the update operation of
+    /// the owner itself, so both are committed to the persistent store atomically.
+    /// See `GuardedOperationsHelper::start_linked_update`.
+    pub(crate) async fn start_linked_remove_owners(
+        &self,
+        registry: &Registry,
+        request: &ReplicaOwners,
+        linked: &mut LinkedTxn,
+    ) -> Result<(), SvcError> {
+        // we don't really need the state, this is a configuration-only change.
+        let state = Default::default();
+        let mut current = self.lock().owners.clone();
+        current.disown(request);
+        self.start_linked_update(
+            registry,
+            &state,
+            ReplicaOperation::OwnerUpdate(current),
+            linked,
+        )
+        .await
+    }
+
     /// Faults this replica.
     /// The replica is first removed from the nexus, which will let us know if it's safe to destroy
     /// it.
@@ -301,26 +309,104 @@ impl ResourceLifecycleExt<SnapshotCloneSpecParams> for OperationGuardArc<Replica
         registry: &Registry,
         request: &SnapshotCloneSpecParams,
     ) -> Result<Self::CreateOutput, SvcError> {
-        let specs = registry.specs();
+        let create_replica = CreateReplica::from(request);
+        let (mut replica, node) = Self::start_create_replica(registry, &create_replica).await?;
+
+        let result = node.create_snapshot_clone(request.params()).await;
+        let on_fail = OnCreateFail::eeinval_delete(&result);
+
+        replica.complete_create(result, registry, on_fail).await
+    }
+}
+
+impl OperationGuardArc<ReplicaSpec> {
+    /// Creates a replica linked to the operation of its owner, so the replica spec is committed
+    /// to the persistent store atomically with the owner's spec.
+    /// See `GuardedOperationsHelper::complete_create_linked`.
+    pub(crate) async fn create_linked(
+        registry: &Registry,
+        request: &CreateReplica,
+        linked: &mut LinkedTxn,
+    ) -> Result<(Self, Replica), SvcError> {
+        let (mut replica, node) = Self::start_create_replica(registry, request).await?;
+
+        let result = node.create_replica(request).await;
+        let on_fail = OnCreateFail::eeinval_delete(&result);
+
+        let state = replica
+            .complete_create_linked(result, registry, on_fail, linked)
+            .await?;
+        Ok((replica, state))
+    }
+
+    /// Creates a replica from a snapshot, linked to the operation of its owner, so the replica
+    /// spec is committed to the persistent store atomically with the owner's spec.
+    /// See `GuardedOperationsHelper::complete_create_linked`.
+    pub(crate) async fn create_ext_linked(
+        registry: &Registry,
+        request: &SnapshotCloneSpecParams,
+        linked: &mut LinkedTxn,
+    ) -> Result<(Self, Replica), SvcError> {
+        let create_replica = CreateReplica::from(request);
+        let (mut replica, node) = Self::start_create_replica(registry, &create_replica).await?;
+
+        let result = node.create_snapshot_clone(request.params()).await;
+        let on_fail = OnCreateFail::eeinval_delete(&result);
+
+        let state = replica
+            .complete_create_linked(result, registry, on_fail, linked)
+            .await?;
+        Ok((replica, state))
+    }
 
-        if registry.node_cordoned(request.node())? {
+    /// Destroys the replica, linked to the operation of its owner, so the replica spec is deleted
+    /// from the persistent store atomically with the owner's spec update.
+    /// See `GuardedOperationsHelper::complete_destroy_linked`.
+    pub(crate) async fn destroy_linked(
+        &mut self,
+        registry: &Registry,
+        request: &DestroyReplica,
+        linked: &mut LinkedTxn,
+    ) -> Result<(), SvcError> {
+        let node = registry.node_wrapper(&request.node).await?;
+
+        self.start_destroy_by(registry, &request.disowners).await?;
+
+        let result = destroy_replica(&node, request).await;
+        self.complete_destroy_linked(result, registry, linked).await
+    }
+
+    /// Checks that the replica can be created on the requested node and starts its creation.
+    async fn start_create_replica(
+        registry: &Registry,
+        request: &CreateReplica,
+    ) -> Result<(Self, Arc<tokio::sync::RwLock<NodeWrapper>>), SvcError> {
+        if registry.node_cordoned(&request.node)? {
             return Err(CordonedNode {
-                node_id: request.node().to_string(),
+                node_id: request.node.to_string(),
             });
         }
 
-        let node = registry.node_wrapper(request.node()).await?;
+        let node = registry.node_wrapper(&request.node).await?;
 
-        let create_replica = CreateReplica::from(request);
-        let mut replica = specs
-            .get_or_create_replica(&create_replica)
+        let replica = registry
+            .specs()
+            .get_or_create_replica(request)
             .operation_guard_wait()
             .await?;
-        let _ = replica.start_create(registry, &create_replica).await?;
-
-        let result = node.create_snapshot_clone(request.params()).await;
-        let on_fail = OnCreateFail::eeinval_delete(&result);
+        let _ = replica.start_create(registry, request).await?;
+        Ok((replica, node))
+    }
+}
 
-        replica.complete_create(result, registry, on_fail).await
+/// Destroys the replica on the given node, which is not an error if it no longer exists.
+async fn destroy_replica(
+    node: &Arc<tokio::sync::RwLock<NodeWrapper>>,
+    request: &DestroyReplica,
+) -> Result<(), SvcError> {
+    match node.destroy_replica(request).await {
+        Ok(()) => Ok(()),
+        Err(error) if error.tonic_code() == tonic::Code::NotFound => Ok(()),
+        Err(error) => Err(error),
     }
 }
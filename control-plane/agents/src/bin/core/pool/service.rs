@@ -1,10 +1,11 @@
 use crate::controller::{
     registry::Registry,
     resources::{
-        operations::{ResourceLifecycle, ResourceResize, ResourceSharing},
+        operations::{ResourceCordon, ResourceLifecycle, ResourceResize, ResourceSharing},
         operations_helper::{OperationSequenceGuard, ResourceSpecsLocked},
         OperationGuardArc, ResourceMutex,
     },
+    scheduling::rebalance::replica_moves,
     wrapper::GetterOps,
 };
 use agents::errors::{PoolNotFound, ReplicaNotFound, SvcError};
@@ -20,14 +21,15 @@ use grpc::{
 };
 use stor_port::{
     transport_api::{
-        v0::{Pools, Replicas},
+        v0::{Pools, ReplicaMoves, Replicas},
         ReplyError,
     },
     types::v0::{
         store::{pool::PoolSpec, replica::ReplicaSpec},
         transport::{
-            CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetPools, GetReplicas,
-            NodeId, Pool, PoolId, Replica, ResizeReplica, ShareReplica, UnshareReplica,
+            CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetPoolRebalance,
+            GetPools, GetReplicas, NodeId, Pool, PoolId, Replica, ResizeReplica, ShareReplica,
+            UnshareReplica,
         },
     },
 };
@@ -68,6 +70,34 @@ impl PoolOperations for Service {
         let pools = self.get_pools(&req).await?;
         Ok(pools)
     }
+
+    async fn get_rebalance(
+        &self,
+        req: &GetPoolRebalance,
+        _ctx: Option<Context>,
+    ) -> Result<ReplicaMoves, ReplyError> {
+        let moves = self.get_pool_rebalance(req).await?;
+        Ok(moves)
+    }
+
+    /// Apply a cordon label to the specified pool.
+    async fn cordon(&self, id: PoolId, label: String) -> Result<Pool, ReplyError> {
+        let pool = self.cordon_pool(id, label).await?;
+        Ok(pool)
+    }
+
+    /// Remove a cordon label from the specified pool.
+    async fn uncordon(&self, id: PoolId, label: String) -> Result<Pool, ReplyError> {
+        let pool = self.uncordon_pool(id, label).await?;
+        Ok(pool)
+    }
+
+    /// Apply an evacuation label to the specified pool. The reconciler will perform the
+    /// evacuation.
+    async fn evacuate(&self, id: PoolId, label: String) -> Result<Pool, ReplyError> {
+        let pool = self.evacuate_pool(id, label).await?;
+        Ok(pool)
+    }
 }
 
 #[tonic::async_trait]
@@ -187,6 +217,16 @@ impl Service {
         Ok(Pools(pools))
     }
 
+    /// Get the replica moves which the pool rebalancer would make, even if it's not enabled.
+    #[tracing::instrument(level = "debug", skip(self), err)]
+    pub(super) async fn get_pool_rebalance(
+        &self,
+        _request: &GetPoolRebalance,
+    ) -> Result<ReplicaMoves, SvcError> {
+        let threshold = self.registry.pool_rebalance_args().pool_rebalance_threshold;
+        Ok(ReplicaMoves(replica_moves(&self.registry, threshold).await))
+    }
+
     /// Get replicas according to the filter
     #[tracing::instrument(level = "info", skip(self), err)]
     pub(super) async fn get_replicas(&self, request: &GetReplicas) -> Result<Replicas, SvcError> {
@@ -284,6 +324,31 @@ impl Service {
         pool.destroy(&self.registry, request).await
     }
 
+    /// Cordon the specified pool, preventing new replicas from being placed on it.
+    #[tracing::instrument(level = "info", skip(self), err, fields(pool.id = %id))]
+    pub(super) async fn cordon_pool(&self, id: PoolId, label: String) -> Result<Pool, SvcError> {
+        let mut pool = self.specs().guarded_pool(&id).await?;
+        pool.cordon(&self.registry, label).await?;
+        self.registry.ctrl_pool(&id).await
+    }
+
+    /// Uncordon the specified pool, cancelling its evacuation if the label is the evacuation
+    /// label.
+    #[tracing::instrument(level = "info", skip(self), err, fields(pool.id = %id))]
+    pub(super) async fn uncordon_pool(&self, id: PoolId, label: String) -> Result<Pool, SvcError> {
+        let mut pool = self.specs().guarded_pool(&id).await?;
+        pool.uncordon(&self.registry, label).await?;
+        self.registry.ctrl_pool(&id).await
+    }
+
+    /// Start evacuating the specified pool.
+    #[tracing::instrument(level = "info", skip(self), err, fields(pool.id = %id))]
+    pub(super) async fn evacuate_pool(&self, id: PoolId, label: String) -> Result<Pool, SvcError> {
+        let mut pool = self.specs().guarded_pool(&id).await?;
+        pool.evacuate(&self.registry, label).await?;
+        self.registry.ctrl_pool(&id).await
+    }
+
     /// Create a replica using the given parameters.
     #[tracing::instrument(level = "info", skip(self), err, fields(replica.uuid = %request.uuid))]
     pub(super) async fn create_replica(
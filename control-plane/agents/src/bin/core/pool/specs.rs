@@ -30,7 +30,7 @@ impl GuardedOperationsHelper for OperationGuardArc<PoolSpec> {
     type Owners = ();
     type Status = PoolStatus;
     type State = PoolState;
-    type UpdateOp = ();
+    type UpdateOp = PoolOperation;
     type Inner = PoolSpec;
 
     fn validate_destroy(&self, registry: &Registry) -> Result<(), SvcError> {
@@ -60,8 +60,52 @@ impl SpecOperationsHelper for PoolSpec {
     type Owners = ();
     type Status = PoolStatus;
     type State = PoolState;
-    type UpdateOp = ();
+    type UpdateOp = PoolOperation;
 
+    async fn start_update_op(
+        &mut self,
+        _: &Registry,
+        _state: &Self::State,
+        op: Self::UpdateOp,
+    ) -> Result<(), SvcError> {
+        match &op {
+            PoolOperation::Cordon(label) => {
+                // Do not allow the same label to be applied more than once.
+                if self.has_cordon_label(label) {
+                    return Err(SvcError::PoolCordonLabel {
+                        pool_id: self.id.to_string(),
+                        label: label.clone(),
+                    });
+                }
+            }
+            PoolOperation::Evacuate(label) => {
+                if let Some(evacuation) = &self.evacuation {
+                    return Err(SvcError::PoolEvacuationInProgress {
+                        pool_id: self.id.to_string(),
+                        label: evacuation.label.clone(),
+                    });
+                }
+                if self.has_cordon_label(label) {
+                    return Err(SvcError::PoolCordonLabel {
+                        pool_id: self.id.to_string(),
+                        label: label.clone(),
+                    });
+                }
+            }
+            PoolOperation::Uncordon(label) => {
+                // Check that the label is present.
+                if !self.has_cordon_label(label) {
+                    return Err(SvcError::PoolUncordonLabel {
+                        pool_id: self.id.to_string(),
+                        label: label.clone(),
+                    });
+                }
+            }
+            _ => {}
+        }
+        self.start_op(op);
+        Ok(())
+    }
     fn start_create_op(&mut self, _request: &Self::Create) {
         self.start_op(PoolOperation::Create);
     }
@@ -295,6 +339,18 @@ impl ResourceSpecsLocked {
         let specs = self.read();
         specs.pools.get(id).cloned()
     }
+    /// Get the guarded PoolSpec for the given pool `id`.
+    pub(crate) async fn guarded_pool(
+        &self,
+        id: &PoolId,
+    ) -> Result<OperationGuardArc<PoolSpec>, SvcError> {
+        match self.pool_rsc(id) {
+            None => Err(PoolNotFound {
+                pool_id: id.to_owned(),
+            }),
+            Some(pool) => pool.operation_guard_wait().await,
+        }
+    }
     /// Get a PoolSpec for the given pool `id`, if it exists.
     pub(crate) fn pool(&self, id: &PoolId) -> Result<PoolSpec, SvcError> {
         let specs = self.read();
@@ -0,0 +1,246 @@
+use crate::controller::{registry::Registry, resources::migration::migrate_product_v1_value};
+use agents::errors::SvcError;
+use serde::de::DeserializeOwned;
+use std::{collections::HashSet, str::FromStr};
+use stor_port::{
+    pstor::{key_prefix, ApiVersion, StorableObjectType, Store, StoreTxnRequest, API_VERSION},
+    types::v0::{
+        store::{
+            app_node::AppNodeSpec,
+            definitions::StoreError,
+            nexus::NexusSpec,
+            nexus_persistence::NexusInfo,
+            node::NodeSpec,
+            pool::PoolSpec,
+            rebuild_history::VolumeRebuildHistory,
+            registry::CoreRegistryConfig,
+            replica::ReplicaSpec,
+            snapshots::{
+                export::SnapshotExportSpec, group::SnapshotGroupSpec,
+                schedule::SnapshotScheduleSpec, volume::VolumeSnapshot,
+            },
+            switchover::SwitchOverSpec,
+            volume::VolumeSpec,
+        },
+        transport::{SpecsBackup, SpecsBackupEntry, SpecsRestored, SPECS_BACKUP_VERSION},
+    },
+};
+
+/// Take a backup of all the objects persisted by the control-plane.
+/// The lease and leader entries are left out as they're tied to the running instances.
+pub(super) async fn backup_specs(registry: &Registry) -> Result<SpecsBackup, SvcError> {
+    let prefix = format!("{}/", key_prefix(API_VERSION));
+    let store_entries = {
+        let mut store = registry.store().lock().await;
+        store
+            .get_values_paged_all(&prefix, registry.etcd_max_page_size())
+            .await?
+    };
+
+    let mut entries = Vec::with_capacity(store_entries.len());
+    for (key, value) in store_entries {
+        let Some(key) = key.strip_prefix(&prefix) else {
+            continue;
+        };
+        let entry = SpecsBackupEntry {
+            key: key.to_string(),
+            value,
+        };
+        if matches!(
+            entry.kind(),
+            Some(
+                StorableObjectType::StoreLeaseLock
+                    | StorableObjectType::StoreLeaseOwner
+                    | StorableObjectType::HaClusterLeader
+            )
+        ) {
+            continue;
+        }
+        entries.push(entry);
+    }
+
+    tracing::info!(entries = entries.len(), "Taken a backup of the specs");
+    Ok(SpecsBackup {
+        version: SPECS_BACKUP_VERSION,
+        api_version: API_VERSION.to_string(),
+        timestamp: chrono::Utc::now(),
+        entries,
+    })
+}
+
+/// Restore all the objects from the given backup into the persistent store and load them.
+/// The store must not contain any resources yet, except for the ones which get created as the
+/// cluster comes up, such as the nodes, which are left untouched.
+/// All entries are written in a single store transaction, so either the whole backup is restored
+/// or nothing is; the store must therefore allow as many operations per transaction as there are
+/// entries to restore (etcd's `--max-txn-ops`).
+pub(super) async fn restore_specs(
+    registry: &Registry,
+    backup: SpecsBackup,
+) -> Result<SpecsRestored, SvcError> {
+    let timestamp = backup.timestamp;
+    let entries = validate_backup(backup)?;
+
+    let prefix = format!("{}/", key_prefix(API_VERSION));
+    let etcd_max_page_size = registry.etcd_max_page_size();
+    // hold the store lock throughout so that no other resources get persisted in the meantime
+    let mut store = registry.store().lock().await;
+
+    let existing = store
+        .get_values_paged_all(&prefix, etcd_max_page_size)
+        .await?
+        .into_iter()
+        .filter_map(|(key, _)| key.strip_prefix(&prefix).map(ToString::to_string))
+        .collect::<HashSet<_>>();
+    if let Some(key) = existing.iter().find(|key| {
+        !SpecsBackupEntry::key_kind(key)
+            .map(bootstrap_kind)
+            .unwrap_or_default()
+    }) {
+        return Err(SvcError::SpecsRestoreStoreNotEmpty {
+            details: format!("found entry with key '{prefix}{key}'"),
+        });
+    }
+    if registry.specs().has_resources() {
+        return Err(SvcError::SpecsRestoreStoreNotEmpty {
+            details: "resources are already loaded".to_string(),
+        });
+    }
+
+    let mut restored = SpecsRestored::default();
+    let mut txn = StoreTxnRequest::new();
+    for entry in entries {
+        if existing.contains(&entry.key) {
+            restored.skipped += 1;
+            continue;
+        }
+        let key = format!("{prefix}{}", entry.key);
+        // don't overwrite any entry which may have been created since we've listed the store
+        txn.when_revision(&key, 0);
+        txn.put_kv(&key, &entry.value)?;
+        restored.restored += 1;
+    }
+    if restored.restored > 0 {
+        store.txn(txn).await.map_err(|error| match error {
+            StoreError::TxnCompare { keys } => SvcError::SpecsRestoreStoreNotEmpty {
+                details: format!("entries {keys:?} have been created meanwhile"),
+            },
+            error => error.into(),
+        })?;
+    }
+
+    registry
+        .specs()
+        .reload(&mut *store, etcd_max_page_size)
+        .await?;
+
+    tracing::info!(
+        restored = restored.restored,
+        skipped = restored.skipped,
+        "Restored the specs from a backup taken at {timestamp}"
+    );
+    Ok(restored)
+}
+
+/// Kinds of objects which are persisted as the cluster comes up, before any resource is created.
+fn bootstrap_kind(kind: StorableObjectType) -> bool {
+    matches!(
+        kind,
+        StorableObjectType::NodeSpec
+            | StorableObjectType::AppNodeSpec
+            | StorableObjectType::CoreRegistryConfig
+            | StorableObjectType::StoreLeaseLock
+            | StorableObjectType::StoreLeaseOwner
+            | StorableObjectType::HaClusterLeader
+    )
+}
+
+/// Validate the backup and migrate its entries to the current api version.
+fn validate_backup(backup: SpecsBackup) -> Result<Vec<SpecsBackupEntry>, SvcError> {
+    if backup.version == 0 || backup.version > SPECS_BACKUP_VERSION {
+        return Err(SvcError::InvalidSpecsBackup {
+            details: format!("unsupported backup version {}", backup.version),
+        });
+    }
+    // entries from any known api version are migrated to the current one as they're validated
+    let api_version =
+        ApiVersion::from_str(&backup.api_version).map_err(|_| SvcError::InvalidSpecsBackup {
+            details: format!("unsupported api version '{}'", backup.api_version),
+        })?;
+    if api_version.to_string() != API_VERSION.to_string() {
+        tracing::info!(
+            "Migrating the specs backup from api version '{api_version}' to '{API_VERSION}'"
+        );
+    }
+
+    let mut keys = HashSet::with_capacity(backup.entries.len());
+    let mut entries = Vec::with_capacity(backup.entries.len());
+    for entry in backup.entries {
+        if entry.key.is_empty() || entry.key.split('/').any(str::is_empty) {
+            return Err(SvcError::InvalidSpecsBackup {
+                details: format!("invalid key '{}'", entry.key),
+            });
+        }
+        if !keys.insert(entry.key.clone()) {
+            return Err(SvcError::InvalidSpecsBackup {
+                details: format!("duplicate key '{}'", entry.key),
+            });
+        }
+        let Some(kind) = entry.kind() else {
+            return Err(SvcError::InvalidSpecsBackup {
+                details: format!("unknown object type for key '{}'", entry.key),
+            });
+        };
+        let value = validate_value(kind, &entry.key, entry.value)?;
+        entries.push(SpecsBackupEntry {
+            key: entry.key,
+            value,
+        });
+    }
+    Ok(entries)
+}
+
+/// Check that the value can be loaded as its object type and migrate it to the latest.
+fn validate_value(
+    kind: StorableObjectType,
+    key: &str,
+    value: serde_json::Value,
+) -> Result<serde_json::Value, SvcError> {
+    let value =
+        migrate_product_v1_value(kind, value).map_err(|error| SvcError::InvalidSpecsBackup {
+            details: format!("failed to migrate key '{key}': {error}"),
+        })?;
+    match kind {
+        StorableObjectType::VolumeSpec => check_value::<VolumeSpec>(key, &value),
+        StorableObjectType::NodeSpec => check_value::<NodeSpec>(key, &value),
+        StorableObjectType::NexusSpec => check_value::<NexusSpec>(key, &value),
+        StorableObjectType::PoolSpec => check_value::<PoolSpec>(key, &value),
+        StorableObjectType::ReplicaSpec => check_value::<ReplicaSpec>(key, &value),
+        StorableObjectType::VolumeSnapshot => check_value::<VolumeSnapshot>(key, &value),
+        StorableObjectType::AppNodeSpec => check_value::<AppNodeSpec>(key, &value),
+        StorableObjectType::SnapshotScheduleSpec => {
+            check_value::<SnapshotScheduleSpec>(key, &value)
+        }
+        StorableObjectType::VolumeRebuildHistory => {
+            check_value::<VolumeRebuildHistory>(key, &value)
+        }
+        StorableObjectType::SnapshotGroupSpec => check_value::<SnapshotGroupSpec>(key, &value),
+        StorableObjectType::SnapshotExportSpec => check_value::<SnapshotExportSpec>(key, &value),
+        StorableObjectType::NexusInfo => check_value::<NexusInfo>(key, &value),
+        StorableObjectType::CoreRegistryConfig => check_value::<CoreRegistryConfig>(key, &value),
+        StorableObjectType::SwitchOver => check_value::<SwitchOverSpec>(key, &value),
+        StorableObjectType::WatchConfig => Ok(()),
+        _ => Err(SvcError::InvalidSpecsBackup {
+            details: format!("object type {kind} of key '{key}' cannot be restored"),
+        }),
+    }?;
+    Ok(value)
+}
+
+fn check_value<T: DeserializeOwned>(key: &str, value: &serde_json::Value) -> Result<(), SvcError> {
+    T::deserialize(value)
+        .map(|_| ())
+        .map_err(|error| SvcError::InvalidSpecsBackup {
+            details: format!("failed to deserialise key '{key}': {error}"),
+        })
+}
@@ -1,3 +1,4 @@
+mod backup;
 mod service;
 use crate::controller::registry::Registry;
 use agents::Service;
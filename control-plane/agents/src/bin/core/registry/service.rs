@@ -1,12 +1,17 @@
+use super::backup;
 use crate::{controller, controller::resources::operations_helper::ResourceSpecsLocked};
 use agents::errors::SvcError;
 use grpc::{
     context::Context,
-    operations::registry::traits::{GetSpecsInfo, GetStatesInfo, RegistryOperations},
+    operations::registry::traits::{
+        BackupSpecsInfo, GetSpecsInfo, GetStatesInfo, RegistryOperations, RestoreSpecsInfo,
+    },
 };
 use stor_port::{
     transport_api::ReplyError,
-    types::v0::transport::{GetSpecs, GetStates, Specs, States},
+    types::v0::transport::{
+        BackupSpecs, GetSpecs, GetStates, RestoreSpecs, Specs, SpecsBackup, SpecsRestored, States,
+    },
 };
 
 /// Registry Service
@@ -36,6 +41,27 @@ impl RegistryOperations for Service {
         let states = self.get_states(&req).await?;
         Ok(states)
     }
+
+    async fn backup_specs(
+        &self,
+        backup_specs: &dyn BackupSpecsInfo,
+        _ctx: Option<Context>,
+    ) -> Result<SpecsBackup, ReplyError> {
+        let req = backup_specs.into();
+        let backup = self.backup_specs(&req).await?;
+        Ok(backup)
+    }
+
+    async fn restore_specs(
+        &self,
+        restore_specs: &dyn RestoreSpecsInfo,
+        _ctx: Option<Context>,
+    ) -> Result<SpecsRestored, ReplyError> {
+        let req: RestoreSpecs = restore_specs.into();
+        let service = self.clone();
+        let restored = Context::spawn(async move { service.restore_specs(req).await }).await??;
+        Ok(restored)
+    }
 }
 
 impl Service {
@@ -81,4 +107,20 @@ impl Service {
             replicas,
         })
     }
+
+    /// Take a backup of all the specs from the persistent store.
+    pub(crate) async fn backup_specs(
+        &self,
+        _request: &BackupSpecs,
+    ) -> Result<SpecsBackup, SvcError> {
+        backup::backup_specs(&self.registry).await
+    }
+
+    /// Restore all the specs from a backup into the persistent store.
+    pub(crate) async fn restore_specs(
+        &self,
+        request: RestoreSpecs,
+    ) -> Result<SpecsRestored, SvcError> {
+        backup::restore_specs(&self.registry, request.backup).await
+    }
 }
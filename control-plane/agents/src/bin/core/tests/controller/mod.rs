@@ -1,10 +1,16 @@
 use deployer_cluster::{etcd_client::Client, *};
+use grpc::operations::registry::traits::RegistryOperations;
 use stor_port::{
-    pstor::{etcd::Etcd, key_prefix_obj, ApiVersion, StorableObjectType, StoreKv, StoreObj},
+    pstor::{
+        etcd::Etcd, key_prefix_obj, ApiVersion, Error as StoreError, StorableObjectType, StoreKv,
+        StoreObj, StoreTxn, StoreTxnRequest,
+    },
+    transport_api::ReplyErrorKind,
     types::v0::{
         openapi::models,
         store::registry::{ControlPlaneService, StoreLeaseOwner, StoreLeaseOwnerKey},
         transport,
+        transport::{BackupSpecs, GetSpecs, RestoreSpecs, SPECS_BACKUP_VERSION},
     },
 };
 
@@ -69,6 +75,122 @@ async fn bootstrap_registry() {
     assert_eq!(specs, restart_specs);
 }
 
+/// Test that the specs can be restored from a backup after they've been lost from the store.
+#[tokio::test]
+async fn backup_restore_specs() {
+    let size = 15 * 1024 * 1024;
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_pools(1)
+        .with_replicas(2, size, transport::Protocol::None)
+        .with_agents(vec!["core"])
+        // don't let the reconcile interfere with the tests
+        .with_reconcile_period(
+            std::time::Duration::from_secs(1000),
+            std::time::Duration::from_secs(1000),
+        )
+        .build()
+        .await
+        .unwrap();
+
+    let registry = cluster.grpc_client().registry();
+    let specs = registry.get_specs(&GetSpecs {}, None).await.unwrap();
+    assert_eq!(specs.replicas.len(), 2);
+
+    let backup = registry.backup_specs(&BackupSpecs {}, None).await.unwrap();
+    assert_eq!(backup.version, SPECS_BACKUP_VERSION);
+    let kinds = backup
+        .entries
+        .iter()
+        .map(|entry| entry.kind().map(|kind| kind.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds
+            .iter()
+            .filter(|kind| kind.as_deref() == Some("ReplicaSpec"))
+            .count(),
+        2
+    );
+    assert!(kinds.iter().any(|kind| kind.as_deref() == Some("PoolSpec")));
+    assert!(!kinds
+        .iter()
+        .any(|kind| kind.as_deref() == Some("StoreLeaseLock")));
+
+    // the store still has all the resources
+    let error = registry
+        .restore_specs(
+            &RestoreSpecs {
+                backup: backup.clone(),
+            },
+            None,
+        )
+        .await
+        .expect_err("The store is not empty");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    let mut unsupported = backup.clone();
+    unsupported.version = SPECS_BACKUP_VERSION + 1;
+    let error = registry
+        .restore_specs(
+            &RestoreSpecs {
+                backup: unsupported,
+            },
+            None,
+        )
+        .await
+        .expect_err("The backup version is not supported");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let mut unknown_api = backup.clone();
+    unknown_api.api_version = "v999".to_string();
+    let error = registry
+        .restore_specs(
+            &RestoreSpecs {
+                backup: unknown_api,
+            },
+            None,
+        )
+        .await
+        .expect_err("The api version is not known");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    // lose the pool and replica specs from the store
+    let mut etcd = Etcd::new("0.0.0.0:2379").await.unwrap();
+    for kind in [
+        StorableObjectType::PoolSpec,
+        StorableObjectType::ReplicaSpec,
+    ] {
+        etcd.delete_values_prefix(&key_prefix_obj(kind, ApiVersion::V0))
+            .await
+            .unwrap();
+    }
+    cluster.restart_core().await;
+    cluster
+        .volume_service_liveness(None)
+        .await
+        .expect("Should have restarted by now");
+    let lost_specs = registry.get_specs(&GetSpecs {}, None).await.unwrap();
+    assert!(lost_specs.pools.is_empty());
+    assert!(lost_specs.replicas.is_empty());
+
+    let restored = registry
+        .restore_specs(&RestoreSpecs { backup }, None)
+        .await
+        .unwrap();
+    assert_eq!(restored.restored, 3);
+    assert!(restored.skipped > 0);
+
+    let restored_specs = registry.get_specs(&GetSpecs {}, None).await.unwrap();
+    assert_eq!(specs.pools, restored_specs.pools);
+    assert_eq!(specs.replicas.len(), restored_specs.replicas.len());
+    for replica in &specs.replicas {
+        assert!(restored_specs
+            .replicas
+            .iter()
+            .any(|r| r.uuid == replica.uuid));
+    }
+}
+
 /// Test that store lease lock in the core agent works as expected
 #[tokio::test]
 async fn store_lease_lock() {
@@ -123,6 +245,73 @@ async fn store_lease_lock() {
     .expect_err("One core-agent is already running!");
 }
 
+/// Test that the etcd store transactions are only committed if all compares succeed and that all
+/// of their operations are committed atomically.
+#[tokio::test]
+async fn store_txn() {
+    // deploy etcd only...
+    let _cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_jaeger(false)
+        .with_io_engines(0)
+        .with_agents(vec![])
+        .build()
+        .await
+        .unwrap();
+
+    // the transactions of a leased store are also conditional on the lease lock
+    let mut store = Etcd::new_leased(
+        ["0.0.0.0:2379"],
+        ControlPlaneService::CoreAgent.to_string(),
+        std::time::Duration::from_secs(2),
+    )
+    .await
+    .unwrap();
+
+    let (a, b) = ("/txn/a", "/txn/b");
+    store.put_kv(&a, &1).await.unwrap();
+    let (_, revision) = store.get_kv_revision(&a).await.unwrap();
+
+    let mut txn = StoreTxnRequest::new();
+    txn.when_revision(&a, revision);
+    txn.put_kv(&a, &2).unwrap();
+    store.txn(txn.clone()).await.unwrap();
+    let error = store.txn(txn).await.unwrap_err();
+    assert!(matches!(error, StoreError::TxnCompare { .. }), "{error:?}");
+    let (value, new_revision) = store.get_kv_revision(&a).await.unwrap();
+    assert_eq!(value, Value::from(2));
+    assert!(new_revision > revision);
+
+    // the whole transaction fails if a single compare fails
+    let mut txn = StoreTxnRequest::new();
+    txn.when_revision(&a, new_revision);
+    txn.when_revision(&b, 0);
+    txn.put_kv(&a, &4).unwrap();
+    txn.put_kv(&b, &4).unwrap();
+    store.put_kv(&b, &0).await.unwrap();
+    let error = store.txn(txn.clone()).await.unwrap_err();
+    assert!(matches!(error, StoreError::TxnCompare { .. }), "{error:?}");
+    assert_eq!(store.get_kv(&a).await.unwrap(), Value::from(2));
+
+    store.delete_kv(&b).await.unwrap();
+    store.txn(txn).await.unwrap();
+    let (a_value, a_revision) = store.get_kv_revision(&a).await.unwrap();
+    let (b_value, b_revision) = store.get_kv_revision(&b).await.unwrap();
+    assert_eq!((a_value, b_value), (Value::from(4), Value::from(4)));
+    assert_eq!(
+        a_revision, b_revision,
+        "Both keys should be modified atomically"
+    );
+
+    let mut txn = StoreTxnRequest::new();
+    txn.when_revision(&b, b_revision);
+    txn.delete_kv(&a);
+    txn.delete_kv(&b);
+    store.txn(txn).await.unwrap();
+    store.get_kv(&a).await.unwrap_err();
+    store.get_kv(&b).await.unwrap_err();
+}
+
 /// Test that store lease lock works as expected
 #[tokio::test]
 async fn core_agent_lease_lock() {
@@ -152,6 +152,8 @@ fn test_deserialization_v1_to_v2() {
                 },
                 sequencer: Default::default(),
                 operation: None,
+                cordon_labels: vec![],
+                evacuation: None,
             }),
         },
         TestEntry {
@@ -4,6 +4,7 @@ use grpc::{
     operations::{
         node::traits::NodeOperations, pool::traits::PoolOperations,
         registry::traits::RegistryOperations, replica::traits::ReplicaOperations,
+        volume::traits::VolumeOperations,
     },
 };
 use itertools::Itertools;
@@ -21,11 +22,15 @@ use stor_port::{
                 VolumePolicy,
             },
         },
-        store::replica::{ReplicaSpec, ReplicaSpecKey},
+        store::{
+            pool::{PoolEvacuation, PoolEvacuationStatus},
+            replica::{ReplicaSpec, ReplicaSpecKey},
+        },
         transport::{
-            CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetSpecs, NexusId,
-            NodeId, Protocol, Replica, ReplicaId, ReplicaName, ReplicaOwners, ReplicaShareProtocol,
-            ReplicaStatus, ShareReplica, UnshareReplica, VolumeId,
+            CreatePool, CreateReplica, CreateVolume, DestroyPool, DestroyReplica, Filter,
+            GetPoolRebalance, GetSpecs, NexusId, NodeId, PoolId, Protocol, PublishVolume, Replica,
+            ReplicaId, ReplicaName, ReplicaOwners, ReplicaShareProtocol, ReplicaStatus,
+            ShareReplica, UnshareReplica, VolumeId,
         },
     },
 };
@@ -884,6 +889,7 @@ async fn disown_unused_replicas() {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -891,7 +897,7 @@ async fn disown_unused_replicas() {
 
     cluster.composer().pause(&node).await.unwrap();
     volumes_api
-        .del_volume_target(&volume.spec.uuid, Some(false))
+        .del_volume_target(&volume.spec.uuid, Some(false), None)
         .await
         .expect_err("io-engine is down");
     cluster.composer().kill(&node).await.unwrap();
@@ -1035,3 +1041,341 @@ async fn destroy_after_restart() {
 
     assert_eq!(pool.state().unwrap().id, create.id);
 }
+
+#[tokio::test]
+async fn pool_rebalance_dry_run() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let client = cluster.grpc_client();
+    let create_pool = |node: NodeId, id: &str| CreatePool {
+        node,
+        id: id.into(),
+        disks: vec!["malloc:///disk0?size_mb=100".into()],
+        labels: None,
+    };
+    client
+        .pool()
+        .create(&create_pool(cluster.node(0), "pool-0"), None)
+        .await
+        .unwrap();
+
+    for _ in 0 .. 3 {
+        let volume = client
+            .volume()
+            .create(
+                &CreateVolume {
+                    uuid: VolumeId::new(),
+                    size: 8 * 1024 * 1024,
+                    replicas: 1,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        client
+            .volume()
+            .publish(
+                &PublishVolume::new(
+                    volume.spec().uuid.clone(),
+                    Some(cluster.node(0)),
+                    None,
+                    HashMap::new(),
+                    vec![],
+                ),
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    // A single pool cannot be imbalanced.
+    let moves = client
+        .pool()
+        .get_rebalance(&GetPoolRebalance::default(), None)
+        .await
+        .unwrap();
+    assert!(moves.into_inner().is_empty());
+
+    client
+        .pool()
+        .create(&create_pool(cluster.node(1), "pool-1"), None)
+        .await
+        .unwrap();
+
+    // Only one replica is moved off each over-utilised pool at a time.
+    let moves = client
+        .pool()
+        .get_rebalance(&GetPoolRebalance::default(), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(moves.len(), 1);
+    assert_eq!(moves[0].source_pool, "pool-0".into());
+    assert_eq!(moves[0].target_node, cluster.node(1));
+    assert_eq!(moves[0].target_pool, "pool-1".into());
+
+    // The pool rebalancer is not enabled, so nothing is actually moved.
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let replicas = client
+        .replica()
+        .get(Filter::Pool("pool-1".into()), None)
+        .await
+        .unwrap();
+    assert!(replicas.into_inner().is_empty());
+}
+
+#[tokio::test]
+async fn pool_cordon_evacuate() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+
+    let client = cluster.grpc_client();
+    let pool_0 = cluster.pool(0, 0);
+    let pool_1 = cluster.pool(1, 0);
+
+    let pool = client
+        .pool()
+        .cordon(pool_1.clone(), "cordon".into())
+        .await
+        .unwrap();
+    assert_eq!(
+        pool.spec().unwrap().cordon_labels,
+        vec!["cordon".to_string()]
+    );
+    let error = client
+        .pool()
+        .cordon(pool_1.clone(), "cordon".into())
+        .await
+        .expect_err("Already cordoned with the label");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    // No replicas are placed on the cordoned pool.
+    let volume = client
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let replicas = client
+        .replica()
+        .get(Filter::Pool(pool_1.clone()), None)
+        .await
+        .unwrap();
+    assert!(replicas.into_inner().is_empty());
+
+    let error = client
+        .pool()
+        .uncordon(pool_1.clone(), "other".into())
+        .await
+        .expect_err("Not cordoned with the label");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    let pool = client
+        .pool()
+        .uncordon(pool_1.clone(), "cordon".into())
+        .await
+        .unwrap();
+    assert!(pool.spec().unwrap().cordon_labels.is_empty());
+
+    client
+        .volume()
+        .publish(
+            &PublishVolume::new(
+                volume.spec().uuid.clone(),
+                Some(cluster.node(0)),
+                None,
+                HashMap::new(),
+                vec![],
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let pool = client
+        .pool()
+        .evacuate(pool_0.clone(), "evacuate".into())
+        .await
+        .unwrap();
+    let spec = pool.spec().unwrap();
+    assert!(spec.cordoned());
+    assert_eq!(
+        spec.evacuation.as_ref().map(|e| &e.status),
+        Some(&PoolEvacuationStatus::Evacuating)
+    );
+    let error = client
+        .pool()
+        .evacuate(pool_0.clone(), "again".into())
+        .await
+        .expect_err("Already evacuating");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    // The replica is moved onto the other pool, after which the pool is evacuated.
+    let start = std::time::Instant::now();
+    loop {
+        let pools = client
+            .pool()
+            .get(Filter::Pool(pool_0.clone()), None)
+            .await
+            .unwrap()
+            .into_inner();
+        let evacuation = pools[0].spec().and_then(|spec| spec.evacuation);
+        if evacuation.as_ref().map(|e| &e.status) == Some(&PoolEvacuationStatus::Evacuated) {
+            assert_eq!(evacuation.unwrap().remaining_replicas, 0);
+            break;
+        }
+        if start.elapsed() > Duration::from_secs(30) {
+            panic!("Timeout waiting for the pool to be evacuated: {evacuation:?}");
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    let replicas = client
+        .replica()
+        .get(Filter::Pool(pool_1), None)
+        .await
+        .unwrap();
+    assert_eq!(replicas.into_inner().len(), 1);
+
+    // Removing the evacuation label uncordons the pool.
+    let pool = client
+        .pool()
+        .uncordon(pool_0, "evacuate".into())
+        .await
+        .unwrap();
+    let spec = pool.spec().unwrap();
+    assert!(!spec.cordoned());
+    assert!(spec.evacuation.is_none());
+}
+
+/// Test that the replicas of unpublished volumes are recorded as blocked by the evacuation, and
+/// that they're moved once their volume is published.
+#[tokio::test]
+async fn pool_evacuate_unpublished() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+
+    let client = cluster.grpc_client();
+    let pool_0 = cluster.pool(0, 0);
+    let pool_1 = cluster.pool(1, 0);
+
+    // Place the volume replica on the pool which is then evacuated.
+    client
+        .pool()
+        .cordon(pool_1.clone(), "cordon".into())
+        .await
+        .unwrap();
+    let volume = client
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    client
+        .pool()
+        .uncordon(pool_1.clone(), "cordon".into())
+        .await
+        .unwrap();
+
+    client
+        .pool()
+        .evacuate(pool_0.clone(), "evacuate".into())
+        .await
+        .unwrap();
+    let evacuation = wait_pool_evacuation(&cluster, &pool_0, |evacuation| {
+        evacuation.blocked_replicas == 1
+    })
+    .await;
+    assert_eq!(evacuation.status, PoolEvacuationStatus::Evacuating);
+    assert_eq!(evacuation.remaining_replicas, 1);
+
+    // Once the volume is published its replica is moved, completing the evacuation.
+    client
+        .volume()
+        .publish(
+            &PublishVolume::new(
+                volume.spec().uuid.clone(),
+                Some(cluster.node(0)),
+                None,
+                HashMap::new(),
+                vec![],
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    let evacuation = wait_pool_evacuation(&cluster, &pool_0, |evacuation| {
+        evacuation.status == PoolEvacuationStatus::Evacuated
+    })
+    .await;
+    assert_eq!(evacuation.remaining_replicas, 0);
+    assert_eq!(evacuation.blocked_replicas, 0);
+    let replicas = client
+        .replica()
+        .get(Filter::Pool(pool_1), None)
+        .await
+        .unwrap();
+    assert_eq!(replicas.into_inner().len(), 1);
+}
+
+/// Wait until the evacuation of the given pool matches the predicate, returning it.
+async fn wait_pool_evacuation(
+    cluster: &Cluster,
+    pool: &PoolId,
+    predicate: impl Fn(&PoolEvacuation) -> bool,
+) -> PoolEvacuation {
+    let start = std::time::Instant::now();
+    loop {
+        let pools = cluster
+            .grpc_client()
+            .pool()
+            .get(Filter::Pool(pool.clone()), None)
+            .await
+            .unwrap()
+            .into_inner();
+        let evacuation = pools[0].spec().and_then(|spec| spec.evacuation);
+        if let Some(evacuation) = evacuation.as_ref().filter(|e| predicate(e)) {
+            return evacuation.clone();
+        }
+        if start.elapsed() > Duration::from_secs(30) {
+            panic!("Timeout waiting for the pool evacuation: {evacuation:?}");
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
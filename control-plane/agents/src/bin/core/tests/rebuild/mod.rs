@@ -11,7 +11,11 @@ use stor_port::types::v0::{
             ReplicaState, VolumePolicy, VolumeStatus,
         },
     },
-    transport::{Filter, GetRebuildRecord, NexusId, NexusStatus, RebuildHistory, VolumeId},
+    store::rebuild_history::VolumeRebuildRecord,
+    transport::{
+        Filter, GetRebuildHistories, GetRebuildRecord, NexusId, NexusStatus, RebuildHistory,
+        VolumeId,
+    },
 };
 // Wait time for the nexus to be online from Degraded state.
 const REBUILD_WAIT_TIME: u64 = 12;
@@ -67,6 +71,7 @@ async fn rebuild_history_for_full_rebuild() {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -166,6 +171,30 @@ async fn rebuild_history_for_full_rebuild() {
         !history.records.get(0).unwrap().is_partial,
         "Rebuild type is not Full rebuild in rest"
     );
+
+    // The rebuild history is also persisted for the volume.
+    let volume_id: VolumeId = vol.spec.uuid.into();
+    let histories = poll_persisted_rebuild_history(&nexus_client, &volume_id)
+        .await
+        .expect("Failed to get the persisted rebuild history");
+    assert_eq!(histories.len(), 1);
+    assert_eq!(histories[0].nexus, nexus_id);
+    assert_eq!(histories[0].node.as_str(), vol_target);
+    let partial = nexus_client
+        .get_rebuild_histories(
+            &GetRebuildHistories {
+                volume: Some(volume_id),
+                partial: Some(true),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect("Failed to get the persisted rebuild history");
+    assert!(
+        partial.into_inner().is_empty(),
+        "There should be no partial rebuild in the persisted history"
+    );
 }
 
 // This test:
@@ -197,6 +226,7 @@ async fn rebuild_history_for_partial_rebuild() {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -359,6 +389,7 @@ async fn rebuild_partial_disabled() {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -498,6 +529,31 @@ async fn poll_rebuild_history(
     Err(())
 }
 
+/// Checks if the volume has persisted rebuild records for predefined interval.
+async fn poll_persisted_rebuild_history(
+    nexus_client: &impl NexusOperations,
+    volume_id: &VolumeId,
+) -> Result<Vec<VolumeRebuildRecord>, ()> {
+    let timeout = Duration::from_secs(REBUILD_WAIT_TIME);
+    let start = std::time::Instant::now();
+    let request = GetRebuildHistories {
+        volume: Some(volume_id.clone()),
+        ..Default::default()
+    };
+    loop {
+        if let Ok(histories) = nexus_client.get_rebuild_histories(&request, None).await {
+            if let Some(history) = histories.into_inner().pop() {
+                return Ok(history.records);
+            }
+        }
+        if std::time::Instant::now() > (start + timeout) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    Err(())
+}
+
 /// Checks if pool is online. Returns pool if yes, None if not.
 async fn wait_pool_online(cluster: &Cluster, replica: &Replica, timeout: Duration) -> Option<Pool> {
     let client = cluster.rest_v00();
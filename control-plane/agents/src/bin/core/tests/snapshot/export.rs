@@ -0,0 +1,276 @@
+#![cfg(test)]
+
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::{
+    CreateVolumeSnapshot, DestroyVolumeSnapshot, VolumeOperations,
+};
+use std::time::Duration;
+use stor_port::{
+    transport_api::ReplyErrorKind,
+    types::v0::{
+        store::snapshots::export::{BackupTarget, SnapshotExportSpec, SnapshotExportStatus},
+        transport::{
+            CreateSnapshotExport, CreateVolume, DestroySnapshotExport, DestroyVolume,
+            EncryptionKeyProvider, EncryptionKeyRef, Filter, RestoreSnapshotExport,
+            SnapshotExportId, SnapshotId, VolumeId,
+        },
+    },
+};
+
+const VOLUME_SIZE: u64 = 20 * 1024 * 1024;
+const CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+
+#[tokio::test]
+async fn snapshot_export() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_io_engines(1)
+        .with_tmpfs_pool_ix(0, 200 * 1024 * 1024)
+        .with_csi(true, true)
+        .with_csi_registration(true)
+        .with_cache_period("1s")
+        // don't let the reconcile interfere with the tests
+        .with_reconcile_period(Duration::from_secs(1000), Duration::from_secs(1000))
+        .build()
+        .await
+        .unwrap();
+
+    let vol_cli = cluster.grpc_client().volume();
+    let target = BackupTarget::Directory {
+        node: cluster.csi_node(0).to_string().into(),
+        path: "/tmp/snapshot-exports".to_string(),
+    };
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: VOLUME_SIZE,
+                replicas: 1,
+                thin: true,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let mut snapshots = vec![];
+    for _ in 0 .. 2 {
+        let snapshot = vol_cli
+            .create_snapshot(
+                &CreateVolumeSnapshot::new(volume.uuid(), SnapshotId::new()),
+                None,
+            )
+            .await
+            .unwrap();
+        snapshots.push(snapshot.spec().snap_id().clone());
+    }
+
+    // The backup path must be absolute and must not traverse up the directory tree.
+    for path in ["snapshot-exports", "/tmp/../var/tmp/snapshot-exports"] {
+        let error = vol_cli
+            .create_snapshot_export(
+                &CreateSnapshotExport::new(
+                    SnapshotExportId::new(),
+                    snapshots[0].clone(),
+                    BackupTarget::Directory {
+                        node: cluster.csi_node(0).to_string().into(),
+                        path: path.to_string(),
+                    },
+                    None,
+                ),
+                None,
+            )
+            .await
+            .expect_err("Invalid backup path");
+        assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+    }
+
+    // The backup would hold the data in clear, so encrypted snapshots cannot be exported.
+    let encrypted = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: VOLUME_SIZE,
+                replicas: 1,
+                thin: true,
+                encryption: Some(EncryptionKeyRef::new(
+                    EncryptionKeyProvider::Kms,
+                    "volume-key",
+                )),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let encrypted_snapshot = vol_cli
+        .create_snapshot(
+            &CreateVolumeSnapshot::new(encrypted.uuid(), SnapshotId::new()),
+            None,
+        )
+        .await
+        .unwrap();
+    let error = vol_cli
+        .create_snapshot_export(
+            &CreateSnapshotExport::new(
+                SnapshotExportId::new(),
+                encrypted_snapshot.spec().snap_id().clone(),
+                target.clone(),
+                None,
+            ),
+            None,
+        )
+        .await
+        .expect_err("Encrypted snapshots cannot be exported");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+    let exports = vol_cli.get_snapshot_exports(None, None).await.unwrap();
+    assert!(exports.into_inner().is_empty());
+    vol_cli
+        .destroy_snapshot(&DestroyVolumeSnapshot::from(&encrypted_snapshot), None)
+        .await
+        .unwrap();
+    vol_cli
+        .destroy(&DestroyVolume::new(encrypted.uuid()), None)
+        .await
+        .unwrap();
+
+    let full = vol_cli
+        .create_snapshot_export(
+            &CreateSnapshotExport::new(
+                SnapshotExportId::new(),
+                snapshots[0].clone(),
+                target.clone(),
+                None,
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    // The snapshot is exported in the background.
+    assert_eq!(full.status, SnapshotExportStatus::Exporting);
+    let full = wait_exported(&cluster, &full.id).await;
+    assert_eq!(full.status, SnapshotExportStatus::Exported);
+    assert_eq!(&full.volume_id, volume.uuid());
+    assert_eq!(full.size, VOLUME_SIZE);
+    assert!(full.timestamp.is_some());
+    let stats = full.stats.clone().unwrap();
+    assert_eq!(stats.chunks, VOLUME_SIZE / CHUNK_SIZE);
+    assert_eq!(stats.changed_chunks, stats.chunks);
+
+    // Nothing was written since the first snapshot, so no chunk differs from the base.
+    let incremental = vol_cli
+        .create_snapshot_export(
+            &CreateSnapshotExport::new(
+                SnapshotExportId::new(),
+                snapshots[1].clone(),
+                target.clone(),
+                Some(full.id.clone()),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    let incremental = wait_exported(&cluster, &incremental.id).await;
+    assert_eq!(incremental.status, SnapshotExportStatus::Exported);
+    let stats = incremental.stats.clone().unwrap();
+    assert_eq!(stats.chunks, VOLUME_SIZE / CHUNK_SIZE);
+    assert_eq!(stats.changed_chunks, 0);
+
+    let other_target = BackupTarget::Directory {
+        node: cluster.csi_node(0).to_string().into(),
+        path: "/tmp/other-snapshot-exports".to_string(),
+    };
+    let error = vol_cli
+        .create_snapshot_export(
+            &CreateSnapshotExport::new(
+                SnapshotExportId::new(),
+                snapshots[1].clone(),
+                other_target,
+                Some(full.id.clone()),
+            ),
+            None,
+        )
+        .await
+        .expect_err("The base was exported into a different backup target");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    let error = vol_cli
+        .destroy_snapshot_export(&DestroySnapshotExport::new(full.id.clone()), None)
+        .await
+        .expect_err("The export is the base of another export");
+    assert_eq!(error.kind, ReplyErrorKind::InUse);
+
+    let params = CreateVolume {
+        uuid: VolumeId::new(),
+        size: VOLUME_SIZE,
+        replicas: 1,
+        thin: true,
+        ..Default::default()
+    };
+    let error = vol_cli
+        .restore_snapshot_export(
+            &RestoreSnapshotExport::new(
+                incremental.id.clone(),
+                CreateVolume {
+                    size: 2 * VOLUME_SIZE,
+                    ..params.clone()
+                },
+            ),
+            None,
+        )
+        .await
+        .expect_err("The volume must have the size of the export");
+    assert_eq!(error.kind, ReplyErrorKind::OutOfRange);
+
+    let restored = vol_cli
+        .restore_snapshot_export(
+            &RestoreSnapshotExport::new(incremental.id.clone(), params.clone()),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(restored.uuid(), &params.uuid);
+    assert_eq!(restored.spec().size, VOLUME_SIZE);
+
+    // The temporary transfer volumes are destroyed once the exports and the restore complete.
+    let volumes = vol_cli.get(Filter::None, false, None, None).await.unwrap();
+    assert_eq!(volumes.entries.len(), 2);
+
+    for export in [&incremental.id, &full.id] {
+        vol_cli
+            .destroy_snapshot_export(&DestroySnapshotExport::new(export.clone()), None)
+            .await
+            .unwrap();
+    }
+    let exports = vol_cli.get_snapshot_exports(None, None).await.unwrap();
+    assert!(exports.into_inner().is_empty());
+}
+
+/// Wait until the snapshot export is exported, returning it.
+async fn wait_exported(cluster: &Cluster, id: &SnapshotExportId) -> SnapshotExportSpec {
+    let vol_cli = cluster.grpc_client().volume();
+    let timeout = Duration::from_secs(30);
+    let start = std::time::Instant::now();
+    loop {
+        let exports = vol_cli
+            .get_snapshot_exports(Some(id.clone()), None)
+            .await
+            .unwrap()
+            .into_inner();
+        let export = exports
+            .into_iter()
+            .next()
+            .expect("The export should not fail");
+        if export.status == SnapshotExportStatus::Exported {
+            return export;
+        }
+        if std::time::Instant::now() > (start + timeout) {
+            panic!(
+                "Timeout waiting for snapshot export {id}, status {:?}",
+                export.status
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
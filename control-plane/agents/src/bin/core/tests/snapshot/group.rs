@@ -0,0 +1,137 @@
+#![cfg(test)]
+
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use std::{collections::HashMap, time::Duration};
+use stor_port::{
+    transport_api::ReplyErrorKind,
+    types::v0::{
+        store::snapshots::group::{SnapshotGroupStatus, SnapshotGroupTarget},
+        transport::{CreateSnapshotGroup, CreateVolume, DestroySnapshotGroup, Filter, VolumeId},
+    },
+};
+
+#[tokio::test]
+async fn snapshot_group() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_cache_period("1s")
+        // don't let the reconcile interfere with the tests
+        .with_reconcile_period(Duration::from_secs(1000), Duration::from_secs(1000))
+        .build()
+        .await
+        .unwrap();
+
+    let vol_cli = cluster.grpc_client().volume();
+
+    let labels = HashMap::from([("app".to_string(), "db".to_string())]);
+    let mut volumes = vec![];
+    for max_snapshots in [None, Some(1)] {
+        let volume = vol_cli
+            .create(
+                &CreateVolume {
+                    uuid: VolumeId::new(),
+                    size: 20 * 1024 * 1024,
+                    replicas: 1,
+                    labels: Some(labels.clone()),
+                    max_snapshots,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        volumes.push(volume.uuid().clone());
+    }
+
+    let error = vol_cli
+        .create_snapshot_group(
+            &CreateSnapshotGroup::new(
+                "missing".into(),
+                SnapshotGroupTarget::Selector(HashMap::from([(
+                    "app".to_string(),
+                    "web".to_string(),
+                )])),
+            ),
+            None,
+        )
+        .await
+        .expect_err("No volume carries the labels");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let group = vol_cli
+        .create_snapshot_group(
+            &CreateSnapshotGroup::new(
+                "by-volumes".into(),
+                SnapshotGroupTarget::Volumes(volumes.clone()),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(group.spec().status, SnapshotGroupStatus::Created);
+    assert!(group.spec().timestamp.is_some());
+    assert_eq!(group.spec().members.len(), volumes.len());
+    for member in &group.spec().members {
+        assert!(group.member_created(member));
+    }
+    for volume in &volumes {
+        assert_eq!(snapshot_count(&cluster, volume).await, 1);
+    }
+
+    let error = vol_cli
+        .create_snapshot_group(
+            &CreateSnapshotGroup::new(
+                "by-volumes".into(),
+                SnapshotGroupTarget::Volumes(volumes.clone()),
+            ),
+            None,
+        )
+        .await
+        .expect_err("The group already exists");
+    assert_eq!(error.kind, ReplyErrorKind::AlreadyExists);
+
+    // The second volume is at its snapshot limit, so no volume is snapshotted by the group.
+    let error = vol_cli
+        .create_snapshot_group(
+            &CreateSnapshotGroup::new("by-selector".into(), SnapshotGroupTarget::Selector(labels)),
+            None,
+        )
+        .await
+        .expect_err("The volume has reached its snapshot limit");
+    assert_eq!(error.kind, ReplyErrorKind::OutOfRange);
+    assert_eq!(snapshot_count(&cluster, &volumes[0]).await, 1);
+    let error = vol_cli
+        .get_snapshot_groups(Some("by-selector".into()), None)
+        .await
+        .expect_err("The group was not created");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+
+    let groups = vol_cli.get_snapshot_groups(None, None).await.unwrap();
+    assert_eq!(groups.into_inner().len(), 1);
+
+    // Deleting the group also deletes its snapshots.
+    vol_cli
+        .destroy_snapshot_group(&DestroySnapshotGroup::new("by-volumes".into()), None)
+        .await
+        .unwrap();
+    for volume in &volumes {
+        assert_eq!(snapshot_count(&cluster, volume).await, 0);
+    }
+    let groups = vol_cli.get_snapshot_groups(None, None).await.unwrap();
+    assert!(groups.into_inner().is_empty());
+}
+
+/// Get the number of snapshots of the given volume.
+async fn snapshot_count(cluster: &Cluster, volume_id: &VolumeId) -> usize {
+    let snapshots = cluster
+        .grpc_client()
+        .volume()
+        .get_snapshots(Filter::Volume(volume_id.clone()), false, None, None)
+        .await
+        .unwrap();
+    snapshots.entries().len()
+}
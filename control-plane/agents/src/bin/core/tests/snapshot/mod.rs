@@ -1 +1,4 @@
+mod export;
 mod fs_cons_snapshot;
+mod group;
+mod schedule;
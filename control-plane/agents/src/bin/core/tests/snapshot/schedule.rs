@@ -0,0 +1,183 @@
+#![cfg(test)]
+
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+use stor_port::{
+    transport_api::ReplyErrorKind,
+    types::v0::{
+        store::snapshots::schedule::{
+            SnapshotRetention, SnapshotScheduleTarget, SnapshotScheduleTrigger,
+        },
+        transport::{
+            CreateSnapshotSchedule, CreateVolume, DestroySnapshotSchedule, Filter, SnapshotId,
+            VolumeId,
+        },
+    },
+};
+
+#[tokio::test]
+async fn snapshot_schedule() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+
+    let vol_cli = cluster.grpc_client().volume();
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: "1e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+                size: 20 * 1024 * 1024,
+                replicas: 1,
+                thin: false,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let labelled = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: "1e3cf927-80c2-47a8-adf0-95c486bdd7b8".try_into().unwrap(),
+                size: 20 * 1024 * 1024,
+                replicas: 1,
+                thin: false,
+                labels: Some(HashMap::from([("tier".to_string(), "gold".to_string())])),
+                max_snapshots: Some(2),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let error = vol_cli
+        .create_snapshot_schedule(
+            &CreateSnapshotSchedule::new(
+                "missing".into(),
+                SnapshotScheduleTarget::Volume(VolumeId::new()),
+                SnapshotScheduleTrigger::Interval(Duration::from_secs(1)),
+                SnapshotRetention::default(),
+                false,
+            ),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+
+    // The retention count prunes the oldest scheduled snapshots.
+    vol_cli
+        .create_snapshot_schedule(
+            &CreateSnapshotSchedule::new(
+                "by-volume".into(),
+                SnapshotScheduleTarget::Volume(volume.uuid().clone()),
+                SnapshotScheduleTrigger::Interval(Duration::from_secs(1)),
+                SnapshotRetention {
+                    count: Some(2),
+                    max_age: None,
+                },
+                false,
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    let first = wait_snapshots(&cluster, volume.uuid(), 2).await;
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let rotated = wait_snapshots(&cluster, volume.uuid(), 2).await;
+    assert_ne!(
+        first, rotated,
+        "The oldest snapshots should have been pruned"
+    );
+
+    // Reaching the volume's snapshot limit prunes the oldest scheduled snapshot instead of
+    // failing the new one.
+    vol_cli
+        .create_snapshot_schedule(
+            &CreateSnapshotSchedule::new(
+                "by-selector".into(),
+                SnapshotScheduleTarget::Selector(HashMap::from([(
+                    "tier".to_string(),
+                    "gold".to_string(),
+                )])),
+                SnapshotScheduleTrigger::Interval(Duration::from_secs(1)),
+                SnapshotRetention::default(),
+                false,
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    let first = wait_snapshots(&cluster, labelled.uuid(), 2).await;
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let rotated = wait_snapshots(&cluster, labelled.uuid(), 2).await;
+    assert_ne!(
+        first, rotated,
+        "The oldest snapshots should have been pruned"
+    );
+
+    let schedules = vol_cli.get_snapshot_schedules(None, None).await.unwrap();
+    assert_eq!(schedules.into_inner().len(), 2);
+
+    // Deleting a schedule stops it, but keeps the snapshots it has taken.
+    for id in ["by-volume", "by-selector"] {
+        vol_cli
+            .destroy_snapshot_schedule(&DestroySnapshotSchedule::new(id.into()), None)
+            .await
+            .unwrap();
+        let error = vol_cli
+            .get_snapshot_schedules(Some(id.into()), None)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind, ReplyErrorKind::NotFound);
+    }
+    let snapshots = wait_snapshots(&cluster, volume.uuid(), 2).await;
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    assert_eq!(snapshots, wait_snapshots(&cluster, volume.uuid(), 2).await);
+}
+
+/// Wait until the volume has exactly `count` created snapshots, returning their ids.
+async fn wait_snapshots(
+    cluster: &Cluster,
+    volume_id: &VolumeId,
+    count: usize,
+) -> HashSet<SnapshotId> {
+    let vol_cli = cluster.grpc_client().volume();
+    let timeout = Duration::from_secs(20);
+    let start = std::time::Instant::now();
+    loop {
+        let snapshots = vol_cli
+            .get_snapshots(Filter::Volume(volume_id.clone()), false, None, None)
+            .await
+            .unwrap();
+        let snapshots = snapshots
+            .entries()
+            .iter()
+            .filter(|snapshot| snapshot.meta().status().created())
+            .map(|snapshot| snapshot.spec().snap_id().clone())
+            .collect::<HashSet<_>>();
+        if snapshots.len() == count {
+            return snapshots;
+        }
+        if std::time::Instant::now() > (start + timeout) {
+            panic!(
+                "Timeout waiting for {count} snapshots of volume {volume_id}, found {}",
+                snapshots.len()
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
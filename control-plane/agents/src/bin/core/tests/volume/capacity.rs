@@ -196,6 +196,7 @@ async fn common_enospc_builder(
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -225,6 +226,7 @@ async fn common_enospc_builder(
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
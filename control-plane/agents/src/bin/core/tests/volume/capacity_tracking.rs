@@ -0,0 +1,91 @@
+#![cfg(test)]
+
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+use std::collections::HashMap;
+use stor_port::{
+    transport_api::{ReplyError, ReplyErrorKind},
+    types::v0::transport::{
+        CreateVolume, GetVolumeCapacity, LabelledTopology, PoolTopology, Topology, VolumeCapacity,
+        VolumeId,
+    },
+};
+
+const MIB: u64 = 1024 * 1024;
+
+async fn get_capacity(
+    vol_cli: &impl VolumeOperations,
+    request: GetVolumeCapacity,
+) -> Result<VolumeCapacity, ReplyError> {
+    vol_cli.get_capacity(&request, None).await
+}
+
+#[tokio::test]
+async fn volume_capacity() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pool(0, "malloc:///p1?size_mb=100")
+        .with_pool(1, "malloc:///p1?size_mb=200")
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let vol_cli = cluster.grpc_client().volume();
+    let capacity = |replicas: u8, thin: bool, topology: Option<Topology>| {
+        get_capacity(&vol_cli, GetVolumeCapacity::new(replicas, thin, topology))
+    };
+
+    // a single replica may be placed on the largest pool
+    let single = capacity(1, false, None).await.unwrap();
+    assert!(single.maximum_volume_size > 100 * MIB);
+    assert!(single.maximum_volume_size < 200 * MIB);
+    assert!(single.available_capacity > single.maximum_volume_size);
+
+    // whereas each replica of a 2 replica volume must be placed on a different node
+    let double = capacity(2, false, None).await.unwrap();
+    assert!(double.maximum_volume_size > 0);
+    assert!(double.maximum_volume_size < 100 * MIB);
+    assert_eq!(double.available_capacity, single.available_capacity / 2);
+
+    // and there are not enough nodes for a 3 replica volume
+    let triple = capacity(3, false, None).await.unwrap();
+    assert_eq!(triple.maximum_volume_size, 0);
+
+    // thin provisioned replicas only need a fraction of the volume size to be free
+    let thin = capacity(1, true, None).await.unwrap();
+    assert!(thin.maximum_volume_size >= single.maximum_volume_size);
+
+    // no pool has the requested labels
+    let topology = Topology {
+        node: None,
+        pool: Some(PoolTopology::Labelled(LabelledTopology {
+            exclusion: HashMap::new(),
+            inclusion: HashMap::from([("zone".to_string(), "none".to_string())]),
+            affinity: vec![],
+        })),
+    };
+    let labelled = capacity(1, false, Some(topology)).await.unwrap();
+    assert_eq!(labelled, Default::default());
+
+    let error = capacity(0, false, None).await.expect_err("No replicas");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    // the capacity taken by a volume is no longer available
+    vol_cli
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 50 * MIB,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let single_after = capacity(1, false, None).await.unwrap();
+    assert!(single_after.available_capacity <= single.available_capacity - 50 * MIB);
+}
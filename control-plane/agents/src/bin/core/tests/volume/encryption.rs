@@ -0,0 +1,159 @@
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::{CreateVolumeSnapshot, VolumeOperations};
+use stor_port::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{
+        CreateSnapshotVolume, CreateVolume, EncryptionKeyProvider, EncryptionKeyRef, PublishVolume,
+        SetVolumeProperty, SnapshotId, VolumeProperty, VolumeShareProtocol,
+    },
+};
+
+#[tokio::test]
+async fn volume_encryption() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let vol_cli = cluster.grpc_client().volume();
+    let key_ref = EncryptionKeyRef::new(EncryptionKeyProvider::Kms, "volume-key");
+
+    let error = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: "a1c5e0a4-1d3c-4cd4-9d0d-3b1c9c1d8f01".try_into().unwrap(),
+                size: 20 * 1024 * 1024,
+                replicas: 1,
+                encryption: Some(EncryptionKeyRef::new(
+                    EncryptionKeyProvider::File,
+                    "../volume-key",
+                )),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("The key file must be within the key directory");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: "a1c5e0a4-1d3c-4cd4-9d0d-3b1c9c1d8f01".try_into().unwrap(),
+                size: 20 * 1024 * 1024,
+                replicas: 1,
+                encryption: Some(key_ref.clone()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().encryption, Some(key_ref.clone()));
+
+    let error = vol_cli
+        .publish(
+            &PublishVolume {
+                uuid: volume.uuid().clone(),
+                target_node: Some(cluster.node(0)),
+                share: None,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("The volume is only encrypted when its target is shared");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let error = vol_cli
+        .publish(
+            &PublishVolume {
+                uuid: volume.uuid().clone(),
+                target_node: Some(cluster.node(0)),
+                share: Some(VolumeShareProtocol::Nvmf),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("The kms key provider is not configured");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    let plain_volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: "a1c5e0a4-1d3c-4cd4-9d0d-3b1c9c1d8f02".try_into().unwrap(),
+                size: 20 * 1024 * 1024,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let error = vol_cli
+        .set_property(
+            &SetVolumeProperty::new(
+                plain_volume.uuid().clone(),
+                VolumeProperty::EncryptionKey(key_ref.clone()),
+            ),
+            None,
+        )
+        .await
+        .expect_err("An unencrypted volume cannot be encrypted");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    // the snapshot data is encrypted, so its restores inherit the key
+    let snapshot = vol_cli
+        .create_snapshot(
+            &CreateVolumeSnapshot::new(volume.uuid(), SnapshotId::new()),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let error = vol_cli
+        .create_snapshot_volume(
+            &CreateSnapshotVolume::new(
+                snapshot.spec().snap_id().clone(),
+                CreateVolume {
+                    uuid: "a1c5e0a4-1d3c-4cd4-9d0d-3b1c9c1d8f03".try_into().unwrap(),
+                    size: 20 * 1024 * 1024,
+                    replicas: 1,
+                    thin: true,
+                    encryption: Some(EncryptionKeyRef::new(
+                        EncryptionKeyProvider::Kms,
+                        "another-key",
+                    )),
+                    ..Default::default()
+                },
+            ),
+            None,
+        )
+        .await
+        .expect_err("The restore must use the key of the snapshot");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let restore = vol_cli
+        .create_snapshot_volume(
+            &CreateSnapshotVolume::new(
+                snapshot.spec().snap_id().clone(),
+                CreateVolume {
+                    uuid: "a1c5e0a4-1d3c-4cd4-9d0d-3b1c9c1d8f03".try_into().unwrap(),
+                    size: 20 * 1024 * 1024,
+                    replicas: 1,
+                    thin: true,
+                    ..Default::default()
+                },
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(restore.spec().encryption, Some(key_ref));
+}
@@ -68,6 +68,7 @@ async fn deleting_volume_reconcile(cluster: &Cluster) {
                 target_node: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                read_only: false,
             },
             None,
         )
@@ -162,6 +163,7 @@ async fn offline_replicas_reconcile(cluster: &Cluster, reconcile_period: Duratio
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -230,6 +232,7 @@ async fn unused_nexus_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -252,6 +255,7 @@ async fn unused_nexus_reconcile(cluster: &Cluster) {
         managed: true,
         owner: None,
         config: None,
+        qos: None,
     };
     let nexus = nexus_client.create(&create_nexus, None).await.unwrap();
     let nexus = wait_till_nexus_state(cluster, &nexus.uuid, None).await;
@@ -317,6 +321,7 @@ async fn unused_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -327,7 +332,7 @@ async fn unused_reconcile(cluster: &Cluster) {
     cluster.composer().kill(&nexus_node.id).await.unwrap();
     // 2. now we force unpublish the volume
     volumes_api
-        .del_volume_target(&volume.spec.uuid, Some(true))
+        .del_volume_target(&volume.spec.uuid, Some(true), None)
         .await
         .unwrap();
     // 3. publish on the previously unused node
@@ -341,6 +346,7 @@ async fn unused_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -429,6 +435,7 @@ async fn missing_nexus_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -3,12 +3,18 @@
 mod affinity_group;
 mod capacity;
 mod capacity_limit;
+mod capacity_tracking;
+mod encryption;
 mod garbage_collection;
 mod helpers;
 mod hotspare;
+mod qos;
+mod read_only;
+mod replica_move;
 mod resize;
 mod snapshot;
 mod snapshot_clone;
+mod snapshot_restore;
 mod switchover;
 
 use deployer_cluster::{Cluster, ClusterBuilder};
@@ -131,6 +137,7 @@ async fn nexus_persistence_test_iteration(
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                read_only: false,
             },
             None,
         )
@@ -209,6 +216,7 @@ async fn nexus_persistence_test_iteration(
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                read_only: false,
             },
             None,
         )
@@ -306,6 +314,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                read_only: false,
             },
             None,
         )
@@ -373,6 +382,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                read_only: false,
             },
             None,
         )
@@ -392,6 +402,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: Some(VolumeShareProtocol::Nvmf),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                read_only: false,
             },
             None,
         )
@@ -427,11 +438,12 @@ async fn publishing_test(cluster: &Cluster) {
                 share: Some(VolumeShareProtocol::Iscsi),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                read_only: false,
             },
             None,
         )
         .await
-        .expect_err("The volume publish should fail with Invalid protocol error");
+        .expect_err("The volume is already published via nvmf");
 
     volume_client
         .publish(
@@ -441,6 +453,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: Some(VolumeShareProtocol::Nvmf),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                read_only: false,
             },
             None,
         )
@@ -460,6 +473,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                read_only: false,
             },
             None,
         )
@@ -0,0 +1,124 @@
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+use stor_port::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{
+        CreateVolume, PublishVolume, SetVolumeProperty, UnpublishVolume, VolumeProperty, VolumeQos,
+        VolumeShareProtocol,
+    },
+};
+
+#[tokio::test]
+async fn volume_qos() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///p1?size_mb=100")
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let vol_cli = cluster.grpc_client().volume();
+
+    let qos = VolumeQos {
+        rw_iops: Some(2000),
+        read_mbps: Some(100),
+        ..Default::default()
+    };
+
+    let error = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: "ec4e66fd-3b33-4439-b504-d49aba53da26".try_into().unwrap(),
+                size: 5242880,
+                replicas: 1,
+                qos: Some(VolumeQos {
+                    rw_iops: Some(1500),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("The iops limit must be a multiple of 1000");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: "ec4e66fd-3b33-4439-b504-d49aba53da26".try_into().unwrap(),
+                size: 5242880,
+                replicas: 1,
+                qos: Some(qos.clone()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().qos, Some(qos));
+
+    // the limits are applied when the target is created
+    let volume = vol_cli
+        .publish(
+            &PublishVolume {
+                uuid: volume.uuid().clone(),
+                target_node: Some(cluster.node(0)),
+                share: Some(VolumeShareProtocol::Nvmf),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    // and updated on the current target when the property is set
+    let qos = VolumeQos {
+        write_mbps: Some(50),
+        ..Default::default()
+    };
+    let volume = vol_cli
+        .set_property(
+            &SetVolumeProperty::new(volume.uuid().clone(), VolumeProperty::Qos(qos.clone())),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().qos, Some(qos));
+
+    let error = vol_cli
+        .set_property(
+            &SetVolumeProperty::new(
+                volume.uuid().clone(),
+                VolumeProperty::Qos(VolumeQos {
+                    rw_iops: Some(10),
+                    ..Default::default()
+                }),
+            ),
+            None,
+        )
+        .await
+        .expect_err("The iops limit must be a multiple of 1000");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    // removing all limits clears the qos from the spec
+    let volume = vol_cli
+        .set_property(
+            &SetVolumeProperty::new(
+                volume.uuid().clone(),
+                VolumeProperty::Qos(VolumeQos::default()),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().qos, None);
+
+    vol_cli
+        .unpublish(&UnpublishVolume::new(volume.uuid(), false), None)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,106 @@
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+use stor_port::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{
+        CreateVolume, Filter, PublishVolume, UnpublishVolume, VolumeShareProtocol,
+    },
+};
+
+#[tokio::test]
+async fn read_only_multi_node_publish() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///p1?size_mb=100")
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let vol_cli = cluster.grpc_client().volume();
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: "1e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let publish = |frontend_node: &str, read_only: bool| PublishVolume {
+        uuid: volume.uuid().clone(),
+        target_node: Some(cluster.node(0)),
+        share: Some(VolumeShareProtocol::Nvmf),
+        frontend_nodes: vec![frontend_node.to_string()],
+        read_only,
+        ..Default::default()
+    };
+
+    let volume = vol_cli
+        .publish(&publish("app-node-1", true), None)
+        .await
+        .unwrap();
+    let frontend = volume.spec().active_config().unwrap().frontend().clone();
+    assert!(frontend.read_only());
+    assert_eq!(frontend.node_names(), vec!["app-node-1".to_string()]);
+
+    // a read-only target may be shared with further read-only frontend nodes
+    let volume = vol_cli
+        .publish(&publish("app-node-2", true), None)
+        .await
+        .unwrap();
+    let frontend = volume.spec().active_config().unwrap().frontend().clone();
+    assert_eq!(
+        frontend.node_names(),
+        vec!["app-node-1".to_string(), "app-node-2".to_string()]
+    );
+
+    // but not with writers
+    let error = vol_cli
+        .publish(&publish("app-node-3", false), None)
+        .await
+        .expect_err("Volume is already published read-only");
+    assert_eq!(error.kind, ReplyErrorKind::AlreadyPublished);
+
+    // unpublishing a single frontend node keeps the target for the others
+    vol_cli
+        .unpublish(
+            &UnpublishVolume::new(volume.uuid(), false)
+                .with_frontend_node(Some("app-node-1".to_string())),
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = vol_cli
+        .get(Filter::Volume(volume.uuid().clone()), false, None, None)
+        .await
+        .unwrap()
+        .entries
+        .remove(0);
+    let frontend = volume.spec().active_config().unwrap().frontend().clone();
+    assert_eq!(frontend.node_names(), vec!["app-node-2".to_string()]);
+
+    // and the target is removed along with the last frontend node
+    vol_cli
+        .unpublish(
+            &UnpublishVolume::new(volume.uuid(), false)
+                .with_frontend_node(Some("app-node-2".to_string())),
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = vol_cli
+        .get(Filter::Volume(volume.uuid().clone()), false, None, None)
+        .await
+        .unwrap()
+        .entries
+        .remove(0);
+    assert!(volume.spec().active_config().is_none());
+}
@@ -0,0 +1,211 @@
+#![cfg(test)]
+
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use std::{collections::HashMap, time::Duration};
+use stor_port::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{
+        CancelVolumeReplicaMove, CreateVolume, Filter, MoveVolumeReplica, PoolId, PublishVolume,
+        ReplicaId, ReplicaMoveRole, Volume, VolumeId,
+    },
+};
+
+async fn cluster(reconcile_period: Duration) -> Cluster {
+    ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap()
+}
+
+/// Create and publish a single replica volume, returning it along with its replica.
+async fn published_volume(cluster: &Cluster) -> (Volume, ReplicaId, PoolId) {
+    let volume_client = cluster.grpc_client().volume();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .publish(
+            &PublishVolume::new(
+                volume.spec().uuid.clone(),
+                Some(cluster.node(0)),
+                None,
+                HashMap::new(),
+                vec![],
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    let (replica, topology) = volume.state().replica_topology.into_iter().next().unwrap();
+    let pool = topology.pool().clone().unwrap();
+    (volume, replica, pool)
+}
+
+/// Pick a pool other than the given one.
+fn other_pool(cluster: &Cluster, pool: &PoolId) -> PoolId {
+    (0 .. 3)
+        .map(|node| cluster.pool(node, 0))
+        .find(|p| p != pool)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn replica_move() {
+    let cluster = cluster(Duration::from_secs(1)).await;
+    let volume_client = cluster.grpc_client().volume();
+    let (volume, replica, pool) = published_volume(&cluster).await;
+    let target_pool = other_pool(&cluster, &pool);
+
+    let moving = volume_client
+        .move_replica(
+            &MoveVolumeReplica::new(
+                volume.uuid().clone(),
+                replica.clone(),
+                Some(target_pool.clone()),
+                None,
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    let topology = moving.state().replica_topology;
+    assert_eq!(topology.len(), 2);
+    assert_eq!(
+        topology.get(&replica).unwrap().replica_move(),
+        Some(ReplicaMoveRole::Source)
+    );
+    let (_, new_topology) = topology.iter().find(|(id, _)| *id != &replica).unwrap();
+    assert_eq!(new_topology.replica_move(), Some(ReplicaMoveRole::Target));
+    assert_eq!(new_topology.pool(), &Some(target_pool.clone()));
+
+    // A second move cannot be started whilst one is in progress.
+    let error = volume_client
+        .move_replica(
+            &MoveVolumeReplica::new(volume.uuid().clone(), replica.clone(), None, None),
+            None,
+        )
+        .await
+        .expect_err("A replica move is already in progress");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    // Once rebuilt, the moved replica is retired.
+    let start = std::time::Instant::now();
+    loop {
+        let volume = volume_client
+            .get(Filter::Volume(volume.uuid().clone()), false, None, None)
+            .await
+            .unwrap();
+        let volume = volume.entries.first().unwrap();
+        let topology = volume.state().replica_topology;
+        if volume.spec().replica_move.is_none() && topology.len() == 1 {
+            let (_, topology) = topology.into_iter().next().unwrap();
+            assert_eq!(topology.pool(), &Some(target_pool));
+            assert_eq!(topology.replica_move(), None);
+            break;
+        }
+        if start.elapsed() > Duration::from_secs(30) {
+            panic!("Timeout waiting for the replica move to complete: {volume:?}");
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+#[tokio::test]
+async fn replica_move_unpublished() {
+    let cluster = cluster(Duration::from_secs(1)).await;
+    let volume_client = cluster.grpc_client().volume();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let (replica, _) = volume.state().replica_topology.into_iter().next().unwrap();
+
+    let error = volume_client
+        .move_replica(
+            &MoveVolumeReplica::new(volume.uuid().clone(), replica, None, None),
+            None,
+        )
+        .await
+        .expect_err("The volume is not published");
+    assert_eq!(error.kind, ReplyErrorKind::NotPublished);
+
+    let volume = volume_client
+        .get(Filter::Volume(volume.uuid().clone()), false, None, None)
+        .await
+        .unwrap();
+    let volume = volume.entries.first().unwrap();
+    assert!(volume.spec().replica_move.is_none());
+    assert_eq!(volume.state().replica_topology.len(), 1);
+}
+
+#[tokio::test]
+async fn replica_move_cancel() {
+    // don't let the reconcile complete the move
+    let cluster = cluster(Duration::from_secs(1000)).await;
+    let volume_client = cluster.grpc_client().volume();
+    let (volume, replica, pool) = published_volume(&cluster).await;
+
+    volume_client
+        .move_replica(
+            &MoveVolumeReplica::new(volume.uuid().clone(), replica.clone(), None, None),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let error = volume_client
+        .cancel_replica_move(
+            &CancelVolumeReplicaMove::new(volume.uuid().clone(), ReplicaId::new()),
+            None,
+        )
+        .await
+        .expect_err("Not the replica being moved");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+
+    let volume = volume_client
+        .cancel_replica_move(
+            &CancelVolumeReplicaMove::new(volume.uuid().clone(), replica.clone()),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(volume.spec().replica_move.is_none());
+    let topology = volume.state().replica_topology;
+    assert_eq!(topology.len(), 1);
+    let replica_topology = topology.get(&replica).unwrap();
+    assert_eq!(replica_topology.pool(), &Some(pool));
+    assert_eq!(replica_topology.replica_move(), None);
+
+    let error = volume_client
+        .cancel_replica_move(
+            &CancelVolumeReplicaMove::new(volume.uuid().clone(), replica),
+            None,
+        )
+        .await
+        .expect_err("No replica move in progress");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+}
@@ -8,7 +8,7 @@ use stor_port::{
     transport_api::{ReplyError, ReplyErrorKind},
     types::v0::transport::{
         CreateVolume, DestroyVolume, Filter, PublishVolume, ReplicaId, ResizeReplica, ResizeVolume,
-        Volume, VolumeId, VolumeShareProtocol,
+        UnpublishVolume, Volume, VolumeId, VolumeShareProtocol,
     },
 };
 
@@ -97,6 +97,8 @@ async fn resize_unpublished_and_published() {
                 uuid: volume.uuid().clone(),
                 requested_size: EXPANDED_SIZE,
                 cluster_capacity_limit: None,
+                allow_shrink: false,
+                filesystem_size: None,
             },
             None,
         )
@@ -164,6 +166,8 @@ async fn resize_published(cluster: &Cluster) {
                 uuid: volume.uuid().clone(),
                 requested_size: EXPANDED_SIZE,
                 cluster_capacity_limit: None,
+                allow_shrink: false,
+                filesystem_size: None,
             },
             None,
         )
@@ -222,6 +226,8 @@ async fn resize_on_no_capacity_pool() {
                 uuid: volume.uuid().clone(),
                 requested_size: EXPANDED_SIZE,
                 cluster_capacity_limit: None,
+                allow_shrink: false,
+                filesystem_size: None,
             },
             None,
         )
@@ -244,6 +250,8 @@ async fn resize_on_no_capacity_pool() {
                 uuid: volume.uuid().clone(),
                 requested_size: EXPANDED_SIZE,
                 cluster_capacity_limit: Some(EXPANDED_SIZE + CAPACITY_LIMIT_DIFF),
+                allow_shrink: false,
+                filesystem_size: None,
             },
             None,
         )
@@ -355,6 +363,8 @@ async fn resize_with_cluster_capacity_limit_concurrent() {
                         uuid: volume_id.try_into().unwrap(),
                         requested_size: EXPANDED_SIZE,
                         cluster_capacity_limit: Some(4 * EXPANDED_SIZE),
+                        allow_shrink: false,
+                        filesystem_size: None,
                     },
                     None,
                 )
@@ -412,6 +422,8 @@ async fn grpc_resize_volume_with_limit(
                 uuid: volume.uuid().clone(),
                 requested_size: new_size,
                 cluster_capacity_limit: capacity,
+                allow_shrink: false,
+                filesystem_size: None,
             },
             None,
         )
@@ -617,3 +629,125 @@ async fn resize_replica_space_reclaim() {
         }
     }
 }
+
+#[tokio::test]
+async fn resize_shrink() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pool(0, "malloc:///p1?size_mb=200")
+        .with_pool(1, "malloc:///p1?size_mb=200")
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+
+    let vol_cli = cluster.grpc_client().volume();
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: EXPANDED_SIZE,
+                replicas: 2,
+                thin: true,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Shrinking must be explicitly allowed.
+    let result = vol_cli
+        .resize(&ResizeVolume::new(volume.uuid().clone(), SIZE, None), None)
+        .await;
+    assert!(matches!(
+        result,
+        Err(ReplyError {
+            kind: ReplyErrorKind::NotAcceptable,
+            ..
+        })
+    ));
+
+    // A published volume can't be shrunk.
+    vol_cli
+        .publish(
+            &PublishVolume {
+                uuid: volume.uuid().clone(),
+                share: Some(VolumeShareProtocol::Nvmf),
+                target_node: Some(cluster.node(0)),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let shrink = ResizeVolume::new(volume.uuid().clone(), SIZE, None).with_allow_shrink(true);
+    let result = vol_cli
+        .resize(&shrink.clone().with_filesystem_size(SIZE), None)
+        .await;
+    assert!(matches!(
+        result,
+        Err(ReplyError {
+            kind: ReplyErrorKind::FailedPrecondition,
+            ..
+        })
+    ));
+    vol_cli
+        .unpublish(&UnpublishVolume::new(volume.uuid(), false), None)
+        .await
+        .unwrap();
+
+    // The size of the filesystem must be confirmed, and must fit within the new size.
+    for request in [
+        shrink.clone(),
+        shrink.clone().with_filesystem_size(SIZE + 1),
+    ] {
+        let result = vol_cli.resize(&request, None).await;
+        assert!(matches!(
+            result,
+            Err(ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                ..
+            })
+        ));
+    }
+
+    let resized_volume = vol_cli
+        .resize(&shrink.with_filesystem_size(SIZE), None)
+        .await
+        .unwrap();
+    validate_resized_volume(&cluster, volume.uuid(), &resized_volume, SIZE, false).await;
+
+    // The allocation of thick provisioned replicas says nothing about the data on the volume.
+    let thick_volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: EXPANDED_SIZE,
+                replicas: 1,
+                thin: false,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let result = vol_cli
+        .resize(
+            &ResizeVolume::new(thick_volume.uuid().clone(), SIZE, None)
+                .with_allow_shrink(true)
+                .with_filesystem_size(0),
+            None,
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(ReplyError {
+            kind: ReplyErrorKind::FailedPrecondition,
+            ..
+        })
+    ));
+}
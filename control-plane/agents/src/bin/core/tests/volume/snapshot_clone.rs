@@ -1,9 +1,17 @@
 use deployer_cluster::ClusterBuilder;
-use grpc::operations::volume::traits::{CreateVolumeSnapshot, VolumeOperations};
+use grpc::operations::volume::traits::{
+    CreateVolumeSnapshot, DestroyVolumeSnapshot, VolumeOperations,
+};
 use std::time::Duration;
 use stor_port::{
     transport_api::ReplyErrorKind,
-    types::v0::transport::{CreateSnapshotVolume, CreateVolume, Filter, SnapshotId},
+    types::v0::{
+        store::volume::VolumeContentSource,
+        transport::{
+            CloneVolume, CreateSnapshotVolume, CreateVolume, Filter, SetVolumeProperty, SnapshotId,
+            VolumeProperty,
+        },
+    },
 };
 
 #[tokio::test]
@@ -122,3 +130,138 @@ async fn snapshot_clone() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn volume_clone() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+
+    let vol_cli = cluster.grpc_client().volume();
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: "2e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+                size: 20 * 1024 * 1024,
+                replicas: 1,
+                thin: false,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let error = vol_cli
+        .clone_volume(
+            &CloneVolume::new(
+                volume.uuid().clone(),
+                CreateVolume {
+                    uuid: "2e3cf927-80c2-47a8-adf0-95c486bdd7b8".try_into().unwrap(),
+                    size: 20 * 1024 * 1024,
+                    replicas: 1,
+                    thin: false,
+                    ..Default::default()
+                },
+            ),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let error = vol_cli
+        .clone_volume(
+            &CloneVolume::new(
+                volume.uuid().clone(),
+                CreateVolume {
+                    uuid: "2e3cf927-80c2-47a8-adf0-95c486bdd7b8".try_into().unwrap(),
+                    size: 60 * 1024 * 1024,
+                    replicas: 1,
+                    thin: true,
+                    ..Default::default()
+                },
+            ),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::OutOfRange);
+
+    let clone = vol_cli
+        .clone_volume(
+            &CloneVolume::new(
+                volume.uuid().clone(),
+                CreateVolume {
+                    uuid: "2e3cf927-80c2-47a8-adf0-95c486bdd7b8".try_into().unwrap(),
+                    size: 20 * 1024 * 1024,
+                    replicas: 1,
+                    thin: true,
+                    ..Default::default()
+                },
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(matches!(
+        clone.spec().content_source,
+        Some(VolumeContentSource::Volume(ref source, _)) if source == volume.uuid()
+    ));
+
+    // The clone source snapshot is hidden from the user.
+    let snapshots = vol_cli
+        .get_snapshots(Filter::None, false, None, None)
+        .await
+        .unwrap();
+    assert!(snapshots.entries().is_empty());
+    let source = vol_cli
+        .get(Filter::Volume(volume.uuid().clone()), false, None, None)
+        .await
+        .unwrap();
+    assert_eq!(source.entries[0].spec().num_snapshots, 0);
+
+    // Nor is it accounted against the snapshot limit.
+    vol_cli
+        .set_property(
+            &SetVolumeProperty::new(volume.uuid().clone(), VolumeProperty::MaxSnapshots(1)),
+            None,
+        )
+        .await
+        .unwrap();
+    let snapshot = vol_cli
+        .create_snapshot(
+            &CreateVolumeSnapshot::new(volume.uuid(), SnapshotId::new()),
+            None,
+        )
+        .await
+        .unwrap();
+    let error = vol_cli
+        .create_snapshot(
+            &CreateVolumeSnapshot::new(volume.uuid(), SnapshotId::new()),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::OutOfRange);
+    vol_cli
+        .destroy_snapshot(&DestroyVolumeSnapshot::from(&snapshot), None)
+        .await
+        .unwrap();
+
+    // Once the clone is gone, so is its source snapshot.
+    vol_cli.destroy(&clone, None).await.unwrap();
+    let source = vol_cli
+        .get(Filter::Volume(volume.uuid().clone()), false, None, None)
+        .await
+        .unwrap();
+    assert_eq!(source.entries[0].spec().num_snapshots, 0);
+}
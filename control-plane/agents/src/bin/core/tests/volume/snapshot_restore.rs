@@ -0,0 +1,127 @@
+#![cfg(test)]
+
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{
+    replica::traits::ReplicaOperations,
+    volume::traits::{CreateVolumeSnapshot, RestoreVolumeSnapshot, VolumeOperations},
+};
+use std::{collections::HashMap, time::Duration};
+use stor_port::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{
+        CreateVolume, Filter, PublishVolume, ReplicaKind, SnapshotId, UnpublishVolume, VolumeId,
+    },
+};
+
+#[tokio::test]
+async fn snapshot_restore() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        // don't let the reconcile interfere with the tests
+        .with_reconcile_period(Duration::from_secs(1000), Duration::from_secs(1000))
+        .build()
+        .await
+        .unwrap();
+
+    let vol_cli = cluster.grpc_client().volume();
+    let repl_cli = cluster.grpc_client().replica();
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 20 * 1024 * 1024,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let snapshot = vol_cli
+        .create_snapshot(
+            &CreateVolumeSnapshot::new(volume.uuid(), SnapshotId::new()),
+            None,
+        )
+        .await
+        .unwrap();
+    let snap_id = snapshot.spec().snap_id().clone();
+
+    let replicas = repl_cli
+        .get(Filter::Volume(volume.uuid().clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(replicas.len(), 2);
+
+    vol_cli
+        .publish(
+            &PublishVolume::new(
+                volume.uuid().clone(),
+                Some(cluster.node(0)),
+                None,
+                HashMap::new(),
+                vec![],
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // A published volume cannot be restored, as its data is in use.
+    let error = vol_cli
+        .restore_snapshot(
+            &RestoreVolumeSnapshot::new(volume.uuid(), snap_id.clone()),
+            None,
+        )
+        .await
+        .expect_err("The volume is published");
+    assert_eq!(error.kind, ReplyErrorKind::AlreadyPublished);
+
+    vol_cli
+        .unpublish(&UnpublishVolume::new(volume.uuid(), false), None)
+        .await
+        .unwrap();
+
+    // The snapshot can only be restored to its own source volume.
+    let error = vol_cli
+        .restore_snapshot(
+            &RestoreVolumeSnapshot::new(&VolumeId::new(), snap_id.clone()),
+            None,
+        )
+        .await
+        .expect_err("The volume does not exist");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+
+    let restored = vol_cli
+        .restore_snapshot(
+            &RestoreVolumeSnapshot::new(volume.uuid(), snap_id.clone()),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(restored.spec().size, volume.spec().size);
+    assert_eq!(restored.spec().num_replicas, 2);
+
+    // The replicas are replaced by clones of the replica snapshots.
+    let restored_replicas = repl_cli
+        .get(Filter::Volume(volume.uuid().clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(restored_replicas.len(), replicas.len());
+    for replica in &restored_replicas {
+        assert_eq!(replica.kind, ReplicaKind::SnapshotClone);
+        assert!(!replicas.iter().any(|r| r.uuid == replica.uuid));
+    }
+
+    // The snapshot is kept and may be restored again.
+    vol_cli
+        .restore_snapshot(&RestoreVolumeSnapshot::new(volume.uuid(), snap_id), None)
+        .await
+        .unwrap();
+}
@@ -67,6 +67,7 @@ async fn old_nexus_delete_after_vol_destroy() {
                 target_node: Some(cluster.node(0)),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![cluster.node(1).to_string()],
+                read_only: false,
             },
             None,
         )
@@ -235,6 +236,7 @@ async fn lazy_delete_shutdown_targets() {
                 target_node: Some(cluster.node(0)),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![cluster.node(1).to_string()],
+                read_only: false,
             },
             None,
         )
@@ -377,6 +379,7 @@ async fn volume_republish_nexus_recreation() {
                 target_node: Some(replica_node.into()),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![cluster.node(1).to_string()],
+                read_only: false,
             },
             None,
         )
@@ -485,6 +488,7 @@ async fn node_exhaustion() {
                 target_node: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![cluster.node(1).to_string()],
+                read_only: false,
             },
             None,
         )
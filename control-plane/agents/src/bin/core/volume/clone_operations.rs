@@ -2,13 +2,21 @@ use crate::{
     controller::{
         registry::Registry,
         resources::{
-            operations::{ResourceCloning, ResourceLifecycle, ResourceLifecycleExt},
-            operations_helper::{GuardedOperationsHelper, SpecOperationsHelper},
+            operations::{
+                ResourceCloning, ResourceLifecycle, ResourceLifecycleExt,
+                ResourceLifecycleWithLifetime,
+            },
+            operations_helper::{
+                GuardedOperationsHelper, OperationSequenceGuard, SpecOperationsHelper,
+            },
             OperationGuardArc, TraceStrLog,
         },
         scheduling::{volume::CloneVolumeSnapshot, ResourceFilter},
     },
-    volume::operations::{Context, CreateVolumeExe, CreateVolumeExeVal, CreateVolumeSource},
+    volume::{
+        operations::{Context, CreateVolumeExe, CreateVolumeExeVal, CreateVolumeSource},
+        DestroyVolumeSnapshotRequest,
+    },
 };
 use agents::errors::{self, SvcError};
 use stor_port::{
@@ -22,8 +30,8 @@ use stor_port::{
             volume::{VolumeContentSource, VolumeSpec},
         },
         transport::{
-            CreateSnapshotVolume, DestroyVolume, Replica, SnapshotCloneId, SnapshotCloneParameters,
-            SnapshotCloneSpecParams,
+            CreateSnapshotVolume, DestroyVolume, EncryptionKeyRef, Replica, SnapshotCloneId,
+            SnapshotCloneParameters, SnapshotCloneSpecParams,
         },
     },
 };
@@ -35,11 +43,64 @@ pub(crate) struct SnapshotCloneOp<'a>(
 
 impl SnapshotCloneOp<'_> {
     /// Get a Snapshot Source for VolumeContentSource from the same.
+    /// Internal snapshots are only used to clone volumes, and so they become a Volume Source.
     pub(crate) fn to_snapshot_source(&self) -> VolumeContentSource {
-        VolumeContentSource::new_snapshot_source(
-            self.1.uuid().clone(),
-            self.1.as_ref().spec().source_id().clone(),
-        )
+        let spec = self.1.as_ref().spec();
+        if spec.internal() {
+            VolumeContentSource::new_volume_source(spec.source_id().clone(), spec.uuid().clone())
+        } else {
+            VolumeContentSource::new_snapshot_source(spec.uuid().clone(), spec.source_id().clone())
+        }
+    }
+    /// Get the encryption key reference of the new volume.
+    /// The snapshot data is encrypted with the source volume's key, and so the new volume must
+    /// be encrypted with the same key.
+    pub(crate) fn encryption(&self) -> Option<EncryptionKeyRef> {
+        self.0
+            .params()
+            .encryption
+            .clone()
+            .or_else(|| self.1.as_ref().metadata().encryption().clone())
+    }
+}
+
+impl OperationGuardArc<VolumeSnapshot> {
+    /// Destroy this snapshot if it's an internal snapshot which is no longer used by any clone.
+    /// If the source volume is busy, the snapshot is left for the garbage collector.
+    pub(crate) async fn destroy_unused_clone_source(
+        &mut self,
+        registry: &Registry,
+    ) -> Result<(), SvcError> {
+        let snapshot = self.as_ref();
+        if !snapshot.spec().internal()
+            || !snapshot.status().created()
+            || snapshot.num_restores() > 0
+        {
+            return Ok(());
+        }
+
+        let snap_id = self.uuid().clone();
+        let vol_id = snapshot.spec().source_id().clone();
+        let snap_rsc = self.resource().clone();
+        match registry.specs().volume_rsc(&vol_id) {
+            None => {
+                self.destroy(
+                    registry,
+                    &DestroyVolumeSnapshotRequest::new(snap_rsc, None, snap_id),
+                )
+                .await
+            }
+            Some(volume) => {
+                let mut volume = volume.operation_guard()?;
+                volume
+                    .destroy_snap_guarded(
+                        registry,
+                        self,
+                        &DestroyVolumeSnapshotRequest::new(snap_rsc, Some(vol_id), snap_id),
+                    )
+                    .await
+            }
+        }
     }
 }
 
@@ -111,6 +172,14 @@ impl CreateVolumeExeVal for SnapshotCloneOp<'_> {
             new_volume.size == snapshot.metadata().spec_size(),
             errors::ClonedSnapshotVolumeSize {}
         );
+        if new_volume.encryption.is_some()
+            && &new_volume.encryption != snapshot.metadata().encryption()
+        {
+            return Err(SvcError::InvalidVolumeEncryption {
+                vol_id: new_volume.uuid.to_string(),
+                reason: "the volume must be encrypted with the key of the snapshot".to_string(),
+            });
+        }
 
         Ok(())
     }
@@ -143,10 +212,15 @@ impl CreateVolumeExe for SnapshotCloneOp<'_> {
         // todo: need to add new replica and do full rebuild, if clonable snapshots
         // count is less than volume replicas count.
         for (idx, clone_replica) in clone_replicas.iter().enumerate() {
-            match OperationGuardArc::<ReplicaSpec>::create_ext(context.registry, clone_replica)
-                .await
+            match OperationGuardArc::<ReplicaSpec>::create_ext_linked(
+                context.registry,
+                clone_replica,
+                &mut context.linked,
+            )
+            .await
             {
-                Ok(replica) => {
+                Ok((guard, replica)) => {
+                    context.replicas.push(guard);
                     replicas.push(replica);
                     if idx + 1 == volume_replicas {
                         break;
@@ -6,14 +6,20 @@ mod clone_operations;
 mod operations;
 mod operations_helper;
 mod registry;
+mod restore_operations;
 mod scheduling;
 mod service;
+mod snapshot_exports;
 mod snapshot_helpers;
+mod snapshot_groups;
 mod snapshot_operations;
+mod snapshot_schedules;
 mod specs;
 
 pub(crate) use operations::MoveReplicaRequest;
 pub(crate) use snapshot_operations::DestroyVolumeSnapshotRequest;
+pub(crate) use snapshot_schedules::VolumeFilesystem;
+pub(crate) use specs::volume_move_replica_candidates;
 
 /// Configure the Service and return the builder.
 pub(crate) fn configure(builder: agents::Service) -> agents::Service {
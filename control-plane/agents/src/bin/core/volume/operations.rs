@@ -9,8 +9,8 @@ use crate::{
                 ResourceResize, ResourceSharing, ResourceShutdownOperations,
             },
             operations_helper::{
-                GuardedOperationsHelper, OnCreateFail, OperationSequenceGuard, ResourceSpecsLocked,
-                SpecOperationsHelper,
+                GuardedOperationsHelper, LinkedTxn, OnCreateFail, OperationSequenceGuard,
+                ResourceSpecsLocked, SpecOperationsHelper,
             },
             OperationGuardArc, ResourceUid, TraceSpan, TraceStrLog,
         },
@@ -21,13 +21,13 @@ use crate::{
         snapshot_operations::DestroyVolumeSnapshotRequest,
         specs::{
             create_volume_replicas, healthy_volume_replicas, resizeable_replicas,
-            volume_move_replica_candidates, CreateReplicaCandidate,
+            shrinkable_replicas, volume_move_replica_candidates, CreateReplicaCandidate,
         },
     },
 };
-use agents::errors::SvcError;
+use agents::errors::{NotEnough, SvcError};
 use stor_port::{
-    transport_api::ErrorChain,
+    transport_api::{ErrorChain, ResourceKind},
     types::v0::{
         store::{
             nexus_persistence::NexusInfoKey,
@@ -36,10 +36,11 @@ use stor_port::{
         },
         transport::{
             CreateReplica, CreateVolume, DestroyNexus, DestroyReplica, DestroyShutdownTargets,
-            DestroyVolume, NodeTopology, Protocol, PublishVolume, Replica, ReplicaId,
-            ReplicaOwners, RepublishVolume, ResizeVolume, SetVolumeProperty, SetVolumeReplica,
-            ShareNexus, ShareVolume, ShutdownNexus, UnpublishVolume, UnshareNexus, UnshareVolume,
-            Volume,
+            DestroyVolume, MoveVolumeReplica, NodeId, NodeTopology, PoolId, Protocol,
+            PublishVolume, Replica, ReplicaId, ReplicaMove, ReplicaOwners, RepublishVolume,
+            ResizeVolume, SetVolumeProperty, SetVolumeReplica, ShareNexus, ShareVolume,
+            ShutdownNexus, UnpublishVolume, UnshareNexus, UnshareVolume, Volume, VolumeProperty,
+            VolumeShareProtocol,
         },
     },
 };
@@ -215,8 +216,12 @@ impl ResourceResize for OperationGuardArc<VolumeSpec> {
 
         // Pre-check - Ensure pools that host replicas have enough space to resize the replicas,
         // and also ensure that the replicas are Online.
-        let resizeable_replicas =
-            resizeable_replicas(&spec, registry, request.requested_size).await?;
+        // When shrinking, ensure the volume is unpublished and its data fits the new size.
+        let resizeable_replicas = if request.allow_shrink && request.requested_size < spec.size {
+            shrinkable_replicas(&spec, registry, request).await?
+        } else {
+            resizeable_replicas(&spec, registry, request.requested_size).await?
+        };
 
         let spec_clone = self
             .start_update(
@@ -340,6 +345,13 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
         request: &Self::Publish,
     ) -> Result<Self::PublishOutput, SvcError> {
         let state = registry.volume_state(&request.uuid).await?;
+        if request.read_only {
+            if let Some(target_cfg) = self.as_ref().active_config().cloned() {
+                return self
+                    .add_read_only_frontend(registry, request, &state, target_cfg)
+                    .await;
+            }
+        }
         let nexus_node = self
             .next_target_node(registry, request, &state, false)
             .await?;
@@ -352,8 +364,9 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
                 nexus_node.candidate(),
                 &request.share,
                 frontend_nodes,
+                request.read_only,
             )
-            .await;
+            .await?;
 
         let operation = VolumeOperation::Publish(PublishOperation::new(
             target_cfg.clone(),
@@ -361,38 +374,24 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
         ));
         let spec_clone = self.start_update(registry, &state, operation).await?;
 
-        // Create a Nexus on the requested or auto-selected node.
-        let result = self.create_nexus(registry, &target_cfg).await;
-
-        let (mut nexus, nexus_state) = self
+        // Create a Nexus on the requested or auto-selected node, and share it if it was requested.
+        // The nexus is committed to the store along with the volume target.
+        let mut linked = LinkedTxn::new();
+        let share = request
+            .share
+            .map(|share| (share, target_cfg.frontend().node_nqns()));
+        let result = self
+            .create_nexus(registry, &target_cfg, share, &mut linked)
+            .await;
+        let (mut nexus, _) = self
             .validate_update_step(registry, result, &spec_clone)
             .await?;
 
-        // Share the Nexus if it was requested.
-        let mut result = Ok(());
-        if let Some(share) = request.share {
-            let allowed_hosts = target_cfg.frontend().node_nqns();
-            result = match nexus
-                .share(
-                    registry,
-                    &ShareNexus::new(&nexus_state, share, allowed_hosts),
-                )
-                .await
-            {
-                Ok(_) => Ok(()),
-                Err(error) => {
-                    // Since we failed to share, we'll revert back to the previous state.
-                    // If we fail to do this inline, the reconcilers will pick up the slack.
-                    nexus
-                        .destroy(registry, &DestroyNexus::from(nexus_state).with_disown_all())
-                        .await
-                        .ok();
-                    Err(error)
-                }
-            }
-        }
-
-        self.complete_update(registry, result, spec_clone).await?;
+        let result = self
+            .complete_update_txn(registry, Ok(()), spec_clone, &mut linked)
+            .await;
+        nexus.complete_linked_op(registry, &mut linked).await;
+        result?;
 
         // If there was a previous nexus we should delete the persisted NexusInfo structure.
         if let Some(nexus_id) = last_target {
@@ -418,19 +417,31 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
         let specs = registry.specs();
 
         let state = registry.volume_state(&request.uuid).await?;
+        if let Some(frontend_node) = request.frontend_node() {
+            // other frontend nodes may still be using a read-only target
+            if self
+                .remove_read_only_frontend(registry, &state, frontend_node)
+                .await?
+            {
+                return Ok(());
+            }
+        }
 
         let spec_clone = self
             .start_update(registry, &state, VolumeOperation::Unpublish)
             .await?;
 
+        // The nexus is deleted from the store along with the volume target.
+        let mut linked = LinkedTxn::new();
         let volume_target = spec_clone.target().expect("already validated");
-        let result = match specs.nexus_opt(volume_target.nexus()).await? {
+        let mut nexus = specs.nexus_opt(volume_target.nexus()).await?;
+        let result = match &mut nexus {
             None => Ok(()),
-            Some(mut nexus) => {
+            Some(nexus) => {
                 let nexus_clone = nexus.lock().clone();
                 let destroy = DestroyNexus::from(&nexus_clone).with_disown(&request.uuid);
                 // Destroy the Nexus
-                match nexus.destroy(registry, &destroy).await {
+                match nexus.destroy_linked(registry, &destroy, &mut linked).await {
                     Ok(_) => Ok(()),
                     Err(error) if !request.force() => Err(error),
                     Err(error) => {
@@ -454,7 +465,13 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
             }
         };
 
-        self.complete_update(registry, result, spec_clone).await
+        let result = self
+            .complete_update_txn(registry, result, spec_clone, &mut linked)
+            .await;
+        if let Some(nexus) = &mut nexus {
+            nexus.complete_linked_op(registry, &mut linked).await;
+        }
+        result
     }
 
     async fn republish(
@@ -486,6 +503,15 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
                 vol_id: request.uuid.to_string(),
             }),
         }?;
+        // iSCSI initiators are not ANA multipath capable and so they cannot switchover to a new
+        // target, rather they keep on reconnecting to the existing one.
+        if let Some(share @ VolumeShareProtocol::Iscsi) = target_cfg.target().protocol() {
+            return Err(SvcError::InvalidShareProtocol {
+                kind: ResourceKind::Volume,
+                id: request.uuid.to_string(),
+                share: share.to_string(),
+            });
+        }
 
         let mut older_nexus = specs.nexus(target_cfg.target().nexus()).await?;
         let mut move_nexus = true;
@@ -542,8 +568,9 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
                 nexus_node.candidate(),
                 &Some(request.share),
                 &nodes,
+                target_cfg.frontend().read_only(),
             )
-            .await;
+            .await?;
         let operation = VolumeOperation::Republish(RepublishOperation::new(target_cfg.clone()));
 
         let spec_clone = self.start_update(registry, &state, operation).await?;
@@ -557,33 +584,22 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
         self.validate_update_step(registry, result, &spec_clone)
             .await?;
 
-        // Create a Nexus on the requested or auto-selected node.
-        let result = self.create_nexus(registry, &target_cfg).await;
-        let (mut nexus, nexus_state) = self
+        // Create and share a Nexus on the requested or auto-selected node.
+        // The nexus is committed to the store along with the volume target.
+        let mut linked = LinkedTxn::new();
+        let share = Some((request.share, target_cfg.frontend().node_nqns()));
+        let result = self
+            .create_nexus(registry, &target_cfg, share, &mut linked)
+            .await;
+        let (mut nexus, _) = self
             .validate_update_step(registry, result, &spec_clone)
             .await?;
-        let allowed_host = target_cfg.frontend().node_nqns();
-        // Share the Nexus.
-        let result = match nexus
-            .share(
-                registry,
-                &ShareNexus::new(&nexus_state, request.share, allowed_host),
-            )
-            .await
-        {
-            Ok(_) => Ok(()),
-            Err(error) => {
-                // Since we failed to share, we'll revert back to the previous state.
-                // If we fail to do this inline, the reconcilers will pick up the slack.
-                nexus
-                    .destroy(registry, &DestroyNexus::from(nexus_state).with_disown_all())
-                    .await
-                    .ok();
-                Err(error)
-            }
-        };
 
-        self.complete_update(registry, result, spec_clone).await?;
+        let result = self
+            .complete_update_txn(registry, Ok(()), spec_clone, &mut linked)
+            .await;
+        nexus.complete_linked_op(registry, &mut linked).await;
+        result?;
 
         let volume = registry.volume(&request.uuid).await?;
         registry
@@ -601,6 +617,10 @@ pub(crate) struct MoveReplicaRequest {
     /// Delete the moved replica after we've created the replacement replica?
     /// todo: we might only want to delete after rebuild completes only..
     delete: bool,
+    /// Only create the replacement replica on this pool, rather than on any suitable pool.
+    target_pool: Option<PoolId>,
+    /// Only create the replacement replica on this node, rather than on any suitable node.
+    target_node: Option<NodeId>,
 }
 impl MoveReplicaRequest {
     /// Get a reference to the replica.
@@ -618,6 +638,28 @@ impl From<&ENoSpcReplica> for MoveReplicaRequest {
         Self {
             replica: value.replica().uuid.clone(),
             delete: false,
+            target_pool: None,
+            target_node: None,
+        }
+    }
+}
+impl From<&ReplicaMove> for MoveReplicaRequest {
+    fn from(value: &ReplicaMove) -> Self {
+        Self {
+            replica: value.replica.clone(),
+            delete: false,
+            target_pool: Some(value.target_pool.clone()),
+            target_node: Some(value.target_node.clone()),
+        }
+    }
+}
+impl From<&MoveVolumeReplica> for MoveReplicaRequest {
+    fn from(value: &MoveVolumeReplica) -> Self {
+        Self {
+            replica: value.replica.clone(),
+            delete: false,
+            target_pool: value.pool.clone(),
+            target_node: value.node.clone(),
         }
     }
 }
@@ -654,18 +696,38 @@ impl ResourceReplicas for OperationGuardArc<VolumeSpec> {
         registry: &Registry,
         request: &Self::MoveRequest,
     ) -> Result<Self::MoveResp, SvcError> {
-        let candidates =
+        let nexus = self
+            .as_ref()
+            .target()
+            .and_then(|t| registry.specs().nexus_rsc(t.nexus()));
+        if request.delete && nexus.is_none() {
+            // Without a target the new replica is not rebuilt, so deleting the moved replica
+            // could lose the volume data.
+            return Err(SvcError::ReplicaMoveNotPublished {
+                volume: self.uuid().to_string(),
+                replica: request.replica().to_string(),
+            });
+        }
+
+        let mut candidates =
             volume_move_replica_candidates(registry, self.as_ref(), request.replica()).await?;
+        if let Some(pool) = &request.target_pool {
+            candidates.retain(|candidate| &candidate.pool_id == pool);
+        }
+        if let Some(node) = &request.target_node {
+            candidates.retain(|candidate| &candidate.node == node);
+        }
+        if candidates.is_empty() {
+            return Err(SvcError::NotEnoughResources {
+                source: NotEnough::OfPools { have: 0, need: 1 },
+            });
+        }
 
         let new_replica = self
             .create_volume_replica_with(registry, candidates)
             .await?;
 
-        if let Some(nexus_spec) = &self
-            .as_ref()
-            .target()
-            .and_then(|t| registry.specs().nexus_rsc(t.nexus()))
-        {
+        if let Some(nexus_spec) = &nexus {
             let mut guard = nexus_spec.operation_guard()?;
             guard.attach_replica(registry, &new_replica).await?;
 
@@ -673,9 +735,6 @@ impl ResourceReplicas for OperationGuardArc<VolumeSpec> {
                 self.remove_child_replica(request.replica(), &mut guard, registry)
                     .await?;
             }
-        } else if request.delete {
-            // todo: if there's no nexus, should we delete it?
-            // For now let the reconciler delete it?
         }
 
         Ok(new_replica)
@@ -695,7 +754,17 @@ impl ResourceProperty for OperationGuardArc<VolumeSpec> {
         let operation = VolumeOperation::SetVolumeProperty(request.property.clone());
         let spec_clone = self.start_update(registry, &state, operation).await?;
 
-        self.complete_update(registry, Ok(()), spec_clone).await?;
+        let result = match &request.property {
+            // the limits are applied when the target is created, so only the current one
+            // needs to be updated
+            VolumeProperty::Qos(qos) => self.set_target_qos(registry, qos).await,
+            VolumeProperty::EncryptionKey(key_ref) => {
+                self.verify_encryption_key(registry, key_ref).await
+            }
+            VolumeProperty::MaxSnapshots(_) => Ok(()),
+        };
+
+        self.complete_update(registry, result, spec_clone).await?;
         Ok(())
     }
 }
@@ -884,20 +953,27 @@ impl ResourceLifecycleExt<CreateVolumeSource<'_>> for OperationGuardArc<VolumeSp
         // If the volume is a part of the ag, create or update accordingly.
         registry.specs().get_or_create_affinity_group(&volume_clone);
 
-        let context = Context {
+        let mut context = Context {
             registry,
             volume: &mut volume,
+            linked: LinkedTxn::new(),
+            replicas: vec![],
         };
         let result = match request_src {
-            CreateVolumeSource::None(params) => params.run(context).await,
-            CreateVolumeSource::Snapshot(params) => params.run(context).await,
+            CreateVolumeSource::None(params) => params.run(&mut context).await,
+            CreateVolumeSource::Snapshot(params) => params.run(&mut context).await,
         };
 
         // we can destroy volume on error because there's no volume resource created on the nodes,
         // only sub-resources (such as nexuses/replicas which will be garbage-collected later).
-        volume
-            .complete_create(result, registry, OnCreateFail::Delete)
-            .await?;
+        // The replicas are committed to the store along with the volume, so they are not left
+        // behind without their owner, nor the volume without its replicas.
+        let result = context
+            .volume
+            .complete_create_txn(result, registry, OnCreateFail::Delete, &mut context.linked)
+            .await;
+        context.complete_replicas().await;
+        result?;
         Ok(volume)
     }
 }
@@ -924,6 +1000,22 @@ impl CreateVolumeSource<'_> {
 pub(super) struct Context<'a> {
     pub(super) registry: &'a Registry,
     pub(super) volume: &'a mut OperationGuardArc<VolumeSpec>,
+    /// The created replicas, which are committed to the store along with the volume.
+    pub(super) linked: LinkedTxn,
+    /// The guards of the created replicas, held until they're committed.
+    pub(super) replicas: Vec<OperationGuardArc<ReplicaSpec>>,
+}
+
+impl Context<'_> {
+    /// Complete the creation of the replicas, once the linked transaction has been attempted.
+    /// If it was not, the replicas are committed on their own.
+    pub(super) async fn complete_replicas(&mut self) {
+        for mut replica in self.replicas.drain(..) {
+            replica
+                .complete_linked_op(self.registry, &mut self.linked)
+                .await;
+        }
+    }
 }
 
 /// Trait that abstracts away the pre-flight validation checks when creating a volume.
@@ -936,18 +1028,20 @@ pub(super) trait CreateVolumeExeVal: Sync + Send {
 pub(super) trait CreateVolumeExe: CreateVolumeExeVal {
     type Candidates: Send + Sync;
 
-    async fn run<'a>(&'a self, mut context: Context<'a>) -> Result<Vec<Replica>, SvcError> {
-        let result = self.setup(&mut context).await;
+    async fn run<'a>(&'a self, context: &mut Context<'a>) -> Result<Vec<Replica>, SvcError> {
+        let result = self.setup(context).await;
         let candidates = context
             .volume
             .validate_create_step_ext(context.registry, result, OnCreateFail::Delete)
             .await?;
-        let replicas = self.create(&mut context, candidates).await;
+        let replicas = self.create(context, candidates).await;
 
         // we can't fulfil the required replication factor, so let the caller
         // decide what to do next
         if replicas.len() < context.volume.as_ref().num_replicas as usize {
-            self.undo(&mut context, replicas).await;
+            // the replicas must be committed on their own so they can be undone
+            context.complete_replicas().await;
+            self.undo(context, replicas).await;
             Err(SvcError::ReplicaCreateNumber {
                 id: context.volume.uid_str(),
             })
@@ -970,6 +1064,21 @@ impl CreateVolumeExeVal for CreateVolume {
             self.allowed_nodes().is_empty() || self.allowed_nodes().len() >= self.replicas as usize,
             agents::errors::InvalidArguments {}
         );
+        if let Some(qos) = &self.qos {
+            qos.validate()
+                .map_err(|reason| SvcError::InvalidVolumeQos {
+                    vol_id: self.uuid.to_string(),
+                    reason,
+                })?;
+        }
+        if let Some(key_ref) = &self.encryption {
+            key_ref
+                .validate()
+                .map_err(|reason| SvcError::InvalidVolumeEncryption {
+                    vol_id: self.uuid.to_string(),
+                    reason,
+                })?;
+        }
         Ok(())
     }
 }
@@ -1018,8 +1127,15 @@ impl CreateVolumeExe for CreateVolume {
             } else {
                 replica.clone()
             };
-            match OperationGuardArc::<ReplicaSpec>::create(context.registry, &replica).await {
-                Ok(replica) => {
+            match OperationGuardArc::<ReplicaSpec>::create_linked(
+                context.registry,
+                &replica,
+                &mut context.linked,
+            )
+            .await
+            {
+                Ok((guard, replica)) => {
+                    context.replicas.push(guard);
                     replicas.push(replica);
                 }
                 Err(error) => {
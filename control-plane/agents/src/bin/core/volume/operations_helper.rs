@@ -1,16 +1,18 @@
 use crate::{
     controller::{
+        io_engine::NexusApi,
         registry::Registry,
         resources::{
-            operations::{ResourceLifecycle, ResourceOwnerUpdate, ResourceResize},
+            operations::{ResourceLifecycle, ResourceReplicas, ResourceResize, ResourceSharing},
             operations_helper::{
-                GuardedOperationsHelper, OperationSequenceGuard, ResourceSpecsLocked,
+                GuardedOperationsHelper, LinkedTxn, OperationSequenceGuard, ResourceSpecsLocked,
             },
             OperationGuardArc, ResourceUid, TraceSpan, TraceStrLog,
         },
         scheduling::resources::{HealthyChildItems, ReplicaItem},
     },
     volume::{
+        operations::MoveReplicaRequest,
         scheduling::target_node_candidate,
         specs::{
             healthy_volume_replicas, nexus_attach_candidates, nexus_child_remove_candidates,
@@ -29,12 +31,16 @@ use stor_port::{
             nexus::{NexusSpec, ReplicaUri},
             nexus_child::NexusChild,
             replica::ReplicaSpec,
-            volume::{FrontendConfig, TargetConfig, VolumeOperation, VolumeSpec, VolumeTarget},
+            volume::{
+                FrontendConfig, InitiatorAccess, TargetConfig, VolumeOperation, VolumeReplicaMove,
+                VolumeSpec, VolumeTarget,
+            },
         },
         transport::{
-            CreateNexus, CreateReplica, Nexus, NexusId, NexusNvmePreemption, NexusNvmfConfig,
-            NodeId, NvmeReservation, NvmfControllerIdRange, Protocol, Replica, ReplicaId,
-            ReplicaOwners, ResizeNexus, ResizeReplica, Volume, VolumeShareProtocol, VolumeState,
+            CreateNexus, CreateReplica, EncryptionKeyRef, HostNqn, Nexus, NexusId,
+            NexusNvmePreemption, NexusNvmfConfig, NodeId, NvmeReservation, NvmfControllerIdRange,
+            Protocol, PublishVolume, Replica, ReplicaId, ReplicaOwners, ResizeNexus, ResizeReplica,
+            SetNexusQos, ShareNexus, Volume, VolumeQos, VolumeShareProtocol, VolumeState,
         },
     },
     HostAccessControl,
@@ -57,7 +63,8 @@ impl OperationGuardArc<VolumeSpec> {
         node: &NodeId,
         share: &Option<VolumeShareProtocol>,
         frontend_nodes: &[String],
-    ) -> TargetConfig {
+        read_only: bool,
+    ) -> Result<TargetConfig, SvcError> {
         let nexus = NexusId::new();
         let resv_key = nexus.as_u128() as u64;
         let range = match self.as_ref().config() {
@@ -75,9 +82,18 @@ impl OperationGuardArc<VolumeSpec> {
                 }
             }
         };
-        let host_acl = registry.host_acl_nodename(HostAccessControl::Nexuses, frontend_nodes);
-        let frontend = FrontendConfig::from_acls(host_acl);
-        TargetConfig::new(
+        let frontend = match read_only {
+            true => FrontendConfig::from_read_only_acls(
+                registry.initiators_nodename(*share, frontend_nodes)?,
+                registry.host_acl_enabled(HostAccessControl::Nexuses),
+            ),
+            false => FrontendConfig::from_acls(registry.host_acl_nodename(
+                HostAccessControl::Nexuses,
+                *share,
+                frontend_nodes,
+            )?),
+        };
+        Ok(TargetConfig::new(
             VolumeTarget::new(node.clone(), nexus, *share),
             NexusNvmfConfig::new(
                 range,
@@ -86,7 +102,7 @@ impl OperationGuardArc<VolumeSpec> {
                 NexusNvmePreemption::Holder,
             ),
             frontend,
-        )
+        ))
     }
 
     /// Remove the given NexusChild Replica but make sure we're not removing the last healthy
@@ -162,6 +178,126 @@ impl OperationGuardArc<VolumeSpec> {
         Ok(())
     }
 
+    /// Remove the given replica from the volume target, eg: the moved replica once it has been
+    /// replaced by a new replica, or the new replica when the replica move is cancelled.
+    /// The removed replica is then garbage collected as the volume has one replica too many.
+    pub(crate) async fn retire_replica(
+        &mut self,
+        registry: &Registry,
+        replica_id: &ReplicaId,
+    ) -> Result<(), SvcError> {
+        let Some(nexus) = self
+            .as_ref()
+            .target()
+            .and_then(|t| registry.specs().nexus_rsc(t.nexus()))
+        else {
+            return Err(SvcError::VolumeNotPublished {
+                vol_id: self.uuid().to_string(),
+            });
+        };
+        let mut nexus = nexus.operation_guard()?;
+        self.remove_child_replica(replica_id, &mut nexus, registry)
+            .await
+    }
+
+    /// Start moving a replica of the volume onto another pool.
+    /// The new replica is added to the volume target and the move is recorded in the volume spec,
+    /// allowing the moved replica to be retired once the new replica has been rebuilt.
+    /// If the move cannot be recorded, the hot spare reconciler removes the excess replica.
+    /// The volume must be published, as the new replica is rebuilt by the volume target:
+    /// an unpublished volume fails with `SvcError::ReplicaMoveNotPublished`.
+    pub(crate) async fn start_replica_move(
+        &mut self,
+        registry: &Registry,
+        request: &MoveReplicaRequest,
+    ) -> Result<Replica, SvcError> {
+        if let Some(replica_move) = &self.as_ref().replica_move {
+            return Err(SvcError::ReplicaMoveInProgress {
+                volume: self.uuid().to_string(),
+                replica: replica_move.replica.to_string(),
+            });
+        }
+        if self.as_ref().target().is_none() {
+            return Err(SvcError::ReplicaMoveNotPublished {
+                volume: self.uuid().to_string(),
+                replica: request.replica().to_string(),
+            });
+        }
+        if !registry
+            .specs()
+            .volume_replicas(self.uuid())
+            .iter()
+            .any(|replica| &replica.lock().uuid == request.replica())
+        {
+            return Err(SvcError::ReplicaNotFound {
+                replica_id: request.replica().clone(),
+            });
+        }
+
+        let new_replica = self.move_replica(registry, request).await?;
+
+        let state = registry.volume_state(self.uuid()).await?;
+        let operation = VolumeOperation::StartReplicaMove(VolumeReplicaMove {
+            replica: request.replica().clone(),
+            new_replica: new_replica.uuid.clone(),
+        });
+        let spec_clone = self.start_update(registry, &state, operation).await?;
+        self.complete_update(registry, Ok(()), spec_clone).await?;
+        Ok(new_replica)
+    }
+
+    /// Complete the replica move in progress by retiring the moved replica.
+    /// This must only be called once the new replica has been rebuilt.
+    pub(crate) async fn complete_replica_move(
+        &mut self,
+        registry: &Registry,
+    ) -> Result<(), SvcError> {
+        let Some(replica_move) = self.as_ref().replica_move.clone() else {
+            return Ok(());
+        };
+        match self.retire_replica(registry, &replica_move.replica).await {
+            Ok(_) | Err(SvcError::ChildNotFound { .. }) => {}
+            Err(error) => return Err(error),
+        }
+        self.end_replica_move(registry).await
+    }
+
+    /// Cancel the replica move in progress by retiring the new replica, keeping the moved replica.
+    pub(crate) async fn cancel_replica_move(
+        &mut self,
+        registry: &Registry,
+        replica_id: &ReplicaId,
+    ) -> Result<(), SvcError> {
+        let replica_move = match &self.as_ref().replica_move {
+            Some(replica_move) if &replica_move.replica == replica_id => replica_move.clone(),
+            _ => {
+                return Err(SvcError::ReplicaMoveNotFound {
+                    volume: self.uuid().to_string(),
+                    replica: replica_id.to_string(),
+                })
+            }
+        };
+        match self
+            .retire_replica(registry, &replica_move.new_replica)
+            .await
+        {
+            Ok(_)
+            | Err(SvcError::ChildNotFound { .. })
+            | Err(SvcError::VolumeNotPublished { .. }) => {}
+            Err(error) => return Err(error),
+        }
+        self.end_replica_move(registry).await
+    }
+
+    /// Forget the replica move in progress, leaving any excess replica to be garbage collected.
+    pub(crate) async fn end_replica_move(&mut self, registry: &Registry) -> Result<(), SvcError> {
+        let state = registry.volume_state(self.uuid()).await?;
+        let spec_clone = self
+            .start_update(registry, &state, VolumeOperation::EndReplicaMove)
+            .await?;
+        self.complete_update(registry, Ok(()), spec_clone).await
+    }
+
     /// Increase the replica count of the given volume by 1
     /// Creates a new data replica from a list of candidates
     /// Adds the replica to the volume nexuses (if any)
@@ -183,15 +319,36 @@ impl OperationGuardArc<VolumeSpec> {
             .validate_update_step(registry, result, &spec_clone)
             .await?;
 
-        // Create the data replica from the pool candidates
-        let result = self.create_volume_replica_with(registry, candidates).await;
-        let replica = self
+        // Create the data replica from the pool candidates, committing it to the store along with
+        // the volume
+        let mut linked = LinkedTxn::new();
+        let result = self
+            .create_volume_replica_linked(registry, candidates, &mut linked)
+            .await;
+        let (mut replica_guard, replica) = self
             .validate_update_step(registry, result, &spec_clone)
             .await?;
+        let result = self
+            .complete_update_txn(registry, Ok(()), spec_clone, &mut linked)
+            .await;
+        replica_guard
+            .complete_linked_op(registry, &mut linked)
+            .await;
+        // attaching the replica to the nexus requires the replica guard
+        drop(replica_guard);
+        result?;
 
-        // Add the newly created replica to the nexus, if it's up
-        let result = self.attach_to_target(registry, replica).await;
-        self.complete_update(registry, result, spec_clone).await?;
+        // Add the newly created replica to the nexus, if it's up.
+        // The replica is already part of the volume, so if it can't be attached now, the volume
+        // reconciler will attach it later.
+        if let Err(error) = self.attach_to_target(registry, replica).await {
+            self.warn_span(|| {
+                tracing::warn!(
+                    "Failed to attach the new replica to the volume target: {}",
+                    error.full_string()
+                )
+            });
+        }
 
         registry.volume(&state.uuid).await
     }
@@ -229,10 +386,26 @@ impl OperationGuardArc<VolumeSpec> {
                 .await?;
 
             // todo: we could ignore it here, since we've already removed it from the nexus
-            //  now remove the replica from the pool
-            let result = self.destroy_replica(registry, remove.spec()).await;
+            //  now remove the replica from the pool, deleting it from the store along with the
+            //  volume update
+            let mut linked = LinkedTxn::new();
+            let (mut replica, result) = match registry.specs().replica(&remove.spec().uuid).await {
+                Ok(mut replica) => {
+                    let result = self
+                        .destroy_replica_linked(registry, &mut replica, &mut linked)
+                        .await;
+                    (Some(replica), result)
+                }
+                Err(error) => (None, Err(error)),
+            };
 
-            self.complete_update(registry, result, spec_clone).await?;
+            let result = self
+                .complete_update_txn(registry, result, spec_clone, &mut linked)
+                .await;
+            if let Some(replica) = &mut replica {
+                replica.complete_linked_op(registry, &mut linked).await;
+            }
+            result?;
         }
 
         registry.volume(&state.uuid).await
@@ -305,16 +478,26 @@ impl OperationGuardArc<VolumeSpec> {
             .validate_update_step(registry, replica, &spec_clone)
             .await?;
 
-        // disown it from the volume first, so at the very least it can be garbage collected
-        // at a later point if the node is not accessible
+        // disown it from the volume, atomically with completing the volume update, so at the very
+        // least it can be garbage collected at a later point if the node is not accessible
         let disowner = ReplicaOwners::from_volume(self.uuid());
-        let result = replica.remove_owners(registry, &disowner, true).await;
+        let mut linked = LinkedTxn::new();
+        let result = replica
+            .start_linked_remove_owners(registry, &disowner, &mut linked)
+            .await;
         self.validate_update_step(registry, result, &spec_clone)
             .await?;
 
-        // the garbage collector will destroy it at a later time
+        let result = self
+            .complete_update_txn(registry, Ok(()), spec_clone, &mut linked)
+            .await;
+        replica.complete_linked_op(registry, &mut linked).await;
+
+        // even if the store is not accessible we still attempt to destroy it, as before, though it
+        // can't be destroyed while its disowning is not stored: the garbage collector will destroy
+        // it at a later time
         if let Err(error) = replica.destroy_volume_replica(registry, None).await {
-            spec_clone.error_span(|| {
+            self.error_span(|| {
                 tracing::error!(
                     "Failed to destroy replica '{}'. Error: '{}'. It will be garbage collected.",
                     replica_id,
@@ -322,8 +505,7 @@ impl OperationGuardArc<VolumeSpec> {
                 )
             });
         }
-        self.complete_update(registry, Ok(()), spec_clone).await?;
-        Ok(())
+        result
     }
 
     /// Attach existing replicas to the given volume nexus until it reaches the required number of
@@ -467,14 +649,32 @@ impl OperationGuardArc<VolumeSpec> {
         let mut result = Err(SvcError::NotEnoughResources {
             source: NotEnough::OfReplicas { have: 0, need: 1 },
         });
-        for mut attempt in candidates {
-            if let Some(target) = &self.as_ref().target() {
-                if target.node() == &attempt.node {
-                    attempt.share = Protocol::None;
-                }
+        for attempt in candidates {
+            let attempt = self.local_replica_unshared(attempt);
+            result = OperationGuardArc::<ReplicaSpec>::create(registry, &attempt).await;
+            if result.is_ok() {
+                break;
             }
+        }
+        result
+    }
 
-            result = OperationGuardArc::<ReplicaSpec>::create(registry, &attempt).await;
+    /// Create a replica for the given volume using the provided list of candidates in order.
+    /// The replica is linked to the volume update, see
+    /// `OperationGuardArc::<ReplicaSpec>::create_linked`.
+    async fn create_volume_replica_linked(
+        &self,
+        registry: &Registry,
+        candidates: Vec<CreateReplica>,
+        linked: &mut LinkedTxn,
+    ) -> Result<(OperationGuardArc<ReplicaSpec>, Replica), SvcError> {
+        let mut result = Err(SvcError::NotEnoughResources {
+            source: NotEnough::OfReplicas { have: 0, need: 1 },
+        });
+        for attempt in candidates {
+            let attempt = self.local_replica_unshared(attempt);
+            result =
+                OperationGuardArc::<ReplicaSpec>::create_linked(registry, &attempt, linked).await;
             if result.is_ok() {
                 break;
             }
@@ -482,6 +682,16 @@ impl OperationGuardArc<VolumeSpec> {
         result
     }
 
+    /// A replica which is local to the volume target must not be shared.
+    fn local_replica_unshared(&self, mut replica: CreateReplica) -> CreateReplica {
+        if let Some(target) = &self.as_ref().target() {
+            if target.node() == &replica.node {
+                replica.share = Protocol::None;
+            }
+        }
+        replica
+    }
+
     /// Create `count` replicas for the given volume.
     pub(crate) async fn create_volume_replicas(
         &self,
@@ -574,6 +784,162 @@ impl OperationGuardArc<VolumeSpec> {
         Ok(())
     }
 
+    /// Set the IO limits on the volume target(nexus), if the volume is published.
+    pub(super) async fn set_target_qos(
+        &self,
+        registry: &Registry,
+        qos: &VolumeQos,
+    ) -> Result<(), SvcError> {
+        let Some(target) = self.as_ref().target() else {
+            return Ok(());
+        };
+        let nexus = registry.nexus(target.nexus()).await?;
+        let node = registry.node_wrapper(target.node()).await?;
+        node.set_nexus_qos(&SetNexusQos::new(
+            &nexus.node,
+            &nexus.uuid,
+            nexus.name,
+            qos.clone(),
+        ))
+        .await
+    }
+
+    /// Verify that the new key reference resolves to the key which encrypts the volume.
+    /// The io-engine cannot re-encrypt the volume data, so only the reference to the key may be
+    /// rotated, example: when the key is moved to another provider.
+    pub(super) async fn verify_encryption_key(
+        &self,
+        registry: &Registry,
+        key_ref: &EncryptionKeyRef,
+    ) -> Result<(), SvcError> {
+        let keys = registry.encryption_keys();
+        let key = keys.resolve(key_ref).await?;
+        let Some(current_ref) = &self.as_ref().encryption else {
+            return Ok(());
+        };
+        match keys.resolve(current_ref).await {
+            Ok(current_key) if current_key != key => Err(SvcError::InvalidVolumeEncryption {
+                vol_id: self.uuid().to_string(),
+                reason: format!("the key '{key_ref}' does not match the key '{current_ref}'"),
+            }),
+            Ok(_) => Ok(()),
+            Err(error) => {
+                // the current key may no longer be available, which is likely why it's rotated
+                self.warn_span(|| {
+                    tracing::warn!(
+                        %error,
+                        "Cannot verify the new encryption key against the current key"
+                    )
+                });
+                Ok(())
+            }
+        }
+    }
+
+    /// Allow the frontend nodes of the request to access the current read-only target.
+    /// The target nexus is re-shared with the extended host access list.
+    pub(super) async fn add_read_only_frontend(
+        &mut self,
+        registry: &Registry,
+        request: &PublishVolume,
+        state: &VolumeState,
+        target_cfg: TargetConfig,
+    ) -> Result<Volume, SvcError> {
+        let target = target_cfg.target();
+        if !target_cfg.frontend().read_only() || request.share.as_ref() != target.protocol() {
+            return Err(SvcError::VolumeAlreadyPublished {
+                vol_id: self.uuid().to_string(),
+                node: target.node().to_string(),
+                protocol: format!("{:?}", target.protocol()),
+            });
+        }
+
+        let frontend = target_cfg.frontend().with_acls(
+            registry
+                .initiators_nodename(request.share, &request.frontend_nodes)?
+                .into_iter()
+                .map(|h| h.with_access(InitiatorAccess::ReadOnly))
+                .collect(),
+        );
+        if &frontend == target_cfg.frontend() {
+            // the frontend nodes are already allowed
+            return registry.volume(self.uuid()).await;
+        }
+
+        let spec_clone = self
+            .start_update(
+                registry,
+                state,
+                VolumeOperation::UpdateFrontend(frontend.clone()),
+            )
+            .await?;
+
+        let result = self.reshare_target(registry, target, &frontend).await;
+        self.complete_update(registry, result, spec_clone).await?;
+
+        registry.volume(self.uuid()).await
+    }
+
+    /// Remove the given frontend node from the current read-only target.
+    /// Returns false if the target should be unpublished instead, ie: when it's not a read-only
+    /// target or when the node is its last frontend node.
+    pub(super) async fn remove_read_only_frontend(
+        &mut self,
+        registry: &Registry,
+        state: &VolumeState,
+        frontend_node: &str,
+    ) -> Result<bool, SvcError> {
+        let Some(target_cfg) = self.as_ref().active_config().cloned() else {
+            return Ok(false);
+        };
+        let frontend = target_cfg.frontend().without_nodename(frontend_node);
+        if !frontend.read_only() || frontend.nodes_info().is_empty() {
+            return Ok(false);
+        }
+        if &frontend == target_cfg.frontend() {
+            // the frontend node was already removed
+            return Ok(true);
+        }
+
+        let spec_clone = self
+            .start_update(
+                registry,
+                state,
+                VolumeOperation::UpdateFrontend(frontend.clone()),
+            )
+            .await?;
+        let result = self
+            .reshare_target(registry, target_cfg.target(), &frontend)
+            .await;
+        self.complete_update(registry, result, spec_clone).await?;
+        Ok(true)
+    }
+
+    /// Share the target nexus with the host access list of the given frontend configuration.
+    /// Nothing to do if the target is not shared or if the host access list is not enforced.
+    async fn reshare_target(
+        &self,
+        registry: &Registry,
+        target: &VolumeTarget,
+        frontend: &FrontendConfig,
+    ) -> Result<(), SvcError> {
+        let Some(share) = target.protocol().cloned() else {
+            return Ok(());
+        };
+        if !frontend.restricted() {
+            return Ok(());
+        }
+        let mut nexus = registry.specs().nexus(target.nexus()).await?;
+        let nexus_state = registry.nexus(target.nexus()).await?;
+        nexus
+            .share(
+                registry,
+                &ShareNexus::new(&nexus_state, share, frontend.node_nqns()),
+            )
+            .await?;
+        Ok(())
+    }
+
     /// Add the given replica to the target nexus of the volume.
     async fn attach_to_target(
         &self,
@@ -612,12 +978,15 @@ impl OperationGuardArc<VolumeSpec> {
         }
     }
 
-    /// Create a nexus for the given volume on the specified target_node.
+    /// Create a nexus for the given volume on the specified target_node, and share it if requested.
     /// Existing replicas may be shared/unshared so we can connect to them.
+    /// The nexus is linked to the volume update, see `OperationGuardArc::<NexusSpec>::create_linked`.
     pub(super) async fn create_nexus(
         &self,
         registry: &Registry,
         target_config: &TargetConfig,
+        share: Option<(VolumeShareProtocol, Vec<HostNqn>)>,
+        linked: &mut LinkedTxn,
     ) -> Result<(OperationGuardArc<NexusSpec>, Nexus), SvcError> {
         let vol_spec = self.as_ref();
         let target_node = target_config.target().node();
@@ -661,7 +1030,7 @@ impl OperationGuardArc<VolumeSpec> {
         }
 
         // Create the nexus on the requested node
-        let (guard, nexus) = OperationGuardArc::<NexusSpec>::create(
+        let (guard, nexus) = OperationGuardArc::<NexusSpec>::create_linked(
             registry,
             &CreateNexus::new(
                 target_node,
@@ -671,7 +1040,10 @@ impl OperationGuardArc<VolumeSpec> {
                 true,
                 Some(&vol_spec.uuid),
                 Some(target_config.config().clone()),
-            ),
+            )
+            .with_qos(vol_spec.qos.clone()),
+            share,
+            linked,
         )
         .await?;
 
@@ -753,22 +1125,24 @@ impl OperationGuardArc<VolumeSpec> {
     }
 
     /// Disown and if possible destroy the given volume replica.
-    pub(super) async fn destroy_replica(
+    /// The replica is linked to the volume update, see
+    /// `OperationGuardArc::<ReplicaSpec>::destroy_linked`.
+    async fn destroy_replica_linked(
         &self,
         registry: &Registry,
-        replica_spec: &ReplicaSpec,
+        replica: &mut OperationGuardArc<ReplicaSpec>,
+        linked: &mut LinkedTxn,
     ) -> Result<(), SvcError> {
-        match ResourceSpecsLocked::replica_node(registry, replica_spec).await {
+        let replica_spec = replica.lock().clone();
+        match ResourceSpecsLocked::replica_node(registry, &replica_spec).await {
             // Should never happen, but just in case...
             None => Err(SvcError::Internal {
                 details: "Failed to find the node where a replica lives".to_string(),
             }),
             Some(node) => {
                 let destroy_by = ReplicaOwners::from_volume(self.uuid());
-                let mut replica = registry.specs().replica(&replica_spec.uuid).await?;
-                replica
-                    .destroy(registry, &replica.destroy_request(destroy_by, &node))
-                    .await
+                let request = replica.destroy_request(destroy_by, &node);
+                replica.destroy_linked(registry, &request, linked).await
             }
         }
     }
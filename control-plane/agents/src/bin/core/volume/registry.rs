@@ -76,7 +76,10 @@ impl Registry {
         // Construct the topological information for the volume replicas.
         let mut replica_topology = HashMap::new();
         for replica_spec in &replica_specs {
-            let replica = self.replica_topology(replica_spec, &nexus).await;
+            let replica = self
+                .replica_topology(replica_spec, &nexus)
+                .await
+                .with_replica_move(volume_spec.replica_move_role(&replica_spec.uuid));
             if let Some(usage) = replica.usage() {
                 let allocated = usage.allocated();
                 let allocated_snaps = usage.allocated_snapshots();
@@ -295,6 +298,13 @@ impl Registry {
                 })?;
 
         let snapshot = snapshot.lock().clone();
+        // Internal snapshots are hidden from the user.
+        if snapshot.spec().internal() {
+            return Err(SvcError::VolSnapshotNotFound {
+                snap_id: snap_id.to_string(),
+                source_id: vol_id.map(|v| v.to_string()),
+            });
+        }
         if let Some(vol_id) = vol_id {
             let snap_source_id = snapshot.spec().source_id();
 
@@ -323,7 +333,7 @@ impl Registry {
 
         let mut snapshots = Vec::with_capacity(snap_specs.len());
 
-        for spec in snap_specs {
+        for spec in snap_specs.into_iter().filter(|s| !s.spec().internal()) {
             let state = self.snapshot_state(&spec).await;
             snapshots.push(grpc_mod::VolumeSnapshot::new(&spec, state));
         }
@@ -336,7 +346,7 @@ impl Registry {
         let snap_specs = self.specs().snapshots();
         let mut snapshots = Vec::with_capacity(snap_specs.len());
 
-        for spec in snap_specs {
+        for spec in snap_specs.into_iter().filter(|s| !s.spec().internal()) {
             let state = self.snapshot_state(&spec).await;
             snapshots.push(grpc_mod::VolumeSnapshot::new(&spec, state));
         }
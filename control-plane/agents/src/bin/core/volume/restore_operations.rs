@@ -0,0 +1,245 @@
+use crate::controller::{
+    registry::Registry,
+    resources::{
+        operations::{ResourceLifecycle, ResourceLifecycleExt, ResourceOwnerUpdate},
+        operations_helper::{
+            GuardedOperationsHelper, OperationSequenceGuard, ResourceSpecsLocked,
+            SpecOperationsHelper,
+        },
+        OperationGuardArc, TraceSpan, TraceStrLog,
+    },
+};
+use agents::errors::{self, SvcError};
+use stor_port::{
+    transport_api::ErrorChain,
+    types::v0::{
+        store::{
+            nexus_persistence::NexusInfoKey,
+            replica::{PoolRef, ReplicaSpec},
+            snapshots::volume::VolumeSnapshot,
+            volume::{
+                VolumeOperation, VolumeSnapshotRestore, VolumeSnapshotRestoreClone, VolumeSpec,
+            },
+        },
+        transport::{ReplicaId, ReplicaOwners, SnapshotCloneParameters, SnapshotCloneSpecParams},
+    },
+};
+
+impl OperationGuardArc<VolumeSpec> {
+    /// Restore the volume in place to the given snapshot of the volume.
+    /// Each replica snapshot is cloned into a new replica of the volume and the current replicas
+    /// are then destroyed. Replicas which did not have the snapshot are not replaced by a clone,
+    /// and so they're rebuilt from the clones once the volume is published.
+    /// The restore is recorded in the volume spec, allowing it to be resumed if interrupted.
+    pub(crate) async fn restore_snapshot(
+        &mut self,
+        registry: &Registry,
+        snapshot: &OperationGuardArc<VolumeSnapshot>,
+    ) -> Result<(), SvcError> {
+        let snap = snapshot.as_ref();
+        if snap.spec().source_id() != self.uuid() {
+            return Err(SvcError::InvalidSnapshotSource {
+                snap_id: snap.spec().uuid().to_string(),
+                invalid_source_id: self.uuid().to_string(),
+                correct_source_id: snap.spec().source_id().to_string(),
+            });
+        }
+        snafu::ensure!(snap.status().created(), errors::SnapshotNotCreated {});
+        if snap.metadata().spec_size() != self.as_ref().size {
+            return Err(SvcError::SnapshotRestoreSize {
+                snap_id: snap.spec().uuid().to_string(),
+                vol_id: self.uuid().to_string(),
+            });
+        }
+
+        let clones = match snap.metadata().replica_snapshots() {
+            Some(snapshots) if !snapshots.is_empty() => snapshots
+                .iter()
+                .map(|snapshot| VolumeSnapshotRestoreClone {
+                    replica_snapshot: snapshot.spec().uuid().clone(),
+                    replica: ReplicaId::new(),
+                })
+                .collect(),
+            _ => {
+                return Err(SvcError::NoHealthyReplicas {
+                    id: self.uuid().to_string(),
+                })
+            }
+        };
+        let restore = VolumeSnapshotRestore {
+            snapshot: snap.spec().uuid().clone(),
+            replicas: registry
+                .specs()
+                .volume_replicas(self.uuid())
+                .iter()
+                .map(|replica| replica.lock().uuid.clone())
+                .collect(),
+            clones,
+        };
+
+        let state = registry.volume_state(self.uuid()).await?;
+        let spec_clone = self
+            .start_update(
+                registry,
+                &state,
+                VolumeOperation::RestoreSnapshot(restore.clone()),
+            )
+            .await?;
+
+        let result = self.restore_snapshot_replicas(registry, &restore).await;
+        self.complete_update(registry, result, spec_clone).await
+    }
+
+    /// Resume the snapshot restore which was interrupted, eg: by a restart of the core agent.
+    /// Returns whether the restore has now been handled.
+    pub(crate) async fn resume_snapshot_restore(&mut self, registry: &Registry) -> bool {
+        let Some(restore) = self.as_ref().snapshot_restore().cloned() else {
+            return true;
+        };
+        let spec_clone = self.lock().clone();
+
+        let result = self.restore_snapshot_replicas(registry, &restore).await;
+        match self.complete_update(registry, result, spec_clone).await {
+            Ok(_) => self.info_span(|| {
+                tracing::info!(
+                    snapshot.uuid = restore.snapshot.as_str(),
+                    "Resumed the snapshot restore"
+                )
+            }),
+            Err(error) => self.error_span(|| {
+                tracing::error!(
+                    snapshot.uuid = restore.snapshot.as_str(),
+                    error = error.full_string(),
+                    "Failed to resume the snapshot restore"
+                )
+            }),
+        }
+        !self.as_ref().dirty()
+    }
+
+    /// Replace the volume replicas with the clones of the replica snapshots.
+    /// Clones which have already been created are reused, allowing the restore to be retried.
+    async fn restore_snapshot_replicas(
+        &self,
+        registry: &Registry,
+        restore: &VolumeSnapshotRestore,
+    ) -> Result<(), SvcError> {
+        let snapshot = registry
+            .specs()
+            .volume_snapshot_rsc(&restore.snapshot)
+            .map(|snapshot| snapshot.lock().clone());
+
+        let mut restored = 0;
+        for clone in &restore.clones {
+            if let Some(replica) = registry.specs().replica_rsc(&clone.replica) {
+                if replica.lock().status.created() {
+                    restored += 1;
+                    continue;
+                }
+            }
+            let Some(params) = snapshot
+                .as_ref()
+                .and_then(|snapshot| self.restore_clone_params(registry, snapshot, clone))
+            else {
+                continue;
+            };
+            match OperationGuardArc::<ReplicaSpec>::create_ext(registry, &params).await {
+                Ok(_) => restored += 1,
+                Err(error) => {
+                    self.error(&format!(
+                        "Failed to clone replica snapshot {} for volume, error: {}",
+                        clone.replica_snapshot,
+                        error.full_string()
+                    ));
+                }
+            }
+        }
+        if restored == 0 {
+            return Err(SvcError::NoHealthyReplicas {
+                id: self.uuid().to_string(),
+            });
+        }
+
+        for replica in &restore.replicas {
+            self.retire_restored_replica(registry, replica).await;
+        }
+
+        // The persisted replica health information refers to the replaced replicas, and without
+        // it all the clones, which hold the same data, are considered healthy.
+        let health_info_id = self.as_ref().health_info_id().cloned();
+        if let Some(nexus_id) = health_info_id {
+            ResourceSpecsLocked::delete_nexus_info(
+                &NexusInfoKey::new(&Some(self.uuid().clone()), &nexus_id),
+                registry,
+            )
+            .await;
+        }
+        Ok(())
+    }
+
+    /// Get the parameters to create the given restore clone, on the pool of its replica snapshot.
+    fn restore_clone_params(
+        &self,
+        registry: &Registry,
+        snapshot: &VolumeSnapshot,
+        clone: &VolumeSnapshotRestoreClone,
+    ) -> Option<SnapshotCloneSpecParams> {
+        let replica_snapshot = snapshot
+            .metadata()
+            .replica_snapshots()?
+            .iter()
+            .find(|snapshot| snapshot.spec().uuid() == &clone.replica_snapshot)?;
+        let source = replica_snapshot.spec().source_id();
+        let node = registry
+            .specs()
+            .pool_rsc(source.pool_id())?
+            .lock()
+            .node
+            .clone();
+
+        Some(SnapshotCloneSpecParams::new(
+            SnapshotCloneParameters::new(
+                clone.replica_snapshot.clone(),
+                clone.replica.to_string(),
+                clone.replica.clone(),
+            ),
+            replica_snapshot.meta().source_spec_size(),
+            PoolRef::Uuid(source.pool_id().clone(), source.pool_uuid().clone()),
+            node,
+            self.uuid().clone(),
+        ))
+    }
+
+    /// Destroy a replica which has been replaced by the snapshot restore.
+    /// If it cannot be destroyed it's disowned from the volume, and so it cannot be mistaken for
+    /// a healthy replica, leaving it to be destroyed by the garbage collector.
+    async fn retire_restored_replica(&self, registry: &Registry, replica_id: &ReplicaId) {
+        let Some(replica) = registry.specs().replica_rsc(replica_id) else {
+            return;
+        };
+        let Ok(mut replica) = replica.operation_guard_wait().await else {
+            return;
+        };
+        let disowner = ReplicaOwners::from_volume(self.uuid());
+
+        let destroyed = match ResourceSpecsLocked::replica_node(registry, replica.as_ref()).await {
+            Some(node) => {
+                let request = replica.destroy_request(disowner.clone(), &node);
+                replica.destroy(registry, &request).await
+            }
+            None => Err(SvcError::Internal {
+                details: "Failed to find the node where a replica lives".to_string(),
+            }),
+        };
+        if let Err(error) = destroyed {
+            tracing::warn!(replica.uuid=%replica_id, error=%error,
+                "Restored replica destruction failed. This will be garbage collected later"
+            );
+            if replica.as_ref().owners.owned_by(self.uuid()) {
+                if let Err(error) = replica.remove_owners(registry, &disowner, true).await {
+                    tracing::error!(replica.uuid=%replica_id, error=%error, "Failed to disown volume replica");
+                }
+            }
+        }
+    }
+}
@@ -10,35 +10,50 @@ use crate::{
             operations_helper::{OperationSequenceGuard, ResourceSpecsLocked},
             OperationGuardArc,
         },
+        scheduling::volume::VolumeCapacityCandidates,
     },
-    volume::snapshot_operations::DestroyVolumeSnapshotRequest,
+    volume::{snapshot_operations::DestroyVolumeSnapshotRequest, MoveReplicaRequest},
 };
-use agents::errors::SvcError;
+use agents::errors::{self, SvcError};
 use grpc::{
     context::Context,
     operations::{
         volume::traits::{
-            CreateSnapshotVolumeInfo, CreateVolumeInfo, CreateVolumeSnapshot,
-            CreateVolumeSnapshotInfo, DestroyShutdownTargetsInfo, DestroyVolumeInfo,
-            DestroyVolumeSnapshot, DestroyVolumeSnapshotInfo, PublishVolumeInfo,
-            RepublishVolumeInfo, ResizeVolumeInfo, SetVolumePropertyInfo, SetVolumeReplicaInfo,
-            ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
-            VolumeSnapshot, VolumeSnapshots,
+            CancelVolumeReplicaMoveInfo, CloneVolumeInfo, CreateSnapshotExportInfo,
+            CreateSnapshotGroupInfo, CreateSnapshotScheduleInfo, CreateSnapshotVolumeInfo,
+            CreateVolumeInfo, CreateVolumeSnapshot, CreateVolumeSnapshotInfo,
+            DestroyShutdownTargetsInfo, DestroySnapshotExportInfo, DestroySnapshotGroupInfo,
+            DestroySnapshotScheduleInfo, DestroyVolumeInfo, DestroyVolumeSnapshot,
+            DestroyVolumeSnapshotInfo, MoveVolumeReplicaInfo, PublishVolumeInfo,
+            RepublishVolumeInfo, ResizeVolumeInfo, RestoreSnapshotExportInfo,
+            RestoreVolumeSnapshot, RestoreVolumeSnapshotInfo, SetVolumePropertyInfo,
+            SetVolumeReplicaInfo, ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo,
+            VolumeOperations, VolumeSnapshot, VolumeSnapshots,
         },
         Pagination,
     },
 };
 use stor_port::{
-    transport_api::{v0::Volumes, ReplyError, ResourceKind},
+    transport_api::{
+        v0::{SnapshotExports, SnapshotGroups, SnapshotSchedules, Volumes},
+        ReplyError, ResourceKind,
+    },
     types::v0::{
         store::{
-            snapshots::volume::VolumeSnapshotUserSpec,
-            volume::{VolumeContentSource, VolumeSpec},
+            snapshots::{
+                export::SnapshotExportSpec, schedule::SnapshotScheduleSpec,
+                volume::VolumeSnapshotUserSpec,
+            },
+            volume::VolumeSpec,
         },
         transport::{
-            CreateSnapshotVolume, CreateVolume, DestroyShutdownTargets, DestroyVolume, Filter,
-            PublishVolume, RepublishVolume, ResizeVolume, SetVolumeProperty, SetVolumeReplica,
-            ShareVolume, UnpublishVolume, UnshareVolume, Volume,
+            CancelVolumeReplicaMove, CloneVolume, CreateSnapshotExport, CreateSnapshotGroup,
+            CreateSnapshotSchedule, CreateSnapshotVolume, CreateVolume, DestroyShutdownTargets,
+            DestroySnapshotExport, DestroySnapshotGroup, DestroySnapshotSchedule, DestroyVolume,
+            Filter, GetVolumeCapacity, MoveVolumeReplica, PublishVolume, RepublishVolume,
+            ResizeVolume, RestoreSnapshotExport, SetVolumeProperty, SetVolumeReplica, ShareVolume,
+            SnapshotExportId, SnapshotGroup, SnapshotGroupId, SnapshotId, SnapshotScheduleId,
+            UnpublishVolume, UnshareVolume, Volume, VolumeCapacity, VolumeId,
         },
     },
 };
@@ -175,6 +190,43 @@ impl VolumeOperations for Service {
         Ok(volume)
     }
 
+    async fn move_replica(
+        &self,
+        req: &dyn MoveVolumeReplicaInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let request = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.move_volume_replica(&request).await }).await??;
+        Ok(volume)
+    }
+
+    async fn cancel_replica_move(
+        &self,
+        req: &dyn CancelVolumeReplicaMoveInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let request = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.cancel_volume_replica_move(&request).await })
+                .await??;
+        Ok(volume)
+    }
+
+    async fn get_capacity(
+        &self,
+        req: &GetVolumeCapacity,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeCapacity, ReplyError> {
+        let request = req.clone();
+        let service = self.clone();
+        let capacity =
+            Context::spawn(async move { service.get_volume_capacity(&request).await }).await??;
+        Ok(capacity)
+    }
+
     async fn probe(&self, _ctx: Option<Context>) -> Result<bool, ReplyError> {
         return Ok(true);
     }
@@ -214,6 +266,18 @@ impl VolumeOperations for Service {
         Ok(())
     }
 
+    async fn restore_snapshot(
+        &self,
+        request: &dyn RestoreVolumeSnapshotInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let service = self.clone();
+        let request = request.info();
+        let volume =
+            Context::spawn(async move { service.restore_snapshot(request).await }).await??;
+        Ok(volume)
+    }
+
     async fn get_snapshots(
         &self,
         filter: Filter,
@@ -239,6 +303,17 @@ impl VolumeOperations for Service {
         Ok(volume)
     }
 
+    async fn clone_volume(
+        &self,
+        req: &dyn CloneVolumeInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let request = req.into();
+        let service = self.clone();
+        let volume = Context::spawn(async move { service.clone_volume(&request).await }).await??;
+        Ok(volume)
+    }
+
     async fn resize(
         &self,
         req: &dyn ResizeVolumeInfo,
@@ -249,6 +324,115 @@ impl VolumeOperations for Service {
         let volume = Context::spawn(async move { service.resize_volume(&request).await }).await??;
         Ok(volume)
     }
+
+    async fn create_snapshot_schedule(
+        &self,
+        req: &dyn CreateSnapshotScheduleInfo,
+        _ctx: Option<Context>,
+    ) -> Result<SnapshotScheduleSpec, ReplyError> {
+        let request = req.into();
+        let service = self.clone();
+        let schedule =
+            Context::spawn(async move { service.create_snapshot_schedule(&request).await })
+                .await??;
+        Ok(schedule)
+    }
+
+    async fn destroy_snapshot_schedule(
+        &self,
+        req: &dyn DestroySnapshotScheduleInfo,
+        _ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let request = req.into();
+        let service = self.clone();
+        Context::spawn(async move { service.destroy_snapshot_schedule(&request).await }).await??;
+        Ok(())
+    }
+
+    async fn get_snapshot_schedules(
+        &self,
+        id: Option<SnapshotScheduleId>,
+        _ctx: Option<Context>,
+    ) -> Result<SnapshotSchedules, ReplyError> {
+        let schedules = self.get_snapshot_schedules(id)?;
+        Ok(schedules)
+    }
+
+    async fn create_snapshot_group(
+        &self,
+        req: &dyn CreateSnapshotGroupInfo,
+        _ctx: Option<Context>,
+    ) -> Result<SnapshotGroup, ReplyError> {
+        let request = req.into();
+        let service = self.clone();
+        let group =
+            Context::spawn(async move { service.create_snapshot_group(&request).await }).await??;
+        Ok(group)
+    }
+
+    async fn destroy_snapshot_group(
+        &self,
+        req: &dyn DestroySnapshotGroupInfo,
+        _ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let request = req.into();
+        let service = self.clone();
+        Context::spawn(async move { service.destroy_snapshot_group(&request).await }).await??;
+        Ok(())
+    }
+
+    async fn get_snapshot_groups(
+        &self,
+        id: Option<SnapshotGroupId>,
+        _ctx: Option<Context>,
+    ) -> Result<SnapshotGroups, ReplyError> {
+        let groups = self.get_snapshot_groups(id)?;
+        Ok(groups)
+    }
+
+    async fn create_snapshot_export(
+        &self,
+        req: &dyn CreateSnapshotExportInfo,
+        _ctx: Option<Context>,
+    ) -> Result<SnapshotExportSpec, ReplyError> {
+        let request = req.into();
+        let service = self.clone();
+        let export =
+            Context::spawn(async move { service.create_snapshot_export(&request).await }).await??;
+        Ok(export)
+    }
+
+    async fn destroy_snapshot_export(
+        &self,
+        req: &dyn DestroySnapshotExportInfo,
+        _ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let request = req.into();
+        let service = self.clone();
+        Context::spawn(async move { service.destroy_snapshot_export(&request).await }).await??;
+        Ok(())
+    }
+
+    async fn get_snapshot_exports(
+        &self,
+        id: Option<SnapshotExportId>,
+        _ctx: Option<Context>,
+    ) -> Result<SnapshotExports, ReplyError> {
+        let exports = self.get_snapshot_exports(id)?;
+        Ok(exports)
+    }
+
+    async fn restore_snapshot_export(
+        &self,
+        req: &dyn RestoreSnapshotExportInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let request = req.into();
+        let service = self.clone();
+        let volume = Context::spawn(async move { service.restore_snapshot_export(&request).await })
+            .await??;
+        Ok(volume)
+    }
 }
 
 impl Service {
@@ -326,27 +510,7 @@ impl Service {
     /// Destroy a volume using the given parameters.
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn destroy_volume(&self, request: &DestroyVolume) -> Result<(), SvcError> {
-        let mut volume = self.specs().volume(&request.uuid).await?;
-        let content_source = volume.as_ref().content_source.as_ref();
-        let snap_guard = match content_source {
-            None => None,
-            Some(VolumeContentSource::Snapshot(snap_uuid, _)) => {
-                match self.specs().volume_snapshot(snap_uuid).await {
-                    Ok(snap_guard) => Some(snap_guard),
-                    Err(SvcError::VolSnapshotNotFound { .. }) => None,
-                    Err(error) => return Err(error),
-                }
-            }
-        };
-
-        match snap_guard {
-            None => volume.destroy(&self.registry, request).await,
-            Some(mut snap_guard) => {
-                snap_guard
-                    .destroy_clone(&self.registry, request, volume)
-                    .await
-            }
-        }
+        self.specs().destroy_volume(&self.registry, request).await
     }
 
     /// Destroy the shutdown targets associate with the volume.
@@ -427,6 +591,49 @@ impl Service {
         volume.set_property(&self.registry, request).await?;
         self.registry.volume(&request.uuid).await
     }
+    /// Move a volume replica onto another pool.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid, replica.uuid = %request.replica))]
+    pub(super) async fn move_volume_replica(
+        &self,
+        request: &MoveVolumeReplica,
+    ) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        volume
+            .start_replica_move(&self.registry, &MoveReplicaRequest::from(request))
+            .await?;
+        self.registry.volume(&request.uuid).await
+    }
+    /// Cancel the replica move in progress of a volume.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid, replica.uuid = %request.replica))]
+    pub(super) async fn cancel_volume_replica_move(
+        &self,
+        request: &CancelVolumeReplicaMove,
+    ) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        volume
+            .cancel_replica_move(&self.registry, &request.replica)
+            .await?;
+        self.registry.volume(&request.uuid).await
+    }
+    /// Get the capacity available to new volumes created with the given parameters.
+    #[tracing::instrument(level = "debug", skip(self), err)]
+    pub(super) async fn get_volume_capacity(
+        &self,
+        request: &GetVolumeCapacity,
+    ) -> Result<VolumeCapacity, SvcError> {
+        if request.replicas == 0 {
+            return Err(SvcError::InvalidArguments {});
+        }
+        let spec = VolumeSpec {
+            uuid: VolumeId::new(),
+            num_replicas: request.replicas,
+            topology: request.topology.clone(),
+            thin: request.thin,
+            ..Default::default()
+        };
+        let candidates = VolumeCapacityCandidates::new(&spec, &self.registry).await;
+        Ok(candidates.capacity())
+    }
     /// Create a volume snapshot.
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.source_id, snapshot.source_uuid = %request.source_id, snapshot.uuid = %request.snap_id))]
     async fn create_snapshot(
@@ -469,6 +676,23 @@ impl Service {
         Ok(VolumeSnapshot::new(snapshot.as_ref(), state))
     }
 
+    /// Restore a volume in place to one of its snapshots.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.source_id, snapshot.uuid = %request.snap_id))]
+    async fn restore_snapshot(&self, request: RestoreVolumeSnapshot) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(request.source_id()).await?;
+        let snapshot = self
+            .specs()
+            .volume_snapshot_rsc(request.snap_id())
+            .ok_or(SvcError::VolSnapshotNotFound {
+                snap_id: request.snap_id().to_string(),
+                source_id: Some(request.source_id().to_string()),
+            })?
+            .operation_guard()?;
+
+        volume.restore_snapshot(&self.registry, &snapshot).await?;
+        self.registry.volume(request.source_id()).await
+    }
+
     /// Delete a volume snapshot.
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = ?request.source_id, snapshot.source_uuid = ?request.source_id, snapshot.uuid = %request.snap_id))]
     async fn destroy_snapshot(&self, request: DestroyVolumeSnapshot) -> Result<(), SvcError> {
@@ -599,6 +823,53 @@ impl Service {
         self.registry.volume(&request.params().uuid).await
     }
 
+    /// Create a new volume as a clone of an existing volume.
+    /// A hidden snapshot of the source volume is taken and then restored into the new volume.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.params().uuid, volume.source_uuid = %request.source_uuid()))]
+    pub(super) async fn clone_volume(&self, request: &CloneVolume) -> Result<Volume, SvcError> {
+        let _permit = self.create_volume_permit().await?;
+        let mut source = self.specs().volume(request.source_uuid()).await?;
+
+        // Validate early, to avoid taking a snapshot which cannot be restored.
+        let params = request.params();
+        snafu::ensure!(params.thin, errors::ClonedVolumeThin {});
+        snafu::ensure!(
+            params.size == source.as_ref().size,
+            errors::ClonedVolumeSize {}
+        );
+        if params.encryption.is_some() && params.encryption != source.as_ref().encryption {
+            return Err(SvcError::InvalidVolumeEncryption {
+                vol_id: params.uuid.to_string(),
+                reason: "the clone must be encrypted with the key of its source volume".to_string(),
+            });
+        }
+
+        let mut snapshot = source
+            .create_snap(
+                &self.registry,
+                &VolumeSnapshotUserSpec::new_internal(source.uuid(), SnapshotId::new()),
+            )
+            .await?;
+        // The snapshot guard is held until the clone is created, preventing its collection.
+        drop(source);
+
+        let result = snapshot
+            .create_clone(
+                &self.registry,
+                &request.to_snapshot_volume(snapshot.uuid().clone()),
+            )
+            .await;
+        if let Err(error) = snapshot.destroy_unused_clone_source(&self.registry).await {
+            tracing::warn!(
+                snapshot.uuid = %snapshot.uuid(),
+                %error,
+                "Failed to destroy the clone source snapshot, will be garbage collected"
+            );
+        }
+        result?;
+        self.registry.volume(&params.uuid).await
+    }
+
     /// Resize an existing volume to the requested new capacity.
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn resize_volume(&self, request: &ResizeVolume) -> Result<Volume, SvcError> {
@@ -630,4 +901,126 @@ impl Service {
         *self.capacity_limit_borrow.write() -= required;
         resize_ret
     }
+
+    /// Create or update a snapshot schedule.
+    #[tracing::instrument(level = "info", skip(self), err, fields(snapshot_schedule.id = %request.id))]
+    pub(super) async fn create_snapshot_schedule(
+        &self,
+        request: &CreateSnapshotSchedule,
+    ) -> Result<SnapshotScheduleSpec, SvcError> {
+        self.specs()
+            .create_snapshot_schedule(&self.registry, request)
+            .await
+    }
+
+    /// Delete a snapshot schedule.
+    #[tracing::instrument(level = "info", skip(self), err, fields(snapshot_schedule.id = %request.id))]
+    pub(super) async fn destroy_snapshot_schedule(
+        &self,
+        request: &DestroySnapshotSchedule,
+    ) -> Result<(), SvcError> {
+        self.specs()
+            .destroy_snapshot_schedule(&self.registry, request)
+            .await
+    }
+
+    /// Get all snapshot schedules, or a specific one.
+    pub(super) fn get_snapshot_schedules(
+        &self,
+        id: Option<SnapshotScheduleId>,
+    ) -> Result<SnapshotSchedules, SvcError> {
+        match id {
+            Some(id) => Ok(SnapshotSchedules(vec![self
+                .specs()
+                .snapshot_schedule(&id)?])),
+            None => Ok(SnapshotSchedules(self.specs().snapshot_schedules())),
+        }
+    }
+
+    /// Create a snapshot group, snapshotting all of its volumes at a single quiesce point.
+    #[tracing::instrument(level = "info", skip(self), err, fields(snapshot_group.id = %request.id))]
+    pub(super) async fn create_snapshot_group(
+        &self,
+        request: &CreateSnapshotGroup,
+    ) -> Result<SnapshotGroup, SvcError> {
+        let specs = self.specs();
+        let group = specs.create_snapshot_group(&self.registry, request).await?;
+        Ok(specs.snapshot_group_state(group))
+    }
+
+    /// Delete a snapshot group, along with its member snapshots.
+    #[tracing::instrument(level = "info", skip(self), err, fields(snapshot_group.id = %request.id))]
+    pub(super) async fn destroy_snapshot_group(
+        &self,
+        request: &DestroySnapshotGroup,
+    ) -> Result<(), SvcError> {
+        self.specs()
+            .destroy_snapshot_group(&self.registry, request)
+            .await
+    }
+
+    /// Get all snapshot groups, or a specific one.
+    pub(super) fn get_snapshot_groups(
+        &self,
+        id: Option<SnapshotGroupId>,
+    ) -> Result<SnapshotGroups, SvcError> {
+        let specs = self.specs();
+        let groups = match id {
+            Some(id) => vec![specs.snapshot_group(&id)?],
+            None => specs.snapshot_groups(),
+        };
+        Ok(SnapshotGroups(
+            groups
+                .into_iter()
+                .map(|group| specs.snapshot_group_state(group))
+                .collect(),
+        ))
+    }
+
+    /// Export a volume snapshot into a backup target.
+    #[tracing::instrument(level = "info", skip(self), err, fields(snapshot_export.id = %request.id, snapshot.uuid = %request.snapshot_id))]
+    pub(super) async fn create_snapshot_export(
+        &self,
+        request: &CreateSnapshotExport,
+    ) -> Result<SnapshotExportSpec, SvcError> {
+        self.specs()
+            .create_snapshot_export(&self.registry, request)
+            .await
+    }
+
+    /// Delete a snapshot export from its backup target.
+    #[tracing::instrument(level = "info", skip(self), err, fields(snapshot_export.id = %request.id))]
+    pub(super) async fn destroy_snapshot_export(
+        &self,
+        request: &DestroySnapshotExport,
+    ) -> Result<(), SvcError> {
+        self.specs()
+            .destroy_snapshot_export(&self.registry, request)
+            .await
+    }
+
+    /// Get all snapshot exports, or a specific one.
+    pub(super) fn get_snapshot_exports(
+        &self,
+        id: Option<SnapshotExportId>,
+    ) -> Result<SnapshotExports, SvcError> {
+        let specs = self.specs();
+        match id {
+            Some(id) => Ok(SnapshotExports(vec![specs.snapshot_export(&id)?])),
+            None => Ok(SnapshotExports(specs.snapshot_exports())),
+        }
+    }
+
+    /// Create a new volume from a snapshot export, along with its chain of base exports.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.params().uuid, snapshot_export.id = %request.export_id()))]
+    pub(super) async fn restore_snapshot_export(
+        &self,
+        request: &RestoreSnapshotExport,
+    ) -> Result<Volume, SvcError> {
+        let _permit = self.create_volume_permit().await?;
+        self.specs()
+            .restore_snapshot_export(&self.registry, request)
+            .await?;
+        self.registry.volume(&request.params().uuid).await
+    }
 }
@@ -0,0 +1,645 @@
+use crate::controller::{
+    registry::Registry,
+    resources::{
+        operations::{
+            ResourceCloning, ResourceLifecycle, ResourcePublishing, ResourceSnapshotting,
+        },
+        operations_helper::{OperationSequenceGuard, ResourceSpecsLocked},
+        OperationGuardArc,
+    },
+};
+use agents::errors::{self, SvcError};
+use chrono::Utc;
+use grpc::csi_node_plugin::{
+    node_plugin_client::NodePluginClient, DeleteSnapshotExportRequest, ExportSnapshotRequest,
+    ImportSnapshotRequest,
+};
+use std::collections::HashMap;
+use stor_port::{
+    pstor::ObjectKey,
+    transport_api::ResourceKind,
+    types::v0::{
+        store::{
+            snapshots::{
+                export::{
+                    BackupTarget, SnapshotExportSpec, SnapshotExportSpecKey, SnapshotExportStats,
+                    SnapshotExportStatus,
+                },
+                volume::{VolumeSnapshot, VolumeSnapshotUserSpec},
+            },
+            volume::VolumeSpec,
+        },
+        transport::{
+            CreateSnapshotExport, CreateSnapshotVolume, CreateVolume, DestroySnapshotExport,
+            DestroyVolume, PublishVolume, RestoreSnapshotExport, SnapshotExportId, SnapshotId,
+            UnpublishVolume, VolumeId, VolumeShareProtocol,
+        },
+    },
+};
+
+/// Label of the temporary volumes through which the snapshot exports are transferred.
+/// The value is the id of the snapshot export.
+const SNAPSHOT_EXPORT_TRANSFER_LABEL: &str = "openebs.io/snapshot-export-transfer";
+/// The size of the chunks which the exported snapshots are split into.
+const SNAPSHOT_EXPORT_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+
+impl ResourceSpecsLocked {
+    /// Get a copy of all snapshot export specs.
+    pub(crate) fn snapshot_exports(&self) -> Vec<SnapshotExportSpec> {
+        let specs = self.read();
+        specs
+            .snapshot_exports
+            .values()
+            .map(|v| v.lock().clone())
+            .collect()
+    }
+
+    /// Get a copy of the snapshot export spec with the given id.
+    pub(crate) fn snapshot_export(
+        &self,
+        id: &SnapshotExportId,
+    ) -> Result<SnapshotExportSpec, SvcError> {
+        let specs = self.read();
+        match specs.snapshot_exports.get(id) {
+            Some(spec) => Ok(spec.lock().clone()),
+            None => Err(SvcError::NotFound {
+                kind: ResourceKind::SnapshotExport,
+                id: id.to_string(),
+            }),
+        }
+    }
+
+    /// Export a volume snapshot into its backup target.
+    /// The snapshot is cloned into a temporary volume which is published to the target's app
+    /// node, which then copies the volume's data into the backup target.
+    /// If a base export is given, only the chunks which differ from the base are copied.
+    /// Snapshots of encrypted volumes cannot be exported.
+    /// As copying the data may take a long time, the export is returned as soon as it's stored
+    /// as exporting, and the copy completes in the background. Its status then changes to
+    /// exported, or the export is deleted if it fails.
+    pub(crate) async fn create_snapshot_export(
+        &self,
+        registry: &Registry,
+        request: &CreateSnapshotExport,
+    ) -> Result<SnapshotExportSpec, SvcError> {
+        let target = ExportTarget::new(self, &request.id, &request.target)?;
+        // The snapshot guard is held until the export completes, which also keeps the reconciler
+        // from mistaking the export for an interrupted one.
+        let snapshot = self.volume_snapshot(&request.snapshot_id).await?;
+        snafu::ensure!(
+            snapshot.as_ref().status().created(),
+            errors::SnapshotNotCreated {}
+        );
+        let volume_id = snapshot.as_ref().spec().source_id().clone();
+        // The transfer volume's target decrypts the data, so the backup would be stored in clear.
+        if snapshot.as_ref().metadata().encryption().is_some() {
+            return Err(SvcError::InvalidVolumeEncryption {
+                vol_id: volume_id.to_string(),
+                reason: "snapshots of encrypted volumes cannot be exported".to_string(),
+            });
+        }
+        if let Some(base_id) = &request.base {
+            self.validate_snapshot_export_base(request, &volume_id, base_id)?;
+        }
+
+        let export = SnapshotExportSpec::new(
+            request.id.clone(),
+            request.snapshot_id.clone(),
+            volume_id,
+            request.target.clone(),
+            request.base.clone(),
+            snapshot.as_ref().metadata().spec_size(),
+        );
+        self.insert_snapshot_export(registry, &export).await?;
+
+        let specs = self.clone();
+        let registry = registry.clone();
+        let exporting = export.clone();
+        tokio::spawn(async move {
+            specs
+                .complete_snapshot_export(&registry, target, snapshot, exporting)
+                .await;
+        });
+        Ok(export)
+    }
+
+    /// Delete a snapshot export from its backup target.
+    /// An export cannot be deleted while other exports are incremental to it.
+    pub(crate) async fn destroy_snapshot_export(
+        &self,
+        registry: &Registry,
+        request: &DestroySnapshotExport,
+    ) -> Result<(), SvcError> {
+        let mut export = self.snapshot_export(&request.id)?;
+        if export.status == SnapshotExportStatus::Exporting {
+            return Err(SvcError::Conflict {});
+        }
+        if let Some(dependent) = self
+            .snapshot_exports()
+            .into_iter()
+            .find(|dependent| dependent.base.as_ref() == Some(&export.id))
+        {
+            return Err(SvcError::SnapshotExportInUse {
+                export_id: export.id.to_string(),
+                dependent_id: dependent.id.to_string(),
+            });
+        }
+        let target = ExportTarget::new(self, &export.id, &export.target)?;
+        export.status = SnapshotExportStatus::Deleting;
+        self.store_snapshot_export(registry, &export).await?;
+        self.remove_snapshot_export(registry, &target, &export)
+            .await
+    }
+
+    /// Create a new volume from the snapshot export, along with its chain of base exports.
+    /// The exports are imported into a temporary volume which is then snapshotted, and the new
+    /// volume is created as a clone of that snapshot, allowing its replicas to be placed as
+    /// requested.
+    pub(crate) async fn restore_snapshot_export(
+        &self,
+        registry: &Registry,
+        request: &RestoreSnapshotExport,
+    ) -> Result<(), SvcError> {
+        let export = self.snapshot_export(request.export_id())?;
+        if export.status != SnapshotExportStatus::Exported {
+            return Err(SvcError::SnapshotExportNotExported {
+                export_id: export.id.to_string(),
+            });
+        }
+        let params = request.params();
+        snafu::ensure!(params.thin, errors::ClonedSnapshotVolumeThin {});
+        snafu::ensure!(
+            params.size == export.size,
+            errors::ClonedSnapshotVolumeSize {}
+        );
+        let target = ExportTarget::new(self, &export.id, &export.target)?;
+
+        let import_params = CreateVolume {
+            uuid: VolumeId::new(),
+            labels: Some(Self::snapshot_export_labels(&export.id)),
+            affinity_group: None,
+            max_snapshots: None,
+            ..params.clone()
+        };
+        let import_id = import_params.uuid.clone();
+        let volume = OperationGuardArc::<VolumeSpec>::create(registry, &import_params).await?;
+        let result = self
+            .restore_snapshot_export_volume(registry, &target, &export, request, volume)
+            .await;
+        self.destroy_snapshot_export_volume(registry, &import_id)
+            .await;
+        result
+    }
+
+    /// Finish the snapshot export operation which was interrupted, eg: by a restart of the core
+    /// agent. An export which was still exporting is deleted, as it may be incomplete.
+    /// If the exported snapshot is busy then the export may still be in progress and is skipped.
+    pub(crate) async fn reconcile_snapshot_export(
+        &self,
+        registry: &Registry,
+        export: &SnapshotExportSpec,
+    ) -> Result<(), SvcError> {
+        let mut snapshot = match self.volume_snapshot_rsc(&export.snapshot_id) {
+            Some(snapshot) => Some(snapshot.operation_guard()?),
+            None => None,
+        };
+        // The guard is now held, so the export's status can no longer change under us.
+        let Ok(export) = self.snapshot_export(&export.id) else {
+            return Ok(());
+        };
+        if export.status == SnapshotExportStatus::Exported {
+            return Ok(());
+        }
+        if let Some(volume) = self.volume_rsc(&export.transfer_volume) {
+            let mut volume = volume.operation_guard()?;
+            let request = DestroyVolume::new(&export.transfer_volume);
+            match &mut snapshot {
+                Some(snapshot) => snapshot.destroy_clone(registry, &request, volume).await?,
+                None => volume.destroy(registry, &request).await?,
+            }
+        }
+        let target = ExportTarget::new(self, &export.id, &export.target)?;
+        self.remove_snapshot_export(registry, &target, &export)
+            .await
+    }
+
+    /// Get the temporary volumes through which the snapshot exports are transferred.
+    pub(crate) fn snapshot_export_volumes(&self) -> Vec<VolumeId> {
+        self.volumes_rsc()
+            .into_iter()
+            .filter_map(|volume| {
+                let volume = volume.lock();
+                volume
+                    .labels
+                    .as_ref()
+                    .filter(|labels| labels.contains_key(SNAPSHOT_EXPORT_TRANSFER_LABEL))
+                    .map(|_| volume.uuid.clone())
+            })
+            .collect()
+    }
+
+    /// Destroy the temporary volume of a snapshot export which was left behind, eg: by a restart
+    /// of the core agent while restoring an export.
+    /// The transfer volumes of the exports which are still exporting are reconciled along with
+    /// their export, and busy volumes may still be in use and are skipped.
+    pub(crate) async fn reconcile_snapshot_export_volume(
+        &self,
+        registry: &Registry,
+        volume_id: &VolumeId,
+    ) -> Result<(), SvcError> {
+        if self.snapshot_exports().iter().any(|export| {
+            export.status == SnapshotExportStatus::Exporting && &export.transfer_volume == volume_id
+        }) {
+            return Ok(());
+        }
+        let Some(volume) = self.volume_rsc(volume_id) else {
+            return Ok(());
+        };
+        drop(volume.operation_guard()?);
+        match self
+            .destroy_volume(registry, &DestroyVolume::new(volume_id))
+            .await
+        {
+            Ok(()) | Err(SvcError::VolumeNotFound { .. }) => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Copy the snapshot into the backup target of its export, through a temporary clone.
+    /// The export is then stored as exported, or deleted if it failed.
+    async fn complete_snapshot_export(
+        &self,
+        registry: &Registry,
+        target: ExportTarget,
+        mut snapshot: OperationGuardArc<VolumeSnapshot>,
+        mut export: SnapshotExportSpec,
+    ) {
+        let params = CreateVolume {
+            uuid: export.transfer_volume.clone(),
+            size: export.size,
+            replicas: 1,
+            labels: Some(Self::snapshot_export_labels(&export.id)),
+            thin: true,
+            ..Default::default()
+        };
+        let result = match snapshot
+            .create_clone(
+                registry,
+                &CreateSnapshotVolume::new(export.snapshot_id.clone(), params),
+            )
+            .await
+        {
+            Ok(mut volume) => {
+                let result = target
+                    .transfer(registry, &mut volume, |uri| target.export(&export, uri))
+                    .await;
+                let destroy = DestroyVolume::new(&export.transfer_volume);
+                if let Err(error) = snapshot.destroy_clone(registry, &destroy, volume).await {
+                    tracing::warn!(
+                        volume.uuid = %export.transfer_volume,
+                        %error,
+                        "Failed to destroy the snapshot export transfer volume, will be reconciled later"
+                    );
+                }
+                result
+            }
+            Err(error) => Err(error),
+        };
+
+        let error = match result {
+            Ok(stats) => {
+                export.status = SnapshotExportStatus::Exported;
+                export.timestamp = Some(Utc::now());
+                export.stats = Some(stats);
+                match self.store_snapshot_export(registry, &export).await {
+                    Ok(()) => {
+                        tracing::info!(snapshot_export.id = %export.id, "Exported the snapshot");
+                        return;
+                    }
+                    Err(error) => error,
+                }
+            }
+            Err(error) => error,
+        };
+        tracing::error!(
+            snapshot_export.id = %export.id,
+            %error,
+            "Failed to export the snapshot, deleting the export"
+        );
+        if let Err(rollback_error) = self
+            .remove_snapshot_export(registry, &target, &export)
+            .await
+        {
+            tracing::error!(
+                snapshot_export.id = %export.id,
+                error = %rollback_error,
+                "Failed to delete the failed snapshot export, it will be reconciled later"
+            );
+        }
+    }
+
+    /// Import the snapshot export into the given volume, and clone the volume into the volume
+    /// which is requested by the restore.
+    async fn restore_snapshot_export_volume(
+        &self,
+        registry: &Registry,
+        target: &ExportTarget,
+        export: &SnapshotExportSpec,
+        request: &RestoreSnapshotExport,
+        mut volume: OperationGuardArc<VolumeSpec>,
+    ) -> Result<(), SvcError> {
+        let volume_id = volume.uuid().clone();
+        target
+            .transfer(registry, &mut volume, |uri| {
+                target.import(export, &volume_id, uri)
+            })
+            .await?;
+
+        let mut snapshot = volume
+            .create_snap(
+                registry,
+                &VolumeSnapshotUserSpec::new_internal(&volume_id, SnapshotId::new()),
+            )
+            .await?;
+        // The snapshot guard is held until the clone is created, preventing its collection.
+        drop(volume);
+
+        let result = snapshot
+            .create_clone(
+                registry,
+                &request.to_snapshot_volume(snapshot.uuid().clone()),
+            )
+            .await;
+        if let Err(error) = snapshot.destroy_unused_clone_source(registry).await {
+            tracing::warn!(
+                snapshot.uuid = %snapshot.uuid(),
+                %error,
+                "Failed to destroy the clone source snapshot, will be garbage collected"
+            );
+        }
+        result.map(|_| ())
+    }
+
+    /// Check that the base export can be used for an incremental export of the request.
+    fn validate_snapshot_export_base(
+        &self,
+        request: &CreateSnapshotExport,
+        volume_id: &VolumeId,
+        base_id: &SnapshotExportId,
+    ) -> Result<(), SvcError> {
+        let base = self.snapshot_export(base_id)?;
+        let reason = if base.status != SnapshotExportStatus::Exported {
+            "the base has not been exported"
+        } else if &base.volume_id != volume_id {
+            "the base is an export of a different volume"
+        } else if base.target != request.target {
+            "the base was exported into a different backup target"
+        } else {
+            return Ok(());
+        };
+        Err(SvcError::SnapshotExportBase {
+            export_id: request.id.to_string(),
+            base_id: base_id.to_string(),
+            reason: reason.to_string(),
+        })
+    }
+
+    /// Get the labels of the temporary volumes of the given snapshot export.
+    fn snapshot_export_labels(export_id: &SnapshotExportId) -> HashMap<String, String> {
+        HashMap::from([(
+            SNAPSHOT_EXPORT_TRANSFER_LABEL.to_string(),
+            export_id.to_string(),
+        )])
+    }
+
+    /// Destroy the temporary volume of a snapshot export, leaving it for the reconciler if it
+    /// cannot be destroyed now.
+    async fn destroy_snapshot_export_volume(&self, registry: &Registry, volume_id: &VolumeId) {
+        match self
+            .destroy_volume(registry, &DestroyVolume::new(volume_id))
+            .await
+        {
+            Ok(()) | Err(SvcError::VolumeNotFound { .. }) => {}
+            Err(error) => {
+                tracing::warn!(
+                    volume.uuid = %volume_id,
+                    %error,
+                    "Failed to destroy the snapshot export transfer volume, will be reconciled later"
+                );
+            }
+        }
+    }
+
+    /// Delete the export from its backup target, and then the export spec itself.
+    async fn remove_snapshot_export(
+        &self,
+        registry: &Registry,
+        target: &ExportTarget,
+        export: &SnapshotExportSpec,
+    ) -> Result<(), SvcError> {
+        target.delete(export).await?;
+        registry
+            .delete_kv(&SnapshotExportSpecKey::from(&export.id).key())
+            .await?;
+        let mut specs = self.write();
+        specs.snapshot_exports.remove(&export.id);
+        Ok(())
+    }
+
+    /// Insert a new snapshot export spec, failing if one with the same id already exists.
+    async fn insert_snapshot_export(
+        &self,
+        registry: &Registry,
+        export: &SnapshotExportSpec,
+    ) -> Result<(), SvcError> {
+        {
+            let mut specs = self.write();
+            if specs.snapshot_exports.get(&export.id).is_some() {
+                return Err(SvcError::AlreadyExists {
+                    kind: ResourceKind::SnapshotExport,
+                    id: export.id.to_string(),
+                });
+            }
+            specs.snapshot_exports.insert(export.clone());
+        }
+        if let Err(error) = registry.store_obj(export).await {
+            let mut specs = self.write();
+            specs.snapshot_exports.remove(&export.id);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Persist the snapshot export spec and update the in-memory copy.
+    async fn store_snapshot_export(
+        &self,
+        registry: &Registry,
+        export: &SnapshotExportSpec,
+    ) -> Result<(), SvcError> {
+        registry.store_obj(export).await?;
+        let specs = self.read();
+        if let Some(spec) = specs.snapshot_exports.get(&export.id) {
+            *spec.lock() = export.clone();
+        }
+        Ok(())
+    }
+}
+
+/// The backup target of a snapshot export, which is accessed through its app node's plugin.
+struct ExportTarget {
+    endpoint: String,
+    node: String,
+    path: String,
+    export_id: SnapshotExportId,
+}
+
+impl ExportTarget {
+    /// Get the backup target of the given snapshot export.
+    /// The path must be absolute and must not traverse up the directory tree. The app node's
+    /// plugin further ensures that it resolves within its configured backup base directory.
+    fn new(
+        specs: &ResourceSpecsLocked,
+        export_id: &SnapshotExportId,
+        target: &BackupTarget,
+    ) -> Result<Self, SvcError> {
+        let path = std::path::Path::new(target.path());
+        let path_error = |reason: &str| SvcError::SnapshotExportPath {
+            export_id: export_id.to_string(),
+            path: target.path().to_string(),
+            reason: reason.to_string(),
+        };
+        if !path.is_absolute() {
+            return Err(path_error("the path must be absolute"));
+        }
+        if path
+            .components()
+            .any(|component| component == std::path::Component::ParentDir)
+        {
+            return Err(path_error("the path must not contain '..'"));
+        }
+        let app_node = specs
+            .app_node_spec(target.app_node())
+            .ok_or(SvcError::NotFound {
+                kind: ResourceKind::AppNode,
+                id: target.app_node().to_string(),
+            })?;
+        Ok(Self {
+            endpoint: format!("http://{}", app_node.endpoint),
+            node: target.app_node().to_string(),
+            path: target.path().to_string(),
+            export_id: export_id.clone(),
+        })
+    }
+
+    /// Publish the volume to the target's app node, run the given transfer on the volume's
+    /// target uri and then unpublish the volume again.
+    async fn transfer<T, F: std::future::Future<Output = Result<T, SvcError>>>(
+        &self,
+        registry: &Registry,
+        volume: &mut OperationGuardArc<VolumeSpec>,
+        transfer: impl FnOnce(String) -> F,
+    ) -> Result<T, SvcError> {
+        let request = PublishVolume::new(
+            volume.uuid().clone(),
+            None,
+            Some(VolumeShareProtocol::Nvmf),
+            HashMap::new(),
+            vec![self.node.clone()],
+        );
+        let published = volume.publish(registry, &request).await?;
+        let result = match published.state().target {
+            Some(target) => transfer(target.device_uri).await,
+            None => Err(self.error("the transfer volume has no target")),
+        };
+        let unpublish = UnpublishVolume::new(volume.uuid(), true);
+        let unpublish_result = volume.unpublish(registry, &unpublish).await;
+        let output = result?;
+        unpublish_result?;
+        Ok(output)
+    }
+
+    /// Export the transfer volume of the snapshot export, into which the snapshot was cloned.
+    async fn export(
+        &self,
+        export: &SnapshotExportSpec,
+        uri: String,
+    ) -> Result<SnapshotExportStats, SvcError> {
+        let mut client = self.client().await?;
+        let reply = client
+            .export_snapshot(ExportSnapshotRequest {
+                volume_id: export.transfer_volume.to_string(),
+                uri,
+                publish_context: HashMap::new(),
+                path: self.path.clone(),
+                export_id: export.id.to_string(),
+                base_export_id: export.base.as_ref().map(ToString::to_string),
+                chunk_size: SNAPSHOT_EXPORT_CHUNK_SIZE,
+            })
+            .await
+            .map_err(|status| self.error(status))?
+            .into_inner();
+        if reply.size < export.size {
+            return Err(self.error(format!(
+                "exported {} bytes out of {}",
+                reply.size, export.size
+            )));
+        }
+        Ok(SnapshotExportStats {
+            chunks: reply.chunks,
+            changed_chunks: reply.changed_chunks,
+            written_bytes: reply.written_bytes,
+        })
+    }
+
+    /// Import the snapshot export chain into the given volume.
+    async fn import(
+        &self,
+        export: &SnapshotExportSpec,
+        volume_id: &VolumeId,
+        uri: String,
+    ) -> Result<(), SvcError> {
+        let mut client = self.client().await?;
+        client
+            .import_snapshot(ImportSnapshotRequest {
+                volume_id: volume_id.to_string(),
+                uri,
+                publish_context: HashMap::new(),
+                path: self.path.clone(),
+                export_id: export.id.to_string(),
+            })
+            .await
+            .map_err(|status| self.error(status))?;
+        Ok(())
+    }
+
+    /// Delete the export from the backup target, which succeeds if it no longer exists.
+    async fn delete(&self, export: &SnapshotExportSpec) -> Result<(), SvcError> {
+        let mut client = self.client().await?;
+        client
+            .delete_snapshot_export(DeleteSnapshotExportRequest {
+                path: self.path.clone(),
+                export_id: export.id.to_string(),
+            })
+            .await
+            .map_err(|status| self.error(status))?;
+        Ok(())
+    }
+
+    async fn client(&self) -> Result<NodePluginClient<tonic::transport::Channel>, SvcError> {
+        NodePluginClient::connect(self.endpoint.clone())
+            .await
+            .map_err(|error| {
+                self.error(format!(
+                    "failed to connect to the app node plugin at '{}': {error}",
+                    self.endpoint
+                ))
+            })
+    }
+
+    fn error(&self, details: impl std::fmt::Display) -> SvcError {
+        SvcError::SnapshotExportTransfer {
+            export_id: self.export_id.to_string(),
+            details: details.to_string(),
+        }
+    }
+}
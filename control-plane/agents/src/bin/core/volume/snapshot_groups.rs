@@ -0,0 +1,358 @@
+use crate::{
+    controller::{
+        registry::Registry,
+        resources::{
+            operations::{ResourceLifecycleWithLifetime, ResourceSnapshotting},
+            operations_helper::{OperationSequenceGuard, ResourceSpecsLocked},
+            OperationGuardArc,
+        },
+    },
+    volume::{DestroyVolumeSnapshotRequest, VolumeFilesystem},
+};
+use agents::errors::SvcError;
+use chrono::Utc;
+use stor_port::{
+    pstor::ObjectKey,
+    transport_api::ResourceKind,
+    types::v0::{
+        store::{
+            snapshots::{
+                group::{
+                    SnapshotGroupMember, SnapshotGroupSpec, SnapshotGroupSpecKey,
+                    SnapshotGroupStatus, SnapshotGroupTarget,
+                },
+                volume::VolumeSnapshotUserSpec,
+            },
+            volume::VolumeSpec,
+        },
+        transport::{
+            CreateSnapshotGroup, DestroySnapshotGroup, SnapshotGroup, SnapshotGroupId, VolumeId,
+        },
+    },
+};
+
+impl ResourceSpecsLocked {
+    /// Get a copy of all snapshot group specs.
+    pub(crate) fn snapshot_groups(&self) -> Vec<SnapshotGroupSpec> {
+        let specs = self.read();
+        specs
+            .snapshot_groups
+            .values()
+            .map(|v| v.lock().clone())
+            .collect()
+    }
+
+    /// Get a copy of the snapshot group spec with the given id.
+    pub(crate) fn snapshot_group(
+        &self,
+        id: &SnapshotGroupId,
+    ) -> Result<SnapshotGroupSpec, SvcError> {
+        let specs = self.read();
+        match specs.snapshot_groups.get(id) {
+            Some(spec) => Ok(spec.lock().clone()),
+            None => Err(SvcError::NotFound {
+                kind: ResourceKind::SnapshotGroup,
+                id: id.to_string(),
+            }),
+        }
+    }
+
+    /// Get the snapshot group along with the state of its member snapshots.
+    pub(crate) fn snapshot_group_state(&self, spec: SnapshotGroupSpec) -> SnapshotGroup {
+        let created = spec
+            .members
+            .iter()
+            .filter(|member| {
+                self.volume_snapshot_rsc(&member.snapshot_id)
+                    .map_or(false, |snapshot| snapshot.lock().status().created())
+            })
+            .map(|member| member.snapshot_id.clone())
+            .collect();
+        SnapshotGroup::new(spec, created)
+    }
+
+    /// Create a snapshot group, snapshotting all of its volumes at a single quiesce point.
+    /// The filesystems of all published volumes are frozen before any snapshot is taken, and are
+    /// only unfrozen once all snapshots have been taken.
+    /// If any snapshot fails, the snapshots which were already taken are deleted along with the
+    /// group, as they would not be consistent with each other.
+    pub(crate) async fn create_snapshot_group(
+        &self,
+        registry: &Registry,
+        request: &CreateSnapshotGroup,
+    ) -> Result<SnapshotGroupSpec, SvcError> {
+        let volume_ids = self.snapshot_group_volumes(&request.id, &request.target)?;
+
+        // The volume guards are held throughout, which also keeps the reconciler from
+        // mistaking the group for an interrupted creation.
+        let mut volumes = Vec::with_capacity(volume_ids.len());
+        for volume_id in &volume_ids {
+            volumes.push(self.volume(volume_id).await?);
+        }
+        for volume in &volumes {
+            if let Some(max_snapshots) = volume.as_ref().max_snapshots {
+                if volume.as_ref().metadata.num_snapshots() as u32 >= max_snapshots {
+                    return Err(SvcError::SnapshotMaxLimit {
+                        max_snapshots,
+                        volume_id: volume.uuid().to_string(),
+                    });
+                }
+            }
+        }
+
+        let members = volume_ids
+            .into_iter()
+            .map(SnapshotGroupMember::new)
+            .collect();
+        let mut group = SnapshotGroupSpec::new(request.id.clone(), request.target.clone(), members);
+        self.insert_snapshot_group(registry, &group).await?;
+
+        match Self::snapshot_group_members(registry, &group, &mut volumes).await {
+            Ok(unfreeze_result) => {
+                group.status = SnapshotGroupStatus::Created;
+                group.timestamp = Some(Utc::now());
+                self.store_snapshot_group(registry, &group).await?;
+                unfreeze_result?;
+                Ok(group)
+            }
+            Err(error) => {
+                if let Err(rollback_error) = self
+                    .remove_snapshot_group(registry, &group, &mut volumes)
+                    .await
+                {
+                    tracing::error!(
+                        snapshot_group.id = %group.id,
+                        error = %rollback_error,
+                        "Failed to delete the snapshots of the failed snapshot group, it will be reconciled later"
+                    );
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Delete a snapshot group, along with its member snapshots.
+    pub(crate) async fn destroy_snapshot_group(
+        &self,
+        registry: &Registry,
+        request: &DestroySnapshotGroup,
+    ) -> Result<(), SvcError> {
+        let mut group = self.snapshot_group(&request.id)?;
+        if group.status == SnapshotGroupStatus::Creating {
+            return Err(SvcError::Conflict {});
+        }
+        group.status = SnapshotGroupStatus::Deleting;
+        self.store_snapshot_group(registry, &group).await?;
+
+        let mut volumes = vec![];
+        for volume_id in Self::snapshot_group_member_volumes(&group) {
+            match self.volume(&volume_id).await {
+                Ok(volume) => volumes.push(volume),
+                Err(SvcError::VolumeNotFound { .. }) => {}
+                Err(error) => return Err(error),
+            }
+        }
+        self.remove_snapshot_group(registry, &group, &mut volumes)
+            .await
+    }
+
+    /// Finish the snapshot group operation which was interrupted, eg: by a restart of the core
+    /// agent. A group which was still being created is deleted, as its snapshots may not all have
+    /// been taken at the same quiesce point.
+    /// If any of the volumes is busy then the group may still be in progress and is skipped.
+    pub(crate) async fn reconcile_snapshot_group(
+        &self,
+        registry: &Registry,
+        group: &SnapshotGroupSpec,
+    ) -> Result<(), SvcError> {
+        let mut volumes = vec![];
+        for volume_id in Self::snapshot_group_member_volumes(group) {
+            if let Some(volume) = self.volume_rsc(&volume_id) {
+                volumes.push(volume.operation_guard()?);
+            }
+        }
+        // The guards are now held, so the group's status can no longer change under us.
+        let Ok(group) = self.snapshot_group(&group.id) else {
+            return Ok(());
+        };
+        if group.status == SnapshotGroupStatus::Created {
+            return Ok(());
+        }
+        self.remove_snapshot_group(registry, &group, &mut volumes)
+            .await
+    }
+
+    /// Get the created volumes which are targeted by the snapshot group, sorted by their id so
+    /// their guards are always taken in the same order.
+    fn snapshot_group_volumes(
+        &self,
+        group_id: &SnapshotGroupId,
+        target: &SnapshotGroupTarget,
+    ) -> Result<Vec<VolumeId>, SvcError> {
+        let mut volume_ids = match target {
+            SnapshotGroupTarget::Volumes(volume_ids) => {
+                // Fail early if a volume does not exist, though it may still be deleted later.
+                for volume_id in volume_ids {
+                    self.volume_clone(volume_id)?;
+                }
+                volume_ids.clone()
+            }
+            _ => self
+                .volumes_rsc()
+                .into_iter()
+                .filter_map(|volume| {
+                    let volume = volume.lock();
+                    (volume.status.created() && target.matches(&volume))
+                        .then(|| volume.uuid.clone())
+                })
+                .collect(),
+        };
+        volume_ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        volume_ids.dedup();
+        if volume_ids.is_empty() {
+            return Err(SvcError::SnapshotGroupNoVolumes {
+                group_id: group_id.to_string(),
+            });
+        }
+        Ok(volume_ids)
+    }
+
+    /// Get the volumes of the snapshot group's members, sorted by their id.
+    fn snapshot_group_member_volumes(group: &SnapshotGroupSpec) -> Vec<VolumeId> {
+        let mut volume_ids = group
+            .members
+            .iter()
+            .map(|member| member.volume_id.clone())
+            .collect::<Vec<_>>();
+        volume_ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        volume_ids.dedup();
+        volume_ids
+    }
+
+    /// Freeze the filesystems of the published volumes, snapshot all volumes and then unfreeze
+    /// the filesystems again.
+    /// Returns the result of unfreezing the filesystems once all snapshots have been taken.
+    async fn snapshot_group_members(
+        registry: &Registry,
+        group: &SnapshotGroupSpec,
+        volumes: &mut [OperationGuardArc<VolumeSpec>],
+    ) -> Result<Result<(), SvcError>, SvcError> {
+        let mut frozen = vec![];
+        let mut result = Ok(());
+        for volume in volumes.iter() {
+            let Some(filesystem) = VolumeFilesystem::published(registry, volume.as_ref()) else {
+                continue;
+            };
+            if let Err(error) = filesystem.freeze().await {
+                result = Err(error);
+                break;
+            }
+            frozen.push(filesystem);
+        }
+
+        if result.is_ok() {
+            for (volume, member) in volumes.iter_mut().zip(&group.members) {
+                let spec = VolumeSnapshotUserSpec::new_grouped(
+                    &member.volume_id,
+                    member.snapshot_id.clone(),
+                    group.id.clone(),
+                );
+                if let Err(error) = volume.create_snap(registry, &spec).await {
+                    result = Err(error);
+                    break;
+                }
+            }
+        }
+
+        // Always unfreeze the filesystems, even if the snapshot creation failed.
+        let mut unfreeze_result = Ok(());
+        for filesystem in &frozen {
+            if let Err(error) = filesystem.unfreeze().await {
+                unfreeze_result = Err(error);
+            }
+        }
+        result.map(|_| unfreeze_result)
+    }
+
+    /// Delete the member snapshots of the snapshot group, and then the group itself.
+    /// The given volume guards must be held for all member volumes which still exist.
+    async fn remove_snapshot_group(
+        &self,
+        registry: &Registry,
+        group: &SnapshotGroupSpec,
+        volumes: &mut [OperationGuardArc<VolumeSpec>],
+    ) -> Result<(), SvcError> {
+        for member in &group.members {
+            let Some(snapshot) = self.volume_snapshot_rsc(&member.snapshot_id) else {
+                continue;
+            };
+            match volumes
+                .iter_mut()
+                .find(|volume| volume.uuid() == &member.volume_id)
+            {
+                Some(volume) => {
+                    let request = DestroyVolumeSnapshotRequest::new(
+                        snapshot,
+                        Some(member.volume_id.clone()),
+                        member.snapshot_id.clone(),
+                    );
+                    volume.destroy_snap(registry, &request).await?;
+                }
+                None => {
+                    let mut snapshot_guard = snapshot.operation_guard_wait().await?;
+                    let request = DestroyVolumeSnapshotRequest::new(
+                        snapshot,
+                        None,
+                        member.snapshot_id.clone(),
+                    );
+                    snapshot_guard.destroy(registry, &request).await?;
+                }
+            }
+        }
+
+        registry
+            .delete_kv(&SnapshotGroupSpecKey::from(&group.id).key())
+            .await?;
+        let mut specs = self.write();
+        specs.snapshot_groups.remove(&group.id);
+        Ok(())
+    }
+
+    /// Insert a new snapshot group spec, failing if one with the same id already exists.
+    async fn insert_snapshot_group(
+        &self,
+        registry: &Registry,
+        group: &SnapshotGroupSpec,
+    ) -> Result<(), SvcError> {
+        {
+            let mut specs = self.write();
+            if specs.snapshot_groups.get(&group.id).is_some() {
+                return Err(SvcError::AlreadyExists {
+                    kind: ResourceKind::SnapshotGroup,
+                    id: group.id.to_string(),
+                });
+            }
+            specs.snapshot_groups.insert(group.clone());
+        }
+        if let Err(error) = registry.store_obj(group).await {
+            let mut specs = self.write();
+            specs.snapshot_groups.remove(&group.id);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Persist the snapshot group spec and update the in-memory copy.
+    async fn store_snapshot_group(
+        &self,
+        registry: &Registry,
+        group: &SnapshotGroupSpec,
+    ) -> Result<(), SvcError> {
+        registry.store_obj(group).await?;
+        let specs = self.read();
+        if let Some(spec) = specs.snapshot_groups.get(&group.id) {
+            *spec.lock() = group.clone();
+        }
+        Ok(())
+    }
+}
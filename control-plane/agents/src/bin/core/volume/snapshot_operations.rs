@@ -60,6 +60,10 @@ impl ResourceSnapshotting for OperationGuardArc<VolumeSpec> {
 
         let operation = VolumeOperation::CreateSnapshot(request.uuid().clone());
         let spec_clone = self.start_update(registry, &state, operation).await?;
+        if request.internal() {
+            // not accounted as a user snapshot, even before it is committed
+            self.lock().set_internal_snapshot(request.uuid());
+        }
 
         let snap_result = OperationGuardArc::<VolumeSnapshot>::create(
             registry,
@@ -88,7 +92,19 @@ impl ResourceSnapshotting for OperationGuardArc<VolumeSpec> {
     ) -> Result<(), SvcError> {
         // Get the snapshot operation guard
         let mut snapshot_guard = request.volume_snapshot.operation_guard_wait().await?;
+        self.destroy_snap_guarded(registry, &mut snapshot_guard, request)
+            .await
+    }
+}
 
+impl OperationGuardArc<VolumeSpec> {
+    /// Destroy the volume snapshot, using the given snapshot operation guard.
+    pub(crate) async fn destroy_snap_guarded(
+        &mut self,
+        registry: &Registry,
+        snapshot_guard: &mut OperationGuardArc<VolumeSnapshot>,
+        request: &DestroyVolumeSnapshotRequest,
+    ) -> Result<(), SvcError> {
         // Get the volume state and start update.
         let state = registry
             .volume_state(
@@ -166,7 +182,7 @@ impl ResourceLifecycleWithLifetime for OperationGuardArc<VolumeSnapshot> {
         };
         let specs = registry.specs();
         let mut snapshot = specs
-            .get_or_create_snapshot(request)
+            .get_or_create_snapshot(request, volume.as_ref().encryption.clone())
             .operation_guard_wait()
             .await?;
 
@@ -0,0 +1,189 @@
+use crate::controller::{registry::Registry, resources::operations_helper::ResourceSpecsLocked};
+use agents::errors::SvcError;
+use grpc::csi_node_plugin::{
+    node_plugin_client::NodePluginClient, FreezeFsRequest, UnfreezeFsRequest,
+};
+use std::time::Duration;
+use stor_port::{
+    pstor::ObjectKey,
+    transport_api::ResourceKind,
+    types::v0::{
+        store::{
+            snapshots::schedule::{SnapshotScheduleSpec, SnapshotScheduleSpecKey},
+            volume::VolumeSpec,
+        },
+        transport::{
+            AppNodeId, CreateSnapshotSchedule, DestroySnapshotSchedule, SnapshotScheduleId,
+        },
+    },
+};
+
+/// Number of attempts at unfreezing a filesystem before giving up.
+const UNFREEZE_ATTEMPTS: u32 = 3;
+/// Delay between the attempts at unfreezing a filesystem.
+const UNFREEZE_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+impl ResourceSpecsLocked {
+    /// Get a copy of all snapshot schedule specs.
+    pub(crate) fn snapshot_schedules(&self) -> Vec<SnapshotScheduleSpec> {
+        let specs = self.read();
+        specs
+            .snapshot_schedules
+            .values()
+            .map(|v| v.lock().clone())
+            .collect()
+    }
+
+    /// Get a copy of the snapshot schedule spec with the given id.
+    pub(crate) fn snapshot_schedule(
+        &self,
+        id: &SnapshotScheduleId,
+    ) -> Result<SnapshotScheduleSpec, SvcError> {
+        let specs = self.read();
+        match specs.snapshot_schedules.get(id) {
+            Some(spec) => Ok(spec.lock().clone()),
+            None => Err(SvcError::NotFound {
+                kind: ResourceKind::SnapshotSchedule,
+                id: id.to_string(),
+            }),
+        }
+    }
+
+    /// Create a snapshot schedule spec, or update the existing one with the same id.
+    pub(crate) async fn create_snapshot_schedule(
+        &self,
+        registry: &Registry,
+        request: &CreateSnapshotSchedule,
+    ) -> Result<SnapshotScheduleSpec, SvcError> {
+        if let Some(volume_id) = request.target.volume() {
+            // Fail early if the volume does not exist, though it may still be deleted later.
+            self.volume_clone(volume_id)?;
+        }
+
+        let schedule = SnapshotScheduleSpec::from(request);
+        registry.store_obj(&schedule).await?;
+
+        let mut specs = self.write();
+        match specs.snapshot_schedules.get(&schedule.id) {
+            Some(spec) => {
+                *spec.lock() = schedule.clone();
+            }
+            None => {
+                specs.snapshot_schedules.insert(schedule.clone());
+            }
+        }
+        Ok(schedule)
+    }
+
+    /// Delete the snapshot schedule spec.
+    /// The snapshots which were already taken by the schedule are not deleted.
+    pub(crate) async fn destroy_snapshot_schedule(
+        &self,
+        registry: &Registry,
+        request: &DestroySnapshotSchedule,
+    ) -> Result<(), SvcError> {
+        self.snapshot_schedule(&request.id)?;
+        registry
+            .delete_kv(&SnapshotScheduleSpecKey::from(request).key())
+            .await?;
+        let mut specs = self.write();
+        specs.snapshot_schedules.remove(&request.id);
+        Ok(())
+    }
+}
+
+/// The filesystem of a published volume, which may be frozen on its app node.
+pub(crate) struct VolumeFilesystem {
+    endpoint: String,
+    volume_id: String,
+}
+
+impl VolumeFilesystem {
+    /// Get the filesystem of the given volume, if the volume is published to an app node.
+    pub(crate) fn published(registry: &Registry, volume: &VolumeSpec) -> Option<Self> {
+        let node_name = volume
+            .target_config
+            .as_ref()
+            .filter(|config| config.active_target().is_some())
+            .and_then(|config| config.frontend().node_name())?;
+        let app_node = registry
+            .specs()
+            .app_node_spec(&AppNodeId::from(node_name))?;
+        Some(Self {
+            endpoint: format!("http://{}", app_node.endpoint),
+            volume_id: volume.uuid.to_string(),
+        })
+    }
+
+    /// Freeze the filesystem through the app node's plugin.
+    /// Raw block volumes have no filesystem to freeze, and are therefore skipped.
+    pub(crate) async fn freeze(&self) -> Result<(), SvcError> {
+        let mut client = self.client().await?;
+        match client
+            .freeze_fs(FreezeFsRequest {
+                volume_id: self.volume_id.clone(),
+            })
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(status) if status.code() == tonic::Code::InvalidArgument => Ok(()),
+            Err(status) => Err(self.error(status)),
+        }
+    }
+
+    /// Unfreeze the filesystem through the app node's plugin.
+    /// A frozen filesystem blocks all of the application's writes, so the unfreeze is retried
+    /// a few times before giving up.
+    pub(crate) async fn unfreeze(&self) -> Result<(), SvcError> {
+        let mut attempt = 1;
+        loop {
+            match self.try_unfreeze().await {
+                Err(error) if attempt < UNFREEZE_ATTEMPTS => {
+                    tracing::warn!(
+                        volume.uuid = %self.volume_id,
+                        %error,
+                        attempt,
+                        "Failed to unfreeze the volume filesystem, retrying"
+                    );
+                    attempt += 1;
+                    tokio::time::sleep(UNFREEZE_RETRY_DELAY).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn try_unfreeze(&self) -> Result<(), SvcError> {
+        let mut client = self.client().await?;
+        match client
+            .unfreeze_fs(UnfreezeFsRequest {
+                volume_id: self.volume_id.clone(),
+            })
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(status) if status.code() == tonic::Code::InvalidArgument => Ok(()),
+            Err(status) => Err(self.error(status)),
+        }
+    }
+
+    async fn client(&self) -> Result<NodePluginClient<tonic::transport::Channel>, SvcError> {
+        NodePluginClient::connect(self.endpoint.clone())
+            .await
+            .map_err(|error| SvcError::Internal {
+                details: format!(
+                    "Failed to connect to the app node plugin at '{}': {error}",
+                    self.endpoint
+                ),
+            })
+    }
+
+    fn error(&self, status: tonic::Status) -> SvcError {
+        SvcError::Internal {
+            details: format!(
+                "Failed to quiesce the filesystem of volume '{}': {status}",
+                self.volume_id
+            ),
+        }
+    }
+}
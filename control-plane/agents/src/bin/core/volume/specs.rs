@@ -2,6 +2,7 @@ use crate::{
     controller::{
         registry::Registry,
         resources::{
+            operations::{ResourceCloning, ResourceLifecycle},
             operations_helper::{
                 GuardedOperationsHelper, OnCreateFail, OperationSequenceGuard, ResourceSpecs,
                 ResourceSpecsLocked, SpecOperationsHelper,
@@ -41,8 +42,9 @@ use stor_port::{
             SpecStatus, SpecTransaction,
         },
         transport::{
-            CreateReplica, CreateVolume, NodeId, PoolId, Protocol, Replica, ReplicaId, ReplicaName,
-            ReplicaOwners, SnapshotId, VolumeId, VolumeShareProtocol, VolumeState, VolumeStatus,
+            CreateReplica, CreateVolume, DestroyVolume, EncryptionKeyRef, NodeId, PoolId, Protocol,
+            Replica, ReplicaId, ReplicaName, ReplicaOwners, ResizeVolume, SnapshotId, VolumeId,
+            VolumeProperty, VolumeQos, VolumeShareProtocol, VolumeState, VolumeStatus,
         },
     },
 };
@@ -357,6 +359,79 @@ pub(crate) async fn resizeable_replicas(
     Ok(resizable_replicas)
 }
 
+/// Get the replicas of the volume if it can be shrunk to the requested size.
+/// The volume must be unpublished and all of its replicas must be online.
+/// As the filesystem on the volume is not visible here, the caller must confirm the size of the
+/// filesystem, which must fit within the requested size. Additionally, the data allocated on each
+/// replica must also fit within the requested size. Thick provisioned replicas are fully
+/// allocated and so cannot be checked, hence only thin provisioned volumes can be shrunk.
+pub(crate) async fn shrinkable_replicas(
+    spec: &VolumeSpec,
+    registry: &Registry,
+    request: &ResizeVolume,
+) -> Result<Vec<Replica>, SvcError> {
+    let requested_size = request.requested_size;
+    let shrink_error = |reason: &str| SvcError::VolumeShrink {
+        vol_id: spec.uuid_str(),
+        requested_size,
+        reason: reason.to_string(),
+    };
+    if spec.target().is_some() {
+        return Err(shrink_error("the volume is published"));
+    }
+    if !spec.thin {
+        return Err(shrink_error("only thin provisioned volumes can be shrunk"));
+    }
+    match request.filesystem_size {
+        None => {
+            return Err(shrink_error(
+                "the size of the filesystem on the volume must be confirmed",
+            ))
+        }
+        Some(filesystem_size) if filesystem_size > requested_size => {
+            return Err(shrink_error(&format!(
+                "the filesystem on the volume is '{filesystem_size}' bytes"
+            )))
+        }
+        Some(_) => {}
+    }
+
+    let spec_replicas = registry.specs().volume_replicas(spec.uid());
+    let mut replicas = Vec::with_capacity(spec_replicas.len());
+    for replica in spec_replicas {
+        let replica_id = replica.uuid().clone();
+        match registry.replica(&replica_id).await {
+            Ok(replica) if replica.online() => replicas.push(replica),
+            _ => {
+                return Err(shrink_error(&format!("replica '{replica_id}' is offline")));
+            }
+        }
+    }
+    if replicas.len() != spec.num_replicas as usize {
+        return Err(shrink_error("not all replicas of the volume are present"));
+    }
+
+    if let Some(replica) = replicas.iter().find(|replica| !replica.thin) {
+        return Err(shrink_error(&format!(
+            "replica '{}' is thick provisioned",
+            replica.uuid
+        )));
+    }
+    let allocated = replicas
+        .iter()
+        .filter_map(|replica| replica.space.as_ref())
+        .map(|space| space.allocated_bytes)
+        .max()
+        .unwrap_or_default();
+    if allocated > requested_size {
+        return Err(shrink_error(&format!(
+            "'{allocated}' bytes are allocated on the replicas"
+        )));
+    }
+
+    Ok(replicas)
+}
+
 /// Implementation of the ResourceSpecs which is retrieved from the ResourceSpecsLocked.
 /// During these calls, no other thread can add/remove elements from the list.
 impl ResourceSpecs {
@@ -430,17 +505,23 @@ impl ResourceSpecs {
             false => pagination.max_entries(),
         };
 
+        // Internal snapshots are hidden, so they're always filtered out.
         if let Some(vol_id) = vol_id {
             // We need to filter the resource map based on volume id and apply the pagination
             // parameters on that.
             PaginatedResult::new(
-                self.volume_snapshots
-                    .paginate_filter(offset, length, |s| s.lock().spec().source_id() == vol_id),
+                self.volume_snapshots.paginate_filter(offset, length, |s| {
+                    let snapshot = s.lock();
+                    !snapshot.spec().internal() && snapshot.spec().source_id() == vol_id
+                }),
                 last_result,
             )
         } else {
-            // Use complete resource map for pagination, without any filtering.
-            PaginatedResult::new(self.volume_snapshots.paginate(offset, length), last_result)
+            PaginatedResult::new(
+                self.volume_snapshots
+                    .paginate_filter(offset, length, |s| !s.lock().spec().internal()),
+                last_result,
+            )
         }
     }
 }
@@ -464,6 +545,18 @@ impl ResourceSpecsLocked {
             }),
         }
     }
+    /// Get the IO limits of the volume which owns the given nexus, if any.
+    pub(crate) fn nexus_owner_qos(&self, nexus: &NexusSpec) -> Option<VolumeQos> {
+        let owner = nexus.owner.as_ref()?;
+        self.volume_rsc(owner)
+            .and_then(|volume| volume.lock().qos.clone())
+    }
+    /// Get the encryption key reference of the volume which owns the given nexus, if any.
+    pub(crate) fn nexus_owner_encryption(&self, nexus: &NexusSpec) -> Option<EncryptionKeyRef> {
+        let owner = nexus.owner.as_ref()?;
+        self.volume_rsc(owner)
+            .and_then(|volume| volume.lock().encryption.clone())
+    }
     /// Get a guarded VolumeSpec for the volume with the given ID.
     pub(crate) async fn volume(
         &self,
@@ -477,6 +570,42 @@ impl ResourceSpecsLocked {
         }
     }
 
+    /// Destroy the volume with the given parameters.
+    /// A volume which was created from a snapshot is destroyed as a clone of the snapshot, and
+    /// the hidden snapshot of a volume clone is also destroyed once no clone references it.
+    pub(crate) async fn destroy_volume(
+        &self,
+        registry: &Registry,
+        request: &DestroyVolume,
+    ) -> Result<(), SvcError> {
+        let mut volume = self.volume(&request.uuid).await?;
+        let content_source = volume.as_ref().content_source.as_ref();
+        let snap_guard = match content_source {
+            None => None,
+            Some(source) => match self.volume_snapshot(source.snapshot()).await {
+                Ok(snap_guard) => Some(snap_guard),
+                Err(SvcError::VolSnapshotNotFound { .. }) => None,
+                Err(error) => return Err(error),
+            },
+        };
+
+        match snap_guard {
+            None => volume.destroy(registry, request).await,
+            Some(mut snap_guard) => {
+                snap_guard.destroy_clone(registry, request, volume).await?;
+                // The hidden snapshot of a volume clone is no longer needed by this volume.
+                if let Err(error) = snap_guard.destroy_unused_clone_source(registry).await {
+                    tracing::warn!(
+                        snapshot.uuid = %snap_guard.uuid(),
+                        %error,
+                        "Failed to destroy the clone source snapshot, will be garbage collected"
+                    );
+                }
+                Ok(())
+            }
+        }
+    }
+
     /// Get the AffinityGroupSpec for the given Affinity Group id.
     pub(crate) fn affinity_group_spec(
         &self,
@@ -812,6 +941,7 @@ impl ResourceSpecsLocked {
                 CreateVolumeSource::Snapshot(create_from_snap) => {
                     let mut spec = VolumeSpec::from(request.source());
                     spec.set_content_source(Some(create_from_snap.to_snapshot_source()));
+                    spec.encryption = create_from_snap.encryption();
                     specs.volumes.insert(spec)
                 }
             })
@@ -844,6 +974,10 @@ impl ResourceSpecsLocked {
 
         let volumes = self.volumes_rsc();
         for volume_spec in volumes {
+            // an interrupted snapshot restore is resumed by the snapshot restore reconciler
+            if volume_spec.lock().snapshot_restore().is_some() {
+                continue;
+            }
             if let Ok(mut guard) = volume_spec.operation_guard() {
                 if !guard.handle_incomplete_ops(registry).await {
                     // Not all pending operations could be handled.
@@ -897,15 +1031,19 @@ impl ResourceSpecsLocked {
     }
 
     /// Get or Create the resourced VolumeSnapshot for the given request.
+    /// The snapshot inherits the `encryption` key reference of its source volume.
     pub(crate) fn get_or_create_snapshot(
         &self,
         request: &VolumeSnapshotUserSpec,
+        encryption: Option<EncryptionKeyRef>,
     ) -> ResourceMutex<VolumeSnapshot> {
         let mut specs = self.write();
         if let Some(snapshot) = specs.volume_snapshots.get(request.uuid()) {
             snapshot.clone()
         } else {
-            specs.volume_snapshots.insert(VolumeSnapshot::from(request))
+            specs
+                .volume_snapshots
+                .insert(VolumeSnapshot::from(request).with_encryption(encryption))
         }
     }
 }
@@ -950,6 +1088,7 @@ impl SpecOperationsHelper for VolumeSpec {
                 | VolumeOperation::Republish(..)
                 | VolumeOperation::CreateSnapshot(..)
                 | VolumeOperation::DestroySnapshot(..)
+                | VolumeOperation::EndReplicaMove
         ) {
             // don't attempt to modify the volume parameters if the nexus target is not "stable"
             if self.target().is_some() != state.target.is_some() {
@@ -961,26 +1100,26 @@ impl SpecOperationsHelper for VolumeSpec {
         }
 
         match &operation {
-            VolumeOperation::Share(protocol) => match protocol {
-                VolumeShareProtocol::Nvmf => match &self.target() {
-                    None => Err(SvcError::VolumeNotPublished {
-                        vol_id: self.uuid_str(),
+            VolumeOperation::Share(_) => match &self.target() {
+                None => Err(SvcError::VolumeNotPublished {
+                    vol_id: self.uuid_str(),
+                }),
+                Some(target) => match target.protocol() {
+                    None => Ok(()),
+                    Some(protocol) => Err(SvcError::AlreadyShared {
+                        kind: self.kind(),
+                        id: self.uuid_str(),
+                        share: protocol.to_string(),
                     }),
-                    Some(target) => match target.protocol() {
-                        None => Ok(()),
-                        Some(protocol) => Err(SvcError::AlreadyShared {
-                            kind: self.kind(),
-                            id: self.uuid_str(),
-                            share: protocol.to_string(),
-                        }),
-                    },
                 },
-                VolumeShareProtocol::Iscsi => Err(SvcError::InvalidShareProtocol {
-                    kind: ResourceKind::Volume,
-                    id: self.uuid_str(),
-                    share: format!("{protocol:?}"),
-                }),
             },
+            // the volume is only encrypted when its target is shared
+            VolumeOperation::Unshare if self.encrypted() => {
+                Err(SvcError::InvalidVolumeEncryption {
+                    vol_id: self.uuid_str(),
+                    reason: "an encrypted volume cannot be unshared".to_string(),
+                })
+            }
             VolumeOperation::Unshare => match self.target() {
                 None => Err(SvcError::NotShared {
                     kind: self.kind(),
@@ -993,27 +1132,27 @@ impl SpecOperationsHelper for VolumeSpec {
                 _ => Ok(()),
             },
             VolumeOperation::PublishOld(_) => Err(SvcError::InvalidArguments {}),
+            VolumeOperation::Publish(args) if args.protocol().is_none() && self.encrypted() => {
+                Err(SvcError::InvalidVolumeEncryption {
+                    vol_id: self.uuid_str(),
+                    reason: "an encrypted volume must be published with a share protocol"
+                        .to_string(),
+                })
+            }
             VolumeOperation::Publish(args) => match args.protocol() {
                 None => Ok(()),
-                Some(protocol) => match protocol {
-                    VolumeShareProtocol::Nvmf => {
-                        if let Some(target) = self.target() {
-                            Err(SvcError::VolumeAlreadyPublished {
-                                vol_id: self.uuid_str(),
-                                node: target.node().to_string(),
-                                protocol: format!("{:?}", target.protocol()),
-                            })
-                        } else {
-                            self.publish_context = Some(args.publish_context());
-                            Ok(())
-                        }
+                Some(_) => {
+                    if let Some(target) = self.target() {
+                        Err(SvcError::VolumeAlreadyPublished {
+                            vol_id: self.uuid_str(),
+                            node: target.node().to_string(),
+                            protocol: format!("{:?}", target.protocol()),
+                        })
+                    } else {
+                        self.publish_context = Some(args.publish_context());
+                        Ok(())
                     }
-                    VolumeShareProtocol::Iscsi => Err(SvcError::InvalidShareProtocol {
-                        kind: ResourceKind::Volume,
-                        id: self.uuid_str(),
-                        share: format!("{:?}", args.protocol()),
-                    }),
-                },
+                }
             },
             VolumeOperation::Republish(args) => match args.protocol() {
                 VolumeShareProtocol::Nvmf => Ok(()),
@@ -1023,6 +1162,20 @@ impl SpecOperationsHelper for VolumeSpec {
                     share: format!("{:?}", args.protocol()),
                 }),
             },
+            VolumeOperation::UpdateFrontend(frontend) => match self.active_config() {
+                None => Err(SvcError::VolumeNotPublished {
+                    vol_id: self.uuid_str(),
+                }),
+                // only read-only targets may be shared by multiple frontend nodes
+                Some(config) if !config.frontend().read_only() || !frontend.read_only() => {
+                    Err(SvcError::VolumeAlreadyPublished {
+                        vol_id: self.uuid_str(),
+                        node: config.target().node().to_string(),
+                        protocol: format!("{:?}", config.target().protocol()),
+                    })
+                }
+                Some(_) => Ok(()),
+            },
             VolumeOperation::Unpublish if self.target().is_none() => {
                 Err(SvcError::VolumeNotPublished {
                     vol_id: self.uuid_str(),
@@ -1034,7 +1187,12 @@ impl SpecOperationsHelper for VolumeSpec {
             }
 
             VolumeOperation::SetReplica(replica_count) => {
-                if self.has_snapshots() {
+                if let Some(replica_move) = &self.replica_move {
+                    Err(SvcError::ReplicaMoveInProgress {
+                        volume: self.uuid_str(),
+                        replica: replica_move.replica.to_string(),
+                    })
+                } else if self.has_snapshots() {
                     Err(SvcError::NReplSnapshotNotAllowed {})
                 } else if *replica_count == self.num_replicas {
                     Err(SvcError::ReplicaCountAchieved {
@@ -1121,7 +1279,48 @@ impl SpecOperationsHelper for VolumeSpec {
             VolumeOperation::CreateSnapshot(_) => Ok(()),
             VolumeOperation::DestroySnapshot(_) => Ok(()),
             VolumeOperation::Resize(_) => Ok(()),
+            VolumeOperation::SetVolumeProperty(VolumeProperty::Qos(qos)) => {
+                qos.validate().map_err(|reason| SvcError::InvalidVolumeQos {
+                    vol_id: self.uuid_str(),
+                    reason,
+                })
+            }
+            VolumeOperation::SetVolumeProperty(VolumeProperty::EncryptionKey(key_ref)) => {
+                if self.encrypted() {
+                    key_ref.validate()
+                } else {
+                    Err("the volume is not encrypted".to_string())
+                }
+                .map_err(|reason| SvcError::InvalidVolumeEncryption {
+                    vol_id: self.uuid_str(),
+                    reason,
+                })
+            }
             VolumeOperation::SetVolumeProperty(_) => Ok(()),
+            VolumeOperation::StartReplicaMove(_) => match &self.replica_move {
+                Some(replica_move) => Err(SvcError::ReplicaMoveInProgress {
+                    volume: self.uuid_str(),
+                    replica: replica_move.replica.to_string(),
+                }),
+                None => Ok(()),
+            },
+            VolumeOperation::EndReplicaMove => Ok(()),
+            VolumeOperation::RestoreSnapshot(_) => {
+                if let Some(target) = self.target() {
+                    Err(SvcError::VolumeAlreadyPublished {
+                        vol_id: self.uuid_str(),
+                        node: target.node().to_string(),
+                        protocol: format!("{:?}", target.protocol()),
+                    })
+                } else if let Some(replica_move) = &self.replica_move {
+                    Err(SvcError::ReplicaMoveInProgress {
+                        volume: self.uuid_str(),
+                        replica: replica_move.replica.to_string(),
+                    })
+                } else {
+                    Ok(())
+                }
+            }
         }?;
         self.start_op(operation);
         Ok(())
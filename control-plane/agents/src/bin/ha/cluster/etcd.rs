@@ -1,10 +1,13 @@
 use crate::switchover::SwitchOverRequest;
 use futures::lock::Mutex;
 use http::Uri;
-use std::{sync::Arc, time::Duration};
+use std::{future::Future, sync::Arc, time::Duration};
 use stor_port::{
     pstor::{etcd::Etcd, *},
-    types::v0::store::switchover::SwitchOverSpec,
+    types::v0::store::{
+        registry::{ControlPlaneService, HaClusterLeader, HaClusterLeaderKey},
+        switchover::SwitchOverSpec,
+    },
 };
 use tracing::{debug, error};
 
@@ -18,8 +21,32 @@ pub(crate) struct EtcdStore {
 impl EtcdStore {
     /// Create a new Etcd client.
     pub(crate) async fn new(endpoint: Uri, timeout: Duration) -> Result<Self, Error> {
-        match tokio::time::timeout(timeout, async { Etcd::new(&endpoint.to_string()).await }).await
-        {
+        Self::connect(Etcd::new(&endpoint.to_string()), timeout, timeout).await
+    }
+
+    /// Create a new Etcd client which holds the leadership lease of the HA cluster agent.
+    /// All writes are then fenced by the lease, and if another instance takes over the lease
+    /// this process exits.
+    /// Fails if the lease cannot be obtained within the `lease_ttl`, ie: if there is a leader.
+    pub(crate) async fn new_leased(
+        endpoint: Uri,
+        timeout: Duration,
+        lease_ttl: Duration,
+    ) -> Result<Self, Error> {
+        let store = Etcd::new_leased(
+            [endpoint.to_string()],
+            ControlPlaneService::HaClusterAgent.to_string(),
+            lease_ttl,
+        );
+        Self::connect(store, timeout + lease_ttl, timeout).await
+    }
+
+    async fn connect(
+        store: impl Future<Output = Result<Etcd, Error>>,
+        connect_timeout: Duration,
+        timeout: Duration,
+    ) -> Result<Self, Error> {
+        match tokio::time::timeout(connect_timeout, store).await {
             Ok(v) => {
                 let store = v?;
                 Ok(Self {
@@ -31,7 +58,7 @@ impl EtcdStore {
                 error!(%error, "Failed to create persistent store client");
                 Err(Error::Timeout {
                     operation: "connect".to_string(),
-                    timeout,
+                    timeout: connect_timeout,
                 })
             }
         }
@@ -83,6 +110,30 @@ impl EtcdStore {
         }
     }
 
+    /// Get the current leader of the HA cluster agent instances, if any.
+    pub(crate) async fn leader(&self) -> Result<Option<HaClusterLeader>, anyhow::Error> {
+        let mut store = self.store.lock().await;
+        match tokio::time::timeout(self.timeout, async move {
+            store.get_obj(&HaClusterLeaderKey::default()).await
+        })
+        .await
+        {
+            Ok(result) => match result {
+                Ok(leader) => Ok(Some(leader)),
+                Err(Error::MissingEntry { .. }) => Ok(None),
+                Err(error) => Err(error.into()),
+            },
+            Err(error) => {
+                error!(%error, "Failed to read from persistent store");
+                Err(Error::Timeout {
+                    operation: "Get".to_string(),
+                    timeout: self.timeout,
+                }
+                .into())
+            }
+        }
+    }
+
     /// Get incomplete requests stored in Etcd.
     /// Request with error or path published is considered a complete request.
     pub(crate) async fn fetch_incomplete_requests(
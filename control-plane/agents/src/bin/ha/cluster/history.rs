@@ -0,0 +1,113 @@
+use crate::switchover::SwitchOverRequest;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+use stor_port::types::v0::transport::{SwitchOverInfo, SwitchOvers, VolumeId};
+
+/// Keeps track of the in-flight switchovers along with a bounded history of the completed ones,
+/// so that they can be queried.
+/// The history is kept in memory only, whereas the in-flight switchovers are reloaded from the
+/// persistent store on restart.
+#[derive(Debug, Clone)]
+pub(crate) struct SwitchOverHistory {
+    inner: Arc<parking_lot::Mutex<HistoryInner>>,
+}
+
+#[derive(Debug)]
+struct HistoryInner {
+    /// The switchovers which are in-flight, by volume.
+    in_flight: HashMap<VolumeId, SwitchOverInfo>,
+    /// The completed switchovers, from the most to the least recent.
+    completed: VecDeque<SwitchOverInfo>,
+    /// Maximum number of completed switchovers to retain.
+    size: usize,
+}
+
+impl SwitchOverHistory {
+    /// Create a new `Self` which retains up to `size` completed switchovers.
+    pub(crate) fn new(size: usize) -> Self {
+        Self {
+            inner: Arc::new(parking_lot::Mutex::new(HistoryInner {
+                in_flight: HashMap::new(),
+                completed: VecDeque::with_capacity(size),
+                size,
+            })),
+        }
+    }
+
+    /// Record the current state of the given in-flight switchover request.
+    /// The last error is retained until a new one is encountered.
+    pub(crate) fn update(&self, request: &SwitchOverRequest, error: Option<String>) {
+        let mut info = request.info();
+        let mut inner = self.inner.lock();
+        info.last_error = error.or_else(|| {
+            inner
+                .in_flight
+                .get(&info.volume)
+                .and_then(|existing| existing.last_error.clone())
+        });
+        inner.in_flight.insert(info.volume.clone(), info);
+    }
+
+    /// Move the given switchover request from the in-flight switchovers into the history of the
+    /// completed ones, evicting the oldest ones if the history is full.
+    pub(crate) fn complete(&self, request: &SwitchOverRequest) {
+        let mut info = request.info();
+        let mut inner = self.inner.lock();
+        if let Some(existing) = inner.in_flight.remove(&info.volume) {
+            info.last_error = existing.last_error;
+        }
+        info.end_time = Some(info.last_update);
+        if inner.size == 0 {
+            return;
+        }
+        inner.completed.truncate(inner.size - 1);
+        inner.completed.push_front(info);
+    }
+
+    /// Get the in-flight switchovers, most recently started first, followed by the completed
+    /// switchovers, most recently completed first.
+    pub(crate) fn list(&self) -> SwitchOvers {
+        let inner = self.inner.lock();
+        let mut in_flight = inner.in_flight.values().cloned().collect::<Vec<_>>();
+        in_flight.sort_by(|a, b| b.start_time.cmp(&a.start_time));
+        in_flight.extend(inner.completed.iter().cloned());
+        SwitchOvers(in_flight)
+    }
+}
+
+#[test]
+fn switchover_history() {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use stor_port::types::v0::transport::SwitchOverStage;
+    let sock: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
+    let vol1 = VolumeId::try_from("ec4e66fd-3b33-4439-b504-d49aba53da26").unwrap();
+    let vol2 = VolumeId::try_from("a9d7ddc4-8a31-4b87-9b20-4b4c5a6a3a31").unwrap();
+    let vol3 = VolumeId::try_from("0ba9c4e6-bc5c-4e4c-8e0e-6c7ea1c2ab3c").unwrap();
+    let sw1 = SwitchOverRequest::new(sock, vol1.clone(), "nn".into(), "nw".to_string());
+    let sw2 = SwitchOverRequest::new(sock, vol2.clone(), "nn".into(), "nw".to_string());
+    let sw3 = SwitchOverRequest::new(sock, vol3.clone(), "nn".into(), "nw".to_string());
+
+    let history = SwitchOverHistory::new(2);
+    history.update(&sw1, None);
+    history.update(&sw1, Some("failed".to_string()));
+    history.update(&sw1, None);
+    history.update(&sw2, None);
+    let list = history.list().into_inner();
+    assert_eq!(list.len(), 2);
+    assert!(list.iter().all(|info| !info.completed()));
+    let info1 = list.iter().find(|info| info.volume == vol1).unwrap();
+    assert_eq!(info1.last_error, Some("failed".to_string()));
+    assert_eq!(info1.stage, SwitchOverStage::Init);
+
+    history.complete(&sw1);
+    history.complete(&sw2);
+    history.update(&sw3, None);
+    history.complete(&sw3);
+    let list = history.list().into_inner();
+    assert_eq!(list.len(), 2);
+    assert!(list.iter().all(|info| info.completed()));
+    assert_eq!(list[0].volume, vol3);
+    assert_eq!(list[1].volume, vol2);
+}
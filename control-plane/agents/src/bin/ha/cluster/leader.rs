@@ -0,0 +1,142 @@
+use crate::{etcd::EtcdStore, volume::VolumeMover};
+use grpc::operations::ha_node::client::ClusterAgentClient;
+use http::Uri;
+use once_cell::sync::OnceCell;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use stor_port::{
+    pstor::Error as StoreError,
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind},
+    types::v0::store::registry::HaClusterLeader,
+};
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+/// Leadership state of this HA cluster agent instance.
+/// Only the leader processes the switchover requests, whereas the standby instances forward
+/// them to the leader whilst campaigning to take over, should the leader go away.
+#[derive(Clone)]
+pub(crate) struct Leadership {
+    inner: Arc<LeadershipInner>,
+}
+
+struct LeadershipInner {
+    /// Store client used to look up the current leader.
+    store: EtcdStore,
+    /// The grpc endpoint advertised to the other instances, if any.
+    endpoint: Option<SocketAddr>,
+    /// The volume mover, set once this instance becomes the leader.
+    mover: OnceCell<VolumeMover>,
+    /// Cached client to the current leader.
+    leader: Mutex<Option<(SocketAddr, Arc<ClusterAgentClient>)>>,
+}
+
+impl Leadership {
+    /// Create a new `Self` which advertises the given endpoint once it becomes the leader.
+    pub(crate) fn new(store: EtcdStore, endpoint: Option<SocketAddr>) -> Self {
+        Self {
+            inner: Arc::new(LeadershipInner {
+                store,
+                endpoint,
+                mover: OnceCell::new(),
+                leader: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Get the volume mover if this instance is the leader.
+    pub(crate) fn mover(&self) -> Option<&VolumeMover> {
+        self.inner.mover.get()
+    }
+
+    /// Campaign for the leadership until it's won.
+    /// Once elected, the leader is advertised, the mover is built with the leased store so that
+    /// all of its writes are fenced by the lease and the incomplete requests are resumed.
+    /// If the lease is lost thereafter, the process exits so a standby instance may take over.
+    pub(crate) async fn campaign(
+        &self,
+        store_endpoint: Uri,
+        timeout: Duration,
+        lease_ttl: Duration,
+        new_mover: impl FnOnce(EtcdStore) -> VolumeMover,
+    ) -> anyhow::Result<()> {
+        let store = loop {
+            match EtcdStore::new_leased(store_endpoint.clone(), timeout, lease_ttl).await {
+                Ok(store) => break store,
+                Err(StoreError::Timeout { .. }) => {
+                    tracing::debug!("Another instance holds the leadership, retrying...");
+                }
+                Err(error) => {
+                    warn!(%error, "Failed to campaign for the leadership, retrying...");
+                    tokio::time::sleep(timeout).await;
+                }
+            }
+        };
+
+        let instance_name = std::env::var("MY_POD_NAME").unwrap_or_default();
+        info!(instance.name = %instance_name, "Elected as the leader");
+        store
+            .store_obj(&HaClusterLeader {
+                instance_name,
+                endpoint: self.inner.endpoint,
+            })
+            .await?;
+
+        let entries = store.fetch_incomplete_requests().await?;
+        let mover = new_mover(store);
+        mover.send_switchover_req(entries).await?;
+
+        if self.inner.mover.set(mover).is_err() {
+            error!("The leadership should only be won once");
+        }
+        Ok(())
+    }
+
+    /// Get a client to the current leader, so that requests can be forwarded to it.
+    pub(crate) async fn leader(&self) -> Result<Arc<ClusterAgentClient>, ReplyError> {
+        let leader = self
+            .inner
+            .store
+            .leader()
+            .await
+            .map_err(|error| unavailable(format!("Failed to look up the leader: {error}")))?
+            .ok_or_else(|| unavailable("No leader has been elected".to_string()))?;
+        let endpoint = leader.endpoint.ok_or_else(|| {
+            unavailable(format!(
+                "The leader '{}' has no endpoint to forward to",
+                leader.instance_name
+            ))
+        })?;
+        if Some(endpoint) == self.inner.endpoint {
+            // we're not the leader, this is a stale entry from our own past leadership
+            return Err(unavailable(format!(
+                "The leader '{}' is no longer available",
+                leader.instance_name
+            )));
+        }
+
+        let mut cached = self.inner.leader.lock().await;
+        match cached.as_ref() {
+            Some((cached_endpoint, client)) if cached_endpoint == &endpoint => Ok(client.clone()),
+            _ => {
+                let uri = Uri::builder()
+                    .scheme("http")
+                    .authority(endpoint.to_string())
+                    .path_and_query("")
+                    .build()
+                    .map_err(|error| unavailable(error.to_string()))?;
+                let client = Arc::new(ClusterAgentClient::new(uri, None).await);
+                *cached = Some((endpoint, client.clone()));
+                Ok(client)
+            }
+        }
+    }
+}
+
+fn unavailable(source: String) -> ReplyError {
+    ReplyError {
+        kind: ReplyErrorKind::Unavailable,
+        resource: ResourceKind::Unknown,
+        source,
+        extra: "This HA cluster agent instance is not the leader".to_string(),
+    }
+}
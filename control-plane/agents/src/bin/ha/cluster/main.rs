@@ -2,7 +2,7 @@ use clap::Parser;
 use grpc::client::CoreClient;
 use http::Uri;
 use once_cell::sync::OnceCell;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use tracing::info;
 use utils::{
     package_description,
@@ -11,7 +11,10 @@ use utils::{
 };
 
 mod etcd;
+mod history;
+mod leader;
 mod nodes;
+mod policy;
 mod server;
 mod switchover;
 mod volume;
@@ -23,6 +26,12 @@ struct Cli {
     #[clap(long, short, default_value = DEFAULT_CLUSTER_AGENT_SERVER_ADDR)]
     grpc_endpoint: SocketAddr,
 
+    /// IP address advertised to the other cluster-agent instances, so that they may forward
+    /// requests to this instance when it's the leader.
+    /// If not set, the IP address of the grpc endpoint is used, unless it's unspecified.
+    #[clap(long, env = "MY_POD_IP")]
+    advertise_ip: Option<IpAddr>,
+
     /// The Persistent Store URL to connect to.
     #[clap(long, short, default_value = "http://localhost:2379")]
     store: Uri,
@@ -31,6 +40,10 @@ struct Cli {
     #[clap(long, default_value = utils::STORE_OP_TIMEOUT)]
     store_timeout: humantime::Duration,
 
+    /// The lease lock ttl for the leadership of the cluster-agent instances.
+    #[clap(long, default_value = utils::STORE_LEASE_LOCK_TTL)]
+    store_lease_ttl: humantime::Duration,
+
     /// Core gRPC server URL or address.
     #[clap(long, short, default_value = DEFAULT_GRPC_CLIENT_ADDR)]
     core_grpc: Uri,
@@ -43,9 +56,40 @@ struct Cli {
     #[clap(short, long, env = "TRACING_TAGS", value_delimiter=',', value_parser = utils::tracing_telemetry::parse_key_value)]
     tracing_tags: Vec<KeyValue>,
 
-    /// If set, configures the fast requeue period to this duration.
+    /// The fast requeue period of a failed switchover stage.
+    #[clap(long, default_value = "10s")]
+    fast_requeue: humantime::Duration,
+
+    /// Number of fast requeues of a failed switchover stage, before requeueing slowly.
+    #[clap(long, default_value_t = 18)]
+    fast_requeue_attempts: u64,
+
+    /// The slow requeue period of a failed switchover stage.
+    #[clap(long, default_value = "30s")]
+    slow_requeue: humantime::Duration,
+
+    /// Overrides the fast requeue period of a switchover stage, eg: `republish-volume=5s`.
+    /// The stages are: init, republish-volume, replace-path, delete-target, successful, errored.
+    #[clap(long)]
+    stage_requeue: Vec<policy::StageBackoff>,
+
+    /// Maximum number of switchovers running concurrently across the cluster.
+    #[clap(long, default_value_t = 4)]
+    max_switchovers: usize,
+
+    /// Maximum number of switchovers running concurrently for the volume targets of a node.
     #[clap(long)]
-    fast_requeue: Option<humantime::Duration>,
+    max_node_switchovers: Option<usize>,
+
+    /// Volume label of a switchover priority class, as `key=value`, eg: `tier=critical`.
+    /// May be repeated, from the highest to the lowest priority class.
+    /// Volumes which match no class have the lowest priority.
+    #[clap(long)]
+    priority_label: Vec<policy::VolumeLabel>,
+
+    /// Maximum number of completed switchovers which are retained for querying.
+    #[clap(long, default_value_t = 100)]
+    switchover_history: usize,
 
     /// Events message-bus endpoint url.
     #[clap(long, short)]
@@ -64,6 +108,14 @@ impl Cli {
     fn args() -> Self {
         Cli::parse()
     }
+
+    /// The grpc endpoint advertised to the other instances, if any.
+    fn advertise_endpoint(&self) -> Option<SocketAddr> {
+        let ip = self
+            .advertise_ip
+            .or_else(|| Some(self.grpc_endpoint.ip()).filter(|ip| !ip.is_unspecified()))?;
+        Some(SocketAddr::new(ip, self.grpc_endpoint.port()))
+    }
 }
 
 /// Once cell static variable to store the grpc client and initialize once at startup.
@@ -100,19 +152,36 @@ async fn main() -> anyhow::Result<()> {
         .ok()
         .expect("Expect to be initialised only once");
 
-    let store = etcd::EtcdStore::new(cli.store, cli.store_timeout.into()).await?;
+    let store = etcd::EtcdStore::new(cli.store.clone(), cli.store_timeout.into()).await?;
     let node_list = nodes::NodeList::new();
-
-    let entries = store.fetch_incomplete_requests().await?;
-
-    // Node list has ref counted list internally.
-    let mover = volume::VolumeMover::new(store, cli.fast_requeue, node_list.clone());
-    mover.send_switchover_req(entries).await?;
+    let history = history::SwitchOverHistory::new(cli.switchover_history);
+    let leadership = leader::Leadership::new(store, cli.advertise_endpoint());
+    let policy = policy::SwitchOverPolicy::new(
+        cli.max_switchovers,
+        cli.max_node_switchovers,
+        cli.priority_label.clone(),
+        policy::Backoff::new(
+            cli.fast_requeue.into(),
+            cli.fast_requeue_attempts,
+            cli.slow_requeue.into(),
+            cli.stage_requeue.clone(),
+        ),
+    );
 
     info!("Starting cluster-agent server");
-    let result = server::ClusterAgent::new(cli.grpc_endpoint, node_list, mover)
-        .run()
-        .await;
+    let grpc_server =
+        server::ClusterAgent::new(cli.grpc_endpoint, node_list.clone(), leadership.clone()).run();
+    let campaign = leadership.campaign(
+        cli.store,
+        cli.store_timeout.into(),
+        cli.store_lease_ttl.into(),
+        |store| volume::VolumeMover::new(store, policy, node_list, history),
+    );
+
+    let result = tokio::select! {
+        result = grpc_server => result.map_err(anyhow::Error::from),
+        Err(error) = campaign => Err(error),
+    };
     utils::tracing_telemetry::flush_traces();
     result?;
 
@@ -4,8 +4,9 @@ use crate::{
 };
 use stor_port::{
     transport_api::{ReplyError, ResourceKind},
-    types::v0::transport::NodeId,
+    types::v0::transport::{NodeId, SwitchOverInfo, VolumeId},
 };
+use utils::NVME_TARGET_NQN_PREFIX;
 
 use std::{collections::HashMap, net::SocketAddr, sync::Arc};
 use tokio::sync::Mutex;
@@ -105,4 +106,46 @@ impl NodeList {
         failed_path.insert(path, record);
         Ok(())
     }
+
+    /// Send a planned switchover request to the switchover engine, which moves the target of the
+    /// given volume whilst its path from the given node is still healthy.
+    /// The node agent of the node must have registered.
+    pub(crate) async fn move_target(
+        &self,
+        node: NodeId,
+        volume: VolumeId,
+        target_node: Option<NodeId>,
+        mover: &VolumeMover,
+    ) -> Result<SwitchOverInfo, ReplyError> {
+        let endpoint = self.list.lock().await.get(&node).copied().ok_or_else(|| {
+            ReplyError::failed_precondition(
+                ResourceKind::Node,
+                node.to_string(),
+                "The HA node agent of the frontend node is not registered".to_owned(),
+            )
+        })?;
+        let path = format!("{NVME_TARGET_NQN_PREFIX}{volume}");
+
+        let mut failed_path = self.failed_path.lock().await;
+        if failed_path.contains_key(&path) {
+            return Err(ReplyError::already_exist(
+                ResourceKind::NvmePath,
+                path,
+                "Path is already being switched over".to_owned(),
+            ));
+        }
+
+        info!(node.id=%node, %path, ?target_node, "Sending planned switchover");
+
+        let request = SwitchOverRequest::new(endpoint, volume, node, path.clone())
+            .with_target_node(target_node);
+        let info = request.info();
+        let stage = mover.start(request).await?;
+        let record = PathRecord {
+            _socket: endpoint,
+            stage,
+        };
+        failed_path.insert(path, record);
+        Ok(info)
+    }
 }
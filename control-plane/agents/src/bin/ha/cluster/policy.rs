@@ -0,0 +1,324 @@
+use crate::switchover::{Stage, SwitchOverRequest};
+use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
+use stor_port::types::v0::transport::{NodeId, Volume};
+use tokio::sync::Notify;
+
+/// Policy which throttles and prioritises the switchovers, so that a mass failure does not flood
+/// the core agent with republish calls.
+#[derive(Debug, Clone)]
+pub(crate) struct SwitchOverPolicy {
+    /// Maximum number of switchovers running concurrently across the cluster.
+    max_concurrent: usize,
+    /// Maximum number of switchovers running concurrently for the targets of a node.
+    max_per_node: Option<usize>,
+    /// Volume labels of the priority classes, from the highest to the lowest priority.
+    priority_labels: Vec<VolumeLabel>,
+    /// Requeue delays of the failed switchovers.
+    backoff: Backoff,
+}
+
+impl SwitchOverPolicy {
+    /// Create a new `Self`.
+    pub(crate) fn new(
+        max_concurrent: usize,
+        max_per_node: Option<usize>,
+        priority_labels: Vec<VolumeLabel>,
+        backoff: Backoff,
+    ) -> Self {
+        Self {
+            max_concurrent: max_concurrent.max(1),
+            max_per_node: max_per_node.map(|max| max.max(1)),
+            priority_labels,
+            backoff,
+        }
+    }
+
+    /// Maximum number of switchovers running concurrently across the cluster.
+    pub(crate) fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    /// Get the requeue delays of the failed switchovers.
+    pub(crate) fn backoff(&self) -> &Backoff {
+        &self.backoff
+    }
+
+    /// Classify the switchover of the given volume.
+    /// The priority is given by the first priority class matching the volume labels, the higher
+    /// the class the higher the priority, whereas volumes without any matching label get the
+    /// lowest priority.
+    pub(crate) fn classify(&self, volume: &Volume) -> SwitchOverClass {
+        let spec = volume.spec();
+        let labels = spec.labels.unwrap_or_default();
+        let priority = self
+            .priority_labels
+            .iter()
+            .position(|label| labels.get(&label.key) == Some(&label.value))
+            .map(|index| (self.priority_labels.len() - index) as u32)
+            .unwrap_or_default();
+        SwitchOverClass {
+            priority,
+            node: spec
+                .active_config()
+                .map(|config| config.target().node().clone()),
+        }
+    }
+}
+
+/// A volume label, specified as `key=value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VolumeLabel {
+    key: String,
+    value: String,
+}
+
+impl FromStr for VolumeLabel {
+    type Err = String;
+
+    fn from_str(label: &str) -> Result<Self, Self::Err> {
+        match label.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok(Self {
+                key: key.to_string(),
+                value: value.to_string(),
+            }),
+            _ => Err(format!(
+                "Invalid volume label '{label}', expected 'key=value'"
+            )),
+        }
+    }
+}
+
+/// The priority and the throttling node of a switchover.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct SwitchOverClass {
+    /// The priority of the switchover, where 0 is the lowest.
+    pub(crate) priority: u32,
+    /// The node of the volume target which is switched over, if known.
+    pub(crate) node: Option<NodeId>,
+}
+
+/// Requeue delays of the failed switchovers.
+/// The failed switchovers are first requeued after a fast delay, which may be tuned per stage,
+/// and then after a slow delay once the fast attempts are exhausted.
+#[derive(Debug, Clone)]
+pub(crate) struct Backoff {
+    fast: Duration,
+    fast_attempts: u64,
+    slow: Duration,
+    stages: HashMap<Stage, Duration>,
+}
+
+impl Backoff {
+    /// Create a new `Self`.
+    pub(crate) fn new(
+        fast: Duration,
+        fast_attempts: u64,
+        slow: Duration,
+        stages: Vec<StageBackoff>,
+    ) -> Self {
+        Self {
+            fast,
+            fast_attempts,
+            slow,
+            stages: stages
+                .into_iter()
+                .map(|backoff| (backoff.stage, backoff.delay))
+                .collect(),
+        }
+    }
+
+    /// Get the delay before retrying the given stage after `retry_count` failed attempts.
+    pub(crate) fn delay(&self, stage: Stage, retry_count: u64) -> Duration {
+        if retry_count < self.fast_attempts {
+            self.stages.get(&stage).copied().unwrap_or(self.fast)
+        } else {
+            self.slow
+        }
+    }
+}
+
+/// The fast requeue delay of a stage, specified as `stage=duration`, eg: `republish-volume=5s`.
+#[derive(Debug, Clone)]
+pub(crate) struct StageBackoff {
+    stage: Stage,
+    delay: Duration,
+}
+
+impl FromStr for StageBackoff {
+    type Err = String;
+
+    fn from_str(backoff: &str) -> Result<Self, Self::Err> {
+        let Some((stage, delay)) = backoff.split_once('=') else {
+            return Err(format!(
+                "Invalid stage backoff '{backoff}', expected 'stage=duration'"
+            ));
+        };
+        let stage = match stage {
+            "init" => Stage::Init,
+            "republish-volume" => Stage::RepublishVolume,
+            "replace-path" => Stage::ReplacePath,
+            "delete-target" => Stage::DeleteTarget,
+            "successful" => Stage::Successful,
+            "errored" => Stage::Errored,
+            _ => return Err(format!("Invalid switchover stage '{stage}'")),
+        };
+        let delay = humantime::parse_duration(delay).map_err(|error| error.to_string())?;
+        Ok(Self { stage, delay })
+    }
+}
+
+/// Queue of the switchovers waiting to be processed.
+/// The switchovers are dequeued by priority and then by their natural order, skipping over
+/// those whose target node has reached its concurrency limit.
+#[derive(Debug, Clone)]
+pub(crate) struct SwitchOverQueue {
+    inner: Arc<parking_lot::Mutex<QueueInner>>,
+    notify: Arc<Notify>,
+    max_per_node: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+struct QueueInner {
+    pending: Vec<SwitchOverRequest>,
+    running: HashMap<NodeId, usize>,
+}
+
+impl SwitchOverQueue {
+    /// Create a new `Self` which runs no more than `max_per_node` switchovers per node.
+    pub(crate) fn new(policy: &SwitchOverPolicy) -> Self {
+        Self {
+            inner: Default::default(),
+            notify: Arc::new(Notify::new()),
+            max_per_node: policy.max_per_node,
+        }
+    }
+
+    /// Add the given request to the queue.
+    pub(crate) fn push(&self, request: SwitchOverRequest) {
+        self.inner.lock().pending.push(request);
+        self.notify.notify_waiters();
+    }
+
+    /// Wait for the next request which can be processed.
+    /// The request counts towards the concurrency limit of its node until the returned slot is
+    /// dropped.
+    pub(crate) async fn pop(&self) -> (SwitchOverRequest, QueueSlot) {
+        loop {
+            let notified = self.notify.notified();
+            if let Some(next) = self.try_pop() {
+                return next;
+            }
+            notified.await;
+        }
+    }
+
+    fn try_pop(&self) -> Option<(SwitchOverRequest, QueueSlot)> {
+        let mut inner = self.inner.lock();
+        let index = inner
+            .pending
+            .iter()
+            .enumerate()
+            .filter(
+                |(_, request)| match (request.throttle_node(), self.max_per_node) {
+                    (Some(node), Some(max)) => inner.running.get(node).copied().unwrap_or(0) < max,
+                    _ => true,
+                },
+            )
+            .min_by(|(_, a), (_, b)| b.priority().cmp(&a.priority()).then_with(|| a.cmp(b)))
+            .map(|(index, _)| index)?;
+        let request = inner.pending.swap_remove(index);
+        let node = request.throttle_node().cloned();
+        if let Some(node) = &node {
+            *inner.running.entry(node.clone()).or_default() += 1;
+        }
+        let slot = QueueSlot {
+            queue: self.clone(),
+            node,
+        };
+        Some((request, slot))
+    }
+}
+
+/// A switchover being processed, which counts towards the concurrency limit of its node.
+pub(crate) struct QueueSlot {
+    queue: SwitchOverQueue,
+    node: Option<NodeId>,
+}
+
+impl Drop for QueueSlot {
+    fn drop(&mut self) {
+        let Some(node) = &self.node else {
+            return;
+        };
+        let mut inner = self.queue.inner.lock();
+        if let Some(running) = inner.running.get_mut(node) {
+            *running -= 1;
+            if *running == 0 {
+                inner.running.remove(node);
+            }
+        }
+        drop(inner);
+        self.queue.notify.notify_waiters();
+    }
+}
+
+#[test]
+fn switchover_backoff() {
+    let backoff = Backoff::new(
+        Duration::from_secs(10),
+        2,
+        Duration::from_secs(30),
+        vec!["republish-volume=5s".parse().unwrap()],
+    );
+    assert_eq!(
+        backoff.delay(Stage::ReplacePath, 0),
+        Duration::from_secs(10)
+    );
+    assert_eq!(
+        backoff.delay(Stage::RepublishVolume, 1),
+        Duration::from_secs(5)
+    );
+    assert_eq!(
+        backoff.delay(Stage::RepublishVolume, 2),
+        Duration::from_secs(30)
+    );
+    assert!("republish=5s".parse::<StageBackoff>().is_err());
+    assert!("tier".parse::<VolumeLabel>().is_err());
+}
+
+#[tokio::test]
+async fn switchover_queue() {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use stor_port::types::v0::transport::VolumeId;
+    let sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
+    let request = |volume: &str, priority: u32, node: &str| {
+        let volume = VolumeId::try_from(volume).unwrap();
+        let mut request = SwitchOverRequest::new(sock, volume, "nn".into(), "nw".to_string());
+        request.set_class(SwitchOverClass {
+            priority,
+            node: Some(node.into()),
+        });
+        request
+    };
+
+    let backoff = Backoff::new(Duration::ZERO, 0, Duration::ZERO, vec![]);
+    let policy = SwitchOverPolicy::new(4, Some(1), vec![], backoff);
+    let queue = SwitchOverQueue::new(&policy);
+    let low = request("ec4e66fd-3b33-4439-b504-d49aba53da26", 0, "node-1");
+    let high = request("a9d7ddc4-8a31-4b87-9b20-4b4c5a6a3a31", 2, "node-1");
+    let other = request("0ba9c4e6-bc5c-4e4c-8e0e-6c7ea1c2ab3c", 1, "node-2");
+    queue.push(low.clone());
+    queue.push(high.clone());
+    queue.push(other.clone());
+
+    let (first, slot) = queue.pop().await;
+    assert_eq!(first, high);
+    // node-1 is at its limit, so the lower priority request of node-2 goes first
+    let (second, _other_slot) = queue.pop().await;
+    assert_eq!(second, other);
+    assert!(queue.try_pop().is_none());
+
+    drop(slot);
+    let (third, _slot) = queue.pop().await;
+    assert_eq!(third, low);
+}
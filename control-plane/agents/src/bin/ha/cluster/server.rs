@@ -1,38 +1,41 @@
-use crate::{nodes::NodeList, volume::VolumeMover};
+use crate::{leader::Leadership, nodes::NodeList};
 use grpc::{
     context::Context,
     operations::ha_node::{
         server::ClusterAgentServer,
-        traits::{ClusterAgentOperations, NodeInfo, ReportFailedPathsInfo},
+        traits::{
+            ClusterAgentOperations, GetSwitchOversInfo, MoveVolumeTargetInfo, NodeInfo,
+            ReportFailedPathsInfo,
+        },
     },
 };
 use std::{net::SocketAddr, sync::Arc};
 use stor_port::{
     transport_api::{ReplyError, ResourceKind},
-    types::v0::transport::FailedPathsResponse,
+    types::v0::transport::{FailedPathsResponse, SwitchOverInfo, SwitchOvers},
 };
 
 /// High-level object that represents HA Cluster agent gRPC server.
 pub(crate) struct ClusterAgent {
     endpoint: SocketAddr,
     nodes: NodeList,
-    mover: VolumeMover,
+    leadership: Leadership,
 }
 
 impl ClusterAgent {
     /// Returns a new `Self` with the given parameters.
-    pub(crate) fn new(endpoint: SocketAddr, nodes: NodeList, mover: VolumeMover) -> Self {
+    pub(crate) fn new(endpoint: SocketAddr, nodes: NodeList, leadership: Leadership) -> Self {
         ClusterAgent {
             endpoint,
             nodes,
-            mover,
+            leadership,
         }
     }
     /// Runs this server as a future until a shutdown signal is received.
     pub(crate) async fn run(self) -> Result<(), agents::ServiceError> {
         let r = ClusterAgentServer::new(Arc::new(ClusterAgentSvc {
             nodes: self.nodes,
-            mover: self.mover,
+            leadership: self.leadership,
         }));
         agents::Service::builder()
             .with_service(r.into_grpc_server())
@@ -43,7 +46,7 @@ impl ClusterAgent {
 
 struct ClusterAgentSvc {
     nodes: NodeList,
-    mover: VolumeMover,
+    leadership: Leadership,
 }
 
 #[tonic::async_trait]
@@ -52,7 +55,7 @@ impl ClusterAgentOperations for ClusterAgentSvc {
     async fn register(
         &self,
         request: &dyn NodeInfo,
-        _context: Option<Context>,
+        context: Option<Context>,
     ) -> Result<(), ReplyError> {
         if request.node().is_empty() {
             return Err(ReplyError::missing_argument(
@@ -64,6 +67,16 @@ impl ClusterAgentOperations for ClusterAgentSvc {
             .register_node(request.node().into(), request.endpoint())
             .await;
         tracing::trace!(agent = request.node(), "node successfully registered");
+        if self.leadership.mover().is_none() {
+            // the leader needs the node agent endpoints to carry out the planned switchovers
+            let forwarded = match self.leadership.leader().await {
+                Ok(leader) => leader.register(request, context).await,
+                Err(error) => Err(error),
+            };
+            if let Err(error) = forwarded {
+                tracing::warn!(%error, agent = request.node(), "Failed to forward the node registration to the leader");
+            }
+        }
         Ok(())
     }
 
@@ -71,8 +84,12 @@ impl ClusterAgentOperations for ClusterAgentSvc {
     async fn report_failed_nvme_paths(
         &self,
         request: &dyn ReportFailedPathsInfo,
-        _context: Option<Context>,
+        context: Option<Context>,
     ) -> Result<FailedPathsResponse, ReplyError> {
+        let Some(mover) = self.leadership.mover() else {
+            let leader = self.leadership.leader().await?;
+            return leader.report_failed_nvme_paths(request, context).await;
+        };
         let mut report = FailedPathsResponse::default();
 
         for path in request.failed_paths().into_iter() {
@@ -81,7 +98,7 @@ impl ClusterAgentOperations for ClusterAgentSvc {
                 .report_failed_path(
                     request.node().into(),
                     path.target_nqn().to_string(),
-                    self.mover.clone(),
+                    mover.clone(),
                     request.endpoint(),
                 )
                 .await
@@ -102,4 +119,38 @@ impl ClusterAgentOperations for ClusterAgentSvc {
 
         Ok(report)
     }
+
+    #[tracing::instrument(level = "debug", skip(self), err)]
+    async fn get_switchovers(
+        &self,
+        request: &dyn GetSwitchOversInfo,
+        context: Option<Context>,
+    ) -> Result<SwitchOvers, ReplyError> {
+        match self.leadership.mover() {
+            Some(mover) => Ok(mover.history().list()),
+            None => {
+                let leader = self.leadership.leader().await?;
+                leader.get_switchovers(request, context).await
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.volume()))]
+    async fn move_volume_target(
+        &self,
+        request: &dyn MoveVolumeTargetInfo,
+        context: Option<Context>,
+    ) -> Result<SwitchOverInfo, ReplyError> {
+        match self.leadership.mover() {
+            Some(mover) => {
+                mover
+                    .move_target(&self.nodes, request.volume(), request.target_node())
+                    .await
+            }
+            None => {
+                let leader = self.leadership.leader().await?;
+                leader.move_volume_target(request, context).await
+            }
+        }
+    }
 }
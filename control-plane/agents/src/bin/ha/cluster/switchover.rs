@@ -1,4 +1,10 @@
-use crate::{core_grpc, etcd::EtcdStore, nodes::NodeList};
+use crate::{
+    core_grpc,
+    etcd::EtcdStore,
+    history::SwitchOverHistory,
+    nodes::NodeList,
+    policy::{QueueSlot, SwitchOverClass, SwitchOverPolicy, SwitchOverQueue},
+};
 use agents::eventing::Event;
 use anyhow::anyhow;
 use chrono::Utc;
@@ -10,7 +16,7 @@ use grpc::operations::{
     volume::traits::VolumeOperations,
 };
 use serde::{Deserialize, Serialize};
-use std::{cmp::Ordering, collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
+use std::{cmp::Ordering, collections::HashMap, net::SocketAddr, sync::Arc};
 use stor_port::{
     transport_api::{ReplyError, ReplyErrorKind},
     types::v0::{
@@ -19,15 +25,11 @@ use stor_port::{
             SpecTransaction,
         },
         transport::{
-            DestroyShutdownTargets, GetController, NodeId, NvmeSubsystem, ReplacePath,
-            RepublishVolume, Volume, VolumeId, VolumeShareProtocol,
+            self, DestroyShutdownTargets, Filter, GetController, NodeId, NvmeSubsystem,
+            ReplacePath, RepublishVolume, SwitchOverInfo, Volume, VolumeId, VolumeShareProtocol,
         },
     },
 };
-use tokio::sync::{
-    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
-    Mutex,
-};
 use tonic::transport::Uri;
 use tracing::{error, info, warn};
 
@@ -38,7 +40,7 @@ fn client() -> impl VolumeOperations {
 const REPLACE_PATH_DEADLINE_RETRIES: u64 = 3;
 
 /// Stage represents the steps for switchover request.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Copy, Clone, Ord, PartialOrd)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Copy, Clone, Ord, PartialOrd, Hash)]
 pub(crate) enum Stage {
     /// Initialize switchover request.
     Init,
@@ -97,6 +99,12 @@ pub(crate) struct SwitchOverRequest {
     publish_context: Option<HashMap<String, String>>,
     /// The first time we handle exhaustion, retry right away.
     fast_exhaustion_retry: bool,
+    /// The switchover was requested, rather than triggered by a failed path.
+    planned: bool,
+    /// The node on which to create the new target, otherwise picked by the scheduler.
+    target_node: Option<NodeId>,
+    /// The priority and throttling node, once classified.
+    class: Option<SwitchOverClass>,
 }
 
 impl Ord for SwitchOverRequest {
@@ -134,9 +142,21 @@ impl SwitchOverRequest {
             reuse_existing: true,
             publish_context: None,
             fast_exhaustion_retry: true,
+            planned: false,
+            target_node: None,
+            class: None,
         }
     }
 
+    /// Turn this into a planned switchover, which moves the target onto the given node, or onto
+    /// one picked by the scheduler, even though the existing target is still healthy.
+    pub(crate) fn with_target_node(mut self, target_node: Option<NodeId>) -> Self {
+        self.planned = true;
+        self.reuse_existing = false;
+        self.target_node = target_node;
+        self
+    }
+
     /// Get a ref-counted switchover stage.
     pub(crate) fn stage_arc(&self) -> SwitchOverStage {
         self.stage.clone()
@@ -147,6 +167,24 @@ impl SwitchOverRequest {
         self.reuse_existing = reuse_existing;
     }
 
+    /// Set the priority and throttling node.
+    pub(crate) fn set_class(&mut self, class: SwitchOverClass) {
+        self.class = Some(class);
+    }
+
+    /// Get the priority, the lowest if not classified.
+    pub(crate) fn priority(&self) -> u32 {
+        self.class
+            .as_ref()
+            .map(|class| class.priority)
+            .unwrap_or_default()
+    }
+
+    /// Get the node whose switchovers are throttled together with this one, if classified.
+    pub(crate) fn throttle_node(&self) -> Option<&NodeId> {
+        self.class.as_ref().and_then(|class| class.node.as_ref())
+    }
+
     /// Get the node agent socket address.
     pub(crate) fn socket(&self) -> SocketAddr {
         self.callback_uri
@@ -156,6 +194,26 @@ impl SwitchOverRequest {
         &self.existing_nqn
     }
 
+    /// Get the current status of the switchover.
+    pub(crate) fn info(&self) -> SwitchOverInfo {
+        SwitchOverInfo {
+            volume: self.volume_id.clone(),
+            node: self.node_name.clone(),
+            planned: self.planned,
+            target_node: self.target_node.clone(),
+            priority: self.priority(),
+            stage: self.stage().into(),
+            retry_count: self.retry_count,
+            existing_nqn: self.existing_nqn.clone(),
+            new_path: self.new_path.clone(),
+            reuse_existing: self.reuse_existing,
+            last_error: None,
+            start_time: self.timestamp,
+            last_update: Utc::now(),
+            end_time: None,
+        }
+    }
+
     /// Update stage with next stage.
     /// If a stage is PublishPath or Errored then it will not be updated.
     pub(crate) fn update_next_stage(&mut self) {
@@ -246,7 +304,7 @@ impl SwitchOverRequest {
     ) -> Result<Volume, ReplyError> {
         let republish_req = RepublishVolume {
             uuid: self.volume_id.clone(),
-            target_node: None,
+            target_node: self.target_node.clone(),
             share: VolumeShareProtocol::Nvmf,
             reuse_existing: self.reuse_existing,
             frontend_node: self.node_name.clone(),
@@ -301,6 +359,13 @@ impl SwitchOverRequest {
                 self.set_stage(Stage::Errored);
                 Err(error)
             }
+            Err(error) if error.kind == ReplyErrorKind::InvalidArgument => {
+                // Non-ANA targets, eg: iSCSI, cannot be switched over as the initiator is not able
+                // to move onto a new path, instead it keeps on reconnecting to the existing one.
+                warn!(volume.uuid=%self.volume_id, %error, "Volume target does not support switchover, cancelling");
+                self.set_stage(Stage::Errored);
+                Err(error)
+            }
             Err(error) if error.kind == ReplyErrorKind::ResourceExhausted => {
                 self.destroy_shutdown().await;
                 self.send_republish_volume(true).await
@@ -466,77 +531,69 @@ impl SwitchOverRequest {
 #[derive(Debug, Clone)]
 pub(crate) struct SwitchOverEngine {
     etcd: EtcdStore,
-    fast_requeue: Option<humantime::Duration>,
+    policy: SwitchOverPolicy,
     nodes: NodeList,
-    channel: UnboundedSender<SwitchOverRequest>,
-}
-
-/// Number of Switchover worker tasks to be spawned.
-const WORKER_NUM: u8 = 4;
-
-enum ReQueue {
-    /// Sleep duration during Fast requeue phase.
-    Fast = 10,
-    /// Sleep duration during Slow requeue phase.
-    Slow = 30,
-    /// Number of Fast request requeue per stage.
-    NumFast = 18,
+    history: SwitchOverHistory,
+    queue: SwitchOverQueue,
 }
 
 impl SwitchOverEngine {
     /// Creates a new switchover engine to process Nvme path failures.
     pub(crate) fn new(
         etcd: EtcdStore,
-        fast_requeue: Option<humantime::Duration>,
+        policy: SwitchOverPolicy,
         nodes: NodeList,
+        history: SwitchOverHistory,
     ) -> Self {
-        let (rq_tx, rq_rx) = unbounded_channel();
-
         let sw = SwitchOverEngine {
-            channel: rq_tx,
+            queue: SwitchOverQueue::new(&policy),
             etcd,
             nodes,
-            fast_requeue,
+            history,
+            policy,
         };
 
-        sw.init_worker(Arc::new(Mutex::new(rq_rx)));
+        sw.init_worker();
         sw
     }
     pub(crate) fn nodes(&self) -> &NodeList {
         &self.nodes
     }
+    /// Get the history of the switchovers.
+    pub(crate) fn history(&self) -> &SwitchOverHistory {
+        &self.history
+    }
 
-    /// Instantiates worker task to asynchronously process Switchover request.
-    pub(crate) fn init_worker(&self, recv: Arc<Mutex<UnboundedReceiver<SwitchOverRequest>>>) {
-        for i in 0 .. WORKER_NUM {
+    /// Instantiates worker tasks to asynchronously process Switchover request.
+    /// The number of workers bounds the number of switchovers running across the cluster.
+    pub(crate) fn init_worker(&self) {
+        for i in 0 .. self.policy.max_concurrent() {
             info!(worker = i, "Spawning Switchover Engine worker");
             let cloned_self = self.clone();
-            let cloned_channel = recv.clone();
-            tokio::spawn(async move { cloned_self.worker(cloned_channel, i).await });
+            tokio::spawn(async move { cloned_self.worker(i).await });
         }
     }
 
     /// Switchover request to be handled synchronously in each worker task.
-    async fn worker(self, recv: Arc<Mutex<UnboundedReceiver<SwitchOverRequest>>>, worker_num: u8) {
+    async fn worker(self, worker_num: usize) {
         loop {
-            let request = match recv.lock().await.recv().await {
-                None => break,
-                Some(request) => request,
-            };
+            let (request, slot) = self.queue.pop().await;
 
             info!(
                 volume.uuid = %request.volume_id,
                 worker = worker_num,
+                priority = request.priority(),
                 "Volume switchover picked up by worker"
             );
-            self.work_request(request).await;
+            self.work_request(request, slot).await;
         }
-        info!(worker = worker_num, "Switchover Engine worker stopped");
     }
 
     /// Handle Switchover request synchronously as long as it's succeeding on each stage.
     /// Failed requests are sent back to the work queue to be picked up later.
-    async fn work_request(&self, mut request: SwitchOverRequest) {
+    /// The request counts towards the concurrency limit of its node until the given slot is
+    /// released, ie: until it completes or is sent back to the work queue.
+    async fn work_request(&self, mut request: SwitchOverRequest, slot: QueueSlot) {
         loop {
             let result = match request.stage() {
                 Stage::Init => {
@@ -551,6 +608,7 @@ impl SwitchOverEngine {
                     match request.errored_switchover(&self.etcd, &self.nodes).await {
                         Ok(_) => {
                             event.generate();
+                            self.history.complete(&request);
                             break;
                         }
                         Err(e) => Err(e),
@@ -559,6 +617,7 @@ impl SwitchOverEngine {
                 Stage::Successful => match request.delete_switchover(&self.etcd).await {
                     Ok(_) => {
                         request.event(EventAction::SwitchOver).generate();
+                        self.history.complete(&request);
                         break;
                     }
                     Err(e) => Err(e),
@@ -568,6 +627,7 @@ impl SwitchOverEngine {
                 Ok(_) => {
                     // reset retry count back to the start after successfully completing a stage.
                     request.retry_count = 0;
+                    self.history.update(&request, None);
                 }
                 Err(error) => {
                     info!(
@@ -576,6 +636,8 @@ impl SwitchOverEngine {
                         "Sending failed Switchover request back to the work queue"
                     );
                     request.retry_count += 1;
+                    self.history.update(&request, Some(error.to_string()));
+                    drop(slot);
                     self.enqueue(request);
                     break;
                 }
@@ -583,24 +645,33 @@ impl SwitchOverEngine {
         }
     }
 
-    /// Sends Switchover request to the channel after sleeping for sometime (if necessary).
-    pub(crate) fn enqueue(&self, req: SwitchOverRequest) {
-        let tx_clone = self.channel.clone();
-        let fast_requeue = self.fast_requeue;
+    /// Sends Switchover request to the work queue after sleeping for sometime (if necessary).
+    /// Requests which are not classified yet are classified from their volume beforehand.
+    pub(crate) fn enqueue(&self, mut req: SwitchOverRequest) {
+        self.history.update(&req, None);
+        let queue = self.queue.clone();
+        let policy = self.policy.clone();
         tokio::spawn(async move {
-            let errored_request = req.retry_count > 0;
-            let retry_delay = if req.retry_count < ReQueue::NumFast as u64 {
-                match fast_requeue {
-                    None => ReQueue::Fast as u64,
-                    Some(duration) => duration.as_secs(),
+            if req.retry_count > 0 {
+                let retry_delay = policy.backoff().delay(req.stage(), req.retry_count);
+                tokio::time::sleep(retry_delay).await;
+            }
+            if req.class.is_none() {
+                match client()
+                    .get(Filter::Volume(req.volume_id.clone()), false, None, None)
+                    .await
+                {
+                    Ok(volumes) => {
+                        if let Some(volume) = volumes.entries.first() {
+                            req.set_class(policy.classify(volume));
+                        }
+                    }
+                    Err(error) => {
+                        warn!(volume.uuid=%req.volume_id, %error, "Failed to classify the switchover, using the lowest priority");
+                    }
                 }
-            } else {
-                ReQueue::Slow as u64
-            };
-            if errored_request {
-                tokio::time::sleep(Duration::from_secs(retry_delay)).await;
             }
-            tx_clone.send(req)
+            queue.push(req)
         });
     }
 }
@@ -619,6 +690,8 @@ impl From<&SwitchOverRequest> for SwitchOverSpec {
             retry_count: req.retry_count,
             reuse_existing: req.reuse_existing,
             publish_context: req.publish_context.clone(),
+            planned: req.planned,
+            target_node: req.target_node.clone(),
         }
     }
 }
@@ -642,6 +715,9 @@ impl From<&SwitchOverSpec> for SwitchOverRequest {
             reuse_existing: req.reuse_existing,
             publish_context: req.publish_context.clone(),
             fast_exhaustion_retry: true,
+            planned: req.planned,
+            target_node: req.target_node.clone(),
+            class: None,
         }
     }
 }
@@ -659,6 +735,19 @@ impl From<Stage> for Operation {
     }
 }
 
+impl From<Stage> for transport::SwitchOverStage {
+    fn from(stage: Stage) -> Self {
+        match stage {
+            Stage::Init => Self::Init,
+            Stage::RepublishVolume => Self::RepublishVolume,
+            Stage::ReplacePath => Self::ReplacePath,
+            Stage::DeleteTarget => Self::DeleteTarget,
+            Stage::Successful => Self::Successful,
+            Stage::Errored => Self::Errored,
+        }
+    }
+}
+
 impl From<Operation> for Stage {
     fn from(op: Operation) -> Self {
         match op {
@@ -732,3 +821,29 @@ fn switchover_ord() {
     test_vec.sort();
     assert_eq!(test_vec, vec![sw1, sw4, sw5, sw3, sw2]);
 }
+
+#[test]
+fn planned_switchover() {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    let sock: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
+    let vol = VolumeId::try_from("ec4e66fd-3b33-4439-b504-d49aba53da26").unwrap();
+    let sw = SwitchOverRequest::new(sock, vol, "nn".into(), "nw".to_string())
+        .with_target_node(Some("io-engine-2".into()));
+    assert!(sw.planned);
+    assert!(!sw.reuse_existing);
+
+    let spec = SwitchOverSpec::from(&sw);
+    assert!(spec.planned);
+    assert_eq!(spec.target_node, Some("io-engine-2".into()));
+    let reloaded = SwitchOverRequest::from(&spec);
+    assert_eq!(reloaded, sw);
+
+    // switchovers persisted before planned switchovers existed are reactive
+    let mut value = serde_json::to_value(&spec).unwrap();
+    let object = value.as_object_mut().unwrap();
+    object.remove("planned");
+    object.remove("target_node");
+    let spec: SwitchOverSpec = serde_json::from_value(value).unwrap();
+    assert!(!spec.planned);
+    assert_eq!(spec.target_node, None);
+}
@@ -1,12 +1,16 @@
 use crate::{
+    core_grpc,
     etcd::EtcdStore,
+    history::SwitchOverHistory,
     nodes::NodeList,
+    policy::SwitchOverPolicy,
     switchover::{SwitchOverEngine, SwitchOverRequest, SwitchOverStage},
 };
+use grpc::operations::volume::traits::VolumeOperations;
 use std::{convert::TryFrom, net::SocketAddr};
 use stor_port::{
     transport_api::{ReplyError, ResourceKind},
-    types::v0::transport::{NodeId, VolumeId},
+    types::v0::transport::{Filter, NodeId, SwitchOverInfo, VolumeId, VolumeShareProtocol},
 };
 use utils::NVME_TARGET_NQN_PREFIX;
 
@@ -21,13 +25,19 @@ impl VolumeMover {
     /// Create a new `Self`.
     pub(crate) fn new(
         etcd: EtcdStore,
-        fast_requeue: Option<humantime::Duration>,
+        policy: SwitchOverPolicy,
         nodes: NodeList,
+        history: SwitchOverHistory,
     ) -> Self {
-        let engine = SwitchOverEngine::new(etcd.clone(), fast_requeue, nodes);
+        let engine = SwitchOverEngine::new(etcd.clone(), policy, nodes, history);
         Self { engine, etcd }
     }
 
+    /// Get the history of the switchovers.
+    pub(crate) fn history(&self) -> &SwitchOverHistory {
+        self.engine.history()
+    }
+
     /// Switchover build the switchover request for the given nqn and send it to SwitchOverEngine.
     #[tracing::instrument(level = "info", skip(self), err)]
     pub(crate) async fn switchover(
@@ -44,7 +54,71 @@ impl VolumeMover {
             ReplyError::invalid_argument(ResourceKind::Volume, "volume", error.to_string())
         })?;
 
-        let req = SwitchOverRequest::new(uri, volume_uuid, node, nqn);
+        self.start(SwitchOverRequest::new(uri, volume_uuid, node, nqn))
+            .await
+    }
+
+    /// Move the target of the given volume onto the given node, or onto one picked by the
+    /// scheduler, by switching over its frontend node onto the new target.
+    #[tracing::instrument(level = "info", skip(self, nodes), err)]
+    pub(crate) async fn move_target(
+        &self,
+        nodes: &NodeList,
+        volume: VolumeId,
+        target_node: Option<NodeId>,
+    ) -> Result<SwitchOverInfo, ReplyError> {
+        let volume = core_grpc()
+            .volume()
+            .get(Filter::Volume(volume.clone()), false, None, None)
+            .await?
+            .entries
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                ReplyError::not_found(ResourceKind::Volume, volume.to_string(), "".into())
+            })?;
+        let spec = volume.spec();
+        let Some(config) = spec.active_config() else {
+            return Err(ReplyError::failed_precondition(
+                ResourceKind::Volume,
+                spec.uuid.to_string(),
+                "The volume is not published".into(),
+            ));
+        };
+        if matches!(config.target().protocol(), Some(VolumeShareProtocol::Iscsi)) {
+            // iSCSI initiators cannot move onto a new path.
+            return Err(ReplyError::invalid_argument(
+                ResourceKind::Volume,
+                "protocol",
+                "Volume target does not support switchover",
+            ));
+        }
+        if target_node.as_ref() == Some(config.target().node()) {
+            return Err(ReplyError::invalid_argument(
+                ResourceKind::Volume,
+                "target_node",
+                "The volume target is already on the node",
+            ));
+        }
+        let [frontend_node]: [String; 1] =
+            config.frontend().node_names().try_into().map_err(|_| {
+                ReplyError::failed_precondition(
+                    ResourceKind::Volume,
+                    spec.uuid.to_string(),
+                    "The volume target must have a single frontend node to be moved".into(),
+                )
+            })?;
+
+        nodes
+            .move_target(frontend_node.into(), spec.uuid.clone(), target_node, self)
+            .await
+    }
+
+    /// Persist the given switchover request and send it to SwitchOverEngine.
+    pub(crate) async fn start(
+        &self,
+        req: SwitchOverRequest,
+    ) -> Result<SwitchOverStage, ReplyError> {
         let stage_arc = req.stage_arc();
 
         // calling start_op here to store the request in etcd
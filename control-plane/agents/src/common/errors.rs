@@ -43,6 +43,12 @@ pub enum SvcError {
     CordonLabel { node_id: String, label: String },
     #[snafu(display("Node {node_id} does not have a cordon label '{label}'"))]
     UncordonLabel { node_id: String, label: String },
+    #[snafu(display("Pool {pool_id} is already cordoned with label '{label}'"))]
+    PoolCordonLabel { pool_id: String, label: String },
+    #[snafu(display("Pool {pool_id} does not have a cordon label '{label}'"))]
+    PoolUncordonLabel { pool_id: String, label: String },
+    #[snafu(display("Pool {pool_id} is already being evacuated with label '{label}'"))]
+    PoolEvacuationInProgress { pool_id: String, label: String },
     #[snafu(display(
         "Timed out after '{:?}' attempting to connect to node '{}' via gRPC endpoint '{}'",
         timeout,
@@ -137,6 +143,8 @@ pub enum SvcError {
     VolumeNotPublished { vol_id: String },
     #[snafu(display("Node '{}' not allowed to access target for volume '{}'", node, vol_id))]
     FrontendNodeNotAllowed { node: String, vol_id: String },
+    #[snafu(display("App node '{}' has not registered a valid iSCSI initiator name", node))]
+    InvalidIscsiInitiator { node: String },
     #[snafu(display("{} {} cannot be shared over invalid protocol '{}'", kind.to_string(), id, share))]
     InvalidShareProtocol {
         kind: ResourceKind,
@@ -165,6 +173,23 @@ pub enum SvcError {
         requested_size: u64,
         current_size: u64,
     },
+    #[snafu(display(
+        "Volume '{}' can't be shrunk to '{}': {}",
+        vol_id,
+        requested_size,
+        reason
+    ))]
+    VolumeShrink {
+        vol_id: String,
+        requested_size: u64,
+        reason: String,
+    },
+    #[snafu(display("Invalid QoS limits for volume '{}': {}", vol_id, reason))]
+    InvalidVolumeQos { vol_id: String, reason: String },
+    #[snafu(display("Invalid encryption for volume '{}': {}", vol_id, reason))]
+    InvalidVolumeEncryption { vol_id: String, reason: String },
+    #[snafu(display("Failed to resolve the encryption key '{}': {}", key, reason))]
+    EncryptionKeyResolve { key: String, reason: String },
     #[snafu(display("Replica '{}' not found", replica_id))]
     ReplicaNotFound { replica_id: ReplicaId },
     #[snafu(display("{} '{}' is already shared over {}", kind.to_string(), id, share))]
@@ -257,6 +282,24 @@ pub enum SvcError {
         volume_id: String,
         volume_state: String,
     },
+    #[snafu(display(
+        "Volume '{}' already has a replica move in progress, for replica '{}'",
+        volume,
+        replica
+    ))]
+    ReplicaMoveInProgress { volume: String, replica: String },
+    #[snafu(display(
+        "Volume '{}' has no replica move in progress for replica '{}'",
+        volume,
+        replica
+    ))]
+    ReplicaMoveNotFound { volume: String, replica: String },
+    #[snafu(display(
+        "Cannot move replica '{}' of volume '{}': the volume must be published, as the new replica is rebuilt by the volume target",
+        replica,
+        volume
+    ))]
+    ReplicaMoveNotPublished { volume: String, replica: String },
     #[snafu(display("Could not get rebuild history for nexus'{}'", nexus_id))]
     RebuildHistoryNotFound { nexus_id: String },
     #[snafu(display("No suitable replica removal candidates found for Volume '{}'", id))]
@@ -367,6 +410,10 @@ pub enum SvcError {
     ClonedSnapshotVolumeSize {},
     #[snafu(display("Cloned snapshot volume only supported for 1 replica"))]
     ClonedSnapshotVolumeRepl {},
+    #[snafu(display("Cloned volumes must be thin provisioned"))]
+    ClonedVolumeThin {},
+    #[snafu(display("Cloned volume must match the source volume size"))]
+    ClonedVolumeSize {},
     #[snafu(display("The source snapshot is not created"))]
     SnapshotNotCreated {},
     #[snafu(display("Draining is not allowed without HA"))]
@@ -402,6 +449,41 @@ pub enum SvcError {
         replicas: u8,
         id: String,
     },
+    #[snafu(display(
+        "Volume '{vol_id}' cannot be restored to snapshot '{snap_id}' as it has since been resized"
+    ))]
+    SnapshotRestoreSize { snap_id: String, vol_id: String },
+    #[snafu(display("Snapshot group '{group_id}' does not target any volume"))]
+    SnapshotGroupNoVolumes { group_id: String },
+    #[snafu(display(
+        "Snapshot export '{export_id}' cannot be based on export '{base_id}': {reason}"
+    ))]
+    SnapshotExportBase {
+        export_id: String,
+        base_id: String,
+        reason: String,
+    },
+    #[snafu(display(
+        "Snapshot export '{export_id}' is the base of snapshot export '{dependent_id}'"
+    ))]
+    SnapshotExportInUse {
+        export_id: String,
+        dependent_id: String,
+    },
+    #[snafu(display("Snapshot export '{export_id}' has not been exported"))]
+    SnapshotExportNotExported { export_id: String },
+    #[snafu(display("Invalid backup path '{path}' for snapshot export '{export_id}': {reason}"))]
+    SnapshotExportPath {
+        export_id: String,
+        path: String,
+        reason: String,
+    },
+    #[snafu(display("Failed to transfer snapshot export '{export_id}': {details}"))]
+    SnapshotExportTransfer { export_id: String, details: String },
+    #[snafu(display("Invalid specs backup: {details}"))]
+    InvalidSpecsBackup { details: String },
+    #[snafu(display("Cannot restore the specs into a non-empty store: {details}"))]
+    SpecsRestoreStoreNotEmpty { details: String },
     #[snafu(display("Cannot set '{attribute}' on Replica '{replica}: {source}'"))]
     ReplicaSetPropertyFailed {
         attribute: String,
@@ -617,6 +699,27 @@ impl From<SvcError> for ReplyError {
                 extra,
             },
 
+            SvcError::PoolCordonLabel { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Pool,
+                source,
+                extra,
+            },
+
+            SvcError::PoolUncordonLabel { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Pool,
+                source,
+                extra,
+            },
+
+            SvcError::PoolEvacuationInProgress { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Pool,
+                source,
+                extra,
+            },
+
             SvcError::GrpcConnectTimeout { .. } => ReplyError {
                 kind: ReplyErrorKind::Timeout,
                 resource: ResourceKind::Node,
@@ -752,6 +855,12 @@ impl From<SvcError> for ReplyError {
                 source,
                 extra,
             },
+            SvcError::InvalidIscsiInitiator { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::AppNode,
+                source,
+                extra,
+            },
             SvcError::WatchResourceNotFound { kind } => ReplyError {
                 kind: ReplyErrorKind::NotFound,
                 resource: kind,
@@ -824,6 +933,24 @@ impl From<SvcError> for ReplyError {
                 source,
                 extra,
             },
+            SvcError::ReplicaMoveInProgress { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Volume,
+                source,
+                extra,
+            },
+            SvcError::ReplicaMoveNotFound { .. } => ReplyError {
+                kind: ReplyErrorKind::NotFound,
+                resource: ResourceKind::Replica,
+                source,
+                extra,
+            },
+            SvcError::ReplicaMoveNotPublished { .. } => ReplyError {
+                kind: ReplyErrorKind::NotPublished,
+                resource: ResourceKind::Volume,
+                source,
+                extra,
+            },
             SvcError::RebuildHistoryNotFound { .. } => ReplyError {
                 kind: ReplyErrorKind::NotFound,
                 resource: ResourceKind::Nexus,
@@ -933,6 +1060,30 @@ impl From<SvcError> for ReplyError {
                 source,
                 extra,
             },
+            SvcError::VolumeShrink { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Volume,
+                source,
+                extra,
+            },
+            SvcError::InvalidVolumeQos { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Volume,
+                source,
+                extra,
+            },
+            SvcError::InvalidVolumeEncryption { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Volume,
+                source,
+                extra,
+            },
+            SvcError::EncryptionKeyResolve { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Volume,
+                source,
+                extra,
+            },
             SvcError::Unimplemented { resource, .. } => ReplyError {
                 kind: ReplyErrorKind::Unimplemented,
                 resource,
@@ -1035,6 +1186,18 @@ impl From<SvcError> for ReplyError {
                 source,
                 extra,
             },
+            SvcError::ClonedVolumeThin {} => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Volume,
+                source,
+                extra,
+            },
+            SvcError::ClonedVolumeSize {} => ReplyError {
+                kind: ReplyErrorKind::OutOfRange,
+                resource: ResourceKind::Volume,
+                source,
+                extra,
+            },
             SvcError::SnapshotNotCreated {} => ReplyError {
                 kind: ReplyErrorKind::InvalidArgument,
                 resource: ResourceKind::VolumeSnapshot,
@@ -1083,6 +1246,60 @@ impl From<SvcError> for ReplyError {
                 source,
                 extra,
             },
+            SvcError::SnapshotRestoreSize { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::VolumeSnapshot,
+                source,
+                extra,
+            },
+            SvcError::SnapshotGroupNoVolumes { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::SnapshotGroup,
+                source,
+                extra,
+            },
+            SvcError::SnapshotExportBase { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::SnapshotExport,
+                source,
+                extra,
+            },
+            SvcError::SnapshotExportInUse { .. } => ReplyError {
+                kind: ReplyErrorKind::InUse,
+                resource: ResourceKind::SnapshotExport,
+                source,
+                extra,
+            },
+            SvcError::SnapshotExportNotExported { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::SnapshotExport,
+                source,
+                extra,
+            },
+            SvcError::SnapshotExportPath { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::SnapshotExport,
+                source,
+                extra,
+            },
+            SvcError::SnapshotExportTransfer { .. } => ReplyError {
+                kind: ReplyErrorKind::Internal,
+                resource: ResourceKind::SnapshotExport,
+                source,
+                extra,
+            },
+            SvcError::InvalidSpecsBackup { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Spec,
+                source,
+                extra,
+            },
+            SvcError::SpecsRestoreStoreNotEmpty { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Spec,
+                source,
+                extra,
+            },
             SvcError::ReplicaSetPropertyFailed { .. } => ReplyError {
                 kind: ReplyErrorKind::FailedPersist,
                 resource: ResourceKind::Replica,
@@ -4,9 +4,9 @@ use stor_port::types::v0::openapi::{
     clients::tower::StatusCode,
     models,
     models::{
-        AffinityGroup, AppNode, CreateVolumeBody, Node, NodeTopology, Pool, PoolTopology,
-        PublishVolumeBody, ResizeVolumeBody, RestJsonError, Topology, Volume, VolumePolicy,
-        VolumeShareProtocol, Volumes,
+        AffinityGroup, AppNode, CreateVolumeBody, EncryptionKeyRef, Node, NodeTopology,
+        PoolTopology, PublishVolumeBody, ResizeVolumeBody, RestJsonError, Topology, Volume,
+        VolumeCapacity, VolumeCapacityBody, VolumePolicy, VolumeQos, VolumeShareProtocol, Volumes,
     },
 };
 
@@ -178,9 +178,28 @@ impl RestApiClient {
         }
     }
 
-    /// List all pools available in IoEngine cluster.
-    pub(crate) async fn list_pools(&self) -> Result<Vec<Pool>, ApiClientError> {
-        let response = self.rest_client.pools_api().get_pools().await?;
+    /// Get the capacity available to new volumes created with the given parameters.
+    #[instrument(skip(self))]
+    pub(crate) async fn get_volume_capacity(
+        &self,
+        replicas: u8,
+        thin: bool,
+        volume_topology: CreateVolumeTopology,
+    ) -> Result<VolumeCapacity, ApiClientError> {
+        let topology =
+            Topology::new_all(volume_topology.node_topology, volume_topology.pool_topology);
+
+        let req = VolumeCapacityBody {
+            replicas,
+            thin,
+            topology: Some(topology),
+        };
+
+        let response = self
+            .rest_client
+            .volumes_api()
+            .put_volume_capacity(req)
+            .await?;
         Ok(response.into_body())
     }
 
@@ -209,12 +228,6 @@ impl RestApiClient {
         Ok(response.into_body())
     }
 
-    /// List pools available on target IoEngine node.
-    pub(crate) async fn get_node_pools(&self, node: &str) -> Result<Vec<Pool>, ApiClientError> {
-        let pools = self.rest_client.pools_api().get_node_pools(node).await?;
-        Ok(pools.into_body())
-    }
-
     /// Create a volume of target size and provision storage resources for it.
     /// This operation is not idempotent, so the caller is responsible for taking
     /// all actions with regards to idempotency.
@@ -229,6 +242,8 @@ impl RestApiClient {
         thin: bool,
         affinity_group: Option<AffinityGroup>,
         max_snapshots: Option<u32>,
+        qos: Option<VolumeQos>,
+        encryption: Option<EncryptionKeyRef>,
     ) -> Result<Volume, ApiClientError> {
         let topology =
             Topology::new_all(volume_topology.node_topology, volume_topology.pool_topology);
@@ -242,6 +257,8 @@ impl RestApiClient {
             labels: None,
             affinity_group,
             max_snapshots,
+            qos,
+            encryption,
         };
 
         let result = self
@@ -267,6 +284,8 @@ impl RestApiClient {
         thin: bool,
         affinity_group: Option<AffinityGroup>,
         max_snapshots: Option<u32>,
+        qos: Option<VolumeQos>,
+        encryption: Option<EncryptionKeyRef>,
     ) -> Result<Volume, ApiClientError> {
         let topology =
             Topology::new_all(volume_topology.node_topology, volume_topology.pool_topology);
@@ -280,6 +299,8 @@ impl RestApiClient {
             labels: None,
             affinity_group,
             max_snapshots,
+            qos,
+            encryption,
         };
         let result = self
             .rest_client
@@ -289,6 +310,47 @@ impl RestApiClient {
         Ok(result.into_body())
     }
 
+    /// Create a volume as a clone of the source volume and provision storage resources for it.
+    /// This operation is not idempotent, so the caller is responsible for taking
+    /// all actions with regards to idempotency.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(fields(volume.uuid = %volume_id, volume.source_uuid = %source_id), skip(self, volume_id, source_id))]
+    pub(crate) async fn clone_volume(
+        &self,
+        volume_id: &uuid::Uuid,
+        source_id: &uuid::Uuid,
+        replicas: u8,
+        size: u64,
+        volume_topology: CreateVolumeTopology,
+        thin: bool,
+        affinity_group: Option<AffinityGroup>,
+        max_snapshots: Option<u32>,
+        qos: Option<VolumeQos>,
+        encryption: Option<EncryptionKeyRef>,
+    ) -> Result<Volume, ApiClientError> {
+        let topology =
+            Topology::new_all(volume_topology.node_topology, volume_topology.pool_topology);
+
+        let req = CreateVolumeBody {
+            replicas,
+            size,
+            thin,
+            topology: Some(topology),
+            policy: VolumePolicy::new_all(true),
+            labels: None,
+            affinity_group,
+            max_snapshots,
+            qos,
+            encryption,
+        };
+        let result = self
+            .rest_client
+            .volumes_api()
+            .put_volume_clone(source_id, volume_id, req)
+            .await?;
+        Ok(result.into_body())
+    }
+
     /// Delete volume and reclaim all storage resources associated with it.
     /// This operation is idempotent, so the caller does not see errors indicating
     /// absence of the resource.
@@ -360,16 +422,19 @@ impl RestApiClient {
     }
 
     /// Unpublish volume (i.e. destroy a target which exposes the volume).
+    /// If the frontend node is specified and the target is read-only, only the frontend node is
+    /// removed from the target, unless it's the last one.
     #[instrument(fields(volume.uuid = %volume_id), skip(self, volume_id))]
     pub(crate) async fn unpublish_volume(
         &self,
         volume_id: &uuid::Uuid,
         force: bool,
+        frontend_node: Option<&str>,
     ) -> Result<(), ApiClientError> {
         Self::delete_idempotent(
             self.rest_client
                 .volumes_api()
-                .del_volume_target(volume_id, Some(force))
+                .del_volume_target(volume_id, Some(force), frontend_node)
                 .await,
             true,
         )?;
@@ -378,6 +443,7 @@ impl RestApiClient {
     }
 
     /// Publish volume (i.e. make it accessible via specified protocol by creating a target).
+    /// A read-only volume which is already published is made accessible to the frontend node.
     #[instrument(fields(volume.uuid = %volume_id), skip(self, volume_id))]
     pub(crate) async fn publish_volume(
         &self,
@@ -386,6 +452,7 @@ impl RestApiClient {
         protocol: VolumeShareProtocol,
         frontend_node: String,
         publish_context: &HashMap<String, String>,
+        read_only: bool,
     ) -> Result<Volume, ApiClientError> {
         let publish_volume_body = PublishVolumeBody::new_all(
             publish_context.clone(),
@@ -394,6 +461,7 @@ impl RestApiClient {
             protocol,
             None,
             frontend_node,
+            read_only,
         );
         let volume = self
             .rest_client
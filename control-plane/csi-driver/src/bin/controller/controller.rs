@@ -4,14 +4,15 @@ use crate::{
 };
 use csi_driver::{
     context::{CreateParams, CreateSnapshotParams, PublishParams, QuiesceFsCandidate},
+    filesystem::FileSystem,
     node::internal::{node_plugin_client::NodePluginClient, FreezeFsRequest, UnfreezeFsRequest},
 };
 use rpc::csi::{volume_content_source::Type, Topology as CsiTopology, *};
 use stor_port::types::v0::openapi::{
     models,
     models::{
-        AffinityGroup, LabelledTopology, NodeSpec, NodeStatus, Pool, PoolStatus, PoolTopology,
-        SpecStatus, Volume, VolumeShareProtocol,
+        AffinityGroup, LabelledTopology, NodeSpec, NodeStatus, PoolTopology, SpecStatus, Volume,
+        VolumeShareProtocol,
     },
 };
 use utils::{CREATED_BY_KEY, DSP_OPERATOR};
@@ -23,7 +24,6 @@ use tracing::{debug, error, instrument, trace, warn};
 use uuid::Uuid;
 use volume_capability::AccessType;
 
-const OPENEBS_TOPOLOGY_KEY: &str = "openebs.io/nodename";
 const VOLUME_NAME_PATTERN: &str =
     r"pvc-([0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})";
 const SNAPSHOT_NAME_PATTERN: &str =
@@ -53,12 +53,19 @@ impl CsiControllerSvc {
     }
 }
 
-/// Check whether target volume capabilities are valid. As of now, only
-/// SingleNodeWriter capability is supported.
+/// Check whether the access mode is supported. As of now, only the SingleNodeWriter and
+/// the read-only access modes are supported.
+fn supported_access_mode(mode: i32) -> bool {
+    mode == volume_capability::access_mode::Mode::SingleNodeWriter as i32
+        || mode == volume_capability::access_mode::Mode::SingleNodeReaderOnly as i32
+        || mode == volume_capability::access_mode::Mode::MultiNodeReaderOnly as i32
+}
+
+/// Check whether target volume capabilities are valid.
 fn check_volume_capabilities(capabilities: &[VolumeCapability]) -> Result<(), tonic::Status> {
     for c in capabilities {
         if let Some(access_mode) = c.access_mode.as_ref() {
-            if access_mode.mode != volume_capability::access_mode::Mode::SingleNodeWriter as i32 {
+            if !supported_access_mode(access_mode.mode) {
                 return Err(Status::invalid_argument(format!(
                     "Invalid volume access mode: {:?}",
                     access_mode.mode
@@ -69,10 +76,23 @@ fn check_volume_capabilities(capabilities: &[VolumeCapability]) -> Result<(), to
     Ok(())
 }
 
+/// Check whether the volume capability only allows for reading.
+fn read_only_capability(capability: &VolumeCapability) -> bool {
+    capability
+        .access_mode
+        .as_ref()
+        .map_or(false, |access_mode| {
+            access_mode.mode == volume_capability::access_mode::Mode::SingleNodeReaderOnly as i32
+                || access_mode.mode
+                    == volume_capability::access_mode::Mode::MultiNodeReaderOnly as i32
+        })
+}
+
 /// Parse string protocol into REST API protocol enum.
 fn parse_protocol(proto: Option<&String>) -> Result<VolumeShareProtocol, Status> {
     match proto.map(|s| s.as_str()) {
         None | Some("nvmf") => Ok(VolumeShareProtocol::Nvmf),
+        Some("iscsi") => Ok(VolumeShareProtocol::Iscsi),
         _ => Err(Status::invalid_argument(format!(
             "Invalid protocol: {proto:?}"
         ))),
@@ -161,6 +181,59 @@ fn frontend_nodes_allowed<'a>(
     }
 }
 
+/// Get the nodes where the volume is published for the application.
+fn volume_published_nodes(volume: &Volume) -> Vec<String> {
+    match volume
+        .spec
+        .target
+        .as_ref()
+        .and_then(|t| t.frontend_nodes.as_ref())
+    {
+        Some(nodes) => nodes.iter().map(|node| node.name.clone()).collect(),
+        None => vec![],
+    }
+}
+
+/// Get the health condition of the volume, from its status and its replica topology.
+/// A volume is only healthy when it's online and none of its replicas is degraded or
+/// being rebuilt.
+fn volume_condition(volume: &Volume) -> VolumeCondition {
+    let status = &volume.state.status;
+    let mut issues = volume
+        .state
+        .replica_topology
+        .iter()
+        .filter_map(|(uuid, topology)| {
+            if let Some(progress) = topology.rebuild_progress {
+                return Some(format!("replica {uuid} is rebuilding ({progress}%)"));
+            }
+            match (&topology.state, &topology.child_status) {
+                (models::ReplicaState::Online, None | Some(models::ChildState::Online)) => None,
+                (models::ReplicaState::Online, Some(child)) => {
+                    Some(format!("replica {uuid} is {child} in the volume target"))
+                }
+                (state, _) => Some(format!("replica {uuid} is {state}")),
+            }
+        })
+        .collect::<Vec<_>>();
+    issues.sort();
+
+    if status == &models::VolumeStatus::Online && issues.is_empty() {
+        return VolumeCondition {
+            abnormal: false,
+            message: "Volume is healthy".to_string(),
+        };
+    }
+    let mut message = format!("Volume is {status}");
+    if !issues.is_empty() {
+        message = format!("{message}: {}", issues.join(", "));
+    }
+    VolumeCondition {
+        abnormal: true,
+        message,
+    }
+}
+
 impl From<ApiClientError> for Status {
     fn from(error: ApiClientError) -> Self {
         match error {
@@ -231,6 +304,14 @@ fn check_existing_volume(
     Ok(())
 }
 
+/// The content source of a volume which is being created.
+enum ContentSource {
+    /// Restore the given snapshot.
+    Snapshot(Uuid),
+    /// Clone the given volume.
+    Volume(Uuid),
+}
+
 struct VolumeTopologyMapper {}
 
 impl VolumeTopologyMapper {
@@ -268,12 +349,16 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                                 snapshot_source.snapshot_id
                             ))
                         })?;
-                    Some(snapshot_uuid)
+                    Some(ContentSource::Snapshot(snapshot_uuid))
                 }
-                Some(Type::Volume(_)) => {
-                    return Err(Status::invalid_argument(
-                        "Volume creation from volume source is not supported",
-                    ));
+                Some(Type::Volume(volume_source)) => {
+                    let source_uuid = Uuid::parse_str(&volume_source.volume_id).map_err(|_e| {
+                        Status::invalid_argument(format!(
+                            "Malformed source volume UUID: {}",
+                            volume_source.volume_id
+                        ))
+                    })?;
+                    Some(ContentSource::Volume(source_uuid))
                 }
                 _ => {
                     return Err(Status::invalid_argument(
@@ -321,6 +406,15 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         let context = CreateParams::try_from(&args.parameters)?;
         let replica_count = context.replica_count();
 
+        // The filesystem options must be known to the filesystems of the volume capabilities.
+        for capability in &args.volume_capabilities {
+            if let Some(AccessType::Mount(mount)) = &capability.access_type {
+                if let Ok(fs_type) = FileSystem::from_str(&mount.fs_type) {
+                    context.publish_params().validate_fs_options(&fs_type)?;
+                }
+            }
+        }
+
         let parsed_vol_uuid = Uuid::parse_str(&volume_uuid).map_err(|_e| {
             Status::invalid_argument(format!("Malformed volume UUID: {volume_uuid}"))
         })?;
@@ -353,9 +447,11 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
 
                 let sts_affinity_group_name = context.sts_affinity_group();
                 let max_snapshots = context.max_snapshots();
+                let qos = context.qos();
+                let encryption = context.encryption();
 
                 let volume = match volume_content_source {
-                    Some(snapshot_uuid) => {
+                    Some(ContentSource::Snapshot(snapshot_uuid)) => {
                         RestApiClient::get_client()
                             .create_snapshot_volume(
                                 &parsed_vol_uuid,
@@ -366,6 +462,24 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                                 thin,
                                 sts_affinity_group_name.clone().map(AffinityGroup::new),
                                 max_snapshots,
+                                qos.clone(),
+                                encryption.clone(),
+                            )
+                            .await?
+                    }
+                    Some(ContentSource::Volume(source_uuid)) => {
+                        RestApiClient::get_client()
+                            .clone_volume(
+                                &parsed_vol_uuid,
+                                &source_uuid,
+                                replica_count,
+                                size,
+                                volume_topology,
+                                thin,
+                                sts_affinity_group_name.clone().map(AffinityGroup::new),
+                                max_snapshots,
+                                qos.clone(),
+                                encryption.clone(),
                             )
                             .await?
                     }
@@ -379,6 +493,8 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                                 thin,
                                 sts_affinity_group_name.clone().map(AffinityGroup::new),
                                 max_snapshots,
+                                qos.clone(),
+                                encryption.clone(),
                             )
                             .await?
                     }
@@ -448,11 +564,6 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
     ) -> Result<tonic::Response<ControllerPublishVolumeResponse>, tonic::Status> {
         let args = request.into_inner();
         tracing::trace!(volume.uuid = %args.volume_id, request = ?args);
-        if args.readonly {
-            return Err(Status::invalid_argument(
-                "Read-only volumes are not supported",
-            ));
-        }
 
         let protocol = parse_protocol(args.volume_context.get("protocol"))?;
 
@@ -469,12 +580,15 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         })?;
         let _guard = csi_driver::limiter::VolumeOpGuard::new(volume_id)?;
 
-        match args.volume_capability {
-            Some(c) => check_volume_capabilities(&[c])?,
+        let read_only = match &args.volume_capability {
+            Some(c) => {
+                check_volume_capabilities(std::slice::from_ref(c))?;
+                args.readonly || read_only_capability(c)
+            }
             None => {
                 return Err(Status::invalid_argument("Missing volume capability"));
             }
-        }
+        };
 
         // Check if the volume is already published.
         let volume = RestApiClient::get_client().get_volume(&volume_id).await?;
@@ -497,7 +611,34 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                         return Err(Status::failed_precondition(m));
                     }
 
-                    if let Some((node, uri)) = get_volume_share_location(&volume) {
+                    if target.read_only.unwrap_or_default() != read_only {
+                        let m = format!(
+                            "Volume {volume_id} is already published with read-only set to {}",
+                            !read_only
+                        );
+                        error!("{}", m);
+                        return Err(Status::failed_precondition(m));
+                    }
+
+                    if read_only {
+                        // A read-only target may be shared by multiple app nodes.
+                        let v = RestApiClient::get_client()
+                            .publish_volume(&volume_id, None, protocol, node_id.clone(), &publish_context, true)
+                            .await?;
+                        match get_volume_share_location(&v) {
+                            Some((node, uri)) => {
+                                debug!("Read-only volume {volume_id} published for {node_id} on {node} => {uri}");
+                                uri
+                            }
+                            None => {
+                                let m = format!(
+                                    "Volume {volume_id} reports no info about its publishing status"
+                                );
+                                error!("{}", m);
+                                return Err(Status::internal(m));
+                            }
+                        }
+                    } else if let Some((node, uri)) = get_volume_share_location(&volume) {
                         // Make sure volume is accessible from the same app node.
                         if let Err(allowed) = frontend_nodes_allowed(target, &node_id) {
                             let m = format!(
@@ -545,7 +686,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
 
                     // Volume is not published.
                     let v = RestApiClient::get_client()
-                        .publish_volume(&volume_id, target_node, protocol, args.node_id.clone(), &publish_context)
+                        .publish_volume(&volume_id, target_node, protocol, args.node_id.clone(), &publish_context, read_only)
                         .await?;
 
                     if let Some((node, uri)) = get_volume_share_location(&v) {
@@ -608,7 +749,11 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
 
         // Do forced volume upublish as Kubernetes already detached the volume.
         RestApiClient::get_client()
-            .unpublish_volume(&volume_uuid, true)
+            .unpublish_volume(
+                &volume_uuid,
+                true,
+                (!args.node_id.is_empty()).then_some(args.node_id.as_str()),
+            )
             .await
             .map_err(|e| {
                 Status::not_found(format!(
@@ -644,9 +789,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
             .into_iter()
             .filter(|cap| {
                 if let Some(access_mode) = cap.access_mode.as_ref() {
-                    if access_mode.mode
-                        == volume_capability::access_mode::Mode::SingleNodeWriter as i32
-                    {
+                    if supported_access_mode(access_mode.mode) {
                         return true;
                     }
                 }
@@ -666,7 +809,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         } else {
             ValidateVolumeCapabilitiesResponse {
                 confirmed: None,
-                message: "The only supported capability is SINGLE_NODE_WRITER".to_string(),
+                message: "The only supported capabilities are SINGLE_NODE_WRITER, SINGLE_NODE_READER_ONLY and MULTI_NODE_READER_ONLY".to_string(),
             }
         };
 
@@ -731,50 +874,50 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         // Check capabilities.
         check_volume_capabilities(&args.volume_capabilities)?;
 
-        // Determine target node, if requested.
-        let node: Option<&String> = if let Some(topology) = args.accessible_topology.as_ref() {
-            topology.segments.get(OPENEBS_TOPOLOGY_KEY)
-        } else {
-            None
-        };
+        // Volumes are only accessible from the nodes which match the node selector, and so no
+        // volume can be provisioned for a topology segment outside of it.
+        if let Some(topology) = args.accessible_topology.as_ref() {
+            let accessible = CsiControllerConfig::get_config()
+                .node_selector_segment()
+                .iter()
+                .all(|(key, value)| topology.segments.get(key).map_or(true, |v| v == value));
+            if !accessible {
+                debug!(
+                    "Topology segment {:?} is not accessible, no capacity available",
+                    topology.segments
+                );
+                return Ok(Response::new(GetCapacityResponse {
+                    available_capacity: 0,
+                    maximum_volume_size: Some(0),
+                    minimum_volume_size: None,
+                }));
+            }
+        }
 
-        let pools: Vec<Pool> = if let Some(node) = node {
-            debug!("Calculating pool capacity for node {}", node);
-            RestApiClient::get_client()
-                .get_node_pools(node)
-                .await
-                .map_err(|e| {
-                    Status::internal(format!(
-                        "Failed to list pools for node {node}, error = {e:?}",
-                    ))
-                })?
-        } else {
-            debug!("Calculating overall pool capacity");
-            RestApiClient::get_client()
-                .list_pools()
-                .await
-                .map_err(|e| {
-                    Status::internal(format!("Failed to list all pools, error = {e:?}",))
-                })?
+        let context = CreateParams::try_from(&args.parameters)?;
+        let thin = match args.parameters.get("thin") {
+            Some(value) => value == "true",
+            None => false,
         };
 
-        let available_capacity: i64 = pools.into_iter().fold(0, |acc, p| match p.state {
-            Some(state) => match state.status {
-                PoolStatus::Online | PoolStatus::Degraded => acc + state.capacity as i64,
-                _ => {
-                    warn!(
-                        "Pool {} on node {} is in '{:?}' state, not accounting it for capacity",
-                        p.id, state.node, state.status,
-                    );
-                    acc
-                }
-            },
-            None => 0,
-        });
+        let capacity = RestApiClient::get_client()
+            .get_volume_capacity(
+                context.replica_count(),
+                thin,
+                context_into_topology(&context),
+            )
+            .await
+            .map_err(|e| {
+                Status::internal(format!("Failed to get the volume capacity, error = {e:?}"))
+            })?;
+        debug!(
+            "Available capacity is {} and the maximum volume size is {}",
+            capacity.available_capacity, capacity.maximum_volume_size
+        );
 
         Ok(Response::new(GetCapacityResponse {
-            available_capacity,
-            maximum_volume_size: None,
+            available_capacity: capacity.available_capacity as i64,
+            maximum_volume_size: Some(capacity.maximum_volume_size as i64),
             minimum_volume_size: None,
         }))
     }
@@ -792,6 +935,9 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
             controller_service_capability::rpc::Type::CreateDeleteSnapshot,
             controller_service_capability::rpc::Type::ListSnapshots,
             controller_service_capability::rpc::Type::ExpandVolume,
+            controller_service_capability::rpc::Type::CloneVolume,
+            controller_service_capability::rpc::Type::GetVolume,
+            controller_service_capability::rpc::Type::VolumeCondition,
         ];
 
         Ok(Response::new(ControllerGetCapabilitiesResponse {
@@ -1042,9 +1188,38 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
     #[instrument(err, skip(self))]
     async fn controller_get_volume(
         &self,
-        _request: tonic::Request<ControllerGetVolumeRequest>,
+        request: tonic::Request<ControllerGetVolumeRequest>,
     ) -> Result<tonic::Response<ControllerGetVolumeResponse>, tonic::Status> {
-        Err(Status::unimplemented("Not implemented"))
+        let args = request.into_inner();
+        tracing::trace!(volume.uuid = %args.volume_id, request = ?args);
+
+        let volume_uuid = Uuid::parse_str(&args.volume_id).map_err(|_e| {
+            Status::invalid_argument(format!("Malformed volume UUID: {}", args.volume_id))
+        })?;
+        let vt_mapper = VolumeTopologyMapper::init().await?;
+        let volume = RestApiClient::get_client().get_volume(&volume_uuid).await?;
+
+        let volume_condition = volume_condition(&volume);
+        if volume_condition.abnormal {
+            warn!(
+                volume.uuid = %volume_uuid,
+                "Volume is abnormal: {}", volume_condition.message
+            );
+        }
+
+        Ok(Response::new(ControllerGetVolumeResponse {
+            volume: Some(rpc::csi::Volume {
+                volume_id: volume.spec.uuid.to_string(),
+                capacity_bytes: volume.spec.size as i64,
+                volume_context: HashMap::new(),
+                content_source: None,
+                accessible_topology: vt_mapper.volume_accessible_topology(),
+            }),
+            status: Some(controller_get_volume_response::VolumeStatus {
+                published_node_ids: volume_published_nodes(&volume),
+                volume_condition: Some(volume_condition),
+            }),
+        }))
     }
 }
 
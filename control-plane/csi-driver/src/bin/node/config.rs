@@ -3,6 +3,7 @@ use csi_driver::Parameters;
 use heck::ToKebabCase;
 use std::{
     collections::HashMap,
+    path::PathBuf,
     sync::{Arc, Mutex, MutexGuard},
 };
 
@@ -27,6 +28,8 @@ pub fn nvme_io_tmo() -> String {
 #[derive(Debug, Default)]
 pub(crate) struct Config {
     nvme: NvmeConfig,
+    /// Base directory which the snapshot export backup directories must be within.
+    backup_dir: Option<PathBuf>,
 }
 impl Config {
     /// Get the `NvmeConfig` as mut.
@@ -37,6 +40,14 @@ impl Config {
     pub(crate) fn nvme(&self) -> &NvmeConfig {
         &self.nvme
     }
+    /// Set the base directory of the snapshot export backup directories.
+    pub(crate) fn set_backup_dir(&mut self, backup_dir: PathBuf) {
+        self.backup_dir = Some(backup_dir);
+    }
+    /// Get the base directory of the snapshot export backup directories, if configured.
+    pub(crate) fn backup_dir(&self) -> Option<&PathBuf> {
+        self.backup_dir.as_ref()
+    }
 }
 
 /// Nvme Configuration parameters.
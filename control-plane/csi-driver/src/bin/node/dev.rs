@@ -38,7 +38,7 @@ use udev::Enumerator;
 use url::Url;
 use uuid::Uuid;
 
-mod iscsi;
+pub(crate) mod iscsi;
 mod nbd;
 pub(crate) mod nvmf;
 mod util;
@@ -67,6 +67,11 @@ pub(crate) trait Detach: Sync + Send {
     async fn detach(&self) -> Result<(), DeviceError>;
     fn devname(&self) -> DeviceName;
     fn devnqn(&self) -> &str;
+    /// Check the health of the device paths, returning the reason why they're not healthy.
+    /// Devices which don't track the state of their paths are always healthy.
+    fn path_health(&self) -> Result<(), String> {
+        Ok(())
+    }
 }
 
 pub(crate) struct Device;
@@ -12,6 +12,20 @@ use super::{Attach, Detach, DeviceError, DeviceName};
 mod iscsiadm;
 use iscsiadm::IscsiAdmin;
 
+/// The file where the iSCSI initiator name of the node is configured.
+const INITIATOR_NAME_FILE: &str = "/etc/iscsi/initiatorname.iscsi";
+
+/// Get the iSCSI initiator name (iqn) of the node, if one is configured.
+pub(crate) fn initiator_name() -> Option<String> {
+    let contents = std::fs::read_to_string(INITIATOR_NAME_FILE).ok()?;
+    contents
+        .lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix("InitiatorName="))
+        .map(|iqn| iqn.trim().to_string())
+        .filter(|iqn| !iqn.is_empty())
+}
+
 pub(super) struct IscsiDevice {
     portal: String,
     iqn: String,
@@ -286,6 +286,31 @@ impl Detach for NvmfDetach {
     fn devnqn(&self) -> &str {
         &self.nqn
     }
+
+    /// Each nvme controller of the subsystem is a path to the volume target, which is only
+    /// usable while the controller is live.
+    fn path_health(&self) -> Result<(), String> {
+        let subsystems = Subsystem::try_from_nqn(&self.nqn)
+            .map_err(|error| format!("failed to get the nvme controllers: {error}"))?;
+        if subsystems.is_empty() {
+            return Err("no nvme controller is connected".to_string());
+        }
+        let unhealthy = subsystems
+            .iter()
+            .filter(|subsystem| subsystem.state != "live")
+            .map(|subsystem| {
+                format!(
+                    "controller {} to {} is {}",
+                    subsystem.name, subsystem.address, subsystem.state
+                )
+            })
+            .collect::<Vec<_>>();
+        if unhealthy.is_empty() {
+            Ok(())
+        } else {
+            Err(unhealthy.join(", "))
+        }
+    }
 }
 
 /// Check for the presence of nvme tcp kernel module.
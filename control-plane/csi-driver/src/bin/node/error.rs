@@ -187,3 +187,71 @@ impl From<FsfreezeError> for ExitCode {
         }
     }
 }
+
+/// Error for the snapshot export and import operations.
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)), context(suffix(false)))]
+pub(crate) enum SnapshotExportError {
+    #[snafu(display("Invalid chunk size: {chunk_size}"))]
+    InvalidChunkSize { chunk_size: u64 },
+    #[snafu(display("Invalid backup directory {path}: {reason}"))]
+    InvalidBackupDir { path: String, reason: String },
+    #[snafu(display(
+        "Snapshot exports are not enabled, as no backup base directory is configured"
+    ))]
+    BackupDirNotConfigured {},
+    #[snafu(display("Export {export_id} not found in {path}"))]
+    ExportNotFound { export_id: String, path: String },
+    #[snafu(display("Export {export_id} already exists in {path}"))]
+    ExportExists { export_id: String, path: String },
+    #[snafu(display("Export {export_id} is the base of export {dependent_id}"))]
+    ExportInUse {
+        export_id: String,
+        dependent_id: String,
+    },
+    #[snafu(display("Export {export_id} is not compatible with its base: {reason}"))]
+    IncompatibleBase { export_id: String, reason: String },
+    #[snafu(display("Chunk {digest} is missing or corrupted: {reason}"))]
+    CorruptedChunk { digest: String, reason: String },
+    #[snafu(display("Device of {size} bytes is too small for the export of {export_size} bytes"))]
+    DeviceTooSmall { size: u64, export_size: u64 },
+    #[snafu(display("Failed to access {path}: {source}"))]
+    Io {
+        source: std::io::Error,
+        path: String,
+    },
+    #[snafu(display("Failed to parse the manifest {path}: {source}"))]
+    Manifest {
+        source: serde_json::error::Error,
+        path: String,
+    },
+    #[snafu(display("Device failure: {source}"))]
+    Device { source: DeviceError },
+}
+
+impl From<SnapshotExportError> for tonic::Status {
+    fn from(error: SnapshotExportError) -> Self {
+        let message = error.to_string();
+        match error {
+            SnapshotExportError::InvalidChunkSize { .. }
+            | SnapshotExportError::InvalidBackupDir { .. } => {
+                tonic::Status::invalid_argument(message)
+            }
+            SnapshotExportError::BackupDirNotConfigured { .. } => {
+                tonic::Status::failed_precondition(message)
+            }
+            SnapshotExportError::ExportNotFound { .. } => tonic::Status::not_found(message),
+            SnapshotExportError::ExportExists { .. } => tonic::Status::already_exists(message),
+            SnapshotExportError::ExportInUse { .. }
+            | SnapshotExportError::IncompatibleBase { .. }
+            | SnapshotExportError::DeviceTooSmall { .. } => {
+                tonic::Status::failed_precondition(message)
+            }
+            SnapshotExportError::CorruptedChunk { .. } => tonic::Status::data_loss(message),
+            SnapshotExportError::Io { .. } | SnapshotExportError::Manifest { .. } => {
+                tonic::Status::internal(message)
+            }
+            SnapshotExportError::Device { source } => source.into(),
+        }
+    }
+}
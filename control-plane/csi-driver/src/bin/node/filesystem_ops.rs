@@ -20,12 +20,14 @@ const XFS_NO_UUID_FLAG: &str = "nouuid";
 /// Error type filesystem operations.
 type Error = String;
 
-/// Ext4 filesystem type.
-pub(crate) struct Ext4Fs;
+/// Ext4, Ext3 and Ext2 filesystem types, which share the e2fsprogs utilities.
+pub(crate) struct ExtFs(Fs);
 /// XFS filesystem type.
 pub(crate) struct XFs;
 /// BTRFS filesystem type.
 pub(crate) struct BtrFs;
+/// F2FS filesystem type.
+pub(crate) struct F2Fs;
 
 /// Filesystem type for csi node ops, wrapper over the parent Filesystem enum.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -48,7 +50,9 @@ impl TryFrom<&MountInfo> for FileSystem {
         Fs::from_str(mnt.fstype.to_lowercase().as_str())
             .map_err(|err| anyhow!("failed to parse FileSystem: {err}"))
             .and_then(|fs_type| match fs_type {
-                Fs::Ext4 | Fs::Xfs | Fs::Btrfs => Ok(FileSystem::from(fs_type)),
+                Fs::Ext4 | Fs::Ext3 | Fs::Ext2 | Fs::Xfs | Fs::Btrfs | Fs::F2fs => {
+                    Ok(FileSystem::from(fs_type))
+                }
                 _ => Err(anyhow!("Unsupported filesystem")),
             })
     }
@@ -70,16 +74,26 @@ impl FileSystem {
     /// Convert the top level enumeration to specific filesystem types to access the supported
     /// operations by them.
     pub(crate) fn fs_ops(&self) -> Result<&dyn FileSystemOps, Error> {
-        static EXT4FS: Ext4Fs = Ext4Fs {};
+        static EXT4FS: ExtFs = ExtFs(Fs::Ext4);
+        static EXT3FS: ExtFs = ExtFs(Fs::Ext3);
+        static EXT2FS: ExtFs = ExtFs(Fs::Ext2);
         static XFS: XFs = XFs {};
         static BRTFS: BtrFs = BtrFs {};
+        static F2FS: F2Fs = F2Fs {};
         match self.0 {
             Fs::Ext4 => Ok(&EXT4FS),
+            Fs::Ext3 => Ok(&EXT3FS),
+            Fs::Ext2 => Ok(&EXT2FS),
             Fs::Xfs => Ok(&XFS),
             Fs::Btrfs => Ok(&BRTFS),
+            Fs::F2fs => Ok(&F2FS),
             _ => Err(format!("Unsupported filesystem {self}")),
         }
     }
+    /// Get the top level filesystem enumeration.
+    pub(crate) fn fs(&self) -> &Fs {
+        &self.0
+    }
     /// Get a specific filesystem property by specifying the device path.
     pub(crate) fn property(device: &str, property: &str) -> Result<String, DevInfoError> {
         let probe = Probe::new_from_filename(device)?;
@@ -90,8 +104,8 @@ impl FileSystem {
 
 #[async_trait]
 pub(crate) trait FileSystemOps: Send + Sync {
-    /// Create the filesystem using its fs util.
-    async fn create(&self, device: &str) -> Result<(), Error>;
+    /// Create the filesystem using its fs util, with the given additional mkfs options.
+    async fn create(&self, device: &str, options: &[String]) -> Result<(), Error>;
     /// Get the default mount options along with the user passed options for specific filesystems.
     fn mount_flags(&self, mount_flags: Vec<String>) -> Vec<String>;
     /// Unmount the filesystem if the filesystem uuid and the provided uuid differ.
@@ -128,18 +142,58 @@ pub(crate) trait FileSystemOps: Send + Sync {
     }
     /// Write the existing filesystem on to new unused blocks on the block device.
     async fn expand(&self, mount_path: &str, dev_path: Option<String>) -> Result<(), Error>;
+    /// Check if the filesystem can be expanded while it's mounted.
+    fn online_expand(&self) -> bool {
+        true
+    }
+    /// Write the existing unmounted filesystem on to new unused blocks on the block device.
+    async fn offline_expand(&self, _device: &str) -> Result<(), Error> {
+        Err("offline expansion is not supported".to_string())
+    }
+}
+
+impl ExtFs {
+    /// Check and repair the filesystem using e2fsck.
+    async fn e2fsck(device: &str) -> Result<(), Error> {
+        let binary = "e2fsck".to_string();
+        let output = Command::new(&binary)
+            .arg("-y")
+            .arg("-f")
+            .arg(device)
+            .output()
+            .await
+            .map_err(|error| format!("failed to execute {binary}: {error}"))?;
+
+        trace!(
+            "Output from {} command: {}, status code: {:?}",
+            binary,
+            String::from_utf8(output.stdout.clone()).unwrap(),
+            output.status.code()
+        );
+
+        // For ext fs repair using e2fsck the success condition is when status code is 0 or 1.
+        if !output.status.success() && output.status.code() != Some(1) {
+            return Err(format!(
+                "{} command failed: {}",
+                binary,
+                String::from_utf8(output.stderr).unwrap()
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
-impl FileSystemOps for Ext4Fs {
-    async fn create(&self, device: &str) -> Result<(), Error> {
-        let binary = "mkfs.ext4";
-        let output = Command::new(binary)
+impl FileSystemOps for ExtFs {
+    async fn create(&self, device: &str, options: &[String]) -> Result<(), Error> {
+        let binary = format!("mkfs.{}", self.0);
+        let output = Command::new(&binary)
+            .args(options)
             .arg(device)
             .output()
             .await
             .map_err(|error| format!("failed to execute {binary}: {error}"))?;
-        ack_command_output(output, binary)
+        ack_command_output(output, &binary)
     }
 
     fn mount_flags(&self, mount_flags: Vec<String>) -> Vec<String> {
@@ -162,31 +216,7 @@ impl FileSystemOps for Ext4Fs {
         _options: &[String],
         _volume_uuid: &Uuid,
     ) -> Result<(), Error> {
-        let binary = "e2fsck".to_string();
-        let output = Command::new(&binary)
-            .arg("-y")
-            .arg("-f")
-            .arg(device)
-            .output()
-            .await
-            .map_err(|error| format!("failed to execute {binary}: {error}"))?;
-
-        trace!(
-            "Output from {} command: {}, status code: {:?}",
-            binary,
-            String::from_utf8(output.stdout.clone()).unwrap(),
-            output.status.code()
-        );
-
-        // For ext4 fs repair using e2fsck the success condition is when status code is 0 or 1.
-        if !output.status.success() && output.status.code() != Some(1) {
-            return Err(format!(
-                "{} command failed: {}",
-                binary,
-                String::from_utf8(output.stderr).unwrap()
-            ));
-        }
-        Ok(())
+        Self::e2fsck(device).await
     }
 
     async fn set_uuid(&self, device: &str, volume_uuid: &Uuid) -> Result<(), Error> {
@@ -240,11 +270,22 @@ impl FileSystemOps for Ext4Fs {
 
         run_fs_expand_command(vec!["resize2fs", dev_path.as_str()]).await
     }
+
+    /// Ext2 doesn't have the resize inode which is required for online expansion.
+    fn online_expand(&self) -> bool {
+        self.0 != Fs::Ext2
+    }
+
+    /// The filesystem must be checked before it can be expanded offline.
+    async fn offline_expand(&self, device: &str) -> Result<(), Error> {
+        Self::e2fsck(device).await?;
+        run_fs_expand_command(vec!["resize2fs", device]).await
+    }
 }
 
 #[async_trait]
 impl FileSystemOps for XFs {
-    async fn create(&self, device: &str) -> Result<(), Error> {
+    async fn create(&self, device: &str, options: &[String]) -> Result<(), Error> {
         let binary = "mkfs.xfs";
         let args = match std::env::var("MKFS_XFS_ARGS") {
             Ok(args) => args
@@ -255,6 +296,7 @@ impl FileSystemOps for XFs {
         };
         let output = Command::new(binary)
             .args(args)
+            .args(options)
             .arg(device)
             .output()
             .await
@@ -344,9 +386,10 @@ impl FileSystemOps for XFs {
 
 #[async_trait]
 impl FileSystemOps for BtrFs {
-    async fn create(&self, device: &str) -> Result<(), Error> {
+    async fn create(&self, device: &str, options: &[String]) -> Result<(), Error> {
         let binary = "mkfs.btrfs";
         let output = Command::new(binary)
+            .args(options)
             .arg(device)
             .output()
             .await
@@ -444,6 +487,77 @@ impl FileSystemOps for BtrFs {
     }
 }
 
+#[async_trait]
+impl FileSystemOps for F2Fs {
+    async fn create(&self, device: &str, options: &[String]) -> Result<(), Error> {
+        let binary = "mkfs.f2fs";
+        let output = Command::new(binary)
+            .args(options)
+            .arg(device)
+            .output()
+            .await
+            .map_err(|error| format!("failed to execute {binary}: {error}"))?;
+        ack_command_output(output, binary)
+    }
+
+    fn mount_flags(&self, mount_flags: Vec<String>) -> Vec<String> {
+        mount_flags
+    }
+
+    fn unmount_on_fs_id_diff(
+        &self,
+        _device_path: &str,
+        _fs_staging_path: &str,
+        _volume_uuid: &Uuid,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn repair(
+        &self,
+        device: &str,
+        _staging_path: &str,
+        _options: &[String],
+        _volume_uuid: &Uuid,
+    ) -> Result<(), Error> {
+        let binary = "fsck.f2fs";
+        let output = Command::new(binary)
+            .arg("-a")
+            .arg(device)
+            .output()
+            .await
+            .map_err(|error| format!("failed to execute {binary}: {error}"))?;
+        ack_command_output(output, binary)
+    }
+
+    /// The f2fs utilities can only set the filesystem uuid on creation.
+    async fn set_uuid(&self, device: &str, volume_uuid: &Uuid) -> Result<(), Error> {
+        let probe_uuid = FileSystem::property(device, "UUID")
+            .map_err(|error| format!("Failed to get UUID of device {device}: {error}"))?;
+
+        if volume_uuid.to_string() != probe_uuid {
+            return Err(format!(
+                "failed to set filesystem uuid to {volume_uuid} for {device}: changing the uuid of an existing f2fs filesystem is not supported"
+            ));
+        }
+        Ok(())
+    }
+
+    async fn expand(&self, mount_path: &str, _dev_path: Option<String>) -> Result<(), Error> {
+        Err(format!(
+            "f2fs mounted on {mount_path} cannot be expanded online"
+        ))
+    }
+
+    fn online_expand(&self) -> bool {
+        false
+    }
+
+    async fn offline_expand(&self, device: &str) -> Result<(), Error> {
+        run_fs_expand_command(vec!["resize.f2fs", device]).await
+    }
+}
+
 // Acknowledge the output from Command.
 fn ack_command_output(output: Output, binary: &str) -> Result<(), Error> {
     trace!(
@@ -477,7 +591,7 @@ pub(crate) async fn run_fs_expand_command(cmd_and_args: Vec<&str>) -> Result<(),
     };
 
     let cmd = cmd_and_args[0];
-    let args = cmd_and_args[1 ..].to_vec();
+    let args = cmd_and_args[1..].to_vec();
 
     let out = Command::new(cmd)
         .args(args.as_slice())
@@ -134,6 +134,23 @@ pub(crate) async fn stage_fs_volume(
         ));
     }
 
+    params.validate_fs_options(fstype.fs()).map_err(|error| {
+        failure!(
+            Code::InvalidArgument,
+            "Failed to stage volume {}: {}",
+            volume_uuid,
+            error.message()
+        )
+    })?;
+
+    // The storage class mount options are applied on top of the requested mount flags.
+    let mut mount_flags = mnt.mount_flags.clone();
+    for option in params.mount_options().iter().flatten() {
+        if !mount_flags.contains(option) {
+            mount_flags.push(option.clone());
+        }
+    }
+
     let mount_flags = fstype
         .fs_ops()
         .map_err(|error| {
@@ -145,10 +162,18 @@ pub(crate) async fn stage_fs_volume(
                 error
             )
         })?
-        .mount_flags(mnt.mount_flags.clone());
-
-    if let Err(error) =
-        prepare_device(fstype, device_path, fs_staging_path, &mount_flags, fs_id).await
+        .mount_flags(mount_flags);
+
+    let mkfs_options = params.mkfs_options().clone().unwrap_or_default();
+    if let Err(error) = prepare_device(
+        fstype,
+        device_path,
+        fs_staging_path,
+        &mount_flags,
+        &mkfs_options,
+        fs_id,
+    )
+    .await
     {
         return Err(failure!(
             Code::Internal,
@@ -5,11 +5,13 @@ use tracing::debug;
 use uuid::Uuid;
 
 /// Prepare the filesystem before mount, change parameters if requested.
+/// Filesystems which cannot be expanded online are expanded here, before being mounted.
 pub(crate) async fn prepare_device(
     fstype: &FileSystem,
     device: &str,
     staging_path: &str,
     options: &[String],
+    mkfs_options: &[String],
     fs_id: &Option<Uuid>,
 ) -> Result<(), String> {
     debug!("Probing device {}", device);
@@ -25,8 +27,12 @@ pub(crate) async fn prepare_device(
                 .set_uuid_with_repair(device, staging_path, options, fs_id)
                 .await?;
         }
+        if !fs_ops.online_expand() {
+            debug!("Expanding filesystem ({}) on device {} offline", fs, device);
+            fs_ops.offline_expand(device).await?;
+        }
         return Ok(());
     }
     debug!("Creating new filesystem ({}) on device {}", fstype, device);
-    fs_ops.create(device).await
+    fs_ops.create(device, mkfs_options).await
 }
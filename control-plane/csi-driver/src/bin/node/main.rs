@@ -43,6 +43,8 @@ mod registration;
 /// complete any existing ones before shutting down.
 #[cfg(target_os = "linux")]
 pub(crate) mod shutdown_event;
+#[cfg(target_os = "linux")]
+mod snapshot_export;
 
 #[tokio::main]
 #[cfg(target_os = "linux")]
@@ -189,6 +189,13 @@ pub(super) async fn main() -> anyhow::Result<()> {
                 )
             ,
         )
+        .arg(
+            Arg::new("snapshot-export-dir")
+                .long("snapshot-export-dir")
+                .value_name("PATH")
+                .required(false)
+                .help("Base directory of the snapshot export backup directories, which must be within it. Snapshot exports are refused if not set")
+        )
         .arg(
             Arg::new("fmt-style")
                 .long("fmt-style")
@@ -301,6 +308,10 @@ pub(super) async fn main() -> anyhow::Result<()> {
     let mut csi_labels = HashMap::new();
     let nvme_enabled = utils::check_nvme_core_ana().unwrap_or_default().to_string();
     csi_labels.insert(utils::CSI_NODE_NVME_ANA.to_string(), nvme_enabled.clone());
+    // The iSCSI initiator name is used for the host access control of iSCSI volume targets.
+    if let Some(iqn) = crate::dev::iscsi::initiator_name() {
+        csi_labels.insert(utils::CSI_NODE_ISCSI_IQN.to_string(), iqn);
+    }
 
     // If running in k8s, label the nodes with generated labels.
     let node_name = matches.get_one::<String>("node-name").unwrap();
@@ -338,6 +349,12 @@ pub(super) async fn main() -> anyhow::Result<()> {
     // Start the CSI server, node plugin grpc server and registration loop if registration is
     // enabled.
     *crate::config::config().nvme_as_mut() = TryFrom::try_from(&matches)?;
+    if let Some(backup_dir) = matches.get_one::<String>("snapshot-export-dir") {
+        let backup_dir = std::fs::canonicalize(backup_dir).map_err(|error| {
+            anyhow::format_err!("Failed to resolve 'snapshot-export-dir' {backup_dir}: {error}")
+        })?;
+        crate::config::config().set_backup_dir(backup_dir);
+    }
     let (csi, grpc, registration) = tokio::join!(
         CsiServer::run(csi_socket, &matches)?,
         NodePluginGrpcServer::run(grpc_sock_addr),
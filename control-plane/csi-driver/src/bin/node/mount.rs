@@ -90,7 +90,14 @@ pub(super) fn subset(first: &[String], second: &[String]) -> bool {
 
 /// Return supported filesystems.
 pub(crate) fn probe_filesystems() -> Vec<FileSystem> {
-    vec![Fs::Xfs.into(), Fs::Ext4.into(), Fs::Btrfs.into()]
+    vec![
+        Fs::Xfs.into(),
+        Fs::Ext4.into(),
+        Fs::Btrfs.into(),
+        Fs::Ext3.into(),
+        Fs::Ext2.into(),
+        Fs::F2fs.into(),
+    ]
 }
 
 // Utility function to transform a vector of options
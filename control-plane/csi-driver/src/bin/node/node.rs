@@ -26,7 +26,7 @@ use rpc::{
 use nix::{errno::Errno, sys};
 use std::{collections::HashMap, path::Path, time::Duration, vec::Vec};
 use tonic::{Code, Request, Response, Status};
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 use uuid::Uuid;
 
 macro_rules! failure {
@@ -175,6 +175,38 @@ async fn detach(uuid: &Uuid, errheader: String) -> Result<(), Status> {
     Ok(())
 }
 
+/// Get the health condition of the volume from the state of the paths of its device.
+/// The condition is unknown if the device cannot be looked up.
+async fn volume_condition(volume_id: &str) -> Option<csi::VolumeCondition> {
+    let uuid = Uuid::parse_str(volume_id).ok()?;
+    match Device::lookup(&uuid).await {
+        Ok(Some(device)) => Some(match device.path_health() {
+            Ok(()) => csi::VolumeCondition {
+                abnormal: false,
+                message: "Volume paths are healthy".to_string(),
+            },
+            Err(message) => {
+                warn!(
+                    volume.uuid = volume_id,
+                    "Volume paths are unhealthy: {message}"
+                );
+                csi::VolumeCondition {
+                    abnormal: true,
+                    message,
+                }
+            }
+        }),
+        Ok(None) => Some(csi::VolumeCondition {
+            abnormal: true,
+            message: "Volume device is not attached".to_string(),
+        }),
+        Err(error) => {
+            warn!(volume.uuid = volume_id, %error, "Failed to lookup the volume device");
+            None
+        }
+    }
+}
+
 #[tonic::async_trait]
 impl node_server::Node for Node {
     async fn node_get_info(
@@ -202,6 +234,7 @@ impl node_server::Node for Node {
             node_service_capability::rpc::Type::StageUnstageVolume,
             node_service_capability::rpc::Type::GetVolumeStats,
             node_service_capability::rpc::Type::ExpandVolume,
+            node_service_capability::rpc::Type::VolumeCondition,
         ];
 
         debug!("NodeGetCapabilities request: {:?}", caps);
@@ -393,6 +426,7 @@ impl node_server::Node for Node {
 
         let volume_path = Path::new(&msg.volume_path);
         if volume_path.exists() {
+            let volume_condition = volume_condition(&msg.volume_id).await;
             // Check if its a filesystem.
             if volume_path.is_dir() {
                 trace!("Getting statfs metrics for : {:?}", volume_path);
@@ -413,7 +447,7 @@ impl node_server::Node for Node {
                                 used: (info.files() - info.files_free()) as i64,
                             },
                         ],
-                        volume_condition: None,
+                        volume_condition,
                     })),
                     Err(err) => match err {
                         Errno::ENOENT => Err(Status::new(Code::NotFound, err.to_string())),
@@ -426,7 +460,7 @@ impl node_server::Node for Node {
             } else {
                 Ok(Response::new(NodeGetVolumeStatsResponse {
                     usage: vec![],
-                    volume_condition: None,
+                    volume_condition,
                 }))
             }
         } else {
@@ -463,6 +497,9 @@ impl node_server::Node for Node {
                             "ext4" => Self::SupportedFilesystem(FileSystem::from(Fs::Ext4)),
                             "xfs" => Self::SupportedFilesystem(FileSystem::from(Fs::Xfs)),
                             "btrfs" => Self::SupportedFilesystem(FileSystem::from(Fs::Btrfs)),
+                            "ext3" => Self::SupportedFilesystem(FileSystem::from(Fs::Ext3)),
+                            "ext2" => Self::SupportedFilesystem(FileSystem::from(Fs::Ext2)),
+                            "f2fs" => Self::SupportedFilesystem(FileSystem::from(Fs::F2fs)),
                             alien_fs => Self::UnsupportedFilesystem(format!(
                                 "'{alien_fs}' is not a supported filesystem"
                             )),
@@ -594,10 +631,21 @@ impl node_server::Node for Node {
             },
         };
 
-        // Expand the filesystem.
-        filesystem_handle
+        let fs_ops = filesystem_handle
             .fs_ops()
-            .map_err(|err| failure!(Code::InvalidArgument, "{}", err))?
+            .map_err(|err| failure!(Code::InvalidArgument, "{}", err))?;
+
+        // Filesystems which cannot be expanded online are expanded when they're next staged.
+        if !fs_ops.online_expand() {
+            warn!(
+                "Filesystem {filesystem_handle} of volume {vol_uuid} cannot be expanded online, \
+                it will be expanded when the volume is next staged",
+            );
+            return success_result;
+        }
+
+        // Expand the filesystem.
+        fs_ops
             .expand(&args.volume_path, Some(dev_path))
             .await
             .map_err(|err| failure!(Code::Internal, "{}", err))?;
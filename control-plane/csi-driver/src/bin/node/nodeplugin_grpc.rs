@@ -8,11 +8,13 @@ use crate::{
     nodeplugin_svc,
     nodeplugin_svc::{find_mount, lookup_device},
     shutdown_event::Shutdown,
+    snapshot_export,
 };
 use csi_driver::node::internal::{
     node_plugin_server::{NodePlugin, NodePluginServer},
-    FindVolumeReply, FindVolumeRequest, FreezeFsReply, FreezeFsRequest, UnfreezeFsReply,
-    UnfreezeFsRequest, VolumeType,
+    DeleteSnapshotExportReply, DeleteSnapshotExportRequest, ExportSnapshotReply,
+    ExportSnapshotRequest, FindVolumeReply, FindVolumeRequest, FreezeFsReply, FreezeFsRequest,
+    ImportSnapshotReply, ImportSnapshotRequest, UnfreezeFsReply, UnfreezeFsRequest, VolumeType,
 };
 use nodeplugin_svc::TypeOfMount;
 use tonic::{transport::Server, Request, Response, Status};
@@ -54,6 +56,66 @@ impl NodePlugin for NodePluginSvc {
             device_path: device.devname(),
         }))
     }
+
+    async fn export_snapshot(
+        &self,
+        request: Request<ExportSnapshotRequest>,
+    ) -> Result<Response<ExportSnapshotReply>, Status> {
+        let request = request.into_inner();
+        debug!(
+            "export_snapshot({}, {}, {})",
+            request.volume_id, request.path, request.export_id
+        );
+        let stats = snapshot_export::export_snapshot(
+            &request.volume_id,
+            &request.uri,
+            &request.publish_context,
+            &request.path,
+            &request.export_id,
+            request.base_export_id,
+            request.chunk_size,
+        )
+        .await?;
+        Ok(Response::new(ExportSnapshotReply {
+            size: stats.size,
+            chunks: stats.chunks,
+            changed_chunks: stats.changed_chunks,
+            written_bytes: stats.written_bytes,
+        }))
+    }
+
+    async fn import_snapshot(
+        &self,
+        request: Request<ImportSnapshotRequest>,
+    ) -> Result<Response<ImportSnapshotReply>, Status> {
+        let request = request.into_inner();
+        debug!(
+            "import_snapshot({}, {}, {})",
+            request.volume_id, request.path, request.export_id
+        );
+        snapshot_export::import_snapshot(
+            &request.volume_id,
+            &request.uri,
+            &request.publish_context,
+            &request.path,
+            &request.export_id,
+        )
+        .await?;
+        Ok(Response::new(ImportSnapshotReply {}))
+    }
+
+    async fn delete_snapshot_export(
+        &self,
+        request: Request<DeleteSnapshotExportRequest>,
+    ) -> Result<Response<DeleteSnapshotExportReply>, Status> {
+        let request = request.into_inner();
+        debug!(
+            "delete_snapshot_export({}, {})",
+            request.path, request.export_id
+        );
+        snapshot_export::delete_snapshot_export(&request.path, &request.export_id).await?;
+        Ok(Response::new(DeleteSnapshotExportReply {}))
+    }
 }
 
 impl From<TypeOfMount> for VolumeType {
@@ -13,8 +13,9 @@ mod tests {
     use csi_driver::node::internal::{
         node_plugin_client::NodePluginClient,
         node_plugin_server::{NodePlugin, NodePluginServer},
-        FindVolumeReply, FindVolumeRequest, FreezeFsReply, FreezeFsRequest, UnfreezeFsReply,
-        UnfreezeFsRequest, VolumeType,
+        DeleteSnapshotExportReply, DeleteSnapshotExportRequest, ExportSnapshotReply,
+        ExportSnapshotRequest, FindVolumeReply, FindVolumeRequest, FreezeFsReply, FreezeFsRequest,
+        ImportSnapshotReply, ImportSnapshotRequest, UnfreezeFsReply, UnfreezeFsRequest, VolumeType,
     };
     use std::{
         str::FromStr,
@@ -73,6 +74,27 @@ mod tests {
                 device_path: "".to_string(),
             }))
         }
+
+        async fn export_snapshot(
+            &self,
+            _request: Request<ExportSnapshotRequest>,
+        ) -> Result<Response<ExportSnapshotReply>, Status> {
+            unimplemented!()
+        }
+
+        async fn import_snapshot(
+            &self,
+            _request: Request<ImportSnapshotRequest>,
+        ) -> Result<Response<ImportSnapshotReply>, Status> {
+            unimplemented!()
+        }
+
+        async fn delete_snapshot_export(
+            &self,
+            _request: Request<DeleteSnapshotExportRequest>,
+        ) -> Result<Response<DeleteSnapshotExportReply>, Status> {
+            unimplemented!()
+        }
     }
 
     /// Tests the shutdown of a tonic service.
@@ -0,0 +1,665 @@
+//! Export of volume snapshots into a backup directory, and import of the exports back into a
+//! volume.
+//! The backup directory holds the content addressed chunks of all exports, along with a manifest
+//! per export:
+//!   <path>/chunks/<sha256> holds the data of a chunk which is not zeroed.
+//!   <path>/exports/<export_id>.json holds the manifest of an export.
+//! The manifest of an incremental export only lists the chunks which differ from its base chain,
+//! and so the contents of an export are given by merging the manifests from the root of its chain.
+//! The backup directories must be within the backup base directory configured on this node.
+use crate::{
+    dev::{sysfs_dev_size, Device},
+    error::{
+        BackupDirNotConfigured, CorruptedChunk, DeviceTooSmall, ExportExists, ExportInUse,
+        IncompatibleBase, InvalidBackupDir, InvalidChunkSize, Io, Manifest, SnapshotExportError,
+    },
+};
+use csi_driver::limiter::VolumeOpGuard;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use snafu::ResultExt;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tonic::Status;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+const ATTACH_TIMEOUT_INTERVAL: Duration = Duration::from_millis(100);
+const ATTACH_RETRIES: u32 = 100;
+
+/// Serializes the operations on each backup directory of this node, so a chunk which is being
+/// written by an export cannot be garbage collected by the deletion of another export.
+static BACKUP_DIR_LOCKS: Lazy<Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>> =
+    Lazy::new(Default::default);
+
+/// The chunks of an export, indexed by their position on the volume.
+/// A chunk without a digest is zeroed, and so its data is not stored.
+type ChunkMap = BTreeMap<u64, Option<String>>;
+
+/// The manifest of an export, stored in the backup directory.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ExportManifest {
+    /// The id of the export.
+    id: String,
+    /// The id of the export which this export is incremental to.
+    base: Option<String>,
+    /// The size of the exported volume in bytes.
+    size: u64,
+    /// The size of the chunks in bytes.
+    chunk_size: u64,
+    /// The chunks which differ from the base chain.
+    chunks: ChunkMap,
+}
+
+impl ExportManifest {
+    /// The number of chunks of the exported volume.
+    fn num_chunks(&self) -> u64 {
+        (self.size + self.chunk_size - 1) / self.chunk_size
+    }
+}
+
+/// The statistics of a completed export.
+#[derive(Debug, Default)]
+pub(crate) struct ExportStats {
+    /// The size of the exported volume in bytes.
+    pub(crate) size: u64,
+    /// The number of chunks of the exported volume.
+    pub(crate) chunks: u64,
+    /// The number of chunks which differ from the base export.
+    pub(crate) changed_chunks: u64,
+    /// The number of bytes written into the backup directory.
+    pub(crate) written_bytes: u64,
+}
+
+/// A backup directory, holding the exports of volume snapshots.
+struct BackupDir {
+    path: PathBuf,
+}
+
+impl BackupDir {
+    /// Get a new `Self` for the given resolved path, see `resolve_backup_dir`.
+    fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+    fn chunks_dir(&self) -> PathBuf {
+        self.path.join("chunks")
+    }
+    fn exports_dir(&self) -> PathBuf {
+        self.path.join("exports")
+    }
+    fn chunk_path(&self, digest: &str) -> PathBuf {
+        self.chunks_dir().join(digest)
+    }
+    fn manifest_path(&self, export_id: &str) -> PathBuf {
+        self.exports_dir().join(format!("{export_id}.json"))
+    }
+
+    /// Create the backup directory layout, if it does not exist yet.
+    fn create(&self) -> Result<(), SnapshotExportError> {
+        for dir in [self.chunks_dir(), self.exports_dir()] {
+            std::fs::create_dir_all(&dir).context(Io {
+                path: dir.display().to_string(),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Read the manifest of the given export.
+    fn manifest(&self, export_id: &str) -> Result<ExportManifest, SnapshotExportError> {
+        let path = self.manifest_path(export_id);
+        let data = match std::fs::read(&path) {
+            Ok(data) => data,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Err(SnapshotExportError::ExportNotFound {
+                    export_id: export_id.to_string(),
+                    path: self.path.display().to_string(),
+                })
+            }
+            Err(source) => {
+                return Err(SnapshotExportError::Io {
+                    source,
+                    path: path.display().to_string(),
+                })
+            }
+        };
+        serde_json::from_slice(&data).context(Manifest {
+            path: path.display().to_string(),
+        })
+    }
+
+    /// Read the manifests of all exports.
+    fn manifests(&self) -> Result<Vec<ExportManifest>, SnapshotExportError> {
+        let dir = self.exports_dir();
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(source) => {
+                return Err(SnapshotExportError::Io {
+                    source,
+                    path: dir.display().to_string(),
+                })
+            }
+        };
+        let mut manifests = vec![];
+        for entry in entries {
+            let entry = entry.context(Io {
+                path: dir.display().to_string(),
+            })?;
+            let path = entry.path();
+            if path.extension().map_or(true, |ext| ext != "json") {
+                continue;
+            }
+            if let Some(export_id) = path.file_stem().and_then(|stem| stem.to_str()) {
+                manifests.push(self.manifest(export_id)?);
+            }
+        }
+        Ok(manifests)
+    }
+
+    /// Get the merged chunks of the export chain which ends at the given export.
+    fn chain_chunks(
+        &self,
+        export_id: &str,
+    ) -> Result<(ExportManifest, ChunkMap), SnapshotExportError> {
+        let mut chain = vec![self.manifest(export_id)?];
+        while let Some(base) = chain.last().and_then(|manifest| manifest.base.clone()) {
+            if chain.iter().any(|manifest| manifest.id == base) {
+                return Err(SnapshotExportError::IncompatibleBase {
+                    export_id: export_id.to_string(),
+                    reason: format!("the chain loops back to export {base}"),
+                });
+            }
+            chain.push(self.manifest(&base)?);
+        }
+
+        let last = chain[0].clone();
+        let mut chunks = ChunkMap::new();
+        for manifest in chain.into_iter().rev() {
+            if manifest.chunk_size != last.chunk_size {
+                return Err(SnapshotExportError::IncompatibleBase {
+                    export_id: export_id.to_string(),
+                    reason: format!(
+                        "export {} has a chunk size of {} instead of {}",
+                        manifest.id, manifest.chunk_size, last.chunk_size
+                    ),
+                });
+            }
+            // The volume may have been shrunk since the previous export.
+            let num_chunks = manifest.num_chunks();
+            chunks.retain(|index, _| *index < num_chunks);
+            chunks.extend(manifest.chunks);
+        }
+        chunks.retain(|_, digest| digest.is_some());
+        Ok((last, chunks))
+    }
+
+    /// Store the given chunk, unless a chunk with the same digest is already stored.
+    /// Returns whether the chunk was written.
+    fn store_chunk(&self, digest: &str, data: &[u8]) -> Result<bool, SnapshotExportError> {
+        let path = self.chunk_path(digest);
+        if path.exists() {
+            return Ok(false);
+        }
+        Self::write_file(&path, data)?;
+        Ok(true)
+    }
+
+    /// Read the given chunk, verifying that its data matches its digest.
+    fn read_chunk(&self, digest: &str) -> Result<Vec<u8>, SnapshotExportError> {
+        let data = std::fs::read(self.chunk_path(digest)).map_err(|error| {
+            SnapshotExportError::CorruptedChunk {
+                digest: digest.to_string(),
+                reason: error.to_string(),
+            }
+        })?;
+        if chunk_digest(&data) != digest {
+            return CorruptedChunk {
+                digest: digest.to_string(),
+                reason: "digest mismatch".to_string(),
+            }
+            .fail();
+        }
+        Ok(data)
+    }
+
+    /// Store the manifest of an export.
+    fn store_manifest(&self, manifest: &ExportManifest) -> Result<(), SnapshotExportError> {
+        let path = self.manifest_path(&manifest.id);
+        let data = serde_json::to_vec_pretty(manifest).context(Manifest {
+            path: path.display().to_string(),
+        })?;
+        Self::write_file(&path, &data)
+    }
+
+    /// Delete the given export, along with the chunks which are no longer referenced by any
+    /// other export.
+    fn delete_export(&self, export_id: &str) -> Result<(), SnapshotExportError> {
+        let manifests = self.manifests()?;
+        if let Some(dependent) = manifests
+            .iter()
+            .find(|manifest| manifest.base.as_deref() == Some(export_id))
+        {
+            return ExportInUse {
+                export_id: export_id.to_string(),
+                dependent_id: dependent.id.clone(),
+            }
+            .fail();
+        }
+        let path = self.manifest_path(export_id);
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(source) => {
+                return Err(SnapshotExportError::Io {
+                    source,
+                    path: path.display().to_string(),
+                })
+            }
+        }
+
+        let referenced = manifests
+            .iter()
+            .filter(|manifest| manifest.id != export_id)
+            .flat_map(|manifest| manifest.chunks.values().flatten())
+            .collect::<HashSet<_>>();
+        let dir = self.chunks_dir();
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(source) => {
+                return Err(SnapshotExportError::Io {
+                    source,
+                    path: dir.display().to_string(),
+                })
+            }
+        };
+        for entry in entries {
+            let entry = entry.context(Io {
+                path: dir.display().to_string(),
+            })?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !referenced.contains(&name) {
+                std::fs::remove_file(entry.path()).context(Io {
+                    path: entry.path().display().to_string(),
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the file through a temporary file, so a partially written file is never visible.
+    fn write_file(path: &Path, data: &[u8]) -> Result<(), SnapshotExportError> {
+        let tmp_path = path.with_extension("tmp");
+        let context = || Io {
+            path: path.display().to_string(),
+        };
+        let mut file = File::create(&tmp_path).with_context(|_| context())?;
+        file.write_all(data).with_context(|_| context())?;
+        file.sync_all().with_context(|_| context())?;
+        std::fs::rename(&tmp_path, path).with_context(|_| context())
+    }
+}
+
+/// Get the hex encoded sha256 digest of the chunk data.
+fn chunk_digest(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Read the chunk at the given index from the device, returning its digest if not zeroed.
+fn read_device_chunk(
+    device: &mut File,
+    device_path: &str,
+    index: u64,
+    chunk_size: u64,
+    size: u64,
+    buffer: &mut Vec<u8>,
+) -> Result<Option<String>, SnapshotExportError> {
+    let offset = index * chunk_size;
+    let len = chunk_size.min(size - offset) as usize;
+    buffer.resize(len, 0);
+    device
+        .seek(SeekFrom::Start(offset))
+        .and_then(|_| device.read_exact(buffer))
+        .context(Io {
+            path: device_path.to_string(),
+        })?;
+    Ok(match buffer.iter().all(|byte| *byte == 0) {
+        true => None,
+        false => Some(chunk_digest(buffer)),
+    })
+}
+
+/// Export the volume device into the backup directory.
+fn export_device(
+    device_path: &str,
+    path: PathBuf,
+    export_id: &str,
+    base_export_id: Option<&str>,
+    chunk_size: u64,
+) -> Result<ExportStats, SnapshotExportError> {
+    let dir = BackupDir::new(path);
+    dir.create()?;
+    if dir.manifest_path(export_id).exists() {
+        return ExportExists {
+            export_id: export_id.to_string(),
+            path: dir.path.display().to_string(),
+        }
+        .fail();
+    }
+    let base_chunks = match base_export_id {
+        Some(base_id) => {
+            let (base, chunks) = dir.chain_chunks(base_id)?;
+            if base.chunk_size != chunk_size {
+                return IncompatibleBase {
+                    export_id: export_id.to_string(),
+                    reason: format!(
+                        "the base export has a chunk size of {} instead of {chunk_size}",
+                        base.chunk_size
+                    ),
+                }
+                .fail();
+            }
+            chunks
+        }
+        None => ChunkMap::new(),
+    };
+
+    let size = sysfs_dev_size(device_path).context(crate::error::Device)? as u64;
+    let mut device = File::open(device_path).context(Io {
+        path: device_path.to_string(),
+    })?;
+    let mut manifest = ExportManifest {
+        id: export_id.to_string(),
+        base: base_export_id.map(ToString::to_string),
+        size,
+        chunk_size,
+        chunks: ChunkMap::new(),
+    };
+    let mut stats = ExportStats {
+        size,
+        chunks: manifest.num_chunks(),
+        ..Default::default()
+    };
+
+    let mut buffer = Vec::with_capacity(chunk_size as usize);
+    for index in 0 .. stats.chunks {
+        let digest = read_device_chunk(
+            &mut device,
+            device_path,
+            index,
+            chunk_size,
+            size,
+            &mut buffer,
+        )?;
+        let base_digest = base_chunks.get(&index).cloned().flatten();
+        if digest == base_digest {
+            continue;
+        }
+        if let Some(digest) = &digest {
+            if dir.store_chunk(digest, &buffer)? {
+                stats.written_bytes += buffer.len() as u64;
+            }
+        }
+        stats.changed_chunks += 1;
+        manifest.chunks.insert(index, digest);
+    }
+
+    // The manifest is stored last, so the export only becomes visible once all of its chunks
+    // have been stored.
+    dir.store_manifest(&manifest)?;
+    Ok(stats)
+}
+
+/// Import the export chain which ends at the given export into the volume device.
+/// The device is expected to be zeroed, and so only the chunks with data are written.
+fn import_device(
+    device_path: &str,
+    path: PathBuf,
+    export_id: &str,
+) -> Result<(), SnapshotExportError> {
+    let dir = BackupDir::new(path);
+    let (manifest, chunks) = dir.chain_chunks(export_id)?;
+
+    let size = sysfs_dev_size(device_path).context(crate::error::Device)? as u64;
+    if size < manifest.size {
+        return DeviceTooSmall {
+            size,
+            export_size: manifest.size,
+        }
+        .fail();
+    }
+    let mut device = OpenOptions::new()
+        .write(true)
+        .open(device_path)
+        .context(Io {
+            path: device_path.to_string(),
+        })?;
+    for (index, digest) in chunks {
+        let Some(digest) = digest else {
+            continue;
+        };
+        let data = dir.read_chunk(&digest)?;
+        device
+            .seek(SeekFrom::Start(index * manifest.chunk_size))
+            .and_then(|_| device.write_all(&data))
+            .context(Io {
+                path: device_path.to_string(),
+            })?;
+    }
+    device.sync_all().context(Io {
+        path: device_path.to_string(),
+    })
+}
+
+/// Attach the device of the volume target with the given uri, returning its device path.
+async fn attach_device(
+    volume_id: &str,
+    uri: &str,
+    publish_context: &HashMap<String, String>,
+) -> Result<String, Status> {
+    let mut device = Device::parse(uri)?;
+    device.parse_parameters(publish_context).await?;
+    if let Some(device_path) = device.find().await? {
+        return Ok(device_path);
+    }
+    debug!(volume.uuid = volume_id, "Attaching volume");
+    device.attach().await?;
+    let device_path =
+        Device::wait_for_device(&*device, ATTACH_TIMEOUT_INTERVAL, ATTACH_RETRIES).await?;
+    device.fixup().await?;
+    Ok(device_path)
+}
+
+/// Detach the device of the volume, if it's attached.
+async fn detach_device(uuid: &Uuid) {
+    match Device::lookup(uuid).await {
+        Ok(Some(device)) => {
+            if let Err(error) = device.detach().await {
+                warn!(volume.uuid = %uuid, %error, "Failed to detach the device");
+            }
+        }
+        Ok(None) => {}
+        Err(error) => {
+            warn!(volume.uuid = %uuid, %error, "Failed to lookup the device");
+        }
+    }
+}
+
+/// The export id is used as a file name within the backup directory, and so it must be a uuid.
+fn validate_export_id(export_id: &str) -> Result<(), Status> {
+    Uuid::parse_str(export_id)
+        .map(|_| ())
+        .map_err(|error| Status::invalid_argument(format!("Invalid export ID: {error}")))
+}
+
+/// Resolve the given backup directory path, which must be an absolute path within the backup
+/// base directory configured on this node.
+/// The path is canonicalized, so neither `..` nor symlinks can escape the base directory. As the
+/// backup directory may not exist yet, only its deepest existing ancestor is canonicalized.
+fn resolve_backup_dir(path: &str) -> Result<PathBuf, SnapshotExportError> {
+    let Some(base_dir) = crate::config::config().backup_dir().cloned() else {
+        return BackupDirNotConfigured {}.fail();
+    };
+    let invalid = |reason: &str| InvalidBackupDir {
+        path: path.to_string(),
+        reason: reason.to_string(),
+    };
+    let requested = Path::new(path);
+    if !requested.is_absolute() {
+        return invalid("the path must be absolute").fail();
+    }
+    if requested
+        .components()
+        .any(|component| matches!(component, Component::ParentDir | Component::CurDir))
+    {
+        return invalid("the path must not contain '.' or '..'").fail();
+    }
+
+    let mut existing = requested;
+    let mut missing = vec![];
+    let resolved = loop {
+        match std::fs::canonicalize(existing) {
+            Ok(resolved) => break resolved,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+                    return invalid("the path does not exist").fail();
+                };
+                missing.push(name);
+                existing = parent;
+            }
+            Err(source) => {
+                return Err(SnapshotExportError::Io {
+                    source,
+                    path: existing.display().to_string(),
+                })
+            }
+        }
+    };
+    let resolved = missing
+        .into_iter()
+        .rev()
+        .fold(resolved, |resolved, name| resolved.join(name));
+    if !resolved.starts_with(&base_dir) {
+        return invalid(&format!(
+            "the path is not within the backup base directory {}",
+            base_dir.display()
+        ))
+        .fail();
+    }
+    Ok(resolved)
+}
+
+/// Lock the given resolved backup directory for the duration of an operation.
+async fn lock_backup_dir(path: &Path) -> tokio::sync::OwnedMutexGuard<()> {
+    let lock = {
+        let mut locks = BACKUP_DIR_LOCKS.lock().expect("not poisoned");
+        // forget the locks which are neither held nor waited upon
+        locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+        locks.entry(path.to_path_buf()).or_default().clone()
+    };
+    lock.lock_owned().await
+}
+
+/// Run the given blocking operation on the device of the volume, which is attached for the
+/// duration of the operation, whilst holding the lock of the given backup directory.
+async fn with_device<T: Send + 'static>(
+    volume_id: &str,
+    uri: &str,
+    publish_context: &HashMap<String, String>,
+    backup_dir: PathBuf,
+    operation: impl FnOnce(String, PathBuf) -> Result<T, SnapshotExportError> + Send + 'static,
+) -> Result<T, Status> {
+    let uuid = Uuid::parse_str(volume_id)
+        .map_err(|error| Status::invalid_argument(format!("Invalid volume ID: {error}")))?;
+    let _guard = VolumeOpGuard::new(uuid)?;
+    let _dir_guard = lock_backup_dir(&backup_dir).await;
+
+    let device_path = attach_device(volume_id, uri, publish_context).await?;
+    let result = tokio::task::spawn_blocking(move || operation(device_path, backup_dir))
+        .await
+        .map_err(|error| Status::aborted(error.to_string()));
+    detach_device(&uuid).await;
+    Ok(result??)
+}
+
+/// Export the volume into the backup directory, storing only the chunks which differ from the
+/// base export, if any.
+pub(crate) async fn export_snapshot(
+    volume_id: &str,
+    uri: &str,
+    publish_context: &HashMap<String, String>,
+    path: &str,
+    export_id: &str,
+    base_export_id: Option<String>,
+    chunk_size: u64,
+) -> Result<ExportStats, Status> {
+    validate_export_id(export_id)?;
+    if let Some(base_export_id) = &base_export_id {
+        validate_export_id(base_export_id)?;
+    }
+    if chunk_size == 0 || chunk_size % 512 != 0 {
+        return Err(InvalidChunkSize { chunk_size }.build().into());
+    }
+    let path = resolve_backup_dir(path)?;
+    let export_id = export_id.to_string();
+    let stats = with_device(
+        volume_id,
+        uri,
+        publish_context,
+        path,
+        move |device_path, path| {
+            export_device(
+                &device_path,
+                path,
+                &export_id,
+                base_export_id.as_deref(),
+                chunk_size,
+            )
+        },
+    )
+    .await?;
+    info!(volume.uuid = volume_id, ?stats, "Exported volume");
+    Ok(stats)
+}
+
+/// Import the export chain which ends at the given export into the volume.
+pub(crate) async fn import_snapshot(
+    volume_id: &str,
+    uri: &str,
+    publish_context: &HashMap<String, String>,
+    path: &str,
+    export_id: &str,
+) -> Result<(), Status> {
+    validate_export_id(export_id)?;
+    let path = resolve_backup_dir(path)?;
+    let export_id = export_id.to_string();
+    with_device(
+        volume_id,
+        uri,
+        publish_context,
+        path,
+        move |device_path, path| import_device(&device_path, path, &export_id),
+    )
+    .await?;
+    info!(volume.uuid = volume_id, "Imported volume");
+    Ok(())
+}
+
+/// Delete the export from the backup directory.
+pub(crate) async fn delete_snapshot_export(path: &str, export_id: &str) -> Result<(), Status> {
+    validate_export_id(export_id)?;
+    let path = resolve_backup_dir(path)?;
+    let _dir_guard = lock_backup_dir(&path).await;
+    let export_id = export_id.to_string();
+    tokio::task::spawn_blocking(move || BackupDir::new(path).delete_export(&export_id))
+        .await
+        .map_err(|error| Status::aborted(error.to_string()))??;
+    Ok(())
+}
@@ -6,7 +6,9 @@ use std::{
     num::ParseIntError,
     str::{FromStr, ParseBoolError},
 };
-use stor_port::types::v0::openapi::models::VolumeShareProtocol;
+use stor_port::types::v0::openapi::models::{
+    EncryptionKeyProvider, EncryptionKeyRef, VolumeQos, VolumeShareProtocol,
+};
 use strum_macros::{AsRefStr, Display, EnumString};
 use tracing::log::warn;
 use utils::K8S_STS_PVC_NAMING_REGEX;
@@ -17,6 +19,7 @@ use uuid::{Error as UuidError, Uuid};
 pub fn parse_protocol(proto: Option<&String>) -> Result<VolumeShareProtocol, tonic::Status> {
     match proto.map(|s| s.as_str()) {
         None | Some("nvmf") => Ok(VolumeShareProtocol::Nvmf),
+        Some("iscsi") => Ok(VolumeShareProtocol::Iscsi),
         _ => Err(tonic::Status::invalid_argument(format!(
             "Invalid protocol: {proto:?}"
         ))),
@@ -39,6 +42,10 @@ pub enum Parameters {
     ReplicaCount,
     #[strum(serialize = "fsType")]
     FileSystem,
+    #[strum(serialize = "mkfsOptions")]
+    MkfsOptions,
+    #[strum(serialize = "mountOptions")]
+    MountOptions,
     #[strum(serialize = "protocol")]
     ShareProtocol,
     // TODO: Get the values from constant.
@@ -54,6 +61,24 @@ pub enum Parameters {
     FsId,
     #[strum(serialize = "maxSnapshots")]
     MaxSnapshots,
+    #[strum(serialize = "qosRwIops")]
+    QosRwIops,
+    #[strum(serialize = "qosRwMbps")]
+    QosRwMbps,
+    #[strum(serialize = "qosReadMbps")]
+    QosReadMbps,
+    #[strum(serialize = "qosWriteMbps")]
+    QosWriteMbps,
+    #[strum(serialize = "encrypted")]
+    Encrypted,
+    #[strum(serialize = "encryptionKeyProvider")]
+    EncryptionKeyProvider,
+    #[strum(serialize = "encryptionKeyName")]
+    EncryptionKeyName,
+    #[strum(serialize = "encryptionKeyNamespace")]
+    EncryptionKeyNamespace,
+    #[strum(serialize = "encryptionKeySecretKey")]
+    EncryptionKeySecretKey,
     #[strum(serialize = "quiesceFs")]
     QuiesceFs,
     #[strum(serialize = "poolAffinityTopologyLabel")]
@@ -147,6 +172,12 @@ impl Parameters {
             None => None,
         })
     }
+    fn parse_u64(value: Option<&String>) -> Result<Option<u64>, ParseIntError> {
+        Ok(match value {
+            Some(value) => value.parse::<u64>().map(Some)?,
+            None => None,
+        })
+    }
     fn parse_bool(value: Option<&String>) -> Result<Option<bool>, ParseBoolError> {
         Ok(match value {
             Some(value) => value.parse::<bool>().map(Some)?,
@@ -191,6 +222,30 @@ impl Parameters {
     ) -> Result<Option<bool>, ParseBoolError> {
         Self::parse_bool(value)
     }
+    /// Parse the whitespace separated value for `Self::MkfsOptions`, eg: "-i 65536 -m 0".
+    pub fn mkfs_options(value: Option<&String>) -> Option<Vec<String>> {
+        value
+            .map(|options| {
+                options
+                    .split_whitespace()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|options| !options.is_empty())
+    }
+    /// Parse the comma separated value for `Self::MountOptions`, eg: "noatime,discard".
+    pub fn mount_options(value: Option<&String>) -> Option<Vec<String>> {
+        value
+            .map(|options| {
+                options
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|option| !option.is_empty())
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|options| !options.is_empty())
+    }
     /// Parse the value for `Self::FsId`
     pub fn fs_id(value: Option<&String>) -> Result<Option<Uuid>, UuidError> {
         Self::parse_uuid(value)
@@ -241,6 +296,91 @@ impl Parameters {
     pub fn max_snapshots(value: Option<&String>) -> Result<Option<u32>, ParseIntError> {
         Self::parse_u32(value)
     }
+    /// Parse the value for `Self::QosRwIops`, `Self::QosRwMbps`, `Self::QosReadMbps` or
+    /// `Self::QosWriteMbps`.
+    pub fn qos_limit(value: Option<&String>) -> Result<Option<u64>, ParseIntError> {
+        Self::parse_u64(value)
+    }
+    /// Parse the volume QoS limits from the given parameters.
+    /// Returns `None` if none of the limits are specified.
+    pub fn qos(args: &HashMap<String, String>) -> Result<Option<VolumeQos>, tonic::Status> {
+        let limit = |param: Parameters| {
+            Self::qos_limit(args.get(param.as_ref())).map_err(|_| {
+                tonic::Status::invalid_argument(format!(
+                    "Invalid `{}` value, expected an u64",
+                    param.as_ref()
+                ))
+            })
+        };
+        let qos = VolumeQos::new_all(
+            limit(Parameters::QosRwIops)?,
+            limit(Parameters::QosRwMbps)?,
+            limit(Parameters::QosReadMbps)?,
+            limit(Parameters::QosWriteMbps)?,
+        );
+        if qos.rw_iops.unwrap_or_default() % 1000 != 0 {
+            return Err(tonic::Status::invalid_argument(format!(
+                "Invalid `{}` value, expected a multiple of 1000",
+                Parameters::QosRwIops.as_ref()
+            )));
+        }
+        let unlimited = [qos.rw_iops, qos.rw_mbps, qos.read_mbps, qos.write_mbps]
+            .iter()
+            .all(Option::is_none);
+        Ok((!unlimited).then_some(qos))
+    }
+    /// Parse the value for `Self::Encrypted`.
+    pub fn encrypted(value: Option<&String>) -> Result<Option<bool>, ParseBoolError> {
+        Self::parse_bool(value)
+    }
+    /// Parse the value for `Self::EncryptionKeyProvider`, defaulting to the Secret provider.
+    pub fn encryption_key_provider(
+        value: Option<&String>,
+    ) -> Result<EncryptionKeyProvider, tonic::Status> {
+        match value.map(|s| s.as_str()) {
+            None | Some("secret") => Ok(EncryptionKeyProvider::Secret),
+            Some("file") => Ok(EncryptionKeyProvider::File),
+            Some("kms") => Ok(EncryptionKeyProvider::Kms),
+            Some(value) => Err(tonic::Status::invalid_argument(format!(
+                "Invalid `{}` value: {value}, expected one of: secret, file, kms",
+                Self::EncryptionKeyProvider.as_ref()
+            ))),
+        }
+    }
+    /// Parse the reference to the volume encryption key from the given parameters.
+    /// Returns `None` if the volume is not encrypted.
+    /// The namespace of a Secret defaults to the namespace of the PVC.
+    pub fn encryption(
+        args: &HashMap<String, String>,
+    ) -> Result<Option<EncryptionKeyRef>, tonic::Status> {
+        let encrypted = Self::encrypted(args.get(Self::Encrypted.as_ref())).map_err(|_| {
+            tonic::Status::invalid_argument("Invalid `encrypted` value, expected a bool")
+        })?;
+        if !encrypted.unwrap_or(false) {
+            return Ok(None);
+        }
+        let provider =
+            Self::encryption_key_provider(args.get(Self::EncryptionKeyProvider.as_ref()))?;
+        let Some(name) = args.get(Self::EncryptionKeyName.as_ref()) else {
+            return Err(tonic::Status::invalid_argument(format!(
+                "The `{}` is required for encrypted volumes",
+                Self::EncryptionKeyName.as_ref()
+            )));
+        };
+        let namespace = match provider {
+            EncryptionKeyProvider::Secret => args
+                .get(Self::EncryptionKeyNamespace.as_ref())
+                .or_else(|| args.get(Self::PvcNamespace.as_ref()))
+                .cloned(),
+            EncryptionKeyProvider::File | EncryptionKeyProvider::Kms => None,
+        };
+        Ok(Some(EncryptionKeyRef::new_all(
+            provider,
+            name,
+            namespace,
+            args.get(Self::EncryptionKeySecretKey.as_ref()).cloned(),
+        )))
+    }
 }
 
 /// Volume publish parameters.
@@ -253,6 +393,8 @@ pub struct PublishParams {
     keep_alive_tmo: Option<u32>,
     fs_type: Option<FileSystem>,
     fs_id: Option<Uuid>,
+    mkfs_options: Option<Vec<String>>,
+    mount_options: Option<Vec<String>>,
     pool_affinity_topology_label: Option<HashMap<String, String>>,
     pool_affinity_topology_key: Option<Vec<String>>,
     pool_has_topology_key: Option<HashMap<String, String>>,
@@ -282,6 +424,29 @@ impl PublishParams {
     pub fn fs_id(&self) -> &Option<Uuid> {
         &self.fs_id
     }
+    /// Get the `Parameters::MkfsOptions` value.
+    pub fn mkfs_options(&self) -> &Option<Vec<String>> {
+        &self.mkfs_options
+    }
+    /// Get the `Parameters::MountOptions` value.
+    pub fn mount_options(&self) -> &Option<Vec<String>> {
+        &self.mount_options
+    }
+    /// Validate the `Parameters::MkfsOptions` and `Parameters::MountOptions` values against the
+    /// known options of the given filesystem.
+    pub fn validate_fs_options(&self, fs_type: &FileSystem) -> Result<(), tonic::Status> {
+        if let Some(options) = &self.mkfs_options {
+            fs_type
+                .validate_mkfs_options(options)
+                .map_err(tonic::Status::invalid_argument)?;
+        }
+        if let Some(options) = &self.mount_options {
+            fs_type
+                .validate_mount_options(options)
+                .map_err(tonic::Status::invalid_argument)?;
+        }
+        Ok(())
+    }
     /// Get the `Parameters::PoolAffinityTopologyLabel` value.
     pub fn pool_affinity_topology_label(&self) -> &Option<HashMap<String, String>> {
         &self.pool_affinity_topology_label
@@ -332,6 +497,15 @@ impl PublishParams {
         if let Some(fs_id) = self.fs_id() {
             publish_context.insert(Parameters::FsId.to_string(), fs_id.to_string());
         }
+        if let Some(mkfs_options) = self.mkfs_options() {
+            publish_context.insert(Parameters::MkfsOptions.to_string(), mkfs_options.join(" "));
+        }
+        if let Some(mount_options) = self.mount_options() {
+            publish_context.insert(
+                Parameters::MountOptions.to_string(),
+                mount_options.join(","),
+            );
+        }
 
         publish_context
     }
@@ -360,6 +534,8 @@ impl TryFrom<&HashMap<String, String>> for PublishParams {
                 .map_err(|_| tonic::Status::invalid_argument("Invalid keep_alive_tmo"))?;
         let fs_id = Parameters::fs_id(args.get(Parameters::FsId.as_ref()))
             .map_err(|_| tonic::Status::invalid_argument("Invalid fs_id"))?;
+        let mkfs_options = Parameters::mkfs_options(args.get(Parameters::MkfsOptions.as_ref()));
+        let mount_options = Parameters::mount_options(args.get(Parameters::MountOptions.as_ref()));
 
         let pool_affinity_topology_label = Parameters::pool_affinity_topology_label(
             args.get(Parameters::PoolAffinityTopologyLabel.as_ref()),
@@ -392,13 +568,15 @@ impl TryFrom<&HashMap<String, String>> for PublishParams {
             args.get(Parameters::NodeSpreadTopologyKey.as_ref()),
         )
         .map_err(|_| tonic::Status::invalid_argument("Invalid node_spread_topology_key"))?;
-        Ok(Self {
+        let params = Self {
             io_timeout,
             nvme_io_timeout,
             ctrl_loss_tmo,
             keep_alive_tmo,
             fs_type,
             fs_id,
+            mkfs_options,
+            mount_options,
             pool_affinity_topology_label,
             pool_affinity_topology_key,
             pool_has_topology_key,
@@ -406,7 +584,11 @@ impl TryFrom<&HashMap<String, String>> for PublishParams {
             node_affinity_topology_key,
             node_has_topology_key,
             node_spread_topology_key,
-        })
+        };
+        if let Some(fs_type) = &params.fs_type {
+            params.validate_fs_options(fs_type)?;
+        }
+        Ok(params)
     }
 }
 
@@ -420,6 +602,8 @@ pub struct CreateParams {
     sts_affinity_group: Option<String>,
     clone_fs_id_as_volume_id: Option<bool>,
     max_snapshots: Option<u32>,
+    qos: Option<VolumeQos>,
+    encryption: Option<EncryptionKeyRef>,
 }
 impl CreateParams {
     /// Get the `Parameters::PublishParams` value.
@@ -447,6 +631,17 @@ impl CreateParams {
     pub fn max_snapshots(&self) -> Option<u32> {
         self.max_snapshots
     }
+    /// Get the volume QoS limits from the `Parameters::QosRwIops`, `Parameters::QosRwMbps`,
+    /// `Parameters::QosReadMbps` and `Parameters::QosWriteMbps` values.
+    pub fn qos(&self) -> &Option<VolumeQos> {
+        &self.qos
+    }
+    /// Get the reference to the volume encryption key from the `Parameters::Encrypted`,
+    /// `Parameters::EncryptionKeyProvider`, `Parameters::EncryptionKeyName`,
+    /// `Parameters::EncryptionKeyNamespace` and `Parameters::EncryptionKeySecretKey` values.
+    pub fn encryption(&self) -> &Option<EncryptionKeyRef> {
+        &self.encryption
+    }
 }
 impl TryFrom<&HashMap<String, String>> for CreateParams {
     type Error = tonic::Status;
@@ -499,6 +694,10 @@ impl TryFrom<&HashMap<String, String>> for CreateParams {
                 tonic::Status::invalid_argument("Invalid `maxSnapshots` value, expected an u32")
             })?;
 
+        let qos = Parameters::qos(args)?;
+
+        let encryption = Parameters::encryption(args)?;
+
         Ok(Self {
             publish_params,
             share_protocol,
@@ -506,6 +705,8 @@ impl TryFrom<&HashMap<String, String>> for CreateParams {
             sts_affinity_group: sts_affinity_group_name,
             clone_fs_id_as_volume_id,
             max_snapshots,
+            qos,
+            encryption,
         })
     }
 }
@@ -1,24 +1,275 @@
 //! This module consists of the filesystem type definition shared between controller and node.
 use strum_macros::EnumString;
 
+/// Mount options which are handled by the VFS and thus supported by all filesystems.
+const GENERIC_MOUNT_OPTIONS: &[&str] = &[
+    "ro",
+    "rw",
+    "sync",
+    "async",
+    "dirsync",
+    "atime",
+    "noatime",
+    "diratime",
+    "nodiratime",
+    "relatime",
+    "norelatime",
+    "strictatime",
+    "nostrictatime",
+    "lazytime",
+    "nolazytime",
+    "nodev",
+    "nosuid",
+    "noexec",
+];
+
 /// A type to enumerate used filesystems.
 #[derive(EnumString, Clone, Debug, Eq, PartialEq)]
 #[strum(serialize_all = "lowercase")]
 pub enum FileSystem {
     Ext4,
+    Ext3,
+    Ext2,
     Xfs,
     Btrfs,
+    F2fs,
     DevTmpFs,
     Unsupported(String),
 }
 
+impl FileSystem {
+    /// Get the mkfs options which are known to be supported when creating the filesystem.
+    pub fn mkfs_options(&self) -> &'static [&'static str] {
+        match self {
+            FileSystem::Ext4 | FileSystem::Ext3 | FileSystem::Ext2 => &[
+                "-b", "-C", "-e", "-E", "-g", "-G", "-i", "-I", "-j", "-J", "-L", "-m", "-N", "-O",
+                "-T",
+            ],
+            FileSystem::Xfs => &["-b", "-d", "-i", "-K", "-l", "-L", "-m", "-n", "-r", "-s"],
+            FileSystem::Btrfs => &[
+                "-d", "-K", "-L", "-m", "-M", "-n", "-O", "-R", "-s", "--csum",
+            ],
+            FileSystem::F2fs => &[
+                "-a", "-C", "-e", "-E", "-g", "-i", "-l", "-o", "-O", "-s", "-t", "-w", "-z",
+            ],
+            FileSystem::DevTmpFs | FileSystem::Unsupported(_) => &[],
+        }
+    }
+    /// Get the mount options which are known to be supported by the filesystem, on top of the
+    /// generic ones.
+    pub fn mount_options(&self) -> &'static [&'static str] {
+        match self {
+            FileSystem::Ext4 => &[
+                "acl",
+                "noacl",
+                "auto_da_alloc",
+                "noauto_da_alloc",
+                "barrier",
+                "nobarrier",
+                "commit",
+                "data",
+                "dax",
+                "delalloc",
+                "nodelalloc",
+                "discard",
+                "nodiscard",
+                "errors",
+                "init_itable",
+                "noinit_itable",
+                "inode_readahead_blks",
+                "journal_async_commit",
+                "journal_checksum",
+                "max_batch_time",
+                "min_batch_time",
+                "quota",
+                "usrquota",
+                "grpquota",
+                "prjquota",
+                "resgid",
+                "resuid",
+                "stripe",
+                "user_xattr",
+                "nouser_xattr",
+            ],
+            FileSystem::Ext3 => &[
+                "acl",
+                "noacl",
+                "barrier",
+                "nobarrier",
+                "commit",
+                "data",
+                "discard",
+                "nodiscard",
+                "errors",
+                "quota",
+                "usrquota",
+                "grpquota",
+                "resgid",
+                "resuid",
+                "user_xattr",
+                "nouser_xattr",
+            ],
+            FileSystem::Ext2 => &[
+                "acl",
+                "noacl",
+                "errors",
+                "grpid",
+                "nogrpid",
+                "quota",
+                "usrquota",
+                "grpquota",
+                "resgid",
+                "resuid",
+                "user_xattr",
+                "nouser_xattr",
+            ],
+            FileSystem::Xfs => &[
+                "allocsize",
+                "attr2",
+                "noattr2",
+                "dax",
+                "discard",
+                "nodiscard",
+                "grpid",
+                "nogrpid",
+                "inode32",
+                "inode64",
+                "largeio",
+                "nolargeio",
+                "logbsize",
+                "logbufs",
+                "noalign",
+                "norecovery",
+                "nouuid",
+                "quota",
+                "usrquota",
+                "uquota",
+                "grpquota",
+                "gquota",
+                "prjquota",
+                "pquota",
+                "sunit",
+                "swalloc",
+                "swidth",
+                "wsync",
+            ],
+            FileSystem::Btrfs => &[
+                "autodefrag",
+                "noautodefrag",
+                "barrier",
+                "nobarrier",
+                "commit",
+                "compress",
+                "compress-force",
+                "datacow",
+                "nodatacow",
+                "datasum",
+                "nodatasum",
+                "discard",
+                "nodiscard",
+                "flushoncommit",
+                "noflushoncommit",
+                "max_inline",
+                "space_cache",
+                "nospace_cache",
+                "ssd",
+                "nossd",
+                "ssd_spread",
+                "subvol",
+                "subvolid",
+                "thread_pool",
+                "user_subvol_rm_allowed",
+            ],
+            FileSystem::F2fs => &[
+                "acl",
+                "noacl",
+                "active_logs",
+                "age_extent_cache",
+                "atgc",
+                "background_gc",
+                "checkpoint_merge",
+                "nocheckpoint_merge",
+                "compress_algorithm",
+                "compress_extension",
+                "disable_roll_forward",
+                "discard",
+                "nodiscard",
+                "extent_cache",
+                "noextent_cache",
+                "flush_merge",
+                "noflush_merge",
+                "fsync_mode",
+                "gc_merge",
+                "nogc_merge",
+                "heap",
+                "no_heap",
+                "inline_data",
+                "noinline_data",
+                "inline_dentry",
+                "noinline_dentry",
+                "inline_xattr",
+                "noinline_xattr",
+                "mode",
+                "user_xattr",
+                "nouser_xattr",
+            ],
+            FileSystem::DevTmpFs | FileSystem::Unsupported(_) => &[],
+        }
+    }
+    /// Validate the mkfs options against the known options of the filesystem.
+    /// The options are a list of arguments where each option may be followed by its value,
+    /// eg: ["-i", "65536", "-m", "reflink=1"].
+    pub fn validate_mkfs_options(&self, options: &[String]) -> Result<(), String> {
+        let known = self.mkfs_options();
+        let mut expect_option = true;
+        for option in options {
+            if !option.starts_with('-') {
+                if expect_option {
+                    return Err(format!("unexpected mkfs value '{option}' for {self}"));
+                }
+                expect_option = true;
+                continue;
+            }
+            let name = match option.split_once('=') {
+                Some((name, _)) if option.starts_with("--") => name,
+                _ => option.as_str(),
+            };
+            // short options may also be given with their value attached, eg: "-i65536"
+            let known_option = known.iter().any(|known| {
+                *known == name || (!known.starts_with("--") && name.starts_with(known))
+            });
+            if !known_option {
+                return Err(format!("unsupported mkfs option '{option}' for {self}"));
+            }
+            expect_option = false;
+        }
+        Ok(())
+    }
+    /// Validate the mount options against the generic and the known options of the filesystem.
+    pub fn validate_mount_options(&self, options: &[String]) -> Result<(), String> {
+        let known = self.mount_options();
+        for option in options {
+            let name = option
+                .split_once('=')
+                .map_or(option.as_str(), |(name, _)| name);
+            if !GENERIC_MOUNT_OPTIONS.contains(&name) && !known.contains(&name) {
+                return Err(format!("unsupported mount option '{option}' for {self}"));
+            }
+        }
+        Ok(())
+    }
+}
+
 // Implement as ref for the FileSystem.
 impl AsRef<str> for FileSystem {
     fn as_ref(&self) -> &str {
         match self {
             FileSystem::Ext4 => "ext4",
+            FileSystem::Ext3 => "ext3",
+            FileSystem::Ext2 => "ext2",
             FileSystem::Xfs => "xfs",
             FileSystem::Btrfs => "btrfs",
+            FileSystem::F2fs => "f2fs",
             FileSystem::DevTmpFs => "devtmpfs",
             FileSystem::Unsupported(inner) => inner,
         }
@@ -31,3 +282,51 @@ impl std::fmt::Display for FileSystem {
         write!(f, "{}", self.as_ref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FileSystem;
+
+    fn options(options: &str) -> Vec<String> {
+        options
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn mkfs_options() {
+        let fs = FileSystem::Xfs;
+        assert!(fs
+            .validate_mkfs_options(&options("-m reflink=1 -i size=512"))
+            .is_ok());
+        assert!(fs.validate_mkfs_options(&options("-f")).is_err());
+        assert!(fs.validate_mkfs_options(&options("reflink=1")).is_err());
+
+        let fs = FileSystem::Ext4;
+        assert!(fs.validate_mkfs_options(&options("-i 65536 -m 0")).is_ok());
+        assert!(fs.validate_mkfs_options(&options("-i65536")).is_ok());
+        assert!(fs.validate_mkfs_options(&options("-i 65536 0")).is_err());
+
+        let fs = FileSystem::Btrfs;
+        assert!(fs
+            .validate_mkfs_options(&options("--csum=xxhash -L data"))
+            .is_ok());
+        assert!(fs.validate_mkfs_options(&options("--force")).is_err());
+    }
+
+    #[test]
+    fn mount_options() {
+        let fs = FileSystem::F2fs;
+        assert!(fs
+            .validate_mount_options(&options("noatime discard mode=adaptive"))
+            .is_ok());
+        assert!(fs.validate_mount_options(&options("nouuid")).is_err());
+
+        let fs = FileSystem::Ext2;
+        assert!(fs
+            .validate_mount_options(&options("errors=remount-ro"))
+            .is_ok());
+        assert!(fs.validate_mount_options(&options("data=journal")).is_err());
+    }
+}
@@ -91,6 +91,14 @@ pub mod csi_node_nvme {
     tonic::include_proto!("v1.csi_node_nvme");
 }
 
+/// CSI node plugin GRPC module for the autogenerated node plugin code.
+/// The node plugin service runs within the csi-node container, alongside the csi node service.
+pub mod csi_node_plugin {
+    #![allow(clippy::derive_partial_eq_without_eq)]
+    #![allow(clippy::upper_case_acronyms)]
+    tonic::include_proto!("node.service");
+}
+
 /// Snapshot GRPC module for the autogenerated common snapshot code.
 #[allow(clippy::large_enum_variant)]
 pub(crate) mod snapshot {
@@ -34,6 +34,9 @@ impl From<ResourceKind> for common::ResourceKind {
             ResourceKind::VolumeSnapshotClone => Self::VolumeSnapshotClone,
             ResourceKind::ReplicaSnapshotClone => Self::ReplicaSnapshotClone,
             ResourceKind::AppNode => Self::AppNode,
+            ResourceKind::SnapshotSchedule => Self::SnapshotSchedule,
+            ResourceKind::SnapshotGroup => Self::SnapshotGroup,
+            ResourceKind::SnapshotExport => Self::SnapshotExport,
         }
     }
 }
@@ -63,6 +66,9 @@ impl From<common::ResourceKind> for ResourceKind {
             common::ResourceKind::VolumeSnapshotClone => Self::VolumeSnapshotClone,
             common::ResourceKind::ReplicaSnapshotClone => Self::ReplicaSnapshotClone,
             common::ResourceKind::AppNode => Self::AppNode,
+            common::ResourceKind::SnapshotSchedule => Self::SnapshotSchedule,
+            common::ResourceKind::SnapshotGroup => Self::SnapshotGroup,
+            common::ResourceKind::SnapshotExport => Self::SnapshotExport,
         }
     }
 }
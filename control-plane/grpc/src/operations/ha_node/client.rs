@@ -1,16 +1,18 @@
 use crate::{
     context::{Client, Context, TracedChannel},
-    ha_cluster_agent::ha_cluster_rpc_client::HaClusterRpcClient,
+    ha_cluster_agent::{
+        get_switch_overs_reply, ha_cluster_rpc_client::HaClusterRpcClient, move_volume_target_reply,
+    },
     ha_node_agent::{get_nvme_controller_response::Reply, ha_node_rpc_client::HaNodeRpcClient},
     operations::ha_node::traits::{
-        ClusterAgentOperations, GetControllerInfo, NodeAgentOperations, NodeInfo, ReplacePathInfo,
-        ReportFailedPathsInfo,
+        ClusterAgentOperations, GetControllerInfo, GetSwitchOversInfo, MoveVolumeTargetInfo,
+        NodeAgentOperations, NodeInfo, ReplacePathInfo, ReportFailedPathsInfo,
     },
 };
 use std::ops::Deref;
 use stor_port::{
     transport_api::{v0::NvmeSubsystems, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{FailedPathsResponse, MessageIdVs},
+    types::v0::transport::{FailedPathsResponse, MessageIdVs, SwitchOverInfo, SwitchOvers},
 };
 use tonic::transport::Uri;
 
@@ -72,6 +74,50 @@ impl ClusterAgentOperations for ClusterAgentClient {
             .into_inner();
         Ok(report.into())
     }
+
+    #[tracing::instrument(
+        name = "ClusterAgentClient::get_switchovers",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn get_switchovers(
+        &self,
+        request: &dyn GetSwitchOversInfo,
+        context: Option<Context>,
+    ) -> Result<SwitchOvers, ReplyError> {
+        let req = self.request(request, context, MessageIdVs::GetSwitchOvers);
+        let response = self.client().get_switch_overs(req).await?.into_inner();
+        match response.reply {
+            Some(get_switch_overs_reply::Reply::Switchovers(switchovers)) => {
+                Ok(SwitchOvers::try_from(switchovers)?)
+            }
+            Some(get_switch_overs_reply::Reply::Error(error)) => Err(error.into()),
+            None => Err(ReplyError::invalid_response(ResourceKind::NvmePath)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "ClusterAgentClient::move_volume_target",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn move_volume_target(
+        &self,
+        request: &dyn MoveVolumeTargetInfo,
+        context: Option<Context>,
+    ) -> Result<SwitchOverInfo, ReplyError> {
+        let req = self.request(request, context, MessageIdVs::MoveVolumeTarget);
+        let response = self.client().move_volume_target(req).await?.into_inner();
+        match response.reply {
+            Some(move_volume_target_reply::Reply::Switchover(switchover)) => {
+                Ok(SwitchOverInfo::try_from(switchover)?)
+            }
+            Some(move_volume_target_reply::Reply::Error(error)) => Err(error.into()),
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
 }
 
 /// Node agent RPC Client.
@@ -1,7 +1,10 @@
 use crate::{
     ha_cluster_agent::{
+        get_switch_overs_reply,
         ha_cluster_rpc_server::{HaClusterRpc, HaClusterRpcServer},
-        FailedNvmePathsResponse, HaNodeInfo, ReportFailedNvmePathsRequest,
+        move_volume_target_reply, FailedNvmePathsResponse, GetSwitchOversReply,
+        GetSwitchOversRequest, HaNodeInfo, MoveVolumeTargetReply, MoveVolumeTargetRequest,
+        ReportFailedNvmePathsRequest,
     },
     ha_node_agent::{
         get_nvme_controller_response,
@@ -11,6 +14,7 @@ use crate::{
     operations::ha_node::traits::{ClusterAgentOperations, NodeAgentOperations, NodeInfoConv},
 };
 
+use stor_port::types::v0::transport::MoveVolumeTarget;
 use tonic::{Request, Response, Status};
 
 use std::sync::Arc;
@@ -111,4 +115,38 @@ impl HaClusterRpc for ClusterAgentServer {
             Err(error) => Err(error.into()),
         }
     }
+
+    async fn get_switch_overs(
+        &self,
+        request: tonic::Request<GetSwitchOversRequest>,
+    ) -> Result<tonic::Response<GetSwitchOversReply>, tonic::Status> {
+        let req = request.into_inner();
+        match self.service.get_switchovers(&req, None).await {
+            Ok(switchovers) => Ok(Response::new(GetSwitchOversReply {
+                reply: Some(get_switch_overs_reply::Reply::Switchovers(
+                    switchovers.into(),
+                )),
+            })),
+            Err(error) => Ok(Response::new(GetSwitchOversReply {
+                reply: Some(get_switch_overs_reply::Reply::Error(error.into())),
+            })),
+        }
+    }
+
+    async fn move_volume_target(
+        &self,
+        request: tonic::Request<MoveVolumeTargetRequest>,
+    ) -> Result<tonic::Response<MoveVolumeTargetReply>, tonic::Status> {
+        let req = MoveVolumeTarget::try_from(request.into_inner())?;
+        match self.service.move_volume_target(&req, None).await {
+            Ok(switchover) => Ok(Response::new(MoveVolumeTargetReply {
+                reply: Some(move_volume_target_reply::Reply::Switchover(
+                    switchover.into(),
+                )),
+            })),
+            Err(error) => Ok(Response::new(MoveVolumeTargetReply {
+                reply: Some(move_volume_target_reply::Reply::Error(error.into())),
+            })),
+        }
+    }
 }
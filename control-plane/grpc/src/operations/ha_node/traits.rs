@@ -2,8 +2,8 @@ use crate::{
     common,
     context::Context,
     ha_cluster_agent::{
-        FailedNvmePath, FailedNvmePathResponse, FailedNvmePathsResponse, HaNodeInfo,
-        ReportFailedNvmePathsRequest,
+        self, FailedNvmePath, FailedNvmePathResponse, FailedNvmePathsResponse,
+        GetSwitchOversRequest, HaNodeInfo, MoveVolumeTargetRequest, ReportFailedNvmePathsRequest,
     },
     ha_node_agent::{GetNvmeControllerRequest, NvmeControllers, ReplacePathRequest},
 };
@@ -12,7 +12,8 @@ use stor_port::{
     transport_api::{v0::NvmeSubsystems, ReplyError, ResourceKind},
     types::v0::transport::{
         cluster_agent::NodeAgentInfo, FailedPath, FailedPathsResponse, GetController,
-        NvmeSubsystem, ReplacePath, ReportFailedPaths,
+        GetSwitchOvers, MoveVolumeTarget, NodeId, NvmeSubsystem, ReplacePath, ReportFailedPaths,
+        SwitchOverInfo, SwitchOverStage, SwitchOvers, VolumeId,
     },
     IntoVec,
 };
@@ -152,6 +153,20 @@ pub trait ClusterAgentOperations: Send + Sync {
         request: &dyn ReportFailedPathsInfo,
         context: Option<Context>,
     ) -> Result<FailedPathsResponse, ReplyError>;
+
+    /// Get the in-flight and the recently completed switchovers.
+    async fn get_switchovers(
+        &self,
+        request: &dyn GetSwitchOversInfo,
+        context: Option<Context>,
+    ) -> Result<SwitchOvers, ReplyError>;
+
+    /// Move the target of a published volume onto another node.
+    async fn move_volume_target(
+        &self,
+        request: &dyn MoveVolumeTargetInfo,
+        context: Option<Context>,
+    ) -> Result<SwitchOverInfo, ReplyError>;
 }
 
 /// NodeInfo trait for the node-agent registration to be implemented by entities which want to
@@ -302,3 +317,163 @@ impl From<FailedPathsResponse> for FailedNvmePathsResponse {
         }
     }
 }
+
+/// GetSwitchOversInfo trait for the get_switchovers operation.
+pub trait GetSwitchOversInfo: Send + Sync + std::fmt::Debug {}
+
+impl GetSwitchOversInfo for GetSwitchOvers {}
+
+impl GetSwitchOversInfo for GetSwitchOversRequest {}
+
+impl From<&dyn GetSwitchOversInfo> for GetSwitchOversRequest {
+    fn from(_: &dyn GetSwitchOversInfo) -> Self {
+        Self {}
+    }
+}
+
+/// MoveVolumeTargetInfo trait for the move_volume_target operation.
+pub trait MoveVolumeTargetInfo: Send + Sync + std::fmt::Debug {
+    /// The volume whose target is moved.
+    fn volume(&self) -> VolumeId;
+    /// The node on which to create the new target, if any.
+    fn target_node(&self) -> Option<NodeId>;
+}
+
+impl MoveVolumeTargetInfo for MoveVolumeTarget {
+    fn volume(&self) -> VolumeId {
+        self.volume.clone()
+    }
+
+    fn target_node(&self) -> Option<NodeId> {
+        self.target_node.clone()
+    }
+}
+
+impl TryFrom<MoveVolumeTargetRequest> for MoveVolumeTarget {
+    type Error = ReplyError;
+
+    fn try_from(value: MoveVolumeTargetRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            volume: VolumeId::try_from(value.volume.as_str()).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::Volume, "volume", error.to_string())
+            })?,
+            target_node: value.target_node.map(Into::into),
+        })
+    }
+}
+
+impl From<&dyn MoveVolumeTargetInfo> for MoveVolumeTargetRequest {
+    fn from(src: &dyn MoveVolumeTargetInfo) -> Self {
+        Self {
+            volume: src.volume().to_string(),
+            target_node: src.target_node().map(|node| node.to_string()),
+        }
+    }
+}
+
+impl From<SwitchOverStage> for ha_cluster_agent::SwitchOverStage {
+    fn from(value: SwitchOverStage) -> Self {
+        match value {
+            SwitchOverStage::Init => Self::Init,
+            SwitchOverStage::RepublishVolume => Self::RepublishVolume,
+            SwitchOverStage::ReplacePath => Self::ReplacePath,
+            SwitchOverStage::DeleteTarget => Self::DeleteTarget,
+            SwitchOverStage::Successful => Self::Successful,
+            SwitchOverStage::Errored => Self::Errored,
+        }
+    }
+}
+
+impl From<ha_cluster_agent::SwitchOverStage> for SwitchOverStage {
+    fn from(value: ha_cluster_agent::SwitchOverStage) -> Self {
+        match value {
+            ha_cluster_agent::SwitchOverStage::Init => Self::Init,
+            ha_cluster_agent::SwitchOverStage::RepublishVolume => Self::RepublishVolume,
+            ha_cluster_agent::SwitchOverStage::ReplacePath => Self::ReplacePath,
+            ha_cluster_agent::SwitchOverStage::DeleteTarget => Self::DeleteTarget,
+            ha_cluster_agent::SwitchOverStage::Successful => Self::Successful,
+            ha_cluster_agent::SwitchOverStage::Errored => Self::Errored,
+        }
+    }
+}
+
+impl From<SwitchOverInfo> for ha_cluster_agent::SwitchOver {
+    fn from(value: SwitchOverInfo) -> Self {
+        Self {
+            volume: value.volume.to_string(),
+            node: value.node.to_string(),
+            planned: value.planned,
+            target_node: value.target_node.map(|node| node.to_string()),
+            priority: value.priority,
+            stage: ha_cluster_agent::SwitchOverStage::from(value.stage) as i32,
+            retry_count: value.retry_count,
+            existing_nqn: value.existing_nqn,
+            new_path: value.new_path,
+            reuse_existing: value.reuse_existing,
+            last_error: value.last_error,
+            start_time: Some(std::time::SystemTime::from(value.start_time).into()),
+            last_update: Some(std::time::SystemTime::from(value.last_update).into()),
+            end_time: value
+                .end_time
+                .map(|time| std::time::SystemTime::from(time).into()),
+        }
+    }
+}
+
+impl TryFrom<ha_cluster_agent::SwitchOver> for SwitchOverInfo {
+    type Error = ReplyError;
+
+    fn try_from(value: ha_cluster_agent::SwitchOver) -> Result<Self, Self::Error> {
+        let invalid = |arg: &'static str, error: String| {
+            ReplyError::invalid_argument(ResourceKind::NvmePath, arg, error)
+        };
+        let time = |arg: &'static str, time: Option<prost_types::Timestamp>| {
+            time.and_then(|time| std::time::SystemTime::try_from(time).ok())
+                .map(Into::into)
+                .ok_or_else(|| invalid(arg, "time conversion failure".to_string()))
+        };
+        let stage = ha_cluster_agent::SwitchOverStage::try_from(value.stage)
+            .map_err(|_| invalid("switchover.stage", "unknown switchover stage".to_string()))?;
+        Ok(Self {
+            volume: VolumeId::try_from(value.volume.as_str())
+                .map_err(|error| invalid("switchover.volume", error.to_string()))?,
+            node: value.node.into(),
+            planned: value.planned,
+            target_node: value.target_node.map(Into::into),
+            priority: value.priority,
+            stage: stage.into(),
+            retry_count: value.retry_count,
+            existing_nqn: value.existing_nqn,
+            new_path: value.new_path,
+            reuse_existing: value.reuse_existing,
+            last_error: value.last_error,
+            start_time: time("switchover.start_time", value.start_time)?,
+            last_update: time("switchover.last_update", value.last_update)?,
+            end_time: match value.end_time {
+                Some(end_time) => Some(time("switchover.end_time", Some(end_time))?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl TryFrom<ha_cluster_agent::SwitchOvers> for SwitchOvers {
+    type Error = ReplyError;
+
+    fn try_from(value: ha_cluster_agent::SwitchOvers) -> Result<Self, Self::Error> {
+        let switchovers = value
+            .switchovers
+            .into_iter()
+            .map(SwitchOverInfo::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(SwitchOvers(switchovers))
+    }
+}
+
+impl From<SwitchOvers> for ha_cluster_agent::SwitchOvers {
+    fn from(value: SwitchOvers) -> Self {
+        Self {
+            switchovers: value.into_inner().into_iter().map(Into::into).collect(),
+        }
+    }
+}
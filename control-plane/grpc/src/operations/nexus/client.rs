@@ -3,8 +3,8 @@ use crate::{
     context::{Client, Context, TracedChannel},
     nexus::{
         add_nexus_child_reply, create_nexus_reply, get_nexuses_reply, get_nexuses_request,
-        nexus_grpc_client::NexusGrpcClient, rebuild_history_reply, share_nexus_reply,
-        GetNexusesRequest,
+        get_rebuild_histories_reply, nexus_grpc_client::NexusGrpcClient, rebuild_history_reply,
+        share_nexus_reply, GetNexusesRequest, GetRebuildHistoriesRequest,
     },
     operations::nexus::traits::{
         AddNexusChildInfo, CreateNexusInfo, DestroyNexusInfo, GetRebuildRecordInfo,
@@ -13,8 +13,13 @@ use crate::{
 };
 use std::{convert::TryFrom, ops::Deref};
 use stor_port::{
-    transport_api::{v0::Nexuses, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{Child, Filter, MessageIdVs, Nexus, RebuildHistory},
+    transport_api::{
+        v0::{Nexuses, VolumeRebuildHistories},
+        ReplyError, ResourceKind, TimeoutOptions,
+    },
+    types::v0::transport::{
+        Child, Filter, GetRebuildHistories, MessageIdVs, Nexus, RebuildHistory,
+    },
 };
 use tonic::transport::Uri;
 
@@ -193,4 +198,23 @@ impl NexusOperations for NexusClient {
             None => Err(ReplyError::invalid_response(ResourceKind::Nexus)),
         }
     }
+
+    async fn get_rebuild_histories(
+        &self,
+        request: &GetRebuildHistories,
+        ctx: Option<Context>,
+    ) -> Result<VolumeRebuildHistories, ReplyError> {
+        let req = GetRebuildHistoriesRequest::from(request);
+        let req = self.request(req, ctx, MessageIdVs::GetRebuildHistories);
+        let response = self.client().get_rebuild_histories(req).await?.into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                get_rebuild_histories_reply::Reply::Histories(histories) => {
+                    Ok(VolumeRebuildHistories::try_from(histories)?)
+                }
+                get_rebuild_histories_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Nexus)),
+        }
+    }
 }
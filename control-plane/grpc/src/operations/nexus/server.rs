@@ -1,18 +1,18 @@
 use crate::{
     misc::traits::ValidateRequestTypes,
     nexus::{
-        add_nexus_child_reply, create_nexus_reply, get_nexuses_reply,
+        add_nexus_child_reply, create_nexus_reply, get_nexuses_reply, get_rebuild_histories_reply,
         nexus_grpc_server::{NexusGrpc, NexusGrpcServer},
         rebuild_history_reply, share_nexus_reply, AddNexusChildReply, AddNexusChildRequest,
         CreateNexusReply, CreateNexusRequest, DestroyNexusReply, DestroyNexusRequest,
-        GetNexusesReply, GetNexusesRequest, RebuildHistoryReply, RebuildHistoryRequest,
-        RemoveNexusChildReply, RemoveNexusChildRequest, ShareNexusReply, ShareNexusRequest,
-        UnshareNexusReply, UnshareNexusRequest,
+        GetNexusesReply, GetNexusesRequest, GetRebuildHistoriesReply, GetRebuildHistoriesRequest,
+        RebuildHistoryReply, RebuildHistoryRequest, RemoveNexusChildReply, RemoveNexusChildRequest,
+        ShareNexusReply, ShareNexusRequest, UnshareNexusReply, UnshareNexusRequest,
     },
     operations::nexus::traits::NexusOperations,
 };
 use std::{convert::TryFrom, sync::Arc};
-use stor_port::types::v0::transport::Filter;
+use stor_port::types::v0::transport::{Filter, GetRebuildHistories};
 use tonic::Response;
 
 /// RPC Nexus Server
@@ -151,4 +151,21 @@ impl NexusGrpc for NexusServer {
             })),
         }
     }
+
+    async fn get_rebuild_histories(
+        &self,
+        request: tonic::Request<GetRebuildHistoriesRequest>,
+    ) -> Result<tonic::Response<GetRebuildHistoriesReply>, tonic::Status> {
+        let req = GetRebuildHistories::try_from(request.into_inner())?;
+        match self.service.get_rebuild_histories(&req, None).await {
+            Ok(histories) => Ok(Response::new(GetRebuildHistoriesReply {
+                reply: Some(get_rebuild_histories_reply::Reply::Histories(
+                    histories.try_into()?,
+                )),
+            })),
+            Err(err) => Ok(Response::new(GetRebuildHistoriesReply {
+                reply: Some(get_rebuild_histories_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
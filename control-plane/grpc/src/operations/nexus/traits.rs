@@ -12,7 +12,10 @@ use crate::{
 use prost_types::Timestamp;
 use std::convert::TryFrom;
 use stor_port::{
-    transport_api::{v0::Nexuses, ReplyError, ResourceKind},
+    transport_api::{
+        v0::{Nexuses, VolumeRebuildHistories},
+        ReplyError, ResourceKind,
+    },
     types::v0::{
         store::{
             nexus::{
@@ -20,12 +23,13 @@ use stor_port::{
                 ReplicaUri,
             },
             nexus_child::NexusChild,
+            rebuild_history::{VolumeRebuildHistory, VolumeRebuildRecord},
         },
         transport::{
             AddNexusChild, Child, ChildState, ChildStateReason, ChildUri, CreateNexus,
-            DestroyNexus, Filter, GetRebuildRecord, HostNqn, Nexus, NexusId, NexusNvmePreemption,
-            NexusNvmfConfig, NexusShareProtocol, NexusStatus, NodeId, NvmeReservation,
-            NvmfControllerIdRange, RebuildHistory, RebuildJobState, RebuildRecord,
+            DestroyNexus, Filter, GetRebuildHistories, GetRebuildRecord, HostNqn, Nexus, NexusId,
+            NexusNvmePreemption, NexusNvmfConfig, NexusShareProtocol, NexusStatus, NodeId,
+            NvmeReservation, NvmfControllerIdRange, RebuildHistory, RebuildJobState, RebuildRecord,
             RemoveNexusChild, ReplicaId, ShareNexus, UnshareNexus, VolumeId,
         },
     },
@@ -79,6 +83,12 @@ pub trait NexusOperations: Send + Sync {
         req: &dyn GetRebuildRecordInfo,
         ctx: Option<Context>,
     ) -> Result<RebuildHistory, ReplyError>;
+    /// Get the persisted rebuild histories of the volumes
+    async fn get_rebuild_histories(
+        &self,
+        req: &GetRebuildHistories,
+        ctx: Option<Context>,
+    ) -> Result<VolumeRebuildHistories, ReplyError>;
 }
 
 impl TryFrom<nexus::Nexus> for Nexus {
@@ -420,6 +430,104 @@ impl TryFrom<RebuildHistoryRecord> for RebuildRecord {
     }
 }
 
+impl From<&GetRebuildHistories> for nexus::GetRebuildHistoriesRequest {
+    fn from(value: &GetRebuildHistories) -> Self {
+        Self {
+            volume_id: value.volume.as_ref().map(ToString::to_string),
+            node_id: value.node.as_ref().map(ToString::to_string),
+            since: value.since.map(Timestamp::from),
+            until: value.until.map(Timestamp::from),
+            partial: value.partial,
+        }
+    }
+}
+
+impl TryFrom<nexus::GetRebuildHistoriesRequest> for GetRebuildHistories {
+    type Error = ReplyError;
+    fn try_from(value: nexus::GetRebuildHistoriesRequest) -> Result<Self, Self::Error> {
+        let time = |time: Option<Timestamp>, arg_name: &str| match time {
+            None => Ok(None),
+            Some(time) => std::time::SystemTime::try_from(time)
+                .map(Some)
+                .map_err(|error| {
+                    ReplyError::invalid_argument(ResourceKind::Volume, arg_name, error.to_string())
+                }),
+        };
+        Ok(Self {
+            volume: match value.volume_id {
+                Some(volume_id) => Some(VolumeId::try_from(StringValue(Some(volume_id)))?),
+                None => None,
+            },
+            node: value.node_id.map(NodeId::from),
+            since: time(value.since, "since")?,
+            until: time(value.until, "until")?,
+            partial: value.partial,
+        })
+    }
+}
+
+impl TryFrom<VolumeRebuildHistories> for nexus::VolumeRebuildHistories {
+    type Error = ReplyError;
+    fn try_from(value: VolumeRebuildHistories) -> Result<Self, Self::Error> {
+        let histories = value
+            .into_inner()
+            .into_iter()
+            .map(|history| {
+                Ok(nexus::VolumeRebuildHistory {
+                    volume_id: history.volume_id.to_string(),
+                    records: history
+                        .records
+                        .into_iter()
+                        .map(|record| {
+                            Ok(nexus::VolumeRebuildRecord {
+                                nexus_id: record.nexus.to_string(),
+                                node_id: record.node.to_string(),
+                                record: Some(record.record.try_into()?),
+                            })
+                        })
+                        .collect::<Result<Vec<_>, ReplyError>>()?,
+                })
+            })
+            .collect::<Result<Vec<_>, ReplyError>>()?;
+        Ok(Self { histories })
+    }
+}
+
+impl TryFrom<nexus::VolumeRebuildHistories> for VolumeRebuildHistories {
+    type Error = ReplyError;
+    fn try_from(value: nexus::VolumeRebuildHistories) -> Result<Self, Self::Error> {
+        let histories = value
+            .histories
+            .into_iter()
+            .map(|history| {
+                Ok(VolumeRebuildHistory {
+                    volume_id: VolumeId::try_from(StringValue(Some(history.volume_id)))?,
+                    records: history
+                        .records
+                        .into_iter()
+                        .map(|record| {
+                            Ok(VolumeRebuildRecord {
+                                nexus: NexusId::try_from(StringValue(Some(record.nexus_id)))?,
+                                node: record.node_id.into(),
+                                record: match record.record {
+                                    Some(record) => record.try_into()?,
+                                    None => {
+                                        return Err(ReplyError::missing_argument(
+                                            ResourceKind::Nexus,
+                                            "record",
+                                        ))
+                                    }
+                                },
+                            })
+                        })
+                        .collect::<Result<Vec<_>, ReplyError>>()?,
+                })
+            })
+            .collect::<Result<Vec<_>, ReplyError>>()?;
+        Ok(VolumeRebuildHistories(histories))
+    }
+}
+
 impl From<GrpcRebuildState> for RebuildJobState {
     fn from(grpc_rebuild_state: GrpcRebuildState) -> Self {
         match grpc_rebuild_state {
@@ -874,6 +982,7 @@ impl From<&dyn CreateNexusInfo> for CreateNexus {
             managed: data.managed(),
             owner: data.owner(),
             config: data.config(),
+            qos: None,
         }
     }
 }
@@ -3,14 +3,19 @@ use crate::{
     context::{Client, Context, TracedChannel},
     operations::pool::traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
     pool::{
-        create_pool_reply, get_pools_reply, get_pools_request, pool_grpc_client::PoolGrpcClient,
-        GetPoolsRequest,
+        cordon_pool_reply, create_pool_reply, evacuate_pool_reply, get_pool_rebalance_reply,
+        get_pools_reply, get_pools_request, pool_grpc_client::PoolGrpcClient, uncordon_pool_reply,
+        CordonPoolRequest, EvacuatePoolRequest, GetPoolRebalanceRequest, GetPoolsRequest,
+        UncordonPoolRequest,
     },
 };
 use std::{convert::TryFrom, ops::Deref};
 use stor_port::{
-    transport_api::{v0::Pools, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{Filter, MessageIdVs, Pool},
+    transport_api::{
+        v0::{Pools, ReplicaMoves},
+        ReplyError, ResourceKind, TimeoutOptions,
+    },
+    types::v0::transport::{Filter, GetPoolRebalance, MessageIdVs, Pool, PoolId},
 };
 use tonic::transport::Uri;
 
@@ -100,4 +105,68 @@ impl PoolOperations for PoolClient {
             None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
         }
     }
+    async fn get_rebalance(
+        &self,
+        req: &GetPoolRebalance,
+        ctx: Option<Context>,
+    ) -> Result<ReplicaMoves, ReplyError> {
+        let req = GetPoolRebalanceRequest::from(req);
+        let req = self.request(req, ctx, MessageIdVs::GetPoolRebalance);
+        let response = self.client().get_pool_rebalance(req).await?.into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                get_pool_rebalance_reply::Reply::Moves(moves) => Ok(ReplicaMoves::try_from(moves)?),
+                get_pool_rebalance_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
+        }
+    }
+
+    #[tracing::instrument(name = "PoolClient::cordon", level = "debug", skip(self), err)]
+    async fn cordon(&self, id: PoolId, label: String) -> Result<Pool, ReplyError> {
+        let req = CordonPoolRequest {
+            pool_id: id.to_string(),
+            label,
+        };
+        let response = self.client().cordon_pool(req).await?.into_inner();
+        match response.reply {
+            Some(cordon_pool_reply) => match cordon_pool_reply {
+                cordon_pool_reply::Reply::Pool(pool) => Ok(Pool::try_from(pool)?),
+                cordon_pool_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
+        }
+    }
+
+    #[tracing::instrument(name = "PoolClient::uncordon", level = "debug", skip(self), err)]
+    async fn uncordon(&self, id: PoolId, label: String) -> Result<Pool, ReplyError> {
+        let req = UncordonPoolRequest {
+            pool_id: id.to_string(),
+            label,
+        };
+        let response = self.client().uncordon_pool(req).await?.into_inner();
+        match response.reply {
+            Some(uncordon_pool_reply) => match uncordon_pool_reply {
+                uncordon_pool_reply::Reply::Pool(pool) => Ok(Pool::try_from(pool)?),
+                uncordon_pool_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
+        }
+    }
+
+    #[tracing::instrument(name = "PoolClient::evacuate", level = "debug", skip(self), err)]
+    async fn evacuate(&self, id: PoolId, label: String) -> Result<Pool, ReplyError> {
+        let req = EvacuatePoolRequest {
+            pool_id: id.to_string(),
+            label,
+        };
+        let response = self.client().evacuate_pool(req).await?.into_inner();
+        match response.reply {
+            Some(evacuate_pool_reply) => match evacuate_pool_reply {
+                evacuate_pool_reply::Reply::Pool(pool) => Ok(Pool::try_from(pool)?),
+                evacuate_pool_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
+        }
+    }
 }
@@ -105,8 +105,11 @@ mod test {
         };
         use std::time::Duration;
         use stor_port::{
-            transport_api::{v0::Pools, ReplyError},
-            types::v0::transport::{Filter, Pool},
+            transport_api::{
+                v0::{Pools, ReplicaMoves},
+                ReplyError,
+            },
+            types::v0::transport::{Filter, GetPoolRebalance, Pool, PoolId},
         };
 
         pub(super) struct Server {}
@@ -136,6 +139,22 @@ mod test {
                 tester.complete();
                 Ok(Pools(vec![]))
             }
+            async fn get_rebalance(
+                &self,
+                _req: &GetPoolRebalance,
+                _ctx: Option<Context>,
+            ) -> Result<ReplicaMoves, ReplyError> {
+                todo!()
+            }
+            async fn cordon(&self, _id: PoolId, _label: String) -> Result<Pool, ReplyError> {
+                todo!()
+            }
+            async fn uncordon(&self, _id: PoolId, _label: String) -> Result<Pool, ReplyError> {
+                todo!()
+            }
+            async fn evacuate(&self, _id: PoolId, _label: String) -> Result<Pool, ReplyError> {
+                todo!()
+            }
         }
     }
 }
@@ -2,13 +2,17 @@ use crate::{
     operations::pool::traits::PoolOperations,
     pool,
     pool::{
-        create_pool_reply, get_pools_reply,
+        cordon_pool_reply, create_pool_reply, evacuate_pool_reply, get_pool_rebalance_reply,
+        get_pools_reply,
         pool_grpc_server::{PoolGrpc, PoolGrpcServer},
-        CreatePoolReply, CreatePoolRequest, DestroyPoolReply, DestroyPoolRequest, GetPoolsReply,
-        GetPoolsRequest,
+        uncordon_pool_reply, CordonPoolReply, CordonPoolRequest, CreatePoolReply,
+        CreatePoolRequest, DestroyPoolReply, DestroyPoolRequest, EvacuatePoolReply,
+        EvacuatePoolRequest, GetPoolRebalanceReply, GetPoolRebalanceRequest, GetPoolsReply,
+        GetPoolsRequest, UncordonPoolReply, UncordonPoolRequest,
     },
 };
 use std::sync::Arc;
+use stor_port::types::v0::transport::GetPoolRebalance;
 use tonic::{Request, Response};
 
 /// gRPC Pool Server
@@ -74,4 +78,61 @@ impl PoolGrpc for PoolServer {
             })),
         }
     }
+    async fn get_pool_rebalance(
+        &self,
+        request: Request<GetPoolRebalanceRequest>,
+    ) -> Result<tonic::Response<GetPoolRebalanceReply>, tonic::Status> {
+        let req = GetPoolRebalance::from(request.into_inner());
+        match self.service.get_rebalance(&req, None).await {
+            Ok(moves) => Ok(Response::new(GetPoolRebalanceReply {
+                reply: Some(get_pool_rebalance_reply::Reply::Moves(moves.into())),
+            })),
+            Err(err) => Ok(Response::new(GetPoolRebalanceReply {
+                reply: Some(get_pool_rebalance_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn cordon_pool(
+        &self,
+        request: Request<CordonPoolRequest>,
+    ) -> Result<tonic::Response<CordonPoolReply>, tonic::Status> {
+        let req: CordonPoolRequest = request.into_inner();
+        match self.service.cordon(req.pool_id.into(), req.label).await {
+            Ok(pool) => Ok(Response::new(CordonPoolReply {
+                reply: Some(cordon_pool_reply::Reply::Pool(pool.into())),
+            })),
+            Err(err) => Ok(Response::new(CordonPoolReply {
+                reply: Some(cordon_pool_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn uncordon_pool(
+        &self,
+        request: Request<UncordonPoolRequest>,
+    ) -> Result<tonic::Response<UncordonPoolReply>, tonic::Status> {
+        let req: UncordonPoolRequest = request.into_inner();
+        match self.service.uncordon(req.pool_id.into(), req.label).await {
+            Ok(pool) => Ok(Response::new(UncordonPoolReply {
+                reply: Some(uncordon_pool_reply::Reply::Pool(pool.into())),
+            })),
+            Err(err) => Ok(Response::new(UncordonPoolReply {
+                reply: Some(uncordon_pool_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn evacuate_pool(
+        &self,
+        request: Request<EvacuatePoolRequest>,
+    ) -> Result<tonic::Response<EvacuatePoolReply>, tonic::Status> {
+        let req: EvacuatePoolRequest = request.into_inner();
+        match self.service.evacuate(req.pool_id.into(), req.label).await {
+            Ok(pool) => Ok(Response::new(EvacuatePoolReply {
+                reply: Some(evacuate_pool_reply::Reply::Pool(pool.into())),
+            })),
+            Err(err) => Ok(Response::new(EvacuatePoolReply {
+                reply: Some(evacuate_pool_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
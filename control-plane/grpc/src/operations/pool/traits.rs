@@ -1,18 +1,22 @@
 use crate::{
     common,
     context::Context,
+    misc::traits::StringValue,
     pool,
     pool::{get_pools_request, CreatePoolRequest, DestroyPoolRequest},
 };
 use std::convert::TryFrom;
 use stor_port::{
-    transport_api::{v0::Pools, ReplyError, ResourceKind},
+    transport_api::{
+        v0::{Pools, ReplicaMoves},
+        ReplyError, ResourceKind,
+    },
     types::v0::{
-        store::pool::{PoolLabel, PoolSpec, PoolSpecStatus},
+        store::pool::{PoolEvacuation, PoolEvacuationStatus, PoolLabel, PoolSpec, PoolSpecStatus},
         transport,
         transport::{
-            CreatePool, CtrlPoolState, DestroyPool, Filter, NodeId, Pool, PoolDeviceUri, PoolId,
-            PoolState,
+            CreatePool, CtrlPoolState, DestroyPool, Filter, GetPoolRebalance, NodeId, Pool,
+            PoolDeviceUri, PoolId, PoolState, ReplicaId, ReplicaMove, VolumeId,
         },
     },
     IntoOption,
@@ -35,6 +39,19 @@ pub trait PoolOperations: Send + Sync {
     ) -> Result<(), ReplyError>;
     /// Get pools based on the filters
     async fn get(&self, filter: Filter, ctx: Option<Context>) -> Result<Pools, ReplyError>;
+    /// Get the replica moves which the pool rebalancer would make
+    async fn get_rebalance(
+        &self,
+        req: &GetPoolRebalance,
+        ctx: Option<Context>,
+    ) -> Result<ReplicaMoves, ReplyError>;
+    /// Cordon the pool with the given ID and associate the label with the cordoned pool.
+    async fn cordon(&self, id: PoolId, label: String) -> Result<Pool, ReplyError>;
+    /// Uncordon the pool with the given ID by removing the associated label.
+    async fn uncordon(&self, id: PoolId, label: String) -> Result<Pool, ReplyError>;
+    /// Evacuate the pool with the given ID, moving its volume replicas onto other pools, and
+    /// associate the label with the cordoned pool.
+    async fn evacuate(&self, id: PoolId, label: String) -> Result<Pool, ReplyError>;
 }
 
 impl TryFrom<pool::PoolDefinition> for PoolSpec {
@@ -80,6 +97,25 @@ impl TryFrom<pool::PoolDefinition> for PoolSpec {
             },
             sequencer: Default::default(),
             operation: None,
+            cordon_labels: pool_spec.cordon_labels,
+            evacuation: match pool_spec.evacuation {
+                Some(evacuation) => Some(PoolEvacuation {
+                    label: evacuation.label,
+                    status: match pool::PoolEvacuationStatus::try_from(evacuation.status) {
+                        Ok(status) => status.into(),
+                        Err(error) => {
+                            return Err(ReplyError::invalid_argument(
+                                ResourceKind::Pool,
+                                "pool.definition.spec.evacuation.status",
+                                error,
+                            ))
+                        }
+                    },
+                    remaining_replicas: evacuation.remaining_replicas,
+                    blocked_replicas: evacuation.blocked_replicas,
+                }),
+                None => None,
+            },
         })
     }
 }
@@ -145,6 +181,13 @@ impl From<PoolSpec> for pool::PoolDefinition {
                 labels: pool_spec
                     .labels
                     .map(|labels| crate::common::StringMapValue { value: labels }),
+                cordon_labels: pool_spec.cordon_labels,
+                evacuation: pool_spec.evacuation.map(|evacuation| pool::PoolEvacuation {
+                    label: evacuation.label,
+                    status: pool::PoolEvacuationStatus::from(evacuation.status) as i32,
+                    remaining_replicas: evacuation.remaining_replicas,
+                    blocked_replicas: evacuation.blocked_replicas,
+                }),
             }),
             metadata: Some(pool::Metadata {
                 uuid: None,
@@ -219,6 +262,58 @@ impl From<get_pools_request::Filter> for Filter {
     }
 }
 
+impl From<&GetPoolRebalance> for pool::GetPoolRebalanceRequest {
+    fn from(_: &GetPoolRebalance) -> Self {
+        Self {}
+    }
+}
+
+impl From<pool::GetPoolRebalanceRequest> for GetPoolRebalance {
+    fn from(_: pool::GetPoolRebalanceRequest) -> Self {
+        Self {}
+    }
+}
+
+impl From<ReplicaMoves> for pool::ReplicaMoves {
+    fn from(moves: ReplicaMoves) -> Self {
+        pool::ReplicaMoves {
+            moves: moves
+                .into_inner()
+                .into_iter()
+                .map(|replica_move| pool::ReplicaMove {
+                    volume_id: replica_move.volume.to_string(),
+                    replica_id: replica_move.replica.to_string(),
+                    source_node_id: replica_move.source_node.to_string(),
+                    source_pool_id: replica_move.source_pool.to_string(),
+                    target_node_id: replica_move.target_node.to_string(),
+                    target_pool_id: replica_move.target_pool.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<pool::ReplicaMoves> for ReplicaMoves {
+    type Error = ReplyError;
+    fn try_from(grpc_moves: pool::ReplicaMoves) -> Result<Self, Self::Error> {
+        let moves = grpc_moves
+            .moves
+            .into_iter()
+            .map(|replica_move| {
+                Ok(ReplicaMove {
+                    volume: VolumeId::try_from(StringValue(Some(replica_move.volume_id)))?,
+                    replica: ReplicaId::try_from(StringValue(Some(replica_move.replica_id)))?,
+                    source_node: replica_move.source_node_id.into(),
+                    source_pool: replica_move.source_pool_id.into(),
+                    target_node: replica_move.target_node_id.into(),
+                    target_pool: replica_move.target_pool_id.into(),
+                })
+            })
+            .collect::<Result<Vec<_>, ReplyError>>()?;
+        Ok(ReplicaMoves(moves))
+    }
+}
+
 /// CreatePoolInfo trait for the pool creation to be implemented by entities which want to avail
 /// this operation
 pub trait CreatePoolInfo: Send + Sync + std::fmt::Debug {
@@ -364,6 +459,24 @@ impl From<transport::PoolStatus> for pool::PoolStatus {
     }
 }
 
+impl From<pool::PoolEvacuationStatus> for PoolEvacuationStatus {
+    fn from(src: pool::PoolEvacuationStatus) -> Self {
+        match src {
+            pool::PoolEvacuationStatus::Evacuating => Self::Evacuating,
+            pool::PoolEvacuationStatus::Evacuated => Self::Evacuated,
+        }
+    }
+}
+
+impl From<PoolEvacuationStatus> for pool::PoolEvacuationStatus {
+    fn from(src: PoolEvacuationStatus) -> Self {
+        match src {
+            PoolEvacuationStatus::Evacuating => Self::Evacuating,
+            PoolEvacuationStatus::Evacuated => Self::Evacuated,
+        }
+    }
+}
+
 impl From<common::SpecStatus> for PoolSpecStatus {
     fn from(src: common::SpecStatus) -> Self {
         match src {
@@ -1,12 +1,20 @@
 use crate::{
     context::{Client, Context, TracedChannel},
-    operations::registry::traits::{GetSpecsInfo, GetStatesInfo, RegistryOperations},
-    registry::{get_specs_reply, get_states_reply, registry_grpc_client::RegistryGrpcClient},
+    operations::registry::{
+        traits::{
+            BackupSpecsInfo, GetSpecsInfo, GetStatesInfo, RegistryOperations, RestoreSpecsInfo,
+        },
+        MAX_MESSAGE_SIZE,
+    },
+    registry::{
+        backup_specs_reply, get_specs_reply, get_states_reply,
+        registry_grpc_client::RegistryGrpcClient, restore_specs_reply, RestoreSpecsRequest,
+    },
 };
 use std::{convert::TryFrom, ops::Deref};
 use stor_port::{
-    transport_api::{ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{MessageIdVs, Specs, States},
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind, TimeoutOptions},
+    types::v0::transport::{MessageIdVs, Specs, SpecsBackup, SpecsRestored, States},
 };
 use tonic::transport::Uri;
 
@@ -25,7 +33,10 @@ impl Deref for RegistryClient {
 impl RegistryClient {
     /// creates a new base tonic endpoint with the timeout options and the address
     pub async fn new<O: Into<Option<TimeoutOptions>>>(addr: Uri, opts: O) -> Self {
-        let client = Client::new(addr, opts, RegistryGrpcClient::new).await;
+        let client = Client::new(addr, opts, |channel| {
+            RegistryGrpcClient::new(channel).max_decoding_message_size(MAX_MESSAGE_SIZE)
+        })
+        .await;
         Self { inner: client }
     }
 }
@@ -64,4 +75,40 @@ impl RegistryOperations for RegistryClient {
             None => Err(ReplyError::invalid_response(ResourceKind::State)),
         }
     }
+
+    async fn backup_specs(
+        &self,
+        request: &dyn BackupSpecsInfo,
+        ctx: Option<Context>,
+    ) -> Result<SpecsBackup, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::BackupSpecs);
+        let response = self.client().backup_specs(req).await?.into_inner();
+        match response.reply {
+            Some(backup_specs_reply) => match backup_specs_reply {
+                backup_specs_reply::Reply::Backup(backup) => serde_json::from_slice(&backup)
+                    .map_err(|error| {
+                        ReplyError::serde_error(ResourceKind::Spec, ReplyErrorKind::Aborted, error)
+                    }),
+                backup_specs_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Spec)),
+        }
+    }
+
+    async fn restore_specs(
+        &self,
+        request: &dyn RestoreSpecsInfo,
+        ctx: Option<Context>,
+    ) -> Result<SpecsRestored, ReplyError> {
+        let req = RestoreSpecsRequest::try_from(request)?;
+        let req = self.request(req, ctx, MessageIdVs::RestoreSpecs);
+        let response = self.client().restore_specs(req).await?.into_inner();
+        match response.reply {
+            Some(restore_specs_reply) => match restore_specs_reply {
+                restore_specs_reply::Reply::Restored(restored) => Ok(restored.into()),
+                restore_specs_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Spec)),
+        }
+    }
 }
@@ -6,3 +6,6 @@ pub mod server;
 
 /// Registry traits for the transport
 pub mod traits;
+
+/// Maximum size of the registry messages, as these may carry a backup of all the specs.
+pub const MAX_MESSAGE_SIZE: usize = 256 * 1024 * 1024;
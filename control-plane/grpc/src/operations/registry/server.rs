@@ -1,12 +1,15 @@
 use crate::{
-    operations::registry::traits::RegistryOperations,
+    misc::traits::ValidateRequestTypes,
+    operations::registry::{traits::RegistryOperations, MAX_MESSAGE_SIZE},
     registry::{
-        get_specs_reply, get_states_reply,
+        backup_specs_reply, get_specs_reply, get_states_reply,
         registry_grpc_server::{RegistryGrpc, RegistryGrpcServer},
-        GetSpecsReply, GetSpecsRequest, GetStatesReply, GetStatesRequest,
+        restore_specs_reply, BackupSpecsReply, BackupSpecsRequest, GetSpecsReply, GetSpecsRequest,
+        GetStatesReply, GetStatesRequest, RestoreSpecsReply, RestoreSpecsRequest,
     },
 };
 use std::sync::Arc;
+use stor_port::transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
 use tonic::Response;
 
 /// gRPC Registry Server
@@ -23,7 +26,7 @@ impl RegistryServer {
     }
     /// converts the registry server to its corresponding grpc server type
     pub fn into_grpc_server(self) -> RegistryGrpcServer<Self> {
-        RegistryGrpcServer::new(self)
+        RegistryGrpcServer::new(self).max_decoding_message_size(MAX_MESSAGE_SIZE)
     }
 }
 
@@ -57,4 +60,41 @@ impl RegistryGrpc for RegistryServer {
             })),
         }
     }
+    async fn backup_specs(
+        &self,
+        request: tonic::Request<BackupSpecsRequest>,
+    ) -> Result<tonic::Response<BackupSpecsReply>, tonic::Status> {
+        let req: BackupSpecsRequest = request.into_inner();
+        let result = match self.service.backup_specs(&req, None).await {
+            Ok(backup) => serde_json::to_vec(&backup).map_err(|error| {
+                ReplyError::serde_error(ResourceKind::Spec, ReplyErrorKind::Internal, error)
+            }),
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(backup) => Ok(Response::new(BackupSpecsReply {
+                reply: Some(backup_specs_reply::Reply::Backup(backup)),
+            })),
+            Err(err) => Ok(Response::new(BackupSpecsReply {
+                reply: Some(backup_specs_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn restore_specs(
+        &self,
+        request: tonic::Request<RestoreSpecsRequest>,
+    ) -> Result<tonic::Response<RestoreSpecsReply>, tonic::Status> {
+        let result = match request.into_inner().validated() {
+            Ok(req) => self.service.restore_specs(&req, None).await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(restored) => Ok(Response::new(RestoreSpecsReply {
+                reply: Some(restore_specs_reply::Reply::Restored(restored.into())),
+            })),
+            Err(err) => Ok(Response::new(RestoreSpecsReply {
+                reply: Some(restore_specs_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
@@ -1,11 +1,12 @@
 use crate::{
     context::Context,
+    misc::traits::ValidateRequestTypes,
     registry,
-    registry::{GetSpecsRequest, GetStatesRequest},
+    registry::{BackupSpecsRequest, GetSpecsRequest, GetStatesRequest, RestoreSpecsRequest},
 };
 use std::convert::TryFrom;
 use stor_port::{
-    transport_api::ReplyError,
+    transport_api::{ReplyError, ResourceKind},
     types::v0::{
         store,
         store::{
@@ -15,7 +16,9 @@ use stor_port::{
             volume::{AffinityGroupSpec, VolumeSpec},
         },
         transport,
-        transport::{GetSpecs, GetStates, Specs},
+        transport::{
+            BackupSpecs, GetSpecs, GetStates, RestoreSpecs, Specs, SpecsBackup, SpecsRestored,
+        },
     },
 };
 
@@ -34,6 +37,18 @@ pub trait RegistryOperations: Send + Sync {
         get_spec: &dyn GetStatesInfo,
         ctx: Option<Context>,
     ) -> Result<transport::States, ReplyError>;
+    /// Backup all the persisted specs
+    async fn backup_specs(
+        &self,
+        backup_specs: &dyn BackupSpecsInfo,
+        ctx: Option<Context>,
+    ) -> Result<SpecsBackup, ReplyError>;
+    /// Restore all the persisted specs from a backup
+    async fn restore_specs(
+        &self,
+        restore_specs: &dyn RestoreSpecsInfo,
+        ctx: Option<Context>,
+    ) -> Result<SpecsRestored, ReplyError>;
 }
 
 /// GetSpecsInfo trait for the get_specs operation
@@ -50,6 +65,98 @@ impl GetStatesInfo for GetStates {}
 
 impl GetStatesInfo for GetStatesRequest {}
 
+/// BackupSpecsInfo trait for the backup_specs operation
+pub trait BackupSpecsInfo: Send + Sync {}
+
+impl BackupSpecsInfo for BackupSpecs {}
+
+impl BackupSpecsInfo for BackupSpecsRequest {}
+
+/// RestoreSpecsInfo trait for the restore_specs operation
+pub trait RestoreSpecsInfo: Send + Sync {
+    /// The backup to restore the specs from
+    fn backup(&self) -> &SpecsBackup;
+}
+
+impl RestoreSpecsInfo for RestoreSpecs {
+    fn backup(&self) -> &SpecsBackup {
+        &self.backup
+    }
+}
+
+/// Intermediate structure that validates the conversion to RestoreSpecsRequest type
+#[derive(Debug)]
+pub struct ValidatedRestoreSpecsRequest {
+    backup: SpecsBackup,
+}
+
+impl RestoreSpecsInfo for ValidatedRestoreSpecsRequest {
+    fn backup(&self) -> &SpecsBackup {
+        &self.backup
+    }
+}
+
+impl ValidateRequestTypes for RestoreSpecsRequest {
+    type Validated = ValidatedRestoreSpecsRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedRestoreSpecsRequest {
+            backup: serde_json::from_slice(&self.backup).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::Spec, "backup", error)
+            })?,
+        })
+    }
+}
+
+impl TryFrom<&dyn RestoreSpecsInfo> for RestoreSpecsRequest {
+    type Error = ReplyError;
+
+    fn try_from(value: &dyn RestoreSpecsInfo) -> Result<Self, Self::Error> {
+        Ok(Self {
+            backup: serde_json::to_vec(value.backup()).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::Spec, "backup", error)
+            })?,
+        })
+    }
+}
+
+impl From<&dyn RestoreSpecsInfo> for RestoreSpecs {
+    fn from(value: &dyn RestoreSpecsInfo) -> Self {
+        Self {
+            backup: value.backup().clone(),
+        }
+    }
+}
+
+impl From<&dyn BackupSpecsInfo> for BackupSpecsRequest {
+    fn from(_: &dyn BackupSpecsInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<&dyn BackupSpecsInfo> for BackupSpecs {
+    fn from(_: &dyn BackupSpecsInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<registry::SpecsRestored> for SpecsRestored {
+    fn from(value: registry::SpecsRestored) -> Self {
+        Self {
+            restored: value.restored,
+            skipped: value.skipped,
+        }
+    }
+}
+
+impl From<SpecsRestored> for registry::SpecsRestored {
+    fn from(value: SpecsRestored) -> Self {
+        Self {
+            restored: value.restored,
+            skipped: value.skipped,
+        }
+    }
+}
+
 impl From<&dyn GetSpecsInfo> for GetSpecsRequest {
     fn from(_: &dyn GetSpecsInfo) -> Self {
         Self {}
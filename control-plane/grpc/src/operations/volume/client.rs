@@ -4,28 +4,45 @@ use crate::{
     operations::{
         volume::{
             traits::{
-                CreateSnapshotVolumeInfo, CreateVolumeInfo, CreateVolumeSnapshotInfo,
-                DestroyShutdownTargetsInfo, DestroyVolumeInfo, PublishVolumeInfo,
-                RepublishVolumeInfo, ResizeVolumeInfo, SetVolumePropertyInfo, SetVolumeReplicaInfo,
-                ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
-                VolumeSnapshot, VolumeSnapshots,
+                CancelVolumeReplicaMoveInfo, CloneVolumeInfo, CreateSnapshotExportInfo,
+                CreateSnapshotGroupInfo, CreateSnapshotScheduleInfo, CreateSnapshotVolumeInfo,
+                CreateVolumeInfo, CreateVolumeSnapshotInfo, DestroyShutdownTargetsInfo,
+                DestroySnapshotExportInfo, DestroySnapshotGroupInfo, DestroySnapshotScheduleInfo,
+                DestroyVolumeInfo, MoveVolumeReplicaInfo, PublishVolumeInfo, RepublishVolumeInfo,
+                ResizeVolumeInfo, RestoreSnapshotExportInfo, SetVolumePropertyInfo,
+                SetVolumeReplicaInfo, ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo,
+                VolumeOperations, VolumeSnapshot, VolumeSnapshots,
             },
-            traits_snapshots::DestroyVolumeSnapshotInfo,
+            traits_snapshots::{DestroyVolumeSnapshotInfo, RestoreVolumeSnapshotInfo},
         },
         Pagination,
     },
     volume::{
-        create_snapshot_reply, create_snapshot_volume_reply, create_volume_reply,
-        get_snapshots_reply, get_snapshots_request, get_volumes_reply, get_volumes_request,
-        publish_volume_reply, republish_volume_reply, resize_volume_reply,
+        cancel_volume_replica_move_reply, clone_volume_reply, create_snapshot_export_reply,
+        create_snapshot_group_reply, create_snapshot_reply, create_snapshot_schedule_reply,
+        create_snapshot_volume_reply, create_volume_reply, get_snapshot_exports_reply,
+        get_snapshot_groups_reply, get_snapshot_schedules_reply, get_snapshots_reply,
+        get_snapshots_request, get_volume_capacity_reply, get_volumes_reply, get_volumes_request,
+        move_volume_replica_reply, publish_volume_reply, republish_volume_reply,
+        resize_volume_reply, restore_snapshot_export_reply, restore_snapshot_reply,
         set_volume_property_reply, set_volume_replica_reply, share_volume_reply,
-        unpublish_volume_reply, volume_grpc_client::VolumeGrpcClient, GetSnapshotsRequest,
+        unpublish_volume_reply, volume_grpc_client::VolumeGrpcClient, GetSnapshotExportsRequest,
+        GetSnapshotGroupsRequest, GetSnapshotSchedulesRequest, GetSnapshotsRequest,
         GetVolumesRequest, ProbeRequest,
     },
 };
 use stor_port::{
-    transport_api::{v0::Volumes, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{Filter, MessageIdVs, Volume},
+    transport_api::{
+        v0::{SnapshotExports, SnapshotGroups, SnapshotSchedules, Volumes},
+        ReplyError, ResourceKind, TimeoutOptions,
+    },
+    types::v0::{
+        store::snapshots::{export::SnapshotExportSpec, schedule::SnapshotScheduleSpec},
+        transport::{
+            Filter, GetVolumeCapacity, MessageIdVs, SnapshotExportId, SnapshotGroup,
+            SnapshotGroupId, SnapshotScheduleId, Volume, VolumeCapacity,
+        },
+    },
 };
 
 use std::{convert::TryFrom, ops::Deref};
@@ -250,6 +267,68 @@ impl VolumeOperations for VolumeClient {
             None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
         }
     }
+
+    #[tracing::instrument(name = "VolumeClient::move_replica", level = "debug", skip(self), err)]
+    async fn move_replica(
+        &self,
+        req: &dyn MoveVolumeReplicaInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(req, ctx, MessageIdVs::MoveVolumeReplica);
+        let response = self.client().move_volume_replica(req).await?.into_inner();
+        match response.reply {
+            Some(move_volume_replica_reply) => match move_volume_replica_reply {
+                move_volume_replica_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                move_volume_replica_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::cancel_replica_move",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn cancel_replica_move(
+        &self,
+        req: &dyn CancelVolumeReplicaMoveInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(req, ctx, MessageIdVs::CancelVolumeReplicaMove);
+        let response = self
+            .client()
+            .cancel_volume_replica_move(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                cancel_volume_replica_move_reply::Reply::Volume(volume) => {
+                    Ok(Volume::try_from(volume)?)
+                }
+                cancel_volume_replica_move_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(name = "VolumeClient::get_capacity", level = "debug", skip(self), err)]
+    async fn get_capacity(
+        &self,
+        req: &GetVolumeCapacity,
+        ctx: Option<Context>,
+    ) -> Result<VolumeCapacity, ReplyError> {
+        let req = self.request(req, ctx, MessageIdVs::GetVolumeCapacity);
+        let response = self.client().get_volume_capacity(req).await?.into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                get_volume_capacity_reply::Reply::Capacity(capacity) => Ok(capacity.into()),
+                get_volume_capacity_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
     #[tracing::instrument(name = "VolumeClient::probe", level = "debug", skip(self))]
     async fn probe(&self, _ctx: Option<Context>) -> Result<bool, ReplyError> {
         match self.client().probe(ProbeRequest {}).await {
@@ -306,6 +385,23 @@ impl VolumeOperations for VolumeClient {
         }
     }
 
+    #[tracing::instrument(name = "VolumeClient::restore_snapshot", level = "debug", skip(self))]
+    async fn restore_snapshot(
+        &self,
+        request: &dyn RestoreVolumeSnapshotInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::RestoreVolumeSnapshot);
+        let response = self.client().restore_snapshot(req).await?.into_inner();
+        match response.reply {
+            Some(restore_snapshot_reply) => match restore_snapshot_reply {
+                restore_snapshot_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                restore_snapshot_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
     #[tracing::instrument(name = "VolumeClient::get_snapshots", level = "debug", skip(self))]
     async fn get_snapshots(
         &self,
@@ -385,4 +481,283 @@ impl VolumeOperations for VolumeClient {
             None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
         }
     }
+
+    #[tracing::instrument(name = "VolumeClient::clone_volume", level = "debug", skip(self), err)]
+    async fn clone_volume(
+        &self,
+        request: &dyn CloneVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::CloneVolume);
+        let response = self.client().clone_volume(req).await?.into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                clone_volume_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                clone_volume_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::create_snapshot_schedule",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn create_snapshot_schedule(
+        &self,
+        request: &dyn CreateSnapshotScheduleInfo,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotScheduleSpec, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::CreateSnapshotSchedule);
+        let response = self
+            .client()
+            .create_snapshot_schedule(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                create_snapshot_schedule_reply::Reply::Schedule(schedule) => {
+                    Ok(SnapshotScheduleSpec::try_from(schedule)?)
+                }
+                create_snapshot_schedule_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::SnapshotSchedule)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::destroy_snapshot_schedule",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn destroy_snapshot_schedule(
+        &self,
+        request: &dyn DestroySnapshotScheduleInfo,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::DestroySnapshotSchedule);
+        let response = self
+            .client()
+            .destroy_snapshot_schedule(req)
+            .await?
+            .into_inner();
+        match response.error {
+            None => Ok(()),
+            Some(err) => Err(err.into()),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::get_snapshot_schedules",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn get_snapshot_schedules(
+        &self,
+        id: Option<SnapshotScheduleId>,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotSchedules, ReplyError> {
+        let req = GetSnapshotSchedulesRequest {
+            id: id.map(|id| id.to_string()),
+        };
+        let req = self.request(req, ctx, MessageIdVs::GetSnapshotSchedules);
+        let response = self
+            .client()
+            .get_snapshot_schedules(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                get_snapshot_schedules_reply::Reply::Response(schedules) => {
+                    Ok(SnapshotSchedules::try_from(schedules)?)
+                }
+                get_snapshot_schedules_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::SnapshotSchedule)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::create_snapshot_group",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn create_snapshot_group(
+        &self,
+        request: &dyn CreateSnapshotGroupInfo,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotGroup, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::CreateSnapshotGroup);
+        let response = self.client().create_snapshot_group(req).await?.into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                create_snapshot_group_reply::Reply::Group(group) => {
+                    Ok(SnapshotGroup::try_from(group)?)
+                }
+                create_snapshot_group_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::SnapshotGroup)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::destroy_snapshot_group",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn destroy_snapshot_group(
+        &self,
+        request: &dyn DestroySnapshotGroupInfo,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::DestroySnapshotGroup);
+        let response = self
+            .client()
+            .destroy_snapshot_group(req)
+            .await?
+            .into_inner();
+        match response.error {
+            None => Ok(()),
+            Some(err) => Err(err.into()),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::get_snapshot_groups",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn get_snapshot_groups(
+        &self,
+        id: Option<SnapshotGroupId>,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotGroups, ReplyError> {
+        let req = GetSnapshotGroupsRequest {
+            id: id.map(|id| id.to_string()),
+        };
+        let req = self.request(req, ctx, MessageIdVs::GetSnapshotGroups);
+        let response = self.client().get_snapshot_groups(req).await?.into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                get_snapshot_groups_reply::Reply::Response(groups) => {
+                    Ok(SnapshotGroups::try_from(groups)?)
+                }
+                get_snapshot_groups_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::SnapshotGroup)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::create_snapshot_export",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn create_snapshot_export(
+        &self,
+        request: &dyn CreateSnapshotExportInfo,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotExportSpec, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::CreateSnapshotExport);
+        let response = self
+            .client()
+            .create_snapshot_export(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                create_snapshot_export_reply::Reply::Export(export) => {
+                    Ok(SnapshotExportSpec::try_from(export)?)
+                }
+                create_snapshot_export_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::SnapshotExport)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::destroy_snapshot_export",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn destroy_snapshot_export(
+        &self,
+        request: &dyn DestroySnapshotExportInfo,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::DestroySnapshotExport);
+        let response = self
+            .client()
+            .destroy_snapshot_export(req)
+            .await?
+            .into_inner();
+        match response.error {
+            None => Ok(()),
+            Some(err) => Err(err.into()),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::get_snapshot_exports",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn get_snapshot_exports(
+        &self,
+        id: Option<SnapshotExportId>,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotExports, ReplyError> {
+        let req = GetSnapshotExportsRequest {
+            id: id.map(|id| id.to_string()),
+        };
+        let req = self.request(req, ctx, MessageIdVs::GetSnapshotExports);
+        let response = self.client().get_snapshot_exports(req).await?.into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                get_snapshot_exports_reply::Reply::Response(exports) => {
+                    Ok(SnapshotExports::try_from(exports)?)
+                }
+                get_snapshot_exports_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::SnapshotExport)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::restore_snapshot_export",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn restore_snapshot_export(
+        &self,
+        request: &dyn RestoreSnapshotExportInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::RestoreSnapshotExport);
+        let response = self
+            .client()
+            .restore_snapshot_export(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(reply) => match reply {
+                restore_snapshot_export_reply::Reply::Volume(volume) => {
+                    Ok(Volume::try_from(volume)?)
+                }
+                restore_snapshot_export_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
 }
@@ -6,4 +6,7 @@ pub mod server;
 
 /// Volume traits for the transport.
 pub mod traits;
+mod traits_snapshot_exports;
+mod traits_snapshot_groups;
+mod traits_snapshot_schedules;
 mod traits_snapshots;
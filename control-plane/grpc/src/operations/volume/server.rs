@@ -2,24 +2,42 @@ use crate::{
     misc::traits::ValidateRequestTypes,
     operations::{volume::traits::VolumeOperations, Event, Pagination},
     volume::{
-        create_snapshot_reply, create_snapshot_volume_reply, create_volume_reply,
-        get_snapshots_reply, get_volumes_reply, publish_volume_reply, republish_volume_reply,
-        resize_volume_reply, set_volume_property_reply, set_volume_replica_reply,
-        share_volume_reply, unpublish_volume_reply,
+        cancel_volume_replica_move_reply, clone_volume_reply, create_snapshot_export_reply,
+        create_snapshot_group_reply, create_snapshot_reply, create_snapshot_schedule_reply,
+        create_snapshot_volume_reply, create_volume_reply, get_snapshot_exports_reply,
+        get_snapshot_groups_reply, get_snapshot_schedules_reply, get_snapshots_reply,
+        get_volume_capacity_reply, get_volumes_reply, move_volume_replica_reply,
+        publish_volume_reply, republish_volume_reply, resize_volume_reply,
+        restore_snapshot_export_reply, restore_snapshot_reply, set_volume_property_reply,
+        set_volume_replica_reply, share_volume_reply, unpublish_volume_reply,
         volume_grpc_server::{VolumeGrpc, VolumeGrpcServer},
-        CreateSnapshotReply, CreateSnapshotRequest, CreateSnapshotVolumeReply,
-        CreateSnapshotVolumeRequest, CreateVolumeReply, CreateVolumeRequest,
-        DestroyShutdownTargetReply, DestroyShutdownTargetRequest, DestroySnapshotReply,
-        DestroySnapshotRequest, DestroyVolumeReply, DestroyVolumeRequest, GetSnapshotsReply,
-        GetSnapshotsRequest, GetVolumesReply, GetVolumesRequest, ProbeRequest, ProbeResponse,
+        CancelVolumeReplicaMoveReply, CancelVolumeReplicaMoveRequest, CloneVolumeReply,
+        CloneVolumeRequest, CreateSnapshotExportReply, CreateSnapshotExportRequest,
+        CreateSnapshotGroupReply, CreateSnapshotGroupRequest, CreateSnapshotReply,
+        CreateSnapshotRequest, CreateSnapshotScheduleReply, CreateSnapshotScheduleRequest,
+        CreateSnapshotVolumeReply, CreateSnapshotVolumeRequest, CreateVolumeReply,
+        CreateVolumeRequest, DestroyShutdownTargetReply, DestroyShutdownTargetRequest,
+        DestroySnapshotExportReply, DestroySnapshotExportRequest, DestroySnapshotGroupReply,
+        DestroySnapshotGroupRequest, DestroySnapshotReply, DestroySnapshotRequest,
+        DestroySnapshotScheduleReply, DestroySnapshotScheduleRequest, DestroyVolumeReply,
+        DestroyVolumeRequest, GetSnapshotExportsReply, GetSnapshotExportsRequest,
+        GetSnapshotGroupsReply, GetSnapshotGroupsRequest, GetSnapshotSchedulesReply,
+        GetSnapshotSchedulesRequest, GetSnapshotsReply, GetSnapshotsRequest,
+        GetVolumeCapacityReply, GetVolumeCapacityRequest, GetVolumesReply, GetVolumesRequest,
+        MoveVolumeReplicaReply, MoveVolumeReplicaRequest, ProbeRequest, ProbeResponse,
         PublishVolumeReply, PublishVolumeRequest, RepublishVolumeReply, RepublishVolumeRequest,
-        ResizeVolumeReply, ResizeVolumeRequest, SetVolumePropertyReply, SetVolumePropertyRequest,
-        SetVolumeReplicaReply, SetVolumeReplicaRequest, ShareVolumeReply, ShareVolumeRequest,
-        UnpublishVolumeReply, UnpublishVolumeRequest, UnshareVolumeReply, UnshareVolumeRequest,
+        ResizeVolumeReply, ResizeVolumeRequest, RestoreSnapshotExportReply,
+        RestoreSnapshotExportRequest, RestoreSnapshotReply, RestoreSnapshotRequest,
+        SetVolumePropertyReply, SetVolumePropertyRequest, SetVolumeReplicaReply,
+        SetVolumeReplicaRequest, ShareVolumeReply, ShareVolumeRequest, UnpublishVolumeReply,
+        UnpublishVolumeRequest, UnshareVolumeReply, UnshareVolumeRequest,
     },
 };
 use std::{convert::TryFrom, sync::Arc};
-use stor_port::types::v0::transport::Filter;
+use stor_port::{
+    transport_api::{ReplyError, ResourceKind},
+    types::v0::transport::{Filter, GetVolumeCapacity, SnapshotExportId},
+};
 use tonic::{Request, Response, Status};
 
 /// RPC Volume Server
@@ -230,6 +248,50 @@ impl VolumeGrpc for VolumeServer {
             })),
         }
     }
+    async fn move_volume_replica(
+        &self,
+        request: tonic::Request<MoveVolumeReplicaRequest>,
+    ) -> Result<tonic::Response<MoveVolumeReplicaReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.move_replica(&req, None).await {
+            Ok(volume) => Ok(Response::new(MoveVolumeReplicaReply {
+                reply: Some(move_volume_replica_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(MoveVolumeReplicaReply {
+                reply: Some(move_volume_replica_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn cancel_volume_replica_move(
+        &self,
+        request: tonic::Request<CancelVolumeReplicaMoveRequest>,
+    ) -> Result<tonic::Response<CancelVolumeReplicaMoveReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.cancel_replica_move(&req, None).await {
+            Ok(volume) => Ok(Response::new(CancelVolumeReplicaMoveReply {
+                reply: Some(cancel_volume_replica_move_reply::Reply::Volume(
+                    volume.into(),
+                )),
+            })),
+            Err(err) => Ok(Response::new(CancelVolumeReplicaMoveReply {
+                reply: Some(cancel_volume_replica_move_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_volume_capacity(
+        &self,
+        request: tonic::Request<GetVolumeCapacityRequest>,
+    ) -> Result<tonic::Response<GetVolumeCapacityReply>, tonic::Status> {
+        let req = GetVolumeCapacity::try_from(request.into_inner())?;
+        match self.service.get_capacity(&req, None).await {
+            Ok(capacity) => Ok(Response::new(GetVolumeCapacityReply {
+                reply: Some(get_volume_capacity_reply::Reply::Capacity(capacity.into())),
+            })),
+            Err(err) => Ok(Response::new(GetVolumeCapacityReply {
+                reply: Some(get_volume_capacity_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
     async fn probe(
         &self,
         _request: tonic::Request<ProbeRequest>,
@@ -268,6 +330,21 @@ impl VolumeGrpc for VolumeServer {
         }
     }
 
+    async fn restore_snapshot(
+        &self,
+        request: tonic::Request<RestoreSnapshotRequest>,
+    ) -> Result<tonic::Response<RestoreSnapshotReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.restore_snapshot(&req, None).await {
+            Ok(volume) => Ok(Response::new(RestoreSnapshotReply {
+                reply: Some(restore_snapshot_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(RestoreSnapshotReply {
+                reply: Some(restore_snapshot_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
     async fn get_snapshots(
         &self,
         request: tonic::Request<GetSnapshotsRequest>,
@@ -314,4 +391,186 @@ impl VolumeGrpc for VolumeServer {
             })),
         }
     }
+
+    async fn clone_volume(
+        &self,
+        request: Request<CloneVolumeRequest>,
+    ) -> Result<Response<CloneVolumeReply>, Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.clone_volume(&req, None).await {
+            Ok(volume) => Ok(Response::new(CloneVolumeReply {
+                reply: Some(clone_volume_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(CloneVolumeReply {
+                reply: Some(clone_volume_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn create_snapshot_schedule(
+        &self,
+        request: Request<CreateSnapshotScheduleRequest>,
+    ) -> Result<Response<CreateSnapshotScheduleReply>, Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.create_snapshot_schedule(&req, None).await {
+            Ok(schedule) => Ok(Response::new(CreateSnapshotScheduleReply {
+                reply: Some(create_snapshot_schedule_reply::Reply::Schedule(
+                    schedule.into(),
+                )),
+            })),
+            Err(err) => Ok(Response::new(CreateSnapshotScheduleReply {
+                reply: Some(create_snapshot_schedule_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn destroy_snapshot_schedule(
+        &self,
+        request: Request<DestroySnapshotScheduleRequest>,
+    ) -> Result<Response<DestroySnapshotScheduleReply>, Status> {
+        let req = request.into_inner();
+        match self.service.destroy_snapshot_schedule(&req, None).await {
+            Ok(()) => Ok(Response::new(DestroySnapshotScheduleReply { error: None })),
+            Err(err) => Ok(Response::new(DestroySnapshotScheduleReply {
+                error: Some(err.into()),
+            })),
+        }
+    }
+
+    async fn get_snapshot_schedules(
+        &self,
+        request: Request<GetSnapshotSchedulesRequest>,
+    ) -> Result<Response<GetSnapshotSchedulesReply>, Status> {
+        let req = request.into_inner();
+        match self
+            .service
+            .get_snapshot_schedules(req.id.map(Into::into), None)
+            .await
+        {
+            Ok(schedules) => Ok(Response::new(GetSnapshotSchedulesReply {
+                reply: Some(get_snapshot_schedules_reply::Reply::Response(
+                    schedules.into(),
+                )),
+            })),
+            Err(err) => Ok(Response::new(GetSnapshotSchedulesReply {
+                reply: Some(get_snapshot_schedules_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn create_snapshot_group(
+        &self,
+        request: Request<CreateSnapshotGroupRequest>,
+    ) -> Result<Response<CreateSnapshotGroupReply>, Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.create_snapshot_group(&req, None).await {
+            Ok(group) => Ok(Response::new(CreateSnapshotGroupReply {
+                reply: Some(create_snapshot_group_reply::Reply::Group(group.into())),
+            })),
+            Err(err) => Ok(Response::new(CreateSnapshotGroupReply {
+                reply: Some(create_snapshot_group_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn destroy_snapshot_group(
+        &self,
+        request: Request<DestroySnapshotGroupRequest>,
+    ) -> Result<Response<DestroySnapshotGroupReply>, Status> {
+        let req = request.into_inner();
+        match self.service.destroy_snapshot_group(&req, None).await {
+            Ok(()) => Ok(Response::new(DestroySnapshotGroupReply { error: None })),
+            Err(err) => Ok(Response::new(DestroySnapshotGroupReply {
+                error: Some(err.into()),
+            })),
+        }
+    }
+
+    async fn get_snapshot_groups(
+        &self,
+        request: Request<GetSnapshotGroupsRequest>,
+    ) -> Result<Response<GetSnapshotGroupsReply>, Status> {
+        let req = request.into_inner();
+        match self
+            .service
+            .get_snapshot_groups(req.id.map(Into::into), None)
+            .await
+        {
+            Ok(groups) => Ok(Response::new(GetSnapshotGroupsReply {
+                reply: Some(get_snapshot_groups_reply::Reply::Response(groups.into())),
+            })),
+            Err(err) => Ok(Response::new(GetSnapshotGroupsReply {
+                reply: Some(get_snapshot_groups_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn create_snapshot_export(
+        &self,
+        request: Request<CreateSnapshotExportRequest>,
+    ) -> Result<Response<CreateSnapshotExportReply>, Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.create_snapshot_export(&req, None).await {
+            Ok(export) => Ok(Response::new(CreateSnapshotExportReply {
+                reply: Some(create_snapshot_export_reply::Reply::Export(export.into())),
+            })),
+            Err(err) => Ok(Response::new(CreateSnapshotExportReply {
+                reply: Some(create_snapshot_export_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn destroy_snapshot_export(
+        &self,
+        request: Request<DestroySnapshotExportRequest>,
+    ) -> Result<Response<DestroySnapshotExportReply>, Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.destroy_snapshot_export(&req, None).await {
+            Ok(()) => Ok(Response::new(DestroySnapshotExportReply { error: None })),
+            Err(err) => Ok(Response::new(DestroySnapshotExportReply {
+                error: Some(err.into()),
+            })),
+        }
+    }
+
+    async fn get_snapshot_exports(
+        &self,
+        request: Request<GetSnapshotExportsRequest>,
+    ) -> Result<Response<GetSnapshotExportsReply>, Status> {
+        let req = request.into_inner();
+        let id = match req.id.map(SnapshotExportId::try_from).transpose() {
+            Ok(id) => id,
+            Err(error) => {
+                return Ok(Response::new(GetSnapshotExportsReply {
+                    reply: Some(get_snapshot_exports_reply::Reply::Error(
+                        ReplyError::invalid_argument(ResourceKind::SnapshotExport, "id", error)
+                            .into(),
+                    )),
+                }))
+            }
+        };
+        match self.service.get_snapshot_exports(id, None).await {
+            Ok(exports) => Ok(Response::new(GetSnapshotExportsReply {
+                reply: Some(get_snapshot_exports_reply::Reply::Response(exports.into())),
+            })),
+            Err(err) => Ok(Response::new(GetSnapshotExportsReply {
+                reply: Some(get_snapshot_exports_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn restore_snapshot_export(
+        &self,
+        request: Request<RestoreSnapshotExportRequest>,
+    ) -> Result<Response<RestoreSnapshotExportReply>, Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.restore_snapshot_export(&req, None).await {
+            Ok(volume) => Ok(Response::new(RestoreSnapshotExportReply {
+                reply: Some(restore_snapshot_export_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(RestoreSnapshotExportReply {
+                reply: Some(restore_snapshot_export_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
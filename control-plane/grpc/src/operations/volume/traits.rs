@@ -1,4 +1,7 @@
-pub use super::traits_snapshots::*;
+pub use super::{
+    traits_snapshot_exports::*, traits_snapshot_groups::*, traits_snapshot_schedules::*,
+    traits_snapshots::*,
+};
 use crate::{
     common,
     context::Context,
@@ -7,29 +10,40 @@ use crate::{
     operations::{Event, Pagination},
     replica, volume,
     volume::{
-        get_volumes_request, CreateSnapshotVolumeRequest, CreateVolumeRequest,
-        DestroyShutdownTargetRequest, DestroyVolumeRequest, PublishVolumeRequest,
-        RegisteredTargets, RepublishVolumeRequest, ResizeVolumeRequest, SetVolumePropertyRequest,
-        SetVolumeReplicaRequest, ShareVolumeRequest, UnpublishVolumeRequest, UnshareVolumeRequest,
+        get_volumes_request, CancelVolumeReplicaMoveRequest, CloneVolumeRequest,
+        CreateSnapshotVolumeRequest, CreateVolumeRequest, DestroyShutdownTargetRequest,
+        DestroyVolumeRequest, GetVolumeCapacityRequest, MoveVolumeReplicaRequest,
+        PublishVolumeRequest, RegisteredTargets, RepublishVolumeRequest, ResizeVolumeRequest,
+        SetVolumePropertyRequest, SetVolumeReplicaRequest, ShareVolumeRequest,
+        UnpublishVolumeRequest, UnshareVolumeRequest,
     },
 };
 use events_api::event::{EventAction, EventCategory, EventMessage, EventMeta, EventSource};
 use std::{borrow::Borrow, collections::HashMap, convert::TryFrom};
 use stor_port::{
-    transport_api::{v0::Volumes, ReplyError, ResourceKind},
+    transport_api::{
+        v0::{SnapshotExports, SnapshotGroups, SnapshotSchedules, Volumes},
+        ReplyError, ResourceKind,
+    },
     types::v0::{
-        store::volume::{
-            AffinityGroupSpec, FrontendConfig, InitiatorAC, TargetConfig, VolumeContentSource,
-            VolumeMetadata, VolumeSpec, VolumeTarget,
+        store::{
+            snapshots::{export::SnapshotExportSpec, schedule::SnapshotScheduleSpec},
+            volume::{
+                AffinityGroupSpec, FrontendConfig, InitiatorAC, InitiatorAccess, TargetConfig,
+                VolumeContentSource, VolumeMetadata, VolumeSpec, VolumeTarget,
+            },
         },
         transport::{
-            AffinityGroup, CreateSnapshotVolume, CreateVolume, DestroyShutdownTargets,
-            DestroyVolume, ExplicitNodeTopology, Filter, LabelledTopology, Nexus, NexusId,
-            NexusNvmfConfig, NodeId, NodeTopology, NvmeNqn, PoolTopology, PublishVolume, ReplicaId,
-            ReplicaStatus, ReplicaTopology, ReplicaUsage, RepublishVolume, ResizeVolume,
-            SetVolumeProperty, SetVolumeReplica, ShareVolume, SnapshotId, Topology,
-            UnpublishVolume, UnshareVolume, Volume, VolumeId, VolumeLabels, VolumePolicy,
-            VolumeProperty, VolumeShareProtocol, VolumeState, VolumeUsage,
+            AffinityGroup, CancelVolumeReplicaMove, CloneVolume, CreateSnapshotVolume,
+            CreateVolume, DestroyShutdownTargets, DestroyVolume, EncryptionKeyProvider,
+            EncryptionKeyRef, ExplicitNodeTopology, Filter, GetVolumeCapacity, LabelledTopology,
+            MoveVolumeReplica, Nexus, NexusId, NexusNvmfConfig, NodeId, NodeTopology, NvmeNqn,
+            PoolId, PoolTopology, PublishVolume, ReplicaId, ReplicaMoveRole, ReplicaStatus,
+            ReplicaTopology, ReplicaUsage, RepublishVolume, ResizeVolume, SetVolumeProperty,
+            SetVolumeReplica, ShareVolume, SnapshotExportId, SnapshotGroup, SnapshotGroupId,
+            SnapshotId, SnapshotScheduleId, Topology, UnpublishVolume, UnshareVolume, Volume,
+            VolumeCapacity, VolumeId, VolumeLabels, VolumePolicy, VolumeProperty, VolumeQos,
+            VolumeShareProtocol, VolumeState, VolumeUsage,
         },
     },
     IntoOption, IntoVec, TryIntoOption,
@@ -100,6 +114,24 @@ pub trait VolumeOperations: Send + Sync {
         req: &dyn SetVolumePropertyInfo,
         ctx: Option<Context>,
     ) -> Result<Volume, ReplyError>;
+    /// Move a volume replica onto another pool.
+    async fn move_replica(
+        &self,
+        req: &dyn MoveVolumeReplicaInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Cancel the replica move in progress of a volume.
+    async fn cancel_replica_move(
+        &self,
+        req: &dyn CancelVolumeReplicaMoveInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Get the capacity available to new volumes created with the given parameters.
+    async fn get_capacity(
+        &self,
+        req: &GetVolumeCapacity,
+        ctx: Option<Context>,
+    ) -> Result<VolumeCapacity, ReplyError>;
     /// Liveness probe for volume service
     async fn probe(&self, ctx: Option<Context>) -> Result<bool, ReplyError>;
     /// Destroy shutdown targets
@@ -120,6 +152,12 @@ pub trait VolumeOperations: Send + Sync {
         request: &dyn DestroyVolumeSnapshotInfo,
         ctx: Option<Context>,
     ) -> Result<(), ReplyError>;
+    /// Restore a volume in place to one of its snapshots.
+    async fn restore_snapshot(
+        &self,
+        request: &dyn RestoreVolumeSnapshotInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
     /// List volume snapshots.
     async fn get_snapshots(
         &self,
@@ -134,12 +172,78 @@ pub trait VolumeOperations: Send + Sync {
         req: &dyn CreateSnapshotVolumeInfo,
         ctx: Option<Context>,
     ) -> Result<Volume, ReplyError>;
+    /// Create a new volume as a clone of an existing volume.
+    async fn clone_volume(
+        &self,
+        req: &dyn CloneVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
     /// Resize a volume
     async fn resize(
         &self,
         req: &dyn ResizeVolumeInfo,
         ctx: Option<Context>,
     ) -> Result<Volume, ReplyError>;
+    /// Create or update a snapshot schedule.
+    async fn create_snapshot_schedule(
+        &self,
+        req: &dyn CreateSnapshotScheduleInfo,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotScheduleSpec, ReplyError>;
+    /// Delete a snapshot schedule.
+    async fn destroy_snapshot_schedule(
+        &self,
+        req: &dyn DestroySnapshotScheduleInfo,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError>;
+    /// Get all snapshot schedules, or a specific one.
+    async fn get_snapshot_schedules(
+        &self,
+        id: Option<SnapshotScheduleId>,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotSchedules, ReplyError>;
+    /// Create a snapshot group, snapshotting all of its volumes at a single quiesce point.
+    async fn create_snapshot_group(
+        &self,
+        req: &dyn CreateSnapshotGroupInfo,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotGroup, ReplyError>;
+    /// Delete a snapshot group, along with its member snapshots.
+    async fn destroy_snapshot_group(
+        &self,
+        req: &dyn DestroySnapshotGroupInfo,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError>;
+    /// Get all snapshot groups, or a specific one.
+    async fn get_snapshot_groups(
+        &self,
+        id: Option<SnapshotGroupId>,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotGroups, ReplyError>;
+    /// Export a volume snapshot into a backup target.
+    async fn create_snapshot_export(
+        &self,
+        req: &dyn CreateSnapshotExportInfo,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotExportSpec, ReplyError>;
+    /// Delete a snapshot export from its backup target.
+    async fn destroy_snapshot_export(
+        &self,
+        req: &dyn DestroySnapshotExportInfo,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError>;
+    /// Get all snapshot exports, or a specific one.
+    async fn get_snapshot_exports(
+        &self,
+        id: Option<SnapshotExportId>,
+        ctx: Option<Context>,
+    ) -> Result<SnapshotExports, ReplyError>;
+    /// Create a new volume from a snapshot export, along with its chain of base exports.
+    async fn restore_snapshot_export(
+        &self,
+        req: &dyn RestoreSnapshotExportInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
 }
 
 impl From<VolumeSpec> for volume::VolumeDefinition {
@@ -167,6 +271,8 @@ impl From<VolumeSpec> for volume::VolumeDefinition {
                 content_source: volume_spec.content_source.into_opt(),
                 num_snapshots: volume_spec.metadata.num_snapshots() as u32,
                 max_snapshots: volume_spec.max_snapshots,
+                qos: volume_spec.qos.into_opt(),
+                encryption: volume_spec.encryption.into_opt(),
             }),
             metadata: Some(volume::Metadata {
                 spec_status: spec_status as i32,
@@ -234,11 +340,20 @@ impl From<volume::FrontendNode> for InitiatorAC {
             value.name,
             NvmeNqn::try_from(&value.nqn).unwrap_or(NvmeNqn::Invalid { nqn: value.nqn }),
         )
+        .with_access(match value.read_only {
+            true => InitiatorAccess::ReadOnly,
+            false => InitiatorAccess::ReadWrite,
+        })
     }
 }
 impl From<volume::FrontendConfig> for FrontendConfig {
     fn from(value: volume::FrontendConfig) -> Self {
-        FrontendConfig::from_acls(value.nodes.into_vec())
+        match value.read_only {
+            true => {
+                FrontendConfig::from_read_only_acls(value.nodes.into_vec(), !value.unrestricted)
+            }
+            false => FrontendConfig::from_acls(value.nodes.into_vec()),
+        }
     }
 }
 
@@ -343,6 +458,7 @@ impl TryFrom<volume::VolumeDefinition> for VolumeSpec {
                 None => None,
             },
             operation: None,
+            replica_move: None,
             thin: volume_spec.thin,
             publish_context: volume_meta
                 .publish_context
@@ -352,6 +468,8 @@ impl TryFrom<volume::VolumeDefinition> for VolumeSpec {
             content_source: volume_spec.content_source.try_into_opt()?,
             num_snapshots: volume_spec.num_snapshots,
             max_snapshots: volume_spec.max_snapshots,
+            qos: volume_spec.qos.into_opt(),
+            encryption: volume_spec.encryption.into_opt(),
         };
         Ok(volume_spec)
     }
@@ -475,10 +593,33 @@ impl TryFrom<volume::ReplicaTopology> for ReplicaTopology {
                 .child_status_reason
                 .and_then(|s| nexus::ChildStateReason::try_from(s).ok().into_opt()),
             topology.rebuild_progress.and_then(|r| u8::try_from(r).ok()),
+        )
+        .with_replica_move(
+            topology
+                .replica_move
+                .and_then(|r| volume::ReplicaMoveRole::try_from(r).ok().into_opt()),
         ))
     }
 }
 
+impl From<volume::ReplicaMoveRole> for ReplicaMoveRole {
+    fn from(src: volume::ReplicaMoveRole) -> Self {
+        match src {
+            volume::ReplicaMoveRole::Source => Self::Source,
+            volume::ReplicaMoveRole::Target => Self::Target,
+        }
+    }
+}
+
+impl From<ReplicaMoveRole> for volume::ReplicaMoveRole {
+    fn from(src: ReplicaMoveRole) -> Self {
+        match src {
+            ReplicaMoveRole::Source => Self::Source,
+            ReplicaMoveRole::Target => Self::Target,
+        }
+    }
+}
+
 impl From<volume::ReplicaUsage> for ReplicaUsage {
     fn from(value: volume::ReplicaUsage) -> Self {
         Self::new(
@@ -506,6 +647,9 @@ impl From<ReplicaTopology> for volume::ReplicaTopology {
                 .child_status_reason()
                 .map(|s| crate::nexus::ChildStateReason::from(s).into()),
             rebuild_progress: replica_topology.rebuild_progress().map(|r| r as u32),
+            replica_move: replica_topology
+                .replica_move()
+                .map(|r| volume::ReplicaMoveRole::from(r).into()),
         }
     }
 }
@@ -796,8 +940,11 @@ impl From<&FrontendConfig> for volume::FrontendConfig {
                 .map(|n| volume::FrontendNode {
                     name: n.node_name().to_string(),
                     nqn: n.node_nqn().to_string(),
+                    read_only: n.access() == InitiatorAccess::ReadOnly,
                 })
                 .collect::<Vec<_>>(),
+            read_only: src.read_only(),
+            unrestricted: !src.restricted(),
         }
     }
 }
@@ -886,6 +1033,61 @@ pub struct ValidatedCreateSnapshotVolumeRequest {
     inner: ValidatedCreateVolumeRequest,
 }
 
+/// Trait to be implemented for CloneVolume operation.
+pub trait CloneVolumeInfo: Send + Sync + std::fmt::Debug {
+    /// Get the clone source volume uuid.
+    fn source_volume(&self) -> &VolumeId;
+    /// Get the generic volume create parameters.
+    fn volume(&self) -> &dyn CreateVolumeInfo;
+}
+
+impl CloneVolumeInfo for CloneVolume {
+    fn source_volume(&self) -> &VolumeId {
+        self.source_uuid()
+    }
+
+    fn volume(&self) -> &dyn CreateVolumeInfo {
+        self.params()
+    }
+}
+
+impl CloneVolumeInfo for ValidatedCloneVolumeRequest {
+    fn source_volume(&self) -> &VolumeId {
+        &self.source_id
+    }
+    fn volume(&self) -> &dyn CreateVolumeInfo {
+        &self.inner
+    }
+}
+impl ValidateRequestTypes for CloneVolumeRequest {
+    type Validated = ValidatedCloneVolumeRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        let Some(volume) = self.volume else {
+            return Err(ReplyError::missing_argument(ResourceKind::Volume, "volume"));
+        };
+
+        Ok(ValidatedCloneVolumeRequest {
+            source_id: match &self.source_volume {
+                Some(id) => VolumeId::try_from(id.as_str()).map_err(|e| {
+                    ReplyError::invalid_argument(ResourceKind::Volume, "source_volume", e)
+                }),
+                None => Err(ReplyError::missing_argument(
+                    ResourceKind::Volume,
+                    "source_volume",
+                )),
+            }?,
+            inner: volume.validated()?,
+        })
+    }
+}
+
+/// Intermediate structure that validates the conversion to CloneVolumeRequest type.
+#[derive(Debug)]
+pub struct ValidatedCloneVolumeRequest {
+    source_id: VolumeId,
+    inner: ValidatedCreateVolumeRequest,
+}
+
 /// Trait to be implemented for CreateVolume operation.
 pub trait CreateVolumeInfo: Send + Sync + std::fmt::Debug {
     /// Uuid of the volume
@@ -908,6 +1110,10 @@ pub trait CreateVolumeInfo: Send + Sync + std::fmt::Debug {
     fn cluster_capacity_limit(&self) -> Option<u64>;
     /// Max snapshot limit per volume.
     fn max_snapshots(&self) -> Option<u32>;
+    /// IO limits enforced on the volume target.
+    fn qos(&self) -> Option<VolumeQos>;
+    /// Reference to the key which encrypts the volume at rest.
+    fn encryption(&self) -> Option<EncryptionKeyRef>;
 }
 
 impl CreateVolumeInfo for CreateVolume {
@@ -950,6 +1156,14 @@ impl CreateVolumeInfo for CreateVolume {
     fn max_snapshots(&self) -> Option<u32> {
         self.max_snapshots
     }
+
+    fn qos(&self) -> Option<VolumeQos> {
+        self.qos.clone()
+    }
+
+    fn encryption(&self) -> Option<EncryptionKeyRef> {
+        self.encryption.clone()
+    }
 }
 
 /// Intermediate structure that validates the conversion to CreateVolumeRequest type.
@@ -1006,6 +1220,14 @@ impl CreateVolumeInfo for ValidatedCreateVolumeRequest {
     fn max_snapshots(&self) -> Option<u32> {
         self.inner.max_snapshots
     }
+
+    fn qos(&self) -> Option<VolumeQos> {
+        self.inner.qos.clone().into_opt()
+    }
+
+    fn encryption(&self) -> Option<EncryptionKeyRef> {
+        self.inner.encryption.clone().into_opt()
+    }
 }
 
 impl ValidateRequestTypes for CreateVolumeRequest {
@@ -1044,6 +1266,8 @@ impl From<&dyn CreateVolumeInfo> for CreateVolume {
             affinity_group: data.affinity_group(),
             cluster_capacity_limit: data.cluster_capacity_limit(),
             max_snapshots: data.max_snapshots(),
+            qos: data.qos().into_opt(),
+            encryption: data.encryption().into_opt(),
         }
     }
 }
@@ -1063,6 +1287,8 @@ impl From<&dyn CreateVolumeInfo> for CreateVolumeRequest {
             affinity_group: data.affinity_group().map(|ag| ag.into()),
             cluster_capacity_limit: data.cluster_capacity_limit(),
             max_snapshots: data.max_snapshots(),
+            qos: data.qos().into_opt(),
+            encryption: data.encryption().into_opt(),
         }
     }
 }
@@ -1082,6 +1308,21 @@ impl From<&dyn CreateSnapshotVolumeInfo> for CreateSnapshotVolumeRequest {
     }
 }
 
+impl From<&dyn CloneVolumeInfo> for CloneVolume {
+    fn from(data: &dyn CloneVolumeInfo) -> Self {
+        Self::new(data.source_volume().clone(), data.volume().into())
+    }
+}
+
+impl From<&dyn CloneVolumeInfo> for CloneVolumeRequest {
+    fn from(data: &dyn CloneVolumeInfo) -> Self {
+        Self {
+            source_volume: Some(data.source_volume().to_string()),
+            volume: Some(data.volume().into()),
+        }
+    }
+}
+
 /// Trait to be implemented for DestroyVolume operation.
 pub trait DestroyVolumeInfo: Send + Sync + std::fmt::Debug {
     /// Uuid of the volume to be destroyed
@@ -1140,6 +1381,8 @@ pub struct ValidatedResizeVolumeRequest {
     uuid: VolumeId,
     requested_size: u64,
     cluster_capacity_limit: Option<u64>,
+    allow_shrink: bool,
+    filesystem_size: Option<u64>,
 }
 /// Trait to be implemented for ResizeVolume operation.
 pub trait ResizeVolumeInfo: Send + Sync + std::fmt::Debug {
@@ -1149,6 +1392,10 @@ pub trait ResizeVolumeInfo: Send + Sync + std::fmt::Debug {
     fn req_size(&self) -> u64;
     /// Total capacity limit for all volumes, in bytes
     fn cluster_capacity_limit(&self) -> Option<u64>;
+    /// Allow the volume to be shrunk
+    fn allow_shrink(&self) -> bool;
+    /// Size of the filesystem on the volume, as confirmed by the caller, in bytes
+    fn filesystem_size(&self) -> Option<u64>;
 }
 
 impl ResizeVolumeInfo for ResizeVolume {
@@ -1163,6 +1410,14 @@ impl ResizeVolumeInfo for ResizeVolume {
     fn cluster_capacity_limit(&self) -> Option<u64> {
         self.cluster_capacity_limit
     }
+
+    fn allow_shrink(&self) -> bool {
+        self.allow_shrink
+    }
+
+    fn filesystem_size(&self) -> Option<u64> {
+        self.filesystem_size
+    }
 }
 
 impl ValidateRequestTypes for ResizeVolumeRequest {
@@ -1172,6 +1427,8 @@ impl ValidateRequestTypes for ResizeVolumeRequest {
             uuid: VolumeId::try_from(StringValue(Some(self.uuid)))?,
             requested_size: self.requested_size,
             cluster_capacity_limit: self.capacity_limit,
+            allow_shrink: self.allow_shrink,
+            filesystem_size: self.filesystem_size,
         })
     }
 }
@@ -1182,6 +1439,8 @@ impl From<&dyn ResizeVolumeInfo> for ResizeVolume {
             uuid: data.uuid(),
             requested_size: data.req_size(),
             cluster_capacity_limit: data.cluster_capacity_limit(),
+            allow_shrink: data.allow_shrink(),
+            filesystem_size: data.filesystem_size(),
         }
     }
 }
@@ -1192,6 +1451,8 @@ impl From<&dyn ResizeVolumeInfo> for ResizeVolumeRequest {
             uuid: data.uuid().to_string(),
             requested_size: data.req_size(),
             capacity_limit: data.cluster_capacity_limit(),
+            allow_shrink: data.allow_shrink(),
+            filesystem_size: data.filesystem_size(),
         }
     }
 }
@@ -1208,6 +1469,14 @@ impl ResizeVolumeInfo for ValidatedResizeVolumeRequest {
     fn cluster_capacity_limit(&self) -> Option<u64> {
         self.cluster_capacity_limit
     }
+
+    fn allow_shrink(&self) -> bool {
+        self.allow_shrink
+    }
+
+    fn filesystem_size(&self) -> Option<u64> {
+        self.filesystem_size
+    }
 }
 
 /// Trait to be implemented for ShareVolume operation.
@@ -1356,6 +1625,8 @@ pub trait PublishVolumeInfo: Send + Sync + std::fmt::Debug {
     fn publish_context(&self) -> HashMap<String, String>;
     /// Hosts allowed to access the nexus.
     fn frontend_nodes(&self) -> Vec<String>;
+    /// Publish read-only for the frontend nodes.
+    fn read_only(&self) -> bool;
 }
 
 impl PublishVolumeInfo for PublishVolume {
@@ -1378,6 +1649,10 @@ impl PublishVolumeInfo for PublishVolume {
     fn frontend_nodes(&self) -> Vec<String> {
         self.frontend_nodes.clone()
     }
+
+    fn read_only(&self) -> bool {
+        self.read_only
+    }
 }
 
 impl PublishVolumeInfo for RepublishVolume {
@@ -1400,6 +1675,10 @@ impl PublishVolumeInfo for RepublishVolume {
     fn frontend_nodes(&self) -> Vec<String> {
         unimplemented!()
     }
+
+    fn read_only(&self) -> bool {
+        unimplemented!()
+    }
 }
 
 /// Intermediate structure that validates the conversion to PublishVolumeRequest type.
@@ -1434,6 +1713,10 @@ impl PublishVolumeInfo for ValidatedPublishVolumeRequest {
     fn frontend_nodes(&self) -> Vec<String> {
         self.frontend_nodes.clone()
     }
+
+    fn read_only(&self) -> bool {
+        self.inner.read_only
+    }
 }
 
 impl ValidateRequestTypes for PublishVolumeRequest {
@@ -1468,6 +1751,7 @@ impl From<&dyn PublishVolumeInfo> for PublishVolume {
             share: data.share(),
             publish_context: data.publish_context(),
             frontend_nodes: data.frontend_nodes(),
+            read_only: data.read_only(),
         }
     }
 }
@@ -1487,6 +1771,7 @@ impl From<&dyn PublishVolumeInfo> for PublishVolumeRequest {
             share,
             publish_context: data.publish_context(),
             frontend_nodes: data.frontend_nodes(),
+            read_only: data.read_only(),
         }
     }
 }
@@ -1623,6 +1908,8 @@ pub trait UnpublishVolumeInfo: Send + Sync + std::fmt::Debug {
     fn uuid(&self) -> VolumeId;
     /// Force unpublish
     fn force(&self) -> bool;
+    /// Only remove this frontend node from a read-only target
+    fn frontend_node(&self) -> Option<String>;
 }
 
 impl UnpublishVolumeInfo for UnpublishVolume {
@@ -1633,6 +1920,10 @@ impl UnpublishVolumeInfo for UnpublishVolume {
     fn force(&self) -> bool {
         self.force()
     }
+
+    fn frontend_node(&self) -> Option<String> {
+        self.frontend_node().cloned()
+    }
 }
 
 /// Intermediate structure that validates the conversion to UnpublishVolumeRequest type.
@@ -1649,6 +1940,9 @@ impl UnpublishVolumeInfo for ValidatedUnpublishVolumeRequest {
     fn force(&self) -> bool {
         self.inner.force
     }
+    fn frontend_node(&self) -> Option<String> {
+        self.inner.frontend_node.clone()
+    }
 }
 
 impl ValidateRequestTypes for UnpublishVolumeRequest {
@@ -1663,7 +1957,7 @@ impl ValidateRequestTypes for UnpublishVolumeRequest {
 
 impl From<&dyn UnpublishVolumeInfo> for UnpublishVolume {
     fn from(data: &dyn UnpublishVolumeInfo) -> Self {
-        UnpublishVolume::new(&data.uuid(), data.force())
+        UnpublishVolume::new(&data.uuid(), data.force()).with_frontend_node(data.frontend_node())
     }
 }
 
@@ -1672,6 +1966,7 @@ impl From<&dyn UnpublishVolumeInfo> for UnpublishVolumeRequest {
         Self {
             uuid: Some(data.uuid().to_string()),
             force: data.force(),
+            frontend_node: data.frontend_node(),
         }
     }
 }
@@ -1778,6 +2073,10 @@ impl From<SetVolumePropertyRequest> for Option<VolumeProperty> {
                 volume::volume_property::Attr::MaxSnapshots(volume::MaxSnapshotValue { value }) => {
                     VolumeProperty::MaxSnapshots(value)
                 }
+                volume::volume_property::Attr::Qos(qos) => VolumeProperty::Qos(qos.into()),
+                volume::volume_property::Attr::EncryptionKey(key_ref) => {
+                    VolumeProperty::EncryptionKey(key_ref.into())
+                }
             })
         })
     }
@@ -1791,6 +2090,71 @@ impl From<VolumeProperty> for volume::VolumeProperty {
                     volume::MaxSnapshotValue { value },
                 )),
             },
+            VolumeProperty::Qos(qos) => volume::VolumeProperty {
+                attr: Some(volume::volume_property::Attr::Qos(qos.into())),
+            },
+            VolumeProperty::EncryptionKey(key_ref) => volume::VolumeProperty {
+                attr: Some(volume::volume_property::Attr::EncryptionKey(key_ref.into())),
+            },
+        }
+    }
+}
+
+impl From<volume::VolumeQos> for VolumeQos {
+    fn from(qos: volume::VolumeQos) -> Self {
+        Self {
+            rw_iops: qos.rw_iops,
+            rw_mbps: qos.rw_mbps,
+            read_mbps: qos.read_mbps,
+            write_mbps: qos.write_mbps,
+        }
+    }
+}
+impl From<VolumeQos> for volume::VolumeQos {
+    fn from(qos: VolumeQos) -> Self {
+        Self {
+            rw_iops: qos.rw_iops,
+            rw_mbps: qos.rw_mbps,
+            read_mbps: qos.read_mbps,
+            write_mbps: qos.write_mbps,
+        }
+    }
+}
+impl From<volume::EncryptionKeyProvider> for EncryptionKeyProvider {
+    fn from(src: volume::EncryptionKeyProvider) -> Self {
+        match src {
+            volume::EncryptionKeyProvider::File => Self::File,
+            volume::EncryptionKeyProvider::Secret => Self::Secret,
+            volume::EncryptionKeyProvider::Kms => Self::Kms,
+        }
+    }
+}
+impl From<EncryptionKeyProvider> for volume::EncryptionKeyProvider {
+    fn from(src: EncryptionKeyProvider) -> Self {
+        match src {
+            EncryptionKeyProvider::File => Self::File,
+            EncryptionKeyProvider::Secret => Self::Secret,
+            EncryptionKeyProvider::Kms => Self::Kms,
+        }
+    }
+}
+impl From<volume::EncryptionKeyRef> for EncryptionKeyRef {
+    fn from(key_ref: volume::EncryptionKeyRef) -> Self {
+        Self {
+            provider: key_ref.provider().into(),
+            name: key_ref.name,
+            namespace: key_ref.namespace,
+            key: key_ref.key,
+        }
+    }
+}
+impl From<EncryptionKeyRef> for volume::EncryptionKeyRef {
+    fn from(key_ref: EncryptionKeyRef) -> Self {
+        Self {
+            provider: volume::EncryptionKeyProvider::from(key_ref.provider) as i32,
+            name: key_ref.name,
+            namespace: key_ref.namespace,
+            key: key_ref.key,
         }
     }
 }
@@ -1829,6 +2193,189 @@ impl From<&dyn SetVolumePropertyInfo> for SetVolumePropertyRequest {
         }
     }
 }
+
+/// Trait to be implemented for MoveVolumeReplica operation.
+pub trait MoveVolumeReplicaInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume.
+    fn uuid(&self) -> VolumeId;
+    /// Uuid of the replica to be moved.
+    fn replica(&self) -> ReplicaId;
+    /// Pool on which the new replica should be created.
+    fn pool(&self) -> Option<PoolId>;
+    /// Node on which the new replica should be created.
+    fn node(&self) -> Option<NodeId>;
+}
+
+impl MoveVolumeReplicaInfo for MoveVolumeReplica {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn replica(&self) -> ReplicaId {
+        self.replica.clone()
+    }
+
+    fn pool(&self) -> Option<PoolId> {
+        self.pool.clone()
+    }
+
+    fn node(&self) -> Option<NodeId> {
+        self.node.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to MoveVolumeReplicaRequest type.
+#[derive(Debug)]
+pub struct ValidatedMoveVolumeReplicaRequest {
+    inner: MoveVolumeReplicaRequest,
+    uuid: VolumeId,
+    replica: ReplicaId,
+}
+
+impl MoveVolumeReplicaInfo for ValidatedMoveVolumeReplicaRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+    fn replica(&self) -> ReplicaId {
+        self.replica.clone()
+    }
+    fn pool(&self) -> Option<PoolId> {
+        self.inner.pool.clone().map(Into::into)
+    }
+    fn node(&self) -> Option<NodeId> {
+        self.inner.node.clone().map(Into::into)
+    }
+}
+
+impl ValidateRequestTypes for MoveVolumeReplicaRequest {
+    type Validated = ValidatedMoveVolumeReplicaRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedMoveVolumeReplicaRequest {
+            uuid: VolumeId::try_from(StringValue(Some(self.uuid.clone())))?,
+            replica: ReplicaId::try_from(StringValue(Some(self.replica.clone())))?,
+            inner: self,
+        })
+    }
+}
+
+impl From<&dyn MoveVolumeReplicaInfo> for MoveVolumeReplica {
+    fn from(data: &dyn MoveVolumeReplicaInfo) -> Self {
+        Self::new(data.uuid(), data.replica(), data.pool(), data.node())
+    }
+}
+
+impl From<&dyn MoveVolumeReplicaInfo> for MoveVolumeReplicaRequest {
+    fn from(data: &dyn MoveVolumeReplicaInfo) -> Self {
+        Self {
+            uuid: data.uuid().to_string(),
+            replica: data.replica().to_string(),
+            pool: data.pool().map(|pool| pool.to_string()),
+            node: data.node().map(|node| node.to_string()),
+        }
+    }
+}
+
+/// Trait to be implemented for CancelVolumeReplicaMove operation.
+pub trait CancelVolumeReplicaMoveInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume.
+    fn uuid(&self) -> VolumeId;
+    /// Uuid of the replica being moved.
+    fn replica(&self) -> ReplicaId;
+}
+
+impl CancelVolumeReplicaMoveInfo for CancelVolumeReplicaMove {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn replica(&self) -> ReplicaId {
+        self.replica.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to CancelVolumeReplicaMoveRequest type.
+#[derive(Debug)]
+pub struct ValidatedCancelVolumeReplicaMoveRequest {
+    uuid: VolumeId,
+    replica: ReplicaId,
+}
+
+impl CancelVolumeReplicaMoveInfo for ValidatedCancelVolumeReplicaMoveRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+    fn replica(&self) -> ReplicaId {
+        self.replica.clone()
+    }
+}
+
+impl ValidateRequestTypes for CancelVolumeReplicaMoveRequest {
+    type Validated = ValidatedCancelVolumeReplicaMoveRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedCancelVolumeReplicaMoveRequest {
+            uuid: VolumeId::try_from(StringValue(Some(self.uuid)))?,
+            replica: ReplicaId::try_from(StringValue(Some(self.replica)))?,
+        })
+    }
+}
+
+impl From<&dyn CancelVolumeReplicaMoveInfo> for CancelVolumeReplicaMove {
+    fn from(data: &dyn CancelVolumeReplicaMoveInfo) -> Self {
+        Self::new(data.uuid(), data.replica())
+    }
+}
+
+impl From<&dyn CancelVolumeReplicaMoveInfo> for CancelVolumeReplicaMoveRequest {
+    fn from(data: &dyn CancelVolumeReplicaMoveInfo) -> Self {
+        Self {
+            uuid: data.uuid().to_string(),
+            replica: data.replica().to_string(),
+        }
+    }
+}
+
+impl From<&GetVolumeCapacity> for GetVolumeCapacityRequest {
+    fn from(req: &GetVolumeCapacity) -> Self {
+        Self {
+            replicas: req.replicas.into(),
+            thin: req.thin,
+            topology: req.topology.clone().map(From::from),
+        }
+    }
+}
+
+impl TryFrom<GetVolumeCapacityRequest> for GetVolumeCapacity {
+    type Error = ReplyError;
+    fn try_from(req: GetVolumeCapacityRequest) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            u8::try_from(req.replicas).map_err(|error| {
+                ReplyError::invalid_argument(
+                    ResourceKind::Volume,
+                    "get_volume_capacity_request.replicas",
+                    error.to_string(),
+                )
+            })?,
+            req.thin,
+            req.topology.map(Topology::try_from).transpose()?,
+        ))
+    }
+}
+
+impl From<VolumeCapacity> for volume::VolumeCapacity {
+    fn from(capacity: VolumeCapacity) -> Self {
+        Self {
+            available_capacity: capacity.available_capacity,
+            maximum_volume_size: capacity.maximum_volume_size,
+        }
+    }
+}
+
+impl From<volume::VolumeCapacity> for VolumeCapacity {
+    fn from(capacity: volume::VolumeCapacity) -> Self {
+        Self::new(capacity.available_capacity, capacity.maximum_volume_size)
+    }
+}
+
 /// A helper to convert the replica topology map form grpc type to corresponding control plane type.
 fn to_replica_topology_map(
     map: HashMap<String, volume::ReplicaTopology>,
@@ -2001,6 +2548,12 @@ impl TryFrom<volume::volume_spec::VolumeContentSource> for VolumeContentSource {
                     SnapshotId::try_from(StringValue(snap_source.snapshot))?,
                     VolumeId::try_from(StringValue(snap_source.snap_source_vol))?,
                 )),
+                volume::volume_spec::volume_content_source::VolumeContentSource::Volume(
+                    vol_source,
+                ) => Ok(Self::Volume(
+                    VolumeId::try_from(StringValue(vol_source.source_vol))?,
+                    SnapshotId::try_from(StringValue(vol_source.snapshot))?,
+                )),
             },
         }
     }
@@ -2019,6 +2572,16 @@ impl From<VolumeContentSource> for volume::volume_spec::VolumeContentSource {
                     ),
                 ),
             },
+            VolumeContentSource::Volume(vol, snap) => volume::volume_spec::VolumeContentSource {
+                volume_content_source: Some(
+                    volume::volume_spec::volume_content_source::VolumeContentSource::Volume(
+                        volume::volume_spec::VolumeAsSource {
+                            source_vol: Some(vol.to_string()),
+                            snapshot: Some(snap.to_string()),
+                        },
+                    ),
+                ),
+            },
         }
     }
 }
@@ -0,0 +1,402 @@
+use crate::{
+    misc::traits::ValidateRequestTypes,
+    operations::volume::traits::{CreateVolumeInfo, ValidatedCreateVolumeRequest},
+    volume,
+    volume::{
+        CreateSnapshotExportRequest, DestroySnapshotExportRequest, RestoreSnapshotExportRequest,
+    },
+};
+use std::convert::TryFrom;
+use stor_port::{
+    transport_api::{v0::SnapshotExports, ReplyError, ResourceKind},
+    types::v0::{
+        store::snapshots::export::{
+            BackupTarget, SnapshotExportSpec, SnapshotExportStats, SnapshotExportStatus,
+        },
+        transport::{
+            CreateSnapshotExport, DestroySnapshotExport, RestoreSnapshotExport, SnapshotExportId,
+            SnapshotId, VolumeId,
+        },
+    },
+};
+
+/// Snapshot export creation information.
+pub trait CreateSnapshotExportInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the snapshot export.
+    fn id(&self) -> SnapshotExportId;
+    /// Id of the snapshot to be exported.
+    fn snapshot_id(&self) -> SnapshotId;
+    /// The backup target which the snapshot is exported to.
+    fn target(&self) -> BackupTarget;
+    /// The export which this export is incremental to, if any.
+    fn base(&self) -> Option<SnapshotExportId>;
+}
+
+/// Snapshot export deletion information.
+pub trait DestroySnapshotExportInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the snapshot export.
+    fn id(&self) -> SnapshotExportId;
+}
+
+/// Snapshot export restore information.
+pub trait RestoreSnapshotExportInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the snapshot export to be restored.
+    fn export_id(&self) -> &SnapshotExportId;
+    /// Get the generic volume create parameters.
+    fn volume(&self) -> &dyn CreateVolumeInfo;
+}
+
+impl CreateSnapshotExportInfo for CreateSnapshotExport {
+    fn id(&self) -> SnapshotExportId {
+        self.id.clone()
+    }
+
+    fn snapshot_id(&self) -> SnapshotId {
+        self.snapshot_id.clone()
+    }
+
+    fn target(&self) -> BackupTarget {
+        self.target.clone()
+    }
+
+    fn base(&self) -> Option<SnapshotExportId> {
+        self.base.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to CreateSnapshotExportRequest type.
+#[derive(Debug)]
+pub struct ValidatedCreateSnapshotExportRequest {
+    inner: CreateSnapshotExport,
+}
+
+impl CreateSnapshotExportInfo for ValidatedCreateSnapshotExportRequest {
+    fn id(&self) -> SnapshotExportId {
+        self.inner.id.clone()
+    }
+
+    fn snapshot_id(&self) -> SnapshotId {
+        self.inner.snapshot_id.clone()
+    }
+
+    fn target(&self) -> BackupTarget {
+        self.inner.target.clone()
+    }
+
+    fn base(&self) -> Option<SnapshotExportId> {
+        self.inner.base.clone()
+    }
+}
+
+impl ValidateRequestTypes for CreateSnapshotExportRequest {
+    type Validated = ValidatedCreateSnapshotExportRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        let id = SnapshotExportId::try_from(self.id.as_str()).map_err(|error| {
+            ReplyError::invalid_argument(ResourceKind::SnapshotExport, "id", error)
+        })?;
+        let snapshot_id = SnapshotId::try_from(self.snapshot_id.as_str()).map_err(|error| {
+            ReplyError::invalid_argument(ResourceKind::SnapshotExport, "snapshot_id", error)
+        })?;
+        let base = match self.base {
+            Some(base) => Some(SnapshotExportId::try_from(base.as_str()).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::SnapshotExport, "base", error)
+            })?),
+            None => None,
+        };
+        let Some(target) = self.target else {
+            return Err(ReplyError::missing_argument(
+                ResourceKind::SnapshotExport,
+                "target",
+            ));
+        };
+        Ok(ValidatedCreateSnapshotExportRequest {
+            inner: CreateSnapshotExport::new(
+                id,
+                snapshot_id,
+                BackupTarget::try_from(target)?,
+                base,
+            ),
+        })
+    }
+}
+
+impl DestroySnapshotExportInfo for DestroySnapshotExport {
+    fn id(&self) -> SnapshotExportId {
+        self.id.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to DestroySnapshotExportRequest type.
+#[derive(Debug)]
+pub struct ValidatedDestroySnapshotExportRequest {
+    id: SnapshotExportId,
+}
+
+impl DestroySnapshotExportInfo for ValidatedDestroySnapshotExportRequest {
+    fn id(&self) -> SnapshotExportId {
+        self.id.clone()
+    }
+}
+
+impl ValidateRequestTypes for DestroySnapshotExportRequest {
+    type Validated = ValidatedDestroySnapshotExportRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedDestroySnapshotExportRequest {
+            id: SnapshotExportId::try_from(self.id.as_str()).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::SnapshotExport, "id", error)
+            })?,
+        })
+    }
+}
+
+impl RestoreSnapshotExportInfo for RestoreSnapshotExport {
+    fn export_id(&self) -> &SnapshotExportId {
+        self.export_id()
+    }
+
+    fn volume(&self) -> &dyn CreateVolumeInfo {
+        self.params()
+    }
+}
+
+/// Intermediate structure that validates the conversion to RestoreSnapshotExportRequest type.
+#[derive(Debug)]
+pub struct ValidatedRestoreSnapshotExportRequest {
+    export_id: SnapshotExportId,
+    inner: ValidatedCreateVolumeRequest,
+}
+
+impl RestoreSnapshotExportInfo for ValidatedRestoreSnapshotExportRequest {
+    fn export_id(&self) -> &SnapshotExportId {
+        &self.export_id
+    }
+
+    fn volume(&self) -> &dyn CreateVolumeInfo {
+        &self.inner
+    }
+}
+
+impl ValidateRequestTypes for RestoreSnapshotExportRequest {
+    type Validated = ValidatedRestoreSnapshotExportRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        let Some(volume) = self.volume else {
+            return Err(ReplyError::missing_argument(ResourceKind::Volume, "volume"));
+        };
+        Ok(ValidatedRestoreSnapshotExportRequest {
+            export_id: SnapshotExportId::try_from(self.export_id.as_str()).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::SnapshotExport, "export_id", error)
+            })?,
+            inner: volume.validated()?,
+        })
+    }
+}
+
+impl From<&dyn CreateSnapshotExportInfo> for CreateSnapshotExport {
+    fn from(data: &dyn CreateSnapshotExportInfo) -> Self {
+        Self::new(data.id(), data.snapshot_id(), data.target(), data.base())
+    }
+}
+
+impl From<&dyn CreateSnapshotExportInfo> for CreateSnapshotExportRequest {
+    fn from(data: &dyn CreateSnapshotExportInfo) -> Self {
+        Self {
+            id: data.id().to_string(),
+            snapshot_id: data.snapshot_id().to_string(),
+            target: Some(data.target().into()),
+            base: data.base().map(|base| base.to_string()),
+        }
+    }
+}
+
+impl From<&dyn DestroySnapshotExportInfo> for DestroySnapshotExport {
+    fn from(data: &dyn DestroySnapshotExportInfo) -> Self {
+        Self::new(data.id())
+    }
+}
+
+impl From<&dyn DestroySnapshotExportInfo> for DestroySnapshotExportRequest {
+    fn from(data: &dyn DestroySnapshotExportInfo) -> Self {
+        Self {
+            id: data.id().to_string(),
+        }
+    }
+}
+
+impl From<&dyn RestoreSnapshotExportInfo> for RestoreSnapshotExport {
+    fn from(data: &dyn RestoreSnapshotExportInfo) -> Self {
+        Self::new(data.export_id().clone(), data.volume().into())
+    }
+}
+
+impl From<&dyn RestoreSnapshotExportInfo> for RestoreSnapshotExportRequest {
+    fn from(data: &dyn RestoreSnapshotExportInfo) -> Self {
+        Self {
+            export_id: data.export_id().to_string(),
+            volume: Some(data.volume().into()),
+        }
+    }
+}
+
+impl From<BackupTarget> for volume::BackupTarget {
+    fn from(value: BackupTarget) -> Self {
+        let target = match value {
+            BackupTarget::Directory { node, path } => {
+                volume::backup_target::Target::Directory(volume::backup_target::Directory {
+                    node: node.to_string(),
+                    path,
+                })
+            }
+        };
+        Self {
+            target: Some(target),
+        }
+    }
+}
+
+impl TryFrom<volume::BackupTarget> for BackupTarget {
+    type Error = ReplyError;
+    fn try_from(value: volume::BackupTarget) -> Result<Self, Self::Error> {
+        match value.target {
+            Some(volume::backup_target::Target::Directory(directory)) => {
+                if directory.node.is_empty() {
+                    return Err(ReplyError::missing_argument(
+                        ResourceKind::SnapshotExport,
+                        "target.directory.node",
+                    ));
+                }
+                if !directory.path.starts_with('/') {
+                    return Err(ReplyError::invalid_argument(
+                        ResourceKind::SnapshotExport,
+                        "target.directory.path",
+                        "the path must be absolute",
+                    ));
+                }
+                Ok(Self::Directory {
+                    node: directory.node.into(),
+                    path: directory.path,
+                })
+            }
+            None => Err(ReplyError::missing_argument(
+                ResourceKind::SnapshotExport,
+                "target",
+            )),
+        }
+    }
+}
+
+impl From<SnapshotExportStatus> for volume::SnapshotExportStatus {
+    fn from(value: SnapshotExportStatus) -> Self {
+        match value {
+            SnapshotExportStatus::Exporting => Self::Exporting,
+            SnapshotExportStatus::Exported => Self::Exported,
+            SnapshotExportStatus::Deleting => Self::ExportDeleting,
+        }
+    }
+}
+
+impl From<volume::SnapshotExportStatus> for SnapshotExportStatus {
+    fn from(value: volume::SnapshotExportStatus) -> Self {
+        match value {
+            volume::SnapshotExportStatus::Exporting => Self::Exporting,
+            volume::SnapshotExportStatus::Exported => Self::Exported,
+            volume::SnapshotExportStatus::ExportDeleting => Self::Deleting,
+        }
+    }
+}
+
+impl From<SnapshotExportSpec> for volume::SnapshotExport {
+    fn from(value: SnapshotExportSpec) -> Self {
+        Self {
+            id: value.id.to_string(),
+            snapshot_id: value.snapshot_id.to_string(),
+            volume_id: value.volume_id.to_string(),
+            target: Some(value.target.into()),
+            base: value.base.map(|base| base.to_string()),
+            size: value.size,
+            transfer_volume: value.transfer_volume.to_string(),
+            status: volume::SnapshotExportStatus::from(value.status) as i32,
+            timestamp: value
+                .timestamp
+                .map(|timestamp| std::time::SystemTime::from(timestamp).into()),
+            stats: value.stats.map(|stats| volume::SnapshotExportStats {
+                chunks: stats.chunks,
+                changed_chunks: stats.changed_chunks,
+                written_bytes: stats.written_bytes,
+            }),
+        }
+    }
+}
+
+impl TryFrom<volume::SnapshotExport> for SnapshotExportSpec {
+    type Error = ReplyError;
+    fn try_from(value: volume::SnapshotExport) -> Result<Self, Self::Error> {
+        let invalid = |arg: &'static str, error: String| {
+            ReplyError::invalid_argument(ResourceKind::SnapshotExport, arg, error)
+        };
+        let status = volume::SnapshotExportStatus::try_from(value.status).map_err(|_| {
+            invalid(
+                "export.status",
+                "unknown snapshot export status".to_string(),
+            )
+        })?;
+        let target = match value.target {
+            Some(target) => BackupTarget::try_from(target)?,
+            None => {
+                return Err(ReplyError::missing_argument(
+                    ResourceKind::SnapshotExport,
+                    "export.target",
+                ))
+            }
+        };
+        let base = match value.base {
+            Some(base) => Some(
+                SnapshotExportId::try_from(base.as_str())
+                    .map_err(|error| invalid("export.base", error.to_string()))?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            id: SnapshotExportId::try_from(value.id.as_str())
+                .map_err(|error| invalid("export.id", error.to_string()))?,
+            snapshot_id: SnapshotId::try_from(value.snapshot_id.as_str())
+                .map_err(|error| invalid("export.snapshot_id", error.to_string()))?,
+            volume_id: VolumeId::try_from(value.volume_id.as_str())
+                .map_err(|error| invalid("export.volume_id", error.to_string()))?,
+            target,
+            base,
+            size: value.size,
+            transfer_volume: VolumeId::try_from(value.transfer_volume.as_str())
+                .map_err(|error| invalid("export.transfer_volume", error.to_string()))?,
+            status: status.into(),
+            timestamp: value
+                .timestamp
+                .and_then(|timestamp| std::time::SystemTime::try_from(timestamp).ok())
+                .map(Into::into),
+            stats: value.stats.map(|stats| SnapshotExportStats {
+                chunks: stats.chunks,
+                changed_chunks: stats.changed_chunks,
+                written_bytes: stats.written_bytes,
+            }),
+        })
+    }
+}
+
+impl TryFrom<volume::SnapshotExports> for SnapshotExports {
+    type Error = ReplyError;
+    fn try_from(value: volume::SnapshotExports) -> Result<Self, Self::Error> {
+        let exports = value
+            .exports
+            .into_iter()
+            .map(SnapshotExportSpec::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(SnapshotExports(exports))
+    }
+}
+
+impl From<SnapshotExports> for volume::SnapshotExports {
+    fn from(value: SnapshotExports) -> Self {
+        Self {
+            exports: value.into_inner().into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{
+    common,
+    misc::traits::ValidateRequestTypes,
+    volume,
+    volume::{CreateSnapshotGroupRequest, DestroySnapshotGroupRequest},
+};
+use std::convert::TryFrom;
+use stor_port::{
+    transport_api::{v0::SnapshotGroups, ReplyError, ResourceKind},
+    types::v0::{
+        store::snapshots::group::{
+            SnapshotGroupMember, SnapshotGroupSpec, SnapshotGroupStatus, SnapshotGroupTarget,
+        },
+        transport::{
+            CreateSnapshotGroup, DestroySnapshotGroup, SnapshotGroup, SnapshotGroupId, SnapshotId,
+            VolumeId,
+        },
+    },
+};
+
+/// Snapshot group creation information.
+pub trait CreateSnapshotGroupInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the snapshot group.
+    fn id(&self) -> SnapshotGroupId;
+    /// The volumes which are snapshotted by the group.
+    fn target(&self) -> SnapshotGroupTarget;
+}
+
+/// Snapshot group deletion information.
+pub trait DestroySnapshotGroupInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the snapshot group.
+    fn id(&self) -> SnapshotGroupId;
+}
+
+impl CreateSnapshotGroupInfo for CreateSnapshotGroup {
+    fn id(&self) -> SnapshotGroupId {
+        self.id.clone()
+    }
+
+    fn target(&self) -> SnapshotGroupTarget {
+        self.target.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to CreateSnapshotGroupRequest type.
+#[derive(Debug)]
+pub struct ValidatedCreateSnapshotGroupRequest {
+    inner: CreateSnapshotGroup,
+}
+
+impl CreateSnapshotGroupInfo for ValidatedCreateSnapshotGroupRequest {
+    fn id(&self) -> SnapshotGroupId {
+        self.inner.id.clone()
+    }
+
+    fn target(&self) -> SnapshotGroupTarget {
+        self.inner.target.clone()
+    }
+}
+
+impl ValidateRequestTypes for CreateSnapshotGroupRequest {
+    type Validated = ValidatedCreateSnapshotGroupRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        if self.id.is_empty() {
+            return Err(ReplyError::missing_argument(
+                ResourceKind::SnapshotGroup,
+                "id",
+            ));
+        }
+        let Some(target) = self.target else {
+            return Err(ReplyError::missing_argument(
+                ResourceKind::SnapshotGroup,
+                "target",
+            ));
+        };
+        Ok(ValidatedCreateSnapshotGroupRequest {
+            inner: CreateSnapshotGroup::new(self.id.into(), SnapshotGroupTarget::try_from(target)?),
+        })
+    }
+}
+
+impl DestroySnapshotGroupInfo for DestroySnapshotGroup {
+    fn id(&self) -> SnapshotGroupId {
+        self.id.clone()
+    }
+}
+
+impl DestroySnapshotGroupInfo for DestroySnapshotGroupRequest {
+    fn id(&self) -> SnapshotGroupId {
+        self.id.clone().into()
+    }
+}
+
+impl From<&dyn CreateSnapshotGroupInfo> for CreateSnapshotGroup {
+    fn from(data: &dyn CreateSnapshotGroupInfo) -> Self {
+        Self::new(data.id(), data.target())
+    }
+}
+
+impl From<&dyn CreateSnapshotGroupInfo> for CreateSnapshotGroupRequest {
+    fn from(data: &dyn CreateSnapshotGroupInfo) -> Self {
+        Self {
+            id: data.id().to_string(),
+            target: Some(data.target().into()),
+        }
+    }
+}
+
+impl From<&dyn DestroySnapshotGroupInfo> for DestroySnapshotGroup {
+    fn from(data: &dyn DestroySnapshotGroupInfo) -> Self {
+        Self::new(data.id())
+    }
+}
+
+impl From<&dyn DestroySnapshotGroupInfo> for DestroySnapshotGroupRequest {
+    fn from(data: &dyn DestroySnapshotGroupInfo) -> Self {
+        Self {
+            id: data.id().to_string(),
+        }
+    }
+}
+
+impl From<SnapshotGroupTarget> for volume::SnapshotGroupTarget {
+    fn from(value: SnapshotGroupTarget) -> Self {
+        let target = match value {
+            SnapshotGroupTarget::Volumes(volumes) => {
+                volume::snapshot_group_target::Target::Volumes(
+                    volume::snapshot_group_target::VolumeIds {
+                        ids: volumes.iter().map(ToString::to_string).collect(),
+                    },
+                )
+            }
+            SnapshotGroupTarget::Selector(selector) => {
+                volume::snapshot_group_target::Target::Selector(common::StringMapValue {
+                    value: selector,
+                })
+            }
+            SnapshotGroupTarget::AffinityGroup(affinity_group) => {
+                volume::snapshot_group_target::Target::AffinityGroup(affinity_group)
+            }
+        };
+        Self {
+            target: Some(target),
+        }
+    }
+}
+
+impl TryFrom<volume::SnapshotGroupTarget> for SnapshotGroupTarget {
+    type Error = ReplyError;
+    fn try_from(value: volume::SnapshotGroupTarget) -> Result<Self, Self::Error> {
+        match value.target {
+            Some(volume::snapshot_group_target::Target::Volumes(volumes))
+                if !volumes.ids.is_empty() =>
+            {
+                let volumes = volumes
+                    .ids
+                    .into_iter()
+                    .map(|volume_id| {
+                        VolumeId::try_from(volume_id).map_err(|error| {
+                            ReplyError::invalid_argument(
+                                ResourceKind::SnapshotGroup,
+                                "target.volumes",
+                                error,
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Self::Volumes(volumes))
+            }
+            Some(volume::snapshot_group_target::Target::Selector(selector))
+                if !selector.value.is_empty() =>
+            {
+                Ok(Self::Selector(selector.value))
+            }
+            Some(volume::snapshot_group_target::Target::AffinityGroup(affinity_group))
+                if !affinity_group.is_empty() =>
+            {
+                Ok(Self::AffinityGroup(affinity_group))
+            }
+            Some(_) => Err(ReplyError::invalid_argument(
+                ResourceKind::SnapshotGroup,
+                "target",
+                "the target must not be empty",
+            )),
+            None => Err(ReplyError::missing_argument(
+                ResourceKind::SnapshotGroup,
+                "target",
+            )),
+        }
+    }
+}
+
+impl From<SnapshotGroupStatus> for volume::SnapshotGroupStatus {
+    fn from(value: SnapshotGroupStatus) -> Self {
+        match value {
+            SnapshotGroupStatus::Creating => Self::Creating,
+            SnapshotGroupStatus::Created => Self::Created,
+            SnapshotGroupStatus::Deleting => Self::Deleting,
+        }
+    }
+}
+
+impl From<volume::SnapshotGroupStatus> for SnapshotGroupStatus {
+    fn from(value: volume::SnapshotGroupStatus) -> Self {
+        match value {
+            volume::SnapshotGroupStatus::Creating => Self::Creating,
+            volume::SnapshotGroupStatus::Created => Self::Created,
+            volume::SnapshotGroupStatus::Deleting => Self::Deleting,
+        }
+    }
+}
+
+impl From<SnapshotGroup> for volume::SnapshotGroup {
+    fn from(value: SnapshotGroup) -> Self {
+        let members = value
+            .spec()
+            .members
+            .iter()
+            .map(|member| volume::SnapshotGroupMember {
+                volume_id: member.volume_id.to_string(),
+                snapshot_id: member.snapshot_id.to_string(),
+                created: value.member_created(member),
+            })
+            .collect();
+        let spec = value.spec();
+        Self {
+            id: spec.id.to_string(),
+            target: Some(spec.target.clone().into()),
+            members,
+            status: volume::SnapshotGroupStatus::from(spec.status) as i32,
+            timestamp: spec
+                .timestamp
+                .map(|timestamp| std::time::SystemTime::from(timestamp).into()),
+        }
+    }
+}
+
+impl TryFrom<volume::SnapshotGroup> for SnapshotGroup {
+    type Error = ReplyError;
+    fn try_from(value: volume::SnapshotGroup) -> Result<Self, Self::Error> {
+        let status = volume::SnapshotGroupStatus::try_from(value.status).map_err(|_| {
+            ReplyError::invalid_argument(
+                ResourceKind::SnapshotGroup,
+                "group.status",
+                "unknown snapshot group status",
+            )
+        })?;
+        let target = match value.target {
+            Some(target) => SnapshotGroupTarget::try_from(target)?,
+            None => {
+                return Err(ReplyError::missing_argument(
+                    ResourceKind::SnapshotGroup,
+                    "group.target",
+                ))
+            }
+        };
+        let mut created = vec![];
+        let mut members = vec![];
+        for member in value.members {
+            let member_created = member.created;
+            let member = SnapshotGroupMember {
+                volume_id: VolumeId::try_from(member.volume_id).map_err(|error| {
+                    ReplyError::invalid_argument(
+                        ResourceKind::SnapshotGroup,
+                        "group.members.volume_id",
+                        error,
+                    )
+                })?,
+                snapshot_id: SnapshotId::try_from(member.snapshot_id).map_err(|error| {
+                    ReplyError::invalid_argument(
+                        ResourceKind::SnapshotGroup,
+                        "group.members.snapshot_id",
+                        error,
+                    )
+                })?,
+            };
+            if member_created {
+                created.push(member.snapshot_id.clone());
+            }
+            members.push(member);
+        }
+        let spec = SnapshotGroupSpec {
+            id: value.id.into(),
+            target,
+            members,
+            status: status.into(),
+            timestamp: value
+                .timestamp
+                .and_then(|timestamp| std::time::SystemTime::try_from(timestamp).ok())
+                .map(Into::into),
+        };
+        Ok(SnapshotGroup::new(spec, created))
+    }
+}
+
+impl TryFrom<volume::SnapshotGroups> for SnapshotGroups {
+    type Error = ReplyError;
+    fn try_from(value: volume::SnapshotGroups) -> Result<Self, Self::Error> {
+        let groups = value
+            .groups
+            .into_iter()
+            .map(SnapshotGroup::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(SnapshotGroups(groups))
+    }
+}
+
+impl From<SnapshotGroups> for volume::SnapshotGroups {
+    fn from(value: SnapshotGroups) -> Self {
+        Self {
+            groups: value.into_inner().into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{
+    common,
+    misc::traits::ValidateRequestTypes,
+    volume,
+    volume::{CreateSnapshotScheduleRequest, DestroySnapshotScheduleRequest},
+};
+use std::{convert::TryFrom, time::Duration};
+use stor_port::{
+    transport_api::{v0::SnapshotSchedules, ReplyError, ResourceKind},
+    types::v0::{
+        store::snapshots::schedule::{
+            SnapshotRetention, SnapshotScheduleSpec, SnapshotScheduleTarget,
+            SnapshotScheduleTrigger,
+        },
+        transport::{
+            CreateSnapshotSchedule, DestroySnapshotSchedule, SnapshotScheduleId, VolumeId,
+        },
+    },
+};
+
+/// Snapshot schedule creation information.
+pub trait CreateSnapshotScheduleInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the snapshot schedule.
+    fn id(&self) -> SnapshotScheduleId;
+    /// The volumes which are snapshotted by the schedule.
+    fn target(&self) -> SnapshotScheduleTarget;
+    /// When the snapshots of a volume are taken.
+    fn trigger(&self) -> SnapshotScheduleTrigger;
+    /// Retention policy for the scheduled snapshots.
+    fn retention(&self) -> SnapshotRetention;
+    /// Quiesce the filesystem of published volumes before taking the snapshot.
+    fn quiesce(&self) -> bool;
+}
+
+/// Snapshot schedule deletion information.
+pub trait DestroySnapshotScheduleInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the snapshot schedule.
+    fn id(&self) -> SnapshotScheduleId;
+}
+
+impl CreateSnapshotScheduleInfo for CreateSnapshotSchedule {
+    fn id(&self) -> SnapshotScheduleId {
+        self.id.clone()
+    }
+
+    fn target(&self) -> SnapshotScheduleTarget {
+        self.target.clone()
+    }
+
+    fn trigger(&self) -> SnapshotScheduleTrigger {
+        self.trigger.clone()
+    }
+
+    fn retention(&self) -> SnapshotRetention {
+        self.retention.clone()
+    }
+
+    fn quiesce(&self) -> bool {
+        self.quiesce
+    }
+}
+
+/// Intermediate structure that validates the conversion to CreateSnapshotScheduleRequest type.
+#[derive(Debug)]
+pub struct ValidatedCreateSnapshotScheduleRequest {
+    inner: SnapshotScheduleSpec,
+}
+
+impl CreateSnapshotScheduleInfo for ValidatedCreateSnapshotScheduleRequest {
+    fn id(&self) -> SnapshotScheduleId {
+        self.inner.id.clone()
+    }
+
+    fn target(&self) -> SnapshotScheduleTarget {
+        self.inner.target.clone()
+    }
+
+    fn trigger(&self) -> SnapshotScheduleTrigger {
+        self.inner.trigger.clone()
+    }
+
+    fn retention(&self) -> SnapshotRetention {
+        self.inner.retention.clone()
+    }
+
+    fn quiesce(&self) -> bool {
+        self.inner.quiesce
+    }
+}
+
+impl ValidateRequestTypes for CreateSnapshotScheduleRequest {
+    type Validated = ValidatedCreateSnapshotScheduleRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        let Some(schedule) = self.schedule else {
+            return Err(ReplyError::missing_argument(
+                ResourceKind::SnapshotSchedule,
+                "schedule",
+            ));
+        };
+        Ok(ValidatedCreateSnapshotScheduleRequest {
+            inner: SnapshotScheduleSpec::try_from(schedule)?,
+        })
+    }
+}
+
+impl DestroySnapshotScheduleInfo for DestroySnapshotSchedule {
+    fn id(&self) -> SnapshotScheduleId {
+        self.id.clone()
+    }
+}
+
+impl DestroySnapshotScheduleInfo for DestroySnapshotScheduleRequest {
+    fn id(&self) -> SnapshotScheduleId {
+        self.id.clone().into()
+    }
+}
+
+impl From<&dyn CreateSnapshotScheduleInfo> for CreateSnapshotSchedule {
+    fn from(data: &dyn CreateSnapshotScheduleInfo) -> Self {
+        Self::new(
+            data.id(),
+            data.target(),
+            data.trigger(),
+            data.retention(),
+            data.quiesce(),
+        )
+    }
+}
+
+impl From<&dyn CreateSnapshotScheduleInfo> for CreateSnapshotScheduleRequest {
+    fn from(data: &dyn CreateSnapshotScheduleInfo) -> Self {
+        let schedule = SnapshotScheduleSpec::from(&CreateSnapshotSchedule::from(data));
+        Self {
+            schedule: Some(schedule.into()),
+        }
+    }
+}
+
+impl From<&dyn DestroySnapshotScheduleInfo> for DestroySnapshotSchedule {
+    fn from(data: &dyn DestroySnapshotScheduleInfo) -> Self {
+        Self::new(data.id())
+    }
+}
+
+impl From<&dyn DestroySnapshotScheduleInfo> for DestroySnapshotScheduleRequest {
+    fn from(data: &dyn DestroySnapshotScheduleInfo) -> Self {
+        Self {
+            id: data.id().to_string(),
+        }
+    }
+}
+
+impl From<SnapshotScheduleSpec> for volume::SnapshotSchedule {
+    fn from(value: SnapshotScheduleSpec) -> Self {
+        Self {
+            id: value.id.to_string(),
+            target: Some(match value.target {
+                SnapshotScheduleTarget::Volume(volume_id) => {
+                    volume::snapshot_schedule::Target::VolumeId(volume_id.to_string())
+                }
+                SnapshotScheduleTarget::Selector(selector) => {
+                    volume::snapshot_schedule::Target::Selector(common::StringMapValue {
+                        value: selector,
+                    })
+                }
+            }),
+            trigger: Some(match value.trigger {
+                SnapshotScheduleTrigger::Interval(interval) => {
+                    volume::snapshot_schedule::Trigger::IntervalSecs(interval.as_secs())
+                }
+                SnapshotScheduleTrigger::Cron(cron) => {
+                    volume::snapshot_schedule::Trigger::Cron(cron.to_string())
+                }
+            }),
+            retention: Some(volume::SnapshotRetention {
+                count: value.retention.count,
+                max_age_secs: value.retention.max_age.map(|age| age.as_secs()),
+            }),
+            quiesce: value.quiesce,
+        }
+    }
+}
+
+impl TryFrom<volume::SnapshotSchedule> for SnapshotScheduleSpec {
+    type Error = ReplyError;
+    fn try_from(value: volume::SnapshotSchedule) -> Result<Self, Self::Error> {
+        if value.id.is_empty() {
+            return Err(ReplyError::missing_argument(
+                ResourceKind::SnapshotSchedule,
+                "schedule.id",
+            ));
+        }
+        let target = match value.target {
+            Some(volume::snapshot_schedule::Target::VolumeId(volume_id)) => {
+                SnapshotScheduleTarget::Volume(VolumeId::try_from(volume_id).map_err(|error| {
+                    ReplyError::invalid_argument(
+                        ResourceKind::SnapshotSchedule,
+                        "schedule.volume_id",
+                        error,
+                    )
+                })?)
+            }
+            Some(volume::snapshot_schedule::Target::Selector(selector))
+                if !selector.value.is_empty() =>
+            {
+                SnapshotScheduleTarget::Selector(selector.value)
+            }
+            Some(volume::snapshot_schedule::Target::Selector(_)) => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::SnapshotSchedule,
+                    "schedule.selector",
+                    "the label selector must not be empty",
+                ))
+            }
+            None => {
+                return Err(ReplyError::missing_argument(
+                    ResourceKind::SnapshotSchedule,
+                    "schedule.target",
+                ))
+            }
+        };
+        let trigger = match value.trigger {
+            Some(volume::snapshot_schedule::Trigger::IntervalSecs(0)) => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::SnapshotSchedule,
+                    "schedule.interval_secs",
+                    "the interval must not be zero",
+                ))
+            }
+            Some(volume::snapshot_schedule::Trigger::IntervalSecs(secs)) => {
+                SnapshotScheduleTrigger::Interval(Duration::from_secs(secs))
+            }
+            Some(volume::snapshot_schedule::Trigger::Cron(cron)) => {
+                SnapshotScheduleTrigger::Cron(cron.parse().map_err(|error| {
+                    ReplyError::invalid_argument(
+                        ResourceKind::SnapshotSchedule,
+                        "schedule.cron",
+                        error,
+                    )
+                })?)
+            }
+            None => {
+                return Err(ReplyError::missing_argument(
+                    ResourceKind::SnapshotSchedule,
+                    "schedule.trigger",
+                ))
+            }
+        };
+        let retention = value.retention.unwrap_or_default();
+        Ok(SnapshotScheduleSpec::new(
+            value.id.into(),
+            target,
+            trigger,
+            SnapshotRetention {
+                count: retention.count,
+                max_age: retention.max_age_secs.map(Duration::from_secs),
+            },
+            value.quiesce,
+        ))
+    }
+}
+
+impl TryFrom<volume::SnapshotSchedules> for SnapshotSchedules {
+    type Error = ReplyError;
+    fn try_from(value: volume::SnapshotSchedules) -> Result<Self, Self::Error> {
+        let schedules = value
+            .schedules
+            .into_iter()
+            .map(SnapshotScheduleSpec::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(SnapshotSchedules(schedules))
+    }
+}
+
+impl From<SnapshotSchedules> for volume::SnapshotSchedules {
+    fn from(value: SnapshotSchedules) -> Self {
+        Self {
+            schedules: value.into_inner().into_iter().map(Into::into).collect(),
+        }
+    }
+}
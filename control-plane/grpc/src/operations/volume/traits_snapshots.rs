@@ -36,6 +36,12 @@ pub trait DestroyVolumeSnapshotInfo: Send + Sync + std::fmt::Debug {
     fn info(&self) -> SnapshotInfo<Option<VolumeId>>;
 }
 
+/// Volume snapshot restore information.
+pub trait RestoreVolumeSnapshotInfo: Send + Sync + std::fmt::Debug {
+    /// Snapshot restore information.
+    fn info(&self) -> SnapshotInfo<VolumeId>;
+}
+
 /// A volume snapshot.
 #[derive(Debug)]
 pub struct VolumeSnapshot {
@@ -368,6 +374,9 @@ pub type CreateVolumeSnapshot = SnapshotInfo<VolumeId>;
 /// Validated delete volume snapshot parameters.
 pub type DestroyVolumeSnapshot = SnapshotInfo<Option<VolumeId>>;
 
+/// Validated restore volume snapshot parameters.
+pub type RestoreVolumeSnapshot = SnapshotInfo<VolumeId>;
+
 impl ValidateRequestTypes for volume::CreateSnapshotRequest {
     type Validated = CreateVolumeSnapshot;
     fn validated(self) -> Result<Self::Validated, ReplyError> {
@@ -395,6 +404,19 @@ impl ValidateRequestTypes for volume::DestroySnapshotRequest {
         })
     }
 }
+impl ValidateRequestTypes for volume::RestoreSnapshotRequest {
+    type Validated = RestoreVolumeSnapshot;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(RestoreVolumeSnapshot {
+            source_id: self
+                .volume_id
+                .try_into_id(ResourceKind::VolumeSnapshot, "volume_id")?,
+            snap_id: self
+                .snapshot_id
+                .try_into_id(ResourceKind::VolumeSnapshot, "snapshot_id")?,
+        })
+    }
+}
 
 impl CreateVolumeSnapshotInfo for CreateVolumeSnapshot {
     fn info(&self) -> CreateVolumeSnapshot {
@@ -406,6 +428,11 @@ impl DestroyVolumeSnapshotInfo for DestroyVolumeSnapshot {
         self.clone()
     }
 }
+impl RestoreVolumeSnapshotInfo for RestoreVolumeSnapshot {
+    fn info(&self) -> RestoreVolumeSnapshot {
+        self.clone()
+    }
+}
 
 impl From<&dyn CreateVolumeSnapshotInfo> for volume::CreateSnapshotRequest {
     fn from(value: &dyn CreateVolumeSnapshotInfo) -> Self {
@@ -425,6 +452,15 @@ impl From<&dyn DestroyVolumeSnapshotInfo> for volume::DestroySnapshotRequest {
         }
     }
 }
+impl From<&dyn RestoreVolumeSnapshotInfo> for volume::RestoreSnapshotRequest {
+    fn from(value: &dyn RestoreVolumeSnapshotInfo) -> Self {
+        let info = value.info();
+        Self {
+            volume_id: info.source_id.to_string(),
+            snapshot_id: info.snap_id.to_string(),
+        }
+    }
+}
 
 impl TryFrom<volume::VolumeSnapshots> for VolumeSnapshots {
     type Error = ReplyError;
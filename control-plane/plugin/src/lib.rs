@@ -10,13 +10,16 @@ use utils::tracing_telemetry::{FmtLayer, FmtStyle};
 
 use crate::{
     operations::{
-        Cordoning, Drain, Get, GetBlockDevices, GetSnapshotTopology, GetSnapshots, GetWithArgs,
-        List, ListExt, ListWithArgs, Operations, PluginResult, RebuildHistory, ReplicaTopology,
-        Scale,
+        Backup, Cloning, Cordoning, CreateWithArgs, Delete, Drain, Evacuate, Get, GetBlockDevices,
+        GetSnapshotTopology, GetSnapshots, GetWithArgs, List, ListExt, ListWithArgs, Operations,
+        PluginResult, RebuildHistory, ReplicaMove, ReplicaTopology, Restoring, Scale, TargetMove,
     },
     resources::{
-        blockdevice, cordon, drain, node, pool, snapshot, volume, CordonResources, DrainResources,
-        GetCordonArgs, GetDrainArgs, GetResources, ScaleResources, SetPropertyResources,
+        blockdevice, cordon, drain, node, pool, snapshot, snapshot_export, snapshot_group,
+        snapshot_schedule, specs, switchover, volume,
+        BackupResources, CloneResources, CordonResources, CreateResources, DeleteResources,
+        DrainResources, EvacuateResources, GetCordonArgs, GetDrainArgs, GetResources,
+        MoveResources, ResizeResources, RestoreResources, ScaleResources, SetPropertyResources,
         SetVolumeProperties, UnCordonResources,
     },
 };
@@ -89,10 +92,18 @@ impl ExecuteOperation for Operations {
             Operations::Drain(resource) => resource.execute(cli_args).await,
             Operations::Get(resource) => resource.execute(cli_args).await,
             Operations::Scale(resource) => resource.execute(cli_args).await,
+            Operations::Resize(resource) => resource.execute(cli_args).await,
             Operations::Set(resource) => resource.execute(cli_args).await,
             Operations::Cordon(resource) => resource.execute(cli_args).await,
             Operations::Uncordon(resource) => resource.execute(cli_args).await,
+            Operations::Evacuate(resource) => resource.execute(cli_args).await,
             Operations::Label(resource) => resource.execute(cli_args).await,
+            Operations::Clone(resource) => resource.execute(cli_args).await,
+            Operations::Move(resource) => resource.execute(cli_args).await,
+            Operations::Restore(resource) => resource.execute(cli_args).await,
+            Operations::Create(resource) => resource.execute(cli_args).await,
+            Operations::Delete(resource) => resource.execute(cli_args).await,
+            Operations::Backup(resource) => resource.execute(cli_args).await,
         }
     }
 }
@@ -138,8 +149,8 @@ impl ExecuteOperation for GetResources {
                 volume::Volumes::list(&cli_args.output, vol_args).await
             }
             GetResources::Volume { id } => volume::Volume::get(id, &cli_args.output).await,
-            GetResources::RebuildHistory { id } => {
-                volume::Volume::rebuild_history(id, &cli_args.output).await
+            GetResources::RebuildHistory(args) => {
+                volume::Volume::rebuild_history(args, &cli_args.output).await
             }
             GetResources::VolumeReplicaTopologies(vol_args) => {
                 volume::Volume::topologies(&cli_args.output, vol_args).await
@@ -177,6 +188,25 @@ impl ExecuteOperation for GetResources {
                 )
                 .await
             }
+            GetResources::SnapshotSchedules => {
+                snapshot_schedule::SnapshotSchedules::list(&cli_args.output).await
+            }
+            GetResources::SnapshotSchedule { id } => {
+                snapshot_schedule::SnapshotSchedule::get(id, &cli_args.output).await
+            }
+            GetResources::SnapshotGroups => {
+                snapshot_group::SnapshotGroups::list(&cli_args.output).await
+            }
+            GetResources::SnapshotGroup { id } => {
+                snapshot_group::SnapshotGroup::get(id, &cli_args.output).await
+            }
+            GetResources::SnapshotExports => {
+                snapshot_export::SnapshotExports::list(&cli_args.output).await
+            }
+            GetResources::SnapshotExport { id } => {
+                snapshot_export::SnapshotExport::get(id, &cli_args.output).await
+            }
+            GetResources::SwitchOvers => switchover::SwitchOvers::list(&cli_args.output).await,
         }
     }
 }
@@ -194,6 +224,137 @@ impl ExecuteOperation for ScaleResources {
     }
 }
 
+#[async_trait::async_trait(?Send)]
+impl ExecuteOperation for ResizeResources {
+    type Args = CliArgs;
+    type Error = crate::resources::Error;
+    async fn execute(&self, cli_args: &CliArgs) -> PluginResult {
+        match self {
+            ResizeResources::Volume {
+                id,
+                size,
+                allow_shrink,
+                filesystem_size,
+            } => {
+                volume::Volume::resize(
+                    id,
+                    *size,
+                    *allow_shrink,
+                    *filesystem_size,
+                    &cli_args.output,
+                )
+                .await
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ExecuteOperation for CloneResources {
+    type Args = CliArgs;
+    type Error = crate::resources::Error;
+    async fn execute(&self, cli_args: &CliArgs) -> PluginResult {
+        match self {
+            CloneResources::Volume {
+                id,
+                clone_id,
+                replicas,
+            } => volume::Volume::create_clone(id, clone_id, *replicas, &cli_args.output).await,
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ExecuteOperation for MoveResources {
+    type Args = CliArgs;
+    type Error = crate::resources::Error;
+    async fn execute(&self, cli_args: &CliArgs) -> PluginResult {
+        match self {
+            MoveResources::Replica {
+                id,
+                replica_id,
+                cancel: true,
+                ..
+            } => volume::Volume::cancel_replica_move(id, replica_id, &cli_args.output).await,
+            MoveResources::Replica {
+                id,
+                replica_id,
+                pool,
+                node,
+                cancel: false,
+            } => volume::Volume::move_replica(id, replica_id, pool, node, &cli_args.output).await,
+            MoveResources::Target { id, node } => {
+                volume::Volume::move_target(id, node, &cli_args.output).await
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ExecuteOperation for RestoreResources {
+    type Args = CliArgs;
+    type Error = crate::resources::Error;
+    async fn execute(&self, cli_args: &CliArgs) -> PluginResult {
+        match self {
+            RestoreResources::Volume { id, snapshot_id } => {
+                volume::Volume::restore_snapshot(id, snapshot_id, &cli_args.output).await
+            }
+            RestoreResources::Specs { file } => {
+                specs::Specs::restore(file, &cli_args.output).await
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ExecuteOperation for BackupResources {
+    type Args = CliArgs;
+    type Error = crate::resources::Error;
+    async fn execute(&self, cli_args: &CliArgs) -> PluginResult {
+        match self {
+            BackupResources::Specs { file } => specs::Specs::backup(file, &cli_args.output).await,
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ExecuteOperation for CreateResources {
+    type Args = CliArgs;
+    type Error = crate::resources::Error;
+    async fn execute(&self, cli_args: &CliArgs) -> PluginResult {
+        match self {
+            CreateResources::SnapshotSchedule { id, args } => {
+                snapshot_schedule::SnapshotSchedule::create(id, args, &cli_args.output).await
+            }
+            CreateResources::SnapshotGroup { id, args } => {
+                snapshot_group::SnapshotGroup::create(id, args, &cli_args.output).await
+            }
+            CreateResources::SnapshotExport { id, args } => {
+                snapshot_export::SnapshotExport::create(id, args, &cli_args.output).await
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ExecuteOperation for DeleteResources {
+    type Args = CliArgs;
+    type Error = crate::resources::Error;
+    async fn execute(&self, cli_args: &CliArgs) -> PluginResult {
+        match self {
+            DeleteResources::SnapshotSchedule { id } => {
+                snapshot_schedule::SnapshotSchedule::delete(id, &cli_args.output).await
+            }
+            DeleteResources::SnapshotGroup { id } => {
+                snapshot_group::SnapshotGroup::delete(id, &cli_args.output).await
+            }
+            DeleteResources::SnapshotExport { id } => {
+                snapshot_export::SnapshotExport::delete(id, &cli_args.output).await
+            }
+        }
+    }
+}
+
 #[async_trait::async_trait(?Send)]
 impl ExecuteOperation for SetPropertyResources {
     type Args = CliArgs;
@@ -216,6 +377,9 @@ impl ExecuteOperation for CordonResources {
             CordonResources::Node { id, label } => {
                 node::Node::cordon(id, label, &cli_args.output).await
             }
+            CordonResources::Pool { id, label } => {
+                pool::Pool::cordon(id, label, &cli_args.output).await
+            }
         }
     }
 }
@@ -229,6 +393,25 @@ impl ExecuteOperation for UnCordonResources {
             UnCordonResources::Node { id, label } => {
                 node::Node::uncordon(id, label, &cli_args.output).await
             }
+            UnCordonResources::Pool { id, label } => {
+                pool::Pool::uncordon(id, label, &cli_args.output).await
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ExecuteOperation for EvacuateResources {
+    type Args = CliArgs;
+    type Error = crate::resources::Error;
+    async fn execute(&self, cli_args: &CliArgs) -> PluginResult {
+        match self {
+            EvacuateResources::Pool { id, label } => {
+                pool::Pool::evacuate(id, label, &cli_args.output).await
+            }
+            EvacuateResources::Node { id, label } => {
+                pool::Pools::evacuate(id, label, &cli_args.output).await
+            }
         }
     }
 }
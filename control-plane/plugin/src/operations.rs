@@ -1,6 +1,8 @@
 use crate::resources::{
-    error::Error, utils, CordonResources, DrainResources, GetResources, LabelResources,
-    ScaleResources, SetPropertyResources, UnCordonResources,
+    error::Error, utils, BackupResources, CloneResources, CordonResources, CreateResources,
+    DeleteResources, DrainResources, EvacuateResources, GetResources, LabelResources,
+    MoveResources, NodeId, PoolId, ResizeResources, RestoreResources, ScaleResources,
+    SetPropertyResources, UnCordonResources,
 };
 use async_trait::async_trait;
 
@@ -19,6 +21,9 @@ pub enum Operations {
     /// 'Scale' resources.
     #[clap(subcommand)]
     Scale(ScaleResources),
+    /// 'Resize' resources.
+    #[clap(subcommand)]
+    Resize(ResizeResources),
     /// 'Set' resources.
     #[clap(subcommand)]
     Set(SetPropertyResources),
@@ -28,9 +33,30 @@ pub enum Operations {
     /// 'Uncordon' resources.
     #[clap(subcommand)]
     Uncordon(UnCordonResources),
+    /// 'Evacuate' resources.
+    #[clap(subcommand)]
+    Evacuate(EvacuateResources),
     /// 'Label' resources.
     #[clap(subcommand)]
     Label(LabelResources),
+    /// 'Clone' resources.
+    #[clap(subcommand)]
+    Clone(CloneResources),
+    /// 'Move' resources.
+    #[clap(subcommand)]
+    Move(MoveResources),
+    /// 'Restore' resources.
+    #[clap(subcommand)]
+    Restore(RestoreResources),
+    /// 'Create' resources.
+    #[clap(subcommand)]
+    Create(CreateResources),
+    /// 'Delete' resources.
+    #[clap(subcommand)]
+    Delete(DeleteResources),
+    /// 'Backup' resources.
+    #[clap(subcommand)]
+    Backup(BackupResources),
 }
 
 /// Drain trait.
@@ -46,6 +72,14 @@ pub trait Drain {
     ) -> PluginResult;
 }
 
+/// Evacuate trait.
+/// To be implemented by resources which support the 'evacuate' operation.
+#[async_trait(?Send)]
+pub trait Evacuate {
+    type ID;
+    async fn evacuate(id: &Self::ID, label: &str, output: &utils::OutputFormat) -> PluginResult;
+}
+
 /// Label trait.
 /// To be implemented by resources which support the 'label' operation.
 #[async_trait(?Send)]
@@ -99,6 +133,24 @@ pub trait GetWithArgs {
     async fn get(id: &Self::ID, args: &Self::Args, output: &utils::OutputFormat) -> PluginResult;
 }
 
+/// CreateWithArgs trait.
+/// To be implemented by resources which support the 'create' operation with arguments.
+#[async_trait(?Send)]
+pub trait CreateWithArgs {
+    type ID;
+    type Args;
+    async fn create(id: &Self::ID, args: &Self::Args, output: &utils::OutputFormat)
+        -> PluginResult;
+}
+
+/// Delete trait.
+/// To be implemented by resources which support the 'delete' operation.
+#[async_trait(?Send)]
+pub trait Delete {
+    type ID;
+    async fn delete(id: &Self::ID, output: &utils::OutputFormat) -> PluginResult;
+}
+
 /// Scale trait.
 /// To be implemented by resources which support the 'scale' operation.
 #[async_trait(?Send)]
@@ -108,6 +160,74 @@ pub trait Scale {
     async fn resize(
         id: &Self::ID,
         requested_size: u64,
+        allow_shrink: bool,
+        filesystem_size: Option<u64>,
+        output: &utils::OutputFormat,
+    ) -> PluginResult;
+}
+
+/// Cloning trait.
+/// To be implemented by resources which support the 'clone' operation.
+#[async_trait(?Send)]
+pub trait Cloning {
+    type ID;
+    async fn create_clone(
+        id: &Self::ID,
+        clone_id: &Self::ID,
+        replica_count: Option<u8>,
+        output: &utils::OutputFormat,
+    ) -> PluginResult;
+}
+
+/// Restoring trait.
+/// To be implemented by resources which support the 'restore' operation.
+#[async_trait(?Send)]
+pub trait Restoring {
+    type ID;
+    type SnapshotID;
+    async fn restore_snapshot(
+        id: &Self::ID,
+        snapshot_id: &Self::SnapshotID,
+        output: &utils::OutputFormat,
+    ) -> PluginResult;
+}
+
+/// Backup trait.
+/// To be implemented by resources which support the 'backup' operation and restoring from it.
+#[async_trait(?Send)]
+pub trait Backup {
+    async fn backup(file: &std::path::Path, output: &utils::OutputFormat) -> PluginResult;
+    async fn restore(file: &std::path::Path, output: &utils::OutputFormat) -> PluginResult;
+}
+
+/// ReplicaMove trait.
+/// To be implemented by resources which support moving their replicas.
+#[async_trait(?Send)]
+pub trait ReplicaMove {
+    type ID;
+    type ReplicaID;
+    async fn move_replica(
+        id: &Self::ID,
+        replica_id: &Self::ReplicaID,
+        pool: &Option<PoolId>,
+        node: &Option<NodeId>,
+        output: &utils::OutputFormat,
+    ) -> PluginResult;
+    async fn cancel_replica_move(
+        id: &Self::ID,
+        replica_id: &Self::ReplicaID,
+        output: &utils::OutputFormat,
+    ) -> PluginResult;
+}
+
+/// TargetMove trait.
+/// To be implemented by resources which support moving their target.
+#[async_trait(?Send)]
+pub trait TargetMove {
+    type ID;
+    async fn move_target(
+        id: &Self::ID,
+        node: &Option<NodeId>,
         output: &utils::OutputFormat,
     ) -> PluginResult;
 }
@@ -139,8 +259,8 @@ pub trait ReplicaTopology {
 /// To be implemented by resources which support the 'rebuild-history' operation
 #[async_trait(?Send)]
 pub trait RebuildHistory {
-    type ID;
-    async fn rebuild_history(id: &Self::ID, output: &utils::OutputFormat) -> PluginResult;
+    type Args;
+    async fn rebuild_history(args: &Self::Args, output: &utils::OutputFormat) -> PluginResult;
 }
 
 /// GetBlockDevices trait.
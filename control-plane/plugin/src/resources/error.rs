@@ -56,16 +56,33 @@ pub enum Error {
     ListPoolsError {
         source: openapi::tower::client::Error<openapi::models::RestJsonError>,
     },
+    /// Error when pool cordon request fails.
+    #[snafu(display("Failed to cordon pool {id}. Error {source}"))]
+    PoolCordonError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when pool uncordon request fails.
+    #[snafu(display("Failed to uncordon pool {id}. Error {source}"))]
+    PoolUncordonError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when pool evacuate request fails.
+    #[snafu(display("Failed to evacuate pool {id}. Error {source}"))]
+    PoolEvacuateError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
     /// Error when get volume request fails.
     #[snafu(display("Failed to get volume {id}. Error {source}"))]
     GetVolumeError {
         id: String,
         source: openapi::tower::client::Error<openapi::models::RestJsonError>,
     },
-    /// Error when get rebuild history for volume request fails.
-    #[snafu(display("Failed to get rebuild history for volume {id}. Error {source}"))]
+    /// Error when get rebuild history for volumes request fails.
+    #[snafu(display("Failed to get rebuild history. Error {source}"))]
     GetRebuildHistory {
-        id: String,
         source: openapi::tower::client::Error<openapi::models::RestJsonError>,
     },
     /// Error when scale volume request fails.
@@ -80,6 +97,41 @@ pub enum Error {
         id: String,
         source: openapi::tower::client::Error<openapi::models::RestJsonError>,
     },
+    /// Error when clone volume request fails.
+    #[snafu(display("Failed to clone volume {id}. Error {source}"))]
+    CloneVolumeError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when restore volume snapshot request fails.
+    #[snafu(display("Failed to restore volume {id} to snapshot {snapshot_id}. Error {source}"))]
+    RestoreVolumeSnapshotError {
+        id: String,
+        snapshot_id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when move volume replica request fails.
+    #[snafu(display("Failed to move replica {replica_id} of volume {id}. Error {source}"))]
+    MoveVolumeReplicaError {
+        id: String,
+        replica_id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when cancel volume replica move request fails.
+    #[snafu(display(
+        "Failed to cancel the move of replica {replica_id} of volume {id}. Error {source}"
+    ))]
+    CancelVolumeReplicaMoveError {
+        id: String,
+        replica_id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when move volume target request fails.
+    #[snafu(display("Failed to move the target of volume {id}. Error {source}"))]
+    MoveVolumeTargetError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
     /// Error when set volume property request fails.
     #[snafu(display("Failed to set volume {id} property, Error {source}"))]
     SetVolumePropertyError {
@@ -91,6 +143,94 @@ pub enum Error {
     ListSnapshotsError {
         source: openapi::tower::client::Error<openapi::models::RestJsonError>,
     },
+    /// Error when list snapshot schedules request fails.
+    #[snafu(display("Failed to list snapshot schedules. Error {source}"))]
+    ListSnapshotSchedulesError {
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when get snapshot schedule request fails.
+    #[snafu(display("Failed to get snapshot schedule {id}. Error {source}"))]
+    GetSnapshotScheduleError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when create snapshot schedule request fails.
+    #[snafu(display("Failed to create snapshot schedule {id}. Error {source}"))]
+    CreateSnapshotScheduleError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when delete snapshot schedule request fails.
+    #[snafu(display("Failed to delete snapshot schedule {id}. Error {source}"))]
+    DeleteSnapshotScheduleError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when list snapshot groups request fails.
+    #[snafu(display("Failed to list snapshot groups. Error {source}"))]
+    ListSnapshotGroupsError {
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when get snapshot group request fails.
+    #[snafu(display("Failed to get snapshot group {id}. Error {source}"))]
+    GetSnapshotGroupError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when create snapshot group request fails.
+    #[snafu(display("Failed to create snapshot group {id}. Error {source}"))]
+    CreateSnapshotGroupError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when delete snapshot group request fails.
+    #[snafu(display("Failed to delete snapshot group {id}. Error {source}"))]
+    DeleteSnapshotGroupError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when list snapshot exports request fails.
+    #[snafu(display("Failed to list snapshot exports. Error {source}"))]
+    ListSnapshotExportsError {
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when list switchovers request fails.
+    #[snafu(display("Failed to list switchovers. Error {source}"))]
+    ListSwitchOversError {
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when get snapshot export request fails.
+    #[snafu(display("Failed to get snapshot export {id}. Error {source}"))]
+    GetSnapshotExportError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when create snapshot export request fails.
+    #[snafu(display("Failed to create snapshot export {id}. Error {source}"))]
+    CreateSnapshotExportError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when delete snapshot export request fails.
+    #[snafu(display("Failed to delete snapshot export {id}. Error {source}"))]
+    DeleteSnapshotExportError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when backup specs request fails.
+    #[snafu(display("Failed to backup the specs. Error {source}"))]
+    BackupSpecsError {
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when restore specs request fails.
+    #[snafu(display("Failed to restore the specs from {path}. Error {source}"))]
+    RestoreSpecsError {
+        path: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when the specs backup file cannot be written or read.
+    #[snafu(display("Failed to access the specs backup file {path}. Error {reason}"))]
+    SpecsBackupFileError { path: String, reason: String },
     /// Error when get pool request fails.
     #[snafu(display(
         "Error while parsing labels `{labels}`. \
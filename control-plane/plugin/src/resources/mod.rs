@@ -3,7 +3,10 @@ use crate::resources::{
     node::{DrainNodeArgs, GetNodeArgs, GetNodesArgs},
     pool::GetPoolsArgs,
     snapshot::VolumeSnapshotArgs,
-    volume::VolumesArgs,
+    snapshot_export::CreateSnapshotExportArgs,
+    snapshot_group::CreateSnapshotGroupArgs,
+    snapshot_schedule::CreateSnapshotScheduleArgs,
+    volume::{RebuildHistoryArgs, VolumesArgs},
 };
 
 pub mod blockdevice;
@@ -13,13 +16,22 @@ pub mod error;
 pub mod node;
 pub mod pool;
 pub mod snapshot;
+pub mod snapshot_export;
+pub mod snapshot_group;
+pub mod snapshot_schedule;
+pub mod specs;
+pub mod switchover;
 pub mod utils;
 pub mod volume;
 
 pub use error::Error;
 
 pub type VolumeId = openapi::apis::Uuid;
+pub type ReplicaId = openapi::apis::Uuid;
 pub type SnapshotId = openapi::apis::Uuid;
+pub type SnapshotScheduleId = String;
+pub type SnapshotGroupId = String;
+pub type SnapshotExportId = openapi::apis::Uuid;
 pub type ReplicaCount = u8;
 pub type PoolId = String;
 pub type NodeId = String;
@@ -37,8 +49,8 @@ pub enum GetResources {
     Volumes(VolumesArgs),
     /// Get volume with the given ID.
     Volume { id: VolumeId },
-    /// Get Rebuild history for the volume with the given ID.
-    RebuildHistory { id: VolumeId },
+    /// Get the persisted rebuild history of the volumes, optionally filtered.
+    RebuildHistory(RebuildHistoryArgs),
     /// Get the replica topology for all volumes.
     VolumeReplicaTopologies(VolumesArgs),
     /// Get the replica topology for the volume with the given ID.
@@ -48,6 +60,20 @@ pub enum GetResources {
 
     /// Get volume snapshot topology based on input args.
     VolumeSnapshotTopology(VolumeSnapshotArgs),
+    /// Get all snapshot schedules.
+    SnapshotSchedules,
+    /// Get snapshot schedule with the given ID.
+    SnapshotSchedule { id: SnapshotScheduleId },
+    /// Get all snapshot groups.
+    SnapshotGroups,
+    /// Get snapshot group with the given ID.
+    SnapshotGroup { id: SnapshotGroupId },
+    /// Get all snapshot exports.
+    SnapshotExports,
+    /// Get snapshot export with the given ID.
+    SnapshotExport { id: SnapshotExportId },
+    /// Get the in-flight volume switchovers, followed by the recently completed ones.
+    SwitchOvers,
     /// Get all pools.
     Pools(GetPoolsArgs),
     /// Get pool with the given ID.
@@ -74,6 +100,162 @@ pub enum ScaleResources {
     },
 }
 
+/// The types of resources that support the 'resize' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum ResizeResources {
+    /// Resize volume.
+    Volume {
+        /// ID of the volume.
+        id: VolumeId,
+        /// New size of the volume in bytes.
+        size: u64,
+        /// Allow the volume to be shrunk, if the new size is smaller than the current size.
+        /// Only unpublished, thin provisioned volumes without snapshots can be shrunk, and any
+        /// filesystem on the volume must already have been shrunk to fit within the new size.
+        #[clap(long, requires = "filesystem_size")]
+        allow_shrink: bool,
+        /// Size of the filesystem on the volume in bytes, or 0 if it has no filesystem.
+        /// Required to shrink the volume, and must fit within the new size.
+        #[clap(long, requires = "allow_shrink")]
+        filesystem_size: Option<u64>,
+    },
+}
+
+/// The types of resources that support the 'clone' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum CloneResources {
+    /// Clone volume.
+    /// The clone is thin provisioned and has the same size as the source volume.
+    Volume {
+        /// ID of the source volume.
+        id: VolumeId,
+        /// ID of the new volume clone.
+        clone_id: VolumeId,
+        /// Replica count of the clone, defaults to the replica count of the source volume.
+        #[clap(long)]
+        replicas: Option<ReplicaCount>,
+    },
+}
+
+/// The types of resources that support the 'move' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum MoveResources {
+    /// Move a volume replica onto another pool.
+    /// A new replica is added to the volume and the moved replica is only retired once the new
+    /// replica has been rebuilt. The progress is shown by the volume replica topology.
+    /// The volume must be published, as the new replica is rebuilt by the volume target.
+    Replica {
+        /// ID of the volume.
+        id: VolumeId,
+        /// ID of the replica to be moved.
+        replica_id: ReplicaId,
+        /// The pool on which to place the new replica, otherwise picked by the scheduler.
+        #[clap(long)]
+        pool: Option<PoolId>,
+        /// The node on which to place the new replica, otherwise picked by the scheduler.
+        #[clap(long)]
+        node: Option<NodeId>,
+        /// Cancel the move in progress, removing the new replica and keeping the moved replica.
+        #[clap(long, conflicts_with_all = ["pool", "node"])]
+        cancel: bool,
+    },
+    /// Move a published volume target onto another node.
+    /// The application node is switched over onto the new target without waiting for a path
+    /// failure. The progress is shown by the switchovers.
+    Target {
+        /// ID of the volume.
+        id: VolumeId,
+        /// The node on which to create the new target, otherwise picked by the scheduler.
+        #[clap(long)]
+        node: Option<NodeId>,
+    },
+}
+
+/// The types of resources that support the 'backup' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum BackupResources {
+    /// Backup the specs of all the objects persisted by the control-plane into a file.
+    /// The backup can be restored to recover from the loss of the persistent store.
+    Specs {
+        /// Path of the file to write the backup to.
+        file: std::path::PathBuf,
+    },
+}
+
+/// The types of resources that support the 'restore' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum RestoreResources {
+    /// Restore a volume in place to one of its snapshots.
+    /// The volume must not be published and must not have been resized since the snapshot.
+    Volume {
+        /// ID of the volume.
+        id: VolumeId,
+        /// ID of the snapshot to restore the volume to.
+        snapshot_id: SnapshotId,
+    },
+    /// Restore the specs of all the objects persisted by the control-plane from a backup file.
+    /// The persistent store must not contain any resources other than the registered nodes.
+    Specs {
+        /// Path of the backup file.
+        file: std::path::PathBuf,
+    },
+}
+
+/// The types of resources that support the 'create' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum CreateResources {
+    /// Create or update the snapshot schedule with the given ID.
+    /// The schedule periodically snapshots either a single volume or all volumes matching a
+    /// label selector, and deletes the scheduled snapshots which exceed its retention policy.
+    SnapshotSchedule {
+        /// ID of the snapshot schedule.
+        id: SnapshotScheduleId,
+        #[clap(flatten)]
+        args: CreateSnapshotScheduleArgs,
+    },
+    /// Create the snapshot group with the given ID.
+    /// The group snapshots either a list of volumes, all volumes matching a label selector or all
+    /// volumes of an affinity group, freezing their filesystems so that all snapshots are taken at
+    /// a single quiesce point.
+    SnapshotGroup {
+        /// ID of the snapshot group.
+        id: SnapshotGroupId,
+        #[clap(flatten)]
+        args: CreateSnapshotGroupArgs,
+    },
+    /// Export a volume snapshot into a backup directory on an application node, with the given ID.
+    /// With a base export, only the data which changed since the base export is copied.
+    /// Snapshots of encrypted volumes cannot be exported.
+    /// The snapshot is exported in the background, get the export to follow its status.
+    SnapshotExport {
+        /// ID of the snapshot export.
+        id: SnapshotExportId,
+        #[clap(flatten)]
+        args: CreateSnapshotExportArgs,
+    },
+}
+
+/// The types of resources that support the 'delete' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum DeleteResources {
+    /// Delete the snapshot schedule with the given ID.
+    /// The snapshots which were already taken by the schedule are not deleted.
+    SnapshotSchedule {
+        /// ID of the snapshot schedule.
+        id: SnapshotScheduleId,
+    },
+    /// Delete the snapshot group with the given ID, along with its snapshots.
+    SnapshotGroup {
+        /// ID of the snapshot group.
+        id: SnapshotGroupId,
+    },
+    /// Delete the snapshot export with the given ID from its backup directory.
+    SnapshotExport {
+        /// ID of the snapshot export.
+        id: SnapshotExportId,
+    },
+}
+
 /// The types of resources that support the 'SetProperty' operation.
 #[derive(clap::Subcommand, Debug)]
 pub enum SetPropertyResources {
@@ -91,6 +273,56 @@ pub enum SetPropertyResources {
 pub enum SetVolumeProperties {
     /// Max snapshot limit per volume.
     MaxSnapshots { max_snapshots: u32 },
+    /// IO limits enforced on the volume target.
+    /// Limits which are not specified are not enforced.
+    Qos {
+        /// Limit of read and write IO operations per second, must be a multiple of 1000.
+        #[clap(long)]
+        rw_iops: Option<u64>,
+        /// Limit of read and write bandwidth in MiB per second.
+        #[clap(long)]
+        rw_mbps: Option<u64>,
+        /// Limit of read bandwidth in MiB per second.
+        #[clap(long)]
+        read_mbps: Option<u64>,
+        /// Limit of write bandwidth in MiB per second.
+        #[clap(long)]
+        write_mbps: Option<u64>,
+    },
+    /// Reference to the key which encrypts the volume.
+    /// The new key reference must resolve to the volume's current key.
+    EncryptionKey {
+        /// The provider of the key, viz file, secret, kms.
+        #[clap(long)]
+        provider: EncryptionKeyProvider,
+        /// The file name, Secret name or key server identifier of the key.
+        #[clap(long)]
+        name: String,
+        /// The namespace of the Secret.
+        #[clap(long)]
+        namespace: Option<String>,
+        /// The entry of the Secret's data which holds the key.
+        #[clap(long)]
+        key: Option<String>,
+    },
+}
+
+/// The provider of a volume encryption key.
+#[derive(Debug, Clone, Copy, strum_macros::EnumString, strum_macros::AsRefStr, PartialEq)]
+#[strum(serialize_all = "lowercase")]
+pub enum EncryptionKeyProvider {
+    File,
+    Secret,
+    Kms,
+}
+impl From<EncryptionKeyProvider> for openapi::models::EncryptionKeyProvider {
+    fn from(src: EncryptionKeyProvider) -> Self {
+        match src {
+            EncryptionKeyProvider::File => Self::File,
+            EncryptionKeyProvider::Secret => Self::Secret,
+            EncryptionKeyProvider::Kms => Self::Kms,
+        }
+    }
 }
 
 /// The types of resources that support cordoning.
@@ -98,6 +330,9 @@ pub enum SetVolumeProperties {
 pub enum CordonResources {
     /// Cordon the node with the given ID by applying the cordon label to that node.
     Node { id: NodeId, label: String },
+    /// Cordon the pool with the given ID by applying the cordon label to that pool.
+    /// No new replicas are placed on a cordoned pool.
+    Pool { id: PoolId, label: String },
 }
 
 /// The types of resources that support uncordoning.
@@ -106,6 +341,9 @@ pub enum UnCordonResources {
     /// Removes the cordon label from the node.
     /// When the node has no more cordon labels, it is effectively uncordoned.
     Node { id: NodeId, label: String },
+    /// Removes the cordon label from the pool.
+    /// Removing the evacuation label cancels the evacuation of the pool.
+    Pool { id: PoolId, label: String },
 }
 
 /// The types of resources that support the 'get cordon' operation.
@@ -125,6 +363,18 @@ pub enum DrainResources {
     Node(DrainNodeArgs),
 }
 
+/// The types of resources that support the 'evacuate' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum EvacuateResources {
+    /// Evacuate the pool with the given ID, moving all of its volume replicas onto other pools.
+    /// The pool is cordoned with the given label, which once removed cancels the evacuation.
+    /// The replicas of unpublished volumes are reported as blocked until their volumes are
+    /// published.
+    Pool { id: PoolId, label: String },
+    /// Evacuate all the pools of the node with the given ID.
+    Node { id: NodeId, label: String },
+}
+
 /// The types of resources that support the 'label' operation.
 #[derive(clap::Subcommand, Debug)]
 pub enum LabelResources {
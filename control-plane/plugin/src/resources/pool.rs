@@ -1,9 +1,9 @@
 use crate::{
-    operations::{Get, ListWithArgs, PluginResult},
+    operations::{Cordoning, Evacuate, Get, ListWithArgs, PluginResult},
     resources::{
         error::Error,
         utils,
-        utils::{CreateRow, GetHeaderRow},
+        utils::{CreateRow, GetHeaderRow, OutputFormat},
         NodeId, PoolId,
     },
     rest_wrapper::RestClient,
@@ -22,6 +22,17 @@ impl CreateRow for openapi::models::Pool {
         // control plane.
         let managed = self.spec.is_some();
         let spec = self.spec.clone().unwrap_or_default();
+        let cordoned = spec.cordonlabels.as_ref().map_or(false, |l| !l.is_empty());
+        let evacuation = spec.evacuation.as_ref().map(|e| match e.status {
+            openapi::models::PoolEvacuationStatus::Evacuating if e.blocked_replicas > 0 => format!(
+                "{} ({} replicas remaining, {} blocked until their volumes are published)",
+                e.status, e.remaining_replicas, e.blocked_replicas
+            ),
+            openapi::models::PoolEvacuationStatus::Evacuating => {
+                format!("{} ({} replicas remaining)", e.status, e.remaining_replicas)
+            }
+            openapi::models::PoolEvacuationStatus::Evacuated => e.status.to_string(),
+        });
         // In case the state is not coming as filled, either due to pool, node lost, fill in
         // spec data and mark the status as Unknown.
         let state = self.state.clone().unwrap_or(openapi::models::PoolState {
@@ -49,6 +60,8 @@ impl CreateRow for openapi::models::Pool {
             ::utils::bytes::into_human(state.used),
             ::utils::bytes::into_human(free),
             utils::optional_cell(state.committed.map(::utils::bytes::into_human)),
+            cordoned,
+            utils::optional_cell(evacuation),
         ]
     }
 }
@@ -145,6 +158,157 @@ impl Get for Pool {
     }
 }
 
+#[async_trait(?Send)]
+impl Cordoning for Pool {
+    type ID = PoolId;
+    async fn cordon(id: &Self::ID, label: &str, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .pools_api()
+            .put_pool_cordon(id, label)
+            .await
+        {
+            Ok(pool) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, pool.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Pool {id} cordoned successfully")
+                }
+            },
+            Err(e) => {
+                return Err(Error::PoolCordonError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    async fn uncordon(id: &Self::ID, label: &str, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .pools_api()
+            .delete_pool_cordon(id, label)
+            .await
+        {
+            Ok(pool) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, pool.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    let labels = pool
+                        .into_body()
+                        .spec
+                        .and_then(|spec| spec.cordonlabels)
+                        .unwrap_or_default();
+                    if labels.is_empty() {
+                        println!("Pool {id} successfully uncordoned");
+                    } else {
+                        println!(
+                            "Cordon label successfully removed. Remaining cordon labels {labels:?}",
+                        );
+                    }
+                }
+            },
+            Err(e) => {
+                return Err(Error::PoolUncordonError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Evacuate for Pool {
+    type ID = PoolId;
+    async fn evacuate(id: &Self::ID, label: &str, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .pools_api()
+            .put_pool_evacuate(id, label)
+            .await
+        {
+            Ok(pool) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, pool.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Pool {id} evacuation started")
+                }
+            },
+            Err(e) => {
+                return Err(Error::PoolEvacuateError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Evacuate for Pools {
+    type ID = NodeId;
+    /// Evacuate all the pools of the given node.
+    async fn evacuate(id: &Self::ID, label: &str, output: &OutputFormat) -> PluginResult {
+        let pools = RestClient::client()
+            .pools_api()
+            .get_node_pools(id)
+            .await
+            .map(|pools| pools.into_body())
+            .map_err(|e| Error::ListPoolsError { source: e })?;
+        let mut evacuating = Vec::with_capacity(pools.len());
+        for pool in pools {
+            // pools which are already evacuating with this label are left as they are.
+            let already_evacuating = pool
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.evacuation.as_ref())
+                .map_or(false, |evacuation| evacuation.label == label);
+            if already_evacuating {
+                evacuating.push(pool);
+                continue;
+            }
+            match RestClient::client()
+                .pools_api()
+                .put_pool_evacuate(&pool.id, label)
+                .await
+            {
+                Ok(pool) => evacuating.push(pool.into_body()),
+                Err(e) => {
+                    return Err(Error::PoolEvacuateError {
+                        id: pool.id,
+                        source: e,
+                    });
+                }
+            }
+        }
+        match output {
+            OutputFormat::Yaml | OutputFormat::Json => {
+                // Print json or yaml based on output format.
+                utils::print_table(output, evacuating);
+            }
+            OutputFormat::None => {
+                // In case the output format is not specified, show a success message.
+                println!(
+                    "Evacuation of {} pool(s) of node {id} started",
+                    evacuating.len()
+                )
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Check if the labels match the pool labels.
 pub(crate) fn labels_matched(
     pool_labels: &HashMap<String, String>,
@@ -0,0 +1,178 @@
+use crate::{
+    operations::{CreateWithArgs, Delete, Get, List, PluginResult},
+    resources::{
+        error::Error,
+        utils::{self, optional_cell, CreateRow, GetHeaderRow, OutputFormat},
+        SnapshotExportId, SnapshotId,
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use openapi::models::CreateSnapshotExportBody;
+use prettytable::Row;
+
+/// A collection of SnapshotExport resources.
+#[derive(clap::Args, Debug)]
+pub struct SnapshotExports {}
+
+/// SnapshotExport resource.
+#[derive(clap::Args, Debug)]
+pub struct SnapshotExport {}
+
+/// Arguments used when creating a snapshot export.
+#[derive(Debug, Clone, clap::Args)]
+pub struct CreateSnapshotExportArgs {
+    /// ID of the volume snapshot to be exported.
+    #[clap(long)]
+    snapshot: SnapshotId,
+    /// The application node which exports the snapshot into the backup directory.
+    #[clap(long)]
+    node: String,
+    /// The absolute path of the backup directory on the application node, which must be within
+    /// the backup base directory configured on its node plugin.
+    #[clap(long)]
+    path: String,
+    /// Only export the data which changed since the given export of the same volume.
+    #[clap(long)]
+    base: Option<SnapshotExportId>,
+}
+
+impl CreateSnapshotExportArgs {
+    /// Get the snapshot export request body.
+    fn body(&self) -> CreateSnapshotExportBody {
+        CreateSnapshotExportBody {
+            snapshot_id: self.snapshot,
+            node: self.node.clone(),
+            path: self.path.clone(),
+            base_export_id: self.base,
+        }
+    }
+}
+
+impl CreateRow for openapi::models::SnapshotExport {
+    fn row(&self) -> Row {
+        row![
+            self.id,
+            self.snapshot_id,
+            self.volume_id,
+            format!("{}:{}", self.node, self.path),
+            optional_cell(self.base),
+            self.status,
+            ::utils::bytes::into_human(self.size),
+            format!("{}/{}", self.changed_chunks, self.chunks),
+            ::utils::bytes::into_human(self.written_bytes),
+            optional_cell(self.timestamp.clone())
+        ]
+    }
+}
+
+impl GetHeaderRow for openapi::models::SnapshotExport {
+    fn get_header_row(&self) -> Row {
+        (*utils::SNAPSHOT_EXPORT_HEADERS).clone()
+    }
+}
+
+#[async_trait(?Send)]
+impl List for SnapshotExports {
+    async fn list(output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .get_snapshot_exports()
+            .await
+        {
+            Ok(exports) => {
+                // Print table, json or yaml based on output format.
+                utils::print_table(output, exports.into_body());
+            }
+            Err(e) => {
+                return Err(Error::ListSnapshotExportsError { source: e });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Get for SnapshotExport {
+    type ID = SnapshotExportId;
+    async fn get(id: &Self::ID, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .get_snapshot_export(id)
+            .await
+        {
+            Ok(export) => {
+                // Print table, json or yaml based on output format.
+                utils::print_table(output, export.into_body());
+            }
+            Err(e) => {
+                return Err(Error::GetSnapshotExportError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl CreateWithArgs for SnapshotExport {
+    type ID = SnapshotExportId;
+    type Args = CreateSnapshotExportArgs;
+    async fn create(id: &Self::ID, args: &Self::Args, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .put_snapshot_export(id, args.body())
+            .await
+        {
+            Ok(export) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, export.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!(
+                        "Snapshot export {id} created, the snapshot is being exported in the background 🚀"
+                    );
+                    println!(
+                        "Get the snapshot export until its status is Exported; it's deleted if the export fails"
+                    )
+                }
+            },
+            Err(e) => {
+                return Err(Error::CreateSnapshotExportError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Delete for SnapshotExport {
+    type ID = SnapshotExportId;
+    async fn delete(id: &Self::ID, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .del_snapshot_export(id)
+            .await
+        {
+            Ok(_) => {
+                if output.none() {
+                    println!("Snapshot export {id} deleted successfully");
+                }
+            }
+            Err(e) => {
+                return Err(Error::DeleteSnapshotExportError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
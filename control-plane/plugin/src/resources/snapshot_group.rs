@@ -0,0 +1,192 @@
+use crate::{
+    operations::{CreateWithArgs, Delete, Get, List, PluginResult},
+    resources::{
+        error::Error,
+        snapshot_schedule::parse_selector,
+        utils::{self, optional_cell, CreateRow, GetHeaderRow, OutputFormat},
+        SnapshotGroupId, VolumeId,
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use openapi::models::CreateSnapshotGroupBody;
+use prettytable::Row;
+
+/// A collection of SnapshotGroup resources.
+#[derive(clap::Args, Debug)]
+pub struct SnapshotGroups {}
+
+/// SnapshotGroup resource.
+#[derive(clap::Args, Debug)]
+pub struct SnapshotGroup {}
+
+/// Arguments used when creating a snapshot group.
+#[derive(Debug, Clone, clap::Args)]
+#[clap(group(clap::ArgGroup::new("target").required(true).args(["volumes", "selector", "affinity_group"])))]
+pub struct CreateSnapshotGroupArgs {
+    /// Snapshot the volumes with the given IDs (e.g. --volumes id1,id2).
+    #[clap(long, value_delimiter = ',')]
+    volumes: Vec<VolumeId>,
+    /// Snapshot all volumes carrying the given labels, supports '=' only.
+    /// (e.g. -l key1=value1,key2=value2).
+    #[clap(short = 'l', long)]
+    selector: Option<String>,
+    /// Snapshot all volumes of the given affinity group.
+    #[clap(long)]
+    affinity_group: Option<String>,
+}
+
+impl CreateSnapshotGroupArgs {
+    /// Get the snapshot group request body.
+    fn body(&self) -> Result<CreateSnapshotGroupBody, Error> {
+        let selector = match &self.selector {
+            Some(selector) => Some(parse_selector(selector)?),
+            None => None,
+        };
+        Ok(CreateSnapshotGroupBody {
+            volume_ids: (!self.volumes.is_empty()).then(|| self.volumes.clone()),
+            selector,
+            affinity_group: self.affinity_group.clone(),
+        })
+    }
+}
+
+impl CreateRow for openapi::models::SnapshotGroup {
+    fn row(&self) -> Row {
+        let target = match (&self.volume_ids, &self.selector, &self.affinity_group) {
+            (Some(volume_ids), _, _) => volume_ids
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+            (None, Some(selector), _) => {
+                let mut labels = selector
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect::<Vec<_>>();
+                labels.sort();
+                labels.join(",")
+            }
+            (None, None, Some(affinity_group)) => affinity_group.clone(),
+            (None, None, None) => String::new(),
+        };
+        let created = self
+            .snapshots
+            .iter()
+            .filter(|snapshot| snapshot.created)
+            .count();
+        row![
+            self.id,
+            target,
+            self.status,
+            format!("{created}/{}", self.snapshots.len()),
+            optional_cell(self.timestamp.clone())
+        ]
+    }
+}
+
+impl GetHeaderRow for openapi::models::SnapshotGroup {
+    fn get_header_row(&self) -> Row {
+        (*utils::SNAPSHOT_GROUP_HEADERS).clone()
+    }
+}
+
+#[async_trait(?Send)]
+impl List for SnapshotGroups {
+    async fn list(output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .get_snapshot_groups()
+            .await
+        {
+            Ok(groups) => {
+                // Print table, json or yaml based on output format.
+                utils::print_table(output, groups.into_body());
+            }
+            Err(e) => {
+                return Err(Error::ListSnapshotGroupsError { source: e });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Get for SnapshotGroup {
+    type ID = SnapshotGroupId;
+    async fn get(id: &Self::ID, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .get_snapshot_group(id)
+            .await
+        {
+            Ok(group) => {
+                // Print table, json or yaml based on output format.
+                utils::print_table(output, group.into_body());
+            }
+            Err(e) => {
+                return Err(Error::GetSnapshotGroupError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl CreateWithArgs for SnapshotGroup {
+    type ID = SnapshotGroupId;
+    type Args = CreateSnapshotGroupArgs;
+    async fn create(id: &Self::ID, args: &Self::Args, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .put_snapshot_group(id, args.body()?)
+            .await
+        {
+            Ok(group) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, group.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Snapshot group {id} created successfully 🚀")
+                }
+            },
+            Err(e) => {
+                return Err(Error::CreateSnapshotGroupError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Delete for SnapshotGroup {
+    type ID = SnapshotGroupId;
+    async fn delete(id: &Self::ID, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .del_snapshot_group(id)
+            .await
+        {
+            Ok(_) => {
+                if output.none() {
+                    println!("Snapshot group {id} deleted successfully");
+                }
+            }
+            Err(e) => {
+                return Err(Error::DeleteSnapshotGroupError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{
+    operations::{CreateWithArgs, Delete, Get, List, PluginResult},
+    resources::{
+        error::Error,
+        utils::{self, optional_cell, CreateRow, GetHeaderRow, OutputFormat},
+        SnapshotScheduleId, VolumeId,
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use openapi::models::{CreateSnapshotScheduleBody, SnapshotRetention};
+use prettytable::Row;
+use std::{collections::HashMap, time::Duration};
+
+/// A collection of SnapshotSchedule resources.
+#[derive(clap::Args, Debug)]
+pub struct SnapshotSchedules {}
+
+/// SnapshotSchedule resource.
+#[derive(clap::Args, Debug)]
+pub struct SnapshotSchedule {}
+
+/// Arguments used when creating or updating a snapshot schedule.
+#[derive(Debug, Clone, clap::Args)]
+#[clap(group(clap::ArgGroup::new("target").required(true).args(["volume", "selector"])))]
+#[clap(group(clap::ArgGroup::new("trigger").required(true).args(["interval", "cron"])))]
+pub struct CreateSnapshotScheduleArgs {
+    /// Snapshot the volume with the given ID.
+    #[clap(long)]
+    volume: Option<VolumeId>,
+    /// Snapshot all volumes carrying the given labels, supports '=' only.
+    /// (e.g. -l key1=value1,key2=value2).
+    #[clap(short = 'l', long)]
+    selector: Option<String>,
+    /// Interval between consecutive snapshots of a volume (e.g. 30m, 1h, 1day).
+    #[clap(long)]
+    interval: Option<humantime::Duration>,
+    /// Cron expression of the times at which the snapshots of a volume are taken, in UTC.
+    /// (e.g. "0 2 * * *" for every day at 02:00).
+    #[clap(long)]
+    cron: Option<String>,
+    /// Maximum number of scheduled snapshots to keep per volume.
+    #[clap(long)]
+    retain_count: Option<u32>,
+    /// Maximum age of the scheduled snapshots (e.g. 1day, 1week).
+    #[clap(long)]
+    retain_age: Option<humantime::Duration>,
+    /// Quiesce the filesystem of published volumes before taking the snapshot.
+    #[clap(long)]
+    quiesce: bool,
+}
+
+impl CreateSnapshotScheduleArgs {
+    /// Get the snapshot schedule request body.
+    fn body(&self) -> Result<CreateSnapshotScheduleBody, Error> {
+        let selector = match &self.selector {
+            Some(selector) => Some(parse_selector(selector)?),
+            None => None,
+        };
+        Ok(CreateSnapshotScheduleBody {
+            volume_id: self.volume,
+            selector,
+            interval_secs: self
+                .interval
+                .map(|interval| Duration::from(interval).as_secs()),
+            cron: self.cron.clone(),
+            retention: SnapshotRetention {
+                count: self.retain_count,
+                max_age_secs: self.retain_age.map(|age| Duration::from(age).as_secs()),
+            },
+            quiesce: Some(self.quiesce),
+        })
+    }
+}
+
+/// Parse the label selector from the `key1=value1,key2=value2` format.
+pub(crate) fn parse_selector(selector: &str) -> Result<HashMap<String, String>, Error> {
+    selector
+        .split(',')
+        .map(|label| match label.split('=').collect::<Vec<_>>()[..] {
+            [key, value] if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+            _ => Err(Error::LabelNodeFilter {
+                labels: selector.to_string(),
+            }),
+        })
+        .collect()
+}
+
+impl CreateRow for openapi::models::SnapshotSchedule {
+    fn row(&self) -> Row {
+        let target = match (&self.volume_id, &self.selector) {
+            (Some(volume_id), _) => volume_id.to_string(),
+            (None, Some(selector)) => {
+                let mut labels = selector
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect::<Vec<_>>();
+                labels.sort();
+                labels.join(",")
+            }
+            (None, None) => String::new(),
+        };
+        let schedule = match (&self.interval_secs, &self.cron) {
+            (Some(interval), _) => {
+                humantime::format_duration(Duration::from_secs(*interval)).to_string()
+            }
+            (None, Some(cron)) => cron.clone(),
+            (None, None) => String::new(),
+        };
+        row![
+            self.id,
+            target,
+            schedule,
+            optional_cell(self.retention.count),
+            optional_cell(
+                self.retention
+                    .max_age_secs
+                    .map(|age| humantime::format_duration(Duration::from_secs(age)))
+            ),
+            self.quiesce
+        ]
+    }
+}
+
+impl GetHeaderRow for openapi::models::SnapshotSchedule {
+    fn get_header_row(&self) -> Row {
+        (*utils::SNAPSHOT_SCHEDULE_HEADERS).clone()
+    }
+}
+
+#[async_trait(?Send)]
+impl List for SnapshotSchedules {
+    async fn list(output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .get_snapshot_schedules()
+            .await
+        {
+            Ok(schedules) => {
+                // Print table, json or yaml based on output format.
+                utils::print_table(output, schedules.into_body());
+            }
+            Err(e) => {
+                return Err(Error::ListSnapshotSchedulesError { source: e });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Get for SnapshotSchedule {
+    type ID = SnapshotScheduleId;
+    async fn get(id: &Self::ID, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .get_snapshot_schedule(id)
+            .await
+        {
+            Ok(schedule) => {
+                // Print table, json or yaml based on output format.
+                utils::print_table(output, schedule.into_body());
+            }
+            Err(e) => {
+                return Err(Error::GetSnapshotScheduleError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl CreateWithArgs for SnapshotSchedule {
+    type ID = SnapshotScheduleId;
+    type Args = CreateSnapshotScheduleArgs;
+    async fn create(id: &Self::ID, args: &Self::Args, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .put_snapshot_schedule(id, args.body()?)
+            .await
+        {
+            Ok(schedule) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, schedule.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Snapshot schedule {id} created successfully 🚀")
+                }
+            },
+            Err(e) => {
+                return Err(Error::CreateSnapshotScheduleError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Delete for SnapshotSchedule {
+    type ID = SnapshotScheduleId;
+    async fn delete(id: &Self::ID, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .del_snapshot_schedule(id)
+            .await
+        {
+            Ok(_) => {
+                if output.none() {
+                    println!("Snapshot schedule {id} deleted successfully");
+                }
+            }
+            Err(e) => {
+                return Err(Error::DeleteSnapshotScheduleError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
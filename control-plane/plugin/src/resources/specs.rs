@@ -0,0 +1,93 @@
+use crate::{
+    operations::{Backup, PluginResult},
+    resources::{
+        error::Error,
+        utils::{self, CreateRow, GetHeaderRow, OutputFormat},
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use prettytable::Row;
+use std::path::Path;
+
+/// The specs of all the objects persisted by the control-plane.
+#[derive(clap::Args, Debug)]
+pub struct Specs {}
+
+impl CreateRow for openapi::models::SpecsRestored {
+    fn row(&self) -> Row {
+        row![self.restored, self.skipped]
+    }
+}
+
+impl GetHeaderRow for openapi::models::SpecsRestored {
+    fn get_header_row(&self) -> Row {
+        (*utils::SPECS_RESTORED_HEADERS).clone()
+    }
+}
+
+#[async_trait(?Send)]
+impl Backup for Specs {
+    async fn backup(file: &Path, output: &OutputFormat) -> PluginResult {
+        let backup = match RestClient::client().specs_api().get_specs_backup().await {
+            Ok(backup) => backup.into_body(),
+            Err(e) => {
+                return Err(Error::BackupSpecsError { source: e });
+            }
+        };
+        let contents =
+            serde_json::to_vec_pretty(&backup).map_err(|error| Error::SpecsBackupFileError {
+                path: file.display().to_string(),
+                reason: error.to_string(),
+            })?;
+        std::fs::write(file, contents).map_err(|error| Error::SpecsBackupFileError {
+            path: file.display().to_string(),
+            reason: error.to_string(),
+        })?;
+        if output.none() {
+            println!("Specs backup written to {} successfully", file.display());
+        }
+        Ok(())
+    }
+
+    async fn restore(file: &Path, output: &OutputFormat) -> PluginResult {
+        let contents = std::fs::read(file).map_err(|error| Error::SpecsBackupFileError {
+            path: file.display().to_string(),
+            reason: error.to_string(),
+        })?;
+        let backup: serde_json::Value =
+            serde_json::from_slice(&contents).map_err(|error| Error::SpecsBackupFileError {
+                path: file.display().to_string(),
+                reason: error.to_string(),
+            })?;
+        match RestClient::client()
+            .specs_api()
+            .put_specs_restore(backup)
+            .await
+        {
+            Ok(restored) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, restored.into_body());
+                }
+                OutputFormat::None => {
+                    let restored = restored.into_body();
+                    // In case the output format is not specified, show a success message.
+                    println!(
+                        "Specs restored from {} successfully, {} entries restored and {} skipped 🚀",
+                        file.display(),
+                        restored.restored,
+                        restored.skipped
+                    )
+                }
+            },
+            Err(e) => {
+                return Err(Error::RestoreSpecsError {
+                    path: file.display().to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{
+    operations::{List, PluginResult},
+    resources::{
+        error::Error,
+        utils::{self, optional_cell, CreateRow, GetHeaderRow, OutputFormat},
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use prettytable::Row;
+
+/// A collection of volume SwitchOver resources.
+#[derive(clap::Args, Debug)]
+pub struct SwitchOvers {}
+
+impl CreateRow for openapi::models::SwitchOver {
+    fn row(&self) -> Row {
+        row![
+            self.volume_id,
+            self.node,
+            self.planned,
+            self.priority,
+            self.stage,
+            self.retry_count,
+            self.existing_nqn,
+            optional_cell(self.new_path.clone()),
+            self.start_time,
+            optional_cell(self.end_time.clone()),
+            optional_cell(self.last_error.clone())
+        ]
+    }
+}
+
+impl GetHeaderRow for openapi::models::SwitchOver {
+    fn get_header_row(&self) -> Row {
+        (*utils::SWITCHOVER_HEADERS).clone()
+    }
+}
+
+#[async_trait(?Send)]
+impl List for SwitchOvers {
+    async fn list(output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .switch_overs_api()
+            .get_switchovers()
+            .await
+        {
+            Ok(switchovers) => {
+                // Print table, json or yaml based on output format.
+                utils::print_table(output, switchovers.into_body());
+            }
+            Err(e) => {
+                return Err(Error::ListSwitchOversError { source: e });
+            }
+        }
+        Ok(())
+    }
+}
@@ -37,6 +37,8 @@ async fn setup() {
                 thin: false,
                 affinity_group: None,
                 max_snapshots: None,
+                qos: None,
+                encryption: None,
             },
         )
         .await
@@ -110,6 +112,8 @@ async fn get_volumes_paginated() {
                     labels: None,
                     affinity_group: None,
                     max_snapshots: None,
+                    qos: None,
+                    encryption: None,
                 },
             )
             .await
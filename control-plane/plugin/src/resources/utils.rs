@@ -34,6 +34,42 @@ lazy_static! {
         "RESTORES",
         "SNAPSHOT_REPLICAS"
     ];
+    pub static ref SNAPSHOT_SCHEDULE_HEADERS: Row = row![
+        "ID",
+        "TARGET",
+        "SCHEDULE",
+        "RETAIN-COUNT",
+        "RETAIN-AGE",
+        "QUIESCE"
+    ];
+    pub static ref SNAPSHOT_GROUP_HEADERS: Row =
+        row!["ID", "TARGET", "STATUS", "SNAPSHOTS", "TIMESTAMP"];
+    pub static ref SNAPSHOT_EXPORT_HEADERS: Row = row![
+        "ID",
+        "SNAPSHOT",
+        "VOLUME",
+        "TARGET",
+        "BASE",
+        "STATUS",
+        "SIZE",
+        "CHANGED-CHUNKS",
+        "WRITTEN",
+        "TIMESTAMP"
+    ];
+    pub static ref SPECS_RESTORED_HEADERS: Row = row!["RESTORED", "SKIPPED"];
+    pub static ref SWITCHOVER_HEADERS: Row = row![
+        "VOLUME",
+        "NODE",
+        "PLANNED",
+        "PRIORITY",
+        "STAGE",
+        "RETRIES",
+        "EXISTING-NQN",
+        "NEW-PATH",
+        "STARTED",
+        "COMPLETED",
+        "LAST-ERROR"
+    ];
     pub static ref POOLS_HEADERS: Row = row![
         "ID",
         "DISKS",
@@ -43,7 +79,9 @@ lazy_static! {
         "CAPACITY",
         "ALLOCATED",
         "AVAILABLE",
-        "COMMITTED"
+        "COMMITTED",
+        "CORDONED",
+        "EVACUATION"
     ];
     pub static ref NODE_HEADERS: Row = row!["ID", "GRPC ENDPOINT", "STATUS"];
     pub static ref REPLICA_TOPOLOGIES_PREFIX: Row = row!["VOLUME-ID"];
@@ -57,7 +95,8 @@ lazy_static! {
         "SNAPSHOTS",
         "CHILD-STATUS",
         "REASON",
-        "REBUILD"
+        "REBUILD",
+        "MOVE"
     ];
     pub static ref SNAPSHOT_TOPOLOGY_PREFIX: Row = row!["SNAPSHOT-ID"];
     pub static ref SNAPSHOT_TOPOLOGY_HEADERS: Row = row![
@@ -69,6 +108,9 @@ lazy_static! {
         "SOURCE"
     ];
     pub static ref REBUILD_HISTORY_HEADER: Row = row![
+        "VOLUME",
+        "TARGET",
+        "NODE",
         "DST",
         "SRC",
         "STATE",
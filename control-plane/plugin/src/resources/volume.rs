@@ -1,15 +1,21 @@
 use crate::{
-    operations::{Get, ListExt, PluginResult, RebuildHistory, ReplicaTopology, Scale, SetProperty},
+    operations::{
+        Cloning, Get, ListExt, PluginResult, RebuildHistory, ReplicaMove, ReplicaTopology,
+        Restoring, Scale, SetProperty, TargetMove,
+    },
     resources::{
         error::Error,
         utils::{self, optional_cell, CreateRow, CreateRows, GetHeaderRow, OutputFormat},
-        VolumeId,
+        NodeId, PoolId, ReplicaId, SnapshotId, VolumeId,
     },
     rest_wrapper::RestClient,
     SetVolumeProperties,
 };
 use openapi::{
-    models::{SetVolumePropertyBody, VolumeContentSource},
+    models::{
+        CreateVolumeBody, EncryptionKeyRef, MoveReplicaBody, MoveTargetBody, SetVolumePropertyBody,
+        VolumeContentSource, VolumeQos,
+    },
     tower::client::Url,
 };
 
@@ -27,16 +33,40 @@ pub struct Volumes {}
 enum VolumeSource {
     None,
     Snapshot,
+    Volume,
 }
 
 #[derive(Debug, Clone, clap::Args)]
 /// Volume args.
 pub struct VolumesArgs {
     #[clap(long)]
-    /// Shows only volumes created from specific source, viz none, snapshot
+    /// Shows only volumes created from specific source, viz none, snapshot, volume
     source: Option<VolumeSource>,
 }
 
+#[derive(Debug, Clone, clap::Args)]
+#[clap(group(clap::ArgGroup::new("rebuild_kind").args(["partial", "full"])))]
+/// Rebuild history args.
+pub struct RebuildHistoryArgs {
+    /// Shows only the rebuild history of the volume with the given ID.
+    id: Option<VolumeId>,
+    #[clap(long)]
+    /// Shows only the rebuilds which took place on the given node.
+    node: Option<NodeId>,
+    #[clap(long)]
+    /// Shows only the rebuilds which ended at or after the given time (e.g. 2023-06-20T07:25:25Z).
+    since: Option<humantime::Timestamp>,
+    #[clap(long)]
+    /// Shows only the rebuilds which ended at or before the given time (e.g. 2023-06-20T07:25:25Z).
+    until: Option<humantime::Timestamp>,
+    #[clap(long)]
+    /// Shows only the partial rebuilds.
+    partial: bool,
+    #[clap(long)]
+    /// Shows only the full rebuilds.
+    full: bool,
+}
+
 impl CreateRow for openapi::models::Volume {
     fn row(&self) -> Row {
         let state = &self.state;
@@ -62,6 +92,7 @@ impl CreateRow for openapi::models::Volume {
             optional_cell(self.spec.content_source.as_ref().map(|source| {
                 match source {
                     VolumeContentSource::snapshot(_) => "Snapshot",
+                    VolumeContentSource::volume(_) => "Volume",
                 }
             })),
         ]
@@ -136,6 +167,14 @@ async fn get_paginated_volumes(volume_args: &VolumesArgs) -> Option<Vec<openapi:
                     )
             });
         }
+        Some(VolumeSource::Volume) => {
+            volumes.retain(|vol| {
+                matches!(
+                    vol.spec.content_source,
+                    Some(VolumeContentSource::volume(_))
+                )
+            });
+        }
     }
 
     Some(volumes)
@@ -197,10 +236,14 @@ impl Scale for Volume {
     async fn resize(
         id: &Self::ID,
         requested_size: u64,
+        allow_shrink: bool,
+        filesystem_size: Option<u64>,
         output: &utils::OutputFormat,
     ) -> PluginResult {
         let req = openapi::models::ResizeVolumeBody {
             size: requested_size as usize,
+            allow_shrink: allow_shrink.then_some(true),
+            filesystem_size: filesystem_size.map(|size| size as usize),
         };
         match RestClient::client()
             .volumes_api()
@@ -218,7 +261,7 @@ impl Scale for Volume {
                 }
             },
             Err(source) => {
-                return Err(Error::ScaleVolumeError {
+                return Err(Error::ResizeVolumeError {
                     id: id.to_string(),
                     source,
                 });
@@ -228,6 +271,207 @@ impl Scale for Volume {
     }
 }
 
+#[async_trait(?Send)]
+impl Cloning for Volume {
+    type ID = VolumeId;
+    async fn create_clone(
+        id: &Self::ID,
+        clone_id: &Self::ID,
+        replica_count: Option<u8>,
+        output: &utils::OutputFormat,
+    ) -> PluginResult {
+        let source = match RestClient::client().volumes_api().get_volume(id).await {
+            Ok(volume) => volume.into_body(),
+            Err(e) => {
+                return Err(Error::GetVolumeError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        };
+        // A clone must be thin provisioned and match the size of its source.
+        let body = CreateVolumeBody {
+            policy: source.spec.policy,
+            replicas: replica_count.unwrap_or(source.spec.num_replicas),
+            size: source.spec.size,
+            thin: true,
+            topology: source.spec.topology,
+            labels: None,
+            affinity_group: None,
+            max_snapshots: source.spec.max_snapshots,
+            qos: source.spec.qos,
+            encryption: source.spec.encryption,
+        };
+        match RestClient::client()
+            .volumes_api()
+            .put_volume_clone(id, clone_id, body)
+            .await
+        {
+            Ok(volume) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, volume.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Volume {id} cloned into {clone_id} successfully 🚀")
+                }
+            },
+            Err(e) => {
+                return Err(Error::CloneVolumeError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Restoring for Volume {
+    type ID = VolumeId;
+    type SnapshotID = SnapshotId;
+    async fn restore_snapshot(
+        id: &Self::ID,
+        snapshot_id: &Self::SnapshotID,
+        output: &utils::OutputFormat,
+    ) -> PluginResult {
+        match RestClient::client()
+            .snapshots_api()
+            .put_volume_snapshot_restore(id, snapshot_id)
+            .await
+        {
+            Ok(volume) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, volume.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Volume {id} restored to snapshot {snapshot_id} successfully 🚀")
+                }
+            },
+            Err(e) => {
+                return Err(Error::RestoreVolumeSnapshotError {
+                    id: id.to_string(),
+                    snapshot_id: snapshot_id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl ReplicaMove for Volume {
+    type ID = VolumeId;
+    type ReplicaID = ReplicaId;
+    async fn move_replica(
+        id: &Self::ID,
+        replica_id: &Self::ReplicaID,
+        pool: &Option<PoolId>,
+        node: &Option<NodeId>,
+        output: &utils::OutputFormat,
+    ) -> PluginResult {
+        let body = MoveReplicaBody {
+            pool: pool.clone(),
+            node: node.clone(),
+        };
+        match RestClient::client()
+            .volumes_api()
+            .put_volume_replica_move(id, replica_id, body)
+            .await
+        {
+            Ok(volume) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, volume.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Volume {id} replica {replica_id} move started successfully 🚀")
+                }
+            },
+            Err(e) => {
+                return Err(Error::MoveVolumeReplicaError {
+                    id: id.to_string(),
+                    replica_id: replica_id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    async fn cancel_replica_move(
+        id: &Self::ID,
+        replica_id: &Self::ReplicaID,
+        output: &utils::OutputFormat,
+    ) -> PluginResult {
+        match RestClient::client()
+            .volumes_api()
+            .del_volume_replica_move(id, replica_id)
+            .await
+        {
+            Ok(volume) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, volume.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Volume {id} replica {replica_id} move cancelled successfully")
+                }
+            },
+            Err(e) => {
+                return Err(Error::CancelVolumeReplicaMoveError {
+                    id: id.to_string(),
+                    replica_id: replica_id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl TargetMove for Volume {
+    type ID = VolumeId;
+    async fn move_target(
+        id: &Self::ID,
+        node: &Option<NodeId>,
+        output: &utils::OutputFormat,
+    ) -> PluginResult {
+        let body = MoveTargetBody { node: node.clone() };
+        match RestClient::client()
+            .switch_overs_api()
+            .put_volume_target_move(id, body)
+            .await
+        {
+            Ok(switchover) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, switchover.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Volume {id} target move started successfully 🚀")
+                }
+            },
+            Err(e) => {
+                return Err(Error::MoveVolumeTargetError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
 #[async_trait(?Send)]
 impl SetProperty for Volume {
     type ID = VolumeId;
@@ -237,31 +481,53 @@ impl SetProperty for Volume {
         property: &Self::Property,
         output: &utils::OutputFormat,
     ) -> PluginResult {
-        match property {
+        let property_body = match property {
             SetVolumeProperties::MaxSnapshots { max_snapshots } => {
-                let property_body = SetVolumePropertyBody::max_snapshots(*max_snapshots);
-                match RestClient::client()
-                    .volumes_api()
-                    .put_volume_property(id, property_body.clone())
-                    .await
-                {
-                    Ok(volume) => match output {
-                        OutputFormat::Yaml | OutputFormat::Json => {
-                            // Print json or yaml based on output format.
-                            utils::print_table(output, volume.into_body());
-                        }
-                        OutputFormat::None => {
-                            // In case the output format is not specified, show a success message.
-                            println!("Volume {id} property {:?} set successfully", property_body);
-                        }
-                    },
-                    Err(e) => {
-                        return Err(Error::SetVolumePropertyError {
-                            id: id.to_string(),
-                            source: e,
-                        });
-                    }
+                SetVolumePropertyBody::max_snapshots(*max_snapshots)
+            }
+            SetVolumeProperties::Qos {
+                rw_iops,
+                rw_mbps,
+                read_mbps,
+                write_mbps,
+            } => SetVolumePropertyBody::qos(VolumeQos::new_all(
+                *rw_iops,
+                *rw_mbps,
+                *read_mbps,
+                *write_mbps,
+            )),
+            SetVolumeProperties::EncryptionKey {
+                provider,
+                name,
+                namespace,
+                key,
+            } => SetVolumePropertyBody::encryption_key(EncryptionKeyRef::new_all(
+                *provider,
+                name,
+                namespace.clone(),
+                key.clone(),
+            )),
+        };
+        match RestClient::client()
+            .volumes_api()
+            .put_volume_property(id, property_body.clone())
+            .await
+        {
+            Ok(volume) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, volume.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Volume {id} property {:?} set successfully", property_body);
                 }
+            },
+            Err(e) => {
+                return Err(Error::SetVolumePropertyError {
+                    id: id.to_string(),
+                    source: e,
+                });
             }
         }
         Ok(())
@@ -296,21 +562,31 @@ impl ReplicaTopology for Volume {
 
 #[async_trait(?Send)]
 impl RebuildHistory for Volume {
-    type ID = VolumeId;
-    async fn rebuild_history(id: &Self::ID, output: &OutputFormat) -> PluginResult {
+    type Args = RebuildHistoryArgs;
+    async fn rebuild_history(args: &Self::Args, output: &OutputFormat) -> PluginResult {
+        let since = args.since.map(|time| time.to_string());
+        let until = args.until.map(|time| time.to_string());
+        let partial = match (args.partial, args.full) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        };
         match RestClient::client()
             .volumes_api()
-            .get_rebuild_history(id)
+            .get_rebuild_histories(
+                args.id.as_ref(),
+                args.node.as_deref(),
+                since.as_deref(),
+                until.as_deref(),
+                partial,
+            )
             .await
         {
-            Ok(history) => {
-                utils::print_table(output, history.into_body());
+            Ok(histories) => {
+                utils::print_table(output, VolumeRebuildHistories(histories.into_body()));
             }
             Err(e) => {
-                return Err(Error::GetRebuildHistory {
-                    id: id.to_string(),
-                    source: e,
-                });
+                return Err(Error::GetRebuildHistory { source: e });
             }
         }
         Ok(())
@@ -382,45 +658,54 @@ impl CreateRows for HashMap<String, openapi::models::ReplicaTopology> {
                     optional_cell(topology.child_status.as_ref().map(|s| s.to_string())),
                     optional_cell(topology.child_status_reason.as_ref().map(|s| s.to_string())),
                     optional_cell(topology.rebuild_progress.map(|p| format!("{p}%"))),
+                    optional_cell(topology.replica_move.as_ref().map(|m| m.to_string())),
                 ]
             })
             .collect()
     }
 }
 
-impl GetHeaderRow for openapi::models::RebuildHistory {
+#[derive(Default, Debug, serde::Serialize, serde::Deserialize)]
+struct VolumeRebuildHistories(Vec<openapi::models::VolumeRebuildHistory>);
+
+impl GetHeaderRow for VolumeRebuildHistories {
     fn get_header_row(&self) -> Row {
         (*utils::REBUILD_HISTORY_HEADER).clone()
     }
 }
 
-impl CreateRows for openapi::models::RebuildHistory {
+impl CreateRows for VolumeRebuildHistories {
     fn create_rows(&self) -> Vec<Row> {
-        self.records
+        self.0
             .iter()
-            .map(|rec| {
-                let start: DateTime<Utc> = DateTime::from_str(rec.start_time.as_str())
-                    .expect("Cant map time to required format");
-                let start_formatted = start.format("%Y-%m-%dT%H:%M:%SZ");
-                let end: DateTime<Utc> = DateTime::from_str(rec.end_time.as_str())
-                    .expect("Cant map time to required format");
-                let end_formatted = end.format("%Y-%m-%dT%H:%M:%SZ");
-                row![
-                    child_uuid(rec.child_uri.as_str()),
-                    child_uuid(rec.src_uri.as_str()),
-                    rec.rebuild_job_state,
-                    to_human_readable(rec.blocks_total * rec.block_size),
-                    to_human_readable(rec.blocks_recovered * rec.block_size),
-                    to_human_readable(rec.blocks_transferred * rec.block_size),
-                    rec.is_partial,
-                    start_formatted,
-                    end_formatted
-                ]
+            .flat_map(|history| {
+                history.records.iter().map(|volume_rec| {
+                    let rec = &volume_rec.record;
+                    row![
+                        history.volume_id,
+                        volume_rec.target_uuid,
+                        volume_rec.node,
+                        child_uuid(rec.child_uri.as_str()),
+                        child_uuid(rec.src_uri.as_str()),
+                        rec.rebuild_job_state,
+                        to_human_readable(rec.blocks_total * rec.block_size),
+                        to_human_readable(rec.blocks_recovered * rec.block_size),
+                        to_human_readable(rec.blocks_transferred * rec.block_size),
+                        rec.is_partial,
+                        rebuild_time(rec.start_time.as_str()),
+                        rebuild_time(rec.end_time.as_str())
+                    ]
+                })
             })
             .collect()
     }
 }
 
+fn rebuild_time(time: &str) -> String {
+    let time: DateTime<Utc> = DateTime::from_str(time).expect("Cant map time to required format");
+    time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
 fn child_uuid(uri: &str) -> String {
     let Ok(uri) = Url::from_str(uri) else {
         return "".into();
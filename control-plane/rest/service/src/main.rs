@@ -1,7 +1,7 @@
 mod authentication;
 mod v0;
 
-use crate::v0::{CORE_CLIENT, JSON_GRPC_CLIENT};
+use crate::v0::{CLUSTER_AGENT_CLIENT, CORE_CLIENT, JSON_GRPC_CLIENT};
 use actix_service::ServiceFactory;
 use actix_web::{
     body::MessageBody,
@@ -32,6 +32,10 @@ pub(crate) struct CliArgs {
     #[clap(long, short = 'J')]
     json_grpc: Option<Uri>,
 
+    /// The HA Cluster Agent gRPC Server URL or address to connect to the service.
+    #[clap(long)]
+    cluster_agent: Option<Uri>,
+
     /// Path to the certificate file
     #[clap(long, short, required_unless_present = "dummy_certificates")]
     cert_file: Option<String>,
@@ -110,7 +114,10 @@ fn timeout_opts() -> TimeoutOptions {
 
 use actix_web_opentelemetry::RequestTracing;
 use clap::Parser;
-use grpc::{client::CoreClient, operations::jsongrpc::client::JsonGrpcClient};
+use grpc::{
+    client::CoreClient,
+    operations::{ha_node::client::ClusterAgentClient, jsongrpc::client::JsonGrpcClient},
+};
 use http::Uri;
 use stor_port::transport_api::{RequestMinTimeout, TimeoutOptions};
 use utils::tracing_telemetry::{FmtLayer, FmtStyle, KeyValue};
@@ -244,6 +251,14 @@ async fn main() -> anyhow::Result<()> {
             .expect("Expect to be initialised only once");
     }
 
+    // Initialise the cluster agent client to be used in rest
+    if let Some(cluster_agent) = CliArgs::args().cluster_agent {
+        CLUSTER_AGENT_CLIENT
+            .set(ClusterAgentClient::new(cluster_agent, timeout_opts()).await)
+            .ok()
+            .expect("Expect to be initialised only once");
+    }
+
     let server =
         HttpServer::new(app).bind_rustls_021(CliArgs::args().https, get_certificates()?)?;
     let result = if let Some(http) = CliArgs::args().http {
@@ -14,6 +14,7 @@ pub mod snapshots;
 pub mod specs;
 pub mod states;
 pub mod swagger_ui;
+pub mod switchovers;
 pub mod volumes;
 pub mod watches;
 
@@ -25,7 +26,10 @@ use actix_web::{
     web, FromRequest, HttpRequest,
 };
 use futures::future::Ready;
-use grpc::{client::CoreClient, operations::jsongrpc::client::JsonGrpcClient};
+use grpc::{
+    client::CoreClient,
+    operations::{ha_node::client::ClusterAgentClient, jsongrpc::client::JsonGrpcClient},
+};
 use once_cell::sync::OnceCell;
 use rest_client::versions::v0::*;
 use serde::Deserialize;
@@ -42,6 +46,8 @@ use transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
 pub static CORE_CLIENT: OnceCell<CoreClient> = OnceCell::new();
 /// Once cell static variable to store the json grpc client and initialise once at startup
 pub static JSON_GRPC_CLIENT: OnceCell<JsonGrpcClient> = OnceCell::new();
+/// Once cell static variable to store the cluster agent client and initialise once at startup
+pub static CLUSTER_AGENT_CLIENT: OnceCell<ClusterAgentClient> = OnceCell::new();
 
 /// Get Core gRPC Client
 pub(crate) fn core_grpc<'a>() -> &'a CoreClient {
@@ -63,6 +69,19 @@ pub(crate) fn json_grpc<'a>() -> Result<&'a JsonGrpcClient, ReplyError> {
     }
 }
 
+/// Get HA Cluster Agent gRPC Client
+pub(crate) fn cluster_agent<'a>() -> Result<&'a ClusterAgentClient, ReplyError> {
+    match CLUSTER_AGENT_CLIENT.get() {
+        None => Err(ReplyError {
+            kind: ReplyErrorKind::Unavailable,
+            resource: ResourceKind::Unknown,
+            source: "HA Cluster Agent is not configured/running.".to_string(),
+            extra: "".to_string(),
+        }),
+        Some(client) => Ok(client),
+    }
+}
+
 fn version() -> String {
     "v0".into()
 }
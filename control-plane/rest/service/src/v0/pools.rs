@@ -1,6 +1,6 @@
 use super::*;
 use grpc::operations::pool::traits::PoolOperations;
-use stor_port::types::v0::transport::{DestroyPool, Filter};
+use stor_port::types::v0::transport::{DestroyPool, Filter, GetPoolRebalance};
 use transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
 
 fn client() -> impl PoolOperations {
@@ -48,6 +48,13 @@ impl apis::actix_server::Pools for RestApi {
         destroy_pool(Filter::Pool(pool_id.into())).await
     }
 
+    async fn delete_pool_cordon(
+        Path((pool_id, label)): Path<(String, String)>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        let pool = client().uncordon(pool_id.into(), label).await?;
+        Ok(pool.into())
+    }
+
     async fn get_node_pool(
         Path((node_id, pool_id)): Path<(String, String)>,
     ) -> Result<models::Pool, RestError<RestJsonError>> {
@@ -83,6 +90,13 @@ impl apis::actix_server::Pools for RestApi {
         Ok(pool.into())
     }
 
+    async fn get_pool_rebalance() -> Result<Vec<models::ReplicaMove>, RestError<RestJsonError>> {
+        let moves = client()
+            .get_rebalance(&GetPoolRebalance::default(), None)
+            .await?;
+        Ok(moves.into_inner().into_iter().map(From::from).collect())
+    }
+
     async fn get_pools() -> Result<Vec<models::Pool>, RestError<RestJsonError>> {
         let pools = client().get(Filter::None, None).await?;
         Ok(pools.into_inner().into_iter().map(From::from).collect())
@@ -97,6 +111,20 @@ impl apis::actix_server::Pools for RestApi {
         let pool = client().create(&create, None).await?;
         Ok(pool.into())
     }
+
+    async fn put_pool_cordon(
+        Path((pool_id, label)): Path<(String, String)>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        let pool = client().cordon(pool_id.into(), label).await?;
+        Ok(pool.into())
+    }
+
+    async fn put_pool_evacuate(
+        Path((pool_id, label)): Path<(String, String)>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        let pool = client().evacuate(pool_id.into(), label).await?;
+        Ok(pool.into())
+    }
 }
 
 /// returns pool from pool option and returns an error on non existence
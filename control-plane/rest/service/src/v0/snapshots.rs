@@ -1,14 +1,25 @@
 use super::*;
 use grpc::operations::{
     volume::traits::{
-        CreateVolumeSnapshot, DestroyVolumeSnapshot, ReplicaSnapshot, VolumeOperations,
-        VolumeReplicaSnapshotState, VolumeSnapshot,
+        CreateVolumeSnapshot, DestroyVolumeSnapshot, ReplicaSnapshot, RestoreVolumeSnapshot,
+        VolumeOperations, VolumeReplicaSnapshotState, VolumeSnapshot,
     },
     MaxEntries, Pagination, StartingToken,
 };
 use humantime::Timestamp;
 use rest_client::versions::v0::apis::Uuid;
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
+use stor_port::types::v0::{
+    store::snapshots::{
+        export::BackupTarget,
+        group::SnapshotGroupTarget,
+        schedule::{SnapshotScheduleTarget, SnapshotScheduleTrigger},
+    },
+    transport::{
+        CreateSnapshotExport, CreateSnapshotGroup, CreateSnapshotSchedule, DestroySnapshotExport,
+        DestroySnapshotGroup, DestroySnapshotSchedule,
+    },
+};
 
 fn client() -> impl VolumeOperations {
     core_grpc().volume()
@@ -162,6 +173,204 @@ impl apis::actix_server::Snapshots for RestApi {
         let snap = client().create_snapshot(&request, None).await?;
         Ok(to_models_volume_snapshot(&snap))
     }
+
+    async fn put_volume_snapshot_restore(
+        Path((volume_id, snapshot_id)): Path<(Uuid, Uuid)>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let request = RestoreVolumeSnapshot::new(&volume_id.into(), snapshot_id.into());
+        let volume = client().restore_snapshot(&request, None).await?;
+        Ok(volume.into())
+    }
+
+    async fn get_snapshot_schedules(
+    ) -> Result<Vec<models::SnapshotSchedule>, RestError<RestJsonError>> {
+        let schedules = client().get_snapshot_schedules(None, None).await?;
+        Ok(schedules.into_inner().into_iter().map(Into::into).collect())
+    }
+
+    async fn get_snapshot_schedule(
+        Path(schedule_id): Path<String>,
+    ) -> Result<models::SnapshotSchedule, RestError<RestJsonError>> {
+        let schedules = client()
+            .get_snapshot_schedules(Some(schedule_id.clone().into()), None)
+            .await?;
+        let schedule = schedules.into_inner().into_iter().next().ok_or_else(|| {
+            ReplyError::not_found(
+                ResourceKind::SnapshotSchedule,
+                "Snapshot schedule not found".to_string(),
+                schedule_id,
+            )
+        })?;
+        Ok(schedule.into())
+    }
+
+    async fn put_snapshot_schedule(
+        Path(schedule_id): Path<String>,
+        Body(body): Body<models::CreateSnapshotScheduleBody>,
+    ) -> Result<models::SnapshotSchedule, RestError<RestJsonError>> {
+        let target = match (body.volume_id, body.selector) {
+            (Some(volume_id), None) => SnapshotScheduleTarget::Volume(volume_id.into()),
+            (None, Some(selector)) => SnapshotScheduleTarget::Selector(selector),
+            _ => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::SnapshotSchedule,
+                    "snapshot_schedule.target",
+                    "exactly one of volume_id or selector must be specified".to_string(),
+                )
+                .into())
+            }
+        };
+        let trigger = match (body.interval_secs, body.cron) {
+            (Some(0), None) => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::SnapshotSchedule,
+                    "snapshot_schedule.interval_secs",
+                    "the interval must not be zero".to_string(),
+                )
+                .into())
+            }
+            (Some(secs), None) => SnapshotScheduleTrigger::Interval(Duration::from_secs(secs)),
+            (None, Some(cron)) => SnapshotScheduleTrigger::Cron(cron.parse().map_err(|error| {
+                ReplyError::invalid_argument(
+                    ResourceKind::SnapshotSchedule,
+                    "snapshot_schedule.cron",
+                    error,
+                )
+            })?),
+            _ => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::SnapshotSchedule,
+                    "snapshot_schedule.trigger",
+                    "exactly one of interval_secs or cron must be specified".to_string(),
+                )
+                .into())
+            }
+        };
+        let request = CreateSnapshotSchedule::new(
+            schedule_id.into(),
+            target,
+            trigger,
+            body.retention.into(),
+            body.quiesce.unwrap_or_default(),
+        );
+        let schedule = client().create_snapshot_schedule(&request, None).await?;
+        Ok(schedule.into())
+    }
+
+    async fn del_snapshot_schedule(
+        Path(schedule_id): Path<String>,
+    ) -> Result<(), RestError<RestJsonError>> {
+        client()
+            .destroy_snapshot_schedule(&DestroySnapshotSchedule::new(schedule_id.into()), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_snapshot_groups() -> Result<Vec<models::SnapshotGroup>, RestError<RestJsonError>> {
+        let groups = client().get_snapshot_groups(None, None).await?;
+        Ok(groups.into_inner().into_iter().map(Into::into).collect())
+    }
+
+    async fn get_snapshot_group(
+        Path(group_id): Path<String>,
+    ) -> Result<models::SnapshotGroup, RestError<RestJsonError>> {
+        let groups = client()
+            .get_snapshot_groups(Some(group_id.clone().into()), None)
+            .await?;
+        let group = groups.into_inner().into_iter().next().ok_or_else(|| {
+            ReplyError::not_found(
+                ResourceKind::SnapshotGroup,
+                "Snapshot group not found".to_string(),
+                group_id,
+            )
+        })?;
+        Ok(group.into())
+    }
+
+    async fn put_snapshot_group(
+        Path(group_id): Path<String>,
+        Body(body): Body<models::CreateSnapshotGroupBody>,
+    ) -> Result<models::SnapshotGroup, RestError<RestJsonError>> {
+        let target = match (body.volume_ids, body.selector, body.affinity_group) {
+            (Some(volume_ids), None, None) => {
+                SnapshotGroupTarget::Volumes(volume_ids.into_iter().map(Into::into).collect())
+            }
+            (None, Some(selector), None) => SnapshotGroupTarget::Selector(selector),
+            (None, None, Some(affinity_group)) => {
+                SnapshotGroupTarget::AffinityGroup(affinity_group)
+            }
+            _ => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::SnapshotGroup,
+                    "snapshot_group.target",
+                    "exactly one of volume_ids, selector or affinity_group must be specified"
+                        .to_string(),
+                )
+                .into())
+            }
+        };
+        let request = CreateSnapshotGroup::new(group_id.into(), target);
+        let group = client().create_snapshot_group(&request, None).await?;
+        Ok(group.into())
+    }
+
+    async fn del_snapshot_group(
+        Path(group_id): Path<String>,
+    ) -> Result<(), RestError<RestJsonError>> {
+        client()
+            .destroy_snapshot_group(&DestroySnapshotGroup::new(group_id.into()), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_snapshot_exports() -> Result<Vec<models::SnapshotExport>, RestError<RestJsonError>>
+    {
+        let exports = client().get_snapshot_exports(None, None).await?;
+        Ok(exports.into_inner().into_iter().map(Into::into).collect())
+    }
+
+    async fn get_snapshot_export(
+        Path(export_id): Path<Uuid>,
+    ) -> Result<models::SnapshotExport, RestError<RestJsonError>> {
+        let exports = client()
+            .get_snapshot_exports(Some(export_id.into()), None)
+            .await?;
+        let export = exports.into_inner().into_iter().next().ok_or_else(|| {
+            ReplyError::not_found(
+                ResourceKind::SnapshotExport,
+                "Snapshot export not found".to_string(),
+                export_id.to_string(),
+            )
+        })?;
+        Ok(export.into())
+    }
+
+    async fn put_snapshot_export(
+        Path(export_id): Path<Uuid>,
+        Body(body): Body<models::CreateSnapshotExportBody>,
+    ) -> Result<models::SnapshotExport, RestError<RestJsonError>> {
+        let target = BackupTarget::Directory {
+            node: body.node.into(),
+            path: body.path,
+        };
+        let request = CreateSnapshotExport::new(
+            export_id.into(),
+            body.snapshot_id.into(),
+            target,
+            body.base_export_id.map(Into::into),
+        );
+        let export = client().create_snapshot_export(&request, None).await?;
+        Ok(export.into())
+    }
+
+    async fn del_snapshot_export(
+        Path(export_id): Path<Uuid>,
+    ) -> Result<(), RestError<RestJsonError>> {
+        client()
+            .destroy_snapshot_export(&DestroySnapshotExport::new(export_id.into()), None)
+            .await?;
+        Ok(())
+    }
 }
 
 fn to_models_volume_snapshot(snap: &VolumeSnapshot) -> models::VolumeSnapshot {
@@ -1,6 +1,10 @@
 use super::*;
 use grpc::operations::registry::traits::RegistryOperations;
-use stor_port::types::v0::transport::GetSpecs;
+use serde_json::Value;
+use stor_port::{
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind},
+    types::v0::transport::{BackupSpecs, GetSpecs, RestoreSpecs},
+};
 
 fn client() -> impl RegistryOperations {
     core_grpc().registry()
@@ -12,4 +16,23 @@ impl apis::actix_server::Specs for RestApi {
         let specs = client().get_specs(&GetSpecs {}, None).await?;
         Ok(specs.into())
     }
+
+    async fn get_specs_backup() -> Result<Value, RestError<RestJsonError>> {
+        let backup = client().backup_specs(&BackupSpecs {}, None).await?;
+        let backup = serde_json::to_value(backup).map_err(|error| {
+            ReplyError::serde_error(ResourceKind::Spec, ReplyErrorKind::Internal, error)
+        })?;
+        Ok(backup)
+    }
+
+    async fn put_specs_restore(
+        Body(body): Body<Value>,
+    ) -> Result<models::SpecsRestored, RestError<RestJsonError>> {
+        let backup = serde_json::from_value(body)
+            .map_err(|error| ReplyError::invalid_argument(ResourceKind::Spec, "backup", error))?;
+        let restored = client()
+            .restore_specs(&RestoreSpecs { backup }, None)
+            .await?;
+        Ok(restored.into())
+    }
 }
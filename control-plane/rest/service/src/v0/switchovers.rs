@@ -0,0 +1,29 @@
+use super::*;
+use grpc::operations::ha_node::traits::ClusterAgentOperations;
+use stor_port::types::v0::transport::{GetSwitchOvers, MoveVolumeTarget};
+
+#[async_trait::async_trait]
+impl apis::actix_server::SwitchOvers for RestApi {
+    async fn get_switchovers() -> Result<Vec<models::SwitchOver>, RestError<RestJsonError>> {
+        let switchovers = cluster_agent()?
+            .get_switchovers(&GetSwitchOvers {}, None)
+            .await?;
+        Ok(switchovers.into_inner().into_vec())
+    }
+
+    async fn put_volume_target_move(
+        Path(volume_id): Path<Uuid>,
+        Body(move_target_body): Body<models::MoveTargetBody>,
+    ) -> Result<models::SwitchOver, RestError<RestJsonError>> {
+        let switchover = cluster_agent()?
+            .move_volume_target(
+                &MoveVolumeTarget {
+                    volume: volume_id.into(),
+                    target_node: move_target_body.node.map(Into::into),
+                },
+                None,
+            )
+            .await?;
+        Ok(switchover.into())
+    }
+}
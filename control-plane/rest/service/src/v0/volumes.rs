@@ -5,14 +5,17 @@ use grpc::operations::{
 };
 use stor_port::types::v0::{
     openapi::apis::Uuid,
+    store::rebuild_history::VolumeRebuildHistory,
     transport::{
-        DestroyShutdownTargets, DestroyVolume, Filter, GetRebuildRecord, PublishVolume,
+        CancelVolumeReplicaMove, DestroyShutdownTargets, DestroyVolume, Filter,
+        GetRebuildHistories, GetRebuildRecord, GetVolumeCapacity, MoveVolumeReplica, PublishVolume,
         RebuildHistory, RebuildJobState, RebuildRecord, RepublishVolume, ResizeVolume,
         SetVolumeProperty, SetVolumeReplica, ShareVolume, UnpublishVolume, UnshareVolume, Volume,
     },
 };
 
 use humantime::Timestamp;
+use std::time::SystemTime;
 
 fn client() -> impl VolumeOperations {
     core_grpc().volume()
@@ -48,6 +51,18 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(())
     }
 
+    async fn del_volume_replica_move(
+        Path((volume_id, replica_id)): Path<(Uuid, Uuid)>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .cancel_replica_move(
+                &CancelVolumeReplicaMove::new(volume_id.into(), replica_id.into()),
+                None,
+            )
+            .await?;
+        Ok(volume.into())
+    }
+
     async fn del_volume_shutdown_targets(
         Path(volume_id): Path<Uuid>,
     ) -> Result<(), RestError<RestJsonError>> {
@@ -58,11 +73,12 @@ impl apis::actix_server::Volumes for RestApi {
 
     async fn del_volume_target(
         Path(volume_id): Path<Uuid>,
-        Query(force): Query<Option<bool>>,
+        Query((force, frontend_node)): Query<(Option<bool>, Option<String>)>,
     ) -> Result<models::Volume, RestError<RestJsonError>> {
         let volume = client()
             .unpublish(
-                &UnpublishVolume::new(&volume_id.into(), force.unwrap_or(false)),
+                &UnpublishVolume::new(&volume_id.into(), force.unwrap_or(false))
+                    .with_frontend_node(frontend_node),
                 None,
             )
             .await?;
@@ -94,6 +110,30 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(rebuild_history_to_rest(record))
     }
 
+    async fn get_rebuild_histories(
+        Query((volume_id, node_id, since, until, partial)): Query<(
+            Option<Uuid>,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            Option<bool>,
+        )>,
+    ) -> Result<Vec<models::VolumeRebuildHistory>, RestError<RestJsonError>> {
+        let request = GetRebuildHistories {
+            volume: volume_id.map(Into::into),
+            node: node_id.map(Into::into),
+            since: parse_rebuild_time("since", since)?,
+            until: parse_rebuild_time("until", until)?,
+            partial,
+        };
+        let histories = nexus_client().get_rebuild_histories(&request, None).await?;
+        Ok(histories
+            .into_inner()
+            .into_iter()
+            .map(volume_rebuild_history_to_rest)
+            .collect())
+    }
+
     async fn get_volume(
         Path(volume_id): Path<Uuid>,
     ) -> Result<models::Volume, RestError<RestJsonError>> {
@@ -152,6 +192,42 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(volume.into())
     }
 
+    async fn put_volume_clone(
+        Path((source_id, volume_id)): Path<(Uuid, Uuid)>,
+        Body(create_volume_body): Body<models::CreateVolumeBody>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let clone = CreateVolumeBody::from(create_volume_body)
+            .to_clone_volume(source_id.into(), volume_id.into());
+        let volume = client().clone_volume(&clone, None).await?;
+        Ok(volume.into())
+    }
+
+    async fn put_snapshot_export_volume(
+        Path((export_id, volume_id)): Path<(Uuid, Uuid)>,
+        Body(create_volume_body): Body<models::CreateVolumeBody>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let restore = CreateVolumeBody::from(create_volume_body)
+            .to_restore_snapshot_export(export_id.into(), volume_id.into());
+        let volume = client().restore_snapshot_export(&restore, None).await?;
+        Ok(volume.into())
+    }
+
+    async fn put_volume_capacity(
+        Body(volume_capacity_body): Body<models::VolumeCapacityBody>,
+    ) -> Result<models::VolumeCapacity, RestError<RestJsonError>> {
+        let capacity = client()
+            .get_capacity(
+                &GetVolumeCapacity::new(
+                    volume_capacity_body.replicas,
+                    volume_capacity_body.thin,
+                    volume_capacity_body.topology.map(From::from),
+                ),
+                None,
+            )
+            .await?;
+        Ok(capacity.into())
+    }
+
     async fn put_volume(
         Path(volume_id): Path<Uuid>,
         Body(create_volume_body): Body<models::CreateVolumeBody>,
@@ -176,6 +252,24 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(volume.into())
     }
 
+    async fn put_volume_replica_move(
+        Path((volume_id, replica_id)): Path<(Uuid, Uuid)>,
+        Body(move_replica_body): Body<models::MoveReplicaBody>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .move_replica(
+                &MoveVolumeReplica::new(
+                    volume_id.into(),
+                    replica_id.into(),
+                    move_replica_body.pool.map(Into::into),
+                    move_replica_body.node.map(Into::into),
+                ),
+                None,
+            )
+            .await?;
+        Ok(volume.into())
+    }
+
     async fn put_volume_property(
         Path(volume_id): Path<Uuid>,
         Body(set_volume_property_body): Body<models::SetVolumePropertyBody>,
@@ -223,6 +317,8 @@ impl apis::actix_server::Volumes for RestApi {
                     uuid: volume_id.into(),
                     requested_size: resize_volume_body.size as u64,
                     cluster_capacity_limit: None,
+                    allow_shrink: resize_volume_body.allow_shrink.unwrap_or_default(),
+                    filesystem_size: resize_volume_body.filesystem_size.map(|size| size as u64),
                 },
                 None,
             )
@@ -263,6 +359,7 @@ impl apis::actix_server::Volumes for RestApi {
                             share: Some(publish_volume_body.protocol.into()),
                             publish_context: publish_volume_body.publish_context,
                             frontend_nodes: publish_volume_body.frontend_node.into_iter().collect(),
+                            read_only: publish_volume_body.read_only.unwrap_or(false),
                         },
                         None,
                     )
@@ -296,6 +393,34 @@ fn rebuild_history_to_rest(value: RebuildHistory) -> models::RebuildHistory {
     models::RebuildHistory::new(value.uuid, records)
 }
 
+fn volume_rebuild_history_to_rest(value: VolumeRebuildHistory) -> models::VolumeRebuildHistory {
+    let records = value
+        .records
+        .into_iter()
+        .map(|record| {
+            models::VolumeRebuildRecord::new(
+                record.nexus,
+                record.node,
+                rebuild_record_to_rest(record.record),
+            )
+        })
+        .collect();
+    models::VolumeRebuildHistory::new(value.volume_id, records)
+}
+
+/// Parse the optional RFC 3339 time of the `arg_name` query parameter.
+fn parse_rebuild_time(
+    arg_name: &str,
+    time: Option<String>,
+) -> Result<Option<SystemTime>, ReplyError> {
+    time.map(|time| {
+        humantime::parse_rfc3339_weak(&time).map_err(|error| {
+            ReplyError::invalid_argument(ResourceKind::Volume, arg_name, error.to_string())
+        })
+    })
+    .transpose()
+}
+
 fn rebuild_record_to_rest(value: RebuildRecord) -> models::RebuildRecord {
     let start = Timestamp::from(value.start_time).to_string();
     let end = Timestamp::from(value.end_time).to_string();
@@ -28,7 +28,10 @@ use stor_port::{
     transport_api::ReplyError,
     types::v0::{
         openapi::models::RestJsonError,
-        transport::{AffinityGroup, CreateSnapshotVolume, HostNqn, HostNqnParseError, SnapshotId},
+        transport::{
+            AffinityGroup, CloneVolume, CreateSnapshotVolume, EncryptionKeyRef, HostNqn,
+            HostNqnParseError, RestoreSnapshotExport, SnapshotExportId, SnapshotId, VolumeQos,
+        },
     },
 };
 
@@ -172,6 +175,7 @@ impl CreateNexusBody {
             managed: false,
             owner: None,
             config: None,
+            qos: None,
         }
     }
 }
@@ -195,6 +199,10 @@ pub struct CreateVolumeBody {
     pub affinity_group: Option<AffinityGroup>,
     /// Max snapshot limit per volume.
     pub max_snapshots: Option<u32>,
+    /// IO limits enforced on the volume target.
+    pub qos: Option<VolumeQos>,
+    /// Encrypt the volume at rest with the referenced key.
+    pub encryption: Option<EncryptionKeyRef>,
 }
 impl From<models::CreateVolumeBody> for CreateVolumeBody {
     fn from(src: models::CreateVolumeBody) -> Self {
@@ -207,6 +215,8 @@ impl From<models::CreateVolumeBody> for CreateVolumeBody {
             thin: src.thin,
             affinity_group: src.affinity_group.map(|ag| ag.into()),
             max_snapshots: src.max_snapshots,
+            qos: src.qos.into_opt(),
+            encryption: src.encryption.into_opt(),
         }
     }
 }
@@ -221,6 +231,8 @@ impl From<CreateVolume> for CreateVolumeBody {
             thin: create.thin,
             affinity_group: create.affinity_group,
             max_snapshots: create.max_snapshots,
+            qos: create.qos,
+            encryption: create.encryption,
         }
     }
 }
@@ -238,6 +250,8 @@ impl CreateVolumeBody {
             affinity_group: self.affinity_group.clone(),
             cluster_capacity_limit: None,
             max_snapshots: self.max_snapshots,
+            qos: self.qos.clone(),
+            encryption: self.encryption.clone(),
         }
     }
     /// Convert into rpc request type.
@@ -248,6 +262,18 @@ impl CreateVolumeBody {
     ) -> CreateSnapshotVolume {
         CreateSnapshotVolume::new(snapshot_id, self.to_create_volume(volume_id))
     }
+    /// Convert into rpc request type.
+    pub fn to_clone_volume(&self, source_id: VolumeId, volume_id: VolumeId) -> CloneVolume {
+        CloneVolume::new(source_id, self.to_create_volume(volume_id))
+    }
+    /// Convert into rpc request type.
+    pub fn to_restore_snapshot_export(
+        &self,
+        export_id: SnapshotExportId,
+        volume_id: VolumeId,
+    ) -> RestoreSnapshotExport {
+        RestoreSnapshotExport::new(export_id, self.to_create_volume(volume_id))
+    }
 }
 
 impl RestClient {
@@ -270,6 +296,12 @@ impl From<models::SetVolumePropertyBody> for SetVolumePropertyBody {
             models::SetVolumePropertyBody::max_snapshots(x) => Self {
                 property: VolumeProperty::MaxSnapshots(x),
             },
+            models::SetVolumePropertyBody::qos(x) => Self {
+                property: VolumeProperty::Qos(x.into()),
+            },
+            models::SetVolumePropertyBody::encryption_key(x) => Self {
+                property: VolumeProperty::EncryptionKey(x.into()),
+            },
         }
     }
 }
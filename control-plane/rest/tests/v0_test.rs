@@ -302,6 +302,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -332,7 +333,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
 
     let volume = client
         .volumes_api()
-        .del_volume_target(&volume_state.uuid, None)
+        .del_volume_target(&volume_state.uuid, None, None)
         .await
         .unwrap();
     tracing::info!("Volume: {:#?}", volume);
@@ -163,6 +163,12 @@ pub enum ResourceKind {
     AffinityGroup,
     /// App Node.
     AppNode,
+    /// Snapshot Schedule.
+    SnapshotSchedule,
+    /// Snapshot Group.
+    SnapshotGroup,
+    /// Snapshot Export.
+    SnapshotExport,
 }
 
 /// Error type which is returned over the transport for any operation.
@@ -2,7 +2,14 @@
 use super::*;
 
 use crate::{
-    impl_message, impl_vector_request, impl_vector_request_token, types::v0::transport::*,
+    impl_message, impl_vector_request, impl_vector_request_token,
+    types::v0::{
+        store::{
+            rebuild_history::VolumeRebuildHistory,
+            snapshots::{export::SnapshotExportSpec, schedule::SnapshotScheduleSpec},
+        },
+        transport::*,
+    },
 };
 
 // Only V0 should export this macro
@@ -33,6 +40,8 @@ impl_message!(DestroyPool);
 impl_message!(ImportPool);
 impl_vector_request!(Pools, Pool);
 impl_message!(GetPools);
+impl_vector_request!(ReplicaMoves, ReplicaMove);
+impl_message!(GetPoolRebalance);
 
 impl_vector_request!(NvmeSubsystems, NvmeSubsystem);
 
@@ -49,6 +58,20 @@ impl_message!(DestroyReplicaSnapshot);
 impl_message!(ListReplicaSnapshots);
 impl_message!(IoEngCreateSnapshotClone);
 impl_message!(CreateSnapshotVolume);
+impl_message!(CloneVolume);
+impl_vector_request!(SnapshotSchedules, SnapshotScheduleSpec);
+impl_message!(CreateSnapshotSchedule);
+impl_message!(DestroySnapshotSchedule);
+impl_message!(GetSnapshotSchedules);
+impl_vector_request!(SnapshotGroups, SnapshotGroup);
+impl_message!(CreateSnapshotGroup);
+impl_message!(DestroySnapshotGroup);
+impl_message!(GetSnapshotGroups);
+impl_vector_request!(SnapshotExports, SnapshotExportSpec);
+impl_message!(CreateSnapshotExport);
+impl_message!(DestroySnapshotExport);
+impl_message!(GetSnapshotExports);
+impl_message!(RestoreSnapshotExport);
 impl_message!(ListSnapshotClones);
 
 impl_vector_request!(Nexuses, Nexus);
@@ -56,6 +79,7 @@ impl_message!(GetNexuses);
 impl_message!(CreateNexus);
 impl_message!(DestroyNexus);
 impl_message!(ResizeNexus);
+impl_message!(SetNexusQos);
 impl_message!(ShareNexus);
 impl_message!(UnshareNexus);
 impl_message!(RemoveNexusChild);
@@ -65,6 +89,8 @@ impl_message!(NexusChildAction);
 impl_message!(ShutdownNexus);
 impl_message!(GetRebuildRecord);
 impl_message!(ListRebuildRecord);
+impl_vector_request!(VolumeRebuildHistories, VolumeRebuildHistory);
+impl_message!(GetRebuildHistories);
 impl_message!(CreateNexusSnapshot);
 
 impl_vector_request_token!(Volumes, Volume);
@@ -78,6 +104,7 @@ impl_message!(DestroyVolume);
 impl_message!(AddVolumeNexus);
 impl_message!(RemoveVolumeNexus);
 impl_message!(SetVolumeReplica);
+impl_message!(GetVolumeCapacity);
 
 impl_message!(JsonGrpcRequest, JsonGrpc);
 
@@ -91,3 +118,5 @@ impl_message!(DeleteWatch);
 
 impl_message!(GetSpecs);
 impl_message!(GetStates);
+impl_message!(BackupSpecs);
+impl_message!(RestoreSpecs);
@@ -6,6 +6,7 @@ pub mod nexus_child;
 pub mod nexus_persistence;
 pub mod node;
 pub mod pool;
+pub mod rebuild_history;
 pub mod registry;
 pub mod replica;
 pub mod snapshots;
@@ -1,12 +1,15 @@
 //! Definition of pool types that can be saved to the persistent store.
 
-use crate::types::v0::{
-    openapi::models,
-    store::{
-        definitions::{ObjectKey, StorableObject, StorableObjectType},
-        AsOperationSequencer, OperationSequence, SpecStatus, SpecTransaction,
+use crate::{
+    types::v0::{
+        openapi::models,
+        store::{
+            definitions::{ObjectKey, StorableObject, StorableObjectType},
+            AsOperationSequencer, OperationSequence, SpecStatus, SpecTransaction,
+        },
+        transport::{self, CreatePool, NodeId, PoolDeviceUri, PoolId},
     },
-    transport::{self, CreatePool, NodeId, PoolDeviceUri, PoolId},
+    IntoOption,
 };
 
 // PoolLabel is the type for the labels
@@ -53,6 +56,8 @@ impl From<&CreatePool> for PoolSpec {
             labels: request.labels.clone(),
             sequencer: OperationSequence::new(),
             operation: None,
+            cordon_labels: vec![],
+            evacuation: None,
         }
     }
 }
@@ -61,6 +66,8 @@ impl PartialEq<CreatePool> for PoolSpec {
         let mut other = PoolSpec::from(other);
         other.status = self.status.clone();
         other.sequencer = self.sequencer.clone();
+        other.cordon_labels = self.cordon_labels.clone();
+        other.evacuation = self.evacuation.clone();
         &other == self
     }
 }
@@ -83,6 +90,128 @@ pub struct PoolSpec {
     pub sequencer: OperationSequence,
     /// Record of the operation in progress
     pub operation: Option<PoolOperationState>,
+    /// Labels used to cordon the pool, preventing new replicas from being placed on it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cordon_labels: Vec<String>,
+    /// Evacuation of the replicas off the pool, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evacuation: Option<PoolEvacuation>,
+}
+
+impl PoolSpec {
+    /// Check if the pool is cordoned, either explicitly or because it's being evacuated.
+    pub fn cordoned(&self) -> bool {
+        !self.cordon_labels.is_empty() || self.evacuation.is_some()
+    }
+    /// Check if the pool is cordoned with the given label, including the evacuation label.
+    pub fn has_cordon_label(&self, label: &str) -> bool {
+        self.cordon_labels.iter().any(|l| l == label)
+            || self.evacuation.as_ref().map_or(false, |e| e.label == label)
+    }
+    /// Check if the pool is being evacuated, ie: it still has replicas to move off.
+    pub fn evacuating(&self) -> bool {
+        self.evacuation
+            .as_ref()
+            .map_or(false, |e| e.status == PoolEvacuationStatus::Evacuating)
+    }
+    /// Cordon the pool with the given label.
+    fn cordon(&mut self, label: String) {
+        self.cordon_labels.push(label);
+    }
+    /// Remove the given cordon label.
+    /// Removing the evacuation label cancels the evacuation.
+    fn uncordon(&mut self, label: &str) {
+        self.cordon_labels.retain(|l| l != label);
+        if self.evacuation.as_ref().map_or(false, |e| e.label == label) {
+            self.evacuation = None;
+        }
+    }
+    /// Start evacuating the pool, cordoning it with the given label.
+    fn evacuate(&mut self, label: String) {
+        self.evacuation = Some(PoolEvacuation::new(label));
+    }
+    /// Update the number of replicas remaining on the pool, marking the pool as evacuated once
+    /// no replicas remain.
+    fn set_evacuation_progress(&mut self, progress: PoolEvacuationProgress) {
+        if let Some(evacuation) = &mut self.evacuation {
+            evacuation.remaining_replicas = progress.remaining_replicas;
+            evacuation.blocked_replicas = progress.blocked_replicas;
+            if progress.remaining_replicas == 0 {
+                evacuation.status = PoolEvacuationStatus::Evacuated;
+            }
+        }
+    }
+}
+
+/// Evacuation of the replicas off a pool.
+/// The pool is cordoned with the evacuation label until it is uncordoned, which also cancels an
+/// evacuation still in progress.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PoolEvacuation {
+    /// Label used to cordon the pool for the evacuation.
+    pub label: String,
+    /// Status of the evacuation.
+    pub status: PoolEvacuationStatus,
+    /// Number of volume replicas still to be moved off the pool.
+    pub remaining_replicas: u64,
+    /// Number of the remaining replicas which cannot be moved until their volume is published,
+    /// as the new replicas are rebuilt by the volume target.
+    #[serde(default)]
+    pub blocked_replicas: u64,
+}
+impl PoolEvacuation {
+    /// Return a new `Self` for an evacuation which is just starting.
+    fn new(label: String) -> Self {
+        Self {
+            label,
+            status: PoolEvacuationStatus::Evacuating,
+            remaining_replicas: 0,
+            blocked_replicas: 0,
+        }
+    }
+    /// Get the progress of the evacuation.
+    pub fn progress(&self) -> PoolEvacuationProgress {
+        PoolEvacuationProgress {
+            remaining_replicas: self.remaining_replicas,
+            blocked_replicas: self.blocked_replicas,
+        }
+    }
+}
+impl From<PoolEvacuation> for models::PoolEvacuation {
+    fn from(src: PoolEvacuation) -> Self {
+        Self::new_all(
+            src.label,
+            src.remaining_replicas,
+            src.status,
+            src.blocked_replicas,
+        )
+    }
+}
+
+/// Progress of the evacuation of a pool.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct PoolEvacuationProgress {
+    /// Number of volume replicas still to be moved off the pool.
+    pub remaining_replicas: u64,
+    /// Number of the remaining replicas which cannot be moved until their volume is published.
+    pub blocked_replicas: u64,
+}
+
+/// Status of the evacuation of a pool.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PoolEvacuationStatus {
+    /// The volume replicas are being moved off the pool.
+    Evacuating,
+    /// All volume replicas have been moved off the pool.
+    Evacuated,
+}
+impl From<PoolEvacuationStatus> for models::PoolEvacuationStatus {
+    fn from(src: PoolEvacuationStatus) -> Self {
+        match src {
+            PoolEvacuationStatus::Evacuating => Self::Evacuating,
+            PoolEvacuationStatus::Evacuated => Self::Evacuated,
+        }
+    }
 }
 
 impl From<&PoolSpec> for ImportPool {
@@ -108,7 +237,20 @@ impl AsOperationSequencer for PoolSpec {
 
 impl From<PoolSpec> for models::PoolSpec {
     fn from(src: PoolSpec) -> Self {
-        Self::new_all(src.disks, src.id, src.labels, src.node, src.status)
+        let cordon_labels = if src.cordon_labels.is_empty() {
+            None
+        } else {
+            Some(src.cordon_labels)
+        };
+        Self::new_all(
+            src.disks,
+            src.id,
+            src.labels,
+            src.node,
+            src.status,
+            cordon_labels,
+            src.evacuation.into_opt(),
+        )
     }
 }
 
@@ -134,6 +276,12 @@ impl SpecTransaction<PoolOperation> for PoolSpec {
                 PoolOperation::Create => {
                     self.status = SpecStatus::Created(transport::PoolStatus::Online);
                 }
+                PoolOperation::Cordon(label) => self.cordon(label),
+                PoolOperation::Uncordon(label) => self.uncordon(&label),
+                PoolOperation::Evacuate(label) => self.evacuate(label),
+                PoolOperation::SetEvacuationProgress(progress) => {
+                    self.set_evacuation_progress(progress)
+                }
             }
         }
         self.clear_op();
@@ -166,6 +314,10 @@ impl SpecTransaction<PoolOperation> for PoolSpec {
 pub enum PoolOperation {
     Create,
     Destroy,
+    Cordon(String),
+    Uncordon(String),
+    Evacuate(String),
+    SetEvacuationProgress(PoolEvacuationProgress),
 }
 
 impl PartialEq<transport::PoolState> for PoolSpec {
@@ -0,0 +1,178 @@
+use crate::types::v0::transport::{NexusId, NodeId, RebuildRecord, VolumeId};
+use pstor::{ApiVersion, ObjectKey, StorableObject, StorableObjectType};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+/// Key used by the store to uniquely identify a VolumeRebuildHistory structure.
+pub struct VolumeRebuildHistoryKey(VolumeId);
+
+impl From<&VolumeId> for VolumeRebuildHistoryKey {
+    fn from(id: &VolumeId) -> Self {
+        Self(id.clone())
+    }
+}
+
+impl ObjectKey for VolumeRebuildHistoryKey {
+    type Kind = StorableObjectType;
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V0
+    }
+
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::VolumeRebuildHistory
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for VolumeRebuildHistory {
+    type Key = VolumeRebuildHistoryKey;
+
+    fn key(&self) -> Self::Key {
+        VolumeRebuildHistoryKey(self.volume_id.clone())
+    }
+}
+
+/// Retention policy of the persisted rebuild history of each volume.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebuildHistoryRetention {
+    /// Maximum number of rebuild records to keep per volume.
+    pub max_records: usize,
+    /// Maximum age of the rebuild records, by their end time.
+    pub max_age: Option<Duration>,
+}
+
+/// A rebuild record of a volume, along with the target where the rebuild took place.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct VolumeRebuildRecord {
+    /// The volume target which rebuilt the child.
+    pub nexus: NexusId,
+    /// The node where the volume target was placed.
+    pub node: NodeId,
+    /// The rebuild record.
+    #[serde(flatten)]
+    pub record: RebuildRecord,
+}
+
+impl VolumeRebuildRecord {
+    /// Check if `other` is the same rebuild job as `self`.
+    fn same_job(&self, other: &Self) -> bool {
+        self.nexus == other.nexus
+            && self.record.child_uri == other.record.child_uri
+            && self.record.start_time == other.record.start_time
+    }
+}
+
+/// The persisted rebuild history of a volume, across all of its targets.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct VolumeRebuildHistory {
+    /// The volume identification.
+    pub volume_id: VolumeId,
+    /// The rebuild records, sorted from newest to oldest by their end time.
+    pub records: Vec<VolumeRebuildRecord>,
+}
+
+impl VolumeRebuildHistory {
+    /// Create a new empty history for the given volume.
+    pub fn new(volume_id: VolumeId) -> Self {
+        Self {
+            volume_id,
+            records: vec![],
+        }
+    }
+
+    /// Merge the given records with the existing ones, skipping the records which are already
+    /// present and applying the retention policy.
+    /// Returns true if the history has been modified.
+    pub fn merge(
+        &mut self,
+        records: impl IntoIterator<Item = VolumeRebuildRecord>,
+        retention: &RebuildHistoryRetention,
+    ) -> bool {
+        let previous = self.records.clone();
+        for record in records {
+            if !self
+                .records
+                .iter()
+                .any(|existing| existing.same_job(&record))
+            {
+                self.records.push(record);
+            }
+        }
+        self.records
+            .sort_by(|a, b| b.record.end_time.cmp(&a.record.end_time));
+        self.retain(retention, SystemTime::now());
+        // records which are dropped by the retention policy as soon as they're merged do not
+        // count as a modification, otherwise they'd be merged again and again.
+        self.records != previous
+    }
+
+    /// Apply the retention policy, relative to `now`.
+    /// Returns true if any record has been removed.
+    pub fn retain(&mut self, retention: &RebuildHistoryRetention, now: SystemTime) -> bool {
+        let len = self.records.len();
+        self.records.truncate(retention.max_records);
+        if let Some(max_age) = retention.max_age {
+            self.records.retain(|record| {
+                now.duration_since(record.record.end_time)
+                    .map_or(true, |age| age <= max_age)
+            });
+        }
+        self.records.len() != len
+    }
+}
+
+#[test]
+fn rebuild_history_merge() {
+    use crate::types::v0::transport::RebuildJobState;
+    let now = SystemTime::now();
+    let record = |nexus: &NexusId, child: &str, age_secs: u64| VolumeRebuildRecord {
+        nexus: nexus.clone(),
+        node: "io-engine-1".into(),
+        record: RebuildRecord {
+            child_uri: child.into(),
+            src_uri: "bdev:///src".into(),
+            state: RebuildJobState::Completed,
+            blocks_total: 100,
+            blocks_recovered: 100,
+            blocks_transferred: 100,
+            blocks_remaining: 0,
+            blocks_per_task: 10,
+            block_size: 512,
+            is_partial: false,
+            start_time: now - Duration::from_secs(age_secs + 1),
+            end_time: now - Duration::from_secs(age_secs),
+        },
+    };
+    let retention = RebuildHistoryRetention {
+        max_records: 3,
+        max_age: Some(Duration::from_secs(100)),
+    };
+    let nexus = NexusId::new();
+    let nexus2 = NexusId::new();
+    let mut history = VolumeRebuildHistory::new(VolumeId::new());
+
+    assert!(history.merge(vec![record(&nexus, "bdev:///a", 10)], &retention));
+    // the same record is only recorded once
+    assert!(!history.merge(vec![record(&nexus, "bdev:///a", 10)], &retention));
+    // records of previous targets are kept
+    assert!(history.merge(vec![record(&nexus2, "bdev:///a", 5)], &retention));
+    assert_eq!(history.records.len(), 2);
+    assert_eq!(history.records[0].nexus, nexus2);
+
+    // records are expired by age
+    assert!(!history.merge(vec![record(&nexus2, "bdev:///b", 200)], &retention));
+    assert_eq!(history.records.len(), 2);
+
+    // and only the newest records are kept
+    let records = (0..5).map(|i| record(&nexus2, "bdev:///c", i));
+    assert!(history.merge(records, &retention));
+    assert_eq!(history.records.len(), 3);
+    assert!(history
+        .records
+        .iter()
+        .all(|r| r.record.child_uri.as_str() == "bdev:///c"));
+}
@@ -1,7 +1,7 @@
 use crate::types::v0::store::definitions::{ObjectKey, StorableObject, StorableObjectType};
 use pstor::ApiVersion;
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{fmt::Debug, net::SocketAddr};
 
 /// Registry configuration loaded from/stored into the persistent store
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -110,6 +110,45 @@ impl StorableObject for CoreRegistryConfig {
 #[derive(Serialize, Deserialize, Debug, Clone, strum_macros::Display)]
 pub enum ControlPlaneService {
     CoreAgent,
+    HaClusterAgent,
+}
+
+/// The HA cluster agent instance which currently holds the leadership, stored by the leader so
+/// that the standby instances can forward requests to it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HaClusterLeader {
+    /// The name of the leader instance.
+    pub instance_name: String,
+    /// The grpc endpoint of the leader, if it's reachable by the other instances.
+    pub endpoint: Option<SocketAddr>,
+}
+
+/// Key used to store the `HaClusterLeader`.
+#[derive(Debug, Default)]
+pub struct HaClusterLeaderKey {}
+
+impl ObjectKey for HaClusterLeaderKey {
+    type Kind = StorableObjectType;
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V0
+    }
+
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::HaClusterLeader
+    }
+
+    fn key_uuid(&self) -> String {
+        ControlPlaneService::HaClusterAgent.to_string()
+    }
+}
+
+impl StorableObject for HaClusterLeader {
+    type Key = HaClusterLeaderKey;
+
+    fn key(&self) -> Self::Key {
+        HaClusterLeaderKey {}
+    }
 }
 
 /// Key used by the store lock api to identify the lock.
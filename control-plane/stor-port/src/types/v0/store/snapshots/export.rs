@@ -0,0 +1,157 @@
+use crate::types::v0::{
+    openapi::models,
+    transport::{AppNodeId, SnapshotExportId, SnapshotId, VolumeId},
+};
+use chrono::{DateTime, Utc};
+use pstor::{ApiVersion, ObjectKey, StorableObject, StorableObjectType};
+use serde::{Deserialize, Serialize};
+
+/// Key used by the store to uniquely identify a SnapshotExportSpec structure.
+pub struct SnapshotExportSpecKey(SnapshotExportId);
+
+impl From<&SnapshotExportId> for SnapshotExportSpecKey {
+    fn from(id: &SnapshotExportId) -> Self {
+        Self(id.clone())
+    }
+}
+
+impl ObjectKey for SnapshotExportSpecKey {
+    type Kind = StorableObjectType;
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V0
+    }
+
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::SnapshotExportSpec
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for SnapshotExportSpec {
+    type Key = SnapshotExportSpecKey;
+
+    fn key(&self) -> Self::Key {
+        SnapshotExportSpecKey(self.id.clone())
+    }
+}
+
+/// The backup target which snapshots are exported to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum BackupTarget {
+    /// A directory on an application node, which may itself be backed by a network filesystem
+    /// or an object store mount.
+    Directory {
+        /// The application node which exports the snapshots into the directory.
+        node: AppNodeId,
+        /// The path of the directory on the application node.
+        path: String,
+    },
+}
+
+impl BackupTarget {
+    /// Get the application node which moves the data to and from the target.
+    pub fn app_node(&self) -> &AppNodeId {
+        match self {
+            Self::Directory { node, .. } => node,
+        }
+    }
+    /// Get the path of the target directory.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Directory { path, .. } => path,
+        }
+    }
+}
+
+/// Status of a snapshot export.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotExportStatus {
+    /// The snapshot is being exported.
+    /// If the export is interrupted the partial export is deleted.
+    Exporting,
+    /// The snapshot was exported and can be restored.
+    Exported,
+    /// The export is being deleted from the backup target.
+    Deleting,
+}
+
+impl From<SnapshotExportStatus> for models::SnapshotExportStatus {
+    fn from(src: SnapshotExportStatus) -> Self {
+        match src {
+            SnapshotExportStatus::Exporting => Self::Exporting,
+            SnapshotExportStatus::Exported => Self::Exported,
+            SnapshotExportStatus::Deleting => Self::Deleting,
+        }
+    }
+}
+
+/// Statistics of a completed snapshot export.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotExportStats {
+    /// The number of chunks of the exported snapshot.
+    pub chunks: u64,
+    /// The number of chunks which differ from the base export.
+    pub changed_chunks: u64,
+    /// The number of bytes written into the backup target.
+    pub written_bytes: u64,
+}
+
+/// Export of a volume snapshot into a backup target.
+/// An incremental export only holds the data which changed since its base export, and so it can
+/// only be restored along with its whole chain of base exports.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotExportSpec {
+    /// Snapshot export identification.
+    pub id: SnapshotExportId,
+    /// The exported volume snapshot.
+    pub snapshot_id: SnapshotId,
+    /// The source volume of the exported snapshot.
+    pub volume_id: VolumeId,
+    /// The backup target which the snapshot is exported to.
+    pub target: BackupTarget,
+    /// The export which this export is incremental to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<SnapshotExportId>,
+    /// The size of the exported snapshot in bytes.
+    pub size: u64,
+    /// The temporary volume which the snapshot is read through while being exported.
+    /// It's persisted upfront so it can be cleaned up if the export is interrupted.
+    pub transfer_volume: VolumeId,
+    /// Status of the snapshot export.
+    pub status: SnapshotExportStatus,
+    /// Time at which the export was completed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Statistics of the completed export.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<SnapshotExportStats>,
+}
+
+impl SnapshotExportSpec {
+    /// Create a new `Self`, which is yet to be exported.
+    pub fn new(
+        id: SnapshotExportId,
+        snapshot_id: SnapshotId,
+        volume_id: VolumeId,
+        target: BackupTarget,
+        base: Option<SnapshotExportId>,
+        size: u64,
+    ) -> Self {
+        Self {
+            id,
+            snapshot_id,
+            volume_id,
+            target,
+            base,
+            size,
+            transfer_volume: VolumeId::new(),
+            status: SnapshotExportStatus::Exporting,
+            timestamp: None,
+            stats: None,
+        }
+    }
+}
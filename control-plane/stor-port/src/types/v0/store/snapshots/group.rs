@@ -0,0 +1,166 @@
+use crate::types::v0::{
+    openapi::models,
+    store::volume::{AffinityGroupId, VolumeSpec},
+    transport::{SnapshotGroupId, SnapshotId, VolumeId, VolumeLabels},
+};
+use chrono::{DateTime, Utc};
+use pstor::{ApiVersion, ObjectKey, StorableObject, StorableObjectType};
+use serde::{Deserialize, Serialize};
+
+/// Key used by the store to uniquely identify a SnapshotGroupSpec structure.
+pub struct SnapshotGroupSpecKey(SnapshotGroupId);
+
+impl From<&SnapshotGroupId> for SnapshotGroupSpecKey {
+    fn from(id: &SnapshotGroupId) -> Self {
+        Self(id.clone())
+    }
+}
+
+impl ObjectKey for SnapshotGroupSpecKey {
+    type Kind = StorableObjectType;
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V0
+    }
+
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::SnapshotGroupSpec
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for SnapshotGroupSpec {
+    type Key = SnapshotGroupSpecKey;
+
+    fn key(&self) -> Self::Key {
+        SnapshotGroupSpecKey(self.id.clone())
+    }
+}
+
+/// The volumes which are snapshotted by a snapshot group.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotGroupTarget {
+    /// An explicit list of volumes.
+    Volumes(Vec<VolumeId>),
+    /// All volumes which carry every one of the selector's labels.
+    Selector(VolumeLabels),
+    /// All volumes of the affinity group, eg: all volumes of a StatefulSet.
+    AffinityGroup(AffinityGroupId),
+}
+
+impl SnapshotGroupTarget {
+    /// Check if the given volume is targeted by the snapshot group.
+    pub fn matches(&self, volume: &VolumeSpec) -> bool {
+        match self {
+            Self::Volumes(volumes) => volumes.contains(&volume.uuid),
+            Self::Selector(selector) => match &volume.labels {
+                Some(labels) => selector
+                    .iter()
+                    .all(|(key, value)| labels.get(key) == Some(value)),
+                None => false,
+            },
+            Self::AffinityGroup(affinity_group) => volume
+                .affinity_group
+                .as_ref()
+                .map_or(false, |group| group.id() == affinity_group),
+        }
+    }
+    /// Get the volume ids, if targeting an explicit list of volumes.
+    pub fn volumes(&self) -> Option<&Vec<VolumeId>> {
+        match self {
+            Self::Volumes(volumes) => Some(volumes),
+            _ => None,
+        }
+    }
+    /// Get the label selector, if targeting volumes by their labels.
+    pub fn selector(&self) -> Option<&VolumeLabels> {
+        match self {
+            Self::Selector(selector) => Some(selector),
+            _ => None,
+        }
+    }
+    /// Get the affinity group, if targeting the volumes of an affinity group.
+    pub fn affinity_group(&self) -> Option<&AffinityGroupId> {
+        match self {
+            Self::AffinityGroup(affinity_group) => Some(affinity_group),
+            _ => None,
+        }
+    }
+}
+
+/// A volume snapshot which was taken as part of a snapshot group.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotGroupMember {
+    /// The snapshotted volume.
+    pub volume_id: VolumeId,
+    /// The snapshot of the volume.
+    pub snapshot_id: SnapshotId,
+}
+
+impl SnapshotGroupMember {
+    /// Create a new `Self` for a new snapshot of the given volume.
+    pub fn new(volume_id: VolumeId) -> Self {
+        Self {
+            volume_id,
+            snapshot_id: SnapshotId::new(),
+        }
+    }
+}
+
+/// Status of a snapshot group.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotGroupStatus {
+    /// The member snapshots are being created.
+    /// If the creation is interrupted the member snapshots are deleted along with the group.
+    Creating,
+    /// All member snapshots were created at the same quiesce point.
+    Created,
+    /// The member snapshots are being deleted, after which the group itself is deleted.
+    Deleting,
+}
+
+impl From<SnapshotGroupStatus> for models::SnapshotGroupStatus {
+    fn from(src: SnapshotGroupStatus) -> Self {
+        match src {
+            SnapshotGroupStatus::Creating => Self::Creating,
+            SnapshotGroupStatus::Created => Self::Created,
+            SnapshotGroupStatus::Deleting => Self::Deleting,
+        }
+    }
+}
+
+/// Snapshot group, which snapshots a set of volumes at a single quiesce point.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotGroupSpec {
+    /// Snapshot group identification.
+    pub id: SnapshotGroupId,
+    /// The volumes which are snapshotted by the group.
+    pub target: SnapshotGroupTarget,
+    /// The volume snapshots of the group, resolved from the target when the group is created.
+    pub members: Vec<SnapshotGroupMember>,
+    /// Status of the snapshot group.
+    pub status: SnapshotGroupStatus,
+    /// Time at which the member snapshots were taken.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl SnapshotGroupSpec {
+    /// Create a new `Self`, whose member snapshots are yet to be created.
+    pub fn new(
+        id: SnapshotGroupId,
+        target: SnapshotGroupTarget,
+        members: Vec<SnapshotGroupMember>,
+    ) -> Self {
+        Self {
+            id,
+            target,
+            members,
+            status: SnapshotGroupStatus::Creating,
+            timestamp: None,
+        }
+    }
+}
@@ -1,7 +1,13 @@
+pub mod export;
+pub mod group;
 pub mod replica;
+pub mod schedule;
 pub mod volume;
 
-use crate::types::v0::{transport, transport::SnapshotId};
+use crate::types::v0::{
+    transport,
+    transport::{SnapshotGroupId, SnapshotId, SnapshotScheduleId},
+};
 use serde::{Deserialize, Serialize};
 
 /// User specification of a snapshot.
@@ -9,6 +15,16 @@ use serde::{Deserialize, Serialize};
 pub struct SnapshotSpec<SourceId: Clone> {
     source_id: SourceId,
     uuid: SnapshotId,
+    /// Internal snapshots are created by the control-plane itself, eg: to clone a volume, and
+    /// are hidden from the user.
+    #[serde(default, skip_serializing_if = "super::is_default")]
+    internal: bool,
+    /// The snapshot schedule which created this snapshot, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schedule: Option<SnapshotScheduleId>,
+    /// The snapshot group which this snapshot is a member of, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    group: Option<SnapshotGroupId>,
 }
 
 impl<SourceId: Clone> SnapshotSpec<SourceId> {
@@ -17,6 +33,34 @@ impl<SourceId: Clone> SnapshotSpec<SourceId> {
         Self {
             source_id: source_id.clone(),
             uuid,
+            internal: false,
+            schedule: None,
+            group: None,
+        }
+    }
+    /// Create a new internal `Self` from the given parameters.
+    pub fn new_internal(source_id: &SourceId, uuid: SnapshotId) -> Self {
+        Self {
+            internal: true,
+            ..Self::new(source_id, uuid)
+        }
+    }
+    /// Create a new `Self` from the given parameters, created by the given snapshot schedule.
+    pub fn new_scheduled(
+        source_id: &SourceId,
+        uuid: SnapshotId,
+        schedule: SnapshotScheduleId,
+    ) -> Self {
+        Self {
+            schedule: Some(schedule),
+            ..Self::new(source_id, uuid)
+        }
+    }
+    /// Create a new `Self` from the given parameters, as a member of the given snapshot group.
+    pub fn new_grouped(source_id: &SourceId, uuid: SnapshotId, group: SnapshotGroupId) -> Self {
+        Self {
+            group: Some(group),
+            ..Self::new(source_id, uuid)
         }
     }
     /// Get the snapshot source id.
@@ -27,6 +71,18 @@ impl<SourceId: Clone> SnapshotSpec<SourceId> {
     pub fn uuid(&self) -> &SnapshotId {
         &self.uuid
     }
+    /// Check if this is an internal snapshot, hidden from the user.
+    pub fn internal(&self) -> bool {
+        self.internal
+    }
+    /// Get the snapshot schedule which created this snapshot, if any.
+    pub fn schedule(&self) -> Option<&SnapshotScheduleId> {
+        self.schedule.as_ref()
+    }
+    /// Get the snapshot group which this snapshot is a member of, if any.
+    pub fn group(&self) -> Option<&SnapshotGroupId> {
+        self.group.as_ref()
+    }
 }
 
 /// Runtime state of a replica snapshot.
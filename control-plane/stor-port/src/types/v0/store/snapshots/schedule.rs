@@ -0,0 +1,416 @@
+use crate::types::v0::{
+    openapi::models,
+    store::volume::VolumeSpec,
+    transport::{SnapshotScheduleId, VolumeId, VolumeLabels},
+};
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use pstor::{ApiVersion, ObjectKey, StorableObject, StorableObjectType};
+use serde::{Deserialize, Serialize};
+use std::{str::FromStr, time::Duration};
+
+/// Key used by the store to uniquely identify a SnapshotScheduleSpec structure.
+pub struct SnapshotScheduleSpecKey(SnapshotScheduleId);
+
+impl From<&SnapshotScheduleId> for SnapshotScheduleSpecKey {
+    fn from(id: &SnapshotScheduleId) -> Self {
+        Self(id.clone())
+    }
+}
+
+impl ObjectKey for SnapshotScheduleSpecKey {
+    type Kind = StorableObjectType;
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V0
+    }
+
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::SnapshotScheduleSpec
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for SnapshotScheduleSpec {
+    type Key = SnapshotScheduleSpecKey;
+
+    fn key(&self) -> Self::Key {
+        SnapshotScheduleSpecKey(self.id.clone())
+    }
+}
+
+/// The volumes which are snapshotted by a schedule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotScheduleTarget {
+    /// A single volume.
+    Volume(VolumeId),
+    /// All volumes which carry every one of the selector's labels.
+    Selector(VolumeLabels),
+}
+
+impl SnapshotScheduleTarget {
+    /// Check if the given volume is targeted by the schedule.
+    pub fn matches(&self, volume: &VolumeSpec) -> bool {
+        match self {
+            Self::Volume(volume_id) => &volume.uuid == volume_id,
+            Self::Selector(selector) => match &volume.labels {
+                Some(labels) => selector
+                    .iter()
+                    .all(|(key, value)| labels.get(key) == Some(value)),
+                None => false,
+            },
+        }
+    }
+    /// Get the volume id, if targeting a single volume.
+    pub fn volume(&self) -> Option<&VolumeId> {
+        match self {
+            Self::Volume(volume_id) => Some(volume_id),
+            Self::Selector(_) => None,
+        }
+    }
+    /// Get the label selector, if targeting volumes by their labels.
+    pub fn selector(&self) -> Option<&VolumeLabels> {
+        match self {
+            Self::Volume(_) => None,
+            Self::Selector(selector) => Some(selector),
+        }
+    }
+}
+
+/// Retention policy of the scheduled snapshots.
+/// Once either limit is exceeded the oldest scheduled snapshots are pruned.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct SnapshotRetention {
+    /// Maximum number of scheduled snapshots to keep per volume.
+    pub count: Option<u32>,
+    /// Maximum age of the scheduled snapshots.
+    pub max_age: Option<Duration>,
+}
+
+impl SnapshotRetention {
+    /// Check if a scheduled snapshot should be pruned, given its `position` amongst the volume's
+    /// scheduled snapshots (newest first) and its `age`.
+    pub fn expired(&self, position: usize, age: Duration) -> bool {
+        self.count.map_or(false, |count| position >= count as usize)
+            || self.max_age.map_or(false, |max_age| age > max_age)
+    }
+}
+
+/// When the scheduled snapshots of a volume are taken.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotScheduleTrigger {
+    /// At a fixed interval from the previous scheduled snapshot.
+    Interval(Duration),
+    /// At the times matched by a cron expression.
+    Cron(CronSchedule),
+}
+
+impl SnapshotScheduleTrigger {
+    /// Check if a snapshot is due at `now`, given the time of the previous scheduled snapshot.
+    pub fn due(&self, last: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        match self {
+            Self::Interval(interval) => (now - last)
+                .to_std()
+                .map_or(false, |elapsed| elapsed >= *interval),
+            Self::Cron(cron) => cron.next_after(last).map_or(false, |next| next <= now),
+        }
+    }
+    /// Get the interval, if triggered at a fixed interval.
+    pub fn interval(&self) -> Option<Duration> {
+        match self {
+            Self::Interval(interval) => Some(*interval),
+            Self::Cron(_) => None,
+        }
+    }
+    /// Get the cron schedule, if triggered by a cron expression.
+    pub fn cron(&self) -> Option<&CronSchedule> {
+        match self {
+            Self::Interval(_) => None,
+            Self::Cron(cron) => Some(cron),
+        }
+    }
+}
+
+/// A cron expression in the standard 5 field format: `minute hour day-of-month month day-of-week`.
+/// Each field is either `*`, a value, a range `a-b`, a step `*/n` or `a-b/n`, or a comma separated
+/// list of those. Days of the week range from 0 (Sunday) to 7 (also Sunday).
+/// As with cron, if both the day of the month and the day of the week are restricted then a day
+/// matching either one of them is matched. Times are matched in UTC.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    /// Get the first time matched by the schedule which is strictly after the given `time`.
+    /// Returns `None` if the schedule doesn't match any time within the next 28 years, which
+    /// covers every combination of leap years and days of the week.
+    pub fn next_after(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut next = time.with_second(0)?.with_nanosecond(0)? + chrono::Duration::minutes(1);
+        let limit = next + chrono::Duration::days(366 * 28);
+        while next < limit {
+            if !self.day_matches(&next) {
+                let midnight = next.date_naive().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                next = Utc.from_utc_datetime(&midnight);
+            } else if !Self::matches(self.hours, next.hour()) {
+                next = next.with_minute(0)? + chrono::Duration::hours(1);
+            } else if !Self::matches(self.minutes, next.minute()) {
+                next += chrono::Duration::minutes(1);
+            } else {
+                return Some(next);
+            }
+        }
+        None
+    }
+    fn day_matches(&self, time: &DateTime<Utc>) -> bool {
+        if !Self::matches(self.months, time.month()) {
+            return false;
+        }
+        let day_of_month = Self::matches(self.days_of_month, time.day());
+        let day_of_week = Self::matches(self.days_of_week, time.weekday().num_days_from_sunday());
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (false, false) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+    fn matches(field: u64, value: u32) -> bool {
+        field & (1 << value) != 0
+    }
+    /// Parse a single field into a bitmask of the matched values.
+    fn parse_field(field: &str, name: &str, min: u32, max: u32) -> Result<u64, String> {
+        let parse = |value: &str| match value.parse::<u32>() {
+            Ok(value) if (min ..= max).contains(&value) => Ok(value),
+            _ => Err(format!(
+                "invalid {name} '{value}', expected a value between {min} and {max}"
+            )),
+        };
+        let mut mask = 0u64;
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => match step.parse::<usize>() {
+                    Ok(step) if step > 0 => (range, Some(step)),
+                    _ => return Err(format!("invalid {name} step '{step}'")),
+                },
+                None => (part, None),
+            };
+            let (start, end) = match range.split_once('-') {
+                _ if range == "*" => (min, max),
+                Some((start, end)) => (parse(start)?, parse(end)?),
+                // `a/n` starts at `a` and runs until the end of the range.
+                None if step.is_some() => (parse(range)?, max),
+                None => parse(range).map(|value| (value, value))?,
+            };
+            if start > end {
+                return Err(format!("invalid {name} range '{range}'"));
+            }
+            for value in (start ..= end).step_by(step.unwrap_or(1)) {
+                mask |= 1 << value;
+            }
+        }
+        Ok(mask)
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(format!(
+                "expected 5 fields (minute hour day-of-month month day-of-week), found {}",
+                fields.len()
+            ));
+        };
+        let mut week_mask = Self::parse_field(days_of_week, "day of the week", 0, 7)?;
+        // both 0 and 7 are Sunday
+        if week_mask & (1 << 7) != 0 {
+            week_mask = (week_mask | 1) & !(1 << 7);
+        }
+        let schedule = Self {
+            expression: fields.join(" "),
+            minutes: Self::parse_field(minutes, "minute", 0, 59)?,
+            hours: Self::parse_field(hours, "hour", 0, 23)?,
+            days_of_month: Self::parse_field(days_of_month, "day of the month", 1, 31)?,
+            months: Self::parse_field(months, "month", 1, 12)?,
+            days_of_week: week_mask,
+            any_day_of_month: days_of_month.starts_with('*'),
+            any_day_of_week: days_of_week.starts_with('*'),
+        };
+        if schedule.next_after(DateTime::default()).is_none() {
+            return Err(format!("'{expression}' never matches any date"));
+        }
+        Ok(schedule)
+    }
+}
+
+impl TryFrom<String> for CronSchedule {
+    type Error = String;
+
+    fn try_from(expression: String) -> Result<Self, Self::Error> {
+        expression.parse()
+    }
+}
+
+impl From<CronSchedule> for String {
+    fn from(schedule: CronSchedule) -> Self {
+        schedule.expression
+    }
+}
+
+impl std::fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+/// Snapshot schedule, which periodically takes snapshots of the targeted volumes.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotScheduleSpec {
+    /// Snapshot schedule identification.
+    pub id: SnapshotScheduleId,
+    /// The volumes which are snapshotted by the schedule.
+    pub target: SnapshotScheduleTarget,
+    /// When the snapshots of a volume are taken.
+    #[serde(flatten)]
+    pub trigger: SnapshotScheduleTrigger,
+    /// Retention policy for the scheduled snapshots.
+    pub retention: SnapshotRetention,
+    /// Quiesce the filesystem of published volumes before taking the snapshot.
+    #[serde(default)]
+    pub quiesce: bool,
+}
+
+impl SnapshotScheduleSpec {
+    /// Create a new `Self` from the given parameters.
+    pub fn new(
+        id: SnapshotScheduleId,
+        target: SnapshotScheduleTarget,
+        trigger: SnapshotScheduleTrigger,
+        retention: SnapshotRetention,
+        quiesce: bool,
+    ) -> Self {
+        Self {
+            id,
+            target,
+            trigger,
+            retention,
+            quiesce,
+        }
+    }
+}
+
+impl From<SnapshotRetention> for models::SnapshotRetention {
+    fn from(src: SnapshotRetention) -> Self {
+        Self::new_all(src.count, src.max_age.map(|age| age.as_secs()))
+    }
+}
+
+impl From<models::SnapshotRetention> for SnapshotRetention {
+    fn from(src: models::SnapshotRetention) -> Self {
+        Self {
+            count: src.count,
+            max_age: src.max_age_secs.map(Duration::from_secs),
+        }
+    }
+}
+
+impl From<SnapshotScheduleSpec> for models::SnapshotSchedule {
+    fn from(src: SnapshotScheduleSpec) -> Self {
+        Self::new_all(
+            src.id,
+            src.target.volume().map(uuid::Uuid::from),
+            src.target.selector().cloned(),
+            src.trigger.interval().map(|interval| interval.as_secs()),
+            src.trigger.cron().map(ToString::to_string),
+            src.retention,
+            src.quiesce,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+    fn next(expression: &str, after: &str) -> String {
+        let schedule = expression.parse::<CronSchedule>().unwrap();
+        schedule.next_after(time(after)).unwrap().to_rfc3339()
+    }
+
+    #[test]
+    fn cron_next_after() {
+        let after = "2024-02-28T10:15:30Z";
+        assert_eq!(next("* * * * *", after), "2024-02-28T10:16:00+00:00");
+        assert_eq!(next("*/20 * * * *", after), "2024-02-28T10:20:00+00:00");
+        assert_eq!(next("0 */6 * * *", after), "2024-02-28T12:00:00+00:00");
+        assert_eq!(next("5,10 9-10 * * *", after), "2024-02-29T09:05:00+00:00");
+        assert_eq!(next("0 0 * * *", after), "2024-02-29T00:00:00+00:00");
+        assert_eq!(next("0 0 1 * *", after), "2024-03-01T00:00:00+00:00");
+        assert_eq!(
+            next("0 0 29 2 *", "2024-03-01T00:00:00Z"),
+            "2028-02-29T00:00:00+00:00"
+        );
+        // 2024-02-28 is a Wednesday
+        assert_eq!(next("30 2 * * 0", after), "2024-03-03T02:30:00+00:00");
+        assert_eq!(next("30 2 * * 7", after), "2024-03-03T02:30:00+00:00");
+        assert_eq!(next("0 0 * * 1-5", after), "2024-02-29T00:00:00+00:00");
+        // either the day of the month or the day of the week
+        assert_eq!(next("0 0 15 * 5", after), "2024-03-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn cron_invalid() {
+        for expression in [
+            "",
+            "* * * *",
+            "* * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "10-5 * * * *",
+            "a * * * *",
+            "0 0 30 2 *",
+        ] {
+            assert!(expression.parse::<CronSchedule>().is_err(), "{expression}");
+        }
+    }
+
+    #[test]
+    fn trigger_serde() {
+        let interval = SnapshotScheduleTrigger::Interval(Duration::from_secs(60));
+        let value = serde_json::to_value(&interval).unwrap();
+        assert_eq!(value["interval"]["secs"], 60);
+
+        let cron = SnapshotScheduleTrigger::Cron("0  0 * * *".parse().unwrap());
+        let value = serde_json::to_value(&cron).unwrap();
+        assert_eq!(value["cron"], "0 0 * * *");
+        assert_eq!(
+            serde_json::from_value::<SnapshotScheduleTrigger>(value).unwrap(),
+            cron
+        );
+        assert!(serde_json::from_value::<SnapshotScheduleTrigger>(
+            serde_json::json!({ "cron": "0 0 30 2 *" })
+        )
+        .is_err());
+    }
+}
@@ -1,7 +1,9 @@
 use super::{replica::ReplicaSnapshot, SnapshotId, SnapshotSpec};
 use crate::types::v0::{
     store::{AsOperationSequencer, OperationSequence, SpecStatus, SpecTransaction},
-    transport::{GenericSnapshotParameters, SnapshotParameters, SnapshotTxId, VolumeId},
+    transport::{
+        EncryptionKeyRef, GenericSnapshotParameters, SnapshotParameters, SnapshotTxId, VolumeId,
+    },
 };
 use chrono::{DateTime, Utc};
 use pstor::{ApiVersion, ObjectKey, StorableObject, StorableObjectType};
@@ -36,6 +38,11 @@ impl VolumeSnapshot {
             metadata: Default::default(),
         }
     }
+    /// Set the reference to the key which encrypts the source volume.
+    pub fn with_encryption(mut self, encryption: Option<EncryptionKeyRef>) -> Self {
+        self.metadata.encryption = encryption;
+        self
+    }
     /// Get the snapshot status.
     pub fn status(&self) -> &VolumeSnapshotSpecStatus {
         &self.status
@@ -100,10 +107,7 @@ impl VolumeSnapshot {
 }
 impl From<&VolumeSnapshotUserSpec> for VolumeSnapshot {
     fn from(value: &VolumeSnapshotUserSpec) -> Self {
-        Self::new(VolumeSnapshotUserSpec::new(
-            value.source_id(),
-            value.uuid().clone(),
-        ))
+        Self::new(value.clone())
     }
 }
 
@@ -130,6 +134,10 @@ pub struct VolumeSnapshotMeta {
     /// The "actual" snapshots can be accessed by the key `txn_id`.
     /// Failed transactions are any other key.
     transactions: HashMap<SnapshotTxId, Vec<ReplicaSnapshot>>,
+    /// Reference to the key which encrypts the source volume, and so the snapshot data.
+    /// Volumes restored from the snapshot must be encrypted with the same key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encryption: Option<EncryptionKeyRef>,
     /// VolumeSnapshot runtime metadata information.
     #[serde(skip)]
     runtime_meta: VolumeSnapshotRuntimeMetadata,
@@ -159,6 +167,10 @@ impl VolumeSnapshotMeta {
     pub fn total_allocated_size(&self) -> u64 {
         self.total_allocated_size
     }
+    /// Get the reference to the key which encrypts the snapshot data.
+    pub fn encryption(&self) -> &Option<EncryptionKeyRef> {
+        &self.encryption
+    }
     pub fn prepare(&self) -> SnapshotTxId {
         // If this is a create retry, then we must allocate a new transaction id, and prepare
         // replicas
@@ -504,15 +516,29 @@ impl StorableObject for VolumeSnapshot {
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct VolumeSnapshotList {
     snapshots: HashSet<SnapshotId>,
+    /// Internal snapshots, which are hidden from the user.
+    internal: HashSet<SnapshotId>,
 }
 impl VolumeSnapshotList {
     /// Insert snapshot into the list.
     pub fn insert(&mut self, snapshot: SnapshotId) {
         self.snapshots.insert(snapshot);
     }
+    /// Mark the snapshot as internal.
+    pub fn set_internal(&mut self, snapshot: SnapshotId) {
+        self.internal.insert(snapshot);
+    }
     /// Remove snapshot from the list.
     pub fn remove(&mut self, snapshot: &SnapshotId) {
         self.snapshots.remove(snapshot);
+        self.internal.remove(snapshot);
+    }
+    /// Number of snapshots which are visible to the user, ie: not internal.
+    pub fn user_len(&self) -> usize {
+        self.snapshots
+            .iter()
+            .filter(|snapshot| !self.internal.contains(snapshot))
+            .count()
     }
     /// Check if there's any snapshot.
     pub fn is_empty(&self) -> bool {
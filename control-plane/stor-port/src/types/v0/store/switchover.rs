@@ -67,6 +67,12 @@ pub struct SwitchOverSpec {
     pub reuse_existing: bool,
     /// Publish context of the volume.
     pub publish_context: Option<HashMap<String, String>>,
+    /// The switchover was requested, rather than triggered by a failed path.
+    #[serde(default)]
+    pub planned: bool,
+    /// The node on which to create the new target, otherwise picked by the scheduler.
+    #[serde(default)]
+    pub target_node: Option<NodeId>,
 }
 
 impl SwitchOverSpec {
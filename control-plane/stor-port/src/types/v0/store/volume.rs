@@ -8,9 +8,9 @@ use crate::{
             AsOperationSequencer, OperationSequence, SpecStatus, SpecTransaction,
         },
         transport::{
-            self, AffinityGroup, CreateVolume, HostNqn, NexusId, NexusNvmfConfig, NodeId,
-            ReplicaId, SnapshotId, Topology, VolumeId, VolumeLabels, VolumePolicy, VolumeProperty,
-            VolumeShareProtocol, VolumeStatus,
+            self, AffinityGroup, CreateVolume, EncryptionKeyRef, HostNqn, NexusId, NexusNvmfConfig,
+            NodeId, ReplicaId, ReplicaMoveRole, SnapshotId, Topology, VolumeId, VolumeLabels,
+            VolumePolicy, VolumeProperty, VolumeQos, VolumeShareProtocol, VolumeStatus,
         },
     },
     IntoOption,
@@ -49,15 +49,82 @@ impl ObjectKey for VolumeStateKey {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct FrontendConfig {
     host_acl: Vec<InitiatorAC>,
+    /// The target is published read-only, allowing for multiple read-only frontend nodes.
+    #[serde(default)]
+    read_only: bool,
+    /// The frontend nodes are tracked but the host acl is not enforced on the target.
+    /// A read-only target must track its frontend nodes even when the host acl is disabled,
+    /// so that it's only unpublished once its last frontend node is gone.
+    #[serde(default)]
+    unrestricted: bool,
 }
 impl FrontendConfig {
     /// Create new `Self` based on the host access list.
     pub fn from_acls(host_acl: Vec<InitiatorAC>) -> Self {
-        Self { host_acl }
+        Self {
+            host_acl,
+            read_only: false,
+            unrestricted: false,
+        }
+    }
+    /// Create new read-only `Self` based on the frontend initiators.
+    /// All initiators are given read-only access, and if `restricted` only they may access the
+    /// target.
+    pub fn from_read_only_acls(host_acl: Vec<InitiatorAC>, restricted: bool) -> Self {
+        Self {
+            host_acl: host_acl
+                .into_iter()
+                .map(|h| h.with_access(InitiatorAccess::ReadOnly))
+                .collect(),
+            read_only: true,
+            unrestricted: !restricted,
+        }
+    }
+    /// Get a copy of `Self` extended with the given initiators.
+    /// Initiators which are already present are kept as they are.
+    pub fn with_acls(&self, host_acl: Vec<InitiatorAC>) -> Self {
+        let mut config = self.clone();
+        for initiator in host_acl {
+            if !config
+                .host_acl
+                .iter()
+                .any(|h| h.node_name == initiator.node_name)
+            {
+                config.host_acl.push(initiator);
+            }
+        }
+        config
+    }
+    /// Get a copy of `Self` without the given nodename.
+    pub fn without_nodename(&self, nodename: &str) -> Self {
+        let mut config = self.clone();
+        config.host_acl.retain(|h| h.node_name != nodename);
+        config
+    }
+    /// Check if the target is published read-only.
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+    /// Check if the host acl is enforced on the target.
+    pub fn restricted(&self) -> bool {
+        !self.unrestricted
     }
     /// Check if the nodename is allowed.
     pub fn nodename_allowed(&self, nodename: &str) -> bool {
-        self.host_acl.is_empty() || self.host_acl.iter().any(|n| n.node_name() == nodename)
+        self.unrestricted
+            || self.host_acl.is_empty()
+            || self.host_acl.iter().any(|n| n.node_name() == nodename)
+    }
+    /// Get the access of the given nodename, if it's allowed.
+    pub fn nodename_access(&self, nodename: &str) -> Option<InitiatorAccess> {
+        if let Some(initiator) = self.host_acl.iter().find(|n| n.node_name() == nodename) {
+            return Some(initiator.access);
+        }
+        match self.nodename_allowed(nodename) {
+            true if self.read_only => Some(InitiatorAccess::ReadOnly),
+            true => Some(InitiatorAccess::ReadWrite),
+            false => None,
+        }
     }
     /// Get the node names of the acl.
     pub fn node_names(&self) -> Vec<String> {
@@ -68,7 +135,11 @@ impl FrontendConfig {
         self.host_acl.first().map(|h| h.node_name.clone())
     }
     /// Get the hostnqn's for the acl.
+    /// If the acl is not enforced then the list is empty, allowing any host.
     pub fn node_nqns(&self) -> Vec<HostNqn> {
+        if self.unrestricted {
+            return vec![];
+        }
         self.host_acl.iter().map(|h| h.node_nqn.clone()).collect()
     }
     /// Get the frontend nodes information reference.
@@ -77,6 +148,16 @@ impl FrontendConfig {
     }
 }
 
+/// The access of a frontend initiator to the volume target.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum InitiatorAccess {
+    /// The initiator may read and write.
+    #[default]
+    ReadWrite,
+    /// The initiator may only read.
+    ReadOnly,
+}
+
 /// Volume Frontend
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct InitiatorAC {
@@ -84,6 +165,9 @@ pub struct InitiatorAC {
     node_name: String,
     /// The nvme nqn of the front-end node.
     node_nqn: HostNqn,
+    /// The access of the front-end node.
+    #[serde(default)]
+    access: InitiatorAccess,
 }
 impl InitiatorAC {
     /// Get a new `Self` with the given parameters.
@@ -91,8 +175,14 @@ impl InitiatorAC {
         Self {
             node_name,
             node_nqn,
+            access: InitiatorAccess::ReadWrite,
         }
     }
+    /// Get `Self` with the given access.
+    pub fn with_access(mut self, access: InitiatorAccess) -> Self {
+        self.access = access;
+        self
+    }
     /// Get the nodename of the front-end initiator.
     pub fn node_name(&self) -> &str {
         &self.node_name
@@ -101,6 +191,10 @@ impl InitiatorAC {
     pub fn node_nqn(&self) -> &HostNqn {
         &self.node_nqn
     }
+    /// Get the access of the front-end initiator.
+    pub fn access(&self) -> InitiatorAccess {
+        self.access
+    }
 }
 
 /// Volume Target (node and nexus)
@@ -137,7 +231,11 @@ impl VolumeTarget {
 }
 impl From<&InitiatorAC> for models::NodeAccessInfo {
     fn from(node: &InitiatorAC) -> Self {
-        models::NodeAccessInfo::new_all(node.node_name(), node.node_nqn().to_string())
+        models::NodeAccessInfo::new_all(
+            node.node_name(),
+            node.node_nqn().to_string(),
+            node.access() == InitiatorAccess::ReadOnly,
+        )
     }
 }
 impl From<&TargetConfig> for models::VolumeTarget {
@@ -157,6 +255,7 @@ impl From<&TargetConfig> for models::VolumeTarget {
             src.target.node.clone(),
             src.target.protocol.into_opt(),
             node_names,
+            src.frontend.read_only(),
         )
     }
 }
@@ -209,12 +308,56 @@ pub struct VolumeSpec {
     /// Max snapshots limit per volume.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_snapshots: Option<u32>,
+    /// Replica move in progress.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replica_move: Option<VolumeReplicaMove>,
+    /// IO limits enforced on the volume target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qos: Option<VolumeQos>,
+    /// Reference to the key which encrypts the volume at rest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<EncryptionKeyRef>,
+}
+
+/// A replica move of a volume.
+/// The new replica is added to the volume target alongside the moved replica, which is only
+/// retired once the new replica has been rebuilt.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VolumeReplicaMove {
+    /// The replica being moved.
+    pub replica: ReplicaId,
+    /// The replica replacing the moved replica.
+    pub new_replica: ReplicaId,
+}
+
+/// The in-place restore of a volume to one of its snapshots.
+/// The volume replicas are replaced by clones of the replica snapshots, all of which are recorded
+/// before any of them is created, allowing an interrupted restore to be resumed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VolumeSnapshotRestore {
+    /// The snapshot which the volume is restored to.
+    pub snapshot: SnapshotId,
+    /// The replicas being replaced.
+    pub replicas: Vec<ReplicaId>,
+    /// The replica snapshot clones replacing the replicas.
+    pub clones: Vec<VolumeSnapshotRestoreClone>,
+}
+
+/// A replica snapshot clone which replaces the volume replicas on a snapshot restore.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VolumeSnapshotRestoreClone {
+    /// The replica snapshot being cloned.
+    pub replica_snapshot: SnapshotId,
+    /// The uuid of the clone, which becomes a replica of the volume.
+    pub replica: ReplicaId,
 }
 
 /// Volume Content Source i.e the snapshot or a volume.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum VolumeContentSource {
     Snapshot(SnapshotId, VolumeId),
+    /// A volume clone, restored from a hidden snapshot of the source volume.
+    Volume(VolumeId, SnapshotId),
 }
 
 impl VolumeContentSource {
@@ -222,6 +365,17 @@ impl VolumeContentSource {
     pub fn new_snapshot_source(snapshot: SnapshotId, snap_source_vol: VolumeId) -> Self {
         Self::Snapshot(snapshot, snap_source_vol)
     }
+    /// Create a new `VolumeContentSource::Volume` from the params.
+    pub fn new_volume_source(source_vol: VolumeId, snapshot: SnapshotId) -> Self {
+        Self::Volume(source_vol, snapshot)
+    }
+    /// Get the snapshot which the volume content was restored from.
+    pub fn snapshot(&self) -> &SnapshotId {
+        match self {
+            Self::Snapshot(snapshot, _) => snapshot,
+            Self::Volume(_, snapshot) => snapshot,
+        }
+    }
 }
 
 /// Volume meta information.
@@ -250,9 +404,9 @@ impl VolumeMetadata {
         // we become thin provisioned!
         self.persisted.snapshot_as_thin = Some(true);
     }
-    /// Number of snapshots taken on this volume.
+    /// Number of snapshots taken on this volume, excluding the internal ones.
     pub fn num_snapshots(&self) -> usize {
-        self.runtime.snapshots.len()
+        self.runtime.snapshots.user_len()
     }
 }
 
@@ -363,6 +517,13 @@ impl VolumeSpec {
     pub fn insert_snapshot(&mut self, snapshot: &SnapshotId) {
         self.metadata.insert_snapshot(snapshot.clone());
     }
+    /// Mark the snapshot as internal, so it's not accounted as a user snapshot.
+    pub fn set_internal_snapshot(&mut self, snapshot: &SnapshotId) {
+        self.metadata
+            .runtime
+            .snapshots
+            .set_internal(snapshot.clone());
+    }
     /// Check if there's any snapshot.
     pub fn has_snapshots(&self) -> bool {
         self.metadata.runtime.has_snapshots()
@@ -376,6 +537,10 @@ impl VolumeSpec {
     pub fn snapshot_as_thin(&self) -> Option<bool> {
         self.metadata.persisted.snapshot_as_thin
     }
+    /// Check if the volume is encrypted at rest.
+    pub fn encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
     /// Get the currently active target.
     pub fn target(&self) -> Option<&VolumeTarget> {
         self.target_config
@@ -435,6 +600,27 @@ impl VolumeSpec {
     pub fn set_content_source(&mut self, content_source: Option<VolumeContentSource>) {
         self.content_source = content_source;
     }
+    /// Get the snapshot restore in progress, if any.
+    /// A restore which has already failed or succeeded is merely waiting to be persisted.
+    pub fn snapshot_restore(&self) -> Option<&VolumeSnapshotRestore> {
+        match &self.operation {
+            Some(VolumeOperationState {
+                operation: VolumeOperation::RestoreSnapshot(restore),
+                result: None,
+            }) => Some(restore),
+            _ => None,
+        }
+    }
+    /// Get the part the given replica plays in the replica move in progress, if any.
+    pub fn replica_move_role(&self, replica: &ReplicaId) -> Option<ReplicaMoveRole> {
+        match &self.replica_move {
+            Some(replica_move) if &replica_move.replica == replica => Some(ReplicaMoveRole::Source),
+            Some(replica_move) if &replica_move.new_replica == replica => {
+                Some(ReplicaMoveRole::Target)
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Operation State for a Volume resource.
@@ -497,6 +683,11 @@ impl SpecTransaction<VolumeOperation> for VolumeSpec {
                     self.last_nexus_id = None;
                     self.target_config = Some(args.config);
                 }
+                VolumeOperation::UpdateFrontend(frontend) => {
+                    if let Some(config) = &mut self.target_config {
+                        config.frontend = frontend;
+                    }
+                }
                 VolumeOperation::Unpublish => {
                     self.deactivate_target();
                 }
@@ -513,7 +704,20 @@ impl SpecTransaction<VolumeOperation> for VolumeSpec {
                     VolumeProperty::MaxSnapshots(max_snapshots) => {
                         self.max_snapshots = Some(max_snapshots);
                     }
+                    VolumeProperty::Qos(qos) => {
+                        self.qos = qos.into_limited();
+                    }
+                    VolumeProperty::EncryptionKey(key_ref) => {
+                        self.encryption = Some(key_ref);
+                    }
                 },
+                VolumeOperation::StartReplicaMove(replica_move) => {
+                    self.replica_move = Some(replica_move);
+                }
+                VolumeOperation::EndReplicaMove => {
+                    self.replica_move = None;
+                }
+                VolumeOperation::RestoreSnapshot(_) => {}
             }
         }
         self.clear_op();
@@ -573,12 +777,16 @@ pub enum VolumeOperation {
     #[serde(rename = "Publish2")]
     Publish(PublishOperation),
     Republish(RepublishOperation),
+    UpdateFrontend(FrontendConfig),
     Unpublish,
     RemoveUnusedReplica(ReplicaId),
     CreateSnapshot(SnapshotId),
     DestroySnapshot(SnapshotId),
     Resize(u64),
     SetVolumeProperty(VolumeProperty),
+    StartReplicaMove(VolumeReplicaMove),
+    EndReplicaMove,
+    RestoreSnapshot(VolumeSnapshotRestore),
 }
 
 #[test]
@@ -667,6 +875,9 @@ impl From<VolumeOperation> for models::volume_spec_operation::Operation {
             VolumeOperation::PublishOld(_) => models::volume_spec_operation::Operation::Publish,
             VolumeOperation::Publish(_) => models::volume_spec_operation::Operation::Publish,
             VolumeOperation::Republish(_) => models::volume_spec_operation::Operation::Republish,
+            VolumeOperation::UpdateFrontend(_) => {
+                models::volume_spec_operation::Operation::UpdateFrontend
+            }
             VolumeOperation::Unpublish => models::volume_spec_operation::Operation::Unpublish,
             VolumeOperation::RemoveUnusedReplica(_) => {
                 models::volume_spec_operation::Operation::RemoveUnusedReplica
@@ -681,6 +892,15 @@ impl From<VolumeOperation> for models::volume_spec_operation::Operation {
                 models::volume_spec_operation::Operation::SetVolumeProperty
             }
             VolumeOperation::Resize(_) => models::volume_spec_operation::Operation::Resize,
+            VolumeOperation::StartReplicaMove(_) => {
+                models::volume_spec_operation::Operation::StartReplicaMove
+            }
+            VolumeOperation::EndReplicaMove => {
+                models::volume_spec_operation::Operation::EndReplicaMove
+            }
+            VolumeOperation::RestoreSnapshot(_) => {
+                models::volume_spec_operation::Operation::RestoreSnapshot
+            }
         }
     }
 }
@@ -739,6 +959,8 @@ impl From<&CreateVolume> for VolumeSpec {
             publish_context: None,
             affinity_group: request.affinity_group.clone(),
             max_snapshots: request.max_snapshots,
+            qos: request.qos.clone().and_then(VolumeQos::into_limited),
+            encryption: request.encryption.clone(),
             ..Default::default()
         }
     }
@@ -749,6 +971,10 @@ impl PartialEq<CreateVolume> for VolumeSpec {
         other.status = self.status.clone();
         other.sequencer = self.sequencer.clone();
         other.content_source = self.content_source.clone();
+        if other.content_source.is_some() && other.encryption.is_none() {
+            // volumes restored from a snapshot inherit its encryption key
+            other.encryption = self.encryption.clone();
+        }
         &other == self
     }
 }
@@ -796,6 +1022,8 @@ impl From<VolumeSpec> for models::VolumeSpec {
             src.content_source.into_opt(),
             src.num_snapshots,
             src.max_snapshots,
+            src.qos.into_opt(),
+            src.encryption.into_opt(),
         )
     }
 }
@@ -806,6 +1034,9 @@ impl From<VolumeContentSource> for models::VolumeContentSource {
             VolumeContentSource::Snapshot(snap_id, vol_id) => {
                 Self::snapshot(models::SnapshotAsSource::new_all(snap_id, vol_id))
             }
+            VolumeContentSource::Volume(vol_id, snap_id) => {
+                Self::volume(models::VolumeAsSource::new_all(vol_id, snap_id))
+            }
         }
     }
 }
@@ -816,6 +1047,9 @@ impl From<models::VolumeContentSource> for VolumeContentSource {
             models::VolumeContentSource::snapshot(snap_source) => {
                 Self::Snapshot(snap_source.snapshot.into(), snap_source.volume.into())
             }
+            models::VolumeContentSource::volume(vol_source) => {
+                Self::Volume(vol_source.volume.into(), vol_source.snapshot.into())
+            }
         }
     }
 }
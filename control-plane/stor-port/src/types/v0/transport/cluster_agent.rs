@@ -1,4 +1,5 @@
 use super::*;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, net::SocketAddr};
 
@@ -215,3 +216,120 @@ impl ReplacePath {
         self.publish_context.clone()
     }
 }
+
+/// Get the in-flight and the recently completed switchovers.
+#[derive(Debug, Default, Clone)]
+pub struct GetSwitchOvers {}
+
+/// Move the target of a published volume onto another node, switching over its initiator to
+/// the new target without waiting for a path failure.
+#[derive(Debug, Clone)]
+pub struct MoveVolumeTarget {
+    /// The volume whose target is moved.
+    pub volume: VolumeId,
+    /// The node on which to create the new target, otherwise picked by the scheduler.
+    pub target_node: Option<NodeId>,
+}
+
+/// The stage of a volume switchover.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SwitchOverStage {
+    /// The switchover request is being initialized.
+    Init,
+    /// The volume target is being republished.
+    RepublishVolume,
+    /// The new path is being sent to the node agent.
+    ReplacePath,
+    /// The old volume target is being deleted.
+    DeleteTarget,
+    /// The switchover completed successfully.
+    Successful,
+    /// The switchover failed.
+    Errored,
+}
+
+impl From<SwitchOverStage> for models::SwitchOverStage {
+    fn from(src: SwitchOverStage) -> Self {
+        match src {
+            SwitchOverStage::Init => Self::Init,
+            SwitchOverStage::RepublishVolume => Self::RepublishVolume,
+            SwitchOverStage::ReplacePath => Self::ReplacePath,
+            SwitchOverStage::DeleteTarget => Self::DeleteTarget,
+            SwitchOverStage::Successful => Self::Successful,
+            SwitchOverStage::Errored => Self::Errored,
+        }
+    }
+}
+
+/// Status of a volume switchover, either in-flight or completed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SwitchOverInfo {
+    /// The volume which is being switched over.
+    pub volume: VolumeId,
+    /// The application node which reported the failed path, or whose path is moved.
+    pub node: NodeId,
+    /// Whether the switchover was requested, rather than triggered by a failed path.
+    pub planned: bool,
+    /// The node requested for the new volume target, if any.
+    pub target_node: Option<NodeId>,
+    /// The priority of the switchover, as given by the priority class of its volume.
+    pub priority: u32,
+    /// The current stage of the switchover.
+    pub stage: SwitchOverStage,
+    /// Number of failed attempts in the current stage.
+    pub retry_count: u64,
+    /// The nqn of the failed path.
+    pub existing_nqn: String,
+    /// The new path of the volume target, once republished.
+    pub new_path: Option<String>,
+    /// Whether the existing volume target is reused.
+    pub reuse_existing: bool,
+    /// The last error encountered by the switchover, if any.
+    pub last_error: Option<String>,
+    /// Time at which the switchover was started.
+    pub start_time: DateTime<Utc>,
+    /// Time at which the switchover was last updated.
+    pub last_update: DateTime<Utc>,
+    /// Time at which the switchover completed, if it has.
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+impl SwitchOverInfo {
+    /// Check if the switchover has completed, either successfully or not.
+    pub fn completed(&self) -> bool {
+        self.end_time.is_some()
+    }
+}
+
+impl From<SwitchOverInfo> for models::SwitchOver {
+    fn from(src: SwitchOverInfo) -> Self {
+        Self::new_all(
+            src.volume,
+            src.node.to_string(),
+            src.planned,
+            src.target_node.map(|node| node.to_string()),
+            src.priority,
+            src.stage,
+            src.retry_count,
+            src.existing_nqn,
+            src.new_path,
+            src.reuse_existing,
+            src.last_error,
+            src.start_time.to_rfc3339(),
+            src.last_update.to_rfc3339(),
+            src.end_time.map(|time| time.to_rfc3339()),
+        )
+    }
+}
+
+/// The in-flight switchovers, followed by the retained history of the completed ones, from
+/// the most to the least recent.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct SwitchOvers(pub Vec<SwitchOverInfo>);
+
+impl SwitchOvers {
+    /// Get the inner list of switchovers.
+    pub fn into_inner(self) -> Vec<SwitchOverInfo> {
+        self.0
+    }
+}
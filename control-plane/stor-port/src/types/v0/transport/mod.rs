@@ -10,6 +10,9 @@ pub mod nvme_nqn;
 pub mod pool;
 pub mod replica;
 pub mod snapshot;
+pub mod snapshot_export;
+pub mod snapshot_group;
+pub mod snapshot_schedule;
 pub mod spec;
 pub mod state;
 pub mod volume;
@@ -27,6 +30,9 @@ pub use nvme_nqn::{NvmeNqn as HostNqn, NvmeNqnParseError as HostNqnParseError, *
 pub use pool::*;
 pub use replica::*;
 pub use snapshot::*;
+pub use snapshot_export::*;
+pub use snapshot_group::*;
+pub use snapshot_schedule::*;
 pub use spec::*;
 pub use state::*;
 pub use volume::*;
@@ -79,6 +85,8 @@ pub enum MessageIdVs {
     DestroyPool,
     /// Import Pool.
     ImportPool,
+    /// Get the proposed pool rebalance replica moves.
+    GetPoolRebalance,
     /// Get replicas with filter.
     GetReplicas,
     /// Create Replica.
@@ -112,6 +120,8 @@ pub enum MessageIdVs {
     DestroyNexus,
     /// Resize Nexus.
     ResizeNexus,
+    /// Set the IO limits of a Nexus.
+    SetNexusQos,
     /// Share Nexus.
     ShareNexus,
     /// Unshare Nexus.
@@ -152,14 +162,44 @@ pub enum MessageIdVs {
     SetVolumeReplica,
     /// Set volume property.
     SetVolumeProperty,
+    /// Move a volume replica.
+    MoveVolumeReplica,
+    /// Cancel a volume replica move.
+    CancelVolumeReplicaMove,
+    /// Get the capacity available to new volumes.
+    GetVolumeCapacity,
     /// Create volume snapshot.
     CreateVolumeSnapshot,
     /// Delete volume snapshot.
     DestroyVolumeSnapshot,
+    /// Restore a volume in place to one of its snapshots.
+    RestoreVolumeSnapshot,
     /// Get volume snapshots.
     GetVolumeSnapshots,
     /// Create volume as snapshot clone.
     CreateSnapshotVolume,
+    /// Create volume as a clone of another volume.
+    CloneVolume,
+    /// Create or update a snapshot schedule.
+    CreateSnapshotSchedule,
+    /// Delete a snapshot schedule.
+    DestroySnapshotSchedule,
+    /// Get snapshot schedules.
+    GetSnapshotSchedules,
+    /// Create a snapshot group.
+    CreateSnapshotGroup,
+    /// Delete a snapshot group.
+    DestroySnapshotGroup,
+    /// Get snapshot groups.
+    GetSnapshotGroups,
+    /// Create a snapshot export.
+    CreateSnapshotExport,
+    /// Delete a snapshot export.
+    DestroySnapshotExport,
+    /// Get snapshot exports.
+    GetSnapshotExports,
+    /// Create a volume from a snapshot export.
+    RestoreSnapshotExport,
     /// Generic JSON gRPC message.
     JsonGrpc,
     /// Get block devices.
@@ -174,6 +214,10 @@ pub enum MessageIdVs {
     GetSpecs,
     /// Get States.
     GetStates,
+    /// Backup Specs.
+    BackupSpecs,
+    /// Restore Specs.
+    RestoreSpecs,
     /// High Availability Agents.
     RegisterHaNode,
     /// Report failed NVMe paths.
@@ -184,10 +228,16 @@ pub enum MessageIdVs {
     ReplacePathInfo,
     /// Get Nvme Subsystems.
     GetNvmeSubsystems,
+    /// Get the switchovers.
+    GetSwitchOvers,
+    /// Move a volume target.
+    MoveVolumeTarget,
     /// Get Rebuild records.
     GetRebuildRecord,
     /// List rebuild records.
     ListRebuildRecord,
+    /// Get the persisted rebuild history of the volumes.
+    GetRebuildHistories,
     /// Get an app node.
     GetAppNode,
     /// List app nodes.
@@ -4,6 +4,7 @@ use crate::{
     transport_api::{ReplyError, ResourceKind},
     types::v0::store::{
         definitions::ObjectKey, nexus_child::NexusChild, nexus_persistence::NexusInfoKey,
+        rebuild_history::VolumeRebuildRecord,
     },
     IntoVec,
 };
@@ -240,6 +241,9 @@ pub struct CreateNexus {
     pub owner: Option<VolumeId>,
     /// Nexus Nvmf Configuration
     pub config: Option<NexusNvmfConfig>,
+    /// IO limits to be enforced on the nexus once created.
+    #[serde(default)]
+    pub qos: Option<VolumeQos>,
 }
 
 /// A request to resize a Nexus.
@@ -265,6 +269,32 @@ impl ResizeNexus {
     }
 }
 
+/// A request to set the IO limits of a Nexus.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SetNexusQos {
+    /// Id of the io-engine instance.
+    pub node: NodeId,
+    /// Uuid of the nexus.
+    pub uuid: NexusId,
+    /// Name of the nexus.
+    pub name: String,
+    /// The IO limits, where unset limits are not enforced.
+    pub qos: VolumeQos,
+}
+
+impl SetNexusQos {
+    /// Return new `Self` from the given parameters.
+    pub fn new(node_id: &NodeId, uuid: &NexusId, name: String, qos: VolumeQos) -> Self {
+        Self {
+            node: node_id.clone(),
+            uuid: uuid.clone(),
+            name,
+            qos,
+        }
+    }
+}
+
 /// NVMe reservation types.
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
 pub enum NvmeReservation {
@@ -542,8 +572,20 @@ impl CreateNexus {
             managed,
             owner: owner.cloned(),
             config,
+            qos: None,
         }
     }
+    /// With the IO limits to be enforced on the nexus once created.
+    pub fn with_qos(mut self, qos: Option<VolumeQos>) -> Self {
+        self.qos = qos;
+        self
+    }
+    /// Get the `SetNexusQos` request which enforces the IO limits on the created nexus.
+    pub fn set_qos_request(&self) -> Option<SetNexusQos> {
+        self.qos
+            .clone()
+            .map(|qos| SetNexusQos::new(&self.node, &self.uuid, self.name(), qos))
+    }
     /// Name of the nexus.
     /// When part of a volume, it's set to its `VolumeId`. Otherwise it's set to its `NexusId`.
     pub fn name(&self) -> String {
@@ -692,7 +734,7 @@ impl From<Nexus> for DestroyNexus {
 }
 
 /// Share Nexus Request
-#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Default, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ShareNexus {
     /// id of the io-engine instance
@@ -718,6 +760,18 @@ impl ShareNexus {
         }
     }
 }
+impl std::fmt::Debug for ShareNexus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // the encryption key must not be leaked into the logs
+        f.debug_struct("ShareNexus")
+            .field("node", &self.node)
+            .field("uuid", &self.uuid)
+            .field("key", &self.key.as_ref().map(|_| "<redacted>"))
+            .field("protocol", &self.protocol)
+            .field("allowed_hosts", &self.allowed_hosts)
+            .finish()
+    }
+}
 impl From<&Nexus> for UnshareNexus {
     fn from(from: &Nexus) -> Self {
         Self {
@@ -795,6 +849,38 @@ impl GetRebuildRecord {
     }
 }
 
+/// Get the persisted rebuild history of the volumes, filtered by the given parameters.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+pub struct GetRebuildHistories {
+    /// Only the rebuild history of this volume.
+    pub volume: Option<VolumeId>,
+    /// Only the rebuilds which took place on this node.
+    pub node: Option<NodeId>,
+    /// Only the rebuilds which ended at or after this time.
+    pub since: Option<SystemTime>,
+    /// Only the rebuilds which ended at or before this time.
+    pub until: Option<SystemTime>,
+    /// Only the partial (true) or full (false) rebuilds.
+    pub partial: Option<bool>,
+}
+
+impl GetRebuildHistories {
+    /// Check if the given rebuild record matches the filter parameters.
+    /// The volume is not checked as the records are grouped by volume.
+    pub fn matches(&self, record: &VolumeRebuildRecord) -> bool {
+        self.node.as_ref().map_or(true, |node| node == &record.node)
+            && self
+                .since
+                .map_or(true, |since| record.record.end_time >= since)
+            && self
+                .until
+                .map_or(true, |until| record.record.end_time <= until)
+            && self
+                .partial
+                .map_or(true, |partial| partial == record.record.is_partial)
+    }
+}
+
 /// Rebuild history lists all rebuild jobs for a nexus.
 #[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
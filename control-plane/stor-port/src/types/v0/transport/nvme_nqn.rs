@@ -51,6 +51,17 @@ pub enum NvmeNqn {
     /// The following is an example of an NVMe Qualified Name using the UUID-based format:
     /// • The string “nqn.2014-08.org.nvmexpress:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6“.
     Unique { uuid: uuid::Uuid },
+    /// An iSCSI qualified name, which identifies iSCSI initiators in host access lists.
+    /// This format consists of:
+    /// • The string “iqn”;
+    /// • The string “.” (i.e., the ASCII period character);
+    /// • A date code, in “yyyy-mm” format;
+    /// • The string “.” (i.e., the ASCII period character);
+    /// • The reverse domain name of the naming authority; and
+    /// • An optional colon (:) prefixed string that the naming authority assigns.
+    /// The following is an example of an iSCSI Qualified Name:
+    /// • The string “iqn.1993-08.org.debian:01:2d8c6f4e7a”.
+    Iqn { iqn: String },
     /// Invalid Nqn Format, may be useful as a temporary placeholder.
     Invalid { nqn: String },
 }
@@ -83,6 +94,7 @@ impl Display for NvmeNqn {
         let nqn_str = match self {
             NvmeNqn::Unique { uuid } => format!("{NVME_NQN_UUID_PRE}{uuid}"),
             NvmeNqn::Org { date, domain, name } => format!("nqn.{date}.{domain}:{name}"),
+            NvmeNqn::Iqn { iqn } => iqn.to_string(),
             NvmeNqn::Invalid { nqn } => nqn.to_string(),
         };
         f.write_str(&nqn_str)
@@ -103,6 +115,10 @@ impl NvmeNqn {
             name: format!("node-name:{name}"),
         }
     }
+    /// Check if this is an iSCSI qualified name.
+    pub fn is_iqn(&self) -> bool {
+        matches!(self, Self::Iqn { .. })
+    }
     fn parse_nqn_date(date: &str) -> Result<String, NvmeNqnParseError> {
         match date.split("").collect::<Vec<_>>()[..] {
             ["", y1, y2, y3, y4, "-", m1, m2, ..] => {
@@ -190,6 +206,14 @@ impl TryFrom<&str> for NvmeNqn {
             return Err(NvmeNqnParseError::Empty);
         }
 
+        // iSCSI initiators are identified by their "iqn.yyyy-mm.reverse.domain[:name-string]"
+        if let Some(ym_next) = value.strip_prefix("iqn.") {
+            Self::parse_nqn_date(ym_next)?;
+            return Ok(Self::Iqn {
+                iqn: value.to_string(),
+            });
+        }
+
         // Check for equality with the generic nqn structure of the form
         // "nqn.2014-08.org.nvmexpress:uuid:11111111-2222-3333-4444-555555555555"
         let parts = value.split(NVME_NQN_UUID_PRE).collect::<Vec<_>>();
@@ -296,6 +320,18 @@ fn validate_nvme_nqn() {
     let invalid = "";
     let error = NvmeNqn::try_from(invalid);
     assert_eq!(error, Err(NvmeNqnParseError::Empty));
+    let valid = "iqn.1993-08.org.debian:01:2d8c6f4e7a";
+    let nqn = NvmeNqn::try_from(valid);
+    assert_eq!(
+        nqn,
+        Ok(NvmeNqn::Iqn {
+            iqn: valid.to_string()
+        })
+    );
+    assert_eq!(nqn.unwrap().to_string(), valid);
+    let invalid = "iqn.93-08.org.debian:01:2d8c6f4e7a";
+    let error = NvmeNqn::try_from(invalid);
+    assert_eq!(error, Err(NvmeNqnParseError::InvalidDateFormat));
     let base_valid = "nqn.2014-08.org.borg.long:";
     let long_name = "a".repeat(NVME_NQN_MAX_LEN - base_valid.len());
     let valid = format!("{base_valid}{long_name}");
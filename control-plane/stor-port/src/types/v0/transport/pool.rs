@@ -346,3 +346,39 @@ impl DestroyPool {
         Self { node, id }
     }
 }
+
+/// Get the replica moves which the pool rebalancer would make to even out the pools, without
+/// actually moving anything.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+pub struct GetPoolRebalance {}
+
+/// A volume replica move from an over-utilised pool to an under-utilised pool.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicaMove {
+    /// Id of the volume which owns the replica.
+    pub volume: VolumeId,
+    /// Id of the replica to move.
+    pub replica: ReplicaId,
+    /// Id of the io-engine instance where the replica currently lives.
+    pub source_node: NodeId,
+    /// Id of the pool where the replica currently lives.
+    pub source_pool: PoolId,
+    /// Id of the io-engine instance where the new replica is to be created.
+    pub target_node: NodeId,
+    /// Id of the pool where the new replica is to be created.
+    pub target_pool: PoolId,
+}
+
+impl From<ReplicaMove> for models::ReplicaMove {
+    fn from(src: ReplicaMove) -> Self {
+        models::ReplicaMove::new(
+            src.volume,
+            src.replica,
+            src.source_node,
+            src.source_pool,
+            src.target_node,
+            src.target_pool,
+        )
+    }
+}
@@ -0,0 +1,123 @@
+use super::*;
+use crate::types::v0::store::snapshots::export::{
+    BackupTarget, SnapshotExportSpec, SnapshotExportSpecKey,
+};
+use serde::{Deserialize, Serialize};
+
+rpc_impl_string_uuid!(SnapshotExportId, "UUID of a snapshot export");
+
+impl From<SnapshotExportSpec> for models::SnapshotExport {
+    fn from(src: SnapshotExportSpec) -> Self {
+        let stats = src.stats.unwrap_or_default();
+        Self::new_all(
+            src.id,
+            src.snapshot_id,
+            src.volume_id,
+            src.target.app_node().to_string(),
+            src.target.path(),
+            src.base.map(uuid::Uuid::from),
+            src.size,
+            src.status,
+            src.timestamp.map(|timestamp| timestamp.to_rfc3339()),
+            stats.chunks,
+            stats.changed_chunks,
+            stats.written_bytes,
+        )
+    }
+}
+
+/// Export a volume snapshot into a backup target.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CreateSnapshotExport {
+    /// Snapshot export identification.
+    pub id: SnapshotExportId,
+    /// The volume snapshot to be exported.
+    pub snapshot_id: SnapshotId,
+    /// The backup target which the snapshot is exported to.
+    pub target: BackupTarget,
+    /// The export which this export is incremental to, if any.
+    pub base: Option<SnapshotExportId>,
+}
+
+impl CreateSnapshotExport {
+    /// Create new `Self` from the given parameters.
+    pub fn new(
+        id: SnapshotExportId,
+        snapshot_id: SnapshotId,
+        target: BackupTarget,
+        base: Option<SnapshotExportId>,
+    ) -> Self {
+        Self {
+            id,
+            snapshot_id,
+            target,
+            base,
+        }
+    }
+}
+
+/// Delete a snapshot export from its backup target.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DestroySnapshotExport {
+    /// Snapshot export identification.
+    pub id: SnapshotExportId,
+}
+
+impl DestroySnapshotExport {
+    /// Create new `Self` from the given export id.
+    pub fn new(id: SnapshotExportId) -> Self {
+        Self { id }
+    }
+}
+
+impl From<&DestroySnapshotExport> for SnapshotExportSpecKey {
+    fn from(value: &DestroySnapshotExport) -> Self {
+        (&value.id).into()
+    }
+}
+
+/// Get all snapshot exports (`None`), or a specific one.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct GetSnapshotExports {
+    /// Snapshot export identification.
+    pub id: Option<SnapshotExportId>,
+}
+
+impl GetSnapshotExports {
+    /// Create new `Self` from the given optional export id.
+    pub fn new(id: impl Into<Option<SnapshotExportId>>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+/// Request to create a new volume from a snapshot export, along with its chain of base exports.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RestoreSnapshotExport {
+    /// Unique identification of the snapshot export.
+    export_id: SnapshotExportId,
+    /// Generic volume creation parameters.
+    volume_params: CreateVolume,
+}
+
+impl RestoreSnapshotExport {
+    /// Create a new `Self` from the given parameters.
+    pub fn new(export_id: SnapshotExportId, volume_params: CreateVolume) -> Self {
+        Self {
+            export_id,
+            volume_params,
+        }
+    }
+    /// Get a reference to the snapshot export uuid.
+    pub fn export_id(&self) -> &SnapshotExportId {
+        &self.export_id
+    }
+    /// Get a reference to the generic volume parameters.
+    pub fn params(&self) -> &CreateVolume {
+        &self.volume_params
+    }
+    /// Get a `CreateSnapshotVolume` which creates the new volume from the given snapshot, into
+    /// which the export was imported.
+    pub fn to_snapshot_volume(&self, snapshot_uuid: SnapshotId) -> CreateSnapshotVolume {
+        CreateSnapshotVolume::new(snapshot_uuid, self.volume_params.clone())
+    }
+}
@@ -0,0 +1,113 @@
+use super::*;
+use crate::types::v0::store::snapshots::group::{
+    SnapshotGroupMember, SnapshotGroupSpec, SnapshotGroupSpecKey, SnapshotGroupTarget,
+};
+use serde::{Deserialize, Serialize};
+
+rpc_impl_string_id!(SnapshotGroupId, "ID of a snapshot group");
+
+/// A snapshot group, along with the runtime state of its member snapshots.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotGroup {
+    spec: SnapshotGroupSpec,
+    /// The member snapshots which are currently created.
+    created: Vec<SnapshotId>,
+}
+
+impl SnapshotGroup {
+    /// Create a new `Self` from the given spec and the member snapshots which are created.
+    pub fn new(spec: SnapshotGroupSpec, created: Vec<SnapshotId>) -> Self {
+        Self { spec, created }
+    }
+    /// Get the snapshot group specification.
+    pub fn spec(&self) -> &SnapshotGroupSpec {
+        &self.spec
+    }
+    /// Get the snapshot group id.
+    pub fn id(&self) -> &SnapshotGroupId {
+        &self.spec.id
+    }
+    /// Check if the given member snapshot is created.
+    pub fn member_created(&self, member: &SnapshotGroupMember) -> bool {
+        self.created.contains(&member.snapshot_id)
+    }
+}
+
+impl From<SnapshotGroup> for models::SnapshotGroup {
+    fn from(src: SnapshotGroup) -> Self {
+        let snapshots = src
+            .spec
+            .members
+            .iter()
+            .map(|member| {
+                models::SnapshotGroupMember::new_all(
+                    &member.volume_id,
+                    &member.snapshot_id,
+                    src.member_created(member),
+                )
+            })
+            .collect::<Vec<_>>();
+        let spec = src.spec;
+        Self::new_all(
+            spec.id,
+            spec.target
+                .volumes()
+                .map(|volumes| volumes.iter().map(uuid::Uuid::from).collect()),
+            spec.target.selector().cloned(),
+            spec.target.affinity_group().cloned(),
+            spec.status,
+            spec.timestamp.map(|timestamp| timestamp.to_rfc3339()),
+            snapshots,
+        )
+    }
+}
+
+/// Create a snapshot group, snapshotting all of its volumes at a single quiesce point.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CreateSnapshotGroup {
+    /// Snapshot group identification.
+    pub id: SnapshotGroupId,
+    /// The volumes which are snapshotted by the group.
+    pub target: SnapshotGroupTarget,
+}
+
+impl CreateSnapshotGroup {
+    /// Create new `Self` from the given parameters.
+    pub fn new(id: SnapshotGroupId, target: SnapshotGroupTarget) -> Self {
+        Self { id, target }
+    }
+}
+
+/// Delete a snapshot group, along with its member snapshots.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DestroySnapshotGroup {
+    /// Snapshot group identification.
+    pub id: SnapshotGroupId,
+}
+
+impl DestroySnapshotGroup {
+    /// Create new `Self` from the given group id.
+    pub fn new(id: SnapshotGroupId) -> Self {
+        Self { id }
+    }
+}
+
+impl From<&DestroySnapshotGroup> for SnapshotGroupSpecKey {
+    fn from(value: &DestroySnapshotGroup) -> Self {
+        (&value.id).into()
+    }
+}
+
+/// Get all snapshot groups (`None`), or a specific one.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct GetSnapshotGroups {
+    /// Snapshot group identification.
+    pub id: Option<SnapshotGroupId>,
+}
+
+impl GetSnapshotGroups {
+    /// Create new `Self` from the given optional group id.
+    pub fn new(id: impl Into<Option<SnapshotGroupId>>) -> Self {
+        Self { id: id.into() }
+    }
+}
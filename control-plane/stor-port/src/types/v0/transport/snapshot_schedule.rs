@@ -0,0 +1,88 @@
+use super::*;
+use crate::types::v0::store::snapshots::schedule::{
+    SnapshotRetention, SnapshotScheduleSpec, SnapshotScheduleSpecKey, SnapshotScheduleTarget,
+    SnapshotScheduleTrigger,
+};
+use serde::{Deserialize, Serialize};
+
+rpc_impl_string_id!(SnapshotScheduleId, "ID of a snapshot schedule");
+
+/// Create or update a snapshot schedule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CreateSnapshotSchedule {
+    /// Snapshot schedule identification.
+    pub id: SnapshotScheduleId,
+    /// The volumes which are snapshotted by the schedule.
+    pub target: SnapshotScheduleTarget,
+    /// When the snapshots of a volume are taken.
+    pub trigger: SnapshotScheduleTrigger,
+    /// Retention policy for the scheduled snapshots.
+    pub retention: SnapshotRetention,
+    /// Quiesce the filesystem of published volumes before taking the snapshot.
+    pub quiesce: bool,
+}
+
+impl CreateSnapshotSchedule {
+    /// Create new `Self` from the given parameters.
+    pub fn new(
+        id: SnapshotScheduleId,
+        target: SnapshotScheduleTarget,
+        trigger: SnapshotScheduleTrigger,
+        retention: SnapshotRetention,
+        quiesce: bool,
+    ) -> Self {
+        Self {
+            id,
+            target,
+            trigger,
+            retention,
+            quiesce,
+        }
+    }
+}
+
+impl From<&CreateSnapshotSchedule> for SnapshotScheduleSpec {
+    fn from(request: &CreateSnapshotSchedule) -> Self {
+        SnapshotScheduleSpec::new(
+            request.id.clone(),
+            request.target.clone(),
+            request.trigger.clone(),
+            request.retention.clone(),
+            request.quiesce,
+        )
+    }
+}
+
+/// Delete a snapshot schedule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DestroySnapshotSchedule {
+    /// Snapshot schedule identification.
+    pub id: SnapshotScheduleId,
+}
+
+impl DestroySnapshotSchedule {
+    /// Create new `Self` from the given schedule id.
+    pub fn new(id: SnapshotScheduleId) -> Self {
+        Self { id }
+    }
+}
+
+impl From<&DestroySnapshotSchedule> for SnapshotScheduleSpecKey {
+    fn from(value: &DestroySnapshotSchedule) -> Self {
+        (&value.id).into()
+    }
+}
+
+/// Get all snapshot schedules (`None`), or a specific one.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct GetSnapshotSchedules {
+    /// Snapshot schedule identification.
+    pub id: Option<SnapshotScheduleId>,
+}
+
+impl GetSnapshotSchedules {
+    /// Create new `Self` from the given optional schedule id.
+    pub fn new(id: impl Into<Option<SnapshotScheduleId>>) -> Self {
+        Self { id: id.into() }
+    }
+}
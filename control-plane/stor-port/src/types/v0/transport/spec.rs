@@ -2,7 +2,9 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 use super::*;
-use crate::types::v0::store::{nexus, pool, replica, volume};
+use crate::types::v0::store::{definitions::StorableObjectType, nexus, pool, replica, volume};
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
 
 /// Retrieve all specs from core agent
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -30,3 +32,75 @@ impl From<Specs> for models::Specs {
         Self::new(src.nexuses, src.pools, src.replicas, src.volumes)
     }
 }
+
+/// The current version of the control-plane specs backup format.
+pub const SPECS_BACKUP_VERSION: u32 = 1;
+
+/// Backup all the control-plane specs from the persistent store.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSpecs {}
+
+/// Restore the control-plane specs from a backup into an empty persistent store.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreSpecs {
+    /// The backup to restore from.
+    pub backup: SpecsBackup,
+}
+
+/// A versioned backup of all the objects which the control-plane persists in the store.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecsBackup {
+    /// Version of the backup format.
+    pub version: u32,
+    /// The control-plane api version of the key space the entries were taken from.
+    pub api_version: String,
+    /// When the backup was taken.
+    pub timestamp: DateTime<Utc>,
+    /// The persisted entries.
+    pub entries: Vec<SpecsBackupEntry>,
+}
+
+/// An entry of the `SpecsBackup`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecsBackupEntry {
+    /// The store key, relative to the control-plane key prefix.
+    /// This allows restoring onto a cluster with a different uid or namespace.
+    pub key: String,
+    /// The stored value.
+    pub value: serde_json::Value,
+}
+
+impl SpecsBackupEntry {
+    /// Get the type of the object stored under the entry's key.
+    pub fn kind(&self) -> Option<StorableObjectType> {
+        Self::key_kind(&self.key)
+    }
+    /// Get the type of the object stored under the given key, relative to the key prefix.
+    pub fn key_kind(key: &str) -> Option<StorableObjectType> {
+        match key.split('/').next()? {
+            // The nexus info keys are prefixed with the volume or nexus rather than the type.
+            "volume" | "nexus" => Some(StorableObjectType::NexusInfo),
+            kind => StorableObjectType::from_str(kind).ok(),
+        }
+    }
+}
+
+/// The outcome of restoring the control-plane specs.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecsRestored {
+    /// Number of entries written to the store.
+    pub restored: u64,
+    /// Number of entries which were already present in the store and left untouched.
+    pub skipped: u64,
+}
+
+impl From<SpecsRestored> for models::SpecsRestored {
+    fn from(src: SpecsRestored) -> Self {
+        Self::new(src.restored, src.skipped)
+    }
+}
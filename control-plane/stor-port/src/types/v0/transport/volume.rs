@@ -77,6 +77,181 @@ pub struct VolumeState {
 pub enum VolumeProperty {
     /// Max number of snapshots allowed per volume.
     MaxSnapshots(u32),
+    /// IO limits enforced on the volume target.
+    Qos(VolumeQos),
+    /// Reference to the key which encrypts the volume.
+    EncryptionKey(EncryptionKeyRef),
+}
+
+/// Quality of Service limits enforced on the volume target (nexus).
+/// A limit which is not set, or set to 0, is not enforced.
+/// # Note
+/// The io-engine only supports limiting the combined read and write IOPS.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeQos {
+    /// Limit of read and write IO operations per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rw_iops: Option<u64>,
+    /// Limit of read and write bandwidth in MiB per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rw_mbps: Option<u64>,
+    /// Limit of read bandwidth in MiB per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_mbps: Option<u64>,
+    /// Limit of write bandwidth in MiB per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_mbps: Option<u64>,
+}
+impl VolumeQos {
+    /// The IOPS limit must be a multiple of this value.
+    pub const IOPS_GRANULARITY: u64 = 1000;
+
+    /// Check if no limit is enforced.
+    pub fn is_unlimited(&self) -> bool {
+        [self.rw_iops, self.rw_mbps, self.read_mbps, self.write_mbps]
+            .iter()
+            .all(|limit| limit.unwrap_or_default() == 0)
+    }
+    /// Get `Self` as an option, where `None` means no limits are enforced.
+    pub fn into_limited(self) -> Option<Self> {
+        (!self.is_unlimited()).then_some(self)
+    }
+    /// Validate the limits, returning the reason if they're not supported.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.rw_iops {
+            Some(iops) if iops % Self::IOPS_GRANULARITY != 0 => Err(format!(
+                "the iops limit {iops} must be a multiple of {}",
+                Self::IOPS_GRANULARITY
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+impl From<VolumeQos> for models::VolumeQos {
+    fn from(src: VolumeQos) -> Self {
+        Self::new_all(src.rw_iops, src.rw_mbps, src.read_mbps, src.write_mbps)
+    }
+}
+impl From<models::VolumeQos> for VolumeQos {
+    fn from(src: models::VolumeQos) -> Self {
+        Self {
+            rw_iops: src.rw_iops,
+            rw_mbps: src.rw_mbps,
+            read_mbps: src.read_mbps,
+            write_mbps: src.write_mbps,
+        }
+    }
+}
+
+/// The provider of a volume encryption key.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, strum_macros::Display)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum EncryptionKeyProvider {
+    /// A file in the core agent's encryption key directory.
+    File,
+    /// A Kubernetes Secret.
+    Secret,
+    /// An external key management server.
+    Kms,
+}
+impl From<EncryptionKeyProvider> for models::EncryptionKeyProvider {
+    fn from(src: EncryptionKeyProvider) -> Self {
+        match src {
+            EncryptionKeyProvider::File => Self::File,
+            EncryptionKeyProvider::Secret => Self::Secret,
+            EncryptionKeyProvider::Kms => Self::Kms,
+        }
+    }
+}
+impl From<models::EncryptionKeyProvider> for EncryptionKeyProvider {
+    fn from(src: models::EncryptionKeyProvider) -> Self {
+        match src {
+            models::EncryptionKeyProvider::File => Self::File,
+            models::EncryptionKeyProvider::Secret => Self::Secret,
+            models::EncryptionKeyProvider::Kms => Self::Kms,
+        }
+    }
+}
+
+/// Reference to the key which encrypts a volume at rest.
+/// Only the reference is stored, the key itself is resolved through its provider whenever the
+/// volume target is published.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptionKeyRef {
+    /// The provider of the key.
+    pub provider: EncryptionKeyProvider,
+    /// The name of the key:
+    /// File: the file name, relative to the encryption key directory.
+    /// Secret: the name of the Secret.
+    /// Kms: the identifier of the key on the key server.
+    pub name: String,
+    /// The namespace of the Secret.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// The entry of the Secret's data which holds the key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+impl EncryptionKeyRef {
+    /// The default entry of a Secret's data which holds the key.
+    pub const DEFAULT_SECRET_KEY: &'static str = "encryptionKey";
+
+    /// Create a new `Self` for the given provider and key name.
+    pub fn new(provider: EncryptionKeyProvider, name: impl Into<String>) -> Self {
+        Self {
+            provider,
+            name: name.into(),
+            namespace: None,
+            key: None,
+        }
+    }
+    /// Validate the reference, returning the reason if it's not valid.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("the key name must not be empty".to_string());
+        }
+        match self.provider {
+            EncryptionKeyProvider::File
+                if self.name.contains('/') || self.name.starts_with('.') =>
+            {
+                Err(format!("the key file name '{}' is not valid", self.name))
+            }
+            EncryptionKeyProvider::Secret if self.namespace.is_none() => {
+                Err("the key Secret requires a namespace".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+    /// Get the entry of the Secret's data which holds the key.
+    pub fn secret_key(&self) -> &str {
+        self.key.as_deref().unwrap_or(Self::DEFAULT_SECRET_KEY)
+    }
+}
+impl std::fmt::Display for EncryptionKeyRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.namespace {
+            Some(namespace) => write!(f, "{}://{namespace}/{}", self.provider, self.name),
+            None => write!(f, "{}://{}", self.provider, self.name),
+        }
+    }
+}
+impl From<EncryptionKeyRef> for models::EncryptionKeyRef {
+    fn from(src: EncryptionKeyRef) -> Self {
+        Self::new_all(src.provider, src.name, src.namespace, src.key)
+    }
+}
+impl From<models::EncryptionKeyRef> for EncryptionKeyRef {
+    fn from(src: models::EncryptionKeyRef) -> Self {
+        Self {
+            provider: src.provider.into(),
+            name: src.name,
+            namespace: src.namespace,
+            key: src.key,
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
@@ -470,6 +645,12 @@ pub struct CreateVolume {
     pub cluster_capacity_limit: Option<u64>,
     /// Max Snapshots to limit per volume.
     pub max_snapshots: Option<u32>,
+    /// IO limits enforced on the volume target.
+    #[serde(default)]
+    pub qos: Option<VolumeQos>,
+    /// Encrypt the volume at rest with the referenced key.
+    #[serde(default)]
+    pub encryption: Option<EncryptionKeyRef>,
 }
 
 /// Resize volume request.
@@ -482,6 +663,13 @@ pub struct ResizeVolume {
     pub requested_size: u64,
     /// Total capacity limit of all volumes' provisioning.
     pub cluster_capacity_limit: Option<u64>,
+    /// Allow the volume to be shrunk, if the requested size is smaller than the current size.
+    #[serde(default)]
+    pub allow_shrink: bool,
+    /// Size of the filesystem on the volume in bytes, or 0 if it has no filesystem, as confirmed
+    /// by the caller. Required to shrink the volume, as the filesystem is not visible here.
+    #[serde(default)]
+    pub filesystem_size: Option<u64>,
 }
 impl ResizeVolume {
     /// Create a new `ResizeVolume` request.
@@ -490,8 +678,20 @@ impl ResizeVolume {
             uuid,
             requested_size,
             cluster_capacity_limit,
+            allow_shrink: false,
+            filesystem_size: None,
         }
     }
+    /// Allow the volume to be shrunk.
+    pub fn with_allow_shrink(mut self, allow_shrink: bool) -> Self {
+        self.allow_shrink = allow_shrink;
+        self
+    }
+    /// Confirm the size of the filesystem on the volume, required to shrink it.
+    pub fn with_filesystem_size(mut self, filesystem_size: u64) -> Self {
+        self.filesystem_size = Some(filesystem_size);
+        self
+    }
 }
 
 /// Affinity Group related information.
@@ -576,6 +776,10 @@ pub struct PublishVolume {
     pub publish_context: HashMap<String, String>,
     /// Hosts allowed to access nexus.
     pub frontend_nodes: Vec<String>,
+    /// Publish the volume read-only for the frontend nodes.
+    /// If the volume is already published read-only, the frontend nodes are added to its target.
+    #[serde(default)]
+    pub read_only: bool,
 }
 impl PublishVolume {
     /// Create new `PublishVolume` based on the provided arguments.
@@ -592,8 +796,14 @@ impl PublishVolume {
             share,
             publish_context,
             frontend_nodes,
+            read_only: false,
         }
     }
+    /// Get `Self` with the given read-only access.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
 }
 
 /// Republishes the target on a new node (pre-selected or determined by the control-plane).
@@ -648,6 +858,10 @@ pub struct UnpublishVolume {
     /// the nexus. Note: this option should be used only when we know the node will not become
     /// accessible again and it is safe to do so.
     force: bool,
+    /// Only remove this frontend node from a read-only target which is shared by other frontend
+    /// nodes. The target is unpublished once its last frontend node is removed.
+    #[serde(default)]
+    frontend_node: Option<String>,
 }
 impl UnpublishVolume {
     /// Create a new `UnpublishVolume` for the given uuid.
@@ -655,12 +869,22 @@ impl UnpublishVolume {
         Self {
             uuid: uuid.clone(),
             force,
+            frontend_node: None,
         }
     }
+    /// Get `Self` which only removes the given frontend node from a read-only target.
+    pub fn with_frontend_node(mut self, frontend_node: Option<String>) -> Self {
+        self.frontend_node = frontend_node;
+        self
+    }
     /// It's a force `Self`.
     pub fn force(&self) -> bool {
         self.force
     }
+    /// Get the frontend node which is to be removed from a read-only target.
+    pub fn frontend_node(&self) -> Option<&String> {
+        self.frontend_node.as_ref()
+    }
 }
 
 /// Share Volume request.
@@ -730,6 +954,101 @@ impl SetVolumeProperty {
     }
 }
 
+/// Move a replica of the volume onto another pool.
+/// The new replica is added to the volume target and the moved replica is only retired once the
+/// new replica has been rebuilt.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveVolumeReplica {
+    /// The uuid of the volume.
+    pub uuid: VolumeId,
+    /// The uuid of the replica to be moved.
+    pub replica: ReplicaId,
+    /// Create the new replica on this pool.
+    pub pool: Option<PoolId>,
+    /// Create the new replica on this node.
+    pub node: Option<NodeId>,
+}
+impl MoveVolumeReplica {
+    /// Create new `Self` based on the provided arguments.
+    pub fn new(
+        uuid: VolumeId,
+        replica: ReplicaId,
+        pool: Option<PoolId>,
+        node: Option<NodeId>,
+    ) -> Self {
+        Self {
+            uuid,
+            replica,
+            pool,
+            node,
+        }
+    }
+}
+
+/// Cancel the replica move in progress, keeping the moved replica.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelVolumeReplicaMove {
+    /// The uuid of the volume.
+    pub uuid: VolumeId,
+    /// The uuid of the replica being moved.
+    pub replica: ReplicaId,
+}
+impl CancelVolumeReplicaMove {
+    /// Create new `Self` based on the provided arguments.
+    pub fn new(uuid: VolumeId, replica: ReplicaId) -> Self {
+        Self { uuid, replica }
+    }
+}
+
+/// Get the capacity which is available to new volumes created with the given parameters, as seen
+/// by the volume replica scheduler.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumeCapacity {
+    /// The number of storage replicas.
+    pub replicas: u8,
+    /// The flag indicating whether the volumes are thin provisioned.
+    pub thin: bool,
+    /// The initial replica placement topology.
+    pub topology: Option<Topology>,
+}
+impl GetVolumeCapacity {
+    /// Create new `Self` based on the provided arguments.
+    pub fn new(replicas: u8, thin: bool, topology: Option<Topology>) -> Self {
+        Self {
+            replicas,
+            thin,
+            topology,
+        }
+    }
+}
+
+/// The capacity which is available to new volumes.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeCapacity {
+    /// The capacity available to new volumes, in bytes.
+    pub available_capacity: u64,
+    /// The size of the largest volume which may be created, in bytes.
+    pub maximum_volume_size: u64,
+}
+impl VolumeCapacity {
+    /// Create new `Self` based on the provided arguments.
+    pub fn new(available_capacity: u64, maximum_volume_size: u64) -> Self {
+        Self {
+            available_capacity,
+            maximum_volume_size,
+        }
+    }
+}
+impl From<VolumeCapacity> for models::VolumeCapacity {
+    fn from(src: VolumeCapacity) -> Self {
+        models::VolumeCapacity::new(src.available_capacity, src.maximum_volume_size)
+    }
+}
+
 /// Delete volume request.
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -823,6 +1142,8 @@ pub struct ReplicaTopology {
     usage: Option<ReplicaUsage>,
     /// Current replica's child rebuild progress (%).
     rebuild_progress: Option<u8>,
+    /// The part the replica plays in the replica move of the volume, if any.
+    replica_move: Option<ReplicaMoveRole>,
 }
 
 impl ReplicaTopology {
@@ -844,8 +1165,14 @@ impl ReplicaTopology {
             child_status,
             child_status_reason,
             rebuild_progress,
+            replica_move: None,
         }
     }
+    /// Builder-like specification of the replica's part in the replica move of the volume.
+    pub fn with_replica_move(mut self, replica_move: Option<ReplicaMoveRole>) -> Self {
+        self.replica_move = replica_move;
+        self
+    }
 
     /// Get the ReplicaTopology node ID.
     pub fn node(&self) -> &Option<NodeId> {
@@ -881,6 +1208,29 @@ impl ReplicaTopology {
     pub fn usage(&self) -> Option<&ReplicaUsage> {
         self.usage.as_ref()
     }
+
+    /// Get the part the replica plays in the replica move of the volume.
+    pub fn replica_move(&self) -> Option<ReplicaMoveRole> {
+        self.replica_move
+    }
+}
+
+/// The part a replica plays in the replica move of its volume.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ReplicaMoveRole {
+    /// The replica being moved, which is retired once its replacement has been rebuilt.
+    Source,
+    /// The replacement replica, which is being rebuilt.
+    Target,
+}
+
+impl From<ReplicaMoveRole> for models::ReplicaMoveRole {
+    fn from(src: ReplicaMoveRole) -> Self {
+        match src {
+            ReplicaMoveRole::Source => Self::Source,
+            ReplicaMoveRole::Target => Self::Target,
+        }
+    }
 }
 
 impl From<&ReplicaTopology> for models::ReplicaTopology {
@@ -896,6 +1246,7 @@ impl From<&ReplicaTopology> for models::ReplicaTopology {
                 .and_then(Into::into),
             replica_topology.usage.as_ref().into_opt(),
             replica_topology.rebuild_progress.into_opt(),
+            replica_topology.replica_move.into_opt(),
         )
     }
 }
@@ -963,3 +1314,35 @@ impl CreateSnapshotVolume {
         &self.volume_params
     }
 }
+
+/// Request to create a new volume as a clone of an existing volume.
+/// The clone is created from a hidden snapshot of the source volume, which is garbage collected
+/// once no clone references it anymore.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CloneVolume {
+    /// Unique identification of the source volume.
+    source_uuid: VolumeId,
+    /// Generic volume creation parameters.
+    volume_params: CreateVolume,
+}
+impl CloneVolume {
+    /// Create a new `Self` from the given parameters.
+    pub fn new(source_uuid: VolumeId, volume_params: CreateVolume) -> Self {
+        Self {
+            source_uuid,
+            volume_params,
+        }
+    }
+    /// Get a reference to the source volume uuid.
+    pub fn source_uuid(&self) -> &VolumeId {
+        &self.source_uuid
+    }
+    /// Get a reference to the generic volume parameters.
+    pub fn params(&self) -> &CreateVolume {
+        &self.volume_params
+    }
+    /// Get a `CreateSnapshotVolume` which restores the given snapshot into the new volume.
+    pub fn to_snapshot_volume(&self, snapshot_uuid: SnapshotId) -> CreateSnapshotVolume {
+        CreateSnapshotVolume::new(snapshot_uuid, self.volume_params.clone())
+    }
+}
@@ -152,6 +152,7 @@ impl CsiNode {
         let mut binary = Binary::from_dbg(CSI_NODE)
             .with_args(vec!["--nvme-nr-io-queues", &io_queues.to_string()])
             .with_args(vec!["--node-name", node_name])
+            .with_args(vec!["--snapshot-export-dir", "/tmp"])
             // Make sure that CSI socket is always under shared directory
             // regardless of what its default value is.
             .with_args(vec!["--csi-socket", socket]);
@@ -1,9 +1,10 @@
 use crate::infra::{
-    async_trait, Builder, ComponentAction, Components, ComposeTest, Error, Rest, StartOptions,
+    async_trait, Builder, ComponentAction, Components, ComposeTest, ControlPlaneAgent, Error, Rest,
+    StartOptions,
 };
 use composer::{Binary, ContainerSpec};
 use std::time::Duration;
-use utils::DEFAULT_JSON_GRPC_CLIENT_ADDR;
+use utils::{DEFAULT_CLUSTER_AGENT_CLIENT_ADDR, DEFAULT_JSON_GRPC_CLIENT_ADDR};
 
 #[async_trait]
 impl ComponentAction for Rest {
@@ -53,6 +54,16 @@ impl ComponentAction for Rest {
                 binary = binary.with_args(vec!["--json-grpc", DEFAULT_JSON_GRPC_CLIENT_ADDR]);
             }
 
+            // the cluster agent is configured after the rest server, so check the options instead
+            if options
+                .agents
+                .iter()
+                .any(|agent| matches!(agent, ControlPlaneAgent::HaClusterAgent(_)))
+            {
+                binary =
+                    binary.with_args(vec!["--cluster-agent", DEFAULT_CLUSTER_AGENT_CLIENT_ADDR]);
+            }
+
             if let Some(size) = &options.otel_max_batch_size {
                 binary = binary.with_env("OTEL_BSP_MAX_EXPORT_BATCH_SIZE", size);
             }
@@ -1,5 +1,8 @@
 use kube::CustomResource;
-use openapi::models::{pool_status::PoolStatus as RestPoolStatus, Pool};
+use openapi::models::{
+    pool_status::PoolStatus as RestPoolStatus, Pool, PoolEvacuation as RestPoolEvacuation,
+    PoolEvacuationStatus as RestPoolEvacuationStatus,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -109,6 +112,9 @@ pub struct DiskPoolStatus {
     used: u64,
     /// Available number of bytes.
     available: u64,
+    /// Evacuation of the volume replicas off the pool, if requested.
+    #[serde(default)]
+    pub evacuation: Option<PoolEvacuation>,
 }
 
 impl Default for DiskPoolStatus {
@@ -119,10 +125,34 @@ impl Default for DiskPoolStatus {
             capacity: 0,
             used: 0,
             available: 0,
+            evacuation: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, JsonSchema)]
+/// Evacuation of the volume replicas off the pool, from the respective control plane object.
+pub struct PoolEvacuation {
+    /// Label with which the pool is cordoned for the evacuation.
+    pub label: String,
+    /// Status of the evacuation.
+    pub status: PoolEvacuationStatus,
+    /// Number of volume replicas still to be moved off the pool.
+    pub remaining_replicas: u64,
+    /// Number of the remaining replicas which cannot be moved until their volume is published.
+    #[serde(default)]
+    pub blocked_replicas: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, JsonSchema)]
+/// PoolEvacuationStatus is the control plane status of the evacuation of a pool.
+pub enum PoolEvacuationStatus {
+    /// Volume replicas are still being moved off the pool.
+    Evacuating,
+    /// No volume replicas remain on the pool.
+    Evacuated,
+}
+
 impl DiskPoolStatus {
     /// Set when Pool is not found for some reason.
     pub fn not_found(status: &Option<Self>) -> Self {
@@ -147,6 +177,7 @@ impl DiskPoolStatus {
             capacity: state.capacity,
             used: state.used,
             available: free,
+            evacuation: p.spec.and_then(|spec| spec.evacuation).map(Into::into),
         }
     }
 
@@ -179,9 +210,30 @@ impl From<RestPoolStatus> for PoolStatus {
     }
 }
 
+impl From<RestPoolEvacuation> for PoolEvacuation {
+    fn from(e: RestPoolEvacuation) -> Self {
+        Self {
+            label: e.label,
+            status: e.status.into(),
+            remaining_replicas: e.remaining_replicas,
+            blocked_replicas: e.blocked_replicas,
+        }
+    }
+}
+
+impl From<RestPoolEvacuationStatus> for PoolEvacuationStatus {
+    fn from(s: RestPoolEvacuationStatus) -> Self {
+        match s {
+            RestPoolEvacuationStatus::Evacuating => Self::Evacuating,
+            RestPoolEvacuationStatus::Evacuated => Self::Evacuated,
+        }
+    }
+}
+
 /// Returns DiskPoolStatus from Control plane pool object.
 impl From<Pool> for DiskPoolStatus {
     fn from(p: Pool) -> Self {
+        let evacuation = p.spec.and_then(|spec| spec.evacuation).map(Into::into);
         if let Some(state) = p.state {
             let free = if state.capacity > state.used {
                 state.capacity - state.used
@@ -194,10 +246,12 @@ impl From<Pool> for DiskPoolStatus {
                 capacity: state.capacity,
                 used: state.used,
                 available: free,
+                evacuation,
             }
         } else {
             Self {
                 cr_state: CrPoolState::Created,
+                evacuation,
                 ..Default::default()
             }
         }
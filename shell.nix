@@ -45,6 +45,7 @@ mkShell {
     e2fsprogs
     xfsprogs_5_16
     btrfs-progs
+    f2fs-tools
     nvme-cli
     # python3.9-pyopenssl-22.0.0 marked as broken but fixed on master..
     pytest_inputs
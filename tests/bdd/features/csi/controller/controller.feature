@@ -10,12 +10,17 @@ Scenario: get controller capabilities
 Scenario: get overall storage capacity
     Given 2 Io-Engine nodes with one pool on each node
     When a GetCapacity request is sent to the controller
-    Then CSI controller should report overall capacity equal to aggregated sizes of the pools
+    Then CSI controller should report the capacity which is schedulable on the pools
 
 Scenario: get node storage capacity
     Given 2 Io-Engine nodes with one pool on each node
     When GetCapacity request with node name is sent to the controller
-    Then CSI controller should report capacity for target node
+    Then CSI controller should report the same capacity for every accessible node
+
+Scenario: get storage capacity for more replicas than nodes
+    Given 2 Io-Engine nodes with one pool on each node
+    When a GetCapacity request for 3 replica volumes is sent to the controller
+    Then CSI controller should report no capacity for the largest volume
 
 Scenario: create 1 replica nvmf volume
     Given 2 Io-Engine nodes with one pool on each node
@@ -91,6 +91,11 @@ def test_node_capacity(setup):
     """get node capacity"""
 
 
+@scenario("controller.feature", "get storage capacity for more replicas than nodes")
+def test_capacity_more_replicas_than_nodes(setup):
+    """get storage capacity for more replicas than nodes"""
+
+
 @scenario("controller.feature", "create 1 replica nvmf volume")
 def test_create_1_replica_nvmf_volume(setup):
     """create 1 replica nvmf volume"""
@@ -605,19 +610,20 @@ def check_get_controller_capabilities(get_caps_request):
 )
 def get_overall_capacity(two_pools):
     capacity = csi_rpc_handle().controller.GetCapacity(pb.GetCapacityRequest())
-    return [
-        two_pools[0].state.capacity + two_pools[1].state.capacity,
-        capacity.available_capacity,
-    ]
+    return [two_pools, capacity]
 
 
-@then(
-    "CSI controller should report overall capacity equal to aggregated sizes of the pools"
-)
+@then("CSI controller should report the capacity which is schedulable on the pools")
 def check_get_overall_capacity(get_overall_capacity):
+    [pools, capacity] = get_overall_capacity
+    pool_capacity = [p.state.capacity for p in pools]
+
+    assert 0 < capacity.available_capacity <= sum(
+        pool_capacity
+    ), "Overall capacity exceeds pool sizes"
     assert (
-        get_overall_capacity[0] == get_overall_capacity[1]
-    ), "Overall capacity does not match pool sizes"
+        0 < capacity.maximum_volume_size.value < max(pool_capacity)
+    ), "Maximum volume size exceeds the largest pool size"
 
 
 @when(
@@ -632,20 +638,38 @@ def get_node_capacity(two_pools):
         cap = csi_rpc_handle().controller.GetCapacity(
             pb.GetCapacityRequest(accessible_topology=topology)
         )
-        capacity.append(cap.available_capacity)
+        capacity.append(cap)
     return capacity
 
 
-@then("CSI controller should report capacity for target node")
+@then("CSI controller should report the same capacity for every accessible node")
 def check_get_node_capacity(get_nodes_capacity):
-    pool_api = ApiClient.pools_api()
-
-    for i, p in enumerate([POOL1_UUID, POOL2_UUID]):
-        pool = pool_api.get_pool(p)
+    overall = csi_rpc_handle().controller.GetCapacity(pb.GetCapacityRequest())
 
+    for capacity in get_nodes_capacity:
+        assert (
+            capacity.available_capacity == overall.available_capacity
+        ), "Node capacity does not match the overall capacity"
         assert (
-            pool.state.capacity == get_nodes_capacity[i]
-        ), "Node pool size does not match reported node capacity"
+            capacity.maximum_volume_size == overall.maximum_volume_size
+        ), "Node maximum volume size does not match the overall maximum volume size"
+
+
+@when(
+    "a GetCapacity request for 3 replica volumes is sent to the controller",
+    target_fixture="get_3_replica_capacity",
+)
+def get_3_replica_capacity(two_pools):
+    return csi_rpc_handle().controller.GetCapacity(
+        pb.GetCapacityRequest(parameters={"repl": "3"})
+    )
+
+
+@then("CSI controller should report no capacity for the largest volume")
+def check_get_3_replica_capacity(get_3_replica_capacity):
+    assert (
+        get_3_replica_capacity.maximum_volume_size.value == 0
+    ), "A 3 replica volume cannot be placed on 2 nodes"
 
 
 def csi_create_1_replica_nvmf_volume1():
@@ -1,12 +1,17 @@
-use crate::{common::ApiVersion, products::v2::generate_key, Error};
+use crate::{
+    common::ApiVersion,
+    error::{Error, SerialiseValue},
+    products::v2::generate_key,
+};
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
+use snafu::ResultExt;
 use tokio::sync::mpsc::Receiver;
 
 /// Trait defining the operations that can be performed on a key-value store.
 #[async_trait]
-pub trait Store: StoreKv + StoreObj + Sync + Send + Clone {
+pub trait Store: StoreKv + StoreObj + StoreTxn + Sync + Send + Clone {
     async fn online(&mut self) -> bool;
 }
 
@@ -60,6 +65,123 @@ pub trait StoreObj: StoreKv + Sync + Send + Clone {
     async fn watch_obj<K: ObjectKey>(&mut self, key: &K) -> Result<StoreWatchReceiver, Error>;
 }
 
+/// Trait defining the transactional operations that can be performed on a key-value store.
+/// Every modification of an entry bumps its revision, which can then be used to make further
+/// modifications conditional on the entry not having been modified meanwhile, through
+/// `StoreTxnRequest::when_revision`.
+#[async_trait]
+pub trait StoreTxn: StoreKv + Sync + Send + Clone {
+    /// Get the value from the given `K` key entry from the store, along with the revision at which
+    /// the entry was last modified.
+    async fn get_kv_revision<K: StoreKey>(&mut self, key: &K) -> Result<(Value, i64), Error>;
+    /// Atomically performs all of the `txn` operations, if all of its compares succeed.
+    /// If any compare fails then nothing is modified and `Error::TxnCompare` is returned.
+    async fn txn(&mut self, txn: StoreTxnRequest) -> Result<(), Error>;
+}
+
+/// A condition which must be met for a transaction to be committed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TxnCompare {
+    /// The entry was last modified at the given revision.
+    /// A revision of 0 means the entry must not exist.
+    ModRevision { key: String, revision: i64 },
+}
+impl TxnCompare {
+    /// Get the key which is compared.
+    pub fn key(&self) -> &str {
+        match self {
+            Self::ModRevision { key, .. } => key,
+        }
+    }
+}
+
+/// An operation which is performed as part of a transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TxnOp {
+    /// Put the value under the key.
+    Put { key: String, value: Value },
+    /// Delete the key.
+    Delete { key: String },
+}
+impl TxnOp {
+    /// Get the key which is modified.
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Put { key, .. } => key,
+            Self::Delete { key } => key,
+        }
+    }
+}
+
+/// A set of operations which are performed atomically through `StoreTxn::txn`, if all of
+/// the compares succeed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StoreTxnRequest {
+    compares: Vec<TxnCompare>,
+    ops: Vec<TxnOp>,
+}
+impl StoreTxnRequest {
+    /// Create a new empty transaction.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Only commit the transaction if the `K` key entry was last modified at the given `revision`.
+    /// A `revision` of 0 means the entry must not exist.
+    pub fn when_revision<K: StoreKey>(&mut self, key: &K, revision: i64) {
+        self.compares.push(TxnCompare::ModRevision {
+            key: key.to_string(),
+            revision,
+        });
+    }
+    /// Put the given `V` value under the given `K` key.
+    pub fn put_kv<K: StoreKey, V: StoreValue>(&mut self, key: &K, value: &V) -> Result<(), Error> {
+        self.ops.push(TxnOp::Put {
+            key: key.to_string(),
+            value: serde_json::to_value(value).context(SerialiseValue)?,
+        });
+        Ok(())
+    }
+    /// Put the given `O` object.
+    pub fn put_obj<O: StorableObject>(&mut self, object: &O) -> Result<(), Error> {
+        self.put_kv(&object.key().key(), object)
+    }
+    /// Delete the given `K` key entry.
+    pub fn delete_kv<K: StoreKey>(&mut self, key: &K) {
+        self.ops.push(TxnOp::Delete {
+            key: key.to_string(),
+        });
+    }
+    /// Delete the object with the given `K` object key.
+    pub fn delete_obj<K: ObjectKey>(&mut self, key: &K) {
+        self.delete_kv(&key.key());
+    }
+    /// Append all compares and operations from the `other` transaction.
+    pub fn extend(&mut self, other: StoreTxnRequest) {
+        self.compares.extend(other.compares);
+        self.ops.extend(other.ops);
+    }
+    /// Check if the transaction has no operations.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+    /// Get the compares.
+    pub fn compares(&self) -> &[TxnCompare] {
+        &self.compares
+    }
+    /// Get the operations.
+    pub fn ops(&self) -> &[TxnOp] {
+        &self.ops
+    }
+    /// Get the keys of all compares, useful for error reporting.
+    pub(crate) fn compare_keys(&self) -> Vec<String> {
+        self.compares.iter().map(|c| c.key().to_string()).collect()
+    }
+    /// Split the transaction into its compares and operations.
+    pub(crate) fn into_parts(self) -> (Vec<TxnCompare>, Vec<TxnOp>) {
+        (self.compares, self.ops)
+    }
+}
+
 /// Store keys type trait.
 pub trait StoreKey: Sync + ToString {}
 impl<T> StoreKey for T where T: Sync + ToString {}
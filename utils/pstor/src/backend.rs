@@ -0,0 +1,157 @@
+use crate::{
+    api::{
+        ObjectKey, StorableObject, Store, StoreKey, StoreKv, StoreObj, StoreTxn, StoreTxnRequest,
+        StoreValue, WatchEvent,
+    },
+    error::Error,
+    etcd::Etcd,
+    etcd_keep_alive::ControlPlaneService,
+    memory::MemoryStore,
+};
+use async_trait::async_trait;
+use serde_json::Value;
+use tokio::sync::mpsc::Receiver;
+
+/// Url scheme selecting the `MemoryStore` backend.
+const MEMORY_SCHEME: &str = "mem://";
+/// Url scheme selecting the file backed `MemoryStore` backend.
+const FILE_SCHEME: &str = "file://";
+
+/// A persistent store backend which is selected at runtime through the store url scheme:
+/// - `mem://` => in-process `MemoryStore`, lost on restart.
+/// - `file:///path/to/file` => `MemoryStore` backed by a single file.
+/// - anything else => `Etcd`, eg: `http://localhost:2379`.
+#[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum StoreBackend {
+    /// The etcd store.
+    Etcd(Etcd),
+    /// The in-process store, optionally backed by a file.
+    Memory(MemoryStore),
+}
+
+/// Call the given method on the inner store.
+macro_rules! dispatch {
+    ($self:ident.$method:ident($($arg:expr),*)) => {
+        match $self {
+            StoreBackend::Etcd(store) => store.$method($($arg),*).await,
+            StoreBackend::Memory(store) => store.$method($($arg),*).await,
+        }
+    };
+}
+
+impl StoreBackend {
+    /// Create a new instance of the store selected by the `url`, with a lease associated with
+    /// `service_kind`. See `Etcd::new_leased` and `MemoryStore::new_leased`.
+    pub async fn new_leased(
+        url: &str,
+        service_kind: ControlPlaneService,
+        lease_time: std::time::Duration,
+    ) -> Result<Self, Error> {
+        if let Some(name) = url.strip_prefix(MEMORY_SCHEME) {
+            if !name.is_empty() {
+                return Err(Error::InvalidUrl {
+                    url: url.to_string(),
+                    reason: "named memory stores are not supported".to_string(),
+                });
+            }
+            Ok(Self::Memory(MemoryStore::new().new_leased(service_kind)?))
+        } else if let Some(path) = url.strip_prefix(FILE_SCHEME) {
+            if !path.starts_with('/') {
+                return Err(Error::InvalidUrl {
+                    url: url.to_string(),
+                    reason: "the file path must be absolute".to_string(),
+                });
+            }
+            Ok(Self::Memory(
+                MemoryStore::open(path)?.new_leased(service_kind)?,
+            ))
+        } else {
+            Ok(Self::Etcd(
+                Etcd::new_leased([url], service_kind, lease_time).await?,
+            ))
+        }
+    }
+
+    /// Revokes the lease and releases the associated lock.
+    pub async fn revoke(&self) -> Result<(), Error> {
+        dispatch!(self.revoke())
+    }
+}
+
+#[async_trait]
+impl StoreKv for StoreBackend {
+    async fn put_kv<K: StoreKey, V: StoreValue>(
+        &mut self,
+        key: &K,
+        value: &V,
+    ) -> Result<(), Error> {
+        dispatch!(self.put_kv(key, value))
+    }
+    async fn get_kv<K: StoreKey>(&mut self, key: &K) -> Result<Value, Error> {
+        dispatch!(self.get_kv(key))
+    }
+    async fn delete_kv<K: StoreKey>(&mut self, key: &K) -> Result<(), Error> {
+        dispatch!(self.delete_kv(key))
+    }
+    async fn watch_kv<K: StoreKey>(
+        &mut self,
+        key: &K,
+    ) -> Result<Receiver<Result<WatchEvent, Error>>, Error> {
+        dispatch!(self.watch_kv(key))
+    }
+    async fn get_values_prefix(&mut self, key_prefix: &str) -> Result<Vec<(String, Value)>, Error> {
+        dispatch!(self.get_values_prefix(key_prefix))
+    }
+    async fn get_values_paged(
+        &mut self,
+        key_prefix: &str,
+        limit: i64,
+        range_end: &str,
+    ) -> Result<Vec<(String, Value)>, Error> {
+        dispatch!(self.get_values_paged(key_prefix, limit, range_end))
+    }
+    async fn get_values_paged_all(
+        &mut self,
+        key_prefix: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, Value)>, Error> {
+        dispatch!(self.get_values_paged_all(key_prefix, limit))
+    }
+    async fn delete_values_prefix(&mut self, key_prefix: &str) -> Result<(), Error> {
+        dispatch!(self.delete_values_prefix(key_prefix))
+    }
+}
+
+#[async_trait]
+impl StoreObj for StoreBackend {
+    async fn put_obj<O: StorableObject>(&mut self, object: &O) -> Result<(), Error> {
+        dispatch!(self.put_obj(object))
+    }
+    async fn get_obj<O: StorableObject>(&mut self, key: &O::Key) -> Result<O, Error> {
+        dispatch!(self.get_obj(key))
+    }
+    async fn watch_obj<K: ObjectKey>(
+        &mut self,
+        key: &K,
+    ) -> Result<Receiver<Result<WatchEvent, Error>>, Error> {
+        dispatch!(self.watch_obj(key))
+    }
+}
+
+#[async_trait]
+impl StoreTxn for StoreBackend {
+    async fn get_kv_revision<K: StoreKey>(&mut self, key: &K) -> Result<(Value, i64), Error> {
+        dispatch!(self.get_kv_revision(key))
+    }
+    async fn txn(&mut self, txn: StoreTxnRequest) -> Result<(), Error> {
+        dispatch!(self.txn(txn))
+    }
+}
+
+#[async_trait]
+impl Store for StoreBackend {
+    async fn online(&mut self) -> bool {
+        dispatch!(self.online())
+    }
+}
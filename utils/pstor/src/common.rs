@@ -1,7 +1,7 @@
 use strum_macros::{AsRefStr, Display, EnumString};
 
 /// All types of objects which are storable in our store.
-#[derive(Display, AsRefStr, Copy, Clone, Debug)]
+#[derive(Display, AsRefStr, EnumString, Copy, Clone, Debug)]
 #[allow(dead_code)]
 pub enum StorableObjectType {
     WatchConfig,
@@ -28,6 +28,11 @@ pub enum StorableObjectType {
     StoreLeaseOwner,
     SwitchOver,
     AppNodeSpec,
+    SnapshotScheduleSpec,
+    VolumeRebuildHistory,
+    SnapshotGroupSpec,
+    SnapshotExportSpec,
+    HaClusterLeader,
 }
 
 /// Control plane api versions.
@@ -88,4 +88,22 @@ pub enum Error {
     },
     #[snafu(display("Failed to parse range end for start key: '{}'", start_key))]
     RangeEnd { start_key: String },
+    /// Failed to commit a transaction.
+    #[snafu(display("Failed to commit transaction for keys {:?}. Error {}", keys, source))]
+    TxnCommit {
+        keys: Vec<String>,
+        source: etcd_client::Error,
+    },
+    /// A transaction was not committed because one of its compares failed.
+    #[snafu(display("Transaction compare failed for keys {:?}", keys))]
+    TxnCompare { keys: Vec<String> },
+    /// Failed to access the file backing the store.
+    #[snafu(display("Failed to access store file '{}'. Error {}", path, source))]
+    FileIo {
+        path: String,
+        source: std::io::Error,
+    },
+    /// The store url is not supported.
+    #[snafu(display("Invalid store url '{}', reason: '{}'", url, reason))]
+    InvalidUrl { url: String, reason: String },
 }
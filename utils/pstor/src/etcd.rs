@@ -1,8 +1,11 @@
 use crate::{
-    api::{ObjectKey, StorableObject, Store, StoreKey, StoreKv, StoreObj, StoreValue, WatchEvent},
+    api::{
+        ObjectKey, StorableObject, Store, StoreKey, StoreKv, StoreObj, StoreTxn, StoreTxnRequest,
+        StoreValue, TxnCompare, TxnOp as StoreTxnOp, WatchEvent,
+    },
     error::{
         Connect, Delete, DeletePrefix, DeserialiseValue, Error, Get, GetPrefix, KeyString, Put,
-        SerialiseValue, ValueString, Watch,
+        SerialiseValue, TxnCommit, ValueString, Watch,
     },
     etcd_keep_alive::{ControlPlaneService, EtcdSingletonLock, LeaseLockInfo},
 };
@@ -384,6 +387,85 @@ impl StoreObj for Etcd {
     }
 }
 
+#[async_trait]
+impl StoreTxn for Etcd {
+    /// 'Get' the value and its modification revision for the given key from etcd.
+    async fn get_kv_revision<K: StoreKey>(&mut self, key: &K) -> Result<(Value, i64), Error> {
+        let resp = self.client.get(key.to_string(), None).await.context(Get {
+            key: key.to_string(),
+        })?;
+        match resp.kvs().first() {
+            Some(kv) => Ok((
+                serde_json::from_slice(kv.value()).context(DeserialiseValue {
+                    value: kv.value_str().context(ValueString {})?,
+                })?,
+                kv.mod_revision(),
+            )),
+            None => Err(Error::MissingEntry {
+                key: key.to_string(),
+            }),
+        }
+    }
+
+    /// Commit the transaction in a single etcd Txn, which also compares the lease lock, if any.
+    async fn txn(&mut self, txn: StoreTxnRequest) -> Result<(), Error> {
+        let keys = txn.compare_keys();
+        let (compares, ops) = txn.into_parts();
+        let mut etcd_compares = compares
+            .into_iter()
+            .map(|compare| match compare {
+                TxnCompare::ModRevision { key, revision } => {
+                    Compare::mod_revision(key, CompareOp::Equal, revision)
+                }
+            })
+            .collect::<Vec<_>>();
+        let lease_lock = self.lease_lock()?;
+        if let Some((lease_id, lock_key)) = &lease_lock {
+            etcd_compares.push(Compare::lease(
+                lock_key.clone(),
+                CompareOp::Equal,
+                *lease_id,
+            ));
+        }
+        let etcd_ops = ops
+            .into_iter()
+            .map(|op| match op {
+                StoreTxnOp::Put { key, value } => Ok(TxnOp::put(
+                    key,
+                    serde_json::to_vec(&value).context(SerialiseValue)?,
+                    None,
+                )),
+                StoreTxnOp::Delete { key } => Ok(TxnOp::delete(key, None)),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let resp = self
+            .client
+            .txn(Txn::new().when(etcd_compares).and_then(etcd_ops))
+            .await
+            .context(TxnCommit { keys: keys.clone() })?;
+        if resp.succeeded() {
+            return Ok(());
+        }
+
+        // The Txn response doesn't tell us which compare failed, so check the lease lock
+        // separately as losing it is not the same as a conflicting update.
+        if let Some((lease_id, lock_key)) = lease_lock {
+            let resp = self.client.get(lock_key.clone(), None).await.context(Get {
+                key: lock_key.clone(),
+            })?;
+            if resp.kvs().first().map(|kv| kv.lease()) != Some(lease_id) {
+                return Err(Error::FailedLock {
+                    reason: format!(
+                        "Etcd Txn Compare key '{lock_key}' to lease id '{lease_id:x}' failed"
+                    ),
+                });
+            }
+        }
+        Err(Error::TxnCompare { keys })
+    }
+}
+
 #[async_trait]
 impl Store for Etcd {
     async fn online(&mut self) -> bool {
@@ -13,6 +13,12 @@ mod api;
 pub mod etcd;
 mod etcd_keep_alive;
 
+/// An in-process implementation of the persistent store, optionally backed by a single file.
+pub mod memory;
+
+/// A persistent store implementation selected at runtime through the store url.
+pub mod backend;
+
 /// Definition for the StorableObjectType.
 mod common;
 
@@ -21,7 +27,8 @@ mod products;
 
 /// Export pstor module.
 pub use api::{
-    ObjectKey, StorableObject, Store, StoreKey, StoreKv, StoreObj, StoreWatchReceiver, WatchEvent,
+    ObjectKey, StorableObject, Store, StoreKey, StoreKv, StoreObj, StoreTxn, StoreTxnRequest,
+    StoreWatchReceiver, TxnCompare, TxnOp, WatchEvent,
 };
 pub use common::{ApiVersion, StorableObjectType};
 pub use products::{
@@ -0,0 +1,648 @@
+use crate::{
+    api::{
+        ObjectKey, StorableObject, Store, StoreKey, StoreKv, StoreObj, StoreTxn, StoreTxnRequest,
+        StoreValue, TxnCompare, TxnOp, WatchEvent,
+    },
+    error::{DeserialiseValue, Error, FileIo, SerialiseValue},
+    etcd::get_prefix_range_end,
+    etcd_keep_alive::{ControlPlaneService, StoreLeaseLockKey, StoreLeaseOwner},
+};
+use async_trait::async_trait;
+use nix::{
+    errno::Errno,
+    fcntl::{flock, FlockArg},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use snafu::ResultExt;
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Bound,
+    os::fd::AsRawFd,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::sync::mpsc::{channel, error::TrySendError, Receiver, Sender};
+
+/// In-process key-value store.
+/// All clones of a `MemoryStore` share the same entries, watches and leases, which makes it
+/// usable as a stand-in for a single etcd cluster within one process.
+/// When created with `MemoryStore::open`, every modification is also flushed to a single file
+/// which is reloaded on the next open. The file can only be opened by a single store at a time,
+/// across all processes, as the lease locks only fence the instances sharing the same store.
+#[derive(Clone)]
+pub struct MemoryStore {
+    inner: Arc<parking_lot::Mutex<MemoryStoreInner>>,
+    lease: Option<MemoryLease>,
+}
+
+impl std::fmt::Debug for MemoryStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.inner.lock().file {
+            Some(file) => write!(f, "MemoryStore({})", file.display()),
+            None => write!(f, "MemoryStore"),
+        }
+    }
+}
+
+/// The lease lock held by a `MemoryStore` instance.
+#[derive(Clone, Debug)]
+struct MemoryLease {
+    service_kind: ControlPlaneService,
+    lease_id: i64,
+}
+
+/// An entry of the `MemoryStore`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct MemoryEntry {
+    value: Value,
+    /// The store revision at which the entry was last modified.
+    mod_revision: i64,
+}
+
+/// The data of the `MemoryStore`, which is what gets persisted to the backing file.
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+struct MemoryData {
+    /// The store revision, bumped on every modification.
+    revision: i64,
+    /// The key-value entries, sorted by key.
+    entries: BTreeMap<String, MemoryEntry>,
+}
+impl MemoryData {
+    /// Apply the operations at the given revision.
+    fn apply(&mut self, ops: &[TxnOp], revision: i64) {
+        self.revision = revision;
+        for op in ops {
+            match op {
+                TxnOp::Put { key, value } => {
+                    self.entries.insert(
+                        key.clone(),
+                        MemoryEntry {
+                            value: value.clone(),
+                            mod_revision: revision,
+                        },
+                    );
+                }
+                TxnOp::Delete { key } => {
+                    self.entries.remove(key);
+                }
+            }
+        }
+    }
+    /// Get the revision at which the entry was last modified, or 0 if it does not exist.
+    fn mod_revision(&self, key: &str) -> i64 {
+        self.entries
+            .get(key)
+            .map(|entry| entry.mod_revision)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Default)]
+struct MemoryStoreInner {
+    data: MemoryData,
+    /// Active watches, keyed by the watched key.
+    watchers: HashMap<String, Vec<Sender<Result<WatchEvent, Error>>>>,
+    /// The lease id of the current owner of each service lock.
+    leases: HashMap<ControlPlaneService, i64>,
+    /// The last lease id which was granted.
+    last_lease_id: i64,
+    /// The file backing this store, if any.
+    file: Option<PathBuf>,
+    /// The lock file which is exclusively locked for as long as the backing file is open.
+    /// The lock is released once the file descriptor is closed, ie: when the store is dropped.
+    lock_file: Option<std::fs::File>,
+}
+
+impl MemoryStoreInner {
+    /// Insert the `key` `value` pair and notify any watchers of the key.
+    fn put(&mut self, key: String, value: Value) -> Result<(), Error> {
+        self.commit(vec![TxnOp::Put { key, value }])
+    }
+    /// Remove the given keys and notify any watchers of the keys.
+    fn delete(&mut self, keys: Vec<String>) -> Result<(), Error> {
+        self.commit(keys.into_iter().map(|key| TxnOp::Delete { key }).collect())
+    }
+    /// Atomically apply all operations and then notify any watchers of the modified keys.
+    /// When backed by a file, the operations are applied to a copy of the data which is only
+    /// made visible once it's been flushed to the file.
+    fn commit(&mut self, ops: Vec<TxnOp>) -> Result<(), Error> {
+        let revision = self.data.revision + 1;
+        match &self.file {
+            Some(file) => {
+                let mut data = self.data.clone();
+                data.apply(&ops, revision);
+                Self::flush(file, &data)?;
+                self.data = data;
+            }
+            None => self.data.apply(&ops, revision),
+        }
+        for op in ops {
+            self.notify(op);
+        }
+        Ok(())
+    }
+    /// Notify the watchers of the key modified by the `op`.
+    /// As with etcd, a watch stops watching once the key is deleted.
+    fn notify(&mut self, op: TxnOp) {
+        match op {
+            TxnOp::Put { key, value } => {
+                let Some(senders) = self.watchers.get_mut(&key) else {
+                    return;
+                };
+                senders.retain(|sender| {
+                    match sender.try_send(Ok(WatchEvent::Put(key.clone(), value.clone()))) {
+                        Ok(_) => true,
+                        Err(TrySendError::Full(_)) => {
+                            tracing::error!(%key, "Watch channel is full, dropping the put event");
+                            true
+                        }
+                        // the receiver is gone, so just stop watching.
+                        Err(TrySendError::Closed(_)) => false,
+                    }
+                });
+            }
+            TxnOp::Delete { key } => {
+                for sender in self.watchers.remove(&key).unwrap_or_default() {
+                    let _ = sender.try_send(Ok(WatchEvent::Delete));
+                }
+            }
+        }
+    }
+    /// Collect the entries within the given range, in ascending key order.
+    fn range(&self, start: &str, end: Bound<&str>) -> Vec<(String, Value)> {
+        self.data
+            .entries
+            .range::<str, _>((Bound::Included(start), end))
+            .map(|(key, entry)| (key.clone(), entry.value.clone()))
+            .collect()
+    }
+    /// Write the data to the backing file.
+    /// The data is written to a temporary file first, which is then renamed over the
+    /// backing file, so we never end up with a partially written store.
+    fn flush(file: &Path, data: &MemoryData) -> Result<(), Error> {
+        let bytes = serde_json::to_vec(data).context(SerialiseValue)?;
+        let tmp_file = file.with_extension("tmp");
+        std::fs::write(&tmp_file, bytes).context(FileIo {
+            path: tmp_file.display().to_string(),
+        })?;
+        std::fs::rename(&tmp_file, file).context(FileIo {
+            path: file.display().to_string(),
+        })
+    }
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryStore {
+    /// Create a new empty in-memory store.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(parking_lot::Mutex::new(MemoryStoreInner::default())),
+            lease: None,
+        }
+    }
+    /// Open the store backed by the given `file`, loading all of its existing entries.
+    /// The file is created on the first modification if it does not exist yet.
+    /// An exclusive advisory lock is taken on a `.lock` file alongside it, and held until the
+    /// store is dropped, failing with `Error::FailedLock` if the file is already open.
+    pub fn open(file: impl AsRef<Path>) -> Result<Self, Error> {
+        let file = file.as_ref().to_path_buf();
+        let lock_file = Self::lock_file(&file)?;
+        let data = match std::fs::read(&file) {
+            Ok(bytes) if bytes.is_empty() => MemoryData::default(),
+            Ok(bytes) => serde_json::from_slice(&bytes).context(DeserialiseValue {
+                value: file.display().to_string(),
+            })?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => MemoryData::default(),
+            Err(source) => {
+                return Err(Error::FileIo {
+                    path: file.display().to_string(),
+                    source,
+                })
+            }
+        };
+        Ok(Self {
+            inner: Arc::new(parking_lot::Mutex::new(MemoryStoreInner {
+                data,
+                file: Some(file),
+                lock_file: Some(lock_file),
+                ..Default::default()
+            })),
+            lease: None,
+        })
+    }
+    /// Open and exclusively lock the lock file of the given backing `file`.
+    /// The lock is taken on a separate file as the backing file is replaced on every flush.
+    fn lock_file(file: &Path) -> Result<std::fs::File, Error> {
+        let path = file.with_extension("lock");
+        let lock_file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(&path)
+            .context(FileIo {
+                path: path.display().to_string(),
+            })?;
+        match flock(lock_file.as_raw_fd(), FlockArg::LockExclusiveNonblock) {
+            Ok(()) => Ok(lock_file),
+            Err(Errno::EWOULDBLOCK) => Err(Error::FailedLock {
+                reason: format!(
+                    "Store file '{}' is already open by another store, possibly of another process",
+                    file.display()
+                ),
+            }),
+            Err(errno) => Err(Error::FileIo {
+                path: path.display().to_string(),
+                source: errno.into(),
+            }),
+        }
+    }
+    /// Get a new instance of the store holding the lease lock associated with `service_kind`.
+    /// Much like `Etcd::new_leased`, all modifications made through the returned instance fail
+    /// with `Error::FailedLock` once another instance takes over the lock.
+    /// Unlike etcd, the lock is granted straight away, taking over from any previous owner, as
+    /// there's no keep alive which could let a previous owner's lease expire.
+    pub fn new_leased(&self, service_kind: ControlPlaneService) -> Result<Self, Error> {
+        let lease_id = {
+            let mut inner = self.inner.lock();
+            inner.last_lease_id += 1;
+            let lease_id = inner.last_lease_id;
+            if let Some(previous) = inner.leases.insert(service_kind.clone(), lease_id) {
+                tracing::warn!(
+                    lease.id = lease_id,
+                    previous.lease.id = previous,
+                    "Lease lock for '{service_kind}' has been taken over"
+                );
+            }
+            let lock_key = StoreLeaseLockKey::new(&service_kind).key();
+            inner.put(lock_key, Value::String(format!("{lease_id:x}")))?;
+            let owner = StoreLeaseOwner::new(&service_kind, lease_id);
+            let owner_key = owner.key().key();
+            inner.put(
+                owner_key,
+                serde_json::to_value(&owner).context(SerialiseValue)?,
+            )?;
+            lease_id
+        };
+        Ok(Self {
+            inner: self.inner.clone(),
+            lease: Some(MemoryLease {
+                service_kind,
+                lease_id,
+            }),
+        })
+    }
+    /// Revokes the lease and releases the associated lock, if it is still held by us.
+    pub async fn revoke(&self) -> Result<(), Error> {
+        if let Some(lease) = &self.lease {
+            let mut inner = self.inner.lock();
+            if inner.leases.get(&lease.service_kind) == Some(&lease.lease_id) {
+                inner.leases.remove(&lease.service_kind);
+                let lock_key = StoreLeaseLockKey::new(&lease.service_kind).key();
+                inner.delete(vec![lock_key])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Lock the inner store, checking that our lease lock, if any, is still held.
+    /// Returns `Error::FailedLock` if the lock has been taken over by another instance.
+    fn lock_leased(&self) -> Result<parking_lot::MutexGuard<'_, MemoryStoreInner>, Error> {
+        let inner = self.inner.lock();
+        match &self.lease {
+            Some(lease) if inner.leases.get(&lease.service_kind) != Some(&lease.lease_id) => {
+                Err(Error::FailedLock {
+                    reason: format!(
+                        "Lock '{}' is not held by lease id '{:x}'",
+                        lease.service_kind, lease.lease_id
+                    ),
+                })
+            }
+            _ => Ok(inner),
+        }
+    }
+    /// Add a new watch for the given key.
+    fn watch(&self, key: String) -> Receiver<Result<WatchEvent, Error>> {
+        let (sender, receiver) = channel(100);
+        self.inner
+            .lock()
+            .watchers
+            .entry(key)
+            .or_default()
+            .push(sender);
+        receiver
+    }
+}
+
+#[async_trait]
+impl StoreKv for MemoryStore {
+    async fn put_kv<K: StoreKey, V: StoreValue>(
+        &mut self,
+        key: &K,
+        value: &V,
+    ) -> Result<(), Error> {
+        let value = serde_json::to_value(value).context(SerialiseValue)?;
+        self.lock_leased()?.put(key.to_string(), value)
+    }
+
+    async fn get_kv<K: StoreKey>(&mut self, key: &K) -> Result<Value, Error> {
+        match self.inner.lock().data.entries.get(&key.to_string()) {
+            Some(entry) => Ok(entry.value.clone()),
+            None => Err(Error::MissingEntry {
+                key: key.to_string(),
+            }),
+        }
+    }
+
+    async fn delete_kv<K: StoreKey>(&mut self, key: &K) -> Result<(), Error> {
+        self.lock_leased()?.delete(vec![key.to_string()])
+    }
+
+    async fn watch_kv<K: StoreKey>(
+        &mut self,
+        key: &K,
+    ) -> Result<Receiver<Result<WatchEvent, Error>>, Error> {
+        Ok(self.watch(key.to_string()))
+    }
+
+    async fn get_values_prefix(&mut self, key_prefix: &str) -> Result<Vec<(String, Value)>, Error> {
+        let range_end = get_prefix_range_end(key_prefix).map_err(|_| Error::RangeEnd {
+            start_key: key_prefix.to_string(),
+        })?;
+        Ok(self
+            .inner
+            .lock()
+            .range(key_prefix, Bound::Excluded(range_end.as_str())))
+    }
+
+    async fn get_values_paged(
+        &mut self,
+        key_prefix: &str,
+        limit: i64,
+        range_end: &str,
+    ) -> Result<Vec<(String, Value)>, Error> {
+        if limit <= 2 {
+            return Err(Error::PagedMinimum);
+        }
+        let end = match range_end.is_empty() {
+            true => Bound::Unbounded,
+            false => Bound::Excluded(range_end),
+        };
+        let mut values = self.inner.lock().range(key_prefix, end);
+        values.truncate(limit as usize);
+        Ok(values)
+    }
+
+    async fn get_values_paged_all(
+        &mut self,
+        key_prefix: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, Value)>, Error> {
+        if limit <= 2 {
+            return Err(Error::PagedMinimum);
+        }
+        // everything is local, so there's no point in paging
+        self.get_values_prefix(key_prefix).await
+    }
+
+    async fn delete_values_prefix(&mut self, key_prefix: &str) -> Result<(), Error> {
+        let range_end = get_prefix_range_end(key_prefix).map_err(|_| Error::RangeEnd {
+            start_key: key_prefix.to_string(),
+        })?;
+        let mut inner = self.lock_leased()?;
+        let keys = inner
+            .range(key_prefix, Bound::Excluded(range_end.as_str()))
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        inner.delete(keys)
+    }
+}
+
+#[async_trait]
+impl StoreObj for MemoryStore {
+    async fn put_obj<O: StorableObject>(&mut self, object: &O) -> Result<(), Error> {
+        let value = serde_json::to_value(object).context(SerialiseValue)?;
+        self.lock_leased()?.put(object.key().key(), value)
+    }
+
+    async fn get_obj<O: StorableObject>(&mut self, key: &O::Key) -> Result<O, Error> {
+        let value = self.get_kv(&key.key()).await?;
+        serde_json::from_value(value.clone()).context(DeserialiseValue {
+            value: value.to_string(),
+        })
+    }
+
+    async fn watch_obj<K: ObjectKey>(
+        &mut self,
+        key: &K,
+    ) -> Result<Receiver<Result<WatchEvent, Error>>, Error> {
+        Ok(self.watch(key.key()))
+    }
+}
+
+#[async_trait]
+impl StoreTxn for MemoryStore {
+    async fn get_kv_revision<K: StoreKey>(&mut self, key: &K) -> Result<(Value, i64), Error> {
+        match self.inner.lock().data.entries.get(&key.to_string()) {
+            Some(entry) => Ok((entry.value.clone(), entry.mod_revision)),
+            None => Err(Error::MissingEntry {
+                key: key.to_string(),
+            }),
+        }
+    }
+
+    async fn txn(&mut self, txn: StoreTxnRequest) -> Result<(), Error> {
+        let mut inner = self.lock_leased()?;
+        let compare_ok = txn.compares().iter().all(|compare| match compare {
+            TxnCompare::ModRevision { key, revision } => inner.data.mod_revision(key) == *revision,
+        });
+        if !compare_ok {
+            return Err(Error::TxnCompare {
+                keys: txn.compare_keys(),
+            });
+        }
+        let (_, ops) = txn.into_parts();
+        inner.commit(ops)
+    }
+}
+
+#[async_trait]
+impl Store for MemoryStore {
+    async fn online(&mut self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestStruct {
+        name: String,
+        value: u64,
+    }
+
+    #[tokio::test]
+    async fn memory_kv() {
+        let mut store = MemoryStore::new();
+        let key = "key".to_string();
+        let mut data = TestStruct {
+            name: "John Doe".to_string(),
+            value: 100,
+        };
+
+        store.put_kv(&key, &data).await.unwrap();
+        let result: TestStruct = serde_json::from_value(store.get_kv(&key).await.unwrap()).unwrap();
+        assert_eq!(data, result);
+
+        let mut watch = store.watch_kv(&key).await.unwrap();
+        data.value = 200;
+        store.put_kv(&key, &data).await.unwrap();
+        match watch.recv().await.unwrap().unwrap() {
+            WatchEvent::Put(k, v) => {
+                assert_eq!(k, key);
+                assert_eq!(serde_json::from_value::<TestStruct>(v).unwrap(), data);
+            }
+            WatchEvent::Delete => panic!("Expected a 'put' event"),
+        }
+
+        store.delete_kv(&key).await.unwrap();
+        assert!(matches!(watch.recv().await, Some(Ok(WatchEvent::Delete))));
+        // the watch stops once the key is deleted
+        assert!(watch.recv().await.is_none());
+        store
+            .get_kv(&key)
+            .await
+            .expect_err("Entry should have been deleted");
+    }
+
+    #[tokio::test]
+    async fn memory_paged() {
+        let mut store = MemoryStore::new();
+        for i in 0 .. 10 {
+            store.put_kv(&format!("/prefix/{i}"), &i).await.unwrap();
+        }
+        store.put_kv(&"/prefiy/0", &0).await.unwrap();
+
+        let page = store
+            .get_values_paged("/prefix/", 3, "/prefiy")
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 3);
+        assert_eq!(page.last().unwrap().0, "/prefix/2");
+
+        let all = store.get_values_paged_all("/prefix/", 3).await.unwrap();
+        assert_eq!(all.len(), 10);
+
+        store.delete_values_prefix("/prefix/").await.unwrap();
+        let all = store.get_values_prefix("/prefi").await.unwrap();
+        assert_eq!(all, vec![("/prefiy/0".to_string(), Value::from(0))]);
+    }
+
+    #[tokio::test]
+    async fn memory_lease() {
+        let store = MemoryStore::new();
+        let mut first = store.new_leased("core".to_string()).unwrap();
+        first.put_kv(&"key", &1).await.unwrap();
+
+        let mut second = store.new_leased("core".to_string()).unwrap();
+        second.put_kv(&"key", &2).await.unwrap();
+        let error = first.put_kv(&"key", &3).await.unwrap_err();
+        assert!(matches!(error, Error::FailedLock { .. }));
+        assert_eq!(second.get_kv(&"key").await.unwrap(), Value::from(2));
+
+        second.revoke().await.unwrap();
+        let error = second.delete_kv(&"key").await.unwrap_err();
+        assert!(matches!(error, Error::FailedLock { .. }));
+    }
+
+    #[tokio::test]
+    async fn memory_txn() {
+        let mut store = MemoryStore::new();
+        store.put_kv(&"a", &1).await.unwrap();
+        let (_, revision) = store.get_kv_revision(&"a").await.unwrap();
+
+        let mut txn = StoreTxnRequest::new();
+        txn.when_revision(&"a", revision);
+        txn.put_kv(&"a", &2).unwrap();
+        store.txn(txn.clone()).await.unwrap();
+        let error = store.txn(txn).await.unwrap_err();
+        assert!(matches!(error, Error::TxnCompare { .. }));
+        let (value, new_revision) = store.get_kv_revision(&"a").await.unwrap();
+        assert_eq!(value, Value::from(2));
+        assert!(new_revision > revision);
+
+        // the whole transaction fails if a single compare fails
+        let mut txn = StoreTxnRequest::new();
+        txn.when_revision(&"a", new_revision);
+        txn.when_revision(&"b", 0);
+        txn.put_kv(&"a", &4).unwrap();
+        txn.put_kv(&"b", &4).unwrap();
+        store.put_kv(&"b", &0).await.unwrap();
+        let error = store.txn(txn.clone()).await.unwrap_err();
+        assert!(matches!(error, Error::TxnCompare { .. }));
+        assert_eq!(store.get_kv(&"a").await.unwrap(), Value::from(2));
+
+        store.delete_kv(&"b").await.unwrap();
+        store.txn(txn).await.unwrap();
+        let (a, a_revision) = store.get_kv_revision(&"a").await.unwrap();
+        let (b, b_revision) = store.get_kv_revision(&"b").await.unwrap();
+        assert_eq!((a, b), (Value::from(4), Value::from(4)));
+        assert_eq!(a_revision, b_revision);
+
+        let mut txn = StoreTxnRequest::new();
+        txn.when_revision(&"b", b_revision);
+        txn.delete_kv(&"b");
+        store.txn(txn).await.unwrap();
+        store.get_kv(&"b").await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn file_store() {
+        let file = std::env::temp_dir().join(format!("pstor-{}.json", uuid::Uuid::new_v4()));
+        let mut store = MemoryStore::open(&file).unwrap();
+        store.put_kv(&"key", &"value").await.unwrap();
+        store.put_kv(&"deleted", &"value").await.unwrap();
+        store.delete_kv(&"deleted").await.unwrap();
+        let (_, revision) = store.get_kv_revision(&"key").await.unwrap();
+        drop(store);
+
+        let mut store = MemoryStore::open(&file).unwrap();
+        assert_eq!(
+            store.get_kv_revision(&"key").await.unwrap(),
+            (Value::from("value"), revision)
+        );
+        store.get_kv(&"deleted").await.unwrap_err();
+        drop(store);
+        std::fs::remove_file(&file).unwrap();
+        std::fs::remove_file(file.with_extension("lock")).unwrap();
+    }
+
+    #[tokio::test]
+    async fn file_store_lock() {
+        let file = std::env::temp_dir().join(format!("pstor-{}.json", uuid::Uuid::new_v4()));
+        let store = MemoryStore::open(&file).unwrap();
+        let mut leased = store.new_leased("core".to_string()).unwrap();
+        drop(store);
+
+        // the lock is held by the leased clone, so the file cannot be opened again
+        let error = MemoryStore::open(&file).unwrap_err();
+        assert!(matches!(error, Error::FailedLock { .. }));
+        leased.put_kv(&"key", &"value").await.unwrap();
+
+        drop(leased);
+        let mut store = MemoryStore::open(&file).unwrap();
+        assert_eq!(store.get_kv(&"key").await.unwrap(), Value::from("value"));
+        drop(store);
+        std::fs::remove_file(&file).unwrap();
+        std::fs::remove_file(file.with_extension("lock")).unwrap();
+    }
+}